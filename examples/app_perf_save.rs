@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Simple performance test for the save() phase of rust_xlsxwriter with
+//! several large worksheets. Run it in release mode and measure the peak memory
+//! with an external tool such as `/usr/bin/time -v`.
+
+use std::time::Instant;
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let num_worksheets = 8;
+    let row_max = 50_000;
+    let col_max = 20;
+
+    for _ in 0..num_worksheets {
+        let worksheet = workbook.add_worksheet();
+
+        for row in 0..row_max {
+            for col in 0..col_max {
+                if col % 2 == 0 {
+                    worksheet.write_number(row, col, f64::from(row + u32::from(col)))?;
+                } else {
+                    worksheet.write_string(row, col, "Foo")?;
+                }
+            }
+        }
+    }
+
+    let start = Instant::now();
+    workbook.save("rust_perf_save.xlsx")?;
+
+    println!(
+        "Saved {num_worksheets} worksheets of {row_max} x {col_max} cells in {:.3}s",
+        start.elapsed().as_secs_f64()
+    );
+
+    Ok(())
+}
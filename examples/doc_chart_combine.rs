@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! An example of creating a combined Column and Line chart where the Line chart
+//! series are plotted on a secondary Y axis.
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data for the chart.
+    worksheet.write_column(0, 0, ["Jan", "Feb", "Mar", "Apr", "May"])?;
+    worksheet.write_column(0, 1, [3200, 4100, 3800, 5200, 4900])?;
+    worksheet.write_column(0, 2, [0.12, 0.15, 0.14, 0.19, 0.17])?;
+
+    // Create a new Column chart for the primary series.
+    let mut column_chart = Chart::new(ChartType::Column);
+    column_chart
+        .add_series()
+        .set_name("Sales")
+        .set_categories("Sheet1!$A$1:$A$5")
+        .set_values("Sheet1!$B$1:$B$5");
+
+    // Create a new Line chart for the secondary series.
+    let mut line_chart = Chart::new(ChartType::Line);
+    line_chart
+        .add_series()
+        .set_name("Margin")
+        .set_categories("Sheet1!$A$1:$A$5")
+        .set_values("Sheet1!$C$1:$C$5")
+        .set_secondary_axis(true);
+
+    // Format the secondary Y axis.
+    line_chart.y2_axis().set_name("Margin").set_num_format("0%");
+
+    // Combine the charts.
+    column_chart.combine(&line_chart);
+
+    // Format the primary axes.
+    column_chart.x_axis().set_name("Month");
+    column_chart.y_axis().set_name("Sales");
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 4, &column_chart)?;
+
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
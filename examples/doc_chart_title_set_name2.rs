@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! A chart example demonstrating setting the chart and axis titles from
+//! worksheet cells and setting the title fonts.
+
+use rust_xlsxwriter::{Chart, ChartFont, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data and titles for the chart.
+    worksheet.write(0, 0, "Quarterly results")?;
+    worksheet.write(0, 1, "Quarter")?;
+    worksheet.write(0, 2, "Sales")?;
+    worksheet.write_column(1, 1, ["Q1", "Q2", "Q3", "Q4"])?;
+    worksheet.write_column(1, 2, [50, 30, 40, 60])?;
+
+    // Create a new chart.
+    let mut chart = Chart::new(ChartType::Column);
+
+    // Add a data series using Excel formula syntax to describe the range.
+    chart
+        .add_series()
+        .set_categories("Sheet1!$B$2:$B$5")
+        .set_values("Sheet1!$C$2:$C$5");
+
+    // Set the chart title from a worksheet cell and set the font.
+    chart.title().set_name("Sheet1!$A$1").set_font(
+        ChartFont::new()
+            .set_name("Arial")
+            .set_size(16)
+            .set_color("#1F4E79"),
+    );
+
+    // Set the axis titles from worksheet cells and set the fonts.
+    chart
+        .x_axis()
+        .set_name(("Sheet1", 0, 1))
+        .set_name_font(ChartFont::new().set_italic());
+
+    chart
+        .y_axis()
+        .set_name(("Sheet1", 0, 2))
+        .set_name_font(ChartFont::new().set_bold().set_rotation(-45));
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 4, &chart)?;
+
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding a Formula type conditional format to a worksheet to
+//! highlight alternate rows in a range, a technique known as "striping".
+
+use rust_xlsxwriter::{ConditionalFormatFormula, Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some sample data.
+    for row in 0..10u32 {
+        for col in 0..5u16 {
+            worksheet.write(row, col, row * 10 + u32::from(col))?;
+        }
+    }
+
+    // Add a format. Light blue fill.
+    let format = Format::new().set_background_color("DDEBF7");
+
+    // Write a conditional format over the range to shade every second row.
+    let conditional_format = ConditionalFormatFormula::new()
+        .set_rule("=MOD(ROW(),2)=0")
+        .set_format(format);
+
+    worksheet.add_conditional_format(0, 0, 9, 4, &conditional_format)?;
+
+    // Save the file.
+    workbook.save("conditional_format.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of applying several conditional formats to the same range. The top
+//! 10% of values are highlighted in green and, since the rule has "Stop if
+//! True" set, the remaining above average values are highlighted in yellow.
+
+use rust_xlsxwriter::{
+    ConditionalFormatAverage, ConditionalFormatAverageRule, ConditionalFormatTop,
+    ConditionalFormatTopRule, Format, Workbook, XlsxError,
+};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some sample data.
+    let data = [
+        34, 72, 38, 30, 75, 48, 75, 66, 84, 86, 6, 24, 1, 84, 54, 62, 60, 3, 26, 59,
+    ];
+    worksheet.write_column(0, 0, data)?;
+
+    // Add a format. Green fill with dark green text.
+    let format1 = Format::new()
+        .set_font_color("006100")
+        .set_background_color("C6EFCE");
+
+    // Add a format. Yellow fill with dark yellow text.
+    let format2 = Format::new()
+        .set_font_color("9C6500")
+        .set_background_color("FFEB9C");
+
+    // Write the first, highest priority, conditional format over the range.
+    let conditional_format = ConditionalFormatTop::new()
+        .set_rule(ConditionalFormatTopRule::TopPercent(10))
+        .set_stop_if_true(true)
+        .set_format(format1);
+
+    worksheet.add_conditional_format(0, 0, 19, 0, &conditional_format)?;
+
+    // Write a second, lower priority, conditional format over the same range.
+    let conditional_format = ConditionalFormatAverage::new()
+        .set_rule(ConditionalFormatAverageRule::AboveAverage)
+        .set_format(format2);
+
+    worksheet.add_conditional_format(0, 0, 19, 0, &conditional_format)?;
+
+    // Save the file.
+    workbook.save("conditional_format.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding a data validation to a worksheet cell. This validation
+//! uses a custom formula to restrict input to text that starts with "ID-".
+
+use rust_xlsxwriter::{DataValidation, Formula, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(1, 0, "Enter an ID in cell D2:")?;
+
+    let data_validation = DataValidation::new().allow_custom(Formula::new(r#"=LEFT(D2,3)="ID-""#));
+
+    worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+
+    // Save the file.
+    workbook.save("data_validation.xlsx")?;
+
+    Ok(())
+}
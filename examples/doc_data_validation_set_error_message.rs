@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding a data validation to a worksheet cell with an input
+//! message that is displayed when the cell is selected and a custom warning
+//! message that is displayed if the user enters an invalid value.
+
+use rust_xlsxwriter::{
+    DataValidation, DataValidationErrorStyle, DataValidationRule, Workbook, XlsxError,
+};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(1, 0, "Enter rating in cell D2:")?;
+
+    let data_validation = DataValidation::new()
+        .allow_whole_number(DataValidationRule::Between(1, 10))
+        .set_input_title("Enter a rating")
+        .set_input_message("Enter an integer from 1 to 10")
+        .set_error_style(DataValidationErrorStyle::Warning)
+        .set_error_title("Value out of range")
+        .set_error_message("The rating should be an integer from 1 to 10");
+
+    worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+
+    // Save the file.
+    workbook.save("data_validation.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the font properties for a
+//! Japanese font.
+
+use rust_xlsxwriter::{Format, FormatFontScheme, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let format = Format::new()
+        .set_font_name("MS Gothic")
+        .set_font_family(3)
+        .set_font_charset(128)
+        .set_font_scheme(FormatFontScheme::None);
+
+    worksheet.write_string_with_format(0, 0, "こんにちは世界", &format)?;
+
+    workbook.save("formats.xlsx")?;
+
+    Ok(())
+}
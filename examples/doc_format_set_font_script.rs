@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the superscript and subscript
+//! properties in rich strings, for example for chemical formulas and footnote
+//! markers.
+
+use rust_xlsxwriter::{Format, FormatScript, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let default = Format::default();
+    let superscript = Format::new().set_font_script(FormatScript::Superscript);
+    let subscript = Format::new().set_font_script(FormatScript::Subscript);
+
+    // A chemical formula.
+    let segments = [(&default, "H"), (&subscript, "2"), (&default, "O")];
+    worksheet.write_rich_string(0, 0, &segments)?;
+
+    // A footnote marker.
+    let segments = [(&default, "Revenue"), (&superscript, "1")];
+    worksheet.write_rich_string(1, 0, &segments)?;
+
+    workbook.save("formats.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting a gradient fill for a cell.
+
+use rust_xlsxwriter::{
+    Format, FormatGradient, FormatGradientStop, FormatGradientType, Workbook, XlsxError,
+};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let format1 =
+        Format::new().set_gradient(&FormatGradient::new().set_angle(90).set_gradient_stops(&[
+            FormatGradientStop::new("#FFFFFF", 0),
+            FormatGradientStop::new("#4F81BD", 100),
+        ]));
+
+    let format2 = Format::new().set_gradient(
+        &FormatGradient::new()
+            .set_type(FormatGradientType::Path)
+            .set_gradient_stops(&[
+                FormatGradientStop::new("#FFFFFF", 0),
+                FormatGradientStop::new("#C0504D", 100),
+            ]),
+    );
+
+    worksheet.write_with_format(0, 0, "Linear", &format1)?;
+    worksheet.write_with_format(2, 0, "Path", &format2)?;
+
+    workbook.save("formats.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates checking for future functions.
+
+use rust_xlsxwriter::Formula;
+
+fn main() {
+    assert!(Formula::future_functions().contains(&"STDEV.S"));
+    assert!(Formula::future_functions().contains(&"XMATCH"));
+    assert!(!Formula::future_functions().contains(&"SUM"));
+}
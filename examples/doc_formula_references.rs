@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates building a formula from cell references
+//! and strings.
+
+use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let formula = format!(
+        "=IF({}>{}, {}, {})",
+        Formula::range_reference("Sales Data", 0, 1, 9, 1),
+        Formula::cell_reference("", 0, 0),
+        Formula::string_literal(r#"Over "target""#),
+        Formula::string_literal("Under"),
+    );
+
+    assert_eq!(
+        formula,
+        r#"=IF('Sales Data'!$B$1:$B$10>$A$1, "Over ""target""", "Under")"#
+    );
+
+    worksheet.write_formula(0, 1, formula)?;
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
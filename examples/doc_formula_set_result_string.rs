@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting typed formula results.
+
+use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_number(0, 0, 1)?;
+
+    // A string result that looks like a number.
+    let formula = Formula::new(r#"=TEXT(A1, "000")"#).set_result_string("001");
+    worksheet.write_formula(0, 1, formula)?;
+
+    // A boolean result.
+    let formula = Formula::new("=A1>0").set_result_boolean(true);
+    worksheet.write_formula(0, 2, formula)?;
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing a formula with the Implicit
+//! Intersection Operator.
+
+use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_column(0, 0, [1, 2, 3])?;
+
+    // This is stored as "_xlfn.SINGLE(A1:A3)*2" and displayed by Excel
+    // 365 as "=@A1:A3*2".
+    let formula = Formula::new("=@A1:A3*2").use_implicit_intersection();
+
+    worksheet.write_formula(0, 1, formula)?;
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates checking formulas for syntax errors.
+
+use rust_xlsxwriter::{Formula, XlsxError};
+
+fn main() {
+    assert!(Formula::new("=SUM(A1:A10)").validate().is_ok());
+
+    // Missing closing parenthesis.
+    let result = Formula::new("=SUM(A1:A10").validate();
+    assert!(matches!(result, Err(XlsxError::FormulaError(_))));
+
+    // Misspelled function name.
+    let result = Formula::new("=SUMM(A1:A10)").validate();
+    assert!(matches!(result, Err(XlsxError::FormulaError(_))));
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding named sheet views with different
+//! filters and sorting to a worksheet.
+
+use rust_xlsxwriter::{FilterCondition, NamedSheetView, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write some data to filter.
+    let data = [
+        ("Region", "Sales"),
+        ("East", "3000"),
+        ("West", "5000"),
+        ("East", "4000"),
+        ("South", "2000"),
+    ];
+    for (row, (region, sales)) in data.iter().enumerate() {
+        worksheet.write_string(row as u32, 0, *region)?;
+        worksheet.write_string(row as u32, 1, *sales)?;
+    }
+
+    // The views apply to the worksheet autofilter range.
+    worksheet.autofilter(0, 0, 4, 1)?;
+
+    // Create a view that shows the "East" region only.
+    let filter = FilterCondition::new().add_list_filter("East");
+    let view = NamedSheetView::new("East region").set_filter(0, &filter);
+    worksheet.add_named_sheet_view(&view)?;
+
+    // Create a view that sorts the sales in descending order.
+    let view = NamedSheetView::new("Top sales").set_sort(1, true);
+    worksheet.add_named_sheet_view(&view)?;
+
+    workbook.save("named_sheet_views.xlsx")?;
+
+    Ok(())
+}
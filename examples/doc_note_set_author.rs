@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding notes to worksheet cells with a note specific author and
+//! with the default worksheet author.
+
+use rust_xlsxwriter::{Note, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Set the default author for notes in the worksheet.
+    worksheet.set_comment_author("Rust");
+
+    // Write some data.
+    worksheet.write(0, 0, "Hello")?;
+    worksheet.write(2, 0, "World")?;
+
+    // Create a note with the default worksheet author.
+    let note1 = Note::new("Some text for the note");
+
+    // Create a note with a different author.
+    let note2 = Note::new("Some other text").set_author("Ferris");
+
+    // Add the notes to worksheet cells.
+    worksheet.insert_note(0, 0, &note1)?;
+    worksheet.insert_note(2, 0, &note2)?;
+
+    // Save the file.
+    workbook.save("notes.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding a note to a worksheet cell that is always visible and
+//! that has a user defined size and background color.
+
+use rust_xlsxwriter::{Note, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write some data.
+    worksheet.write(1, 1, "Hello")?;
+
+    // Create a visible note with a custom size and color.
+    let note = Note::new("This note is always visible")
+        .set_visible(true)
+        .set_width(200)
+        .set_height(100)
+        .set_background_color("#CCFFCC");
+
+    // Add the note to a worksheet cell.
+    worksheet.insert_note(1, 1, &note)?;
+
+    // Save the file.
+    workbook.save("notes.xlsx")?;
+
+    Ok(())
+}
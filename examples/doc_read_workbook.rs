@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates reading back the values from a
+//! generated xlsx file.
+
+use rust_xlsxwriter::{read_workbook, CellValue, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, "Hello")?;
+    worksheet.write(1, 0, 123)?;
+    worksheet.write(2, 0, true)?;
+
+    workbook.save("workbook.xlsx")?;
+
+    // Read back the values to verify them.
+    let values = read_workbook("workbook.xlsx")?;
+
+    assert_eq!(values.sheet_names(), vec!["Sheet1"]);
+
+    let sheet = values.sheet("Sheet1").unwrap();
+    assert_eq!(
+        sheet.cell(0, 0),
+        Some(&CellValue::String("Hello".to_string()))
+    );
+    assert_eq!(sheet.cell(1, 0), Some(&CellValue::Number(123.0)));
+    assert_eq!(sheet.cell(2, 0), Some(&CellValue::Boolean(true)));
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding column and win/lose sparklines to a worksheet, with
+//! markers for the highest, lowest and negative points.
+
+use rust_xlsxwriter::{Sparkline, SparklineType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some sample data to plot.
+    worksheet.write_row(0, 0, [-2, 2, 3, -1, 0])?;
+    worksheet.write_row(1, 0, [1, -1, -1, 1, -1])?;
+
+    // Create a column sparkline with high and low point markers.
+    let sparkline1 = Sparkline::new()
+        .set_range(("Sheet1", 0, 0, 0, 4))
+        .set_type(SparklineType::Column)
+        .show_high_point(true)
+        .show_low_point(true);
+
+    // Create a win/lose sparkline with negative points highlighted.
+    let sparkline2 = Sparkline::new()
+        .set_range(("Sheet1", 1, 0, 1, 4))
+        .set_type(SparklineType::WinLose)
+        .show_negative_points(true)
+        .set_negative_points_color("#FF0000");
+
+    // Add them to the worksheet.
+    worksheet.add_sparkline(0, 5, &sparkline1)?;
+    worksheet.add_sparkline(1, 5, &sparkline2)?;
+
+    // Save the file.
+    workbook.save("sparkline.xlsx")?;
+
+    Ok(())
+}
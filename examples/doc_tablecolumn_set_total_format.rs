@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding a format to the total row of a worksheet table.
+
+use rust_xlsxwriter::{Format, Table, TableColumn, TableFunction, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write the table data.
+    worksheet.write_column(3, 1, ["Apples", "Pears", "Bananas"])?;
+    worksheet.write_column(3, 2, [10000.0, 2000.0, 6000.0])?;
+
+    let bold = Format::new().set_bold();
+    let currency = Format::new().set_bold().set_num_format("$#,##0");
+
+    let columns = vec![
+        TableColumn::new()
+            .set_header("Product")
+            .set_total_label("Totals")
+            .set_total_format(bold),
+        TableColumn::new()
+            .set_header("Sales")
+            .set_total_function(TableFunction::Sum)
+            .set_total_format(currency),
+    ];
+
+    let table = Table::new().set_columns(&columns).set_total_row(true);
+
+    worksheet.add_table(2, 1, 6, 2, &table)?;
+
+    workbook.save("tables.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing `mailto:` and file urls to a
+//! worksheet using the `Url` helper constructors.
+
+use rust_xlsxwriter::{Url, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write a mailto url with a subject and body.
+    let url = Url::mailto("sales@example.com")
+        .set_subject("Q3 Sales & Forecast")
+        .set_body("See the attached report.");
+
+    worksheet.write(0, 0, url)?;
+
+    // Write a link to a local file with a Windows path.
+    worksheet.write(1, 0, Url::file(r"C:\Sales Data\Q3 2024.xlsx"))?;
+
+    // Write a link to a file relative to the workbook.
+    worksheet.write(2, 0, Url::file("Reports/Q3.xlsx"))?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates converting strings into valid worksheet
+//! names.
+
+use rust_xlsxwriter::utility;
+
+fn main() {
+    assert_eq!(utility::sanitize_sheet_name("2030/01/01"), "2030_01_01");
+    assert_eq!(utility::sanitize_sheet_name("'Sales'"), "Sales");
+    assert_eq!(utility::sanitize_sheet_name(""), "Sheet");
+    assert_eq!(utility::sanitize_sheet_name("history"), "history_1");
+}
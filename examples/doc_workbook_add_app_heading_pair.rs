@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the company and manager and
+//! adding a custom heading to the application properties.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.add_worksheet();
+
+    workbook.set_company("Acme Corp");
+    workbook.set_manager("Jane Smith");
+
+    // Add a custom heading and part titles.
+    workbook.add_app_heading_pair("Reports", &["Q1 Summary", "Q2 Summary"])?;
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
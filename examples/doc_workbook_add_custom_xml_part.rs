@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a custom XML part to a workbook.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<invoice xmlns="http://example.com/schemas/invoice">
+  <id>INV-1024</id>
+  <status>Approved</status>
+</invoice>"#;
+
+    workbook.add_custom_xml_part(xml, "http://example.com/schemas/invoice")?;
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write(0, 0, "Invoice INV-1024")?;
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding an index worksheet with links to
+//! the other worksheets in a workbook.
+
+use rust_xlsxwriter::{IndexSheetOptions, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.add_worksheet().set_name("Sales")?;
+    workbook.add_worksheet().set_name("Costs")?;
+    workbook.add_worksheet().set_name("Summary")?;
+
+    // Add an index sheet with a link back to it in cell H1 of each sheet.
+    let options = IndexSheetOptions::new()
+        .set_title("Contents")
+        .set_back_link_cell(0, 7);
+
+    workbook.add_index_sheet(&options)?;
+
+    workbook.save("index_sheet.xlsx")?;
+
+    Ok(())
+}
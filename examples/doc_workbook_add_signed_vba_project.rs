@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a signed VBA macro project to a
+//! workbook.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add the VBA project and signature extracted from a signed xlsm file.
+    workbook.add_signed_vba_project(
+        "examples/vbaProject.bin",
+        "examples/vbaProjectSignature.bin",
+    )?;
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write(0, 0, "This workbook contains signed macros.")?;
+
+    workbook.save("signed_macros.xlsm")?;
+
+    Ok(())
+}
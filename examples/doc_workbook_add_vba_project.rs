@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a VBA macro project to a
+//! workbook and saving it as an `xlsm` file.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add the VBA project extracted from an existing xlsm file.
+    workbook.add_vba_project("examples/vbaProject.bin")?;
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write(0, 0, "Run the macros from the Developer tab.")?;
+
+    // Files with macros must have an xlsm extension.
+    workbook.save("macros.xlsm")?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing a dataset that is split across
+//! worksheets when it exceeds the Excel row limit.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // A header row followed by some data rows.
+    let header = vec!["Id".to_string(), "Name".to_string()];
+    let data = (1..=10).map(|id| vec![id.to_string(), format!("Item {id}")]);
+    let rows = std::iter::once(header).chain(data);
+
+    // Write the data. Additional worksheets are added if required.
+    let count = workbook.add_worksheets_from_rows("Data", rows, true)?;
+    assert_eq!(count, 1);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
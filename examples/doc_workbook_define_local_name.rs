@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates creating global and worksheet scoped
+//! defined names.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.add_worksheet();
+    workbook.add_worksheet().set_name("Sales Data")?;
+
+    // Define a global/workbook name.
+    workbook.define_name("Costs", "=Sheet1!$A$1:$A$10")?;
+
+    // Define a local/worksheet name. The sheet name is quoted
+    // automatically.
+    workbook.define_local_name("Sales Data", "Costs", "='Sales Data'!$B$1:$B$10")?;
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
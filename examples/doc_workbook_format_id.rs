@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates creating an interned format id and
+//! using it to write a large range of formatted cells.
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Intern the format once.
+    let format = workbook.format_id(&Format::new().set_num_format("0.00"));
+
+    let worksheet = workbook.add_worksheet();
+    for row in 0..1000 {
+        for col in 0..20 {
+            worksheet.write_number_with_format(row, col, row, &format)?;
+        }
+    }
+
+    workbook.save("formats.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates enforcing a memory budget while writing
+//! data to a workbook.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    workbook.add_worksheet();
+
+    let budget = 10 * 1024 * 1024;
+
+    for row in 0..10_000 {
+        let worksheet = workbook.worksheet_from_index(0)?;
+        worksheet.write(row, 0, format!("Item {row}"))?;
+
+        if row % 1000 == 0 && workbook.memory_usage().total() > budget {
+            return Err(XlsxError::ParameterError("Memory budget exceeded".into()));
+        }
+    }
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
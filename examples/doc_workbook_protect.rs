@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates protecting the structure of a workbook
+//! so that worksheets can't be added, deleted or moved.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let _worksheet1 = workbook.add_worksheet();
+    let _worksheet2 = workbook.add_worksheet().set_hidden(true);
+
+    // Protect the workbook structure.
+    workbook.protect();
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
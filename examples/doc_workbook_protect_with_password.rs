@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates protecting the structure of a workbook
+//! with a password.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let _worksheet = workbook.add_worksheet();
+
+    // Protect the workbook structure with a password.
+    workbook.protect_with_password("abc123");
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates registering named formats with a
+//! workbook and looking them up when writing data.
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Define the styles used in the workbook in one place.
+    workbook.register_format("header", &Format::new().set_bold())?;
+    workbook.register_format("currency", &Format::new().set_num_format("$#,##0.00"))?;
+
+    write_report(&mut workbook)?;
+
+    workbook.save("formats.xlsx")?;
+
+    Ok(())
+}
+
+// Look up the formats by name instead of passing them as parameters.
+fn write_report(workbook: &mut Workbook) -> Result<(), XlsxError> {
+    let header = workbook.format_by_name("header")?;
+    let currency = workbook.format_by_name("currency")?;
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_with_format(0, 0, "Sales", &header)?;
+    worksheet.write_with_format(1, 0, 1234.5, &currency)?;
+
+    Ok(())
+}
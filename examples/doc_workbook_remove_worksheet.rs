@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates removing and moving worksheets in a
+//! workbook.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.add_worksheet().set_name("Summary")?;
+    workbook.add_worksheet().set_name("Data")?;
+    workbook.add_worksheet().set_name("Scratch")?;
+
+    // Remove a worksheet that isn't required.
+    workbook.remove_worksheet("Scratch")?;
+
+    // Move the "Data" worksheet to the start of the workbook.
+    workbook.move_worksheet(1, 0)?;
+
+    assert_eq!(workbook.sheet_names(), vec!["Data", "Summary"]);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
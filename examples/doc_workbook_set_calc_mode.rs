@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the workbook calculation mode to
+//! manual.
+
+use rust_xlsxwriter::{CalcMode, Formula, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_number(0, 0, 2)?;
+    worksheet.write_number(1, 0, 3)?;
+
+    // Set the formula result since Excel won't recalculate it on load.
+    worksheet.write_formula(2, 0, Formula::new("=SUM(A1:A2)").set_result("5"))?;
+
+    workbook.set_calc_mode(CalcMode::Manual);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates turning off the recalculation of formulas
+//! when the file is opened.
+
+use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_number(0, 0, 2)?;
+    worksheet.write_number(1, 0, 3)?;
+
+    // Supply the formula result so that it doesn't need to be calculated.
+    worksheet.write_formula(2, 0, Formula::new("=SUM(A1:A2)").set_result("5"))?;
+
+    // Don't recalculate the formulas on load. Set a calcId that matches
+    // recent versions of Excel to avoid a version triggered
+    // recalculation.
+    workbook.set_calc_on_load(false);
+    workbook.set_calc_id(191_029);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates cancelling a workbook save.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write(0, 0, "Hello")?;
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    workbook.set_cancellation_token(&cancel);
+
+    // Cancel the save, typically from another thread.
+    cancel.store(true, Ordering::Relaxed);
+
+    let result = workbook.save("workbook.xlsx");
+    assert!(matches!(result, Err(XlsxError::SaveCancelled)));
+
+    Ok(())
+}
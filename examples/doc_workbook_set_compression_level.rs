@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the compression level of a
+//! workbook to favor saving speed over file size.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    for row in 0..1000 {
+        worksheet.write(row, 0, row)?;
+    }
+
+    // Use the fastest deflate compression.
+    workbook.set_compression_level(1);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting default page setup options for
+//! all the worksheets in a workbook.
+
+use rust_xlsxwriter::{PageSetup, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Create a common page setup for the worksheets.
+    let page_setup = PageSetup::new()
+        .set_paper_size(9)
+        .set_landscape()
+        .set_margins(0.5, 0.5, 0.75, 0.75, 0.3, 0.3)
+        .set_header("&CQuarterly Report")
+        .set_footer("&CPage &P of &N");
+
+    workbook.set_default_page_setup(&page_setup);
+
+    workbook.add_worksheet();
+    workbook.add_worksheet();
+
+    // Override the default orientation for one worksheet.
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_portrait();
+
+    workbook.save("page_setup.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the hyperlink base for relative
+//! links in a workbook.
+
+use rust_xlsxwriter::{Url, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    // Write a relative link to another file.
+    worksheet.write_url(0, 0, Url::new("file:///summary.xlsx"))?;
+
+    // Set the base path used to resolve relative links.
+    workbook.set_hyperlink_base("https://example.com/reports/");
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
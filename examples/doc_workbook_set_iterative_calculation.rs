@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates turning on iterative calculation for a
+//! workbook with a circular reference.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    // Write formulas with a circular reference.
+    worksheet.write_formula(0, 0, "=B1/2 + 1")?;
+    worksheet.write_formula(0, 1, "=A1")?;
+
+    workbook.set_iterative_calculation(true, 50, 0.0001);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
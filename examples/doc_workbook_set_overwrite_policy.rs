@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates keeping a backup of an existing file
+//! when saving a workbook.
+
+use rust_xlsxwriter::{OverwritePolicy, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write(0, 0, "Version 1")?;
+    workbook.save("report.xlsx")?;
+
+    // Keep a copy of the previous version when saving again.
+    workbook.set_overwrite_policy(OverwritePolicy::Backup);
+
+    let worksheet = workbook.worksheet_from_index(0)?;
+    worksheet.write(0, 0, "Version 2")?;
+    workbook.save("report.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding worksheets with names from
+//! untrusted data.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.set_sanitize_sheet_names(true);
+
+    for name in ["Sales/Costs", "Q1", "q1", "Summary: 2024 [Final]"] {
+        workbook.add_worksheet_with_name(name)?;
+    }
+
+    assert_eq!(
+        workbook.sheet_names(),
+        vec!["Sales_Costs", "Q1", "q1 (2)", "Summary_ 2024 _Final_"]
+    );
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates reserving space for the unique strings
+//! in a large workbook.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    for row in 0..10_000 {
+        worksheet.write(row, 0, format!("Item {row}"))?;
+    }
+
+    // Reserve space for the unique strings.
+    workbook.set_shared_strings_capacity(10_000);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
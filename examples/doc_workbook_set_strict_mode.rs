@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates turning on strict mode so that an
+//! invalid zoom level raises an error when the workbook is saved.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.set_strict_mode(true);
+
+    let worksheet = workbook.add_worksheet();
+
+    // The zoom is outside Excel's range of 10-400.
+    worksheet.set_zoom(500);
+
+    let error = workbook.save("workbook.xlsx").unwrap_err();
+    assert!(matches!(error.inner(), XlsxError::StrictModeWarning(_)));
+    assert_eq!(error.sheet_name(), Some("Sheet1"));
+
+    Ok(())
+}
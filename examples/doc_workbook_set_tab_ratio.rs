@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates increasing the width of the worksheet
+//! tab area in a workbook with several worksheets.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    for _ in 0..12 {
+        workbook.add_worksheet();
+    }
+
+    workbook.set_tab_ratio(80.0);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates saving a workbook as an Excel template.
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let bold = Format::new().set_bold();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_with_format(0, 0, "Region", &bold)?;
+    worksheet.write_with_format(0, 1, "Sales", &bold)?;
+
+    // Save the workbook as a template.
+    workbook.set_template(true);
+
+    workbook.save("template.xltx")?;
+
+    Ok(())
+}
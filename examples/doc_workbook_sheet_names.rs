@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates getting the worksheet names in a
+//! workbook.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.add_worksheet();
+    workbook.add_worksheet().set_name("Data")?;
+    workbook.add_worksheet();
+
+    assert_eq!(workbook.sheet_names(), vec!["Sheet1", "Data", "Sheet3"]);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing a large number of unique strings
+//! as inline strings.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    for row in 0..10_000 {
+        worksheet.write(row, 0, format!("Order {row:06}"))?;
+    }
+
+    // Write the strings inline without a shared strings table.
+    workbook.use_inline_strings(true);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
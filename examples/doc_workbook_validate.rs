@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates validating a workbook before saving it.
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    // Add a chart that refers to a worksheet that doesn't exist.
+    let mut chart = Chart::new(ChartType::Column);
+    chart.add_series().set_values("Data!$A$1:$A$5");
+    worksheet.insert_chart(0, 2, &chart)?;
+
+    // Check the workbook before saving it.
+    for problem in workbook.validate() {
+        println!("{problem}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting an autofilter with a cell color
+//! filter to show the rows where the cells in a column have a yellow fill.
+
+use rust_xlsxwriter::{Color, FilterCondition, Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let highlight = Format::new().set_background_color(Color::Yellow);
+
+    // Add a worksheet with some sample data to filter.
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Region")?;
+    worksheet.write_string(1, 0, "East")?;
+    worksheet.write_string(2, 0, "West")?;
+    worksheet.write_string(3, 0, "East")?;
+    worksheet.write_string(4, 0, "North")?;
+    worksheet.write_string(5, 0, "South")?;
+    worksheet.write_string(6, 0, "West")?;
+
+    worksheet.write_string(0, 1, "Sales")?;
+    worksheet.write_number(1, 1, 3000)?;
+    worksheet.write_number_with_format(2, 1, 8000, &highlight)?;
+    worksheet.write_number(3, 1, 5000)?;
+    worksheet.write_number(4, 1, 4000)?;
+    worksheet.write_number_with_format(5, 1, 7000, &highlight)?;
+    worksheet.write_number(6, 1, 9000)?;
+
+    // Set the autofilter.
+    worksheet.autofilter(0, 0, 6, 1)?;
+
+    // Set a filter to show the cells with a yellow fill.
+    let filter_condition = FilterCondition::new().add_cell_color_filter(Color::Yellow);
+    worksheet.filter_column(1, &filter_condition)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting an autofilter with a "Top 10"
+//! style filter to show the 3 highest values in a column.
+
+use rust_xlsxwriter::{FilterCondition, FilterTop10, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet with some sample data to filter.
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Region")?;
+    worksheet.write_string(1, 0, "East")?;
+    worksheet.write_string(2, 0, "West")?;
+    worksheet.write_string(3, 0, "East")?;
+    worksheet.write_string(4, 0, "North")?;
+    worksheet.write_string(5, 0, "South")?;
+    worksheet.write_string(6, 0, "West")?;
+
+    worksheet.write_string(0, 1, "Sales")?;
+    worksheet.write_number(1, 1, 3000)?;
+    worksheet.write_number(2, 1, 8000)?;
+    worksheet.write_number(3, 1, 5000)?;
+    worksheet.write_number(4, 1, 4000)?;
+    worksheet.write_number(5, 1, 7000)?;
+    worksheet.write_number(6, 1, 9000)?;
+
+    // Set the autofilter.
+    worksheet.autofilter(0, 0, 6, 1)?;
+
+    // Set a "Top 10" filter to show the 3 highest sales values.
+    let filter_condition = FilterCondition::new().add_top10_filter(FilterTop10::TopItems, 3);
+    worksheet.filter_column(1, &filter_condition)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
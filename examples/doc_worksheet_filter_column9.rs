@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting an autofilter with date grouping
+//! list filters to show the dates in March 2024 and all of 2023.
+
+use rust_xlsxwriter::{
+    ExcelDateTime, FilterCondition, FilterDateGrouping, Format, Workbook, XlsxError,
+};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet with some sample data to filter.
+    let worksheet = workbook.add_worksheet();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    worksheet.set_column_width(0, 12)?;
+    worksheet.write_string(0, 0, "Date")?;
+    worksheet.write_string(0, 1, "Sales")?;
+
+    let dates = [
+        (2023, 5, 12, 3000),
+        (2023, 11, 3, 8000),
+        (2024, 1, 22, 5000),
+        (2024, 3, 1, 4000),
+        (2024, 3, 15, 7000),
+        (2024, 4, 2, 9000),
+    ];
+
+    for (row, (year, month, day, sales)) in dates.into_iter().enumerate() {
+        let row = row as u32 + 1;
+        let date = ExcelDateTime::from_ymd(year, month, day)?;
+        worksheet.write_datetime_with_format(row, 0, &date, &date_format)?;
+        worksheet.write_number(row, 1, sales)?;
+    }
+
+    // Set the autofilter.
+    worksheet.autofilter(0, 0, 6, 1)?;
+
+    // Set a filter condition to only show dates in March 2024 or in 2023.
+    let filter_condition = FilterCondition::new()
+        .add_list_date_filter(
+            &ExcelDateTime::from_ymd(2024, 3, 1)?,
+            FilterDateGrouping::Month,
+        )
+        .add_list_date_filter(
+            &ExcelDateTime::from_ymd(2023, 1, 1)?,
+            FilterDateGrouping::Year,
+        );
+    worksheet.filter_column(0, &filter_condition)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
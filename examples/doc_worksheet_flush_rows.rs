@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates flushing rows to reduce the memory used
+//! by a large worksheet.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    for row in 0..100_000 {
+        for col in 0..10 {
+            worksheet.write(row, col, row)?;
+        }
+
+        // Release the memory used by completed rows every 10,000 rows.
+        if row % 10_000 == 0 {
+            worksheet.flush_rows(row)?;
+        }
+    }
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
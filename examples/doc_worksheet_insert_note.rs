@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding a note to a worksheet cell.
+
+use rust_xlsxwriter::{Note, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write some data.
+    worksheet.write(0, 0, "Hello")?;
+
+    // Create a new note.
+    let note = Note::new("Some text for the note");
+
+    // Add the note to a worksheet cell.
+    worksheet.insert_note(0, 0, &note)?;
+
+    // Save the file.
+    workbook.save("notes.xlsx")?;
+
+    Ok(())
+}
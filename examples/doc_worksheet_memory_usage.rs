@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates checking the approximate memory used by
+//! a worksheet.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    for row in 0..1000 {
+        worksheet.write(row, 0, row)?;
+        worksheet.write(row, 1, format!("Item {row}"))?;
+    }
+
+    let usage = worksheet.memory_usage();
+    println!("Cells:   {} bytes", usage.cells());
+    println!("Strings: {} bytes", usage.strings());
+    println!("Total:   {} bytes", usage.total());
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the active pane and the cell
+//! selections in a worksheet with frozen panes.
+
+use rust_xlsxwriter::{Pane, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    // Freeze the top row and first column.
+    worksheet.set_freeze_panes(1, 1)?;
+
+    // Make the top right pane the active pane and select a range in it.
+    worksheet.set_active_pane(Pane::TopRight);
+    worksheet.set_pane_selection(Pane::TopRight, 0, 3, 0, 5)?;
+
+    // Set the selection in the bottom right scrolling pane.
+    worksheet.set_pane_selection(Pane::BottomRight, 9, 3, 9, 3)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
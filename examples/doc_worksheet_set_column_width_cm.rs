@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the width of columns in
+//! centimeters.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Add some text.
+    worksheet.write_string(0, 0, "Normal")?;
+    worksheet.write_string(0, 2, "5 cm")?;
+
+    // Set the column width in centimeters.
+    worksheet.set_column_width_cm(2, 5.0)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
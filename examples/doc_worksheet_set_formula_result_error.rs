@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting an error value as the result of
+//! a formula.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Write a formula and set its result to a divide by zero error.
+    worksheet
+        .write_formula(0, 0, "1/0")?
+        .set_formula_result_error(0, 0, "#DIV/0!")?;
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the results of a column of
+//! formulas.
+
+use rust_xlsxwriter::{FormulaResult, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_column(0, 0, [1, 2, 0])?;
+
+    for row in 0..3 {
+        worksheet.write_formula(row, 1, format!("=A{}*2", row + 1))?;
+        worksheet.write_formula(row, 2, format!("=10/A{}", row + 1))?;
+    }
+
+    // Set the results of a column of formulas.
+    worksheet.set_formula_results(0, 1, 2, 1, &[2.0, 4.0, 0.0])?;
+
+    // Set mixed number and error results.
+    worksheet.set_formula_results(
+        0,
+        2,
+        2,
+        2,
+        &[
+            FormulaResult::Number(10.0),
+            FormulaResult::Number(5.0),
+            FormulaResult::Error("#DIV/0!".to_string()),
+        ],
+    )?;
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting frozen panes that revert to
+//! split panes when they are unfrozen.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_string(0, 0, "Scroll down or across")?;
+
+    // Freeze the top row and leftmost column.
+    worksheet.set_frozen_split_panes(1, 1)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates printing cell errors as blanks.
+
+use rust_xlsxwriter::{Formula, PrintErrorOption, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, Formula::new("=1/0"))?;
+
+    // Print the cell error as a blank.
+    worksheet.set_print_errors_as(PrintErrorOption::Blank);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
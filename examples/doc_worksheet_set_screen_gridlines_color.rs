@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting a light gray color for the
+//! worksheet screen gridlines.
+
+use rust_xlsxwriter::{Color, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, "Hello")?;
+
+    // Set a lighter color for the screen gridlines.
+    worksheet.set_screen_gridlines_color(Color::Silver);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting split panes in worksheets.
+
+use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let mut worksheet1 = Worksheet::new();
+    let mut worksheet2 = Worksheet::new();
+    let mut worksheet3 = Worksheet::new();
+
+    worksheet1.write_string(0, 0, "Scroll down")?;
+    worksheet2.write_string(0, 0, "Scroll across")?;
+    worksheet3.write_string(0, 0, "Scroll down or across")?;
+
+    // Split the worksheet below the first row.
+    worksheet1.set_split_panes(0, 15);
+
+    // Split the worksheet to the right of the first column.
+    worksheet2.set_split_panes(8.43, 0);
+
+    // Split the worksheet below the first row and to the right of the
+    // first column.
+    worksheet3.set_split_panes(8.43, 15);
+
+    workbook.push_worksheet(worksheet1);
+    workbook.push_worksheet(worksheet2);
+    workbook.push_worksheet(worksheet3);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
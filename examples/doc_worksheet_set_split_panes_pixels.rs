@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting split panes in a worksheet
+//! using pixel positions.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_string(0, 0, "Scroll down or across")?;
+
+    // Split the worksheet below the first row and to the right of the
+    // first column.
+    worksheet.set_split_panes_pixels(64, 20);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing an over-length url as plain
+//! text instead of raising an error.
+
+use rust_xlsxwriter::{UrlLengthPolicy, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.set_url_length_policy(UrlLengthPolicy::WriteAsText);
+
+    // This url is too long for Excel and is written as a string.
+    let url = format!("https://www.example.com/{}", "a".repeat(2100));
+    worksheet.write_url(0, 0, url.as_str())?;
+
+    // This url is written as a link.
+    worksheet.write_url(1, 0, "https://www.example.com/")?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the visibility of worksheets.
+//! The second worksheet can be unhidden by the user but the third one can only
+//! be unhidden using VBA.
+
+use rust_xlsxwriter::{SheetVisibility, Workbook, Worksheet, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet1 = Worksheet::new();
+    let mut worksheet2 = Worksheet::new();
+    let mut worksheet3 = Worksheet::new();
+
+    worksheet2.set_visibility(SheetVisibility::Hidden);
+    worksheet3.set_visibility(SheetVisibility::VeryHidden);
+
+    workbook.push_worksheet(worksheet1);
+    workbook.push_worksheet(worksheet2);
+    workbook.push_worksheet(worksheet3);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting different zoom levels for the
+//! worksheet views.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_string(0, 0, "Hello")?;
+
+    // Set a zoom level for each of the views.
+    worksheet.set_zoom_normal(150);
+    worksheet.set_zoom_page_layout(75);
+    worksheet.set_zoom_page_break_preview(60);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
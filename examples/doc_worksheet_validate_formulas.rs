@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates finding the cells with invalid formulas
+//! in a worksheet.
+
+use rust_xlsxwriter::{utility, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_formula(0, 0, "=SUM(B1:B10)")?;
+    worksheet.write_formula(1, 0, "=SUM(B1:B10")?;
+    worksheet.write_formula(2, 0, "=AVERAGEE(B1:B10)")?;
+
+    for (row, col, error) in worksheet.validate_formulas() {
+        println!("{}: {error}", utility::row_col_to_cell(row, col));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates inspecting the state of the worksheets
+//! in a workbook.
+
+use rust_xlsxwriter::{Color, SheetVisibility, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    workbook.add_worksheet().set_tab_color(Color::Red);
+    workbook.add_worksheet().set_active(true);
+    workbook.add_worksheet().set_hidden(true);
+
+    // Inspect the worksheet states.
+    for worksheet in workbook.worksheets() {
+        println!(
+            "{}: active = {}, hidden = {}, visibility = {:?}, tab color = {:?}",
+            worksheet.name(),
+            worksheet.is_active(),
+            worksheet.is_hidden(),
+            worksheet.visibility(),
+            worksheet.tab_color(),
+        );
+    }
+
+    let worksheet = workbook.worksheet_from_index(2)?;
+    assert_eq!(worksheet.visibility(), SheetVisibility::Hidden);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
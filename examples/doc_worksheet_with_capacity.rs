@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates creating a worksheet with capacity hints
+//! for a known-size export.
+
+use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Create a worksheet sized for 1000 rows of 10 columns.
+    let mut worksheet = Worksheet::with_capacity(1000, 10);
+
+    for row in 0..1000 {
+        for col in 0..10 {
+            worksheet.write(row, col, row * 10 + u32::from(col))?;
+        }
+    }
+
+    workbook.push_worksheet(worksheet);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing 2D `ndarray` arrays to a
+//! worksheet, with and without labels.
+
+use ndarray::array;
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let matrix = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+
+    // Write the matrix.
+    worksheet.write_array2(0, 0, &matrix)?;
+
+    // Write the transposed matrix with labels.
+    worksheet.write_array2_with_labels(
+        0,
+        4,
+        &matrix.t(),
+        &["x", "y", "z"],
+        &["Sample 1", "Sample 2"],
+    )?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
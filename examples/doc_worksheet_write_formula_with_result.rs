@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing formulas with typed results.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_number(0, 0, 5)?;
+
+    worksheet.write_formula_with_result(0, 1, "=A1*2", 10)?;
+    worksheet.write_formula_with_result(0, 2, "=A1>3", true)?;
+    worksheet.write_formula_with_result(0, 3, r#"=TEXT(A1, "00")"#, "05")?;
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing an array of JSON objects to a
+//! worksheet.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+use serde_json::json;
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let data = json!([
+        {"id": 1, "name": "Apples", "in_stock": true, "tags": ["fruit"]},
+        {"id": 2, "name": "Pears", "in_stock": false},
+        {"id": 3, "name": "Bread", "price": 2.5, "tags": null},
+    ]);
+
+    worksheet.write_json_rows(0, 0, &data)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing rows of data from an iterator
+//! without collecting the values into an intermediate vector.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let data = "Region,Product,Units\nEast,Apples,100\nWest,Pears,150";
+
+    // Write each line of text as a row, without allocating the fields.
+    for (row, line) in data.lines().enumerate() {
+        worksheet.write_row(row as u32, 0, line.split(','))?;
+    }
+
+    // Or write computed values.
+    worksheet.write_row(4, 0, (1..=10).map(|n| n * n))?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing a shared formula to a column of
+//! cells.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write some test data.
+    for row in 0..1000 {
+        worksheet.write(row, 0, row)?;
+        worksheet.write(row, 1, 2)?;
+    }
+
+    // Write "=A1*B1" to C1, "=A2*B2" to C2, and so on.
+    worksheet.write_shared_formula(0, 2, 999, 2, "=A1*B1")?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing Unix timestamps in seconds and
+//! milliseconds to a worksheet.
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Create a format to use with the timestamps.
+    let format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss.000");
+
+    // Set the column width for clarity.
+    worksheet.set_column_width(0, 25)?;
+
+    // Write timestamps in seconds and milliseconds.
+    worksheet.write_unix_timestamp(0, 0, 1_687_108_108, &format)?;
+    worksheet.write_unix_timestamp_millis(1, 0, 1_687_108_108_250, &format)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
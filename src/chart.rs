@@ -387,6 +387,7 @@ use std::{fmt, mem};
 use crate::{
     drawing::{DrawingObject, DrawingType},
     utility::{self, ToXmlBoolean},
+    warning::Warnings,
     xmlwriter::XMLWriter,
     ColNum, Color, IntoColor, IntoExcelDateTime, ObjectMovement, RowNum, XlsxError, COL_MAX,
     ROW_MAX,
@@ -460,12 +461,15 @@ pub struct Chart {
     scale_width: f64,
     scale_height: f64,
     axis_ids: (u32, u32),
+    axis2_ids: (u32, u32),
     category_has_num_format: bool,
     chart_type: ChartType,
     chart_group_type: ChartType,
     pub(crate) title: ChartTitle,
     pub(crate) x_axis: ChartAxis,
     pub(crate) y_axis: ChartAxis,
+    pub(crate) x2_axis: ChartAxis,
+    pub(crate) y2_axis: ChartAxis,
     pub(crate) legend: ChartLegend,
     pub(crate) chart_area_format: ChartFormat,
     pub(crate) plot_area_format: ChartFormat,
@@ -475,7 +479,6 @@ pub struct Chart {
     show_hidden_data: bool,
     show_na_as_empty: bool,
     default_num_format: String,
-    has_overlap: bool,
     overlap: i8,
     gap: u16,
     style: u8,
@@ -490,6 +493,8 @@ pub struct Chart {
     drop_lines_format: ChartFormat,
     table: Option<ChartDataTable>,
     base_series_index: usize,
+    is_secondary_group: bool,
+    pub(crate) warnings: Warnings,
 }
 
 impl Chart {
@@ -573,6 +578,7 @@ impl Chart {
             drawing_type: DrawingType::Chart,
 
             axis_ids: (0, 0),
+            axis2_ids: (0, 0),
             series: vec![],
             category_has_num_format: false,
             chart_type,
@@ -580,6 +586,8 @@ impl Chart {
             title: ChartTitle::new(),
             x_axis: ChartAxis::new(),
             y_axis: ChartAxis::new(),
+            x2_axis: ChartAxis::new(),
+            y2_axis: ChartAxis::new(),
             legend: ChartLegend::new(),
             chart_area_format: ChartFormat::default(),
             plot_area_format: ChartFormat::default(),
@@ -588,7 +596,6 @@ impl Chart {
             show_hidden_data: false,
             show_na_as_empty: false,
             default_num_format: "General".to_string(),
-            has_overlap: false,
             overlap: 0,
             gap: 150,
             style: 2,
@@ -605,9 +612,11 @@ impl Chart {
             table: None,
             combined_chart: None,
             base_series_index: 0,
+            is_secondary_group: false,
+            warnings: Warnings::new(),
         };
 
-        match chart_type {
+        let mut chart = match chart_type {
             ChartType::Area | ChartType::AreaStacked | ChartType::AreaPercentStacked => {
                 Self::initialize_area_chart(chart)
             }
@@ -639,7 +648,11 @@ impl Chart {
             | ChartType::ScatterSmoothWithMarkers => Self::initialize_scatter_chart(chart),
 
             ChartType::Stock => Self::initialize_stock_chart(chart),
-        }
+        };
+
+        chart.initialize_secondary_axes();
+
+        chart
     }
 
     /// Create a new Area `Chart`.
@@ -1013,6 +1026,34 @@ impl Chart {
         &mut self.y_axis
     }
 
+    /// Get the chart secondary X-Axis object in order to set its properties.
+    ///
+    /// Get a reference to the chart's secondary X-Axis [`ChartAxis`] object in
+    /// order to set its properties. The secondary axes are used by series that
+    /// are plotted on the secondary axis via
+    /// [`series.set_secondary_axis()`](ChartSeries::set_secondary_axis).
+    ///
+    /// The secondary X-Axis is hidden by default. It can be made visible via
+    /// [`ChartAxis::set_hidden()`].
+    ///
+    /// See the [`chart.combine()`][Chart::combine] method for an example.
+    ///
+    pub fn x2_axis(&mut self) -> &mut ChartAxis {
+        &mut self.x2_axis
+    }
+
+    /// Get the chart secondary Y-Axis object in order to set its properties.
+    ///
+    /// Get a reference to the chart's secondary Y-Axis [`ChartAxis`] object in
+    /// order to set its properties. The secondary Y-Axis is displayed on the
+    /// right hand side of the chart.
+    ///
+    /// See the [`chart.combine()`][Chart::combine] method for an example.
+    ///
+    pub fn y2_axis(&mut self) -> &mut ChartAxis {
+        &mut self.y2_axis
+    }
+
     /// Get the chart legend object in order to set its properties.
     ///
     /// Get a reference to the chart's [`ChartLegend`] object in order to set
@@ -1070,8 +1111,77 @@ impl Chart {
 
     /// Create a combination chart with a secondary chart.
     ///
-    /// TODO explain chart `combine()`.
+    /// The `combine()` method is used to combine two charts of different types,
+    /// for example a Column chart and a Line chart. The secondary chart shares
+    /// the category axis and, by default, the value axis of the primary chart.
+    ///
+    /// The series of either chart can be plotted on a secondary Y axis via
+    /// [`series.set_secondary_axis()`](ChartSeries::set_secondary_axis). The
+    /// secondary axes of a chart are set via [`chart.x2_axis()`][Chart::x2_axis]
+    /// and [`chart.y2_axis()`][Chart::y2_axis] on the chart that contains the
+    /// secondary series.
+    ///
+    /// Only one chart can be combined with the primary chart. Pie and Doughnut
+    /// charts can't be used in a combined chart.
+    ///
+    /// # Parameters
+    ///
+    /// * `chart` - The secondary [`Chart`] to combine with the primary chart.
+    ///
+    /// # Examples
+    ///
+    /// An example of creating a combined Column and Line chart where the Line
+    /// chart series are plotted on a secondary Y axis.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_combine.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data for the chart.
+    /// #     worksheet.write_column(0, 0, ["Jan", "Feb", "Mar", "Apr", "May"])?;
+    /// #     worksheet.write_column(0, 1, [3200, 4100, 3800, 5200, 4900])?;
+    /// #     worksheet.write_column(0, 2, [0.12, 0.15, 0.14, 0.19, 0.17])?;
+    /// #
+    ///     // Create a new Column chart for the primary series.
+    ///     let mut column_chart = Chart::new(ChartType::Column);
+    ///     column_chart
+    ///         .add_series()
+    ///         .set_name("Sales")
+    ///         .set_categories("Sheet1!$A$1:$A$5")
+    ///         .set_values("Sheet1!$B$1:$B$5");
+    ///
+    ///     // Create a new Line chart for the secondary series.
+    ///     let mut line_chart = Chart::new(ChartType::Line);
+    ///     line_chart
+    ///         .add_series()
+    ///         .set_name("Margin")
+    ///         .set_categories("Sheet1!$A$1:$A$5")
+    ///         .set_values("Sheet1!$C$1:$C$5")
+    ///         .set_secondary_axis(true);
+    ///
+    ///     // Format the secondary Y axis.
+    ///     line_chart.y2_axis().set_name("Margin").set_num_format("0%");
+    ///
+    ///     // Combine the charts.
+    ///     column_chart.combine(&line_chart);
+    ///
+    ///     // Format the primary axes.
+    ///     column_chart.x_axis().set_name("Month");
+    ///     column_chart.y_axis().set_name("Sales");
+    ///
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 4, &column_chart)?;
     ///
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
     ///
     pub fn combine(&mut self, chart: &Chart) -> &mut Chart {
         self.combined_chart = Some(Box::new(chart.clone()));
@@ -1156,8 +1266,12 @@ impl Chart {
     pub fn set_style(&mut self, style: u8) -> &mut Chart {
         if (1..=48).contains(&style) {
             self.style = style;
+            self.warnings.clear("set_style");
         } else {
-            eprintln!("Style id '{style}' outside Excel range: 1 <= style <= 48.");
+            self.warnings.warn(
+                "set_style",
+                format!("Style id '{style}' outside Excel range: 1 <= style <= 48."),
+            );
         }
 
         self
@@ -1616,7 +1730,8 @@ impl Chart {
     ///
     /// # Parameters
     ///
-    /// * `enable` - Turn the property on/off. It is off by default.
+    /// * `enable` - Turn the property on/off. It is off by default, except for
+    ///   Stock charts where it is on by default to match Excel.
     ///
     /// # Examples
     ///
@@ -2198,6 +2313,15 @@ impl Chart {
                 ));
             }
 
+            // Check for Pie/Doughnut charts with secondary axis series.
+            if series.secondary_axis
+                && matches!(self.chart_group_type, ChartType::Pie | ChartType::Doughnut)
+            {
+                return Err(XlsxError::ChartError(
+                    "Pie and Doughnut charts don't support secondary axes".to_string(),
+                ));
+            }
+
             // Validate the series values range.
             series.value_range.validate()?;
 
@@ -2281,20 +2405,38 @@ impl Chart {
         self.axis_ids = (axis_id1, axis_id2);
     }
 
+    /// Set the chart secondary axis ids.
+    ///
+    /// This method is used to set the secondary axis ids in the same way as
+    /// [`Chart::set_axis_ids()`], for testing.
+    ///
+    #[doc(hidden)]
+    pub fn set_axis2_ids(&mut self, axis_id1: u32, axis_id2: u32) {
+        self.axis2_ids = (axis_id1, axis_id2);
+    }
+
     // -----------------------------------------------------------------------
     // Crate level helper methods.
     // -----------------------------------------------------------------------
 
     // Set chart unique axis ids.
     pub(crate) fn add_axis_ids(&mut self) {
-        if self.axis_ids.0 != 0 {
-            return;
+        if self.axis_ids.0 == 0 {
+            let axis_id_1 = (5000 + self.id) * 10000 + 1;
+            let axis_id_2 = axis_id_1 + 1;
+
+            self.axis_ids = (axis_id_1, axis_id_2);
         }
 
-        let axis_id_1 = (5000 + self.id) * 10000 + 1;
-        let axis_id_2 = axis_id_1 + 1;
+        // The secondary axis ids follow on from the primary ids.
+        if self.axis2_ids.0 == 0 {
+            self.axis2_ids = (self.axis_ids.0 + 2, self.axis_ids.0 + 3);
+        }
+    }
 
-        self.axis_ids = (axis_id_1, axis_id_2);
+    // Check if any of the chart series are plotted on the secondary axis.
+    fn has_secondary_axis(&self) -> bool {
+        self.series.iter().any(|series| series.secondary_axis)
     }
 
     // Check for any legend entries that have been hidden/deleted via the
@@ -2339,6 +2481,25 @@ impl Chart {
     // Chart specific methods.
     // -----------------------------------------------------------------------
 
+    // Initialize the secondary axes from the primary axes. The secondary
+    // category axis is hidden and the secondary value axis crosses at the
+    // maximum category so that it is displayed on the opposite side of the
+    // chart. The axes are reversed for Bar charts.
+    fn initialize_secondary_axes(&mut self) {
+        self.x2_axis = self.x_axis.clone();
+        self.y2_axis = self.y_axis.clone();
+        self.x2_axis.major_gridlines = false;
+        self.y2_axis.major_gridlines = false;
+
+        if self.chart_group_type == ChartType::Bar {
+            self.y2_axis.is_hidden = true;
+            self.y2_axis.crossing = ChartAxisCrossing::Max;
+        } else {
+            self.x2_axis.is_hidden = true;
+            self.x2_axis.crossing = ChartAxisCrossing::Max;
+        }
+    }
+
     // Initialize area charts.
     fn initialize_area_chart(mut self) -> Chart {
         self.x_axis.axis_type = ChartAxisType::Category;
@@ -2383,12 +2544,10 @@ impl Chart {
             self.grouping = ChartGrouping::Clustered;
         } else if self.chart_type == ChartType::BarStacked {
             self.grouping = ChartGrouping::Stacked;
-            self.has_overlap = true;
             self.overlap = 100;
         } else if self.chart_type == ChartType::BarPercentStacked {
             self.grouping = ChartGrouping::PercentStacked;
             self.default_num_format = "0%".to_string();
-            self.has_overlap = true;
             self.overlap = 100;
         }
 
@@ -2412,12 +2571,10 @@ impl Chart {
             self.grouping = ChartGrouping::Clustered;
         } else if self.chart_type == ChartType::ColumnStacked {
             self.grouping = ChartGrouping::Stacked;
-            self.has_overlap = true;
             self.overlap = 100;
         } else if self.chart_type == ChartType::ColumnPercentStacked {
             self.grouping = ChartGrouping::PercentStacked;
             self.default_num_format = "0%".to_string();
-            self.has_overlap = true;
             self.overlap = 100;
         }
 
@@ -2521,6 +2678,9 @@ impl Chart {
         self.chart_group_type = ChartType::Stock;
         self.default_label_position = ChartDataLabelPosition::Right;
 
+        // Excel stock charts have high-low lines by default.
+        self.has_high_low_lines = true;
+
         self
     }
 
@@ -2563,7 +2723,7 @@ impl Chart {
             self.write_gap_width(self.gap);
         }
 
-        if self.has_overlap {
+        if self.overlap != 0 {
             // Write the c:overlap element.
             self.write_overlap();
         }
@@ -2738,6 +2898,8 @@ impl Chart {
 
     // Assemble and write the XML file.
     pub(crate) fn assemble_xml_file(&mut self) {
+        self.collect_warnings();
+
         self.writer.xml_declaration();
 
         // Write the c:chartSpace element.
@@ -2762,6 +2924,23 @@ impl Chart {
 
         // Close the c:chartSpace tag.
         self.writer.xml_end_tag("c:chartSpace");
+
+        if let Some(combined_chart) = &mut self.combined_chart {
+            combined_chart.collect_warnings();
+            self.warnings.extend(&combined_chart.warnings);
+        }
+    }
+
+    // Collect the warnings from the axes and series. Warnings from the other
+    // chart elements are collected as they are written.
+    fn collect_warnings(&mut self) {
+        for axis in [&self.x_axis, &self.y_axis, &self.x2_axis, &self.y2_axis] {
+            self.warnings.extend(&axis.warnings);
+        }
+
+        for series in &self.series {
+            self.warnings.extend(&series.warnings);
+        }
     }
 
     // Write the <c:chartSpace> element.
@@ -2851,35 +3030,99 @@ impl Chart {
         // Write the c:layout element.
         self.write_layout();
 
-        // Write the <c:xxxChart> element for each chart type.
-        self.write_chart_type();
+        // Write the <c:xxxChart> elements for the primary and secondary axes.
+        self.write_chart_group(false);
+        self.write_chart_group(true);
 
-        // Write the combined chart.
+        // Write the combined chart. It shares the primary axes but has its own
+        // secondary axes.
         if let Some(combined_chart) = &mut self.combined_chart {
             combined_chart.axis_ids = self.axis_ids;
+            combined_chart.axis2_ids = (self.axis2_ids.0 + 2, self.axis2_ids.1 + 2);
             combined_chart.base_series_index = self.series.len();
 
             mem::swap(&mut combined_chart.writer, &mut self.writer);
-            combined_chart.write_chart_type();
+            combined_chart.write_chart_group(false);
+            combined_chart.write_chart_group(true);
             mem::swap(&mut combined_chart.writer, &mut self.writer);
         }
 
+        // Write the primary axes.
+        self.write_axes(false);
+
+        // Write the secondary axes, if required.
+        if self.has_secondary_axis() {
+            self.write_secondary_axes();
+        }
+
+        if let Some(combined_chart) = &mut self.combined_chart {
+            if combined_chart.has_secondary_axis() {
+                mem::swap(&mut combined_chart.writer, &mut self.writer);
+                combined_chart.write_secondary_axes();
+                mem::swap(&mut combined_chart.writer, &mut self.writer);
+            }
+        }
+
+        // Write the c:dTable element.
+        if let Some(table) = &self.table {
+            self.write_data_table(&table.clone());
+        }
+
+        // Write the c:spPr element.
+        self.write_sp_pr(&self.plot_area_format.clone());
+
+        self.writer.xml_end_tag("c:plotArea");
+    }
+
+    // Write the <c:xxxChart> element for the series on the primary or secondary
+    // axes. The element is omitted if there aren't any series for the axes.
+    fn write_chart_group(&mut self, is_secondary: bool) {
+        if !self
+            .series
+            .iter()
+            .any(|series| series.secondary_axis == is_secondary)
+        {
+            return;
+        }
+
+        self.is_secondary_group = is_secondary;
+
+        if is_secondary {
+            mem::swap(&mut self.axis_ids, &mut self.axis2_ids);
+            self.write_chart_type();
+            mem::swap(&mut self.axis_ids, &mut self.axis2_ids);
+        } else {
+            self.write_chart_type();
+        }
+
+        self.is_secondary_group = false;
+    }
+
+    // Write the category and value axis elements. Excel writes the secondary
+    // value axis before the secondary category axis.
+    fn write_axes(&mut self, is_secondary: bool) {
         // Reverse the X and Y axes for Bar charts.
         if self.chart_group_type == ChartType::Bar {
-            std::mem::swap(&mut self.x_axis, &mut self.y_axis);
+            mem::swap(&mut self.x_axis, &mut self.y_axis);
         }
 
         match self.chart_group_type {
             ChartType::Pie | ChartType::Doughnut => {}
 
             ChartType::Scatter => {
-                // Write the c:valAx element.
-                self.write_cat_val_ax();
-
-                // Write the c:valAx element.
-                self.write_val_ax();
+                if is_secondary {
+                    self.write_val_ax();
+                    self.write_cat_val_ax();
+                } else {
+                    self.write_cat_val_ax();
+                    self.write_val_ax();
+                }
             }
             _ => {
+                if is_secondary {
+                    self.write_val_ax();
+                }
+
                 if self.x_axis.axis_type == ChartAxisType::Date {
                     // Write the c:dateAx element.
                     self.write_date_ax();
@@ -2888,25 +3131,30 @@ impl Chart {
                     self.write_cat_ax();
                 }
 
-                // Write the c:valAx element.
-                self.write_val_ax();
+                if !is_secondary {
+                    self.write_val_ax();
+                }
             }
         }
 
         // Reset the X and Y axes for Bar charts.
         if self.chart_group_type == ChartType::Bar {
-            std::mem::swap(&mut self.x_axis, &mut self.y_axis);
+            mem::swap(&mut self.x_axis, &mut self.y_axis);
         }
+    }
 
-        // Write the c:dTable element.
-        if let Some(table) = &self.table {
-            self.write_data_table(&table.clone());
-        }
+    // Write the secondary axes by temporarily swapping them with the primary
+    // axes.
+    fn write_secondary_axes(&mut self) {
+        mem::swap(&mut self.x_axis, &mut self.x2_axis);
+        mem::swap(&mut self.y_axis, &mut self.y2_axis);
+        mem::swap(&mut self.axis_ids, &mut self.axis2_ids);
 
-        // Write the c:spPr element.
-        self.write_sp_pr(&self.plot_area_format.clone());
+        self.write_axes(true);
 
-        self.writer.xml_end_tag("c:plotArea");
+        mem::swap(&mut self.x_axis, &mut self.x2_axis);
+        mem::swap(&mut self.y_axis, &mut self.y2_axis);
+        mem::swap(&mut self.axis_ids, &mut self.axis2_ids);
     }
 
     // Write the <c:xxxChart> element.
@@ -2985,6 +3233,11 @@ impl Chart {
     // Write the <c:ser> element.
     fn write_series(&mut self) {
         for (index, series) in self.series.clone().iter_mut().enumerate() {
+            // Only write the series for the current primary/secondary axes.
+            if series.secondary_axis != self.is_secondary_group {
+                continue;
+            }
+
             let max_points = series.value_range.number_of_points();
 
             self.writer.xml_start_tag_only("c:ser");
@@ -3084,6 +3337,11 @@ impl Chart {
     // Write the <c:ser> element for scatter charts.
     fn write_scatter_series(&mut self) {
         for (index, series) in self.series.clone().iter_mut().enumerate() {
+            // Only write the series for the current primary/secondary axes.
+            if series.secondary_axis != self.is_secondary_group {
+                continue;
+            }
+
             let max_points = series.value_range.number_of_points();
 
             self.writer.xml_start_tag_only("c:ser");
@@ -4541,6 +4799,8 @@ impl Chart {
 
     // Write the <c:errBars> element.
     fn write_error_bar(&mut self, axis: &str, error_bars: &ChartErrorBars) {
+        self.warnings.extend(&error_bars.warnings);
+
         self.writer.xml_start_tag_only("c:errBars");
 
         // Write the c:errDir element.
@@ -5004,6 +5264,8 @@ impl Chart {
 
     // Write font sub-elements shared between <a:defRPr> and <a:rPr> elements.
     fn write_font_elements(&mut self, tag: &str, font: &ChartFont) {
+        self.warnings.extend(&font.warnings);
+
         let mut attributes = vec![];
 
         if tag == "a:rPr" {
@@ -5091,6 +5353,7 @@ impl Chart {
             self.writer.xml_empty_tag_only("a:noFill");
         } else if let Some(solid_fill) = &format.solid_fill {
             // Write the a:solidFill element.
+            self.warnings.extend(&solid_fill.warnings);
             self.write_a_solid_fill(solid_fill.color, solid_fill.transparency);
         } else if let Some(pattern_fill) = &format.pattern_fill {
             // Write the a:pattFill element.
@@ -5112,6 +5375,8 @@ impl Chart {
 
     // Write the <a:ln> element.
     fn write_a_ln(&mut self, line: &ChartLine) {
+        self.warnings.extend(&line.warnings);
+
         let mut attributes = vec![];
 
         if let Some(width) = &line.width {
@@ -5171,6 +5436,8 @@ impl Chart {
 
     // Write the <a:pattFill> element.
     fn write_a_patt_fill(&mut self, fill: &ChartPatternFill) {
+        self.warnings.extend(&fill.warnings);
+
         let attributes = [("prst", fill.pattern.to_string())];
 
         self.writer.xml_start_tag("a:pattFill", &attributes);
@@ -5201,6 +5468,8 @@ impl Chart {
 
     // Write the <a:gradFill> element.
     fn write_gradient_fill(&mut self, fill: &ChartGradientFill) {
+        self.warnings.extend(&fill.warnings);
+
         let mut attributes = vec![];
 
         if fill.gradient_type != ChartGradientFillType::Linear {
@@ -5912,6 +6181,8 @@ pub struct ChartSeries {
     pub(crate) y_error_bars: Option<ChartErrorBars>,
     pub(crate) delete_from_legend: bool,
     pub(crate) smooth: Option<bool>,
+    pub(crate) secondary_axis: bool,
+    pub(crate) warnings: Warnings,
 }
 
 #[allow(clippy::new_without_default)]
@@ -6027,6 +6298,8 @@ impl ChartSeries {
             y_error_bars: None,
             delete_from_legend: false,
             smooth: None,
+            secondary_axis: false,
+            warnings: Warnings::new(),
         }
     }
 
@@ -7194,6 +7467,28 @@ impl ChartSeries {
         self
     }
 
+    /// Plot the series on the secondary axis.
+    ///
+    /// Plot a chart series on the secondary Y axis of the chart. This is
+    /// generally used in combined charts, see
+    /// [`chart.combine()`][Chart::combine], or for series that have a
+    /// different range of values to the other series in the chart.
+    ///
+    /// The secondary axes can be formatted via
+    /// [`chart.x2_axis()`][Chart::x2_axis] and
+    /// [`chart.y2_axis()`][Chart::y2_axis].
+    ///
+    /// Secondary axes aren't supported for Pie and Doughnut charts.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    ///
+    pub fn set_secondary_axis(&mut self, enable: bool) -> &mut ChartSeries {
+        self.secondary_axis = enable;
+        self
+    }
+
     /// Set line type charts to smooth for a series.
     ///
     /// Line and Scatter charts can have a linear or smoothed line connecting
@@ -7348,7 +7643,10 @@ impl ChartSeries {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self
+            .warnings
+            .check_color("set_invert_if_negative_color", color)
+        {
             self.invert_if_negative = true;
             self.inverted_color = color;
         }
@@ -7660,8 +7958,9 @@ impl ChartRange {
     pub(crate) fn validate(&self) -> Result<(), XlsxError> {
         let range = self.error_range();
 
-        let error_message = format!("Sheet name error for range: '{range}'");
-        utility::validate_sheetname(&self.sheet_name, &error_message)?;
+        utility::validate_sheetname(&self.sheet_name, || {
+            format!("Sheet name error for range: '{range}'")
+        })?;
 
         if self.first_row > self.last_row {
             return Err(XlsxError::ChartError(format!(
@@ -7879,6 +8178,10 @@ pub enum ChartType {
     /// A Stock chart showing Open-High-Low-Close data. It is also possible to
     /// show High-Low-Close data.
     ///
+    /// Stock charts have High-Low lines by default. These can be turned off
+    /// with [`Chart::set_high_low_lines()`]. Open-High-Low-Close charts also
+    /// usually have Up-Down bars, see [`Chart::set_up_down_bars()`].
+    ///
     /// Note, Volume variants of the Excel stock charts aren't currently
     /// supported but will be in a future release.
     ///
@@ -7978,6 +8281,60 @@ impl ChartTitle {
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/chart_title_set_name.png">
     ///
+    /// A chart example demonstrating setting the chart and axis titles from
+    /// worksheet cells and setting the title fonts.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_title_set_name2.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartFont, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data and titles for the chart.
+    /// #     worksheet.write(0, 0, "Quarterly results")?;
+    /// #     worksheet.write(0, 1, "Quarter")?;
+    /// #     worksheet.write(0, 2, "Sales")?;
+    /// #     worksheet.write_column(1, 1, ["Q1", "Q2", "Q3", "Q4"])?;
+    /// #     worksheet.write_column(1, 2, [50, 30, 40, 60])?;
+    /// #
+    ///     // Create a new chart.
+    ///     let mut chart = Chart::new(ChartType::Column);
+    ///
+    ///     // Add a data series using Excel formula syntax to describe the range.
+    ///     chart
+    ///         .add_series()
+    ///         .set_categories("Sheet1!$B$2:$B$5")
+    ///         .set_values("Sheet1!$C$2:$C$5");
+    ///
+    ///     // Set the chart title from a worksheet cell and set the font.
+    ///     chart
+    ///         .title()
+    ///         .set_name("Sheet1!$A$1")
+    ///         .set_font(ChartFont::new().set_name("Arial").set_size(16).set_color("#1F4E79"));
+    ///
+    ///     // Set the axis titles from worksheet cells and set the fonts.
+    ///     chart
+    ///         .x_axis()
+    ///         .set_name(("Sheet1", 0, 1))
+    ///         .set_name_font(ChartFont::new().set_italic());
+    ///
+    ///     chart
+    ///         .y_axis()
+    ///         .set_name(("Sheet1", 0, 2))
+    ///         .set_name_font(ChartFont::new().set_bold().set_rotation(-45));
+    ///
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 4, &chart)?;
+    ///
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
     pub fn set_name<T>(&mut self, name: T) -> &mut ChartTitle
     where
         T: IntoChartRange,
@@ -9675,6 +10032,7 @@ pub struct ChartAxis {
     pub(crate) display_units_visible: bool,
     pub(crate) crossing: ChartAxisCrossing,
     pub(crate) label_alignment: ChartAxisLabelAlignment,
+    pub(crate) warnings: Warnings,
 }
 
 impl ChartAxis {
@@ -9711,6 +10069,7 @@ impl ChartAxis {
             display_units_visible: false,
             crossing: ChartAxisCrossing::Automatic,
             label_alignment: ChartAxisLabelAlignment::Center,
+            warnings: Warnings::new(),
         }
     }
 
@@ -10568,10 +10927,14 @@ impl ChartAxis {
     {
         let value = value.into();
         if value < 0.0 {
-            eprintln!("Chart axis major unit '{value}' must be >= 0.0 in Excel");
+            self.warnings.warn(
+                "set_major_unit",
+                format!("Chart axis major unit '{value}' must be >= 0.0 in Excel"),
+            );
             return self;
         }
 
+        self.warnings.clear("set_major_unit");
         self.major_unit = value.to_string();
         self
     }
@@ -10591,10 +10954,14 @@ impl ChartAxis {
     {
         let value = value.into();
         if value < 0.0 {
-            eprintln!("Chart axis minor unit '{value}' must be >= 0.0 in Excel");
+            self.warnings.warn(
+                "set_minor_unit",
+                format!("Chart axis minor unit '{value}' must be >= 0.0 in Excel"),
+            );
             return self;
         }
 
+        self.warnings.clear("set_minor_unit");
         self.minor_unit = value.to_string();
         self
     }
@@ -12952,6 +13319,7 @@ pub struct ChartLine {
     transparency: u8,
     dash_type: ChartLineDashType,
     hidden: bool,
+    warnings: Warnings,
 }
 
 impl ChartLine {
@@ -12965,6 +13333,7 @@ impl ChartLine {
             transparency: 0,
             dash_type: ChartLineDashType::Solid,
             hidden: false,
+            warnings: Warnings::new(),
         }
     }
 
@@ -13024,7 +13393,7 @@ impl ChartLine {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_color", color) {
             self.color = color;
         }
 
@@ -13375,6 +13744,7 @@ pub type ChartBorder = ChartLine;
 pub struct ChartSolidFill {
     color: Color,
     transparency: u8,
+    warnings: Warnings,
 }
 
 impl ChartSolidFill {
@@ -13385,6 +13755,7 @@ impl ChartSolidFill {
         ChartSolidFill {
             color: Color::Default,
             transparency: 0,
+            warnings: Warnings::new(),
         }
     }
 
@@ -13444,7 +13815,7 @@ impl ChartSolidFill {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_color", color) {
             self.color = color;
         }
 
@@ -13590,6 +13961,7 @@ pub struct ChartPatternFill {
     background_color: Color,
     foreground_color: Color,
     pattern: ChartPatternFillType,
+    warnings: Warnings,
 }
 
 impl ChartPatternFill {
@@ -13601,6 +13973,7 @@ impl ChartPatternFill {
             background_color: Color::Default,
             foreground_color: Color::Default,
             pattern: ChartPatternFillType::Dotted5Percent,
+            warnings: Warnings::new(),
         }
     }
 
@@ -13734,7 +14107,7 @@ impl ChartPatternFill {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_background_color", color) {
             self.background_color = color;
         }
 
@@ -13755,7 +14128,7 @@ impl ChartPatternFill {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_foreground_color", color) {
             self.foreground_color = color;
         }
 
@@ -14209,6 +14582,7 @@ pub struct ChartFont {
     pub(crate) rotation: Option<i16>,
     pub(crate) has_baseline: bool,
     pub(crate) right_to_left: Option<bool>,
+    pub(crate) warnings: Warnings,
 }
 
 impl Default for ChartFont {
@@ -14235,6 +14609,7 @@ impl ChartFont {
             has_baseline: false,
             has_default_bold: false,
             right_to_left: None,
+            warnings: Warnings::new(),
         }
     }
 
@@ -14405,7 +14780,7 @@ impl ChartFont {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_color", color) {
             self.color = color;
         }
 
@@ -14600,8 +14975,14 @@ impl ChartFont {
     ///
     pub fn set_rotation(&mut self, rotation: i16) -> &mut ChartFont {
         match rotation {
-            270..=271 | -90..=90 => self.rotation = Some(rotation),
-            _ => eprintln!("Rotation '{rotation}' outside range: -90 <= angle <= 90."),
+            270..=271 | -90..=90 => {
+                self.rotation = Some(rotation);
+                self.warnings.clear("set_rotation");
+            }
+            _ => self.warnings.warn(
+                "set_rotation",
+                format!("Rotation '{rotation}' outside range: -90 <= angle <= 90."),
+            ),
         }
 
         self
@@ -15399,6 +15780,7 @@ pub struct ChartGradientFill {
     gradient_type: ChartGradientFillType,
     gradient_stops: Vec<ChartGradientStop>,
     angle: u16,
+    warnings: Warnings,
 }
 
 impl Default for ChartGradientFill {
@@ -15419,6 +15801,7 @@ impl ChartGradientFill {
             gradient_type: ChartGradientFillType::Linear,
             gradient_stops: vec![],
             angle: 90,
+            warnings: Warnings::new(),
         }
     }
 
@@ -15622,12 +16005,25 @@ impl ChartGradientFill {
             }
         }
 
-        if (2..=10).contains(&valid_gradient_stops.len()) {
-            self.gradient_stops = valid_gradient_stops;
+        if !(2..=10).contains(&valid_gradient_stops.len()) {
+            self.warnings.warn(
+                "set_gradient_stops",
+                "Gradient stops must contain between 2 and 10 valid entries.",
+            );
+            return self;
+        }
+
+        if valid_gradient_stops.len() < gradient_stops.len() {
+            self.warnings.warn(
+                "set_gradient_stops",
+                "Invalid gradient stops have been ignored.",
+            );
         } else {
-            eprintln!("Gradient stops must contain between 2 and 10 valid entries.");
+            self.warnings.clear("set_gradient_stops");
         }
 
+        self.gradient_stops = valid_gradient_stops;
+
         self
     }
 
@@ -15641,8 +16037,12 @@ impl ChartGradientFill {
     pub fn set_angle(&mut self, angle: u16) -> &mut ChartGradientFill {
         if (0..360).contains(&angle) {
             self.angle = angle;
+            self.warnings.clear("set_angle");
         } else {
-            eprintln!("Gradient angle '{angle}' must be in the Excel range 0 <= angle < 360");
+            self.warnings.warn(
+                "set_angle",
+                format!("Gradient angle '{angle}' must be in the Excel range 0 <= angle < 360"),
+            );
         }
         self
     }
@@ -15760,10 +16160,10 @@ impl ChartGradientStop {
         // Check and warn but don't raise error since this is too deeply nested.
         // It will be rechecked and rejected at use.
         if !color.is_valid() {
-            eprintln!("Gradient stop color isn't valid.");
+            log::warn!("Gradient stop color isn't valid.");
         }
         if !(0..=100).contains(&position) {
-            eprintln!("Gradient stop '{position}' outside Excel range: 0 <= position <= 100.");
+            log::warn!("Gradient stop '{position}' outside Excel range: 0 <= position <= 100.");
         }
 
         ChartGradientStop { color, position }
@@ -15877,6 +16277,7 @@ pub struct ChartErrorBars {
     format: ChartFormat,
     pub(crate) plus_range: ChartRange,
     pub(crate) minus_range: ChartRange,
+    warnings: Warnings,
 }
 
 impl Default for ChartErrorBars {
@@ -15896,6 +16297,7 @@ impl ChartErrorBars {
             format: ChartFormat::default(),
             plus_range: ChartRange::default(),
             minus_range: ChartRange::default(),
+            warnings: Warnings::new(),
         }
     }
 
@@ -15915,19 +16317,28 @@ impl ChartErrorBars {
         match &error_type {
             ChartErrorBarsType::FixedValue(value) => {
                 if *value <= 0.0 {
-                    eprintln!("Error bar Fixed Value '{value}' must be > 0.0 in Excel");
+                    self.warnings.warn(
+                        "set_type",
+                        format!("Error bar Fixed Value '{value}' must be > 0.0 in Excel"),
+                    );
                     return self;
                 }
             }
             ChartErrorBarsType::Percentage(value) => {
                 if *value < 0.0 {
-                    eprintln!("Error bar Percentage '{value}' must be >= 0.0 in Excel");
+                    self.warnings.warn(
+                        "set_type",
+                        format!("Error bar Percentage '{value}' must be >= 0.0 in Excel"),
+                    );
                     return self;
                 }
             }
             ChartErrorBarsType::StandardDeviation(value) => {
                 if *value < 0.0 {
-                    eprintln!("Error bar Standard Deviation '{value}' must be >= 0.0 in Excel");
+                    self.warnings.warn(
+                        "set_type",
+                        format!("Error bar Standard Deviation '{value}' must be >= 0.0 in Excel"),
+                    );
                     return self;
                 }
             }
@@ -15938,6 +16349,7 @@ impl ChartErrorBars {
             ChartErrorBarsType::StandardError => {}
         }
 
+        self.warnings.clear("set_type");
         self.error_type = error_type;
 
         self
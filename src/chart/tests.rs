@@ -277,6 +277,350 @@ mod chart_tests {
         assert_eq!(expected, got);
     }
 
+    #[test]
+    fn test_scatter_series() {
+        let mut range1 = ChartRange::new_from_range("Sheet1", 0, 0, 2, 0);
+        range1.set_cache(&["1", "2", "3"], ChartRangeCacheDataType::Number);
+
+        let mut range2 = ChartRange::new_from_range("Sheet1", 0, 1, 2, 1);
+        range2.set_cache(&["2", "4", "6"], ChartRangeCacheDataType::Number);
+
+        let mut series = ChartSeries::new();
+        series.set_categories(&range1).set_values(&range2);
+
+        let mut chart = Chart::new(ChartType::ScatterSmoothWithMarkers);
+        chart.push_series(&series);
+        chart.set_axis_ids(54010624, 45705856);
+
+        chart.write_scatter_chart();
+
+        let got = chart.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <c:scatterChart>
+                    <c:scatterStyle val="smoothMarker"/>
+                    <c:ser>
+                    <c:idx val="0"/>
+                    <c:order val="0"/>
+                    <c:xVal>
+                        <c:numRef>
+                        <c:f>Sheet1!$A$1:$A$3</c:f>
+                        <c:numCache>
+                            <c:formatCode>General</c:formatCode>
+                            <c:ptCount val="3"/>
+                            <c:pt idx="0">
+                            <c:v>1</c:v>
+                            </c:pt>
+                            <c:pt idx="1">
+                            <c:v>2</c:v>
+                            </c:pt>
+                            <c:pt idx="2">
+                            <c:v>3</c:v>
+                            </c:pt>
+                        </c:numCache>
+                        </c:numRef>
+                    </c:xVal>
+                    <c:yVal>
+                        <c:numRef>
+                        <c:f>Sheet1!$B$1:$B$3</c:f>
+                        <c:numCache>
+                            <c:formatCode>General</c:formatCode>
+                            <c:ptCount val="3"/>
+                            <c:pt idx="0">
+                            <c:v>2</c:v>
+                            </c:pt>
+                            <c:pt idx="1">
+                            <c:v>4</c:v>
+                            </c:pt>
+                            <c:pt idx="2">
+                            <c:v>6</c:v>
+                            </c:pt>
+                        </c:numCache>
+                        </c:numRef>
+                    </c:yVal>
+                    <c:smooth val="1"/>
+                    </c:ser>
+                    <c:axId val="54010624"/>
+                    <c:axId val="45705856"/>
+                </c:scatterChart>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_bar_overlap_and_gap() {
+        let mut range = ChartRange::new_from_range("Sheet1", 0, 0, 2, 0);
+        range.set_cache(&["1", "2", "3"], ChartRangeCacheDataType::Number);
+
+        // Stacked charts have a default overlap of 100.
+        let chart_types = [
+            ChartType::BarStacked,
+            ChartType::BarPercentStacked,
+            ChartType::ColumnStacked,
+            ChartType::ColumnPercentStacked,
+        ];
+
+        for chart_type in chart_types {
+            let mut series = ChartSeries::new();
+            series.set_values(&range);
+
+            let mut chart = Chart::new(chart_type);
+            chart.push_series(&series);
+            chart.set_axis_ids(1, 2);
+            chart.assemble_xml_file();
+
+            let got = chart.writer.read_to_str();
+            assert!(got.contains(r#"<c:overlap val="100"/>"#));
+            assert!(!got.contains("<c:gapWidth"));
+        }
+
+        // The series overlap and gap apply to clustered and stacked charts.
+        let chart_types = [
+            ChartType::Bar,
+            ChartType::BarStacked,
+            ChartType::Column,
+            ChartType::ColumnPercentStacked,
+        ];
+
+        for chart_type in chart_types {
+            let mut series = ChartSeries::new();
+            series.set_values(&range).set_overlap(-20).set_gap(50);
+
+            let mut chart = Chart::new(chart_type);
+            chart.push_series(&series);
+            chart.set_axis_ids(1, 2);
+            chart.assemble_xml_file();
+
+            let got = chart.writer.read_to_str();
+            assert!(got.contains(r#"<c:gapWidth val="50"/><c:overlap val="-20"/>"#));
+        }
+    }
+
+    #[test]
+    fn test_area_and_radar_types() {
+        let mut range = ChartRange::new_from_range("Sheet1", 0, 0, 2, 0);
+        range.set_cache(&["1", "2", "3"], ChartRangeCacheDataType::Number);
+
+        let data = [
+            (
+                ChartType::Area,
+                r#"<c:areaChart><c:grouping val="standard"/>"#,
+            ),
+            (
+                ChartType::AreaStacked,
+                r#"<c:areaChart><c:grouping val="stacked"/>"#,
+            ),
+            (
+                ChartType::AreaPercentStacked,
+                r#"<c:areaChart><c:grouping val="percentStacked"/>"#,
+            ),
+            (
+                ChartType::Radar,
+                r#"<c:radarChart><c:radarStyle val="marker"/>"#,
+            ),
+            (
+                ChartType::RadarWithMarkers,
+                r#"<c:radarChart><c:radarStyle val="marker"/>"#,
+            ),
+            (
+                ChartType::RadarFilled,
+                r#"<c:radarChart><c:radarStyle val="filled"/>"#,
+            ),
+        ];
+
+        for (chart_type, expected) in data {
+            let mut chart = Chart::new(chart_type);
+            chart.add_series().set_values(&range);
+            chart.set_axis_ids(1, 2);
+            chart.assemble_xml_file();
+
+            let got = chart.writer.read_to_str();
+            assert!(got.contains(expected), "{expected}");
+
+            // Only the plain Radar chart turns off the default markers.
+            let has_no_markers = got.contains(r#"<c:marker><c:symbol val="none"/></c:marker>"#);
+            assert_eq!(chart_type == ChartType::Radar, has_no_markers);
+        }
+    }
+
+    #[test]
+    fn test_stock_lines_and_bars() {
+        let mut range = ChartRange::new_from_range("Sheet1", 0, 0, 2, 0);
+        range.set_cache(&["1", "2", "3"], ChartRangeCacheDataType::Number);
+
+        // Stock charts have high-low lines by default.
+        let mut chart = Chart::new(ChartType::Stock);
+        chart.add_series().set_values(&range);
+        chart.set_axis_ids(1, 2);
+        chart.assemble_xml_file();
+
+        let got = chart.writer.read_to_str();
+        assert!(got.contains("<c:hiLowLines/>"));
+        assert!(!got.contains("<c:upDownBars>"));
+
+        // Open-High-Low-Close charts with up-down bars.
+        let mut chart = Chart::new(ChartType::Stock);
+        chart.add_series().set_values(&range);
+        chart.set_up_down_bars(true);
+        chart.set_axis_ids(1, 2);
+        chart.assemble_xml_file();
+
+        let got = chart.writer.read_to_str();
+        assert!(got.contains("<c:hiLowLines/><c:upDownBars>"));
+
+        // High-low lines can be turned off.
+        let mut chart = Chart::new(ChartType::Stock);
+        chart.add_series().set_values(&range);
+        chart.set_high_low_lines(false);
+        chart.set_axis_ids(1, 2);
+        chart.assemble_xml_file();
+
+        let got = chart.writer.read_to_str();
+        assert!(!got.contains("<c:hiLowLines"));
+    }
+
+    #[test]
+    fn test_secondary_axis() {
+        let mut range = ChartRange::new_from_range("Sheet1", 0, 0, 2, 0);
+        range.set_cache(&["1", "2", "3"], ChartRangeCacheDataType::Number);
+
+        let mut chart = Chart::new(ChartType::Line);
+        chart.add_series().set_values(&range);
+        chart
+            .add_series()
+            .set_values(&range)
+            .set_secondary_axis(true);
+        chart.set_axis_ids(1, 2);
+        chart.set_axis2_ids(3, 4);
+        chart.assemble_xml_file();
+
+        let got = chart.writer.read_to_str();
+
+        // The series are written in separate chart groups with their own axis
+        // ids.
+        assert_eq!(2, got.matches("<c:lineChart>").count());
+        assert!(got.contains(
+            r#"<c:idx val="1"/><c:order val="1"/><c:marker><c:symbol val="none"/></c:marker>"#
+        ));
+        assert!(got.contains(r#"<c:axId val="3"/><c:axId val="4"/></c:lineChart>"#));
+
+        // The secondary value axis is written before the hidden secondary
+        // category axis.
+        assert!(got.contains(
+            r#"<c:valAx><c:axId val="4"/><c:scaling><c:orientation val="minMax"/></c:scaling><c:axPos val="r"/>"#
+        ));
+        assert!(got.contains(r#"<c:crossAx val="3"/><c:crosses val="max"/>"#));
+        assert!(got.contains(
+            r#"<c:catAx><c:axId val="3"/><c:scaling><c:orientation val="minMax"/></c:scaling><c:delete val="1"/><c:axPos val="b"/>"#
+        ));
+
+        // Secondary axes aren't supported for Pie charts.
+        let mut chart = Chart::new(ChartType::Pie);
+        chart
+            .add_series()
+            .set_values(&range)
+            .set_secondary_axis(true);
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+    }
+
+    #[test]
+    fn test_stock_without_high_low_lines() {
+        let mut range = ChartRange::new_from_range("Sheet1", 0, 0, 2, 0);
+        range.set_cache(&["1", "2", "3"], ChartRangeCacheDataType::Number);
+
+        // Turning off the high-low lines restores the previous default output.
+        let mut chart = Chart::new(ChartType::Stock);
+        chart.add_series().set_values(&range);
+        chart.set_high_low_lines(false);
+        chart.set_axis_ids(1, 2);
+        chart.assemble_xml_file();
+
+        let got = chart.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+                <c:lang val="en-US"/>
+                <c:chart>
+                    <c:plotArea>
+                    <c:layout/>
+                    <c:stockChart>
+                        <c:ser>
+                        <c:idx val="0"/>
+                        <c:order val="0"/>
+                        <c:val>
+                            <c:numRef>
+                            <c:f>Sheet1!$A$1:$A$3</c:f>
+                            <c:numCache>
+                                <c:formatCode>General</c:formatCode>
+                                <c:ptCount val="3"/>
+                                <c:pt idx="0">
+                                <c:v>1</c:v>
+                                </c:pt>
+                                <c:pt idx="1">
+                                <c:v>2</c:v>
+                                </c:pt>
+                                <c:pt idx="2">
+                                <c:v>3</c:v>
+                                </c:pt>
+                            </c:numCache>
+                            </c:numRef>
+                        </c:val>
+                        </c:ser>
+                        <c:axId val="1"/>
+                        <c:axId val="2"/>
+                    </c:stockChart>
+                    <c:dateAx>
+                        <c:axId val="1"/>
+                        <c:scaling>
+                        <c:orientation val="minMax"/>
+                        </c:scaling>
+                        <c:axPos val="b"/>
+                        <c:tickLblPos val="nextTo"/>
+                        <c:crossAx val="2"/>
+                        <c:crosses val="autoZero"/>
+                        <c:auto val="1"/>
+                        <c:lblOffset val="100"/>
+                    </c:dateAx>
+                    <c:valAx>
+                        <c:axId val="2"/>
+                        <c:scaling>
+                        <c:orientation val="minMax"/>
+                        </c:scaling>
+                        <c:axPos val="l"/>
+                        <c:majorGridlines/>
+                        <c:numFmt formatCode="General" sourceLinked="1"/>
+                        <c:tickLblPos val="nextTo"/>
+                        <c:crossAx val="1"/>
+                        <c:crosses val="autoZero"/>
+                        <c:crossBetween val="between"/>
+                    </c:valAx>
+                    </c:plotArea>
+                    <c:legend>
+                    <c:legendPos val="r"/>
+                    <c:layout/>
+                    </c:legend>
+                    <c:plotVisOnly val="1"/>
+                </c:chart>
+                <c:printSettings>
+                    <c:headerFooter/>
+                    <c:pageMargins b="0.75" l="0.7" r="0.7" t="0.75" header="0.3" footer="0.3"/>
+                    <c:pageSetup/>
+                </c:printSettings>
+                </c:chartSpace>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
     #[test]
     fn test_range_from_string() {
         let range_string = "=Sheet1!$A$1:$A$5";
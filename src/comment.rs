@@ -0,0 +1,167 @@
+// comment - A module for creating the Excel comments.xml file.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+mod tests;
+
+use std::collections::HashMap;
+
+use crate::{utility, xmlwriter::XMLWriter, Note};
+
+pub struct Comment {
+    pub(crate) writer: XMLWriter,
+    pub(crate) notes: Vec<Note>,
+    author_ids: HashMap<String, usize>,
+}
+
+impl Comment {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new Comment struct.
+    pub(crate) fn new() -> Comment {
+        let writer = XMLWriter::new();
+
+        Comment {
+            writer,
+            notes: vec![],
+            author_ids: HashMap::new(),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    //  Assemble and write the XML file.
+    pub(crate) fn assemble_xml_file(&mut self) {
+        self.writer.xml_declaration();
+
+        // Write the comments element.
+        self.write_comments();
+
+        // Write the authors element.
+        self.write_authors();
+
+        // Write the commentList element.
+        self.write_comment_list();
+
+        // Close the comments tag.
+        self.writer.xml_end_tag("comments");
+    }
+
+    // Write the <comments> element.
+    fn write_comments(&mut self) {
+        let attributes = [(
+            "xmlns",
+            "http://schemas.openxmlformats.org/spreadsheetml/2006/main",
+        )];
+
+        self.writer.xml_start_tag("comments", &attributes);
+    }
+
+    // Write the <authors> element.
+    fn write_authors(&mut self) {
+        self.writer.xml_start_tag_only("authors");
+
+        for note in &self.notes {
+            let author = note.author.clone().unwrap_or_default();
+
+            if !self.author_ids.contains_key(&author) {
+                self.author_ids
+                    .insert(author.clone(), self.author_ids.len());
+
+                // Write the author element.
+                self.writer.xml_data_element_only("author", &author);
+            }
+        }
+
+        self.writer.xml_end_tag("authors");
+    }
+
+    // Write the <commentList> element.
+    fn write_comment_list(&mut self) {
+        self.writer.xml_start_tag_only("commentList");
+
+        for note in &self.notes.clone() {
+            // Write the comment element.
+            self.write_comment(note);
+        }
+
+        self.writer.xml_end_tag("commentList");
+    }
+
+    // Write the <comment> element.
+    fn write_comment(&mut self, note: &Note) {
+        let author = note.author.clone().unwrap_or_default();
+        let author_id = self.author_ids.get(&author).copied().unwrap_or_default();
+
+        let attributes = [
+            (
+                "ref",
+                utility::row_col_to_cell(note.cell_row, note.cell_col),
+            ),
+            ("authorId", author_id.to_string()),
+        ];
+
+        self.writer.xml_start_tag("comment", &attributes);
+
+        // Write the text element.
+        self.write_text(note);
+
+        self.writer.xml_end_tag("comment");
+    }
+
+    // Write the <text> element.
+    fn write_text(&mut self, note: &Note) {
+        self.writer.xml_start_tag_only("text");
+
+        match note.author_prefix() {
+            Some(author) => {
+                // Write the author name as a bold prefix to the note text.
+                self.write_text_run(&format!("{author}:"), true);
+                self.write_text_run(&format!("\n{}", note.text), false);
+            }
+            None => self.write_text_run(&note.text, false),
+        }
+
+        self.writer.xml_end_tag("text");
+    }
+
+    // Write the <r> element.
+    fn write_text_run(&mut self, text: &str, is_bold: bool) {
+        self.writer.xml_start_tag_only("r");
+
+        // Write the rPr element.
+        self.write_run_properties(is_bold);
+
+        // Write the t element.
+        if text.starts_with(['\t', '\n', ' ']) || text.ends_with(['\t', '\n', ' ']) {
+            let attributes = [("xml:space", "preserve")];
+            self.writer.xml_data_element("t", text, &attributes);
+        } else {
+            self.writer.xml_data_element_only("t", text);
+        }
+
+        self.writer.xml_end_tag("r");
+    }
+
+    // Write the <rPr> element.
+    fn write_run_properties(&mut self, is_bold: bool) {
+        self.writer.xml_start_tag_only("rPr");
+
+        if is_bold {
+            self.writer.xml_empty_tag_only("b");
+        }
+
+        self.writer.xml_empty_tag("sz", &[("val", "8")]);
+        self.writer.xml_empty_tag("color", &[("indexed", "81")]);
+        self.writer.xml_empty_tag("rFont", &[("val", "Tahoma")]);
+        self.writer.xml_empty_tag("family", &[("val", "2")]);
+
+        self.writer.xml_end_tag("rPr");
+    }
+}
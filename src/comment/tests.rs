@@ -0,0 +1,134 @@
+// Comment unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod comment_tests {
+
+    use crate::comment::Comment;
+    use crate::test_functions::xml_to_vec;
+    use crate::Note;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_assemble1() {
+        let mut comment = Comment::new();
+
+        let mut note = Note::new("Some text");
+        note.cell_row = 1;
+        note.cell_col = 1;
+
+        comment.notes.push(note);
+
+        comment.assemble_xml_file();
+
+        let got = comment.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <comments xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <authors>
+                <author></author>
+              </authors>
+              <commentList>
+                <comment ref="B2" authorId="0">
+                  <text>
+                    <r>
+                      <rPr>
+                        <sz val="8"/>
+                        <color indexed="81"/>
+                        <rFont val="Tahoma"/>
+                        <family val="2"/>
+                      </rPr>
+                      <t>Some text</t>
+                    </r>
+                  </text>
+                </comment>
+              </commentList>
+            </comments>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_with_authors() {
+        let mut comment = Comment::new();
+
+        let mut note1 = Note::new("Some text").set_author("John");
+        note1.cell_row = 0;
+        note1.cell_col = 0;
+
+        let mut note2 = Note::new("Other text")
+            .set_author("Ferris")
+            .add_author_prefix(false);
+        note2.cell_row = 2;
+        note2.cell_col = 0;
+
+        comment.notes.push(note1);
+        comment.notes.push(note2);
+
+        comment.assemble_xml_file();
+
+        let got = comment.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(&format!(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <comments xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <authors>
+                <author>John</author>
+                <author>Ferris</author>
+              </authors>
+              <commentList>
+                <comment ref="A1" authorId="0">
+                  <text>
+                    <r>
+                      <rPr>
+                        <b/>
+                        <sz val="8"/>
+                        <color indexed="81"/>
+                        <rFont val="Tahoma"/>
+                        <family val="2"/>
+                      </rPr>
+                      <t>John:</t>
+                    </r>
+                    <r>
+                      <rPr>
+                        <sz val="8"/>
+                        <color indexed="81"/>
+                        <rFont val="Tahoma"/>
+                        <family val="2"/>
+                      </rPr>
+                      <t xml:space="preserve">{}Some text</t>
+                    </r>
+                  </text>
+                </comment>
+                <comment ref="A3" authorId="1">
+                  <text>
+                    <r>
+                      <rPr>
+                        <sz val="8"/>
+                        <color indexed="81"/>
+                        <rFont val="Tahoma"/>
+                        <family val="2"/>
+                      </rPr>
+                      <t>Other text</t>
+                    </r>
+                  </text>
+                </comment>
+              </commentList>
+            </comments>
+            "#,
+            "\n"
+        ));
+
+        assert_eq!(expected, got);
+    }
+}
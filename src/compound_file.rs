@@ -0,0 +1,425 @@
+// compound_file - A module for writing OLE Compound File Binary containers.
+//
+// The Compound File Binary (CFB) format is a "file system within a file"
+// that is used by Excel to store encrypted xlsx files. This module implements
+// a simple writer for version 3 (512 byte sector) files as described in the
+// [MS-CFB] specification. It only supports the features required to store
+// encrypted files: streams, nested storages and files larger than the 109 FAT
+// sectors that fit in the header.
+//
+// [MS-CFB]: https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-cfb
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+mod tests;
+
+use std::cmp::Ordering;
+
+// Sector sizes and limits for a version 3 compound file.
+const SECTOR_SIZE: usize = 512;
+const MINI_SECTOR_SIZE: usize = 64;
+const MINI_STREAM_CUTOFF: usize = 4096;
+const DIRECTORY_ENTRY_SIZE: usize = 128;
+const HEADER_DIFAT_ENTRIES: usize = 109;
+const IDS_PER_SECTOR: usize = SECTOR_SIZE / 4;
+
+// Special sector and directory ids.
+const DIFAT_SECTOR: u32 = 0xFFFF_FFFC;
+const FAT_SECTOR: u32 = 0xFFFF_FFFD;
+const END_OF_CHAIN: u32 = 0xFFFF_FFFE;
+const FREE_SECTOR: u32 = 0xFFFF_FFFF;
+const NO_STREAM: u32 = 0xFFFF_FFFF;
+
+pub(crate) struct CompoundFile {
+    entries: Vec<DirectoryEntry>,
+}
+
+impl CompoundFile {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new CompoundFile struct with an empty root storage.
+    pub(crate) fn new() -> CompoundFile {
+        CompoundFile {
+            entries: vec![DirectoryEntry::new("Root Entry", EntryType::Root)],
+        }
+    }
+
+    // Add a stream to the compound file. The path uses "/" to separate any
+    // parent storages, which are created as required.
+    pub(crate) fn add_stream(&mut self, path: &str, data: Vec<u8>) {
+        let mut parent = 0;
+        let mut names: Vec<&str> = path.split('/').collect();
+        let stream_name = names.pop().unwrap_or_default();
+
+        for name in names {
+            parent = match self.find_child(parent, name) {
+                Some(index) => index,
+                None => self.add_entry(parent, DirectoryEntry::new(name, EntryType::Storage)),
+            };
+        }
+
+        let mut stream = DirectoryEntry::new(stream_name, EntryType::Stream);
+        stream.data = data;
+        self.add_entry(parent, stream);
+    }
+
+    // Assemble the compound file into a byte vector.
+    pub(crate) fn assemble_file(&mut self) -> Vec<u8> {
+        // Streams smaller than the cutoff are stored in 64 byte sectors in the
+        // mini stream. Larger streams are stored directly in 512 byte sectors.
+        let mut mini_stream = vec![];
+        let mut mini_fat = vec![];
+        let mut fat = vec![];
+
+        for entry in &mut self.entries {
+            if entry.entry_type != EntryType::Stream
+                || entry.data.is_empty()
+                || entry.data.len() >= MINI_STREAM_CUTOFF
+            {
+                continue;
+            }
+
+            entry.start_sector = Self::add_chain(&mut mini_fat, entry.data.len(), MINI_SECTOR_SIZE);
+            mini_stream.extend_from_slice(&entry.data);
+            Self::pad_to_size(&mut mini_stream, MINI_SECTOR_SIZE);
+        }
+
+        for entry in &mut self.entries {
+            if entry.entry_type == EntryType::Stream && entry.data.len() >= MINI_STREAM_CUTOFF {
+                entry.start_sector = Self::add_chain(&mut fat, entry.data.len(), SECTOR_SIZE);
+            }
+        }
+
+        // The mini stream is stored as the data of the root entry.
+        if !mini_stream.is_empty() {
+            self.entries[0].start_sector =
+                Self::add_chain(&mut fat, mini_stream.len(), SECTOR_SIZE);
+            self.entries[0].stream_size = mini_stream.len();
+        }
+
+        let mini_fat_start = if mini_fat.is_empty() {
+            END_OF_CHAIN
+        } else {
+            Self::add_chain(&mut fat, mini_fat.len() * 4, SECTOR_SIZE)
+        };
+        let num_mini_fat_sectors = (mini_fat.len() * 4).div_ceil(SECTOR_SIZE);
+
+        let directory_size = self.entries.len() * DIRECTORY_ENTRY_SIZE;
+        let directory_start = Self::add_chain(&mut fat, directory_size, SECTOR_SIZE);
+
+        // The FAT has to include the sectors used to store the FAT itself and
+        // any DIFAT sectors needed to locate FAT sectors beyond the first 109.
+        let mut num_fat_sectors = 0;
+        let mut num_difat_sectors = 0;
+        loop {
+            let num_sectors = fat.len() + num_fat_sectors + num_difat_sectors;
+            let fat_sectors_needed = num_sectors.div_ceil(IDS_PER_SECTOR);
+            let difat_sectors_needed = fat_sectors_needed
+                .saturating_sub(HEADER_DIFAT_ENTRIES)
+                .div_ceil(IDS_PER_SECTOR - 1);
+
+            if fat_sectors_needed == num_fat_sectors && difat_sectors_needed == num_difat_sectors {
+                break;
+            }
+
+            num_fat_sectors = fat_sectors_needed;
+            num_difat_sectors = difat_sectors_needed;
+        }
+
+        let fat_start = fat.len();
+        fat.extend(std::iter::repeat_n(FAT_SECTOR, num_fat_sectors));
+
+        let difat_start = fat.len();
+        fat.extend(std::iter::repeat_n(DIFAT_SECTOR, num_difat_sectors));
+
+        fat.resize(num_fat_sectors * IDS_PER_SECTOR, FREE_SECTOR);
+
+        let fat_sector_ids: Vec<u32> = (fat_start..fat_start + num_fat_sectors)
+            .map(Self::sector_id)
+            .collect();
+
+        // Write the file header.
+        let mut file = Vec::with_capacity((fat.len() + 1) * SECTOR_SIZE);
+        let header = Header {
+            num_fat_sectors,
+            directory_start,
+            mini_fat_start,
+            num_mini_fat_sectors,
+            difat_start,
+            num_difat_sectors,
+        };
+        Self::write_header(&mut file, &header, &fat_sector_ids);
+
+        // Write the sector data in the same order that the FAT chains were
+        // allocated above.
+        for entry in &self.entries {
+            if entry.entry_type == EntryType::Stream && entry.data.len() >= MINI_STREAM_CUTOFF {
+                file.extend_from_slice(&entry.data);
+                Self::pad_to_size(&mut file, SECTOR_SIZE);
+            }
+        }
+
+        file.extend_from_slice(&mini_stream);
+        Self::pad_to_size(&mut file, SECTOR_SIZE);
+
+        for sector_id in &mini_fat {
+            file.extend_from_slice(&sector_id.to_le_bytes());
+        }
+        Self::pad_to_size(&mut file, SECTOR_SIZE);
+
+        self.write_directory(&mut file);
+
+        for sector_id in &fat {
+            file.extend_from_slice(&sector_id.to_le_bytes());
+        }
+
+        // Write any DIFAT sectors.
+        Self::write_difat(&mut file, &header, &fat_sector_ids);
+
+        file
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal functions/methods.
+    // -----------------------------------------------------------------------
+
+    // Write the 512 byte file header.
+    fn write_header(file: &mut Vec<u8>, header: &Header, fat_sector_ids: &[u32]) {
+        file.extend_from_slice(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]);
+        file.extend_from_slice(&[0; 16]); // Header CLSID.
+        file.extend_from_slice(&0x003E_u16.to_le_bytes()); // Minor version.
+        file.extend_from_slice(&0x0003_u16.to_le_bytes()); // Major version.
+        file.extend_from_slice(&0xFFFE_u16.to_le_bytes()); // Byte order.
+        file.extend_from_slice(&0x0009_u16.to_le_bytes()); // Sector shift.
+        file.extend_from_slice(&0x0006_u16.to_le_bytes()); // Mini sector shift.
+        file.extend_from_slice(&[0; 6]); // Reserved.
+        file.extend_from_slice(&0_u32.to_le_bytes()); // Directory sectors.
+        file.extend_from_slice(&Self::sector_id(header.num_fat_sectors).to_le_bytes());
+        file.extend_from_slice(&header.directory_start.to_le_bytes());
+        file.extend_from_slice(&0_u32.to_le_bytes()); // Transaction signature.
+        file.extend_from_slice(&Self::sector_id(MINI_STREAM_CUTOFF).to_le_bytes());
+        file.extend_from_slice(&header.mini_fat_start.to_le_bytes());
+        file.extend_from_slice(&Self::sector_id(header.num_mini_fat_sectors).to_le_bytes());
+
+        if header.num_difat_sectors == 0 {
+            file.extend_from_slice(&END_OF_CHAIN.to_le_bytes());
+        } else {
+            file.extend_from_slice(&Self::sector_id(header.difat_start).to_le_bytes());
+        }
+        file.extend_from_slice(&Self::sector_id(header.num_difat_sectors).to_le_bytes());
+
+        // The header holds the ids of the first 109 FAT sectors.
+        for index in 0..HEADER_DIFAT_ENTRIES {
+            let sector_id = fat_sector_ids.get(index).unwrap_or(&FREE_SECTOR);
+            file.extend_from_slice(&sector_id.to_le_bytes());
+        }
+    }
+
+    // Write the DIFAT sectors. Each one holds the ids of 127 FAT sectors and
+    // a pointer to the next DIFAT sector.
+    fn write_difat(file: &mut Vec<u8>, header: &Header, fat_sector_ids: &[u32]) {
+        let overflow_ids = fat_sector_ids
+            .get(HEADER_DIFAT_ENTRIES..)
+            .unwrap_or_default();
+
+        for (index, chunk) in overflow_ids.chunks(IDS_PER_SECTOR - 1).enumerate() {
+            for sector_id in chunk {
+                file.extend_from_slice(&sector_id.to_le_bytes());
+            }
+            for _ in chunk.len()..IDS_PER_SECTOR - 1 {
+                file.extend_from_slice(&FREE_SECTOR.to_le_bytes());
+            }
+
+            if index + 1 == header.num_difat_sectors {
+                file.extend_from_slice(&END_OF_CHAIN.to_le_bytes());
+            } else {
+                let next_sector = header.difat_start + index + 1;
+                file.extend_from_slice(&Self::sector_id(next_sector).to_le_bytes());
+            }
+        }
+    }
+
+    // Add a directory entry as a child of a storage and return its index.
+    fn add_entry(&mut self, parent: usize, entry: DirectoryEntry) -> usize {
+        let index = self.entries.len();
+        self.entries.push(entry);
+        self.entries[parent].children.push(index);
+        index
+    }
+
+    // Find a named child entry of a storage.
+    fn find_child(&self, parent: usize, name: &str) -> Option<usize> {
+        self.entries[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&index| self.entries[index].name == name)
+    }
+
+    // Add a chain of sectors to a FAT or mini FAT and return the first sector.
+    fn add_chain(fat: &mut Vec<u32>, data_size: usize, sector_size: usize) -> u32 {
+        let start = fat.len();
+        let num_sectors = data_size.div_ceil(sector_size);
+
+        for sector in start + 1..start + num_sectors {
+            fat.push(Self::sector_id(sector));
+        }
+        fat.push(END_OF_CHAIN);
+
+        Self::sector_id(start)
+    }
+
+    // Pad a buffer with zeros to a multiple of the sector size.
+    fn pad_to_size(data: &mut Vec<u8>, sector_size: usize) {
+        let padded_size = data.len().div_ceil(sector_size) * sector_size;
+        data.resize(padded_size, 0);
+    }
+
+    // Convert a sector index or count to the 32 bit value stored in the file.
+    fn sector_id(index: usize) -> u32 {
+        u32::try_from(index).unwrap_or(END_OF_CHAIN)
+    }
+
+    // Write the directory entries. The children of each storage are stored as
+    // a binary search tree ordered by name length and then by the uppercase
+    // name. All of the nodes are black which is valid for a balanced tree.
+    fn write_directory(&mut self, file: &mut Vec<u8>) {
+        for parent in 0..self.entries.len() {
+            let mut children = self.entries[parent].children.clone();
+            children.sort_by(|&a, &b| self.entries[a].compare_name(&self.entries[b]));
+            self.entries[parent].child_id = self.build_tree(&children);
+        }
+
+        for entry in &self.entries {
+            entry.write_to(file);
+        }
+
+        // Fill any remaining space in the last sector with unused entries.
+        let entries_per_sector = SECTOR_SIZE / DIRECTORY_ENTRY_SIZE;
+        let num_unused =
+            self.entries.len().next_multiple_of(entries_per_sector) - self.entries.len();
+        for _ in 0..num_unused {
+            let mut unused = DirectoryEntry::new("", EntryType::Unused);
+            unused.start_sector = 0;
+            unused.write_to(file);
+        }
+    }
+
+    // Build a balanced binary tree from a sorted list of sibling entries and
+    // return the id of the root node.
+    fn build_tree(&mut self, siblings: &[usize]) -> u32 {
+        if siblings.is_empty() {
+            return NO_STREAM;
+        }
+
+        let middle = siblings.len() / 2;
+        let left_id = self.build_tree(&siblings[..middle]);
+        let right_id = self.build_tree(&siblings[middle + 1..]);
+
+        let entry = &mut self.entries[siblings[middle]];
+        entry.left_id = left_id;
+        entry.right_id = right_id;
+
+        Self::sector_id(siblings[middle])
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helper enums/structs/functions.
+// -----------------------------------------------------------------------
+
+// The sector locations and counts stored in the file header.
+struct Header {
+    num_fat_sectors: usize,
+    directory_start: u32,
+    mini_fat_start: u32,
+    num_mini_fat_sectors: usize,
+    difat_start: usize,
+    num_difat_sectors: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EntryType {
+    Unused = 0,
+    Storage = 1,
+    Stream = 2,
+    Root = 5,
+}
+
+struct DirectoryEntry {
+    name: String,
+    entry_type: EntryType,
+    data: Vec<u8>,
+    children: Vec<usize>,
+    left_id: u32,
+    right_id: u32,
+    child_id: u32,
+    start_sector: u32,
+    stream_size: usize,
+}
+
+impl DirectoryEntry {
+    fn new(name: &str, entry_type: EntryType) -> DirectoryEntry {
+        DirectoryEntry {
+            name: name.to_string(),
+            entry_type,
+            data: vec![],
+            children: vec![],
+            left_id: NO_STREAM,
+            right_id: NO_STREAM,
+            child_id: NO_STREAM,
+            start_sector: match entry_type {
+                EntryType::Stream | EntryType::Root => END_OF_CHAIN,
+                _ => 0,
+            },
+            stream_size: 0,
+        }
+    }
+
+    // Compare entry names in the order required for the sibling tree.
+    fn compare_name(&self, other: &DirectoryEntry) -> Ordering {
+        let name1: Vec<u16> = self.name.to_uppercase().encode_utf16().collect();
+        let name2: Vec<u16> = other.name.to_uppercase().encode_utf16().collect();
+
+        name1.len().cmp(&name2.len()).then(name1.cmp(&name2))
+    }
+
+    // Write the 128 byte directory entry structure.
+    fn write_to(&self, file: &mut Vec<u8>) {
+        // The name is stored as null terminated UTF-16LE with a maximum length
+        // of 31 characters.
+        let name: Vec<u16> = self.name.encode_utf16().take(31).collect();
+        let mut name_buffer = [0u8; 64];
+        for (index, code_unit) in name.iter().enumerate() {
+            name_buffer[index * 2..index * 2 + 2].copy_from_slice(&code_unit.to_le_bytes());
+        }
+        let name_size = if name.is_empty() {
+            0
+        } else {
+            (name.len() as u16 + 1) * 2
+        };
+
+        let stream_size = match self.entry_type {
+            EntryType::Stream => self.data.len(),
+            _ => self.stream_size,
+        };
+
+        file.extend_from_slice(&name_buffer);
+        file.extend_from_slice(&name_size.to_le_bytes());
+        file.push(self.entry_type as u8);
+        file.push(1); // Black node.
+        file.extend_from_slice(&self.left_id.to_le_bytes());
+        file.extend_from_slice(&self.right_id.to_le_bytes());
+        file.extend_from_slice(&self.child_id.to_le_bytes());
+        file.extend_from_slice(&[0; 16]); // CLSID.
+        file.extend_from_slice(&0_u32.to_le_bytes()); // State bits.
+        file.extend_from_slice(&0_u64.to_le_bytes()); // Creation time.
+        file.extend_from_slice(&0_u64.to_le_bytes()); // Modified time.
+        file.extend_from_slice(&self.start_sector.to_le_bytes());
+        file.extend_from_slice(&(stream_size as u64).to_le_bytes());
+    }
+}
@@ -0,0 +1,202 @@
+// Compound file unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod compound_file_tests {
+
+    use crate::compound_file::CompoundFile;
+    use pretty_assertions::assert_eq;
+
+    fn read_u32(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn sector(data: &[u8], index: u32) -> &[u8] {
+        let offset = 512 + index as usize * 512;
+        &data[offset..offset + 512]
+    }
+
+    // Follow a sector chain in a FAT or mini FAT.
+    fn read_chain(fat: &[u32], start: u32) -> Vec<u32> {
+        let mut chain = vec![];
+        let mut index = start;
+        while index != 0xFFFF_FFFE {
+            chain.push(index);
+            index = fat[index as usize];
+        }
+        chain
+    }
+
+    // Read the FAT using the ids in the header and any DIFAT sectors.
+    fn read_fat(data: &[u8]) -> Vec<u32> {
+        let num_fat_sectors = read_u32(data, 44) as usize;
+        let mut difat_sector = read_u32(data, 68);
+
+        let mut fat_sector_ids: Vec<u32> = (0..109).map(|i| read_u32(data, 76 + i * 4)).collect();
+        while difat_sector != 0xFFFF_FFFE {
+            let sector_data = sector(data, difat_sector);
+            fat_sector_ids.extend((0..127).map(|i| read_u32(sector_data, i * 4)));
+            difat_sector = read_u32(sector_data, 508);
+        }
+
+        fat_sector_ids
+            .iter()
+            .take(num_fat_sectors)
+            .flat_map(|&id| {
+                let sector_data = sector(data, id);
+                (0..128).map(move |i| read_u32(sector_data, i * 4))
+            })
+            .collect()
+    }
+
+    // Read the directory entries as (name, type, left, right, child, start,
+    // size) tuples.
+    fn read_directory(data: &[u8]) -> Vec<(String, u8, u32, u32, u32, u32, usize)> {
+        let fat = read_fat(data);
+        let mut entries = vec![];
+
+        for id in read_chain(&fat, read_u32(data, 48)) {
+            let sector_data = sector(data, id);
+            for entry in sector_data.chunks(128) {
+                let name_size = u16::from_le_bytes([entry[64], entry[65]]) as usize;
+                let name: Vec<u16> = entry[..name_size.saturating_sub(2)]
+                    .chunks(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+
+                entries.push((
+                    String::from_utf16(&name).unwrap(),
+                    entry[66],
+                    read_u32(entry, 68),
+                    read_u32(entry, 72),
+                    read_u32(entry, 76),
+                    read_u32(entry, 116),
+                    read_u32(entry, 120) as usize,
+                ));
+            }
+        }
+
+        entries
+    }
+
+    // Read a stream back from the compound file.
+    fn read_stream(data: &[u8], name: &str) -> Vec<u8> {
+        let fat = read_fat(data);
+        let entries = read_directory(data);
+        let entry = entries.iter().find(|entry| entry.0 == name).unwrap();
+        let mut stream = vec![];
+
+        if entry.6 < 4096 {
+            let mini_fat: Vec<u32> = read_chain(&fat, read_u32(data, 60))
+                .iter()
+                .flat_map(|&id| {
+                    let sector_data = sector(data, id);
+                    (0..128).map(move |i| read_u32(sector_data, i * 4))
+                })
+                .collect();
+            let mini_stream: Vec<u8> = read_chain(&fat, entries[0].5)
+                .iter()
+                .flat_map(|&id| sector(data, id).to_vec())
+                .collect();
+
+            for id in read_chain(&mini_fat, entry.5) {
+                let offset = id as usize * 64;
+                stream.extend_from_slice(&mini_stream[offset..offset + 64]);
+            }
+        } else {
+            for id in read_chain(&fat, entry.5) {
+                stream.extend_from_slice(sector(data, id));
+            }
+        }
+
+        stream.truncate(entry.6);
+        stream
+    }
+
+    #[test]
+    fn test_empty_file() {
+        let mut compound_file = CompoundFile::new();
+        let got = compound_file.assemble_file();
+
+        assert_eq!(1536, got.len());
+        assert_eq!([0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1], got[..8]);
+
+        // Number of FAT sectors, directory start and mini FAT start.
+        assert_eq!(1, read_u32(&got, 44));
+        assert_eq!(0, read_u32(&got, 48));
+        assert_eq!(0xFFFF_FFFE, read_u32(&got, 60));
+
+        let entries = read_directory(&got);
+        assert_eq!("Root Entry", entries[0].0);
+        assert_eq!(5, entries[0].1);
+        assert_eq!(0xFFFF_FFFF, entries[0].4);
+
+        // Unused entries.
+        assert_eq!(0, entries[1].1);
+        assert_eq!(0xFFFF_FFFF, entries[1].2);
+    }
+
+    #[test]
+    fn test_streams() {
+        let small: Vec<u8> = (0..100).map(|i| i as u8).collect();
+        let large: Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+
+        let mut compound_file = CompoundFile::new();
+        compound_file.add_stream("Small", small.clone());
+        compound_file.add_stream("Storage/Large", large.clone());
+        compound_file.add_stream("Storage/Empty", vec![]);
+        let got = compound_file.assemble_file();
+
+        assert_eq!(small, read_stream(&got, "Small"));
+        assert_eq!(large, read_stream(&got, "Large"));
+        assert_eq!(Vec::<u8>::new(), read_stream(&got, "Empty"));
+
+        // The mini stream is the root entry data.
+        let entries = read_directory(&got);
+        assert_eq!(128, entries[0].6);
+        assert_eq!(1, entries[2].1);
+    }
+
+    #[test]
+    fn test_difat_sectors() {
+        // A stream that needs more than the 109 FAT sectors in the header.
+        let large: Vec<u8> = (0..8_000_000).map(|i| (i % 253) as u8).collect();
+
+        let mut compound_file = CompoundFile::new();
+        compound_file.add_stream("Large", large.clone());
+        let got = compound_file.assemble_file();
+
+        assert_eq!(124, read_u32(&got, 44));
+        assert_eq!(1, read_u32(&got, 72));
+        assert_eq!(large, read_stream(&got, "Large"));
+    }
+
+    #[test]
+    fn test_sibling_tree() {
+        let mut compound_file = CompoundFile::new();
+        compound_file.add_stream("bb", vec![0]);
+        compound_file.add_stream("A", vec![0]);
+        compound_file.add_stream("CCC", vec![0]);
+        compound_file.add_stream("B", vec![0]);
+        compound_file.add_stream("aa", vec![0]);
+        let got = compound_file.assemble_file();
+
+        // The siblings are ordered by length and then uppercase name: A, B,
+        // aa, bb, CCC. The root of the tree is the middle entry.
+        let entries = read_directory(&got);
+        let name = |id: u32| entries[id as usize].0.as_str();
+
+        let root = entries[0].4;
+        assert_eq!("aa", name(root));
+        assert_eq!("B", name(entries[root as usize].2));
+        assert_eq!("CCC", name(entries[root as usize].3));
+
+        let left = entries[root as usize].2;
+        let right = entries[root as usize].3;
+        assert_eq!("A", name(entries[left as usize].2));
+        assert_eq!("bb", name(entries[right as usize].2));
+    }
+}
@@ -274,6 +274,83 @@
 //!
 //!
 //!
+//! # Rule priority and "Stop if True"
+//!
+//! More than one conditional format can be applied to the same range by
+//! calling
+//! [`Worksheet::add_conditional_format()`](crate::Worksheet::add_conditional_format)
+//! several times with the same cell range. Excel evaluates the rules for a
+//! range in the order that they were added so the first rule added has the
+//! highest priority. If the rules set conflicting properties, such as the
+//! background color, the property from the highest priority rule that is true
+//! is used.
+//!
+//! Each conditional format type has a `set_stop_if_true()` method. If this is
+//! set for a rule then Excel doesn't evaluate any lower priority rules for a
+//! cell where the rule is true. In the example below the top 10% of values
+//! are highlighted in green and the remaining above average values are
+//! highlighted in yellow:
+//!
+//! ```
+//! # // This code is available in examples/doc_conditional_format_stop_if_true.rs
+//! #
+//! # use rust_xlsxwriter::{
+//! #     ConditionalFormatAverage, ConditionalFormatAverageRule, ConditionalFormatTop,
+//! #     ConditionalFormatTopRule, Format, Workbook, XlsxError,
+//! # };
+//! #
+//! # fn main() -> Result<(), XlsxError> {
+//! #     // Create a new Excel file object.
+//! #     let mut workbook = Workbook::new();
+//! #     let worksheet = workbook.add_worksheet();
+//! #
+//! #     // Add some sample data.
+//! #     let data = [
+//! #         34, 72, 38, 30, 75, 48, 75, 66, 84, 86, 6, 24, 1, 84, 54, 62, 60, 3, 26, 59,
+//! #     ];
+//! #     worksheet.write_column(0, 0, data)?;
+//! #
+//! #     // Add a format. Green fill with dark green text.
+//! #     let format1 = Format::new()
+//! #         .set_font_color("006100")
+//! #         .set_background_color("C6EFCE");
+//! #
+//! #     // Add a format. Yellow fill with dark yellow text.
+//! #     let format2 = Format::new()
+//! #         .set_font_color("9C6500")
+//! #         .set_background_color("FFEB9C");
+//! #
+//!     // Write the first, highest priority, conditional format over the range.
+//!     let conditional_format = ConditionalFormatTop::new()
+//!         .set_rule(ConditionalFormatTopRule::TopPercent(10))
+//!         .set_stop_if_true(true)
+//!         .set_format(format1);
+//!
+//!     worksheet.add_conditional_format(0, 0, 19, 0, &conditional_format)?;
+//!
+//!     // Write a second, lower priority, conditional format over the same range.
+//!     let conditional_format = ConditionalFormatAverage::new()
+//!         .set_rule(ConditionalFormatAverageRule::AboveAverage)
+//!         .set_format(format2);
+//!
+//!     worksheet.add_conditional_format(0, 0, 19, 0, &conditional_format)?;
+//! #
+//! #     // Save the file.
+//! #     workbook.save("conditional_format.xlsx")?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! Note, the priority of rules on different cell ranges is based on the order
+//! of the ranges in the worksheet rather than the order that they were added.
+//! If the relative priority of overlapping rules is important then it is best
+//! to apply them to the same range, or to the same range with
+//! `set_multi_range()`.
+//!
+//!
+//!
+//!
 //! # Relative and absolute references in conditional formats
 //!
 //! When dealing with Excel conditional formats it is important to distinguish
@@ -913,7 +990,10 @@ mod tests;
 #[cfg(feature = "chrono")]
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
-use crate::{xmlwriter::XMLWriter, Color, ExcelDateTime, Format, Formula, IntoColor, XlsxError};
+use crate::{
+    warning::Warnings, xmlwriter::XMLWriter, Color, ExcelDateTime, Format, Formula, IntoColor,
+    XlsxError,
+};
 use std::{borrow::Cow, fmt};
 
 // -----------------------------------------------------------------------
@@ -955,10 +1035,16 @@ pub trait ConditionalFormat {
 
     /// Clone a reference into a concrete Box type.
     fn box_clone(&self) -> Box<dyn ConditionalFormat + Send>;
+
+    /// Get the warnings for any parameters that were ignored.
+    #[doc(hidden)]
+    fn warnings(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 macro_rules! generate_conditional_format_impls {
-    ($($t:ty)*) => ($(
+    ($($t:ty $(=> $warnings:ident)?)*) => ($(
         impl ConditionalFormat for $t {
             fn validate(&self) -> Result<(), XlsxError> {
                 self.validate()
@@ -998,6 +1084,12 @@ macro_rules! generate_conditional_format_impls {
             fn box_clone(&self) -> Box<dyn ConditionalFormat + Send> {
                 Box::new(self.clone())
             }
+
+            $(
+                fn $warnings(&self) -> Vec<String> {
+                    self.$warnings()
+                }
+            )?
         }
     )*)
 }
@@ -1011,10 +1103,10 @@ generate_conditional_format_impls!(
     ConditionalFormatFormula
     ConditionalFormatText
     ConditionalFormatTop
-    ConditionalFormat2ColorScale
-    ConditionalFormat3ColorScale
-    ConditionalFormatDataBar
-    ConditionalFormatIconSet
+    ConditionalFormat2ColorScale => warnings
+    ConditionalFormat3ColorScale => warnings
+    ConditionalFormatDataBar => warnings
+    ConditionalFormatIconSet => warnings
 );
 
 // -----------------------------------------------------------------------
@@ -2183,6 +2275,13 @@ impl ConditionalFormatFormula {
     /// If you encounter any issues you should verify that the formula works in
     /// Excel before transferring it to `rust_xlsxwriter`.
     ///
+    /// Cell references in the formula are evaluated relative to the top left
+    /// cell of the conditional format range, in the same way as a formula that
+    /// is copied and pasted across the range. Use absolute references such as
+    /// `$A$1` to refer to a fixed cell. Formulas that don't refer to a cell,
+    /// such as `=MOD(ROW(),2)=0`, are evaluated for each cell in the range
+    /// which makes them useful for effects like highlighting alternate rows.
+    ///
     /// # Parameters
     ///
     /// * `value` - A [`Formula`] value or type that converts "into" a `Formula`
@@ -2260,6 +2359,43 @@ impl ConditionalFormatFormula {
     /// <img
     /// src="https://rustxlsxwriter.github.io/images/conditional_format_formula.png">
     ///
+    /// Example of using a Formula type conditional format to highlight
+    /// alternate rows in a range, a technique known as "striping".
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_conditional_format_formula_striping.rs
+    /// #
+    /// # use rust_xlsxwriter::{ConditionalFormatFormula, Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some sample data.
+    /// #     for row in 0..10u32 {
+    /// #         for col in 0..5u16 {
+    /// #             worksheet.write(row, col, row * 10 + u32::from(col))?;
+    /// #         }
+    /// #     }
+    /// #
+    ///     // Add a format. Light blue fill.
+    ///     let format = Format::new().set_background_color("DDEBF7");
+    ///
+    ///     // Write a conditional format over the range to shade every second row.
+    ///     let conditional_format = ConditionalFormatFormula::new()
+    ///         .set_rule("=MOD(ROW(),2)=0")
+    ///         .set_format(format);
+    ///
+    ///     worksheet.add_conditional_format(0, 0, 9, 4, &conditional_format)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("conditional_format.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
     pub fn set_rule(mut self, rule: impl Into<Formula>) -> ConditionalFormatFormula {
         self.formula = rule.into();
         self
@@ -3005,23 +3141,29 @@ impl ConditionalFormatText {
         let mut attributes = vec![];
         let anchor = &range_to_anchor(range);
 
-        // Set the rule attributes based on the criteria.
+        // Set the rule attributes based on the criteria. Double quotes in the
+        // text are escaped for the formula string and the length is in UTF-16
+        // units to match the way Excel counts characters.
         let formula = match rule {
             ConditionalFormatTextRule::Contains(text) => {
+                let text = text.replace('"', "\"\"");
                 attributes.push(("type", "containsText".to_string()));
                 format!(r#"NOT(ISERROR(SEARCH("{text}",{anchor})))"#)
             }
             ConditionalFormatTextRule::DoesNotContain(text) => {
+                let text = text.replace('"', "\"\"");
                 attributes.push(("type", "notContainsText".to_string()));
                 format!(r#"ISERROR(SEARCH("{text}",{anchor}))"#)
             }
             ConditionalFormatTextRule::BeginsWith(text) => {
-                let length = text.len();
+                let length = text.encode_utf16().count();
+                let text = text.replace('"', "\"\"");
                 attributes.push(("type", "beginsWith".to_string()));
                 format!(r#"LEFT({anchor},{length})="{text}""#)
             }
             ConditionalFormatTextRule::EndsWith(text) => {
-                let length = text.len();
+                let length = text.encode_utf16().count();
+                let text = text.replace('"', "\"\"");
                 attributes.push(("type", "endsWith".to_string()));
                 format!(r#"RIGHT({anchor},{length})="{text}""#)
             }
@@ -3448,6 +3590,7 @@ pub struct ConditionalFormat2ColorScale {
     has_x14_extensions: bool,
     has_x14_only: bool,
     pub(crate) format: Option<Format>,
+    warnings: Warnings,
 }
 
 impl ConditionalFormat2ColorScale {
@@ -3467,6 +3610,7 @@ impl ConditionalFormat2ColorScale {
             has_x14_extensions: false,
             has_x14_only: false,
             format: None,
+            warnings: Warnings::new(),
         }
     }
 
@@ -3549,12 +3693,17 @@ impl ConditionalFormat2ColorScale {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_minimum",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
 
+        self.warnings.clear("set_minimum");
+
         // The highest and lowest options cannot be set by the user.
         if rule_type != ConditionalFormatType::Lowest
             && rule_type != ConditionalFormatType::Highest
@@ -3598,12 +3747,17 @@ impl ConditionalFormat2ColorScale {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_maximum",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
 
+        self.warnings.clear("set_maximum");
+
         // The highest and lowest options cannot be set by the user.
         if rule_type != ConditionalFormatType::Lowest
             && rule_type != ConditionalFormatType::Highest
@@ -3677,7 +3831,7 @@ impl ConditionalFormat2ColorScale {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_minimum_color", color) {
             self.min_color = color;
         }
 
@@ -3699,7 +3853,7 @@ impl ConditionalFormat2ColorScale {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_maximum_color", color) {
             self.max_color = color;
         }
 
@@ -3713,6 +3867,11 @@ impl ConditionalFormat2ColorScale {
         Ok(())
     }
 
+    // Get the warnings for any parameters that were ignored.
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        self.warnings.messages()
+    }
+
     //  Return the conditional format rule as an XML string.
     pub(crate) fn rule(
         &self,
@@ -3919,6 +4078,7 @@ pub struct ConditionalFormat3ColorScale {
     has_x14_extensions: bool,
     has_x14_only: bool,
     pub(crate) format: Option<Format>,
+    warnings: Warnings,
 }
 
 impl ConditionalFormat3ColorScale {
@@ -3941,6 +4101,7 @@ impl ConditionalFormat3ColorScale {
             has_x14_extensions: false,
             has_x14_only: false,
             format: None,
+            warnings: Warnings::new(),
         }
     }
 
@@ -4023,12 +4184,17 @@ impl ConditionalFormat3ColorScale {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_minimum",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
 
+        self.warnings.clear("set_minimum");
+
         // The highest and lowest options cannot be set by the user.
         if rule_type != ConditionalFormatType::Lowest
             && rule_type != ConditionalFormatType::Highest
@@ -4072,12 +4238,17 @@ impl ConditionalFormat3ColorScale {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_midpoint",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
 
+        self.warnings.clear("set_midpoint");
+
         // The highest and lowest options cannot be set by the user.
         if rule_type != ConditionalFormatType::Lowest
             && rule_type != ConditionalFormatType::Highest
@@ -4121,12 +4292,17 @@ impl ConditionalFormat3ColorScale {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_maximum",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
 
+        self.warnings.clear("set_maximum");
+
         // The highest and lowest options cannot be set by the user.
         if rule_type != ConditionalFormatType::Lowest
             && rule_type != ConditionalFormatType::Highest
@@ -4201,7 +4377,7 @@ impl ConditionalFormat3ColorScale {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_minimum_color", color) {
             self.min_color = color;
         }
 
@@ -4223,7 +4399,7 @@ impl ConditionalFormat3ColorScale {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_midpoint_color", color) {
             self.mid_color = color;
         }
 
@@ -4245,7 +4421,7 @@ impl ConditionalFormat3ColorScale {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_maximum_color", color) {
             self.max_color = color;
         }
 
@@ -4259,6 +4435,11 @@ impl ConditionalFormat3ColorScale {
         Ok(())
     }
 
+    // Get the warnings for any parameters that were ignored.
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        self.warnings.messages()
+    }
+
     //  Return the conditional format rule as an XML string.
     pub(crate) fn rule(
         &self,
@@ -4452,6 +4633,8 @@ pub struct ConditionalFormatDataBar {
     border_off: bool,
     solid_bar: bool,
     bar_only: bool,
+    min_length: u8,
+    max_length: u8,
     direction: ConditionalFormatDataBarDirection,
     axis_position: ConditionalFormatDataBarAxisPosition,
 
@@ -4460,6 +4643,7 @@ pub struct ConditionalFormatDataBar {
     has_x14_extensions: bool,
     has_x14_only: bool,
     pub(crate) format: Option<Format>,
+    warnings: Warnings,
 }
 
 impl ConditionalFormatDataBar {
@@ -4480,6 +4664,8 @@ impl ConditionalFormatDataBar {
             border_off: false,
             solid_bar: false,
             bar_only: false,
+            min_length: 0,
+            max_length: 100,
             direction: ConditionalFormatDataBarDirection::Context,
             axis_position: ConditionalFormatDataBarAxisPosition::Automatic,
 
@@ -4488,6 +4674,7 @@ impl ConditionalFormatDataBar {
             has_x14_extensions: true,
             has_x14_only: false,
             format: None,
+            warnings: Warnings::new(),
         }
     }
 
@@ -4569,11 +4756,16 @@ impl ConditionalFormatDataBar {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_minimum",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
+
+        self.warnings.clear("set_minimum");
         // The highest option cannot be set for the minimum.
         if rule_type != ConditionalFormatType::Highest {
             self.min_type = rule_type;
@@ -4616,12 +4808,17 @@ impl ConditionalFormatDataBar {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_maximum",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
 
+        self.warnings.clear("set_maximum");
+
         // The lowest option cannot be set for the maximum.
         if rule_type != ConditionalFormatType::Lowest {
             self.max_type = rule_type;
@@ -4688,7 +4885,7 @@ impl ConditionalFormatDataBar {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_fill_color", color) {
             self.fill_color = color;
             self.border_color = color;
         }
@@ -4753,7 +4950,7 @@ impl ConditionalFormatDataBar {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_border_color", color) {
             self.border_color = color;
         }
 
@@ -4817,7 +5014,7 @@ impl ConditionalFormatDataBar {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_negative_fill_color", color) {
             self.negative_fill_color = color;
             self.negative_border_color = color;
         }
@@ -4883,7 +5080,10 @@ impl ConditionalFormatDataBar {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self
+            .warnings
+            .check_color("set_negative_border_color", color)
+        {
             self.negative_border_color = color;
         }
 
@@ -5114,6 +5314,65 @@ impl ConditionalFormatDataBar {
         self
     }
 
+    /// Set the minimum and maximum length of the bars in a data bar.
+    ///
+    /// The bar lengths are a percentage of the cell width. The shortest bar,
+    /// for the minimum value, is drawn with the minimum length and the longest
+    /// bar, for the maximum value, is drawn with the maximum length. By default
+    /// Excel uses a range of 0 to 100 percent.
+    ///
+    /// The minimum length must be less than the maximum length and the maximum
+    /// length must be in the range 1..100. Invalid values will raise an
+    /// [`XlsxError::ConditionalFormatError`] error when the conditional format
+    /// is added to a worksheet.
+    ///
+    /// This property only applies to the post Excel 2010 style of data bar.
+    ///
+    /// # Parameters
+    ///
+    /// * `min_length` - The minimum bar length as a percentage of the cell
+    ///   width.
+    /// * `max_length` - The maximum bar length as a percentage of the cell
+    ///   width.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data bar type conditional formatting to a worksheet
+    /// with user defined bar lengths.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_conditional_format_databar_set_bar_length.rs
+    /// #
+    /// # use rust_xlsxwriter::{ConditionalFormatDataBar, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Write the worksheet data.
+    /// #     let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    /// #     worksheet.write_column(2, 1, data)?;
+    /// #
+    ///     // Write a data bar where the bars use 10% to 90% of the cell width.
+    ///     let conditional_format = ConditionalFormatDataBar::new().set_bar_length(10, 90);
+    ///
+    ///     worksheet.add_conditional_format(2, 1, 11, 1, &conditional_format)?;
+    ///
+    /// #     // Save the file.
+    /// #     workbook.save("conditional_format.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_bar_length(mut self, min_length: u8, max_length: u8) -> ConditionalFormatDataBar {
+        self.min_length = min_length;
+        self.max_length = max_length;
+
+        self
+    }
+
     /// Set the position of the axis in a data bar.
     ///
     /// The position can be set to midpoint or turned off.
@@ -5246,7 +5505,7 @@ impl ConditionalFormatDataBar {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_axis_color", color) {
             self.axis_color = color;
         }
 
@@ -5276,12 +5535,22 @@ impl ConditionalFormatDataBar {
     }
 
     // Validate the conditional format.
-    #[allow(clippy::unnecessary_wraps)]
-    #[allow(clippy::unused_self)]
     pub(crate) fn validate(&self) -> Result<(), XlsxError> {
+        if self.min_length >= self.max_length || self.max_length > 100 {
+            return Err(XlsxError::ConditionalFormatError(format!(
+                "ConditionalFormatDataBar bar lengths '{}..{}' must be increasing values in Excel range: 0..100.",
+                self.min_length, self.max_length
+            )));
+        }
+
         Ok(())
     }
 
+    // Get the warnings for any parameters that were ignored.
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        self.warnings.messages()
+    }
+
     //  Return the conditional format rule as an XML string.
     pub(crate) fn rule(
         &self,
@@ -5467,8 +5736,8 @@ impl ConditionalFormatDataBar {
     // Write the <x14:dataBar> element.
     fn write_data_bar(writer: &mut XMLWriter, data_bar: ConditionalFormatDataBar) {
         let mut attributes = vec![
-            ("minLength", "0".to_string()),
-            ("maxLength", "100".to_string()),
+            ("minLength", data_bar.min_length.to_string()),
+            ("maxLength", data_bar.max_length.to_string()),
         ];
 
         if !data_bar.border_off {
@@ -6132,6 +6401,14 @@ impl ConditionalFormatIconSet {
         Ok(())
     }
 
+    // Get the warnings for any parameters that were ignored in the icons.
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        self.icons
+            .iter()
+            .flat_map(|icon| icon.warnings.messages())
+            .collect()
+    }
+
     //  Return the conditional format rule as an XML string.
     pub(crate) fn rule(
         &self,
@@ -6380,6 +6657,7 @@ pub struct ConditionalFormatCustomIcon {
     greater_than: bool,
     rule_type: ConditionalFormatType,
     value: ConditionalFormatValue,
+    warnings: Warnings,
 }
 
 impl ConditionalFormatCustomIcon {
@@ -6395,6 +6673,7 @@ impl ConditionalFormatCustomIcon {
             greater_than: false,
             rule_type: ConditionalFormatType::Percent,
             value: ConditionalFormatValue::new_from_string("0"),
+            warnings: Warnings::new(),
         }
     }
 
@@ -6425,11 +6704,16 @@ impl ConditionalFormatCustomIcon {
         {
             if let Ok(num) = value.value.parse::<f64>() {
                 if !(0.0..=100.0).contains(&num) {
-                    eprintln!("Percent/percentile '{num}' must be in Excel range: 0..100.");
+                    self.warnings.warn(
+                        "set_rule",
+                        format!("Percent/percentile '{num}' must be in Excel range: 0..100."),
+                    );
                     return self;
                 }
             }
         }
+
+        self.warnings.clear("set_rule");
         // The highest option cannot be set for the minimum.
         if rule_type != ConditionalFormatType::Highest {
             self.rule_type = rule_type;
@@ -6540,7 +6824,12 @@ impl ConditionalFormatCustomIcon {
             | ConditionalFormatIconType::ThreeSymbolsCircled
             | ConditionalFormatIconType::ThreeSymbols => {
                 if index >= 3 {
-                    eprintln!("Found '{index}' index. Three symbol Icon Sets have indexes of 0-2.");
+                    self.warnings.warn(
+                        "set_icon_type",
+                        format!(
+                            "Found '{index}' index. Three symbol Icon Sets have indexes of 0-2."
+                        ),
+                    );
                     return self;
                 }
             }
@@ -6550,7 +6839,12 @@ impl ConditionalFormatCustomIcon {
             | ConditionalFormatIconType::FourHistograms
             | ConditionalFormatIconType::FourTrafficLights => {
                 if index >= 4 {
-                    eprintln!("Found '{index}' index. Four symbol Icon Sets have indexes of 0-3.");
+                    self.warnings.warn(
+                        "set_icon_type",
+                        format!(
+                            "Found '{index}' index. Four symbol Icon Sets have indexes of 0-3."
+                        ),
+                    );
                     return self;
                 }
             }
@@ -6560,12 +6854,20 @@ impl ConditionalFormatCustomIcon {
             | ConditionalFormatIconType::FiveHistograms
             | ConditionalFormatIconType::FiveQuadrants => {
                 if index >= 5 {
-                    eprintln!("Found '{index}' index. Five symbol Icon Sets have indexes of 0-4.");
+                    self.warnings.warn(
+                        "set_icon_type",
+                        format!(
+                            "Found '{index}' index. Five symbol Icon Sets have indexes of 0-4."
+                        ),
+                    );
                     return self;
                 }
             }
         }
 
+        self.warnings.clear("set_icon_type");
+
+        self.warnings.clear("set_icon_type");
         self.icon_type = Some(icon_type);
         self.icon_index = index;
         self.is_custom = true;
@@ -7369,6 +7671,34 @@ pub enum ConditionalFormatIconType {
     FiveBoxes,
 }
 
+impl ConditionalFormatIconType {
+    // Get the number of icons in the icon set.
+    pub(crate) fn num_icons(self) -> u8 {
+        match self {
+            Self::ThreeArrows
+            | Self::ThreeArrowsGray
+            | Self::ThreeFlags
+            | Self::ThreeTrafficLights
+            | Self::ThreeTrafficLightsWithRim
+            | Self::ThreeSigns
+            | Self::ThreeStars
+            | Self::ThreeTriangles
+            | Self::ThreeSymbolsCircled
+            | Self::ThreeSymbols => 3,
+            Self::FourArrows
+            | Self::FourArrowsGray
+            | Self::FourRedToBlack
+            | Self::FourHistograms
+            | Self::FourTrafficLights => 4,
+            Self::FiveArrows
+            | Self::FiveBoxes
+            | Self::FiveArrowsGray
+            | Self::FiveHistograms
+            | Self::FiveQuadrants => 5,
+        }
+    }
+}
+
 impl fmt::Display for ConditionalFormatIconType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -82,10 +82,57 @@ mod conditional_format_tests {
         assert_eq!(expected, got);
     }
 
+    #[test]
+    fn text_rule_escaping() {
+        // Double quotes in the text should be escaped in the formula.
+        let conditional_format = ConditionalFormatText::new().set_rule(
+            ConditionalFormatTextRule::Contains("say \"hi\"".to_string()),
+        );
+
+        let got = conditional_format.rule(None, 1, "A1:A4", "");
+        let expected = r#"<cfRule type="containsText" priority="1" operator="containsText" text="say &quot;hi&quot;"><formula>NOT(ISERROR(SEARCH("say ""hi""",A1)))</formula></cfRule>"#;
+
+        assert_eq!(expected, got);
+
+        // The text length should be in characters rather than bytes.
+        let conditional_format = ConditionalFormatText::new()
+            .set_rule(ConditionalFormatTextRule::BeginsWith("Café".to_string()));
+
+        let got = conditional_format.rule(None, 1, "A1:A4", "");
+        let expected = r#"<cfRule type="beginsWith" priority="1" operator="beginsWith" text="Café"><formula>LEFT(A1,4)="Café"</formula></cfRule>"#;
+
+        assert_eq!(expected, got);
+
+        let conditional_format = ConditionalFormatText::new()
+            .set_rule(ConditionalFormatTextRule::EndsWith("ñ\"".to_string()));
+
+        let got = conditional_format.rule(None, 1, "A1:A4", "");
+        let expected = r#"<cfRule type="endsWith" priority="1" operator="endsWith" text="ñ&quot;"><formula>RIGHT(A1,2)="ñ"""</formula></cfRule>"#;
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn formula_striping() {
+        let conditional_format = ConditionalFormatFormula::new()
+            .set_rule("=MOD(ROW(),2)=0")
+            .set_stop_if_true(true);
+
+        let got = conditional_format.rule(Some(0), 1, "", "");
+        let expected = r#"<cfRule type="expression" dxfId="0" priority="1" stopIfTrue="1"><formula>MOD(ROW(),2)=0</formula></cfRule>"#;
+
+        assert_eq!(expected, got);
+    }
+
     #[test]
     fn validation_checks() {
         // Check validations for various conditional formats.
 
+        // Formula format must have a rule.
+        let conditional_format = ConditionalFormatFormula::new().set_rule("=");
+        let result = conditional_format.validate();
+        assert!(matches!(result, Err(XlsxError::ConditionalFormatError(_))));
+
         // Cell format must have a rule.
         let conditional_format = ConditionalFormatCell::new();
         let result = conditional_format.validate();
@@ -5227,4 +5274,144 @@ mod conditional_format_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn data_bar_14() -> Result<(), XlsxError> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_selected(true);
+
+        let conditional_format = ConditionalFormatDataBar::new().set_bar_length(90, 10);
+        let result = worksheet.add_conditional_format(0, 0, 0, 0, &conditional_format);
+        assert!(matches!(result, Err(XlsxError::ConditionalFormatError(_))));
+
+        let conditional_format = ConditionalFormatDataBar::new().set_bar_length(10, 101);
+        let result = worksheet.add_conditional_format(0, 0, 0, 0, &conditional_format);
+        assert!(matches!(result, Err(XlsxError::ConditionalFormatError(_))));
+
+        let conditional_format = ConditionalFormatDataBar::new().set_bar_length(10, 90);
+        worksheet.add_conditional_format(0, 0, 0, 0, &conditional_format)?;
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" xmlns:x14ac="http://schemas.microsoft.com/office/spreadsheetml/2009/9/ac" mc:Ignorable="x14ac">
+              <dimension ref="A1"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15" x14ac:dyDescent="0.25"/>
+              <sheetData/>
+              <conditionalFormatting sqref="A1">
+                <cfRule type="dataBar" priority="1">
+                  <dataBar>
+                    <cfvo type="min"/>
+                    <cfvo type="max"/>
+                    <color rgb="FF638EC6"/>
+                  </dataBar>
+                  <extLst>
+                    <ext xmlns:x14="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main" uri="{B025F937-C7B1-47D3-B67F-A62EFF666E3E}">
+                      <x14:id>{DA7ABA51-AAAA-BBBB-0001-000000000001}</x14:id>
+                    </ext>
+                  </extLst>
+                </cfRule>
+              </conditionalFormatting>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+              <extLst>
+                <ext xmlns:x14="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main" uri="{78C0D931-6437-407d-A8EE-F0AAD7539E65}">
+                  <x14:conditionalFormattings>
+                    <x14:conditionalFormatting xmlns:xm="http://schemas.microsoft.com/office/excel/2006/main">
+                      <x14:cfRule type="dataBar" id="{DA7ABA51-AAAA-BBBB-0001-000000000001}">
+                        <x14:dataBar minLength="10" maxLength="90" border="1" negativeBarBorderColorSameAsPositive="0">
+                          <x14:cfvo type="autoMin"/>
+                          <x14:cfvo type="autoMax"/>
+                          <x14:borderColor rgb="FF638EC6"/>
+                          <x14:negativeFillColor rgb="FFFF0000"/>
+                          <x14:negativeBorderColor rgb="FFFF0000"/>
+                          <x14:axisColor rgb="FF000000"/>
+                        </x14:dataBar>
+                      </x14:cfRule>
+                      <xm:sqref>A1</xm:sqref>
+                    </x14:conditionalFormatting>
+                  </x14:conditionalFormattings>
+                </ext>
+              </extLst>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        Ok(())
+    }
+
+    #[test]
+    fn conditional_format_priority() -> Result<(), XlsxError> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_selected(true);
+
+        worksheet.write(0, 0, 10)?;
+        worksheet.write(1, 0, 20)?;
+
+        let conditional_format = ConditionalFormatTop::new()
+            .set_rule(ConditionalFormatTopRule::TopPercent(10))
+            .set_stop_if_true(true);
+
+        worksheet.add_conditional_format(0, 0, 1, 0, &conditional_format)?;
+
+        let conditional_format = ConditionalFormatAverage::new()
+            .set_rule(ConditionalFormatAverageRule::OneStandardDeviationAbove);
+
+        worksheet.add_conditional_format(0, 0, 1, 0, &conditional_format)?;
+
+        let conditional_format = ConditionalFormatTop::new()
+            .set_rule(ConditionalFormatTopRule::Bottom(5))
+            .set_stop_if_true(true);
+
+        worksheet.add_conditional_format(0, 0, 1, 0, &conditional_format)?;
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1:A2"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData>
+                <row r="1" spans="1:1">
+                  <c r="A1">
+                    <v>10</v>
+                  </c>
+                </row>
+                <row r="2" spans="1:1">
+                  <c r="A2">
+                    <v>20</v>
+                  </c>
+                </row>
+              </sheetData>
+              <conditionalFormatting sqref="A1:A2">
+                <cfRule type="top10" priority="1" stopIfTrue="1" percent="1" rank="10"/>
+                <cfRule type="aboveAverage" priority="2" stdDev="1"/>
+                <cfRule type="top10" priority="3" stopIfTrue="1" bottom="1" rank="5"/>
+              </conditionalFormatting>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        Ok(())
+    }
 }
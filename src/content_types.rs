@@ -106,6 +106,23 @@ impl ContentTypes {
         self.add_override(&part_name, content_type);
     }
 
+    // Add the name of a named sheet view to the ContentTypes overrides.
+    pub(crate) fn add_named_sheet_view_name(&mut self, index: u16) {
+        let content_type = "application/vnd.ms-excel.namedsheetviews+xml";
+        let part_name = format!("/xl/namedSheetViews/namedSheetView{index}.xml");
+
+        self.add_override(&part_name, content_type);
+    }
+
+    // Add the name of a comment file to the ContentTypes overrides.
+    pub(crate) fn add_comment_name(&mut self, index: u16) {
+        let content_type =
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.comments+xml";
+        let part_name = format!("/xl/comments{index}.xml");
+
+        self.add_override(&part_name, content_type);
+    }
+
     // Add the sharedStrings link to the ContentTypes overrides.
     pub(crate) fn add_share_strings(&mut self) {
         self.add_override(
@@ -142,6 +159,39 @@ impl ContentTypes {
         );
     }
 
+    // Change the workbook content type for macro enabled files and templates.
+    pub(crate) fn set_workbook_type(&mut self, has_vba_project: bool, is_template: bool) {
+        let workbook_type = match (has_vba_project, is_template) {
+            (false, false) => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"
+            }
+            (false, true) => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.template.main+xml"
+            }
+            (true, false) => "application/vnd.ms-excel.sheet.macroEnabled.main+xml",
+            (true, true) => "application/vnd.ms-excel.template.macroEnabled.main+xml",
+        };
+
+        for (part_name, content_type) in &mut self.overrides {
+            if part_name == "/xl/workbook.xml" {
+                *content_type = workbook_type.to_string();
+            }
+        }
+    }
+
+    // Add the vbaProject.bin file to the ContentTypes defaults.
+    pub(crate) fn add_vba_project(&mut self) {
+        self.add_default("bin", "application/vnd.ms-office.vbaProject");
+    }
+
+    // Add the vbaProjectSignature.bin file to the ContentTypes overrides.
+    pub(crate) fn add_vba_signature(&mut self) {
+        self.add_override(
+            "/xl/vbaProjectSignature.bin",
+            "application/vnd.ms-office.vbaProjectSignature",
+        );
+    }
+
     // Add the custom properties to the ContentTypes overrides.
     pub(crate) fn add_custom_properties(&mut self) {
         self.add_override(
@@ -150,6 +200,15 @@ impl ContentTypes {
         );
     }
 
+    // Add the name of a custom xml properties file to the ContentTypes
+    // overrides.
+    pub(crate) fn add_custom_xml_props_name(&mut self, index: u16) {
+        let content_type = "application/vnd.openxmlformats-officedocument.customXmlProperties+xml";
+        let part_name = format!("/customXml/itemProps{index}.xml");
+
+        self.add_override(&part_name, content_type);
+    }
+
     // -----------------------------------------------------------------------
     // XML assembly methods.
     // -----------------------------------------------------------------------
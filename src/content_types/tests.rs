@@ -45,4 +45,38 @@ mod content_types_tests {
 
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_assemble_with_vba_project() {
+        let mut content_types = ContentTypes::new();
+
+        content_types.set_workbook_type(true, false);
+        content_types.add_vba_project();
+        content_types.add_worksheet_name(1);
+        content_types.assemble_xml_file();
+
+        let got = content_types.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+
+              <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+              <Default Extension="xml" ContentType="application/xml"/>
+              <Default Extension="bin" ContentType="application/vnd.ms-office.vbaProject"/>
+
+              <Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>
+              <Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+              <Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>
+              <Override PartName="/xl/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>
+              <Override PartName="/xl/workbook.xml" ContentType="application/vnd.ms-excel.sheet.macroEnabled.main+xml"/>
+              <Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>
+             </Types>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
 }
@@ -0,0 +1,80 @@
+// custom_xml - A module for creating the Excel customXml itemProps.xml file.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+mod tests;
+
+use crate::xmlwriter::XMLWriter;
+
+pub struct CustomXmlProps {
+    pub(crate) writer: XMLWriter,
+    pub(crate) item_id: String,
+    pub(crate) schema: String,
+}
+
+impl CustomXmlProps {
+    // -----------------------------------------------------------------------
+    // Public (and crate public) methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new CustomXmlProps struct. The item id is the GUID assigned to
+    // the custom xml part when it was added to the workbook.
+    pub(crate) fn new(item_id: &str, schema: &str) -> CustomXmlProps {
+        let writer = XMLWriter::new();
+
+        CustomXmlProps {
+            writer,
+            item_id: item_id.to_string(),
+            schema: schema.to_string(),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and write the XML file.
+    pub(crate) fn assemble_xml_file(&mut self) {
+        self.writer.xml_declaration();
+
+        // Write the ds:datastoreItem element.
+        self.write_datastore_item();
+
+        // Write the ds:schemaRefs element.
+        self.write_schema_refs();
+
+        // Close the final tag.
+        self.writer.xml_end_tag("ds:datastoreItem");
+    }
+
+    // Write the <ds:datastoreItem> element.
+    fn write_datastore_item(&mut self) {
+        let attributes = [
+            ("ds:itemID", self.item_id.as_str()),
+            (
+                "xmlns:ds",
+                "http://schemas.openxmlformats.org/officeDocument/2006/customXml",
+            ),
+        ];
+
+        self.writer.xml_start_tag("ds:datastoreItem", &attributes);
+    }
+
+    // Write the <ds:schemaRefs> element.
+    fn write_schema_refs(&mut self) {
+        if self.schema.is_empty() {
+            self.writer.xml_empty_tag_only("ds:schemaRefs");
+            return;
+        }
+
+        self.writer.xml_start_tag_only("ds:schemaRefs");
+
+        // Write the ds:schemaRef element.
+        let attributes = [("ds:uri", self.schema.as_str())];
+        self.writer.xml_empty_tag("ds:schemaRef", &attributes);
+
+        self.writer.xml_end_tag("ds:schemaRefs");
+    }
+}
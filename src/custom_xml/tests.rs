@@ -0,0 +1,60 @@
+// CustomXmlProps unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod custom_xml_tests {
+
+    use crate::custom_xml::CustomXmlProps;
+    use crate::test_functions::xml_to_vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_assemble1() {
+        let mut custom_xml = CustomXmlProps::new(
+            "{6F9619FF-8B86-4D11-B42D-00C04FC964FF}",
+            "http://example.com/schemas/invoice",
+        );
+
+        custom_xml.assemble_xml_file();
+
+        let got = custom_xml.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <ds:datastoreItem ds:itemID="{6F9619FF-8B86-4D11-B42D-00C04FC964FF}" xmlns:ds="http://schemas.openxmlformats.org/officeDocument/2006/customXml">
+              <ds:schemaRefs>
+                <ds:schemaRef ds:uri="http://example.com/schemas/invoice"/>
+              </ds:schemaRefs>
+            </ds:datastoreItem>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble2() {
+        let mut custom_xml = CustomXmlProps::new("{3F2504E0-4F89-41D3-9A0C-0305E82C3301}", "");
+
+        custom_xml.assemble_xml_file();
+
+        let got = custom_xml.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <ds:datastoreItem ds:itemID="{3F2504E0-4F89-41D3-9A0C-0305E82C3301}" xmlns:ds="http://schemas.openxmlformats.org/officeDocument/2006/customXml">
+              <ds:schemaRefs/>
+            </ds:datastoreItem>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+}
@@ -1,74 +1,1077 @@
-#[derive(Clone, Default)]
+// data_validation - A module for representing Excel data validations.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+mod tests;
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use crate::{Formula, IntoExcelDateTime, XlsxError};
+
+/// The `DataValidation` struct represents a data validation in Excel.
+///
+/// Data validation is a feature of Excel which allows you to restrict the data
+/// that a user enters in a cell and to display associated help and warning
+/// messages. It also allows you to restrict input to values in a dropdown
+/// list.
+///
+/// A data validation is added to a worksheet cell or range via the
+/// [`Worksheet::add_data_validation()`](crate::Worksheet::add_data_validation)
+/// method.
+///
+/// # Examples
+///
+/// Example of adding a data validation to a worksheet cell. This validation
+/// restricts users to a selection of values from a dropdown list.
+///
+/// ```
+/// # // This code is available in examples/doc_data_validation_allow_list_strings.rs
+/// #
+/// # use rust_xlsxwriter::{DataValidation, Workbook, XlsxError};
+/// #
+/// # fn main() -> Result<(), XlsxError> {
+/// #     // Create a new Excel file object.
+/// #     let mut workbook = Workbook::new();
+/// #     let worksheet = workbook.add_worksheet();
+/// #
+/// #     worksheet.write(1, 0, "Select value in cell D2:")?;
+/// #
+///     let data_validation =
+///         DataValidation::new().allow_list_strings(&["Pass", "Fail", "Incomplete"])?;
+///
+///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+/// #
+/// #     // Save the file.
+/// #     workbook.save("data_validation.xlsx")?;
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// Output file:
+///
+/// <img src="https://rustxlsxwriter.github.io/images/data_validation_allow_list_strings.png">
+///
+#[derive(Clone)]
 pub struct DataValidation {
+    pub(crate) validation_type: DataValidationType,
+    pub(crate) formula1: String,
+    pub(crate) ignore_blank: bool,
+    pub(crate) show_input_message: bool,
+    pub(crate) show_error_message: bool,
+    pub(crate) operator: String,
+    pub(crate) formula2: String,
+    pub(crate) show_dropdown: bool,
+    pub(crate) input_title: String,
+    pub(crate) input_message: String,
+    pub(crate) error_title: String,
+    pub(crate) error_message: String,
+    pub(crate) error_style: DataValidationErrorStyle,
+
+    // The properties of the deprecated data validation API.
+    #[allow(deprecated)]
+    pub(crate) legacy: LegacyDataValidation,
+}
+
+impl DataValidation {
+    /// Create a new data validation struct.
+    ///
+    /// The default type of a new data validation is equivalent to Excel's "Any"
+    /// data validation. Refer to the `allow_*()` methods for information on how
+    /// to restrict the cell input to specific types and values.
+    ///
+    #[allow(clippy::new_without_default)]
+    #[allow(deprecated)]
+    pub fn new() -> DataValidation {
+        DataValidation {
+            validation_type: DataValidationType::Any,
+            formula1: String::new(),
+            ignore_blank: true,
+            show_input_message: true,
+            show_error_message: true,
+            operator: String::new(),
+            formula2: String::new(),
+            show_dropdown: true,
+            input_title: String::new(),
+            input_message: String::new(),
+            error_title: String::new(),
+            error_message: String::new(),
+            error_style: DataValidationErrorStyle::Stop,
+            legacy: LegacyDataValidation::default(),
+        }
+    }
+
+    /// Set a data validation to restrict input to integers in a range.
+    ///
+    /// Restrict cell input to whole/integer numbers in a range defined by a
+    /// [`DataValidationRule`] such as "between" or "greater than". Excel
+    /// raises an error for non-integer or out of range input.
+    ///
+    /// See also [`DataValidation::allow_whole_number_formula()`] for a version
+    /// of this method that takes cell references or formulas as the limits.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with `i32` values.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell. This validation
+    /// restricts input to integer values in a fixed range.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_allow_whole_number.rs
+    /// #
+    /// # use rust_xlsxwriter::{DataValidation, DataValidationRule, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(1, 0, "Enter rating in cell D2:")?;
+    ///
+    ///     let data_validation =
+    ///         DataValidation::new().allow_whole_number(DataValidationRule::Between(1, 10));
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn allow_whole_number(self, rule: DataValidationRule<i32>) -> DataValidation {
+        self.set_rule(DataValidationType::Whole, rule, |value| value.to_string())
+    }
+
+    /// Set a data validation to restrict input to integers in a range defined
+    /// by formulas.
+    ///
+    /// This is the same as [`DataValidation::allow_whole_number()`] except the
+    /// limits are cell references or formulas such as `=D1` or `=MAX(D1:D5)`.
+    ///
+    /// Note, relative cell references in the formula are relative to the top
+    /// left cell of the data validation range, in the same way as a formula
+    /// that is copied across the range. Use absolute references like `$D$1`
+    /// for fixed limits.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with [`Formula`] values.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell. This validation
+    /// restricts input to integer values less than or equal to a limit in
+    /// another cell.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_allow_whole_number_formula.rs
+    /// #
+    /// # use rust_xlsxwriter::{DataValidation, DataValidationRule, Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(0, 0, "Upper limit:")?;
+    ///     worksheet.write(0, 3, 10)?;
+    ///     worksheet.write(1, 0, "Enter rating in cell D2:")?;
+    ///
+    ///     let data_validation = DataValidation::new()
+    ///         .allow_whole_number_formula(DataValidationRule::LessThanOrEqualTo(Formula::new("=D1")));
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn allow_whole_number_formula(self, rule: DataValidationRule<Formula>) -> DataValidation {
+        self.set_rule(DataValidationType::Whole, rule, Self::formula_value)
+    }
+
+    /// Set a data validation to restrict input to decimal numbers in a range.
+    ///
+    /// Restrict cell input to decimal numbers in a range defined by a
+    /// [`DataValidationRule`]. See [`DataValidation::allow_whole_number()`] for
+    /// an example of a similar numeric data validation.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with `f64` values.
+    ///
+    pub fn allow_decimal_number(self, rule: DataValidationRule<f64>) -> DataValidation {
+        self.set_rule(DataValidationType::Decimal, rule, |value| value.to_string())
+    }
+
+    /// Set a data validation to restrict input to decimal numbers in a range
+    /// defined by formulas.
+    ///
+    /// This is the same as [`DataValidation::allow_decimal_number()`] except
+    /// the limits are cell references or formulas. See
+    /// [`DataValidation::allow_whole_number_formula()`] for an example.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with [`Formula`] values.
+    ///
+    pub fn allow_decimal_number_formula(self, rule: DataValidationRule<Formula>) -> DataValidation {
+        self.set_rule(DataValidationType::Decimal, rule, Self::formula_value)
+    }
+
+    /// Set a data validation to restrict input to a list of strings.
+    ///
+    /// Restrict cell input to a list of strings in a dropdown.
+    ///
+    /// The strings are joined by commas and written to the file as a single
+    /// Excel formula string. Excel limits the length of this string to 255
+    /// characters. If you need a longer list of options you should store the
+    /// values in a worksheet range and refer to them from the data validation.
+    ///
+    /// Note, the strings shouldn't contain commas since Excel uses them as the
+    /// separator between list items.
+    ///
+    /// # Parameters
+    ///
+    /// * `list` - A list of string like values. Examples of valid values are
+    ///   `&["Pass", "Fail"]` or `&vec!["Pass", "Fail"]`.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::DataValidationError`] - The list is empty or the joined
+    ///   list of strings exceeds Excel's limit of 255 characters.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell. This validation
+    /// restricts users to a selection of values from a dropdown list.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_allow_list_strings.rs
+    /// #
+    /// # use rust_xlsxwriter::{DataValidation, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     worksheet.write(1, 0, "Select value in cell D2:")?;
+    /// #
+    ///     let data_validation =
+    ///         DataValidation::new().allow_list_strings(&["Pass", "Fail", "Incomplete"])?;
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/data_validation_allow_list_strings.png">
+    ///
+    pub fn allow_list_strings(
+        mut self,
+        list: &[impl AsRef<str>],
+    ) -> Result<DataValidation, XlsxError> {
+        if list.is_empty() {
+            return Err(XlsxError::DataValidationError(
+                "List of strings must not be empty".to_string(),
+            ));
+        }
+
+        let joined = list
+            .iter()
+            .map(|item| item.as_ref().replace('"', "\"\""))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        // Excel's limit applies to the joined string without the escaping.
+        let length = list
+            .iter()
+            .map(|item| item.as_ref().chars().count())
+            .sum::<usize>()
+            + list.len()
+            - 1;
+
+        if length > 255 {
+            return Err(XlsxError::DataValidationError(format!(
+                "Joined list of strings has length {length} which exceeds Excel's limit of 255 characters"
+            )));
+        }
+
+        self.validation_type = DataValidationType::List;
+        self.formula1 = format!("\"{joined}\"");
+        self.operator.clear();
+        self.formula2.clear();
+        Ok(self)
+    }
+
+    /// Set a data validation to restrict input to a list of values from a
+    /// range or formula.
+    ///
+    /// Restrict cell input to a list of values in a dropdown where the values
+    /// are taken from a cell range like `=$D$1:$D$10`, a range in another
+    /// worksheet like `=Sheet2!$A$1:$A$20`, or a defined name like
+    /// `=Categories`.
+    ///
+    /// This is useful when the list of options is longer than the 255
+    /// character limit of [`DataValidation::allow_list_strings()`] or when the
+    /// options need to be changed in Excel without changing the data
+    /// validation.
+    ///
+    /// Note, the range should generally be an absolute reference using `$`
+    /// anchors so that it doesn't change relative to each cell in the data
+    /// validation range.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`Formula`] value representing the range or defined name.
+    ///   Any type that converts "into" a `Formula` such as a `&str` can also be
+    ///   used.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell. This validation
+    /// restricts users to a selection of values from a dropdown list. The list
+    /// data is provided from a cell range.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_allow_list_formula.rs
+    /// #
+    /// # use rust_xlsxwriter::{DataValidation, Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     worksheet.write(1, 0, "Select value in cell D2:")?;
+    /// #
+    ///     // Write the string list data to some cells.
+    ///     let string_list = ["Pass", "Fail", "Incomplete"];
+    ///     worksheet.write_column(1, 5, string_list)?;
+    ///
+    ///     let data_validation =
+    ///         DataValidation::new().allow_list_formula(Formula::new("=$F$2:$F$4"));
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/data_validation_allow_list_formula.png">
+    ///
+    pub fn allow_list_formula(mut self, rule: impl Into<Formula>) -> DataValidation {
+        self.validation_type = DataValidationType::List;
+        self.formula1 = rule.into().expand_formula(true).to_string();
+        self.operator.clear();
+        self.formula2.clear();
+        self
+    }
+
+    /// Set a data validation to restrict input to dates in a range.
+    ///
+    /// Restrict cell input to dates in a range defined by a
+    /// [`DataValidationRule`].
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with [`ExcelDateTime`](crate::ExcelDateTime)
+    ///   values, or any other type that implements [`IntoExcelDateTime`] such
+    ///   as the `Chrono` `NaiveDate` type if the `chrono` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell. This validation
+    /// restricts input to dates in a fixed range.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_allow_date.rs
+    /// #
+    /// # use rust_xlsxwriter::{DataValidation, DataValidationRule, ExcelDateTime, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(1, 0, "Enter date in cell D2:")?;
+    ///
+    ///     let data_validation = DataValidation::new().allow_date(DataValidationRule::Between(
+    ///         ExcelDateTime::parse_from_str("2025-01-01")?,
+    ///         ExcelDateTime::parse_from_str("2025-12-12")?,
+    ///     ));
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn allow_date<T>(self, rule: DataValidationRule<T>) -> DataValidation
+    where
+        T: IntoExcelDateTime,
+    {
+        self.set_rule(DataValidationType::Date, rule, Self::datetime_value)
+    }
+
+    /// Set a data validation to restrict input to dates in a range defined by
+    /// formulas.
+    ///
+    /// This is the same as [`DataValidation::allow_date()`] except the limits
+    /// are cell references or formulas such as `=TODAY()`.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with [`Formula`] values.
+    ///
+    pub fn allow_date_formula(self, rule: DataValidationRule<Formula>) -> DataValidation {
+        self.set_rule(DataValidationType::Date, rule, Self::formula_value)
+    }
+
+    /// Set a data validation to restrict input to times in a range.
+    ///
+    /// Restrict cell input to times in a range defined by a
+    /// [`DataValidationRule`]. See [`DataValidation::allow_date()`] for an
+    /// example of a similar data validation.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with [`ExcelDateTime`](crate::ExcelDateTime)
+    ///   values, or any other type that implements [`IntoExcelDateTime`] such
+    ///   as the `Chrono` `NaiveTime` type if the `chrono` feature is enabled.
+    ///
+    pub fn allow_time<T>(self, rule: DataValidationRule<T>) -> DataValidation
+    where
+        T: IntoExcelDateTime,
+    {
+        self.set_rule(DataValidationType::Time, rule, Self::datetime_value)
+    }
+
+    /// Set a data validation to restrict input to times in a range defined by
+    /// formulas.
+    ///
+    /// This is the same as [`DataValidation::allow_time()`] except the limits
+    /// are cell references or formulas.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with [`Formula`] values.
+    ///
+    pub fn allow_time_formula(self, rule: DataValidationRule<Formula>) -> DataValidation {
+        self.set_rule(DataValidationType::Time, rule, Self::formula_value)
+    }
+
+    /// Set a data validation to restrict input to strings of a certain length.
+    ///
+    /// Restrict cell input to text with a length, in characters, in a range
+    /// defined by a [`DataValidationRule`].
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with `u32` values.
+    ///
+    pub fn allow_text_length(self, rule: DataValidationRule<u32>) -> DataValidation {
+        self.set_rule(DataValidationType::TextLength, rule, |value| {
+            value.to_string()
+        })
+    }
+
+    /// Set a data validation to restrict input to strings with a length in a
+    /// range defined by formulas.
+    ///
+    /// This is the same as [`DataValidation::allow_text_length()`] except the
+    /// limits are cell references or formulas.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`DataValidationRule`] with [`Formula`] values.
+    ///
+    pub fn allow_text_length_formula(self, rule: DataValidationRule<Formula>) -> DataValidation {
+        self.set_rule(DataValidationType::TextLength, rule, Self::formula_value)
+    }
+
+    /// Set a data validation to restrict input based on a custom formula.
+    ///
+    /// Restrict cell input based on a formula that evaluates to `TRUE` for
+    /// valid input and `FALSE` for invalid input. This allows rules that
+    /// aren't covered by the other data validation types such as checking
+    /// that a value starts with a prefix or is unique in a range.
+    ///
+    /// Relative cell references in the formula are relative to the top left
+    /// cell of the data validation range, in the same way as a formula that is
+    /// copied across the range.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule` - A [`Formula`] value. Any type that converts "into" a
+    ///   `Formula` such as a `&str` can also be used.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell. This
+    /// validation uses a custom formula to restrict input to text that starts
+    /// with "ID-".
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_allow_custom.rs
+    /// #
+    /// # use rust_xlsxwriter::{DataValidation, Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(1, 0, "Enter an ID in cell D2:")?;
+    ///
+    ///     let data_validation = DataValidation::new().allow_custom(Formula::new(r#"=LEFT(D2,3)="ID-""#));
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn allow_custom(mut self, rule: impl Into<Formula>) -> DataValidation {
+        self.validation_type = DataValidationType::Custom;
+        self.formula1 = rule.into().expand_formula(true).to_string();
+        self.operator.clear();
+        self.formula2.clear();
+        self
+    }
+
+    /// Set the "Ignore blank" option for the data validation.
+    ///
+    /// The "Ignore blank" option allows users to leave the cell blank without
+    /// triggering the data validation error. It is on by default.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is on by default.
+    ///
+    pub fn ignore_blank(mut self, enable: bool) -> DataValidation {
+        self.ignore_blank = enable;
+        self
+    }
+
+    /// Toggle the option to show the input message for the data validation.
+    ///
+    /// The input message is displayed when the user selects a cell with data
+    /// validation. It is on by default.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is on by default.
+    ///
+    pub fn show_input_message(mut self, enable: bool) -> DataValidation {
+        self.show_input_message = enable;
+        self
+    }
+
+    /// Toggle the option to show the error message for the data validation.
+    ///
+    /// The error message is displayed when the user enters data that doesn't
+    /// match the data validation rule. If it is turned off then Excel doesn't
+    /// enforce the data validation. It is on by default.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is on by default.
+    ///
+    pub fn show_error_message(mut self, enable: bool) -> DataValidation {
+        self.show_error_message = enable;
+        self
+    }
+
+    /// Toggle the option to show a dropdown for a list data validation.
+    ///
+    /// By default Excel displays a dropdown with the allowed values for list
+    /// data validations. This option can be used to turn it off so that the
+    /// user has to type in a value from the list.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is on by default.
+    ///
+    pub fn show_dropdown(mut self, enable: bool) -> DataValidation {
+        self.show_dropdown = enable;
+        self
+    }
+
+    /// Set the title for the input message of the data validation.
+    ///
+    /// The input message is displayed when the user selects a cell with data
+    /// validation. The title is displayed in bold above the message text.
+    /// Excel limits the title to 32 characters.
+    ///
+    /// # Parameters
+    ///
+    /// * `title` - The input message title.
+    ///
+    pub fn set_input_title(mut self, title: impl Into<String>) -> DataValidation {
+        self.input_title = title.into();
+        self
+    }
+
+    /// Set the input message of the data validation.
+    ///
+    /// The input message is displayed when the user selects a cell with data
+    /// validation. It can be used to explain the data validation rule to the
+    /// user. Excel limits the message to 255 characters. Newlines can be added
+    /// with `\n`.
+    ///
+    /// # Parameters
+    ///
+    /// * `message` - The input message text.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell with an input
+    /// message that is displayed when the cell is selected and a custom
+    /// warning message that is displayed if the user enters an invalid value.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_set_error_message.rs
+    /// #
+    /// # use rust_xlsxwriter::{
+    /// #     DataValidation, DataValidationErrorStyle, DataValidationRule, Workbook, XlsxError,
+    /// # };
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(1, 0, "Enter rating in cell D2:")?;
+    ///
+    ///     let data_validation = DataValidation::new()
+    ///         .allow_whole_number(DataValidationRule::Between(1, 10))
+    ///         .set_input_title("Enter a rating")
+    ///         .set_input_message("Enter an integer from 1 to 10")
+    ///         .set_error_style(DataValidationErrorStyle::Warning)
+    ///         .set_error_title("Value out of range")
+    ///         .set_error_message("The rating should be an integer from 1 to 10");
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_input_message(mut self, message: impl Into<String>) -> DataValidation {
+        self.input_message = message.into();
+        self
+    }
+
+    /// Set the title for the error message of the data validation.
+    ///
+    /// The error message is displayed when the user enters data that doesn't
+    /// match the data validation rule. If it isn't set then Excel displays the
+    /// default title "Microsoft Excel". Excel limits the title to 32
+    /// characters.
+    ///
+    /// # Parameters
+    ///
+    /// * `title` - The error message title.
+    ///
+    pub fn set_error_title(mut self, title: impl Into<String>) -> DataValidation {
+        self.error_title = title.into();
+        self
+    }
+
+    /// Set the error message of the data validation.
+    ///
+    /// The error message is displayed when the user enters data that doesn't
+    /// match the data validation rule. If it isn't set then Excel displays a
+    /// default message. Excel limits the message to 255 characters. Newlines
+    /// can be added with `\n`.
+    ///
+    /// See [`DataValidation::set_input_message()`] for an example.
+    ///
+    /// # Parameters
+    ///
+    /// * `message` - The error message text.
+    ///
+    pub fn set_error_message(mut self, message: impl Into<String>) -> DataValidation {
+        self.error_message = message.into();
+        self
+    }
+
+    /// Set the style of the error message of the data validation.
+    ///
+    /// The error style controls the icon and the options in the dialog that is
+    /// displayed when the user enters invalid data. The default "Stop" style
+    /// prevents the user from entering invalid data while the "Warning" and
+    /// "Information" styles allow the user to accept the data.
+    ///
+    /// See [`DataValidation::set_input_message()`] for an example.
+    ///
+    /// # Parameters
+    ///
+    /// * `error_style` - A [`DataValidationErrorStyle`] enum value.
+    ///
+    pub fn set_error_style(mut self, error_style: DataValidationErrorStyle) -> DataValidation {
+        self.error_style = error_style;
+        self
+    }
+
+    // Store the type, operator and limits of a data validation rule.
+    fn set_rule<T>(
+        mut self,
+        validation_type: DataValidationType,
+        rule: DataValidationRule<T>,
+        to_value: impl Fn(T) -> String,
+    ) -> DataValidation {
+        self.validation_type = validation_type;
+        self.operator = rule.to_string();
+
+        match rule {
+            DataValidationRule::EqualTo(value)
+            | DataValidationRule::NotEqualTo(value)
+            | DataValidationRule::GreaterThan(value)
+            | DataValidationRule::GreaterThanOrEqualTo(value)
+            | DataValidationRule::LessThan(value)
+            | DataValidationRule::LessThanOrEqualTo(value) => {
+                self.formula1 = to_value(value);
+                self.formula2 = String::new();
+            }
+            DataValidationRule::Between(min, max) | DataValidationRule::NotBetween(min, max) => {
+                self.formula1 = to_value(min);
+                self.formula2 = to_value(max);
+            }
+        }
+
+        self
+    }
+
+    // Convert a formula rule value to the string stored in the file.
+    fn formula_value(formula: Formula) -> String {
+        formula.expand_formula(true).to_string()
+    }
+
+    // Convert a date/time rule value to the string stored in the file.
+    fn datetime_value<T: IntoExcelDateTime>(datetime: T) -> String {
+        datetime.to_excel_serial_date().to_string()
+    }
+
+    // Validate the data validation.
+    pub(crate) fn validate(&self) -> Result<(), XlsxError> {
+        if self.validation_type == DataValidationType::List && self.formula1.is_empty() {
+            return Err(XlsxError::DataValidationError(
+                "List data validation must have a source range or formula".to_string(),
+            ));
+        }
+
+        let has_two_values = matches!(self.operator.as_str(), "between" | "notBetween");
+
+        if self.validation_type != DataValidationType::Any
+            && (self.formula1.is_empty() || (has_two_values && self.formula2.is_empty()))
+        {
+            return Err(XlsxError::DataValidationError(
+                "Data validation rule values must not be empty".to_string(),
+            ));
+        }
+
+        // Check Excel's limits on the message strings.
+        for (name, value, limit) in [
+            ("input title", &self.input_title, 32),
+            ("input message", &self.input_message, 255),
+            ("error title", &self.error_title, 32),
+            ("error message", &self.error_message, 255),
+        ] {
+            let length = value.chars().count();
+            if length > limit {
+                return Err(XlsxError::DataValidationError(format!(
+                    "Data validation {name} length {length} exceeds Excel's limit of {limit} characters"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------
+// DataValidationErrorStyle
+// -----------------------------------------------------------------------
+
+/// The `DataValidationErrorStyle` enum defines the type of error dialog that is
+/// shown when there is an error in a data validation.
+///
+/// See [`DataValidation::set_error_style()`].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DataValidationErrorStyle {
+    /// Show a "Stop" error dialog. The user can't enter invalid data. This is
+    /// the default.
+    #[default]
+    Stop,
+
+    /// Show a "Warning" error dialog. The user can choose to accept the
+    /// invalid data.
+    Warning,
+
+    /// Show an "Information" error dialog. The user can choose to accept the
+    /// invalid data.
+    Information,
+}
+
+impl fmt::Display for DataValidationErrorStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stop => write!(f, "stop"),
+            Self::Warning => write!(f, "warning"),
+            Self::Information => write!(f, "information"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// DataValidationRule
+// -----------------------------------------------------------------------
+
+/// The `DataValidationRule` enum defines the data validation rule for
+/// [`DataValidation`].
+///
+/// The rule is used with the numeric, date, time and text length data
+/// validations such as [`DataValidation::allow_whole_number()`]. The values
+/// can be constants of the type required by the data validation or, for the
+/// `_formula()` variants of the methods, [`Formula`] values.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataValidationRule<T> {
+    /// Restrict cell input to values that are equal to the target value.
+    EqualTo(T),
+
+    /// Restrict cell input to values that are not equal to the target value.
+    NotEqualTo(T),
+
+    /// Restrict cell input to values that are greater than the target value.
+    GreaterThan(T),
+
+    /// Restrict cell input to values that are greater than or equal to the
+    /// target value.
+    GreaterThanOrEqualTo(T),
+
+    /// Restrict cell input to values that are less than the target value.
+    LessThan(T),
+
+    /// Restrict cell input to values that are less than or equal to the target
+    /// value.
+    LessThanOrEqualTo(T),
+
+    /// Restrict cell input to values that are between the target values,
+    /// inclusive.
+    Between(T, T),
+
+    /// Restrict cell input to values that are not between the target values.
+    NotBetween(T, T),
+}
+
+impl<T> fmt::Display for DataValidationRule<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EqualTo(_) => write!(f, "equal"),
+            Self::Between(_, _) => write!(f, "between"),
+            Self::LessThan(_) => write!(f, "lessThan"),
+            Self::NotEqualTo(_) => write!(f, "notEqual"),
+            Self::NotBetween(_, _) => write!(f, "notBetween"),
+            Self::GreaterThan(_) => write!(f, "greaterThan"),
+            Self::LessThanOrEqualTo(_) => write!(f, "lessThanOrEqual"),
+            Self::GreaterThanOrEqualTo(_) => write!(f, "greaterThanOrEqual"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// DataValidationType
+// -----------------------------------------------------------------------
+
+// The `DataValidationType` enum defines the type of data validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DataValidationType {
+    Any,
+    Whole,
+    Decimal,
+    List,
+    Date,
+    Time,
+    TextLength,
+    Custom,
+}
+
+impl fmt::Display for DataValidationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "none"),
+            Self::Whole => write!(f, "whole"),
+            Self::Decimal => write!(f, "decimal"),
+            Self::List => write!(f, "list"),
+            Self::Date => write!(f, "date"),
+            Self::Time => write!(f, "time"),
+            Self::TextLength => write!(f, "textLength"),
+            Self::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// LegacyDataValidation
+// -----------------------------------------------------------------------
+
+// The original data validation API is kept as a separate type so that it
+// doesn't mix with the builder API. Its fields and setters are accessed from
+// DataValidation via Deref to keep existing code compiling.
+#[allow(deprecated)]
+impl Deref for DataValidation {
+    type Target = LegacyDataValidation;
+
+    fn deref(&self) -> &LegacyDataValidation {
+        &self.legacy
+    }
+}
+
+#[allow(deprecated)]
+impl DerefMut for DataValidation {
+    fn deref_mut(&mut self) -> &mut LegacyDataValidation {
+        &mut self.legacy
+    }
+}
+
+/// The properties of the deprecated data validation API.
+///
+/// Deprecated: use the [`DataValidation`] builder methods and
+/// [`Worksheet::add_data_validation()`](crate::Worksheet::add_data_validation)
+/// instead.
+///
+/// The fields and setters of this struct are available from a
+/// [`DataValidation`] instance and are only used by the deprecated
+/// [`Worksheet::set_data_validation()`](crate::Worksheet::set_data_validation)
+/// method. Where a setter has the same name as a [`DataValidation`] builder
+/// method the builder method takes precedence.
+///
+#[doc(hidden)]
+#[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
+#[derive(Clone, Default)]
+#[allow(deprecated, missing_docs)]
+pub struct LegacyDataValidation {
+    #[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
     pub validation_type: Option<String>,
-    // pub error_style: String
-    // pub ime_mode: String
-    // pub operator: String
+    #[deprecated(since = "0.65.0", note = "use DataValidation::ignore_blank()")]
     pub allow_blank: Option<bool>,
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub show_drop_down: Option<bool>,
+    #[deprecated(since = "0.65.0", note = "use DataValidation::show_input_message()")]
     pub show_input_message: Option<bool>,
+    #[deprecated(since = "0.65.0", note = "use DataValidation::show_error_message()")]
     pub show_error_message: Option<bool>,
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub error_title: Option<String>,
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub error: Option<String>,
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub prompt_title: Option<String>,
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub prompt: Option<String>,
+    #[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
     pub formula1: Option<Formula1>,
+    #[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
     pub formula2: Option<Formula2>,
+    #[deprecated(since = "0.65.0", note = "use Worksheet::add_data_validation()")]
     pub sqref: (String, String),
 }
 
-impl DataValidation {
-    pub fn new() -> Self {
-        DataValidation {
-            ..Default::default()
-        }
-    }
-
+#[allow(deprecated, missing_docs)]
+impl LegacyDataValidation {
+    #[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
     pub fn set_type(&mut self, value: &str) -> &mut Self {
         self.validation_type = Some(value.to_string());
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use DataValidation::ignore_blank()")]
     pub fn set_allow_blank(&mut self, value: bool) -> &mut Self {
         self.allow_blank = Some(value);
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub fn set_show_drop_down(&mut self, value: bool) -> &mut Self {
         self.show_drop_down = Some(value);
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use DataValidation::show_input_message()")]
     pub fn set_show_input_message(&mut self, value: bool) -> &mut Self {
         self.show_input_message = Some(value);
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use DataValidation::show_error_message()")]
     pub fn set_show_error_message(&mut self, value: bool) -> &mut Self {
         self.show_error_message = Some(value);
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub fn set_error_title(&mut self, value: &str) -> &mut Self {
         self.error_title = Some(value.to_string());
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub fn set_error(&mut self, value: &str) -> &mut Self {
         self.error = Some(value.to_string());
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub fn set_prompt_title(&mut self, value: &str) -> &mut Self {
         self.prompt_title = Some(value.to_string());
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use the DataValidation builder methods")]
     pub fn set_prompt(&mut self, value: &str) -> &mut Self {
         self.prompt = Some(value.to_string());
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
     pub fn set_formula1(&mut self, value: &str) -> &mut Self {
         self.formula1 = Some(Formula1 {
             value: value.to_string(),
@@ -76,6 +1079,7 @@ impl DataValidation {
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
     pub fn set_formula2(&mut self, value: &str) -> &mut Self {
         self.formula2 = Some(Formula2 {
             value: value.to_string(),
@@ -83,39 +1087,37 @@ impl DataValidation {
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use Worksheet::add_data_validation()")]
     pub fn set_sqref(&mut self, start: &str, end: &str) -> &mut Self {
         self.sqref = (start.to_string(), end.to_string());
         self
     }
 
+    #[deprecated(since = "0.65.0", note = "use Worksheet::add_data_validation()")]
     pub fn get_attributes(&self) -> Vec<(&str, String)> {
-        let mut attributes = Vec::new();
+        self.attributes()
+    }
+
+    // Get the attributes of the <dataValidation> element.
+    pub(crate) fn attributes(&self) -> Vec<(&str, String)> {
+        let mut attributes = vec![];
+
+        let flag = |enable: bool| if enable { "1" } else { "0" }.to_string();
+
         if let Some(validation_type) = &self.validation_type {
             attributes.push(("type", validation_type.clone()));
         }
-        if let Some(allow_blank) = &self.allow_blank {
-            attributes.push((
-                "allowBlank",
-                if *allow_blank { "1" } else { "0" }.to_string(),
-            ));
+        if let Some(allow_blank) = self.allow_blank {
+            attributes.push(("allowBlank", flag(allow_blank)));
         }
-        if let Some(show_drop_down) = &self.show_drop_down {
-            attributes.push((
-                "showDropDown",
-                if *show_drop_down { "1" } else { "0" }.to_string(),
-            ));
+        if let Some(show_drop_down) = self.show_drop_down {
+            attributes.push(("showDropDown", flag(show_drop_down)));
         }
-        if let Some(show_input_message) = &self.show_input_message {
-            attributes.push((
-                "showInputMessage",
-                if *show_input_message { "1" } else { "0" }.to_string(),
-            ));
+        if let Some(show_input_message) = self.show_input_message {
+            attributes.push(("showInputMessage", flag(show_input_message)));
         }
-        if let Some(show_error_message) = &self.show_error_message {
-            attributes.push((
-                "showErrorMessage",
-                if *show_error_message { "1" } else { "0" }.to_string(),
-            ));
+        if let Some(show_error_message) = self.show_error_message {
+            attributes.push(("showErrorMessage", flag(show_error_message)));
         }
         if let Some(error_title) = &self.error_title {
             attributes.push(("errorTitle", error_title.clone()));
@@ -134,14 +1136,51 @@ impl DataValidation {
 
         attributes
     }
+
+    // Get the formula1 value as written to the file. A list of values, such as
+    // "abc,xyz", is written as an Excel string and the "=" prefix of a formula
+    // is removed.
+    pub(crate) fn formula1_value(&self) -> Option<String> {
+        let value = &self.formula1.as_ref()?.value;
+
+        if self.validation_type.as_deref() == Some("list")
+            && !value.starts_with('=')
+            && !value.starts_with('"')
+        {
+            Some(format!("\"{}\"", value.replace('"', "\"\"")))
+        } else {
+            Some(value.trim_start_matches('=').to_string())
+        }
+    }
+
+    // Get the formula2 value as written to the file.
+    pub(crate) fn formula2_value(&self) -> Option<String> {
+        let value = &self.formula2.as_ref()?.value;
+
+        Some(value.trim_start_matches('=').to_string())
+    }
 }
 
+/// The first value or formula of a deprecated data validation.
+///
+/// Deprecated: use the [`DataValidation`] `allow_*()` methods instead.
+///
+#[doc(hidden)]
+#[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
 #[derive(Clone)]
 pub struct Formula1 {
+    /// The data validation value or formula.
     pub value: String,
 }
 
+/// The second value or formula of a deprecated data validation.
+///
+/// Deprecated: use the [`DataValidation`] `allow_*()` methods instead.
+///
+#[doc(hidden)]
+#[deprecated(since = "0.65.0", note = "use the DataValidation allow_*() methods")]
 #[derive(Clone)]
 pub struct Formula2 {
+    /// The data validation value or formula.
     pub value: String,
 }
@@ -0,0 +1,407 @@
+// data_validation unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod data_validation_tests {
+
+    use crate::test_functions::xml_to_vec;
+    use crate::worksheet::*;
+    use crate::DataValidation;
+    use crate::DataValidationErrorStyle;
+    use crate::DataValidationRule;
+    use crate::ExcelDateTime;
+    use crate::Formula;
+    use crate::XlsxError;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn data_validation_01() -> Result<(), XlsxError> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_selected(true);
+
+        let data_validation = DataValidation::new().allow_list_strings(&["Foo", "Bar", "Baz"])?;
+
+        worksheet.add_data_validation(1, 2, 1, 2, &data_validation)?;
+
+        let data_validation = DataValidation::new()
+            .allow_list_strings(&["Say \"Hi\""])?
+            .ignore_blank(false)
+            .show_input_message(false)
+            .show_error_message(false);
+
+        worksheet.add_data_validation(3, 0, 5, 1, &data_validation)?;
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData/>
+              <dataValidations count="2">
+                <dataValidation type="list" sqref="A4:B6">
+                  <formula1>"Say ""Hi"""</formula1>
+                </dataValidation>
+                <dataValidation type="list" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="C2">
+                  <formula1>"Foo,Bar,Baz"</formula1>
+                </dataValidation>
+              </dataValidations>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        Ok(())
+    }
+
+    #[test]
+    fn data_validation_list_limits() {
+        // The list must not be empty.
+        let list: [&str; 0] = [];
+        let result = DataValidation::new().allow_list_strings(&list);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        // The joined list can be up to 255 characters.
+        let list = ["a".repeat(127), "b".repeat(127)];
+        let result = DataValidation::new().allow_list_strings(&list);
+        assert!(result.is_ok());
+
+        // But not more.
+        let list = ["a".repeat(128), "b".repeat(127)];
+        let result = DataValidation::new().allow_list_strings(&list);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        // The limit is in characters rather than bytes.
+        let list = ["é".repeat(255)];
+        let result = DataValidation::new().allow_list_strings(&list);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn data_validation_range_errors() {
+        let mut worksheet = Worksheet::new();
+        let data_validation = DataValidation::new();
+
+        let result = worksheet.add_data_validation(5, 0, 1, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::RowColumnOrderError)));
+
+        let result = worksheet.add_data_validation(0, 0, ROW_MAX, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    #[test]
+    fn data_validation_02() -> Result<(), XlsxError> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_selected(true);
+
+        let data_validation = DataValidation::new().allow_list_formula("=$D$1:$D$10");
+        worksheet.add_data_validation(0, 0, 0, 0, &data_validation)?;
+
+        let data_validation = DataValidation::new().allow_list_formula(Formula::new("Categories"));
+        worksheet.add_data_validation(1, 0, 1, 0, &data_validation)?;
+
+        let data_validation =
+            DataValidation::new().allow_list_formula(Formula::new("='Sheet 2'!$A$1:$A$300"));
+        worksheet.add_data_validation(2, 0, 2, 0, &data_validation)?;
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData/>
+              <dataValidations count="3">
+                <dataValidation type="list" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A1">
+                  <formula1>$D$1:$D$10</formula1>
+                </dataValidation>
+                <dataValidation type="list" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A2">
+                  <formula1>Categories</formula1>
+                </dataValidation>
+                <dataValidation type="list" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A3">
+                  <formula1>'Sheet 2'!$A$1:$A$300</formula1>
+                </dataValidation>
+              </dataValidations>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // The list source must not be empty.
+        let data_validation = DataValidation::new().allow_list_formula("=");
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn data_validation_03() -> Result<(), XlsxError> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_selected(true);
+
+        let data_validation =
+            DataValidation::new().allow_whole_number(DataValidationRule::Between(1, 10));
+        worksheet.add_data_validation(0, 0, 0, 0, &data_validation)?;
+
+        let data_validation =
+            DataValidation::new().allow_decimal_number(DataValidationRule::GreaterThan(0.5));
+        worksheet.add_data_validation(1, 0, 1, 0, &data_validation)?;
+
+        let data_validation = DataValidation::new().allow_date(DataValidationRule::NotBetween(
+            ExcelDateTime::from_ymd(2024, 1, 1)?,
+            ExcelDateTime::from_ymd(2024, 12, 31)?,
+        ));
+        worksheet.add_data_validation(2, 0, 2, 0, &data_validation)?;
+
+        let data_validation = DataValidation::new().allow_time(
+            DataValidationRule::LessThanOrEqualTo(ExcelDateTime::from_hms(12, 0, 0)?),
+        );
+        worksheet.add_data_validation(3, 0, 3, 0, &data_validation)?;
+
+        let data_validation =
+            DataValidation::new().allow_text_length(DataValidationRule::NotEqualTo(5));
+        worksheet.add_data_validation(4, 0, 4, 0, &data_validation)?;
+
+        let data_validation = DataValidation::new().allow_whole_number_formula(
+            DataValidationRule::Between(Formula::new("=$B$1"), Formula::new("=MAX($B$1:$B$5)")),
+        );
+        worksheet.add_data_validation(5, 0, 5, 0, &data_validation)?;
+
+        let data_validation = DataValidation::new().allow_date_formula(
+            DataValidationRule::GreaterThanOrEqualTo(Formula::new("=TODAY()")),
+        );
+        worksheet.add_data_validation(6, 0, 6, 0, &data_validation)?;
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData/>
+              <dataValidations count="7">
+                <dataValidation type="whole" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A1">
+                  <formula1>1</formula1>
+                  <formula2>10</formula2>
+                </dataValidation>
+                <dataValidation type="decimal" operator="greaterThan" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A2">
+                  <formula1>0.5</formula1>
+                </dataValidation>
+                <dataValidation type="date" operator="notBetween" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A3">
+                  <formula1>45292</formula1>
+                  <formula2>45657</formula2>
+                </dataValidation>
+                <dataValidation type="time" operator="lessThanOrEqual" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A4">
+                  <formula1>0.5</formula1>
+                </dataValidation>
+                <dataValidation type="textLength" operator="notEqual" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A5">
+                  <formula1>5</formula1>
+                </dataValidation>
+                <dataValidation type="whole" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A6">
+                  <formula1>$B$1</formula1>
+                  <formula2>MAX($B$1:$B$5)</formula2>
+                </dataValidation>
+                <dataValidation type="date" operator="greaterThanOrEqual" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A7">
+                  <formula1>TODAY()</formula1>
+                </dataValidation>
+              </dataValidations>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // Formula limits must not be empty.
+        let data_validation = DataValidation::new().allow_decimal_number_formula(
+            DataValidationRule::Between(Formula::new("=A1"), Formula::new("")),
+        );
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        // A list type should clear any previous rule.
+        let data_validation = DataValidation::new()
+            .allow_whole_number(DataValidationRule::Between(1, 10))
+            .allow_list_formula("=$B$1:$B$5");
+        assert!(data_validation.operator.is_empty());
+        assert!(data_validation.formula2.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn data_validation_04() -> Result<(), XlsxError> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_selected(true);
+
+        let data_validation = DataValidation::new()
+            .allow_custom(r#"=LEFT(A1,3)="ID-""#)
+            .set_input_title("Enter an ID")
+            .set_input_message("IDs start with \"ID-\"\nFor example: ID-123")
+            .set_error_style(DataValidationErrorStyle::Information)
+            .set_error_title("Invalid ID")
+            .set_error_message("The ID is invalid");
+        worksheet.add_data_validation(0, 0, 9, 0, &data_validation)?;
+
+        let data_validation = DataValidation::new()
+            .allow_list_strings(&["Yes", "No"])?
+            .show_dropdown(false)
+            .set_error_style(DataValidationErrorStyle::Warning);
+        worksheet.add_data_validation(0, 1, 0, 1, &data_validation)?;
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData/>
+              <dataValidations count="2">
+                <dataValidation type="custom" errorStyle="information" allowBlank="1" showInputMessage="1" showErrorMessage="1" errorTitle="Invalid ID" error="The ID is invalid" promptTitle="Enter an ID" prompt="IDs start with &quot;ID-&quot;&#xA;For example: ID-123" sqref="A1:A10">
+                  <formula1>LEFT(A1,3)="ID-"</formula1>
+                </dataValidation>
+                <dataValidation type="list" errorStyle="warning" allowBlank="1" showDropDown="1" showInputMessage="1" showErrorMessage="1" sqref="B1">
+                  <formula1>"Yes,No"</formula1>
+                </dataValidation>
+              </dataValidations>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        Ok(())
+    }
+
+    #[test]
+    fn data_validation_message_limits() {
+        let mut worksheet = Worksheet::new();
+
+        let data_validation = DataValidation::new()
+            .set_input_title("a".repeat(32))
+            .set_input_message("a".repeat(255))
+            .set_error_title("a".repeat(32))
+            .set_error_message("a".repeat(255));
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(result.is_ok());
+
+        let data_validation = DataValidation::new().set_input_title("a".repeat(33));
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        let data_validation = DataValidation::new().set_input_message("a".repeat(256));
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        let data_validation = DataValidation::new().set_error_title("a".repeat(33));
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        let data_validation = DataValidation::new().set_error_message("a".repeat(256));
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+
+        // A custom rule must have a formula.
+        let data_validation = DataValidation::new().allow_custom("");
+        let result = worksheet.add_data_validation(0, 0, 0, 0, &data_validation);
+        assert!(matches!(result, Err(XlsxError::DataValidationError(_))));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn data_validation_legacy_api() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_selected(true);
+
+        let mut list = DataValidation::new();
+        list.set_sqref("A1", "A4");
+        list.set_formula1("abc,xyz");
+        list.set_type("list");
+
+        let mut whole = DataValidation::new();
+        whole.set_sqref("B1", "B1");
+        whole.set_type("whole");
+        whole.set_formula1("=1");
+        whole.set_formula2("10");
+        whole.set_allow_blank(true);
+        whole.set_show_input_message(true);
+        whole.set_prompt_title("Rating");
+        whole.set_prompt("Enter 1 to 10");
+
+        assert_eq!(whole.prompt_title.as_deref(), Some("Rating"));
+
+        worksheet.set_data_validation(vec![list, whole]);
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData/>
+              <dataValidations count="2">
+                <dataValidation type="list" sqref="A1:A4">
+                  <formula1>"abc,xyz"</formula1>
+                </dataValidation>
+                <dataValidation type="whole" allowBlank="1" showInputMessage="1" promptTitle="Rating" prompt="Enter 1 to 10" sqref="B1:B1">
+                  <formula1>1</formula1>
+                  <formula2>10</formula2>
+                </dataValidation>
+              </dataValidations>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+}
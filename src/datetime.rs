@@ -840,6 +840,70 @@ impl ExcelDateTime {
         Ok(dt)
     }
 
+    /// Create a `ExcelDateTime` instance from a Unix time in milliseconds.
+    ///
+    /// Create a `ExcelDateTime` instance from a [Unix Time] in milliseconds
+    /// since the 1970-01-01 00:00:00 UTC epoch. This is the format used by
+    /// many databases, JavaScript and logging systems. See
+    /// [`ExcelDateTime::from_timestamp()`] for the equivalent function for
+    /// timestamps in seconds.
+    ///
+    /// Leap seconds are not taken into account.
+    ///
+    /// [Unix Time]: https://en.wikipedia.org/wiki/Unix_time
+    ///
+    /// # Parameters
+    ///
+    /// * `timestamp` - Unix time in milliseconds in the range
+    ///   -2,209,075,200,000 to 253,402,300,800,000 (years 1900 to 9999).
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::DateTimeRangeError`] - One of the values used to create the
+    ///   date or time is outside Excel's allowed ranges.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating a datetime from a Unix
+    /// timestamp in milliseconds.
+    ///
+    /// ```
+    /// # use rust_xlsxwriter::{ExcelDateTime, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     // 2000-01-01 12:00:00.000 UTC.
+    ///     let datetime = ExcelDateTime::from_timestamp_millis(946_728_000_000)?;
+    ///
+    ///     assert_eq!(datetime.to_excel(), 36526.5);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_timestamp_millis(timestamp: i64) -> Result<ExcelDateTime, XlsxError> {
+        if !(-2_209_075_200_000..253_402_300_800_000).contains(&timestamp) {
+            return Err(XlsxError::DateTimeRangeError(format!(
+                "Unix timestamp: '{timestamp}' milliseconds outside converted Excel year range of 1900-9999"
+            )));
+        }
+
+        let days = (timestamp / (24 * 60 * 60 * 1000)) as f64;
+        let time = ((timestamp % (24 * 60 * 60 * 1000)) as f64) / (24.0 * 60.0 * 60.0 * 1000.0);
+        let mut datetime = 25568.0 + days + time;
+
+        if datetime >= 60.0 {
+            datetime += 1.0;
+        }
+
+        let dt = ExcelDateTime {
+            serial_datetime: Some(datetime),
+            ..ExcelDateTime::default()
+        };
+
+        Ok(dt)
+    }
+
     /// Convert the `ExcelDateTime` to an Excel serial date.
     ///
     /// An Excel serial date is a f64 number that represents the time since the
@@ -1187,6 +1251,63 @@ impl ExcelDateTime {
         format!("{year}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}Z",)
     }
 
+    // Convert an Excel serial datetime into its year, month, day, hour, minute
+    // and second components. This is the inverse of the 1900 epoch conversion
+    // in `to_excel_from_ymd_hms()` and it includes Excel's false 1900 leap day.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn serial_to_components(number: f64) -> [u16; 6] {
+        let mut days = number.trunc() as i64;
+        let mut seconds = (number.fract() * DAY_SECONDS as f64).round() as u64;
+
+        // Carry any rounding of the time part over to the next day.
+        if seconds >= DAY_SECONDS {
+            seconds -= DAY_SECONDS;
+            days += 1;
+        }
+
+        let hour = seconds / HOUR_SECONDS;
+        let min = (seconds % HOUR_SECONDS) / MINUTE_SECONDS;
+        let sec = seconds % MINUTE_SECONDS;
+
+        // Excel treats 1900 as a leap year so there is a false 1900-02-29.
+        if days == 60 {
+            return [1900, 2, 29, hour as u16, min as u16, sec as u16];
+        }
+
+        // Adjust for the false leap day and move the epoch from 1899-12-31 to
+        // 1970-01-01.
+        if days > 60 {
+            days -= 1;
+        }
+        let days = days - 25_568;
+
+        // Convert the days since 1970-01-01 to a civil date using Howard
+        // Hinnant's days to civil date algorithm.
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        [
+            year as u16,
+            month as u16,
+            day as u16,
+            hour as u16,
+            min as u16,
+            sec as u16,
+        ]
+    }
+
     // Check if a year is a leap year.
     pub(crate) fn is_leap_year(year: u64) -> bool {
         year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
@@ -68,6 +68,12 @@ mod datetime_tests {
 
         let result = ExcelDateTime::from_timestamp(253402300800);
         assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
+
+        let result = ExcelDateTime::from_timestamp_millis(-2209075200001);
+        assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
+
+        let result = ExcelDateTime::from_timestamp_millis(253402300800000);
+        assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
     }
 
     #[test]
@@ -1318,7 +1324,13 @@ mod datetime_tests {
             let (timestamp, expected) = test_data;
             let datetime = ExcelDateTime::from_timestamp(timestamp).unwrap();
             assert_eq!(expected, datetime.to_excel());
+
+            let datetime = ExcelDateTime::from_timestamp_millis(timestamp * 1000).unwrap();
+            assert_eq!(expected, datetime.to_excel());
         }
+
+        let datetime = ExcelDateTime::from_timestamp_millis(946_684_800_250).unwrap();
+        assert_eq!(36526.0 + 0.25 / 86400.0, datetime.to_excel());
     }
 
     #[test]
@@ -1950,4 +1962,44 @@ mod datetime_tests {
             assert!(diff < 0.00000000001);
         }
     }
+
+    #[test]
+    fn serial_to_components() {
+        let dates = [
+            (1899, 12, 31),
+            (1900, 1, 1),
+            (1900, 2, 28),
+            (1900, 2, 29),
+            (1900, 3, 1),
+            (1970, 1, 1),
+            (2000, 2, 29),
+            (2023, 12, 31),
+            (2024, 3, 15),
+            (9999, 12, 31),
+        ];
+
+        for (year, month, day) in dates {
+            let serial = if (year, month, day) == (1900, 2, 29) {
+                60.0
+            } else {
+                ExcelDateTime::from_ymd(year, month, day)
+                    .unwrap()
+                    .to_excel()
+            };
+
+            let got = ExcelDateTime::serial_to_components(serial);
+            assert_eq!([year, u16::from(month), u16::from(day), 0, 0, 0], got);
+        }
+
+        let datetime = ExcelDateTime::from_ymd(2024, 3, 15)
+            .unwrap()
+            .and_hms(13, 45, 30)
+            .unwrap();
+        let got = ExcelDateTime::serial_to_components(datetime.to_excel());
+        assert_eq!([2024, 3, 15, 13, 45, 30], got);
+
+        // Test that rounding up to midnight carries over to the next day.
+        let got = ExcelDateTime::serial_to_components(45_366.999_999_9);
+        assert_eq!([2024, 3, 16, 0, 0, 0], got);
+    }
 }
@@ -87,6 +87,8 @@ impl Drawing {
         match drawing_info.drawing_type {
             DrawingType::Image => self.write_pic(index, drawing_info),
             DrawingType::Chart => self.write_graphic_frame(index, drawing_info),
+            // Notes and other vml objects are stored in a vmlDrawing file.
+            DrawingType::Vml => {}
         }
 
         self.writer.xml_empty_tag_only("xdr:clientData");
@@ -433,6 +435,7 @@ pub(crate) struct DrawingInfo {
 pub(crate) enum DrawingType {
     Image,
     Chart,
+    Vml,
 }
 
 // Trait for object such as Images and Charts that translate to a Drawing object.
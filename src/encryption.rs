@@ -0,0 +1,464 @@
+// encryption - A module for encrypting xlsx files using the ECMA-376 "Agile
+// Encryption" scheme used by Excel 2010 and later.
+//
+// The encrypted file is an OLE Compound File that contains the xlsx zip
+// package encrypted with AES-256 and an XML descriptor with the information
+// required to derive the key from the password. See the [MS-OFFCRYPTO]
+// specification for details.
+//
+// [MS-OFFCRYPTO]: https://learn.microsoft.com/en-us/openspecs/office_file_formats/ms-offcrypto
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+mod tests;
+
+use aes::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use aes::Aes256;
+use sha2::{Digest, Sha512};
+
+use crate::compound_file::CompoundFile;
+use crate::utility;
+use crate::xmlwriter::XMLWriter;
+use crate::XlsxError;
+
+// Encryption parameters. These match the defaults used by Excel.
+const SPIN_COUNT: u32 = 100_000;
+const SALT_SIZE: usize = 16;
+const BLOCK_SIZE: usize = 16;
+const KEY_SIZE: usize = 32;
+const HASH_SIZE: usize = 64;
+const SEGMENT_SIZE: usize = 4096;
+
+// Block keys used to derive the keys and IVs for the various encrypted values.
+const VERIFIER_HASH_INPUT_BLOCK_KEY: [u8; 8] = [0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79];
+const VERIFIER_HASH_VALUE_BLOCK_KEY: [u8; 8] = [0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e];
+const ENCRYPTED_KEY_VALUE_BLOCK_KEY: [u8; 8] = [0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6];
+const HMAC_KEY_BLOCK_KEY: [u8; 8] = [0x5f, 0xb2, 0xad, 0x01, 0x0c, 0xb9, 0xe1, 0xf6];
+const HMAC_VALUE_BLOCK_KEY: [u8; 8] = [0xa0, 0x67, 0x7f, 0x02, 0xb2, 0x2c, 0x84, 0x33];
+
+pub(crate) struct Encryptor {
+    pub(crate) writer: XMLWriter,
+    spin_count: u32,
+    password_salt: [u8; SALT_SIZE],
+    key_data_salt: [u8; SALT_SIZE],
+    secret_key: [u8; KEY_SIZE],
+    verifier_hash_input: [u8; SALT_SIZE],
+    hmac_key: [u8; HASH_SIZE],
+}
+
+impl Encryptor {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new Encryptor struct with random salts and keys.
+    pub(crate) fn new() -> Result<Encryptor, XlsxError> {
+        let mut encryptor = Encryptor {
+            writer: XMLWriter::new(),
+            spin_count: SPIN_COUNT,
+            password_salt: [0; SALT_SIZE],
+            key_data_salt: [0; SALT_SIZE],
+            secret_key: [0; KEY_SIZE],
+            verifier_hash_input: [0; SALT_SIZE],
+            hmac_key: [0; HASH_SIZE],
+        };
+
+        for buffer in [
+            &mut encryptor.password_salt[..],
+            &mut encryptor.key_data_salt[..],
+            &mut encryptor.secret_key[..],
+            &mut encryptor.verifier_hash_input[..],
+            &mut encryptor.hmac_key[..],
+        ] {
+            getrandom::getrandom(buffer).map_err(|err| XlsxError::IoError(err.into()))?;
+        }
+
+        Ok(encryptor)
+    }
+
+    // Encrypt an xlsx zip package with a password and return the data of the
+    // encrypted compound file.
+    pub(crate) fn encrypt_file(
+        &mut self,
+        package: &[u8],
+        password: &str,
+    ) -> Result<Vec<u8>, XlsxError> {
+        if password.is_empty() {
+            return Err(XlsxError::ParameterError(
+                "Encryption password cannot be empty".to_string(),
+            ));
+        }
+
+        if password.chars().count() > 255 {
+            return Err(XlsxError::ParameterError(
+                "Encryption password must be 255 characters or less".to_string(),
+            ));
+        }
+
+        let encrypted_package = self.encrypt_package(package);
+        self.assemble_xml_file(password, &encrypted_package);
+
+        // The EncryptionInfo stream is a version header followed by the XML
+        // descriptor.
+        let mut encryption_info = vec![];
+        encryption_info.extend_from_slice(&4_u16.to_le_bytes());
+        encryption_info.extend_from_slice(&4_u16.to_le_bytes());
+        encryption_info.extend_from_slice(&0x40_u32.to_le_bytes());
+        encryption_info.extend_from_slice(self.writer.read_to_str().as_bytes());
+
+        let mut compound_file = CompoundFile::new();
+        compound_file.add_stream("EncryptionInfo", encryption_info);
+        compound_file.add_stream("EncryptedPackage", encrypted_package);
+        compound_file.add_stream("\u{6}DataSpaces/Version", Self::data_space_version());
+        compound_file.add_stream("\u{6}DataSpaces/DataSpaceMap", Self::data_space_map());
+        compound_file.add_stream(
+            "\u{6}DataSpaces/DataSpaceInfo/StrongEncryptionDataSpace",
+            Self::data_space_definition(),
+        );
+        compound_file.add_stream(
+            "\u{6}DataSpaces/TransformInfo/StrongEncryptionTransform/\u{6}Primary",
+            Self::transform_info(),
+        );
+
+        Ok(compound_file.assemble_file())
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal functions/methods.
+    // -----------------------------------------------------------------------
+
+    // Encrypt the package in 4096 byte segments. Each segment uses an IV
+    // derived from the key data salt and the segment number. The encrypted
+    // data is prefixed with the size of the unencrypted package.
+    fn encrypt_package(&self, package: &[u8]) -> Vec<u8> {
+        let mut encrypted_package = Vec::with_capacity(package.len() + BLOCK_SIZE + 8);
+        encrypted_package.extend_from_slice(&(package.len() as u64).to_le_bytes());
+
+        for (index, segment) in package.chunks(SEGMENT_SIZE).enumerate() {
+            let iv = Self::hash_iv(&self.key_data_salt, &(index as u32).to_le_bytes());
+            let encrypted = Self::aes_cbc_encrypt(&self.secret_key, &iv, segment);
+            encrypted_package.extend_from_slice(&encrypted);
+        }
+
+        encrypted_package
+    }
+
+    // Derive the password key for one of the key encryptor block keys.
+    fn password_key(password_hash: &[u8], block_key: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha512::new();
+        hasher.update(password_hash);
+        hasher.update(block_key);
+        hasher.finalize()[..KEY_SIZE].to_vec()
+    }
+
+    // Hash the password with the salt and spin count. Note, unlike the
+    // worksheet protection hash the iteration count is prepended to the hash
+    // on each iteration.
+    fn hash_password(&self, password: &str) -> Vec<u8> {
+        let mut hasher = Sha512::new();
+        hasher.update(self.password_salt);
+        for code_unit in password.encode_utf16() {
+            hasher.update(code_unit.to_le_bytes());
+        }
+        let mut hash = hasher.finalize();
+
+        for iteration in 0..self.spin_count {
+            let mut hasher = Sha512::new();
+            hasher.update(iteration.to_le_bytes());
+            hasher.update(hash);
+            hash = hasher.finalize();
+        }
+
+        hash.to_vec()
+    }
+
+    // Generate a 16 byte IV from the hash of a salt and a block key.
+    fn hash_iv(salt: &[u8], block_key: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha512::new();
+        hasher.update(salt);
+        hasher.update(block_key);
+        hasher.finalize()[..BLOCK_SIZE].to_vec()
+    }
+
+    // Encrypt data using AES-256 in CBC mode. The data is padded with zeros
+    // to a multiple of the block size.
+    fn aes_cbc_encrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+        let cipher = Aes256::new(GenericArray::from_slice(key));
+        let mut encrypted = Vec::with_capacity(data.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE);
+        let mut previous = [0u8; BLOCK_SIZE];
+        previous.copy_from_slice(iv);
+
+        for chunk in data.chunks(BLOCK_SIZE) {
+            let mut block = GenericArray::from([0u8; BLOCK_SIZE]);
+            for (index, byte) in chunk.iter().enumerate() {
+                block[index] = byte ^ previous[index];
+            }
+            for index in chunk.len()..BLOCK_SIZE {
+                block[index] = previous[index];
+            }
+
+            cipher.encrypt_block(&mut block);
+            previous.copy_from_slice(&block);
+            encrypted.extend_from_slice(&block);
+        }
+
+        encrypted
+    }
+
+    // Calculate the HMAC-SHA512 of the data. This is used to verify the
+    // integrity of the encrypted package.
+    fn hmac_sha512(key: &[u8], data: &[u8]) -> Vec<u8> {
+        const HMAC_BLOCK_SIZE: usize = 128;
+
+        let mut inner_pad = [0x36u8; HMAC_BLOCK_SIZE];
+        let mut outer_pad = [0x5cu8; HMAC_BLOCK_SIZE];
+        for (index, byte) in key.iter().enumerate() {
+            inner_pad[index] ^= byte;
+            outer_pad[index] ^= byte;
+        }
+
+        let mut hasher = Sha512::new();
+        hasher.update(inner_pad);
+        hasher.update(data);
+        let inner_hash = hasher.finalize();
+
+        let mut hasher = Sha512::new();
+        hasher.update(outer_pad);
+        hasher.update(inner_hash);
+        hasher.finalize().to_vec()
+    }
+
+    // Encode a string as a length prefixed UTF-16LE string padded to a 4 byte
+    // boundary, the UNICODE-LP-P4 structure used in the DataSpaces streams.
+    fn unicode_lp_p4(string: &str) -> Vec<u8> {
+        let mut buffer = vec![];
+        let data: Vec<u8> = string.encode_utf16().flat_map(u16::to_le_bytes).collect();
+
+        buffer.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(&data);
+        buffer.resize(buffer.len().next_multiple_of(4), 0);
+
+        buffer
+    }
+
+    // Create the data for the \x06DataSpaces/Version stream.
+    fn data_space_version() -> Vec<u8> {
+        let mut buffer = Self::unicode_lp_p4("Microsoft.Container.DataSpaces");
+
+        // The reader, updater and writer versions are all 1.0.
+        for _ in 0..3 {
+            buffer.extend_from_slice(&1_u16.to_le_bytes());
+            buffer.extend_from_slice(&0_u16.to_le_bytes());
+        }
+
+        buffer
+    }
+
+    // Create the data for the \x06DataSpaces/DataSpaceMap stream.
+    fn data_space_map() -> Vec<u8> {
+        let mut entry = vec![];
+        entry.extend_from_slice(&1_u32.to_le_bytes()); // Reference count.
+        entry.extend_from_slice(&0_u32.to_le_bytes()); // Reference type: stream.
+        entry.extend_from_slice(&Self::unicode_lp_p4("EncryptedPackage"));
+        entry.extend_from_slice(&Self::unicode_lp_p4("StrongEncryptionDataSpace"));
+
+        let mut buffer = vec![];
+        buffer.extend_from_slice(&8_u32.to_le_bytes()); // Header length.
+        buffer.extend_from_slice(&1_u32.to_le_bytes()); // Entry count.
+        buffer.extend_from_slice(&(entry.len() as u32 + 4).to_le_bytes());
+        buffer.extend_from_slice(&entry);
+
+        buffer
+    }
+
+    // Create the data for the \x06DataSpaces/DataSpaceInfo stream.
+    fn data_space_definition() -> Vec<u8> {
+        let mut buffer = vec![];
+        buffer.extend_from_slice(&8_u32.to_le_bytes()); // Header length.
+        buffer.extend_from_slice(&1_u32.to_le_bytes()); // Transform count.
+        buffer.extend_from_slice(&Self::unicode_lp_p4("StrongEncryptionTransform"));
+
+        buffer
+    }
+
+    // Create the data for the \x06DataSpaces/TransformInfo \x06Primary stream.
+    fn transform_info() -> Vec<u8> {
+        let transform_id = Self::unicode_lp_p4("{FF9A3F03-56EF-4613-BDD5-5A41C1D07246}");
+
+        let mut buffer = vec![];
+        buffer.extend_from_slice(&(transform_id.len() as u32 + 8).to_le_bytes());
+        buffer.extend_from_slice(&1_u32.to_le_bytes()); // Transform type.
+        buffer.extend_from_slice(&transform_id);
+        buffer.extend_from_slice(&Self::unicode_lp_p4(
+            "Microsoft.Container.EncryptionTransform",
+        ));
+
+        // The reader, updater and writer versions are all 1.0.
+        for _ in 0..3 {
+            buffer.extend_from_slice(&1_u16.to_le_bytes());
+            buffer.extend_from_slice(&0_u16.to_le_bytes());
+        }
+
+        // The EncryptionTransformInfo structure. The name, block size and
+        // cipher mode are unused for agile encryption.
+        buffer.extend_from_slice(&0_u32.to_le_bytes());
+        buffer.extend_from_slice(&0_u32.to_le_bytes());
+        buffer.extend_from_slice(&0_u32.to_le_bytes());
+        buffer.extend_from_slice(&4_u32.to_le_bytes());
+
+        buffer
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and write the XML encryption descriptor.
+    fn assemble_xml_file(&mut self, password: &str, encrypted_package: &[u8]) {
+        self.writer.reset();
+        self.writer.xml_declaration();
+
+        // Write the encryption element.
+        self.write_encryption();
+
+        // Write the keyData element.
+        self.write_key_data();
+
+        // Write the dataIntegrity element.
+        self.write_data_integrity(encrypted_package);
+
+        // Write the keyEncryptors element.
+        self.write_key_encryptors(password);
+
+        // Close the encryption tag.
+        self.writer.xml_end_tag("encryption");
+    }
+
+    // Write the <encryption> element.
+    fn write_encryption(&mut self) {
+        let attributes = [
+            (
+                "xmlns",
+                "http://schemas.microsoft.com/office/2006/encryption",
+            ),
+            (
+                "xmlns:p",
+                "http://schemas.microsoft.com/office/2006/keyEncryptor/password",
+            ),
+            (
+                "xmlns:c",
+                "http://schemas.microsoft.com/office/2006/keyEncryptor/certificate",
+            ),
+        ];
+
+        self.writer.xml_start_tag("encryption", &attributes);
+    }
+
+    // Write the <keyData> element.
+    fn write_key_data(&mut self) {
+        let mut attributes = Self::cipher_attributes();
+        attributes.push(("saltValue", utility::base64_encode(&self.key_data_salt)));
+
+        self.writer.xml_empty_tag("keyData", &attributes);
+    }
+
+    // Write the <dataIntegrity> element.
+    fn write_data_integrity(&mut self, encrypted_package: &[u8]) {
+        let iv = Self::hash_iv(&self.key_data_salt, &HMAC_KEY_BLOCK_KEY);
+        let encrypted_hmac_key = Self::aes_cbc_encrypt(&self.secret_key, &iv, &self.hmac_key);
+
+        let hmac_value = Self::hmac_sha512(&self.hmac_key, encrypted_package);
+        let iv = Self::hash_iv(&self.key_data_salt, &HMAC_VALUE_BLOCK_KEY);
+        let encrypted_hmac_value = Self::aes_cbc_encrypt(&self.secret_key, &iv, &hmac_value);
+
+        let attributes = [
+            (
+                "encryptedHmacKey",
+                utility::base64_encode(&encrypted_hmac_key),
+            ),
+            (
+                "encryptedHmacValue",
+                utility::base64_encode(&encrypted_hmac_value),
+            ),
+        ];
+
+        self.writer.xml_empty_tag("dataIntegrity", &attributes);
+    }
+
+    // Write the <keyEncryptors> element.
+    fn write_key_encryptors(&mut self, password: &str) {
+        self.writer.xml_start_tag_only("keyEncryptors");
+
+        // Write the keyEncryptor element.
+        self.write_key_encryptor(password);
+
+        self.writer.xml_end_tag("keyEncryptors");
+    }
+
+    // Write the <keyEncryptor> element.
+    fn write_key_encryptor(&mut self, password: &str) {
+        let attributes = [(
+            "uri",
+            "http://schemas.microsoft.com/office/2006/keyEncryptor/password",
+        )];
+
+        self.writer.xml_start_tag("keyEncryptor", &attributes);
+
+        // Write the p:encryptedKey element.
+        self.write_encrypted_key(password);
+
+        self.writer.xml_end_tag("keyEncryptor");
+    }
+
+    // Write the <p:encryptedKey> element.
+    fn write_encrypted_key(&mut self, password: &str) {
+        let password_hash = self.hash_password(password);
+        let salt = self.password_salt;
+
+        let key = Self::password_key(&password_hash, &VERIFIER_HASH_INPUT_BLOCK_KEY);
+        let encrypted_verifier_hash_input =
+            Self::aes_cbc_encrypt(&key, &salt, &self.verifier_hash_input);
+
+        let verifier_hash_value = Sha512::digest(self.verifier_hash_input);
+        let key = Self::password_key(&password_hash, &VERIFIER_HASH_VALUE_BLOCK_KEY);
+        let encrypted_verifier_hash_value =
+            Self::aes_cbc_encrypt(&key, &salt, &verifier_hash_value);
+
+        let key = Self::password_key(&password_hash, &ENCRYPTED_KEY_VALUE_BLOCK_KEY);
+        let encrypted_key_value = Self::aes_cbc_encrypt(&key, &salt, &self.secret_key);
+
+        let mut attributes = vec![("spinCount", self.spin_count.to_string())];
+        attributes.append(&mut Self::cipher_attributes());
+        attributes.push(("saltValue", utility::base64_encode(&salt)));
+        attributes.push((
+            "encryptedVerifierHashInput",
+            utility::base64_encode(&encrypted_verifier_hash_input),
+        ));
+        attributes.push((
+            "encryptedVerifierHashValue",
+            utility::base64_encode(&encrypted_verifier_hash_value),
+        ));
+        attributes.push((
+            "encryptedKeyValue",
+            utility::base64_encode(&encrypted_key_value),
+        ));
+
+        self.writer.xml_empty_tag("p:encryptedKey", &attributes);
+    }
+
+    // Get the cipher attributes shared by the <keyData> and <p:encryptedKey>
+    // elements.
+    fn cipher_attributes() -> Vec<(&'static str, String)> {
+        vec![
+            ("saltSize", SALT_SIZE.to_string()),
+            ("blockSize", BLOCK_SIZE.to_string()),
+            ("keyBits", (KEY_SIZE * 8).to_string()),
+            ("hashSize", HASH_SIZE.to_string()),
+            ("cipherAlgorithm", "AES".to_string()),
+            ("cipherChaining", "ChainingModeCBC".to_string()),
+            ("hashAlgorithm", "SHA512".to_string()),
+        ]
+    }
+}
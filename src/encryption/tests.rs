@@ -0,0 +1,298 @@
+// Encryption unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod encryption_tests {
+
+    use crate::encryption::{
+        Encryptor, ENCRYPTED_KEY_VALUE_BLOCK_KEY, HMAC_KEY_BLOCK_KEY, HMAC_VALUE_BLOCK_KEY,
+        VERIFIER_HASH_INPUT_BLOCK_KEY, VERIFIER_HASH_VALUE_BLOCK_KEY,
+    };
+    use crate::test_functions::xml_to_vec;
+    use crate::xmlwriter::XMLWriter;
+    use crate::{Workbook, XlsxError};
+    use aes::cipher::{generic_array::GenericArray, BlockDecrypt, KeyInit};
+    use aes::Aes256;
+    use pretty_assertions::assert_eq;
+    use sha2::{Digest, Sha512};
+
+    // Create an Encryptor with fixed salts and keys so that the output is
+    // repeatable.
+    fn test_encryptor() -> Encryptor {
+        let mut encryptor = Encryptor {
+            writer: XMLWriter::new(),
+            spin_count: 100_000,
+            password_salt: [0; 16],
+            key_data_salt: [0; 16],
+            secret_key: [0; 32],
+            verifier_hash_input: [0; 16],
+            hmac_key: [0; 64],
+        };
+
+        for (index, byte) in encryptor
+            .password_salt
+            .iter_mut()
+            .chain(encryptor.key_data_salt.iter_mut())
+            .chain(encryptor.secret_key.iter_mut())
+            .chain(encryptor.verifier_hash_input.iter_mut())
+            .chain(encryptor.hmac_key.iter_mut())
+            .enumerate()
+        {
+            *byte = index as u8;
+        }
+
+        encryptor
+    }
+
+    #[test]
+    fn test_assemble() {
+        let mut encryptor = test_encryptor();
+
+        let encrypted_package = encryptor.encrypt_package(b"Hello");
+        encryptor.assemble_xml_file("password", &encrypted_package);
+
+        let got = encryptor.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <encryption xmlns="http://schemas.microsoft.com/office/2006/encryption" xmlns:p="http://schemas.microsoft.com/office/2006/keyEncryptor/password" xmlns:c="http://schemas.microsoft.com/office/2006/keyEncryptor/certificate">
+              <keyData saltSize="16" blockSize="16" keyBits="256" hashSize="64" cipherAlgorithm="AES" cipherChaining="ChainingModeCBC" hashAlgorithm="SHA512" saltValue="EBESExQVFhcYGRobHB0eHw=="/>
+              <dataIntegrity encryptedHmacKey="Gft0QK23K3ymgkMqGb6WAcbpg+Km2/QR8fTZd7Ah0hMLnsQtx/gEKt6P+3MCou/mBoxxIZ8VSwOId5XbnENang==" encryptedHmacValue="ro1tzmcAC4UxOrIXu9ZU1QzYPIIt0kznarI8jo+d2l1uJkKXspwpVMn1aCan41B+Lgi05SCWEAhuis5Q43StRA=="/>
+              <keyEncryptors>
+                <keyEncryptor uri="http://schemas.microsoft.com/office/2006/keyEncryptor/password">
+                  <p:encryptedKey spinCount="100000" saltSize="16" blockSize="16" keyBits="256" hashSize="64" cipherAlgorithm="AES" cipherChaining="ChainingModeCBC" hashAlgorithm="SHA512" saltValue="AAECAwQFBgcICQoLDA0ODw==" encryptedVerifierHashInput="JobI4YmVPCf7SdIvjgE0MQ==" encryptedVerifierHashValue="hYl90LTDQgU7XTHfWxRlh/gF5rdC6VKz2yh+fH8ds5lXFCTrlf/tiQVSp2ZiuTCKDSb2GotROb5ABuBw8hYk2g==" encryptedKeyValue="RYrOxP6anm7V+jzuGa0QeSSbzZ35xRPijTBHbjLSNTc="/>
+                </keyEncryptor>
+              </keyEncryptors>
+            </encryption>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_encrypt_package() {
+        let encryptor = test_encryptor();
+
+        let got = encryptor.encrypt_package(b"Hello");
+        let expected = vec![
+            5, 0, 0, 0, 0, 0, 0, 0, 175, 143, 117, 53, 138, 0, 196, 38, 91, 122, 242, 77, 78, 167,
+            186, 143,
+        ];
+
+        assert_eq!(expected, got);
+
+        // Check the segment and padding sizes.
+        let got = encryptor.encrypt_package(&[0; 4097]);
+        assert_eq!(8 + 4096 + 16, got.len());
+    }
+
+    #[test]
+    fn test_hmac_sha512() {
+        // Test case 2 from RFC 4231.
+        let got = Encryptor::hmac_sha512(b"Jefe", b"what do ya want for nothing?");
+        let expected = [
+            0x16, 0x4b, 0x7a, 0x7b, 0xfc, 0xf8, 0x19, 0xe2, 0xe3, 0x95, 0xfb, 0xe7, 0x3b, 0x56,
+            0xe0, 0xa3, 0x87, 0xbd, 0x64, 0x22, 0x2e, 0x83, 0x1f, 0xd6, 0x10, 0x27, 0x0c, 0xd7,
+            0xea, 0x25, 0x05, 0x54, 0x97, 0x58, 0xbf, 0x75, 0xc0, 0x5a, 0x99, 0x4a, 0x6d, 0x03,
+            0x4f, 0x65, 0xf8, 0xf0, 0xe6, 0xfd, 0xca, 0xea, 0xb1, 0xa3, 0x4d, 0x4a, 0x6b, 0x4b,
+            0x63, 0x6e, 0x07, 0x0a, 0x38, 0xbc, 0xe7, 0x37,
+        ];
+
+        assert_eq!(expected.to_vec(), got);
+    }
+
+    #[test]
+    fn test_data_spaces() {
+        let got = Encryptor::data_space_map();
+        assert_eq!(112, got.len());
+        assert_eq!(104, u32::from_le_bytes([got[8], got[9], got[10], got[11]]));
+
+        let got = Encryptor::transform_info();
+        assert_eq!(88, u32::from_le_bytes([got[0], got[1], got[2], got[3]]));
+        assert_eq!(200, got.len());
+
+        let got = Encryptor::data_space_version();
+        assert_eq!(76, got.len());
+    }
+
+    // Decode a standard, padded, Base64 string.
+    fn base64_decode(data: &str) -> Vec<u8> {
+        const CHARS: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let mut decoded = vec![];
+        let mut bits = 0u32;
+        let mut num_bits = 0;
+        for byte in data.bytes().take_while(|&byte| byte != b'=') {
+            let value = CHARS.iter().position(|&c| c == byte).unwrap() as u32;
+            bits = (bits << 6) | value;
+            num_bits += 6;
+
+            if num_bits >= 8 {
+                num_bits -= 8;
+                decoded.push((bits >> num_bits) as u8);
+                bits &= (1 << num_bits) - 1;
+            }
+        }
+
+        decoded
+    }
+
+    // Get the value of an attribute of the first matching element in the
+    // EncryptionInfo XML.
+    fn attribute<'a>(xml: &'a str, element: &str, name: &str) -> &'a str {
+        let start = xml.find(&format!("<{element} ")).unwrap();
+        let xml = &xml[start..];
+        let start = xml.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+        let end = start + xml[start..].find('"').unwrap();
+
+        &xml[start..end]
+    }
+
+    // Decrypt data using AES-256 in CBC mode.
+    fn aes_cbc_decrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+        let cipher = Aes256::new(GenericArray::from_slice(key));
+        let mut decrypted = Vec::with_capacity(data.len());
+        let mut previous = iv.to_vec();
+
+        for chunk in data.chunks(16) {
+            let mut block = GenericArray::clone_from_slice(chunk);
+            cipher.decrypt_block(&mut block);
+            decrypted.extend(block.iter().zip(&previous).map(|(byte, iv)| byte ^ iv));
+            previous = chunk.to_vec();
+        }
+
+        decrypted
+    }
+
+    // Derive a key from the password and a block key, see [MS-OFFCRYPTO]
+    // 2.3.4.11.
+    fn password_key(password: &str, salt: &[u8], spin_count: u32, block_key: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha512::new();
+        hasher.update(salt);
+        for code_unit in password.encode_utf16() {
+            hasher.update(code_unit.to_le_bytes());
+        }
+        let mut hash = hasher.finalize();
+
+        for iteration in 0..spin_count {
+            hash = Sha512::new()
+                .chain_update(iteration.to_le_bytes())
+                .chain_update(hash)
+                .finalize();
+        }
+
+        Sha512::new()
+            .chain_update(hash)
+            .chain_update(block_key)
+            .finalize()[..32]
+            .to_vec()
+    }
+
+    #[test]
+    fn test_decrypt_package() {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        for row in 0..100 {
+            worksheet.write(row, 0, format!("Row {row}")).unwrap();
+        }
+        let package = workbook.save_to_buffer().unwrap();
+        assert!(package.len() > 4096);
+
+        let password = "Pässwörd";
+        let mut encryptor = Encryptor::new().unwrap();
+        let encrypted_package = encryptor.encrypt_package(&package);
+        encryptor.assemble_xml_file(password, &encrypted_package);
+        let xml = encryptor.writer.read_to_str();
+
+        // Decrypt the secret key using the password.
+        let spin_count = attribute(xml, "p:encryptedKey", "spinCount")
+            .parse::<u32>()
+            .unwrap();
+        let salt = base64_decode(attribute(xml, "p:encryptedKey", "saltValue"));
+        let decrypt_key_value = |value: &str, block_key: &[u8]| {
+            let key = password_key(password, &salt, spin_count, block_key);
+            aes_cbc_decrypt(
+                &key,
+                &salt,
+                &base64_decode(attribute(xml, "p:encryptedKey", value)),
+            )
+        };
+
+        let secret_key = decrypt_key_value("encryptedKeyValue", &ENCRYPTED_KEY_VALUE_BLOCK_KEY);
+
+        // Check the password verifier.
+        let verifier_hash_input =
+            decrypt_key_value("encryptedVerifierHashInput", &VERIFIER_HASH_INPUT_BLOCK_KEY);
+        let verifier_hash_value =
+            decrypt_key_value("encryptedVerifierHashValue", &VERIFIER_HASH_VALUE_BLOCK_KEY);
+        assert_eq!(
+            Sha512::digest(&verifier_hash_input).to_vec(),
+            verifier_hash_value
+        );
+
+        // Decrypt the package in 4096 byte segments.
+        let key_data_salt = base64_decode(attribute(xml, "keyData", "saltValue"));
+        let size = u64::from_le_bytes(encrypted_package[..8].try_into().unwrap()) as usize;
+        let mut decrypted = vec![];
+        for (index, segment) in encrypted_package[8..].chunks(4096).enumerate() {
+            let iv = Sha512::new()
+                .chain_update(&key_data_salt)
+                .chain_update((index as u32).to_le_bytes())
+                .finalize();
+            decrypted.extend(aes_cbc_decrypt(&secret_key, &iv[..16], segment));
+        }
+        decrypted.truncate(size);
+
+        assert_eq!(package, decrypted);
+
+        // Check the data integrity HMAC of the encrypted package.
+        let decrypt_integrity_value = |value: &str, block_key: &[u8]| {
+            let iv = Sha512::new()
+                .chain_update(&key_data_salt)
+                .chain_update(block_key)
+                .finalize();
+            let data = base64_decode(attribute(xml, "dataIntegrity", value));
+            aes_cbc_decrypt(&secret_key, &iv[..16], &data)
+        };
+
+        let hmac_key = decrypt_integrity_value("encryptedHmacKey", &HMAC_KEY_BLOCK_KEY);
+        let hmac_value = decrypt_integrity_value("encryptedHmacValue", &HMAC_VALUE_BLOCK_KEY);
+        assert_eq!(
+            Encryptor::hmac_sha512(&hmac_key, &encrypted_package),
+            hmac_value
+        );
+
+        // A different password doesn't decrypt the secret key.
+        let key = password_key(
+            "password",
+            &salt,
+            spin_count,
+            &ENCRYPTED_KEY_VALUE_BLOCK_KEY,
+        );
+        let encrypted_key_value =
+            base64_decode(attribute(xml, "p:encryptedKey", "encryptedKeyValue"));
+        assert_ne!(
+            secret_key,
+            aes_cbc_decrypt(&key, &salt, &encrypted_key_value)
+        );
+    }
+
+    #[test]
+    fn test_invalid_passwords() {
+        let mut encryptor = test_encryptor();
+
+        let result = encryptor.encrypt_file(b"Hello", "");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let password = "a".repeat(256);
+        let result = encryptor.encrypt_file(b"Hello", &password);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+}
@@ -11,6 +11,8 @@ mod tests;
 use std::error::Error;
 use std::fmt;
 
+use crate::{utility, ColNum, RowNum};
+
 #[cfg(feature = "polars")]
 use polars::prelude::polars_err;
 
@@ -26,6 +28,20 @@ use serde::ser;
 #[derive(Debug)]
 /// The `XlsxError` enum defines the error values for the `rust_xlsxwriter`
 /// library.
+///
+/// The enum is marked as `#[non_exhaustive]` so that new error variants can be
+/// added without breaking compatibility. Code that matches on `XlsxError`
+/// should include a wildcard `_` arm.
+///
+/// Errors that are raised while a worksheet is being processed during a save,
+/// or that are returned by [`Workbook::validate()`](crate::Workbook::validate),
+/// may be wrapped in a [`XlsxError::WorksheetError`] with the name of the
+/// worksheet and, where available, the cell location. This context can be
+/// retrieved via the [`XlsxError::sheet_name()`], [`XlsxError::row()`] and
+/// [`XlsxError::col()`] methods, and the underlying error via
+/// [`XlsxError::inner()`].
+///
+#[non_exhaustive]
 pub enum XlsxError {
     /// A general parameter error that is raised when a parameter conflicts with
     /// an Excel limit or syntax. The nature of the error is in the error string.
@@ -39,6 +55,11 @@ pub enum XlsxError {
     /// specification, i.e., the order is reversed.
     RowColumnOrderError,
 
+    /// Error returned when a row has already been written to disk via
+    /// [`Worksheet::flush_rows()`](crate::Worksheet::flush_rows) and can no
+    /// longer be changed.
+    RowFlushedError,
+
     /// Worksheet name cannot be blank.
     SheetnameCannotBeBlank(String),
 
@@ -133,6 +154,10 @@ pub enum XlsxError {
     /// table is configured incorrectly.
     TableError(String),
 
+    /// A formula syntax error such as unbalanced parentheses, an unknown
+    /// function name or an invalid cell reference.
+    FormulaError(String),
+
     /// Table name is already in use in the workbook.
     TableNameReused(String),
 
@@ -140,10 +165,47 @@ pub enum XlsxError {
     /// incorrect or missing.
     ConditionalFormatError(String),
 
+    /// A general error that is raised when a data validation parameter is
+    /// incorrect or missing.
+    DataValidationError(String),
+
     /// A customizable error that can be used by third parties to raise errors
     /// or to convert other Error types to.
     CustomError(String),
 
+    /// Error raised when a workbook save is cancelled via the token set with
+    /// [`Workbook::set_cancellation_token()`](crate::Workbook::set_cancellation_token).
+    SaveCancelled,
+
+    /// Error raised by [`read_workbook()`](crate::read_workbook) when a
+    /// required part of the xlsx file is missing or invalid.
+    ReadError(String),
+
+    /// Error raised in strict mode, see
+    /// [`Workbook::set_strict_mode()`](crate::Workbook::set_strict_mode),
+    /// when a parameter was ignored with a warning. The string contains the
+    /// warning message. Worksheet warnings are wrapped in a
+    /// [`XlsxError::WorksheetError`].
+    StrictModeWarning(String),
+
+    /// Wrapper for an error that was raised while processing a worksheet,
+    /// along with the name of the worksheet and, where available, the zero
+    /// indexed row and column of the cell that caused it.
+    WorksheetError {
+        /// The name of the worksheet where the error occurred.
+        sheet_name: String,
+
+        /// The zero indexed row of the cell that caused the error, if known.
+        row: Option<RowNum>,
+
+        /// The zero indexed column of the cell that caused the error, if
+        /// known.
+        col: Option<ColNum>,
+
+        /// The underlying error.
+        error: Box<XlsxError>,
+    },
+
     /// Wrapper for a variety of [std::io::Error] errors such as file
     /// permissions when writing the xlsx file to disk. This can be caused by an
     /// non-existent parent directory or, commonly on Windows, if the file is
@@ -170,7 +232,83 @@ pub enum XlsxError {
     PolarsError(PolarsError),
 }
 
-impl Error for XlsxError {}
+impl XlsxError {
+    /// Get the name of the worksheet associated with the error, if any.
+    ///
+    /// This is only available for [`XlsxError::WorksheetError`] errors.
+    ///
+    pub fn sheet_name(&self) -> Option<&str> {
+        match self {
+            XlsxError::WorksheetError { sheet_name, .. } => Some(sheet_name),
+            _ => None,
+        }
+    }
+
+    /// Get the zero indexed row of the cell associated with the error, if
+    /// known.
+    ///
+    pub fn row(&self) -> Option<RowNum> {
+        match self {
+            XlsxError::WorksheetError { row, .. } => *row,
+            _ => None,
+        }
+    }
+
+    /// Get the zero indexed column of the cell associated with the error, if
+    /// known.
+    ///
+    pub fn col(&self) -> Option<ColNum> {
+        match self {
+            XlsxError::WorksheetError { col, .. } => *col,
+            _ => None,
+        }
+    }
+
+    /// Get the underlying error, without any worksheet context.
+    ///
+    /// For a [`XlsxError::WorksheetError`] this returns the wrapped error. For
+    /// all other errors it returns the error itself. This is useful for
+    /// matching on the type of error regardless of whether context was added.
+    ///
+    pub fn inner(&self) -> &XlsxError {
+        match self {
+            XlsxError::WorksheetError { error, .. } => error.inner(),
+            _ => self,
+        }
+    }
+
+    // Wrap an error with the name of the worksheet, and optionally the cell,
+    // where it occurred. Errors that already have context aren't rewrapped.
+    pub(crate) fn in_worksheet(
+        self,
+        sheet_name: &str,
+        cell: Option<(RowNum, ColNum)>,
+    ) -> XlsxError {
+        if let XlsxError::WorksheetError { .. } = self {
+            return self;
+        }
+
+        XlsxError::WorksheetError {
+            sheet_name: sheet_name.to_string(),
+            row: cell.map(|(row, _)| row),
+            col: cell.map(|(_, col)| col),
+            error: Box::new(self),
+        }
+    }
+}
+
+impl Error for XlsxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            XlsxError::IoError(error) => Some(error),
+            XlsxError::ZipError(error) => Some(error),
+            XlsxError::WorksheetError { error, .. } => Some(error.as_ref()),
+            #[cfg(feature = "polars")]
+            XlsxError::PolarsError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
 
 impl fmt::Display for XlsxError {
     #[allow(clippy::too_many_lines)]
@@ -190,6 +328,11 @@ impl fmt::Display for XlsxError {
                 "First row or column in range is greater than last row or column."
             ),
 
+            XlsxError::RowFlushedError => write!(
+                f,
+                "Row has already been flushed from memory and cannot be changed."
+            ),
+
             XlsxError::SheetnameCannotBeBlank(name) => {
                 write!(f, "Worksheet name '{name}' cannot be blank.")
             }
@@ -280,6 +423,10 @@ impl fmt::Display for XlsxError {
                 write!(f, "Table error: '{error}'.")
             }
 
+            XlsxError::FormulaError(error) => {
+                write!(f, "Formula error: '{error}'.")
+            }
+
             XlsxError::TableNameReused(name) => {
                 write!(
                     f,
@@ -291,10 +438,39 @@ impl fmt::Display for XlsxError {
                 write!(f, "Conditional format error: '{error}'.")
             }
 
+            XlsxError::DataValidationError(error) => {
+                write!(f, "Data validation error: '{error}'.")
+            }
+
             XlsxError::CustomError(error) => {
                 write!(f, "{error}")
             }
 
+            XlsxError::SaveCancelled => {
+                write!(f, "Workbook save was cancelled.")
+            }
+
+            XlsxError::ReadError(error) => {
+                write!(f, "Error reading xlsx file: '{error}'.")
+            }
+
+            XlsxError::StrictModeWarning(warning) => {
+                write!(f, "Strict mode warning: '{warning}'.")
+            }
+
+            XlsxError::WorksheetError {
+                sheet_name,
+                row,
+                col,
+                error,
+            } => match (row, col) {
+                (Some(row), Some(col)) => {
+                    let cell = utility::row_col_to_cell(*row, *col);
+                    write!(f, "Worksheet '{sheet_name}', cell {cell}: {error}")
+                }
+                _ => write!(f, "Worksheet '{sheet_name}': {error}"),
+            },
+
             XlsxError::IoError(error) => {
                 write!(f, "{error}")
             }
@@ -7,6 +7,7 @@
 #[cfg(test)]
 mod error_tests {
 
+    use std::error::Error as _;
     use std::io::{Error, ErrorKind};
 
     use crate::XlsxError;
@@ -25,6 +26,10 @@ mod error_tests {
             XlsxError::RowColumnOrderError.to_string(),
             "First row or column in range is greater than last row or column."
         );
+        assert_eq!(
+            XlsxError::RowFlushedError.to_string(),
+            "Row has already been flushed from memory and cannot be changed."
+        );
         assert_eq!(
             XlsxError::SheetnameCannotBeBlank(name.to_string()).to_string(),
             "Worksheet name 'ERROR' cannot be blank."
@@ -83,6 +88,43 @@ mod error_tests {
         );
     }
 
+    #[test]
+    fn test_error_context() {
+        let error = XlsxError::MaxStringLengthExceeded.in_worksheet("Data", Some((2, 1)));
+        assert_eq!(
+            error.to_string(),
+            "Worksheet 'Data', cell B3: String exceeds Excel's limit of 32,767 characters."
+        );
+        assert_eq!(error.sheet_name(), Some("Data"));
+        assert_eq!(error.row(), Some(2));
+        assert_eq!(error.col(), Some(1));
+        assert!(matches!(error.inner(), XlsxError::MaxStringLengthExceeded));
+        assert!(error.source().is_some());
+
+        // Errors with context aren't rewrapped.
+        let error = error.in_worksheet("Other", None);
+        assert_eq!(error.sheet_name(), Some("Data"));
+
+        let error = XlsxError::RowColumnLimitError.in_worksheet("Data", None);
+        assert_eq!(
+            error.to_string(),
+            "Worksheet 'Data': Row or column exceeds Excel's allowed limits (1,048,576 x 16,384)."
+        );
+        assert_eq!(error.row(), None);
+        assert_eq!(error.col(), None);
+
+        let error = XlsxError::SheetnameReused("Sheet1".to_string());
+        assert_eq!(error.sheet_name(), None);
+        assert!(error.source().is_none());
+        assert!(matches!(error.inner(), XlsxError::SheetnameReused(_)));
+
+        let error = catch_io_error().unwrap_err();
+        assert_eq!(error.source().unwrap().to_string(), "ERROR");
+
+        let error = catch_zip_error().unwrap_err();
+        assert!(error.source().is_some());
+    }
+
     fn catch_zip_error() -> Result<(), XlsxError> {
         throw_zip_error()?;
         Ok(())
@@ -6,6 +6,13 @@
 
 #![warn(missing_docs)]
 
+use std::fmt;
+
+use crate::{
+    warning::Warnings, ConditionalFormatIconType, ExcelDateTime, Format, IntoColor,
+    IntoExcelDateTime, XlsxError,
+};
+
 /// The `FilterCondition` struct is used to define autofilter rules.
 ///
 /// Autofilter rules are associated with ranges created using
@@ -27,6 +34,10 @@
 /// [`add_list_filter()`](FilterCondition::add_list_filter) and
 /// [`add_custom_filter()`](FilterCondition::add_custom_filter) methods.
 ///
+/// Excel also supports "Top 10" filters that show the top or bottom N items,
+/// or N percent of items, in a column of numbers. These can be set with the
+/// [`add_top10_filter()`](FilterCondition::add_top10_filter) method.
+///
 ///
 ///
 /// # Examples
@@ -399,8 +410,15 @@ pub struct FilterCondition {
     pub(crate) apply_logical_or: bool,
     pub(crate) should_match_blanks: bool,
     pub(crate) list: Vec<FilterData>,
+    pub(crate) date_groups: Vec<FilterDateGroupItem>,
     pub(crate) custom1: Option<FilterData>,
     pub(crate) custom2: Option<FilterData>,
+    pub(crate) top10: Option<(FilterTop10, u16)>,
+    pub(crate) color_format: Option<Format>,
+    pub(crate) is_cell_color: bool,
+    pub(crate) icon: Option<(ConditionalFormatIconType, u8)>,
+    pub(crate) color_error: Option<String>,
+    pub(crate) warnings: Warnings,
 }
 
 #[allow(clippy::new_without_default)]
@@ -418,8 +436,15 @@ impl FilterCondition {
             apply_logical_or: true,
             should_match_blanks: false,
             list: vec![],
+            date_groups: vec![],
             custom1: None,
             custom2: None,
+            top10: None,
+            color_format: None,
+            is_cell_color: true,
+            icon: None,
+            color_error: None,
+            warnings: Warnings::new(),
         }
     }
 
@@ -627,6 +652,101 @@ impl FilterCondition {
         self
     }
 
+    /// Add a date grouping list filter condition.
+    ///
+    /// In Excel, the list filter for a column of dates is displayed as a tree
+    /// of years, months and days, and the user can select all the dates in a
+    /// year, a month or a day. This method adds one of these date groups to a
+    /// list filter. It can be called multiple times and it can be combined
+    /// with [`add_list_filter()`](FilterCondition::add_list_filter) and
+    /// [`add_list_blanks_filter()`](FilterCondition::add_list_blanks_filter).
+    ///
+    /// Date groups only match dates and times written with
+    /// [`Worksheet::write_datetime()`](crate::Worksheet::write_datetime) or
+    /// similar methods.
+    ///
+    /// # Parameters
+    ///
+    /// * `datetime` - A date/time that falls within the group. This can be an
+    ///   [`ExcelDateTime`] instance or any type that implements the
+    ///   [`IntoExcelDateTime`] trait.
+    /// * `grouping` - The date/time component that the filter groups by, as
+    ///   defined by the [`FilterDateGrouping`] enum. For example
+    ///   [`FilterDateGrouping::Month`] matches all the dates in the same year
+    ///   and month as `datetime`.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting an autofilter with date
+    /// grouping list filters to show the dates in March 2024 and all of 2023.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_filter_column9.rs
+    /// #
+    /// # use rust_xlsxwriter::{
+    /// #     ExcelDateTime, FilterCondition, FilterDateGrouping, Format, Workbook, XlsxError,
+    /// # };
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet with some sample data to filter.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #     let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    /// #     worksheet.set_column_width(0, 12)?;
+    /// #     worksheet.write_string(0, 0, "Date")?;
+    /// #     worksheet.write_string(0, 1, "Sales")?;
+    /// #
+    /// #     let dates = [
+    /// #         (2023, 5, 12, 3000),
+    /// #         (2023, 11, 3, 8000),
+    /// #         (2024, 1, 22, 5000),
+    /// #         (2024, 3, 1, 4000),
+    /// #         (2024, 3, 15, 7000),
+    /// #         (2024, 4, 2, 9000),
+    /// #     ];
+    /// #
+    /// #     for (row, (year, month, day, sales)) in dates.into_iter().enumerate() {
+    /// #         let row = row as u32 + 1;
+    /// #         let date = ExcelDateTime::from_ymd(year, month, day)?;
+    /// #         worksheet.write_datetime_with_format(row, 0, &date, &date_format)?;
+    /// #         worksheet.write_number(row, 1, sales)?;
+    /// #     }
+    /// #
+    /// #     // Set the autofilter.
+    /// #     worksheet.autofilter(0, 0, 6, 1)?;
+    /// #
+    ///     // Set a filter condition to only show dates in March 2024 or in 2023.
+    ///     let filter_condition = FilterCondition::new()
+    ///         .add_list_date_filter(
+    ///             &ExcelDateTime::from_ymd(2024, 3, 1)?,
+    ///             FilterDateGrouping::Month,
+    ///         )
+    ///         .add_list_date_filter(
+    ///             &ExcelDateTime::from_ymd(2023, 1, 1)?,
+    ///             FilterDateGrouping::Year,
+    ///         );
+    ///     worksheet.filter_column(0, &filter_condition)?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn add_list_date_filter(
+        mut self,
+        datetime: impl IntoExcelDateTime,
+        grouping: FilterDateGrouping,
+    ) -> FilterCondition {
+        let serial_datetime = datetime.to_excel_serial_date();
+
+        self.date_groups
+            .push(FilterDateGroupItem::new(serial_datetime, grouping));
+        self.is_list_filter = true;
+        self
+    }
+
     /// Add a custom filter condition.
     ///
     /// Add a custom filter to a column in an autofilter range. Excel only
@@ -705,7 +825,10 @@ impl FilterCondition {
             self.custom2 = Some(value.new_filter_data(criteria));
             self.apply_logical_or = false;
         } else {
-            eprintln!("Excel only allows 2 custom filter conditions.");
+            self.warnings.warn(
+                "add_custom_filter",
+                "Excel only allows 2 custom filter conditions.",
+            );
         }
 
         self.is_list_filter = false;
@@ -724,6 +847,387 @@ impl FilterCondition {
         self.is_list_filter = false;
         self
     }
+
+    /// Add a "Top 10" filter condition.
+    ///
+    /// Add a "Top 10" style filter to a column in an autofilter range. Despite
+    /// the name, Excel allows this type of filter to show the top or bottom N
+    /// items in a column, where N is in the range 1-500, or the top or bottom N
+    /// percent of the items, where N is in the range 1-100.
+    ///
+    /// Only numeric values in the column are considered. Rows with strings or
+    /// blank cells are hidden by the filter.
+    ///
+    /// A "Top 10" filter can't be combined with list or custom filters in the
+    /// same column.
+    ///
+    /// # Parameters
+    ///
+    /// * `top10` - The type of "Top 10" filter as defined by the
+    ///   [`FilterTop10`] enum.
+    /// * `value` - The number of items or the percentage of items to show.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting an autofilter with a "Top 10"
+    /// style filter to show the 3 highest values in a column.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_filter_column8.rs
+    /// #
+    /// # use rust_xlsxwriter::{FilterCondition, FilterTop10, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet with some sample data to filter.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #     worksheet.write_string(0, 0, "Region")?;
+    /// #     worksheet.write_string(1, 0, "East")?;
+    /// #     worksheet.write_string(2, 0, "West")?;
+    /// #     worksheet.write_string(3, 0, "East")?;
+    /// #     worksheet.write_string(4, 0, "North")?;
+    /// #     worksheet.write_string(5, 0, "South")?;
+    /// #     worksheet.write_string(6, 0, "West")?;
+    /// #
+    /// #     worksheet.write_string(0, 1, "Sales")?;
+    /// #     worksheet.write_number(1, 1, 3000)?;
+    /// #     worksheet.write_number(2, 1, 8000)?;
+    /// #     worksheet.write_number(3, 1, 5000)?;
+    /// #     worksheet.write_number(4, 1, 4000)?;
+    /// #     worksheet.write_number(5, 1, 7000)?;
+    /// #     worksheet.write_number(6, 1, 9000)?;
+    /// #
+    /// #     // Set the autofilter.
+    /// #     worksheet.autofilter(0, 0, 6, 1)?;
+    /// #
+    ///     // Set a "Top 10" filter to show the 3 highest sales values.
+    ///     let filter_condition = FilterCondition::new().add_top10_filter(FilterTop10::TopItems, 3);
+    ///     worksheet.filter_column(1, &filter_condition)?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn add_top10_filter(mut self, top10: FilterTop10, value: u16) -> FilterCondition {
+        self.clear_special_filters();
+        self.top10 = Some((top10, value));
+        self.is_list_filter = false;
+        self
+    }
+
+    /// Add a cell color filter condition.
+    ///
+    /// Add a filter to show the rows where the cells in the column have a
+    /// particular fill color. This is equivalent to the Excel "Filter by
+    /// Color" option for cell colors.
+    ///
+    /// The color should match the background color of the cell
+    /// [`Format`](crate::Format). The `rust_xlsxwriter` library uses this to
+    /// hide the rows that don't match the filter, see
+    /// [`Worksheet::filter_automatic_off()`](crate::Worksheet::filter_automatic_off).
+    /// Colors applied by conditional formats aren't taken into account when
+    /// hiding rows.
+    ///
+    /// A color filter can't be combined with other filters in the same column
+    /// and it replaces any previous "Top 10", color or icon filter in the
+    /// condition. An invalid color is reported as an error by
+    /// [`Worksheet::filter_column()`](crate::Worksheet::filter_column).
+    ///
+    /// # Parameters
+    ///
+    /// * `color` - The cell fill color. It can be a [`Color`](crate::Color)
+    ///   enum value or a type that can convert [`IntoColor`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting an autofilter with a cell
+    /// color filter to show the rows where the cells in a column have a yellow
+    /// fill.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_filter_column10.rs
+    /// #
+    /// # use rust_xlsxwriter::{Color, FilterCondition, Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let highlight = Format::new().set_background_color(Color::Yellow);
+    /// #
+    /// #     // Add a worksheet with some sample data to filter.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #     worksheet.write_string(0, 0, "Region")?;
+    /// #     worksheet.write_string(1, 0, "East")?;
+    /// #     worksheet.write_string(2, 0, "West")?;
+    /// #     worksheet.write_string(3, 0, "East")?;
+    /// #     worksheet.write_string(4, 0, "North")?;
+    /// #     worksheet.write_string(5, 0, "South")?;
+    /// #     worksheet.write_string(6, 0, "West")?;
+    /// #
+    /// #     worksheet.write_string(0, 1, "Sales")?;
+    /// #     worksheet.write_number(1, 1, 3000)?;
+    /// #     worksheet.write_number_with_format(2, 1, 8000, &highlight)?;
+    /// #     worksheet.write_number(3, 1, 5000)?;
+    /// #     worksheet.write_number(4, 1, 4000)?;
+    /// #     worksheet.write_number_with_format(5, 1, 7000, &highlight)?;
+    /// #     worksheet.write_number(6, 1, 9000)?;
+    /// #
+    /// #     // Set the autofilter.
+    /// #     worksheet.autofilter(0, 0, 6, 1)?;
+    /// #
+    ///     // Set a filter to show the cells with a yellow fill.
+    ///     let filter_condition = FilterCondition::new().add_cell_color_filter(Color::Yellow);
+    ///     worksheet.filter_column(1, &filter_condition)?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn add_cell_color_filter<T>(mut self, color: T) -> FilterCondition
+    where
+        T: IntoColor,
+    {
+        self.clear_special_filters();
+        self.is_list_filter = false;
+
+        let color = color.new_color();
+        match color.validate() {
+            Ok(()) => {
+                self.color_format = Some(Format::new().set_background_color(color));
+                self.is_cell_color = true;
+            }
+            Err(message) => self.color_error = Some(message),
+        }
+
+        self
+    }
+
+    /// Add a font color filter condition.
+    ///
+    /// Add a filter to show the rows where the cells in the column have a
+    /// particular font color. This is equivalent to the Excel "Filter by
+    /// Color" option for font colors.
+    ///
+    /// See [`add_cell_color_filter()`](FilterCondition::add_cell_color_filter)
+    /// for more details.
+    ///
+    /// # Parameters
+    ///
+    /// * `color` - The font color. It can be a [`Color`](crate::Color) enum
+    ///   value or a type that can convert [`IntoColor`].
+    ///
+    pub fn add_font_color_filter<T>(mut self, color: T) -> FilterCondition
+    where
+        T: IntoColor,
+    {
+        self.clear_special_filters();
+        self.is_list_filter = false;
+
+        let color = color.new_color();
+        match color.validate() {
+            Ok(()) => {
+                self.color_format = Some(Format::new().set_font_color(color));
+                self.is_cell_color = false;
+            }
+            Err(message) => self.color_error = Some(message),
+        }
+
+        self
+    }
+
+    /// Add a conditional format icon filter condition.
+    ///
+    /// Add a filter to show the rows where the cells in the column have a
+    /// particular conditional format icon. This is equivalent to the Excel
+    /// "Filter by Color" option for cell icons.
+    ///
+    /// The icons are generated by Excel at runtime so `rust_xlsxwriter`
+    /// doesn't hide the rows that don't match an icon filter. If required you
+    /// can hide the rows using
+    /// [`Worksheet::set_row_hidden()`](crate::Worksheet::set_row_hidden).
+    ///
+    /// # Parameters
+    ///
+    /// * `icon_type` - The icon set type as defined by the
+    ///   [`ConditionalFormatIconType`] enum.
+    /// * `icon_index` - The zero based index of the icon in the icon set. For
+    ///   example `ConditionalFormatIconType::ThreeArrows` has icons in the
+    ///   range 0-2.
+    ///
+    pub fn add_icon_filter(
+        mut self,
+        icon_type: ConditionalFormatIconType,
+        icon_index: u8,
+    ) -> FilterCondition {
+        self.clear_special_filters();
+        self.icon = Some((icon_type, icon_index));
+        self.is_list_filter = false;
+        self
+    }
+
+    // The top 10, color and icon filters are mutually exclusive so setting one
+    // of them replaces any previous one.
+    fn clear_special_filters(&mut self) {
+        self.top10 = None;
+        self.color_format = None;
+        self.color_error = None;
+        self.icon = None;
+    }
+
+    // Check that the filter condition has been set up correctly.
+    pub(crate) fn validate(&self) -> Result<(), XlsxError> {
+        if let Some(message) = &self.color_error {
+            let error = format!("Invalid color filter: {message}");
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        if let Some((icon_type, icon_index)) = self.icon {
+            let num_icons = icon_type.num_icons();
+
+            if icon_index >= num_icons {
+                let error = format!(
+                    "The icon filter index '{icon_index}' must be in the range 0-{} for icon type '{icon_type}'.",
+                    num_icons - 1
+                );
+                return Err(XlsxError::ParameterError(error));
+            }
+
+            return Ok(());
+        }
+
+        if self.color_format.is_some() {
+            return Ok(());
+        }
+
+        if let Some((top10, value)) = self.top10 {
+            let max = if top10.is_percent() { 100 } else { 500 };
+
+            if value == 0 || value > max {
+                let error =
+                    format!("The 'Top 10' filter value '{value}' must be in the range 1-{max}.");
+                return Err(XlsxError::ParameterError(error));
+            }
+
+            return Ok(());
+        }
+
+        if self.list.is_empty()
+            && self.date_groups.is_empty()
+            && self.custom1.is_none()
+            && !self.should_match_blanks
+        {
+            let error =
+                "The 'filter_condition' doesn't have a data value or condition set.".to_string();
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        Ok(())
+    }
+}
+
+/// The `FilterDateGrouping` enum defines the date/time grouping used in an
+/// autofilter date list filter.
+///
+/// These options are used with the [`FilterCondition`]
+/// [`add_list_date_filter()`](FilterCondition::add_list_date_filter) method.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterDateGrouping {
+    /// Match all the dates in the same year.
+    Year,
+
+    /// Match all the dates in the same year and month.
+    Month,
+
+    /// Match all the dates on the same day.
+    Day,
+
+    /// Match all the times in the same hour of the same day.
+    Hour,
+
+    /// Match all the times in the same minute of the same day.
+    Minute,
+
+    /// Match all the times in the same second of the same day.
+    Second,
+}
+
+impl fmt::Display for FilterDateGrouping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Year => write!(f, "year"),
+            Self::Month => write!(f, "month"),
+            Self::Day => write!(f, "day"),
+            Self::Hour => write!(f, "hour"),
+            Self::Minute => write!(f, "minute"),
+            Self::Second => write!(f, "second"),
+        }
+    }
+}
+
+// A date group item in a date list filter. Excel stores the date components
+// down to the level of the grouping.
+#[derive(Clone)]
+pub(crate) struct FilterDateGroupItem {
+    pub(crate) components: [u16; 6],
+    pub(crate) grouping: FilterDateGrouping,
+}
+
+impl FilterDateGroupItem {
+    fn new(serial_datetime: f64, grouping: FilterDateGrouping) -> FilterDateGroupItem {
+        FilterDateGroupItem {
+            components: ExcelDateTime::serial_to_components(serial_datetime),
+            grouping,
+        }
+    }
+
+    // Get the number of date components that are used for the grouping.
+    pub(crate) fn num_components(&self) -> usize {
+        self.grouping as usize + 1
+    }
+
+    // Check if an Excel serial datetime falls within the date group.
+    pub(crate) fn matches(&self, serial_datetime: f64) -> bool {
+        let components = ExcelDateTime::serial_to_components(serial_datetime);
+        let num_components = self.num_components();
+
+        components[..num_components] == self.components[..num_components]
+    }
+}
+
+/// The `FilterTop10` enum defines the type of "Top 10" filter used in an
+/// autofilter.
+///
+/// These options are used with the [`FilterCondition`]
+/// [`add_top10_filter()`](FilterCondition::add_top10_filter) method.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterTop10 {
+    /// Show the top N items in the column.
+    TopItems,
+
+    /// Show the bottom N items in the column.
+    BottomItems,
+
+    /// Show the top N percent of the items in the column.
+    TopPercent,
+
+    /// Show the bottom N percent of the items in the column.
+    BottomPercent,
+}
+
+impl FilterTop10 {
+    pub(crate) fn is_top(self) -> bool {
+        matches!(self, FilterTop10::TopItems | FilterTop10::TopPercent)
+    }
+
+    pub(crate) fn is_percent(self) -> bool {
+        matches!(self, FilterTop10::TopPercent | FilterTop10::BottomPercent)
+    }
 }
 
 /// The `FilterCriteria` enum defines logical filter criteria used in an
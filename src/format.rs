@@ -8,8 +8,13 @@
 
 mod tests;
 
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 use std::{collections::HashMap, fmt, hash::Hash};
 
+use crate::warning::Warnings;
+
 /// The `Format` struct is used to define cell formatting for data in a worksheet.
 ///
 /// The properties of a cell that can be formatted include: fonts, colors,
@@ -182,6 +187,7 @@ use std::{collections::HashMap, fmt, hash::Hash};
 /// | **Fill**        | Cell pattern          |  [`set_pattern()`](Format::set_pattern())                             |
 /// |                 | Background color      |  [`set_background_color()`](Format::set_background_color())           |
 /// |                 | Foreground color      |  [`set_foreground_color()`](Format::set_foreground_color())           |
+/// |                 | Gradient fill         |  [`set_gradient()`](Format::set_gradient())                           |
 /// | **Protection**  | Unlock cells          |  [`set_unlocked()`](Format::set_unlocked())                           |
 /// |                 | Hide formulas         |  [`set_hidden()`](Format::set_hidden())                               |
 ///
@@ -446,6 +452,8 @@ pub struct Format {
     // Non-UI properties.
     pub(crate) quote_prefix: bool,
     pub(crate) is_dxf_format: bool,
+    pub(crate) interned_id: InternedId,
+    pub(crate) warnings: Warnings,
 }
 
 impl Hash for Format {
@@ -526,6 +534,8 @@ impl Format {
             num_format_index: 0,
             quote_prefix: false,
             is_dxf_format: false,
+            interned_id: InternedId::default(),
+            warnings: Warnings::new(),
         }
     }
 
@@ -552,6 +562,7 @@ impl Format {
         self.fill.pattern != FormatPattern::None
             || !self.fill.background_color.is_auto_or_default()
             || !self.fill.foreground_color.is_auto_or_default()
+            || self.fill.gradient.is_some()
     }
 
     pub(crate) fn set_fill_index(&mut self, fill_index: u16, has_fill: bool) {
@@ -944,7 +955,7 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_font_color", color) {
             self.font.color = color;
         }
 
@@ -1049,22 +1060,42 @@ impl Format {
 
     /// Set the Format font scheme property.
     ///
-    /// This function is implemented for completeness but is rarely used in
-    /// practice.
+    /// The font scheme links a font to the workbook theme. Fonts with a
+    /// [`FormatFontScheme::Minor`] scheme, like the default Calibri font, or a
+    /// [`FormatFontScheme::Major`] scheme change when the theme fonts are
+    /// changed in Excel. Fonts with a [`FormatFontScheme::None`] scheme are
+    /// independent of the theme.
+    ///
+    /// The scheme is set to `None` automatically when a font other than Calibri
+    /// is set via [`set_font_name()`](Format::set_font_name()), so this
+    /// method is generally only needed to detach Calibri from the theme or to
+    /// explicitly link a font to the theme.
     ///
     /// # Parameters
     ///
-    /// * `font_scheme` - The font scheme property.
+    /// * `font_scheme` - The font scheme property defined by a
+    ///   [`FormatFontScheme`] enum value. For backward compatibility the
+    ///   Excel string values `"major"`, `"minor"` and `""` are also accepted.
     ///
-    pub fn set_font_scheme(mut self, font_scheme: impl Into<String>) -> Format {
-        self.font.scheme = font_scheme.into();
+    pub fn set_font_scheme(mut self, font_scheme: impl Into<FormatFontScheme>) -> Format {
+        self.font.scheme = font_scheme.into().to_string();
         self
     }
 
     /// Set the Format font family property.
     ///
-    /// Set the font family. This is usually an integer in the range 1-4. This
-    /// function is implemented for completeness but is rarely used in practice.
+    /// Set the font family. This is an integer that describes the general
+    /// style of the font and which Excel uses to find a substitute font if
+    /// the named font isn't available. The values are:
+    ///
+    /// | Value | Family                                |
+    /// | :---- | :------------------------------------ |
+    /// | 0     | Not applicable.                       |
+    /// | 1     | Roman, serif fonts.                   |
+    /// | 2     | Swiss, sans-serif fonts. The default. |
+    /// | 3     | Modern, monospace fonts.              |
+    /// | 4     | Script fonts.                         |
+    /// | 5     | Decorative fonts.                     |
     ///
     /// # Parameters
     ///
@@ -1077,13 +1108,58 @@ impl Format {
 
     /// Set the Format font character set property.
     ///
-    /// Set the font character. This function is implemented for completeness
-    /// but is rarely used in practice.
+    /// Set the font character set. This is generally only required for East
+    /// Asian and other non-Latin fonts to ensure that Excel selects the correct
+    /// font when the file is opened on a system with a different locale. Some
+    /// common values are:
+    ///
+    /// | Value | Character set          |
+    /// | :---- | :--------------------- |
+    /// | 0     | ANSI/Latin.            |
+    /// | 128   | Japanese (Shift JIS).  |
+    /// | 129   | Korean (Hangul).       |
+    /// | 134   | Simplified Chinese.    |
+    /// | 136   | Traditional Chinese.   |
+    /// | 161   | Greek.                 |
+    /// | 162   | Turkish.               |
+    /// | 177   | Hebrew.                |
+    /// | 178   | Arabic.                |
+    /// | 204   | Cyrillic.              |
+    /// | 222   | Thai.                  |
     ///
     /// # Parameters
     ///
     /// * `font_charset` - The font character set property.
     ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the font properties for a
+    /// Japanese font.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_format_set_font_charset.rs
+    /// #
+    /// # use rust_xlsxwriter::{Format, FormatFontScheme, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let format = Format::new()
+    ///         .set_font_name("MS Gothic")
+    ///         .set_font_family(3)
+    ///         .set_font_charset(128)
+    ///         .set_font_scheme(FormatFontScheme::None);
+    ///
+    ///     worksheet.write_string_with_format(0, 0, "こんにちは世界", &format)?;
+    ///
+    /// #     workbook.save("formats.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
     pub fn set_font_charset(mut self, font_charset: u8) -> Format {
         self.font.charset = font_charset;
         self
@@ -1189,6 +1265,39 @@ impl Format {
     /// * `font_script` - The font superscript or subscript property via a
     ///   [`FormatScript`] enum.
     ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the superscript and
+    /// subscript properties in rich strings, for example for chemical formulas
+    /// and footnote markers.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_format_set_font_script.rs
+    /// #
+    /// # use rust_xlsxwriter::{Format, FormatScript, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let default = Format::default();
+    ///     let superscript = Format::new().set_font_script(FormatScript::Superscript);
+    ///     let subscript = Format::new().set_font_script(FormatScript::Subscript);
+    ///
+    ///     // A chemical formula.
+    ///     let segments = [(&default, "H"), (&subscript, "2"), (&default, "O")];
+    ///     worksheet.write_rich_string(0, 0, &segments)?;
+    ///
+    ///     // A footnote marker.
+    ///     let segments = [(&default, "Revenue"), (&superscript, "1")];
+    ///     worksheet.write_rich_string(1, 0, &segments)?;
+    ///
+    /// #     workbook.save("formats.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
     ///
     pub fn set_font_script(mut self, font_script: FormatScript) -> Format {
         self.font.script = font_script;
@@ -1261,6 +1370,11 @@ impl Format {
             FormatAlign::General => {
                 self.alignment.horizontal = FormatAlign::General;
                 self.alignment.vertical = FormatAlign::General;
+                self.alignment.justify_last = false;
+            }
+            FormatAlign::JustifyLastLine => {
+                self.alignment.horizontal = FormatAlign::Distributed;
+                self.alignment.justify_last = true;
             }
             FormatAlign::Center
             | FormatAlign::CenterAcross
@@ -1270,6 +1384,7 @@ impl Format {
             | FormatAlign::Left
             | FormatAlign::Right => {
                 self.alignment.horizontal = align;
+                self.alignment.justify_last = false;
             }
             FormatAlign::Bottom
             | FormatAlign::Top
@@ -1335,13 +1450,16 @@ impl Format {
     ///
     /// Indentation is a horizontal alignment property. It can be used in Excel
     /// in conjunction with the [Left](FormatAlign::Left),
-    /// [Right](FormatAlign::Right) and [Distributed](FormatAlign::Distributed)
-    /// alignments. It will override any other horizontal properties that don't
-    /// support indentation.
+    /// [Right](FormatAlign::Right), [Distributed](FormatAlign::Distributed)
+    /// and [JustifyLastLine](FormatAlign::JustifyLastLine) alignments. It will
+    /// override any other horizontal properties that don't support indentation
+    /// and the cell will be left aligned, in the same way as Excel's alignment
+    /// dialog.
     ///
     /// # Parameters
     ///
-    /// * `indent` - The indentation level for the cell.
+    /// * `indent` - The indentation level for the cell in the Excel range
+    ///   0-250. Values outside this range are ignored.
     ///
     /// # Examples
     ///
@@ -1376,6 +1494,15 @@ impl Format {
     /// <img src="https://rustxlsxwriter.github.io/images/format_set_indent.png">
     ///
     pub fn set_indent(mut self, indent: u8) -> Format {
+        if indent > 250 {
+            self.warnings.warn(
+                "set_indent",
+                format!("Indent level {indent} outside Excel range: 0 <= indent <= 250."),
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_indent");
         self.alignment.indent = indent;
         self
     }
@@ -1433,9 +1560,16 @@ impl Format {
             270 => self.alignment.rotation = 255,
             -90..=-1 => self.alignment.rotation = -rotation + 90,
             0..=90 => self.alignment.rotation = rotation,
-            _ => eprintln!("Rotation outside range: -90 <= angle <= 90."),
+            _ => {
+                self.warnings.warn(
+                    "set_rotation",
+                    "Rotation outside range: -90 <= angle <= 90.",
+                );
+                return self;
+            }
         }
 
+        self.warnings.clear("set_rotation");
         self
     }
 
@@ -1493,18 +1627,30 @@ impl Format {
     ///
     pub fn set_reading_direction(mut self, reading_direction: u8) -> Format {
         if reading_direction > 2 {
-            eprintln!("Reading direction must be 0, 1 or 2.");
+            self.warnings.warn(
+                "set_reading_direction",
+                "Reading direction must be 0, 1 or 2.",
+            );
             return self;
         }
 
+        self.warnings.clear("set_reading_direction");
         self.alignment.reading_direction = reading_direction;
         self
     }
 
     /// Set the Format shrink property.
     ///
-    /// This method can be used to shrink text so that it fits in a cell
+    /// This method can be used to shrink text so that it fits in a cell. This
+    /// is Excel's "Shrink to fit" alignment option. The font size of the cell
+    /// isn't changed, instead Excel reduces the displayed size of the text so
+    /// that it fits in the column width. This is useful when a column has a
+    /// fixed width but numbers should be shown in full rather than as `###`.
     ///
+    /// As in Excel, the shrink property is ignored if the format also has
+    /// [`set_text_wrap()`](Format::set_text_wrap()) or a
+    /// [Fill](FormatAlign::Fill), [Justify](FormatAlign::Justify) or
+    /// [Distributed](FormatAlign::Distributed) horizontal alignment.
     ///
     /// # Examples
     ///
@@ -1546,7 +1692,12 @@ impl Format {
     ///
     /// To set the pattern colors see
     /// [`set_background_color()`](Format::set_background_color()) and
-    /// [`set_foreground_color()`](Format::set_foreground_color()).
+    /// [`set_foreground_color()`](Format::set_foreground_color()). For
+    /// patterns other than `Solid`, such as [`FormatPattern::LightUp`] or
+    /// [`FormatPattern::DarkTrellis`], the foreground color is the color of the
+    /// pattern lines or dots and the background color is the color of the cell
+    /// behind them. For a `Solid` pattern only the background color is
+    /// required.
     ///
     /// # Parameters
     ///
@@ -1648,7 +1799,7 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_background_color", color) {
             self.fill.background_color = color;
         }
 
@@ -1707,13 +1858,86 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_foreground_color", color) {
             self.fill.foreground_color = color;
         }
 
         self
     }
 
+    /// Set the Format gradient fill property.
+    ///
+    /// Set a gradient fill for a cell, as defined by a [`FormatGradient`]
+    /// struct. This is the equivalent of the "Fill Effects" dialog in Excel
+    /// and is often used to create "heat bar" style headers.
+    ///
+    /// A gradient fill overrides any pattern, background or foreground color
+    /// set for the format.
+    ///
+    /// # Parameters
+    ///
+    /// * `gradient` - The [`FormatGradient`] fill properties. The gradient is
+    ///   ignored if it has less than 2 valid gradient stops.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting a gradient fill for a cell.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_format_set_gradient.rs
+    /// #
+    /// # use rust_xlsxwriter::{
+    /// #     Format, FormatGradient, FormatGradientStop, FormatGradientType, Workbook, XlsxError,
+    /// # };
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let format1 = Format::new().set_gradient(
+    ///         &FormatGradient::new()
+    ///             .set_angle(90)
+    ///             .set_gradient_stops(&[
+    ///                 FormatGradientStop::new("#FFFFFF", 0),
+    ///                 FormatGradientStop::new("#4F81BD", 100),
+    ///             ]),
+    ///     );
+    ///
+    ///     let format2 = Format::new().set_gradient(
+    ///         &FormatGradient::new()
+    ///             .set_type(FormatGradientType::Path)
+    ///             .set_gradient_stops(&[
+    ///                 FormatGradientStop::new("#FFFFFF", 0),
+    ///                 FormatGradientStop::new("#C0504D", 100),
+    ///             ]),
+    ///     );
+    ///
+    ///     worksheet.write_with_format(0, 0, "Linear", &format1)?;
+    ///     worksheet.write_with_format(2, 0, "Path", &format2)?;
+    /// #
+    /// #     workbook.save("formats.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_gradient(mut self, gradient: &FormatGradient) -> Format {
+        self.warnings.extend(&gradient.warnings);
+
+        if gradient.gradient_stops.len() >= 2 {
+            self.fill.gradient = Some(gradient.clone());
+            self.warnings.clear("set_gradient");
+        } else {
+            self.warnings.warn(
+                "set_gradient",
+                "Format gradient must contain at least 2 valid gradient stops.",
+            );
+        }
+
+        self
+    }
+
     /// Set the Format border property.
     ///
     /// Set the cell border style. Individual border elements can be configured
@@ -1837,7 +2061,7 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if !color.is_valid() {
+        if !self.warnings.check_color("set_border_color", color) {
             return self;
         }
 
@@ -1874,7 +2098,7 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_border_top_color", color) {
             self.borders.top_color = color;
         }
 
@@ -1907,7 +2131,7 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_border_bottom_color", color) {
             self.borders.bottom_color = color;
         }
 
@@ -1940,7 +2164,7 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_border_left_color", color) {
             self.borders.left_color = color;
         }
 
@@ -1973,7 +2197,7 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_border_right_color", color) {
             self.borders.right_color = color;
         }
 
@@ -2056,7 +2280,10 @@ impl Format {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self
+            .warnings
+            .check_color("set_border_diagonal_color", color)
+        {
             self.borders.diagonal_color = color;
         }
 
@@ -2276,6 +2503,94 @@ impl Format {
     }
 }
 
+/// The `FormatId` struct represents a [`Format`] that has been interned by a
+/// workbook.
+///
+/// Each write with a [`Format`] requires the worksheet to look up the
+/// properties of the format to find its cell index, which can add up when
+/// writing large, or wide, formatted ranges. A `FormatId` is created once via
+/// [`Workbook::format_id()`](crate::Workbook::format_id) and carries a cheap
+/// identifier that worksheets use to resolve the format index directly.
+///
+/// A `FormatId` dereferences to a `&Format` so it can be used with any of the
+/// `*_with_format()` worksheet methods. It is cheap to clone.
+///
+/// # Examples
+///
+/// The following example demonstrates creating an interned format id and
+/// using it to write a large range of formatted cells.
+///
+/// ```
+/// # // This code is available in examples/doc_workbook_format_id.rs
+/// #
+/// use rust_xlsxwriter::{Format, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///
+///     // Intern the format once.
+///     let format = workbook.format_id(&Format::new().set_num_format("0.00"));
+///
+///     let worksheet = workbook.add_worksheet();
+///     for row in 0..1000 {
+///         for col in 0..20 {
+///             worksheet.write_number_with_format(row, col, row, &format)?;
+///         }
+///     }
+///
+///     workbook.save("formats.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct FormatId(Arc<Format>);
+
+impl FormatId {
+    // Create a new id from a format. Each id is unique within the process so
+    // that ids from different workbooks can't collide in a worksheet.
+    pub(crate) fn new(format: &Format) -> FormatId {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+        let mut format = format.clone();
+        format.interned_id = InternedId(Some(NEXT_ID.fetch_add(1, Ordering::Relaxed)));
+
+        FormatId(Arc::new(format))
+    }
+
+    /// Get the [`Format`] that the id refers to.
+    pub fn format(&self) -> &Format {
+        &self.0
+    }
+}
+
+impl Deref for FormatId {
+    type Target = Format;
+
+    fn deref(&self) -> &Format {
+        &self.0
+    }
+}
+
+impl AsRef<Format> for FormatId {
+    fn as_ref(&self) -> &Format {
+        &self.0
+    }
+}
+
+// The identifier of an interned format. The id isn't propagated when a format
+// is cloned so that a modified copy of an interned format isn't mistaken for
+// the original.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct InternedId(pub(crate) Option<u32>);
+
+impl Clone for InternedId {
+    fn clone(&self) -> Self {
+        InternedId(None)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
 pub(crate) struct Alignment {
     pub(crate) horizontal: FormatAlign,
@@ -2357,6 +2672,7 @@ pub(crate) struct Fill {
     pub(crate) foreground_color: Color,
     pub(crate) background_color: Color,
     pub(crate) pattern: FormatPattern,
+    pub(crate) gradient: Option<FormatGradient>,
 }
 
 // -----------------------------------------------------------------------
@@ -2560,6 +2876,50 @@ impl Color {
         }
     }
 
+    // Get the index of the nearest color in Excel's legacy indexed color
+    // palette. This is used for properties, such as the gridline color, that
+    // only support the indexed colors. The palette starts at index 8.
+    #[allow(clippy::unreadable_literal)]
+    pub(crate) fn palette_index(self) -> u8 {
+        const PALETTE: [u32; 56] = [
+            0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF,
+            0x800000, 0x008000, 0x000080, 0x808000, 0x800080, 0x008080, 0xC0C0C0, 0x808080,
+            0x9999FF, 0x993366, 0xFFFFCC, 0xCCFFFF, 0x660066, 0xFF8080, 0x0066CC, 0xCCCCFF,
+            0x000080, 0xFF00FF, 0xFFFF00, 0x00FFFF, 0x800080, 0x800000, 0x008080, 0x0000FF,
+            0x00CCFF, 0xCCFFFF, 0xCCFFCC, 0xFFFF99, 0x99CCFF, 0xFF99CC, 0xCC99FF, 0xFFCC99,
+            0x3366FF, 0x33CCCC, 0x99CC00, 0xFFCC00, 0xFF9900, 0xFF6600, 0x666699, 0x969696,
+            0x003366, 0x339966, 0x003300, 0x333300, 0x993300, 0x993366, 0x333399, 0x333333,
+        ];
+
+        let rgb = u32::from_str_radix(&self.rgb_hex_value(), 16).unwrap_or_default();
+        let components = |color: u32| {
+            [
+                i32::try_from((color >> 16) & 0xFF).unwrap_or_default(),
+                i32::try_from((color >> 8) & 0xFF).unwrap_or_default(),
+                i32::try_from(color & 0xFF).unwrap_or_default(),
+            ]
+        };
+        let target = components(rgb);
+
+        // Find the first palette entry with the smallest color distance.
+        let mut nearest = 0;
+        let mut min_distance = i32::MAX;
+        for (index, color) in PALETTE.iter().enumerate() {
+            let distance = components(*color)
+                .iter()
+                .zip(target)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<i32>();
+
+            if distance < min_distance {
+                nearest = index;
+                min_distance = distance;
+            }
+        }
+
+        nearest as u8 + 8
+    }
+
     // Get the ARGB hex value for a color. The alpha channel is always FF.
     pub(crate) fn argb_hex_value(self) -> String {
         format!("FF{}", self.rgb_hex_value())
@@ -2780,30 +3140,31 @@ impl Color {
 
     // Check if the RGB and Theme values are in the correct range. Any of the
     // simple enum will be by default.
-    #[allow(clippy::unreadable_literal)]
     pub(crate) fn is_valid(self) -> bool {
-        match self {
-            Color::RGB(color) => {
-                if color > 0xFFFFFF {
-                    eprintln!(
-                        "RGB color '{color:#X}' must be in the the range 0x000000 - 0xFFFFFF."
-                    );
-                    return false;
-                }
-                true
+        match self.validate() {
+            Ok(()) => true,
+            Err(message) => {
+                log::warn!("{message}");
+                false
             }
-            Color::Theme(color, shade) => {
-                if color > 9 {
-                    eprintln!("Theme color '{color}' must be in the the range 0 - 9.");
-                    return false;
-                }
-                if shade > 5 {
-                    eprintln!("Theme shade '{shade}' must be in the the range 0 - 5.");
-                    return false;
-                }
-                true
-            }
-            _ => true,
+        }
+    }
+
+    // Check if the RGB and Theme values are in the correct range and return
+    // the warning message if they aren't.
+    #[allow(clippy::unreadable_literal)]
+    pub(crate) fn validate(self) -> Result<(), String> {
+        match self {
+            Color::RGB(color) if color > 0xFFFFFF => Err(format!(
+                "RGB color '{color:#X}' must be in the the range 0x000000 - 0xFFFFFF."
+            )),
+            Color::Theme(color, _) if color > 9 => Err(format!(
+                "Theme color '{color}' must be in the the range 0 - 9."
+            )),
+            Color::Theme(_, shade) if shade > 5 => Err(format!(
+                "Theme shade '{shade}' must be in the the range 0 - 5."
+            )),
+            _ => Ok(()),
         }
     }
 
@@ -2923,7 +3284,7 @@ impl IntoColor for &str {
         match color {
             Ok(color) => Color::RGB(color),
             Err(_) => {
-                eprintln!("Error parsing '{self}' to RGB color.");
+                log::warn!("Error parsing '{self}' to RGB color.");
                 Color::Default
             }
         }
@@ -3023,6 +3384,166 @@ impl fmt::Display for FormatPattern {
     }
 }
 
+/// The `FormatGradient` struct represents a gradient fill for a [`Format`].
+///
+/// Excel supports two types of gradient fill for cells: a linear gradient
+/// with an angle, and a "path" gradient that radiates from the center of the
+/// cell. The colors of the gradient are defined by two or more
+/// [`FormatGradientStop`] values.
+///
+/// See [`Format::set_gradient()`] for an example.
+///
+#[derive(Debug, Clone, Eq, Default)]
+pub struct FormatGradient {
+    pub(crate) gradient_type: FormatGradientType,
+    pub(crate) gradient_stops: Vec<FormatGradientStop>,
+    pub(crate) angle: u16,
+    pub(crate) warnings: Warnings,
+}
+
+impl Hash for FormatGradient {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.gradient_type.hash(state);
+        self.gradient_stops.hash(state);
+        self.angle.hash(state);
+    }
+}
+
+impl PartialEq for FormatGradient {
+    fn eq(&self, other: &Self) -> bool {
+        self.gradient_type == other.gradient_type
+            && self.gradient_stops == other.gradient_stops
+            && self.angle == other.angle
+    }
+}
+
+impl FormatGradient {
+    /// Create a new `FormatGradient` object to represent a cell gradient
+    /// fill.
+    ///
+    pub fn new() -> FormatGradient {
+        FormatGradient::default()
+    }
+
+    /// Set the type of the gradient fill.
+    ///
+    /// # Parameters
+    ///
+    /// * `gradient_type` - A [`FormatGradientType`] enum value. The default is
+    ///   [`FormatGradientType::Linear`].
+    ///
+    pub fn set_type(mut self, gradient_type: FormatGradientType) -> FormatGradient {
+        self.gradient_type = gradient_type;
+        self
+    }
+
+    /// Set the angle of a linear gradient fill.
+    ///
+    /// # Parameters
+    ///
+    /// * `angle` - The angle of the linear gradient in the range `0 <= angle <
+    ///   360`. The default angle is 0 which is a left to right gradient. An
+    ///   angle of 90 is a top to bottom gradient.
+    ///
+    pub fn set_angle(mut self, angle: u16) -> FormatGradient {
+        if angle < 360 {
+            self.angle = angle;
+            self.warnings.clear("set_angle");
+        } else {
+            self.warnings.warn(
+                "set_angle",
+                format!("Gradient angle '{angle}' must be in the Excel range 0 <= angle < 360"),
+            );
+        }
+
+        self
+    }
+
+    /// Set the gradient stops (color points) for the gradient fill.
+    ///
+    /// # Parameters
+    ///
+    /// * `gradient_stops` - A slice ref of [`FormatGradientStop`] values.
+    ///   There must be at least 2 valid gradient stops. Invalid stops are
+    ///   ignored.
+    ///
+    pub fn set_gradient_stops(mut self, gradient_stops: &[FormatGradientStop]) -> FormatGradient {
+        let valid_gradient_stops: Vec<FormatGradientStop> = gradient_stops
+            .iter()
+            .filter(|gradient_stop| gradient_stop.is_valid())
+            .cloned()
+            .collect();
+
+        if valid_gradient_stops.len() < 2 {
+            self.warnings.warn(
+                "set_gradient_stops",
+                "Gradient stops must contain at least 2 valid entries.",
+            );
+            return self;
+        }
+
+        if valid_gradient_stops.len() < gradient_stops.len() {
+            self.warnings.warn(
+                "set_gradient_stops",
+                "Invalid gradient stops have been ignored.",
+            );
+        } else {
+            self.warnings.clear("set_gradient_stops");
+        }
+
+        self.gradient_stops = valid_gradient_stops;
+
+        self
+    }
+}
+
+/// The `FormatGradientStop` struct represents a gradient fill color point.
+///
+/// A gradient stop defines a color and its position in a [`FormatGradient`]
+/// as a percentage from 0 to 100.
+///
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct FormatGradientStop {
+    pub(crate) color: Color,
+    pub(crate) position: u8,
+}
+
+impl FormatGradientStop {
+    /// Create a new `FormatGradientStop` object to represent a gradient fill
+    /// stop.
+    ///
+    /// # Parameters
+    ///
+    /// * `color` - The gradient stop color property defined by a [`Color`] enum
+    ///   value.
+    /// * `position` - The gradient stop position in the range 0-100.
+    ///
+    pub fn new(color: impl IntoColor, position: u8) -> FormatGradientStop {
+        FormatGradientStop {
+            color: color.new_color(),
+            position,
+        }
+    }
+
+    // Check for valid gradient stop properties.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.color.is_valid() && !self.color.is_auto_or_default() && self.position <= 100
+    }
+}
+
+/// The `FormatGradientType` enum defines the gradient types of a
+/// [`FormatGradient`].
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
+pub enum FormatGradientType {
+    /// The gradient runs linearly at the angle set by
+    /// [`FormatGradient::set_angle()`]. This is the default.
+    #[default]
+    Linear,
+
+    /// The gradient radiates from the center of the cell to the edges.
+    Path,
+}
+
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
 /// The `FormatBorder` enum defines the Excel border types that can be added to
 /// a [`Format`] pattern.
@@ -3175,6 +3696,50 @@ pub enum FormatUnderline {
     DoubleAccounting,
 }
 
+/// The `FormatFontScheme` enum defines the [`Format`] font scheme properties.
+///
+/// See [`Format::set_font_scheme()`] for details.
+///
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
+pub enum FormatFontScheme {
+    /// The font isn't linked to the workbook theme.
+    #[default]
+    None,
+
+    /// The font is the theme "Headings" font.
+    Major,
+
+    /// The font is the theme "Body" font. This is the scheme of the default
+    /// Calibri font.
+    Minor,
+}
+
+impl From<&str> for FormatFontScheme {
+    fn from(value: &str) -> FormatFontScheme {
+        match value.to_lowercase().as_str() {
+            "major" => FormatFontScheme::Major,
+            "minor" => FormatFontScheme::Minor,
+            _ => FormatFontScheme::None,
+        }
+    }
+}
+
+impl From<String> for FormatFontScheme {
+    fn from(value: String) -> FormatFontScheme {
+        FormatFontScheme::from(value.as_str())
+    }
+}
+
+impl fmt::Display for FormatFontScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, ""),
+            Self::Major => write!(f, "major"),
+            Self::Minor => write!(f, "minor"),
+        }
+    }
+}
+
 /// The `FormatScript` enum defines the [`Format`] font superscript and subscript
 /// properties.
 ///
@@ -3224,6 +3789,12 @@ pub enum FormatAlign {
     /// Distribute the words in the text evenly across the cell.
     Distributed,
 
+    /// Distribute the words in the text evenly across the cell, including the
+    /// last line of wrapped text. This is the same as [`FormatAlign::Distributed`]
+    /// with the "Justify distributed" option turned on in Excel's alignment
+    /// dialog.
+    JustifyLastLine,
+
     /// Align text to the top.
     Top,
 
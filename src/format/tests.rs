@@ -9,6 +9,7 @@ mod format_tests {
 
     use crate::Color;
     use crate::Format;
+    use crate::FormatFontScheme;
 
     #[test]
     fn test_hex_value() {
@@ -54,4 +55,21 @@ mod format_tests {
 
         assert_eq!(format1, format2);
     }
+
+    #[test]
+    fn test_font_scheme() {
+        let format1 = Format::new().set_font_scheme(FormatFontScheme::Major);
+        let format2 = Format::new().set_font_scheme("major");
+        assert_eq!(format1, format2);
+        assert_eq!(format1.font.scheme, "major");
+
+        let format1 = Format::new().set_font_scheme(FormatFontScheme::Minor);
+        let format2 = Format::new().set_font_scheme(String::from("Minor"));
+        assert_eq!(format1, format2);
+
+        let format1 = Format::new().set_font_scheme(FormatFontScheme::None);
+        let format2 = Format::new().set_font_scheme("");
+        assert_eq!(format1, format2);
+        assert_eq!(format1.font.scheme, "");
+    }
 }
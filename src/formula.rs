@@ -8,8 +8,12 @@
 
 mod tests;
 
-use regex::Regex;
+use regex::{Captures, Regex};
 use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::worksheet::{COL_MAX, ROW_MAX};
+use crate::{utility, ColNum, RowNum, XlsxError};
 
 /// The `Formula` struct is used to define a worksheet formula.
 ///
@@ -226,10 +230,12 @@ use std::borrow::Cow;
 /// - `DROP`
 /// - `EXPAND`
 /// - `FILTER`
+/// - `GROUPBY`
 /// - `HSTACK`
 /// - `LAMBDA`
 /// - `MAKEARRAY`
 /// - `MAP`
+/// - `PIVOTBY`
 /// - `RANDARRAY`
 /// - `REDUCE`
 /// - `SCAN`
@@ -373,7 +379,10 @@ use std::borrow::Cow;
 /// formula. It is just displayed by Excel 365 when reading "legacy" formulas.
 /// However, it is possible to write it to a formula, if necessary, using
 /// `SINGLE()`. The rare cases where this may be necessary are shown in the
-/// linked document in the previous paragraph.
+/// linked document in the previous paragraph. Alternatively you can write the
+/// "@" operator directly and have it converted to `SINGLE()` using
+/// [`Formula::use_implicit_intersection()`] or
+/// [`worksheet.use_implicit_intersection()`](crate::Worksheet::use_implicit_intersection).
 ///
 ///
 /// ## The Spilled Range Operator "#"
@@ -486,6 +495,14 @@ use std::borrow::Cow;
 /// with how the formulas are stored in Excel. These prefixes won't show up in
 /// the formula, as shown in the image below.
 ///
+/// If future functions are enabled with [`Formula::use_future_functions()`]
+/// or
+/// [`worksheet.use_future_functions()`](crate::Worksheet::use_future_functions)
+/// the `_xlpm.` prefixes are added automatically to the `LAMBDA()` parameters
+/// and to the variable names in `LET()` functions, so the formula above could
+/// also be written as `=LAMBDA(temp, (5/9) * (temp-32))(32)`. This doesn't
+/// apply to defined names.
+///
 /// <img src="https://rustxlsxwriter.github.io/images/app_lambda.png">
 ///
 /// The `LET()` function is often used in conjunction with `LAMBDA()` to assign
@@ -619,7 +636,7 @@ use std::borrow::Cow;
 /// | `_xlfn.HYPGEOM.DIST`             |
 /// | `_xlfn.IFNA`                     |
 /// | `_xlfn.IFS`                      |
-/// | `_xlfn.IMAGE`                   |
+/// | `_xlfn.IMAGE`                    |
 /// | `_xlfn.IMCOSH`                   |
 /// | `_xlfn.IMCOT`                    |
 /// | `_xlfn.IMCSCH`                   |
@@ -647,6 +664,7 @@ use std::borrow::Cow;
 /// | `_xlfn.NORM.S.INV`               |
 /// | `_xlfn.NUMBERVALUE`              |
 /// | `_xlfn.PDURATION`                |
+/// | `_xlfn.PERCENTOF`                |
 /// | `_xlfn.PERCENTILE.EXC`           |
 /// | `_xlfn.PERCENTILE.INC`           |
 /// | `_xlfn.PERCENTRANK.EXC`          |
@@ -659,6 +677,9 @@ use std::borrow::Cow;
 /// | `_xlfn.QUERYSTRING`              |
 /// | `_xlfn.RANK.AVG`                 |
 /// | `_xlfn.RANK.EQ`                  |
+/// | `_xlfn.REGEXEXTRACT`             |
+/// | `_xlfn.REGEXREPLACE`             |
+/// | `_xlfn.REGEXTEST`                |
 /// | `_xlfn.RRI`                      |
 /// | `_xlfn.SECH`                     |
 /// | `_xlfn.SEC`                      |
@@ -676,6 +697,7 @@ use std::borrow::Cow;
 /// | `_xlfn.TEXTAFTER`                |
 /// | `_xlfn.TEXTBEFORE`               |
 /// | `_xlfn.TEXTJOIN`                 |
+/// | `_xlfn.TRIMRANGE`                |
 /// | `_xlfn.UNICHAR`                  |
 /// | `_xlfn.UNICODE`                  |
 /// | `_xlfn.VALUETOTEXT`              |
@@ -705,10 +727,12 @@ use std::borrow::Cow;
 /// | `_xlfn.DROP`                     |
 /// | `_xlfn.EXPAND`                   |
 /// | `_xlfn._xlws.FILTER`             |
+/// | `_xlfn.GROUPBY`                  |
 /// | `_xlfn.HSTACK`                   |
 /// | `_xlfn.LAMBDA`                   |
 /// | `_xlfn.MAKEARRAY`                |
 /// | `_xlfn.MAP`                      |
+/// | `_xlfn.PIVOTBY`                  |
 /// | `_xlfn.RANDARRAY`                |
 /// | `_xlfn.REDUCE`                   |
 /// | `_xlfn.SCAN`                     |
@@ -777,7 +801,8 @@ pub struct Formula {
     formula_string: String,
     expand_future_functions: bool,
     expand_table_functions: bool,
-    pub(crate) result: Box<str>,
+    expand_implicit_intersection: bool,
+    pub(crate) result: Option<FormulaResult>,
 }
 
 impl Formula {
@@ -792,7 +817,8 @@ impl Formula {
             formula_string: formula.into(),
             expand_future_functions: false,
             expand_table_functions: false,
-            result: Box::from(""),
+            expand_implicit_intersection: false,
+            result: None,
         }
     }
 
@@ -844,7 +870,72 @@ impl Formula {
     /// src="https://rustxlsxwriter.github.io/images/worksheet_set_formula_result.png">
     ///
     pub fn set_result(mut self, result: impl Into<String>) -> Formula {
-        self.result = Box::from(result.into());
+        let result = result.into();
+        self.result = if result.is_empty() {
+            None
+        } else {
+            Some(FormulaResult::parse(&result))
+        };
+        self
+    }
+
+    /// Specify a string result for a formula.
+    ///
+    /// The result set by [`Formula::set_result()`] is stored as a number if it
+    /// looks like a number. The `set_result_string()` method always stores the
+    /// result as a string, for example for a formula like `=TEXT(A1, "000")`
+    /// that returns `"001"`.
+    ///
+    /// # Parameters
+    ///
+    /// `result` - The formula result, as a string or string like type.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting typed formula results.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_formula_set_result_string.rs
+    /// #
+    /// # use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write_number(0, 0, 1)?;
+    ///
+    ///     // A string result that looks like a number.
+    ///     let formula = Formula::new(r#"=TEXT(A1, "000")"#).set_result_string("001");
+    ///     worksheet.write_formula(0, 1, formula)?;
+    ///
+    ///     // A boolean result.
+    ///     let formula = Formula::new("=A1>0").set_result_boolean(true);
+    ///     worksheet.write_formula(0, 2, formula)?;
+    /// #
+    /// #     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_result_string(mut self, result: impl Into<String>) -> Formula {
+        self.result = Some(FormulaResult::String(result.into()));
+        self
+    }
+
+    /// Specify a boolean result for a formula.
+    ///
+    /// The `set_result_boolean()` method stores the result of a formula as an
+    /// Excel `TRUE` or `FALSE` value. See [`Formula::set_result_string()`] for
+    /// an example.
+    ///
+    /// # Parameters
+    ///
+    /// `result` - The boolean formula result.
+    ///
+    pub fn set_result_boolean(mut self, result: bool) -> Formula {
+        self.result = Some(FormulaResult::Boolean(result));
         self
     }
 
@@ -939,15 +1030,512 @@ impl Formula {
         self
     }
 
+    /// Enable the Implicit Intersection Operator "@" in the formula.
+    ///
+    /// As explained above in [The Implicit Intersection Operator
+    /// "@"](#the-implicit-intersection-operator-), Excel 365 displays a "@"
+    /// operator in a formula where a single value is implicitly used from a
+    /// range. The operator isn't stored with the formula. Instead Excel stores
+    /// an explicit "@" as the `_xlfn.SINGLE()` function.
+    ///
+    /// By default `rust_xlsxwriter` leaves a "@" in a formula unchanged. Setting
+    /// the `use_implicit_intersection()` property converts operands like
+    /// `@A1:A10` or `@INDEX(A1:A10, 2)` to `_xlfn.SINGLE(A1:A10)` and
+    /// `_xlfn.SINGLE(INDEX(A1:A10, 2))`. A "@" in a string or a structured
+    /// table reference, such as `Table1[@Sales]`, isn't changed.
+    ///
+    /// See also
+    /// [`worksheet.use_implicit_intersection()`](crate::Worksheet::use_implicit_intersection)
+    /// to turn on the conversion for all the formulas in a worksheet.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing a formula with the Implicit
+    /// Intersection Operator.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_formula_use_implicit_intersection.rs
+    /// #
+    /// # use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     worksheet.write_column(0, 0, [1, 2, 3])?;
+    /// #
+    ///     // This is stored as "_xlfn.SINGLE(A1:A3)*2" and displayed by Excel
+    ///     // 365 as "=@A1:A3*2".
+    ///     let formula = Formula::new("=@A1:A3*2").use_implicit_intersection();
+    ///
+    ///     worksheet.write_formula(0, 1, formula)?;
+    /// #
+    /// #     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn use_implicit_intersection(mut self) -> Formula {
+        self.expand_implicit_intersection = true;
+        self
+    }
+
+    /// Get the list of Excel future functions.
+    ///
+    /// The `future_functions()` method returns the names of the functions, as
+    /// listed in [List of Future Functions](#list-of-future-functions), that
+    /// are given a `_xlfn.` prefix by [`Formula::use_future_functions()`] and
+    /// [`worksheet.use_future_functions()`](crate::Worksheet::use_future_functions).
+    /// The dynamic array functions aren't included since they are always
+    /// expanded.
+    ///
+    /// This can be used to check if a formula needs the future function
+    /// handling, or to add the prefixes in another application.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates checking for future functions.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_formula_future_functions.rs
+    /// #
+    /// use rust_xlsxwriter::Formula;
+    ///
+    /// fn main() {
+    ///     assert!(Formula::future_functions().contains(&"STDEV.S"));
+    ///     assert!(Formula::future_functions().contains(&"XMATCH"));
+    ///     assert!(!Formula::future_functions().contains(&"SUM"));
+    /// }
+    /// ```
+    ///
+    pub fn future_functions() -> &'static [&'static str] {
+        FUTURE_FUNCTIONS
+    }
+
+    /// Create an absolute cell reference for use in a formula.
+    ///
+    /// The `cell_reference()` function creates a reference like `$A$1` or,
+    /// if a worksheet name is given, `Sheet2!$A$1`. The worksheet name is
+    /// quoted if required, see [`Formula::quote_sheet_name()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `sheet_name` - The worksheet name. Use `""` for a reference without
+    ///   a worksheet name.
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates building a formula from cell
+    /// references and strings.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_formula_references.rs
+    /// #
+    /// # use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let formula = format!(
+    ///         "=IF({}>{}, {}, {})",
+    ///         Formula::range_reference("Sales Data", 0, 1, 9, 1),
+    ///         Formula::cell_reference("", 0, 0),
+    ///         Formula::string_literal(r#"Over "target""#),
+    ///         Formula::string_literal("Under"),
+    ///     );
+    ///
+    ///     assert_eq!(
+    ///         formula,
+    ///         r#"=IF('Sales Data'!$B$1:$B$10>$A$1, "Over ""target""", "Under")"#
+    ///     );
+    ///
+    ///     worksheet.write_formula(0, 1, formula)?;
+    /// #
+    /// #     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn cell_reference(sheet_name: &str, row: RowNum, col: ColNum) -> String {
+        Self::range_reference(sheet_name, row, col, row, col)
+    }
+
+    /// Create an absolute range reference for use in a formula.
+    ///
+    /// The `range_reference()` function creates a reference like `$A$1:$B$10`
+    /// or, if a worksheet name is given, `Sheet2!$A$1:$B$10`. The worksheet
+    /// name is quoted if required, see [`Formula::quote_sheet_name()`]. If the
+    /// first and last cells are the same a single cell reference is created.
+    ///
+    /// See the example in [`Formula::cell_reference()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `sheet_name` - The worksheet name. Use `""` for a reference without
+    ///   a worksheet name.
+    /// * `first_row` - The first row of the range.
+    /// * `first_col` - The first column of the range.
+    /// * `last_row` - The last row of the range.
+    /// * `last_col` - The last column of the range.
+    ///
+    pub fn range_reference(
+        sheet_name: &str,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+    ) -> String {
+        let range = utility::cell_range_absolute(first_row, first_col, last_row, last_col);
+
+        if sheet_name.is_empty() {
+            range
+        } else {
+            format!("{}!{range}", Self::quote_sheet_name(sheet_name))
+        }
+    }
+
+    /// Quote a worksheet name for use in a formula.
+    ///
+    /// Excel requires worksheet names that contain spaces or some other
+    /// characters to be single quoted in formulas, like `'Sales Data'!A1`, and
+    /// any apostrophes in the name to be doubled. Names that don't need to be
+    /// quoted, or are already quoted, are returned unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `sheet_name` - The worksheet name.
+    ///
+    pub fn quote_sheet_name(sheet_name: &str) -> String {
+        utility::quote_sheetname(sheet_name)
+    }
+
+    /// Create a string literal for use in a formula.
+    ///
+    /// Strings in Excel formulas are enclosed in double quotes and any double
+    /// quotes in the string are escaped by doubling them. The
+    /// `string_literal()` function applies this escaping so that arbitrary
+    /// text can be used safely in a formula.
+    ///
+    /// See the example in [`Formula::cell_reference()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The string to quote.
+    ///
+    pub fn string_literal(text: &str) -> String {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    }
+
+    /// Check the formula for syntax errors.
+    ///
+    /// A malformed formula isn't detected by `rust_xlsxwriter` when it is
+    /// written. Instead Excel displays a warning when the file is opened and
+    /// tries to repair it, without indicating which formula caused the
+    /// problem. The `validate()` method makes some basic checks on the
+    /// formula to catch common errors in advance:
+    ///
+    /// * Unbalanced parentheses and unterminated strings.
+    /// * Unknown function names. Functions with the `_xludf.` user defined
+    ///   function prefix aren't checked.
+    /// * Invalid cell references such as `A0` or `A1048577`, and `#REF!`
+    ///   errors.
+    ///
+    /// The checks aren't a full Excel formula parser so a formula that passes
+    /// them may still be incorrect.
+    ///
+    /// See also [`Worksheet::validate_formulas()`](crate::Worksheet::validate_formulas)
+    /// to check all the formulas in a worksheet.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::FormulaError`] - A description of the first syntax
+    ///   error found in the formula.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates checking formulas for syntax
+    /// errors.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_formula_validate.rs
+    /// #
+    /// # use rust_xlsxwriter::{Formula, XlsxError};
+    /// #
+    /// # fn main() {
+    ///     assert!(Formula::new("=SUM(A1:A10)").validate().is_ok());
+    ///
+    ///     // Missing closing parenthesis.
+    ///     let result = Formula::new("=SUM(A1:A10").validate();
+    ///     assert!(matches!(result, Err(XlsxError::FormulaError(_))));
+    ///
+    ///     // Misspelled function name.
+    ///     let result = Formula::new("=SUMM(A1:A10)").validate();
+    ///     assert!(matches!(result, Err(XlsxError::FormulaError(_))));
+    /// # }
+    /// ```
+    ///
+    pub fn validate(&self) -> Result<(), XlsxError> {
+        let formula = self.expand_formula(false);
+
+        Self::check_syntax(&formula, &[]).map_err(XlsxError::FormulaError)
+    }
+
+    // Check an expanded formula string for unbalanced parentheses, unknown
+    // function names and invalid references. Names in `user_functions`, such
+    // as defined names for LAMBDA functions, are accepted as function names.
+    pub(crate) fn check_syntax(formula: &str, user_functions: &[String]) -> Result<(), String> {
+        let chars: Vec<char> = formula.chars().collect();
+        let mut depth = 0;
+        let mut functions = vec![];
+        let mut names = vec![];
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            match c {
+                // Skip over strings and quoted worksheet names. The quote
+                // character is escaped by doubling it.
+                '"' | '\'' => {
+                    i += 1;
+                    loop {
+                        if i >= chars.len() {
+                            return Err(format!("Unterminated {c} quote in formula '{formula}'"));
+                        }
+                        if chars[i] == c {
+                            if chars.get(i + 1) == Some(&c) {
+                                i += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                        i += 1;
+                    }
+                    i += 1;
+                }
+
+                // Skip over structured references and external workbook
+                // references.
+                '[' => {
+                    let mut brackets = 0;
+                    while i < chars.len() {
+                        match chars[i] {
+                            '[' => brackets += 1,
+                            ']' => brackets -= 1,
+                            _ => {}
+                        }
+                        i += 1;
+                        if brackets == 0 {
+                            break;
+                        }
+                    }
+                    if brackets != 0 {
+                        return Err(format!("Unbalanced brackets in formula '{formula}'"));
+                    }
+                }
+
+                '(' => {
+                    depth += 1;
+                    i += 1;
+                }
+
+                ')' => {
+                    depth -= 1;
+                    if depth < 0 {
+                        return Err(format!("Unbalanced parentheses in formula '{formula}'"));
+                    }
+                    i += 1;
+                }
+
+                '#' => {
+                    let rest: String = chars[i..].iter().collect();
+                    if rest.to_uppercase().starts_with("#REF!") {
+                        return Err(format!("Invalid reference '#REF!' in formula '{formula}'"));
+                    }
+                    i += 1;
+                }
+
+                // Skip over numbers, including exponents like 1E+10.
+                '0'..='9' => {
+                    while i < chars.len() {
+                        let n = chars[i];
+                        if n.is_ascii_digit() || n == '.' {
+                            i += 1;
+                        } else if (n == 'E' || n == 'e')
+                            && chars
+                                .get(i + 1)
+                                .is_some_and(|d| d.is_ascii_digit() || *d == '+' || *d == '-')
+                        {
+                            i += 2;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+
+                _ if c.is_alphabetic() || c == '_' || c == '\\' || c == '$' => {
+                    let start = i;
+                    while i < chars.len()
+                        && (chars[i].is_alphanumeric()
+                            || matches!(chars[i], '_' | '.' | '$' | '\\'))
+                    {
+                        i += 1;
+                    }
+                    let token: String = chars[start..i].iter().collect();
+
+                    let mut next = i;
+                    while next < chars.len() && chars[next] == ' ' {
+                        next += 1;
+                    }
+
+                    match chars.get(next) {
+                        Some('(') => functions.push(token),
+                        Some('!') => {}
+                        _ => {
+                            Self::check_reference(&token, formula)?;
+                            names.push(token.to_uppercase());
+                        }
+                    }
+                }
+
+                _ => i += 1,
+            }
+        }
+
+        if depth != 0 {
+            return Err(format!("Unbalanced parentheses in formula '{formula}'"));
+        }
+
+        // Excel worksheet function names, including the future and dynamic
+        // array functions without their `_xlfn.` prefixes.
+        lazy_static! {
+            static ref EXCEL_FUNCTIONS: HashSet<&'static str> = "\
+            ABS ACCRINT ACCRINTM ACOS ACOSH ACOT ACOTH ADDRESS AGGREGATE AMORDEGRC AMORLINC AND \
+            ANCHORARRAY ARABIC AREAS ARRAYTOTEXT ASC ASIN ASINH ATAN ATAN2 ATANH AVEDEV AVERAGE \
+            AVERAGEA AVERAGEIF AVERAGEIFS BAHTTEXT BASE BESSELI BESSELJ BESSELK BESSELY \
+            BETA.DIST BETA.INV BETADIST BETAINV BIN2DEC BIN2HEX BIN2OCT BINOM.DIST \
+            BINOM.DIST.RANGE BINOM.INV BINOMDIST BITAND BITLSHIFT BITOR BITRSHIFT BITXOR BYCOL \
+            BYROW CALL CEILING CEILING.MATH CEILING.PRECISE CELL CHAR CHIDIST CHIINV CHISQ.DIST \
+            CHISQ.DIST.RT CHISQ.INV CHISQ.INV.RT CHISQ.TEST CHITEST CHOOSE CHOOSECOLS CHOOSEROWS \
+            CLEAN CODE COLUMN COLUMNS COMBIN COMBINA COMPLEX CONCAT CONCATENATE CONFIDENCE \
+            CONFIDENCE.NORM CONFIDENCE.T CONVERT CORREL COS COSH COT COTH COUNT COUNTA \
+            COUNTBLANK COUNTIF COUNTIFS COUPDAYBS COUPDAYS COUPDAYSNC COUPNCD COUPNUM COUPPCD \
+            COVAR COVARIANCE.P COVARIANCE.S CRITBINOM CSC CSCH CUBEKPIMEMBER CUBEMEMBER \
+            CUBEMEMBERPROPERTY CUBERANKEDMEMBER CUBESET CUBESETCOUNT CUBEVALUE CUMIPMT CUMPRINC \
+            DATE DATEDIF DATEVALUE DAVERAGE DAY DAYS DAYS360 DB DBCS DCOUNT DCOUNTA DDB DEC2BIN \
+            DEC2HEX DEC2OCT DECIMAL DEGREES DELTA DEVSQ DGET DISC DMAX DMIN DOLLAR DOLLARDE \
+            DOLLARFR DPRODUCT DROP DSTDEV DSTDEVP DSUM DURATION DVAR DVARP EDATE EFFECT \
+            ENCODEURL EOMONTH ERF ERF.PRECISE ERFC ERFC.PRECISE ERROR.TYPE EUROCONVERT EVEN \
+            EXACT EXP EXPAND EXPON.DIST EXPONDIST F.DIST F.DIST.RT F.INV F.INV.RT F.TEST FACT \
+            FACTDOUBLE FALSE FDIST FILTER FILTERXML FIND FINDB FINV FISHER FISHERINV FIXED FLOOR \
+            FLOOR.MATH FLOOR.PRECISE FORECAST FORECAST.ETS FORECAST.ETS.CONFINT \
+            FORECAST.ETS.SEASONALITY FORECAST.ETS.STAT FORECAST.LINEAR FORMULATEXT FREQUENCY \
+            FTEST FV FVSCHEDULE GAMMA GAMMA.DIST GAMMA.INV GAMMADIST GAMMAINV GAMMALN \
+            GAMMALN.PRECISE GAUSS GCD GEOMEAN GESTEP GETPIVOTDATA GROUPBY GROWTH HARMEAN HEX2BIN \
+            HEX2DEC HEX2OCT HLOOKUP HOUR HSTACK HYPERLINK HYPGEOM.DIST HYPGEOMDIST IF IFERROR \
+            IFNA IFS IMABS IMAGE IMAGINARY IMARGUMENT IMCONJUGATE IMCOS IMCOSH IMCOT IMCSC \
+            IMCSCH IMDIV IMEXP IMLN IMLOG10 IMLOG2 IMPOWER IMPRODUCT IMREAL IMSEC IMSECH IMSIN \
+            IMSINH IMSQRT IMSUB IMSUM IMTAN INDEX INDIRECT INFO INT INTERCEPT INTRATE IPMT IRR \
+            ISBLANK ISERR ISERROR ISEVEN ISFORMULA ISLOGICAL ISNA ISNONTEXT ISNUMBER ISODD \
+            ISOMITTED ISOWEEKNUM ISPMT ISREF ISTEXT JIS KURT LAMBDA LARGE LCM LEFT LEFTB LEN \
+            LENB LET LINEST LN LOG LOG10 LOGEST LOGINV LOGNORM.DIST LOGNORM.INV LOGNORMDIST \
+            LOOKUP LOWER MAKEARRAY MAP MATCH MAX MAXA MAXIFS MDETERM MDURATION MEDIAN MID MIDB \
+            MIN MINA MINIFS MINUTE MINVERSE MIRR MMULT MOD MODE MODE.MULT MODE.SNGL MONTH MROUND \
+            MULTINOMIAL MUNIT N NA NEGBINOM.DIST NEGBINOMDIST NETWORKDAYS NETWORKDAYS.INTL \
+            NOMINAL NORM.DIST NORM.INV NORM.S.DIST NORM.S.INV NORMDIST NORMINV NORMSDIST \
+            NORMSINV NOT NOW NPER NPV NUMBERVALUE OCT2BIN OCT2DEC OCT2HEX ODD ODDFPRICE \
+            ODDFYIELD ODDLPRICE ODDLYIELD OFFSET OR PDURATION PEARSON PERCENTILE PERCENTILE.EXC \
+            PERCENTILE.INC PERCENTOF PERCENTRANK PERCENTRANK.EXC PERCENTRANK.INC PERMUT \
+            PERMUTATIONA PHI PHONETIC PI PIVOTBY PMT POISSON POISSON.DIST POWER PPMT PRICE \
+            PRICEDISC PRICEMAT PROB PRODUCT PROPER PV QUARTILE QUARTILE.EXC QUARTILE.INC \
+            QUERYSTRING QUOTIENT RADIANS RAND RANDARRAY RANDBETWEEN RANK RANK.AVG RANK.EQ RATE \
+            RECEIVED REDUCE REGEXEXTRACT REGEXREPLACE REGEXTEST REGISTER.ID REPLACE REPLACEB \
+            REPT RIGHT RIGHTB ROMAN ROUND ROUNDDOWN ROUNDUP ROW ROWS RRI RSQ RTD SCAN SEARCH \
+            SEARCHB SEC SECH SECOND SEQUENCE SERIESSUM SHEET SHEETS SIGN SIN SINGLE SINH SKEW \
+            SKEW.P SLN SLOPE SMALL SORT SORTBY SQRT SQRTPI STANDARDIZE STDEV STDEV.P STDEV.S \
+            STDEVA STDEVP STDEVPA STEYX SUBSTITUTE SUBTOTAL SUM SUMIF SUMIFS SUMPRODUCT SUMSQ \
+            SUMX2MY2 SUMX2PY2 SUMXMY2 SWITCH SYD T T.DIST T.DIST.2T T.DIST.RT T.INV T.INV.2T \
+            T.TEST TAKE TAN TANH TBILLEQ TBILLPRICE TBILLYIELD TDIST TEXT TEXTAFTER TEXTBEFORE \
+            TEXTJOIN TEXTSPLIT TIME TIMEVALUE TINV TOCOL TODAY TOROW TRANSPOSE TREND TRIM \
+            TRIMMEAN TRIMRANGE TRUE TRUNC TTEST TYPE UNICHAR UNICODE UNIQUE UPPER USDOLLAR VALUE \
+            VALUETOTEXT VAR VAR.P VAR.S VARA VARP VARPA VDB VLOOKUP VSTACK WEBSERVICE WEEKDAY \
+            WEEKNUM WEIBULL WEIBULL.DIST WORKDAY WORKDAY.INTL WRAPCOLS WRAPROWS XIRR XLOOKUP \
+            XMATCH XNPV XOR YEAR YEARFRAC YIELD YIELDDISC YIELDMAT Z.TEST ZTEST"
+                .split_whitespace()
+                .collect();
+        }
+
+        // Check the function names. Names that are also used as plain names
+        // in the formula are assumed to be LET or LAMBDA variables.
+        for function in functions {
+            let mut name = function.to_uppercase();
+            if name.starts_with("_XLUDF.") || name.starts_with("_XLPM.") {
+                continue;
+            }
+            for prefix in ["_XLFN.", "_XLWS."] {
+                if let Some(stripped) = name.strip_prefix(prefix) {
+                    name = stripped.to_string();
+                }
+            }
+
+            if !EXCEL_FUNCTIONS.contains(name.as_str())
+                && !names.contains(&name)
+                && !user_functions
+                    .iter()
+                    .any(|user| user.to_uppercase() == name)
+            {
+                return Err(format!(
+                    "Unknown function '{function}' in formula '{formula}'"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Check that a token that looks like an A1 style cell reference is within
+    // the Excel row limits.
+    fn check_reference(token: &str, formula: &str) -> Result<(), String> {
+        lazy_static! {
+            static ref CELL: Regex = Regex::new(r"^\$?([A-Za-z]{1,3})\$?(\d+)$").unwrap();
+        }
+
+        if let Some(caps) = CELL.captures(token) {
+            let col = utility::column_name_to_number(&caps[1].to_uppercase());
+            let row = caps[2].parse::<u64>().unwrap_or(u64::MAX);
+
+            if col < COL_MAX && (row == 0 || row > u64::from(ROW_MAX)) {
+                return Err(format!(
+                    "Invalid cell reference '{token}' in formula '{formula}'"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     // Check of a dynamic function/formula.
     pub(crate) fn is_dynamic_function(&self) -> bool {
         lazy_static! {
             static ref DYNAMIC_FUNCTION: Regex = Regex::new(
-                r"\b(ANCHORARRAY|BYCOL|BYROW|CHOOSECOLS|CHOOSEROWS|DROP|EXPAND|FILTER|HSTACK|LAMBDA|MAKEARRAY|MAP|RANDARRAY|REDUCE|SCAN|SEQUENCE|SINGLE|SORT|SORTBY|SWITCH|TAKE|TEXTSPLIT|TOCOL|TOROW|UNIQUE|VSTACK|WRAPCOLS|WRAPROWS|XLOOKUP)\("
+                r"\b(ANCHORARRAY|BYCOL|BYROW|CHOOSECOLS|CHOOSEROWS|DROP|EXPAND|FILTER|GROUPBY|HSTACK|LAMBDA|MAKEARRAY|MAP|PIVOTBY|RANDARRAY|REDUCE|SCAN|SEQUENCE|SINGLE|SORT|SORTBY|SWITCH|TAKE|TEXTSPLIT|TOCOL|TOROW|UNIQUE|VSTACK|WRAPCOLS|WRAPROWS|XLOOKUP)\("
             )
             .unwrap();
         }
-        DYNAMIC_FUNCTION.is_match(&self.formula_string)
+        Self::literal_spans(&self.formula_string)
+            .iter()
+            .chain(&[(self.formula_string.len(), self.formula_string.len())])
+            .scan(0, |position, &(start, end)| {
+                let code = &self.formula_string[*position..start];
+                *position = end;
+                Some(code)
+            })
+            .any(|code| DYNAMIC_FUNCTION.is_match(code))
     }
 
     // Utility method to optionally strip equal sign and array braces from a
@@ -955,28 +1543,36 @@ impl Formula {
     pub(crate) fn expand_formula(&self, global_expand_future_functions: bool) -> Box<str> {
         let mut formula = self.formula_string.as_str();
 
-        // Remove array formula braces and the leading = if they exist.
+        // Remove array formula braces and the leading = if they exist. The
+        // closing brace is only removed along with an opening brace so that
+        // array constants like "={1,2;3,4}" aren't truncated.
         if let Some(stripped) = formula.strip_prefix('{') {
-            formula = stripped;
+            formula = stripped.strip_suffix('}').unwrap_or(stripped);
         }
         if let Some(stripped) = formula.strip_prefix('=') {
             formula = stripped;
         }
-        if let Some(stripped) = formula.strip_suffix('}') {
-            formula = stripped;
-        }
 
         // Exit if formula is already expanded by the user.
         if formula.contains("_xlfn.") {
             return Box::from(formula);
         }
 
+        // Convert the "@" implicit intersection operator to SINGLE(), which is
+        // then expanded along with the other dynamic functions.
+        let formula = if self.expand_implicit_intersection {
+            Self::escape_implicit_intersection(formula)
+        } else {
+            Cow::Borrowed(formula)
+        };
+
         // Expand dynamic formulas.
-        let escaped_formula = Self::escape_dynamic_formulas1(formula);
+        let escaped_formula = Self::escape_dynamic_formulas1(&formula);
         let escaped_formula = Self::escape_dynamic_formulas2(&escaped_formula);
 
         let formula = if self.expand_future_functions || global_expand_future_functions {
-            Self::escape_future_functions(&escaped_formula)
+            let formula = Self::escape_future_functions(&escaped_formula);
+            Cow::Owned(Self::escape_lambda_parameters(&formula).into_owned())
         } else {
             escaped_formula
         };
@@ -994,11 +1590,11 @@ impl Formula {
     fn escape_dynamic_formulas1(formula: &str) -> Cow<str> {
         lazy_static! {
             static ref XLFN: Regex = Regex::new(
-                r"\b(ANCHORARRAY|BYCOL|BYROW|CHOOSECOLS|CHOOSEROWS|DROP|EXPAND|HSTACK|LAMBDA|MAKEARRAY|MAP|RANDARRAY|REDUCE|SCAN|SEQUENCE|SINGLE|SORTBY|SWITCH|TAKE|TEXTSPLIT|TOCOL|TOROW|UNIQUE|VSTACK|WRAPCOLS|WRAPROWS|XLOOKUP)\("
+                r"\b(ANCHORARRAY|BYCOL|BYROW|CHOOSECOLS|CHOOSEROWS|DROP|EXPAND|GROUPBY|HSTACK|LAMBDA|MAKEARRAY|MAP|PIVOTBY|RANDARRAY|REDUCE|SCAN|SEQUENCE|SINGLE|SORTBY|SWITCH|TAKE|TEXTSPLIT|TOCOL|TOROW|UNIQUE|VSTACK|WRAPCOLS|WRAPROWS|XLOOKUP)\("
             )
             .unwrap();
         }
-        XLFN.replace_all(formula, "_xlfn.$1(")
+        Self::replace_outside_literals(formula, &XLFN, "_xlfn.$1(")
     }
 
     // Escape/expand the dynamic formula _xlfn._xlws. functions.
@@ -1006,28 +1602,652 @@ impl Formula {
         lazy_static! {
             static ref XLWS: Regex = Regex::new(r"\b(FILTER|SORT)\(").unwrap();
         }
-        XLWS.replace_all(formula, "_xlfn._xlws.$1(")
+        Self::replace_outside_literals(formula, &XLWS, "_xlfn._xlws.$1(")
     }
 
     // Escape/expand future/_xlfn functions.
     fn escape_future_functions(formula: &str) -> Cow<str> {
         lazy_static! {
-            static ref FUTURE: Regex = Regex::new(
-                r"\b(ACOTH|ACOT|AGGREGATE|ARABIC|ARRAYTOTEXT|BASE|BETA.DIST|BETA.INV|BINOM.DIST.RANGE|BINOM.DIST|BINOM.INV|BITAND|BITLSHIFT|BITOR|BITRSHIFT|BITXOR|CEILING.MATH|CEILING.PRECISE|CHISQ.DIST.RT|CHISQ.DIST|CHISQ.INV.RT|CHISQ.INV|CHISQ.TEST|COMBINA|CONCAT|CONFIDENCE.NORM|CONFIDENCE.T|COTH|COT|COVARIANCE.P|COVARIANCE.S|CSCH|CSC|DAYS|DECIMAL|ERF.PRECISE|ERFC.PRECISE|EXPON.DIST|F.DIST.RT|F.DIST|F.INV.RT|F.INV|F.TEST|FILTERXML|FLOOR.MATH|FLOOR.PRECISE|FORECAST.ETS.CONFINT|FORECAST.ETS.SEASONALITY|FORECAST.ETS.STAT|FORECAST.ETS|FORECAST.LINEAR|FORMULATEXT|GAMMA.DIST|GAMMA.INV|GAMMALN.PRECISE|GAMMA|GAUSS|HYPGEOM.DIST|IFNA|IFS|IMAGE|IMCOSH|IMCOT|IMCSCH|IMCSC|IMSECH|IMSEC|IMSINH|IMTAN|ISFORMULA|ISOMITTED|ISOWEEKNUM|LET|LOGNORM.DIST|LOGNORM.INV|MAXIFS|MINIFS|MODE.MULT|MODE.SNGL|MUNIT|NEGBINOM.DIST|NORM.DIST|NORM.INV|NORM.S.DIST|NORM.S.INV|NUMBERVALUE|PDURATION|PERCENTILE.EXC|PERCENTILE.INC|PERCENTRANK.EXC|PERCENTRANK.INC|PERMUTATIONA|PHI|POISSON.DIST|QUARTILE.EXC|QUARTILE.INC|QUERYSTRING|RANK.AVG|RANK.EQ|RRI|SECH|SEC|SHEETS|SHEET|SKEW.P|STDEV.P|STDEV.S|T.DIST.2T|T.DIST.RT|T.DIST|T.INV.2T|T.INV|T.TEST|TEXTAFTER|TEXTBEFORE|TEXTJOIN|UNICHAR|UNICODE|VALUETOTEXT|VAR.P|VAR.S|WEBSERVICE|WEIBULL.DIST|XMATCH|XOR|Z.TEST)\("
-            )
+            static ref FUTURE: Regex = Regex::new(&format!(
+                r"\b({})\(",
+                FUTURE_FUNCTIONS
+                    .iter()
+                    .map(|function| regex::escape(function))
+                    .collect::<Vec<String>>()
+                    .join("|")
+            ))
             .unwrap();
         }
-        FUTURE.replace_all(formula, "_xlfn.$1(")
+        Self::replace_outside_literals(formula, &FUTURE, "_xlfn.$1(")
+    }
+
+    // Add the "_xlpm." prefix to the parameter names of LAMBDA() functions and
+    // the variable names of LET() functions, and to the uses of the names
+    // within the function that declares them.
+    fn escape_lambda_parameters(formula: &str) -> Cow<'_, str> {
+        let tokens = Self::tokenize_names(formula);
+        let mut is_parameter = vec![false; tokens.len()];
+
+        // Find the names declared in the LAMBDA() and LET() arguments. For
+        // LAMBDA() these are all the arguments except the last. For LET()
+        // they are the even numbered arguments except the last.
+        for (index, token) in tokens.iter().enumerate() {
+            let FormulaToken::Name(start, end) = *token else {
+                continue;
+            };
+
+            let name = formula[start..end].to_uppercase();
+            let name = name.strip_prefix("_XLFN.").unwrap_or(&name);
+            let is_lambda = name == "LAMBDA";
+            if !(is_lambda || name == "LET") || tokens.get(index + 1) != Some(&FormulaToken::Open) {
+                continue;
+            }
+
+            // Split the top level function arguments and find the end of the
+            // function, which is the scope of the declared names.
+            let mut arguments = vec![vec![]];
+            let mut depth = 0;
+            let mut scope_end = tokens.len();
+            for (offset, token) in tokens[index + 2..].iter().enumerate() {
+                match token {
+                    FormulaToken::Open => depth += 1,
+                    FormulaToken::Close if depth == 0 => {
+                        scope_end = index + 2 + offset;
+                        break;
+                    }
+                    FormulaToken::Close => depth -= 1,
+                    FormulaToken::Comma if depth == 0 => {
+                        arguments.push(vec![]);
+                        continue;
+                    }
+                    _ => {}
+                }
+                arguments.last_mut().unwrap().push(*token);
+            }
+
+            let mut parameters = HashSet::new();
+            let last = arguments.len() - 1;
+            for (position, argument) in arguments.iter().enumerate() {
+                if position == last || (!is_lambda && position % 2 == 1) {
+                    continue;
+                }
+
+                if let [FormulaToken::Name(start, end)] = argument[..] {
+                    parameters.insert(formula[start..end].to_uppercase());
+                }
+            }
+
+            if parameters.is_empty() {
+                continue;
+            }
+
+            // Mark the uses of the names within the function.
+            for (position, token) in tokens.iter().enumerate().take(scope_end).skip(index + 2) {
+                if let FormulaToken::Name(start, end) = *token {
+                    let is_sheet_name = formula[end..].trim_start().starts_with('!');
+
+                    if !is_sheet_name && parameters.contains(&formula[start..end].to_uppercase()) {
+                        is_parameter[position] = true;
+                    }
+                }
+            }
+        }
+
+        if !is_parameter.contains(&true) {
+            return Cow::Borrowed(formula);
+        }
+
+        // Add the prefix to each use of the names.
+        let mut escaped = String::with_capacity(formula.len());
+        let mut position = 0;
+        for (token, is_parameter) in tokens.iter().zip(is_parameter) {
+            if let (FormulaToken::Name(start, end), true) = (*token, is_parameter) {
+                escaped.push_str(&formula[position..start]);
+                escaped.push_str("_xlpm.");
+                escaped.push_str(&formula[start..end]);
+                position = end;
+            }
+        }
+        escaped.push_str(&formula[position..]);
+
+        Cow::Owned(escaped)
+    }
+
+    // Split a formula into name tokens and the parentheses and commas needed
+    // to find function arguments. Strings, quoted sheet names and structured
+    // references are skipped.
+    fn tokenize_names(formula: &str) -> Vec<FormulaToken> {
+        let mut tokens = vec![];
+        let mut chars = formula.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            match c {
+                '"' | '\'' => {
+                    while let Some((_, next)) = chars.next() {
+                        if next == c {
+                            if chars.peek().map(|(_, n)| *n) == Some(c) {
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                }
+                '[' => {
+                    let mut depth = 1;
+                    for (_, next) in chars.by_ref() {
+                        match next {
+                            '[' => depth += 1,
+                            ']' => depth -= 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                '(' => tokens.push(FormulaToken::Open),
+                ')' => tokens.push(FormulaToken::Close),
+                ',' => tokens.push(FormulaToken::Comma),
+                _ if c.is_alphabetic() || c == '_' || c == '\\' => {
+                    let mut end = start + c.len_utf8();
+                    while let Some(&(index, next)) = chars.peek() {
+                        if next.is_alphanumeric() || next == '_' || next == '.' {
+                            end = index + next.len_utf8();
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+
+                    // Names that are already prefixed are ignored.
+                    if formula[start..end].to_uppercase().starts_with("_XLPM.") {
+                        tokens.push(FormulaToken::Other);
+                    } else {
+                        tokens.push(FormulaToken::Name(start, end));
+                    }
+                }
+                '0'..='9' => {
+                    while chars
+                        .peek()
+                        .is_some_and(|(_, next)| next.is_alphanumeric() || *next == '.')
+                    {
+                        chars.next();
+                    }
+                    tokens.push(FormulaToken::Other);
+                }
+                _ if c.is_whitespace() => {}
+                _ => tokens.push(FormulaToken::Other),
+            }
+        }
+
+        tokens
     }
 
     // Escape/expand table functions.
     fn escape_table_functions(formula: &str) -> Cow<str> {
-        // Convert Excel 2010 "@" table ref to 2007 "#This Row".
+        // Convert Excel 2010 "@" table ref to 2007 "#This Row". Only the "@"
+        // characters at the start of structured references are converted. A
+        // bare column name after the "@" must be bracketed in the 2007 syntax
+        // so "[@Price]" becomes "[[#This Row],[Price]]" and "[@]" becomes
+        // "[#This Row]".
+        let mut escaped = String::with_capacity(formula.len());
+        let mut position = 0;
+        for (start, end) in Self::literal_spans(formula) {
+            let span = &formula[start..end];
+            if let Some(reference) = span.strip_prefix("[@") {
+                escaped.push_str(&formula[position..start]);
+
+                if reference == "]" {
+                    escaped.push_str("[#This Row]");
+                } else if reference.starts_with('[') {
+                    escaped.push_str("[[#This Row],");
+                    escaped.push_str(reference);
+                } else {
+                    escaped.push_str("[[#This Row],[");
+                    escaped.push_str(reference);
+                    escaped.push(']');
+                }
+
+                position = end;
+            }
+        }
+
+        if position == 0 {
+            return Cow::Borrowed(formula);
+        }
+
+        escaped.push_str(&formula[position..]);
+        Cow::Owned(escaped)
+    }
+
+    // Convert the "@" implicit intersection operator to the SINGLE() function
+    // that Excel uses to store it. For example "@A1:A10" is converted to
+    // "SINGLE(A1:A10)".
+    fn escape_implicit_intersection(formula: &str) -> Cow<'_, str> {
+        let spans = Self::literal_spans(formula);
+        let mut escaped = String::with_capacity(formula.len());
+        let mut position = 0;
+
+        for (index, _) in formula.match_indices('@') {
+            if index < position
+                || spans
+                    .iter()
+                    .any(|&(start, end)| start <= index && index < end)
+            {
+                continue;
+            }
+
+            let end = Self::operand_end(formula, index + 1);
+            if end == index + 1 {
+                continue;
+            }
+
+            // Parentheses around the operand aren't repeated.
+            let operand = &formula[index + 1..end];
+            let operand = operand
+                .strip_prefix('(')
+                .and_then(|operand| operand.strip_suffix(')'))
+                .unwrap_or(operand);
+
+            escaped.push_str(&formula[position..index]);
+            escaped.push_str("SINGLE(");
+            escaped.push_str(&Self::escape_implicit_intersection(operand));
+            escaped.push(')');
+            position = end;
+        }
+
+        if position == 0 {
+            return Cow::Borrowed(formula);
+        }
+
+        escaped.push_str(&formula[position..]);
+        Cow::Owned(escaped)
+    }
+
+    // Find the end of the operand following an implicit intersection
+    // operator. The operand can be a cell, range, name or structured
+    // reference, a function call or an expression in parentheses.
+    fn operand_end(formula: &str, start: usize) -> usize {
+        let mut end = start;
+        let mut chars = formula[start..].char_indices().peekable();
+
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '\'' if offset == 0 => {
+                    while let Some((_, next)) = chars.next() {
+                        if next == '\'' {
+                            if chars.peek().map(|(_, n)| *n) == Some('\'') {
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                }
+                '[' | '(' => {
+                    let (open, close) = if c == '[' { ('[', ']') } else { ('(', ')') };
+                    let mut depth = 1;
+                    let mut in_string = false;
+                    for (_, next) in chars.by_ref() {
+                        match next {
+                            '"' => in_string = !in_string,
+                            _ if in_string => {}
+                            _ if next == open => depth += 1,
+                            _ if next == close => depth -= 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+
+                    // A function call or expression completes the operand.
+                    if c == '(' {
+                        return chars.peek().map_or(formula.len(), |(next, _)| start + next);
+                    }
+                }
+                _ if c.is_alphanumeric() || "_.$!:\\".contains(c) => {}
+                _ => break,
+            }
+
+            end = chars.peek().map_or(formula.len(), |(next, _)| start + next);
+        }
+
+        end
+    }
+
+    // Apply a regex replacement to the parts of a formula that aren't strings,
+    // quoted sheet names or structured references so that they aren't
+    // mangled.
+    fn replace_outside_literals<'a>(
+        formula: &'a str,
+        regex: &Regex,
+        replacement: &str,
+    ) -> Cow<'a, str> {
+        let mut escaped = String::with_capacity(formula.len());
+        let mut position = 0;
+        let mut is_changed = false;
+
+        let spans = Self::literal_spans(formula);
+        for (start, end) in spans.into_iter().chain([(formula.len(), formula.len())]) {
+            let code = regex.replace_all(&formula[position..start], replacement);
+            is_changed |= matches!(code, Cow::Owned(_));
+            escaped.push_str(&code);
+            escaped.push_str(&formula[start..end]);
+            position = end;
+        }
+
+        if is_changed {
+            Cow::Owned(escaped)
+        } else {
+            Cow::Borrowed(formula)
+        }
+    }
+
+    // Shift the relative A1 style cell, row and column references in a formula
+    // by a row and column offset, as Excel does when a formula is copied to
+    // another cell. Absolute references aren't changed and references that
+    // are shifted outside the worksheet are converted to "#REF!".
+    pub(crate) fn shift_references(formula: &str, row_offset: i64, col_offset: i64) -> String {
         lazy_static! {
-            static ref TABLE: Regex = Regex::new(r"@").unwrap();
+            static ref REFERENCE: Regex = Regex::new(
+                r"(\$?)([A-Za-z]{1,3})(\$?)(\d+)|(\$?)([A-Za-z]{1,3}):(\$?)([A-Za-z]{1,3})|(\$?)(\d+):(\$?)(\d+)"
+            )
+            .unwrap();
         }
-        TABLE.replace_all(formula, "[#This Row],")
+
+        let mut shifted = String::with_capacity(formula.len());
+        let mut position = 0;
+
+        let spans = Self::literal_spans(formula);
+        for (start, end) in spans.into_iter().chain([(formula.len(), formula.len())]) {
+            let code = &formula[position..start];
+            let mut last = 0;
+
+            for caps in REFERENCE.captures_iter(code) {
+                let token = caps.get(0).unwrap();
+
+                // Ignore function names and defined names that contain a
+                // reference like string such as LOG10() or Sales2024.
+                let before = code[..token.start()].chars().next_back();
+                let after = code[token.end()..].chars().next();
+                if before.is_some_and(|c| c.is_alphanumeric() || "_.\\".contains(c))
+                    || after.is_some_and(|c| c.is_alphanumeric() || "_.(".contains(c))
+                {
+                    continue;
+                }
+
+                if let Some(reference) = Self::shift_reference(&caps, row_offset, col_offset) {
+                    shifted.push_str(&code[last..token.start()]);
+                    shifted.push_str(&reference);
+                    last = token.end();
+                }
+            }
+
+            shifted.push_str(&code[last..]);
+            shifted.push_str(&formula[start..end]);
+            position = end;
+        }
+
+        shifted
     }
+
+    // Shift a cell, column range or row range reference matched by the regex
+    // in shift_references(). Returns None if the token isn't a valid
+    // reference.
+    fn shift_reference(caps: &Captures, row_offset: i64, col_offset: i64) -> Option<String> {
+        // The capture group pairs for the optional "$" and the column name or
+        // row number of each part of the reference.
+        let (groups, separator) = if caps.get(2).is_some() {
+            ([(1, 2), (3, 4)], "")
+        } else if caps.get(6).is_some() {
+            ([(5, 6), (7, 8)], ":")
+        } else {
+            ([(9, 10), (11, 12)], ":")
+        };
+
+        let mut parts = vec![];
+        for (dollar, value) in groups {
+            let is_absolute = !caps[dollar].is_empty();
+            let value = &caps[value];
+            let is_row = value.starts_with(|c: char| c.is_ascii_digit());
+
+            let (number, max, offset) = if is_row {
+                (
+                    value.parse::<i64>().ok()? - 1,
+                    i64::from(ROW_MAX),
+                    row_offset,
+                )
+            } else {
+                let col = utility::column_name_to_number(&value.to_uppercase());
+                (i64::from(col), i64::from(COL_MAX), col_offset)
+            };
+
+            if number < 0 || number >= max {
+                return None;
+            }
+
+            let number = if is_absolute { number } else { number + offset };
+            parts.push((is_absolute, is_row, number, max));
+        }
+
+        let mut reference = String::new();
+        for (index, (is_absolute, is_row, number, max)) in parts.into_iter().enumerate() {
+            if number < 0 || number >= max {
+                return Some("#REF!".to_string());
+            }
+
+            if index > 0 {
+                reference.push_str(separator);
+            }
+
+            if is_absolute {
+                reference.push('$');
+            }
+
+            if is_row {
+                reference.push_str(&(number + 1).to_string());
+            } else {
+                utility::push_column_name(&mut reference, number as ColNum);
+            }
+        }
+
+        Some(reference)
+    }
+
+    // Find the start and end of the strings, quoted sheet names and
+    // structured references in a formula.
+    fn literal_spans(formula: &str) -> Vec<(usize, usize)> {
+        let mut spans = vec![];
+        let mut chars = formula.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            match c {
+                '"' | '\'' => {
+                    while let Some((_, next)) = chars.next() {
+                        if next == c {
+                            if chars.peek().map(|(_, n)| *n) == Some(c) {
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                }
+                '[' => {
+                    // Special characters in structured references are escaped
+                    // with an apostrophe.
+                    let mut depth = 1;
+                    while let Some((_, next)) = chars.next() {
+                        match next {
+                            '\'' => {
+                                chars.next();
+                            }
+                            '[' => depth += 1,
+                            ']' => depth -= 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                _ => continue,
+            }
+
+            let end = chars.peek().map_or(formula.len(), |(index, _)| *index);
+            spans.push((start, end));
+        }
+
+        spans
+    }
+}
+
+// The Excel 2010 and later "future" functions that require a "_xlfn." prefix.
+// The dynamic array functions are handled separately.
+const FUTURE_FUNCTIONS: &[&str] = &[
+    "ACOTH",
+    "ACOT",
+    "AGGREGATE",
+    "ARABIC",
+    "ARRAYTOTEXT",
+    "BASE",
+    "BETA.DIST",
+    "BETA.INV",
+    "BINOM.DIST.RANGE",
+    "BINOM.DIST",
+    "BINOM.INV",
+    "BITAND",
+    "BITLSHIFT",
+    "BITOR",
+    "BITRSHIFT",
+    "BITXOR",
+    "CEILING.MATH",
+    "CEILING.PRECISE",
+    "CHISQ.DIST.RT",
+    "CHISQ.DIST",
+    "CHISQ.INV.RT",
+    "CHISQ.INV",
+    "CHISQ.TEST",
+    "COMBINA",
+    "CONCAT",
+    "CONFIDENCE.NORM",
+    "CONFIDENCE.T",
+    "COTH",
+    "COT",
+    "COVARIANCE.P",
+    "COVARIANCE.S",
+    "CSCH",
+    "CSC",
+    "DAYS",
+    "DECIMAL",
+    "ERF.PRECISE",
+    "ERFC.PRECISE",
+    "EXPON.DIST",
+    "F.DIST.RT",
+    "F.DIST",
+    "F.INV.RT",
+    "F.INV",
+    "F.TEST",
+    "FILTERXML",
+    "FLOOR.MATH",
+    "FLOOR.PRECISE",
+    "FORECAST.ETS.CONFINT",
+    "FORECAST.ETS.SEASONALITY",
+    "FORECAST.ETS.STAT",
+    "FORECAST.ETS",
+    "FORECAST.LINEAR",
+    "FORMULATEXT",
+    "GAMMA.DIST",
+    "GAMMA.INV",
+    "GAMMALN.PRECISE",
+    "GAMMA",
+    "GAUSS",
+    "HYPGEOM.DIST",
+    "IFNA",
+    "IFS",
+    "IMAGE",
+    "IMCOSH",
+    "IMCOT",
+    "IMCSCH",
+    "IMCSC",
+    "IMSECH",
+    "IMSEC",
+    "IMSINH",
+    "IMTAN",
+    "ISFORMULA",
+    "ISOMITTED",
+    "ISOWEEKNUM",
+    "LET",
+    "LOGNORM.DIST",
+    "LOGNORM.INV",
+    "MAXIFS",
+    "MINIFS",
+    "MODE.MULT",
+    "MODE.SNGL",
+    "MUNIT",
+    "NEGBINOM.DIST",
+    "NORM.DIST",
+    "NORM.INV",
+    "NORM.S.DIST",
+    "NORM.S.INV",
+    "NUMBERVALUE",
+    "PDURATION",
+    "PERCENTOF",
+    "PERCENTILE.EXC",
+    "PERCENTILE.INC",
+    "PERCENTRANK.EXC",
+    "PERCENTRANK.INC",
+    "PERMUTATIONA",
+    "PHI",
+    "POISSON.DIST",
+    "QUARTILE.EXC",
+    "QUARTILE.INC",
+    "QUERYSTRING",
+    "RANK.AVG",
+    "RANK.EQ",
+    "REGEXEXTRACT",
+    "REGEXREPLACE",
+    "REGEXTEST",
+    "RRI",
+    "SECH",
+    "SEC",
+    "SHEETS",
+    "SHEET",
+    "SKEW.P",
+    "STDEV.P",
+    "STDEV.S",
+    "T.DIST.2T",
+    "T.DIST.RT",
+    "T.DIST",
+    "T.INV.2T",
+    "T.INV",
+    "T.TEST",
+    "TEXTAFTER",
+    "TEXTBEFORE",
+    "TEXTJOIN",
+    "TRIMRANGE",
+    "UNICHAR",
+    "UNICODE",
+    "VALUETOTEXT",
+    "VAR.P",
+    "VAR.S",
+    "WEBSERVICE",
+    "WEIBULL.DIST",
+    "XMATCH",
+    "XOR",
+    "Z.TEST",
+];
+
+// Tokens used to find the LAMBDA() and LET() parameter names in a formula.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FormulaToken {
+    Name(usize, usize),
+    Open,
+    Close,
+    Comma,
+    Other,
 }
 
 impl From<&str> for Formula {
@@ -1036,8 +2256,149 @@ impl From<&str> for Formula {
     }
 }
 
+impl From<String> for Formula {
+    fn from(value: String) -> Formula {
+        Formula::new(value)
+    }
+}
+
 impl From<&Formula> for Formula {
     fn from(value: &Formula) -> Formula {
         (*value).clone()
     }
 }
+
+// -----------------------------------------------------------------------
+// FormulaResult
+// -----------------------------------------------------------------------
+
+/// The `FormulaResult` enum defines the type of a formula result.
+///
+/// The `rust_xlsxwriter` library doesn't calculate formula results. Instead it
+/// stores a default result that Excel recalculates when the file is opened.
+/// Applications that don't recalculate formulas, or exporters that already
+/// know the results, can store the result with its type using `FormulaResult`
+/// and [`Worksheet::set_formula_results()`](crate::Worksheet::set_formula_results).
+///
+/// A `FormulaResult` can be created from a number, `bool` or string type via
+/// the `From` trait. Strings are stored as string results. Formulas can be
+/// written with a typed result using
+/// [`Worksheet::write_formula_with_result()`](crate::Worksheet::write_formula_with_result).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormulaResult {
+    /// A number result.
+    Number(f64),
+
+    /// A string result.
+    String(String),
+
+    /// A boolean result.
+    Boolean(bool),
+
+    /// An Excel error result such as `#N/A` or `#DIV/0!`.
+    Error(String),
+}
+
+impl FormulaResult {
+    // Convert a result string from the original string based APIs. Results
+    // that look like numbers are stored as numbers, as in Excel.
+    pub(crate) fn parse(result: &str) -> FormulaResult {
+        match result.parse::<f64>() {
+            Ok(number) => FormulaResult::Number(number),
+            Err(_) => FormulaResult::String(result.to_string()),
+        }
+    }
+
+    // Check that an error result is one of the Excel error values.
+    pub(crate) fn is_valid_error(error: &str) -> bool {
+        matches!(
+            error,
+            "#NULL!"
+                | "#DIV/0!"
+                | "#VALUE!"
+                | "#REF!"
+                | "#NAME?"
+                | "#NUM!"
+                | "#N/A"
+                | "#GETTING_DATA"
+                | "#SPILL!"
+                | "#CALC!"
+                | "#FIELD!"
+                | "#BLOCKED!"
+                | "#CONNECT!"
+                | "#BUSY!"
+                | "#UNKNOWN!"
+        )
+    }
+
+    // Check that an error result is a valid Excel error value.
+    pub(crate) fn validate(&self) -> Result<(), XlsxError> {
+        match self {
+            FormulaResult::Error(error) if !Self::is_valid_error(error) => Err(
+                XlsxError::ParameterError(format!("Unknown Excel error value '{error}'")),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    // Get the cell type attribute for the result.
+    pub(crate) fn xml_type(&self) -> &'static str {
+        match self {
+            FormulaResult::Number(_) => "",
+            FormulaResult::String(_) => r#" t="str""#,
+            FormulaResult::Boolean(_) => r#" t="b""#,
+            FormulaResult::Error(_) => r#" t="e""#,
+        }
+    }
+
+    // Get the result as it is displayed in Excel, for autofit.
+    pub(crate) fn display_value(&self) -> String {
+        match self {
+            FormulaResult::Number(number) => number.to_string(),
+            FormulaResult::String(string) | FormulaResult::Error(string) => string.clone(),
+            FormulaResult::Boolean(boolean) => boolean.to_string().to_uppercase(),
+        }
+    }
+}
+
+impl From<f64> for FormulaResult {
+    fn from(value: f64) -> FormulaResult {
+        FormulaResult::Number(value)
+    }
+}
+
+macro_rules! formula_result_from_number {
+    ($($t:ty)*) => ($(
+        impl From<$t> for FormulaResult {
+            fn from(value: $t) -> FormulaResult {
+                FormulaResult::Number(f64::from(value))
+            }
+        }
+    )*)
+}
+formula_result_from_number!(u8 i8 u16 i16 u32 i32 f32);
+
+impl From<bool> for FormulaResult {
+    fn from(value: bool) -> FormulaResult {
+        FormulaResult::Boolean(value)
+    }
+}
+
+impl From<&str> for FormulaResult {
+    fn from(value: &str) -> FormulaResult {
+        FormulaResult::String(value.to_string())
+    }
+}
+
+impl From<String> for FormulaResult {
+    fn from(value: String) -> FormulaResult {
+        FormulaResult::String(value)
+    }
+}
+
+impl From<&FormulaResult> for FormulaResult {
+    fn from(value: &FormulaResult) -> FormulaResult {
+        value.clone()
+    }
+}
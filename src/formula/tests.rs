@@ -7,7 +7,7 @@
 #[cfg(test)]
 mod formula_tests {
 
-    use crate::Formula;
+    use crate::{Formula, FormulaResult, XlsxError};
 
     #[test]
     fn test_dynamic_function_escapes() {
@@ -244,4 +244,320 @@ mod formula_tests {
             assert_eq!(prepared_formula.as_ref(), expected);
         }
     }
+
+    #[test]
+    fn test_lambda_parameter_escapes() {
+        let formula_strings = vec![
+            (
+                "=LAMBDA(temp, (5/9) * (temp-32))(212)",
+                "_xlfn.LAMBDA(_xlpm.temp, (5/9) * (_xlpm.temp-32))(212)",
+            ),
+            (
+                "=LAMBDA(x, y, x + y)(1, 2)",
+                "_xlfn.LAMBDA(_xlpm.x, _xlpm.y, _xlpm.x + _xlpm.y)(1, 2)",
+            ),
+            (
+                "=LET(x, 5, y, x * 2, x + y)",
+                "_xlfn.LET(_xlpm.x, 5, _xlpm.y, _xlpm.x * 2, _xlpm.x + _xlpm.y)",
+            ),
+            (
+                "=LET(add, LAMBDA(a, b, a + b), add(1, 2))",
+                "_xlfn.LET(_xlpm.add, _xlfn.LAMBDA(_xlpm.a, _xlpm.b, _xlpm.a + _xlpm.b), _xlpm.add(1, 2))",
+            ),
+            (
+                "=MAP(A1:A3, LAMBDA(value, value * 2))",
+                "_xlfn.MAP(A1:A3, _xlfn.LAMBDA(_xlpm.value, _xlpm.value * 2))",
+            ),
+            (
+                "=LET(total, SUM(A1:A10), IF(total > 0, \"total\", Sheet1!A1))",
+                "_xlfn.LET(_xlpm.total, SUM(A1:A10), IF(_xlpm.total > 0, \"total\", Sheet1!A1))",
+            ),
+            // Parameters that are already escaped aren't changed.
+            (
+                "=LAMBDA(_xlpm.x, _xlpm.x + 1)(1)",
+                "_xlfn.LAMBDA(_xlpm.x, _xlpm.x + 1)(1)",
+            ),
+            // A worksheet name that matches a parameter isn't changed.
+            (
+                "=LET(Data, 1, Data!A1 + Data)",
+                "_xlfn.LET(_xlpm.Data, 1, Data!A1 + _xlpm.Data)",
+            ),
+            // Names outside the declaring function, such as defined names,
+            // aren't changed.
+            (
+                "=SUM(rate) + LET(rate, 2, rate * 3)",
+                "SUM(rate) + _xlfn.LET(_xlpm.rate, 2, _xlpm.rate * 3)",
+            ),
+            (
+                "=LAMBDA(x, x + 1)(x)",
+                "_xlfn.LAMBDA(_xlpm.x, _xlpm.x + 1)(x)",
+            ),
+            (
+                "=MAP(A1:A3, LAMBDA(a, a * 2)) + MAP(A1:A3, LAMBDA(b, a + b))",
+                "_xlfn.MAP(A1:A3, _xlfn.LAMBDA(_xlpm.a, _xlpm.a * 2)) + _xlfn.MAP(A1:A3, _xlfn.LAMBDA(_xlpm.b, a + _xlpm.b))",
+            ),
+        ];
+
+        for &(formula_string, expected) in &formula_strings {
+            let formula = Formula::new(formula_string).use_future_functions();
+            assert_eq!(expected, &*formula.expand_formula(false));
+
+            let formula = Formula::new(formula_string);
+            assert_eq!(expected, &*formula.expand_formula(true));
+        }
+
+        // The parameters aren't escaped without future function support.
+        let formula = Formula::new("=LET(x, 5, x + 1)");
+        assert_eq!("LET(x, 5, x + 1)", &*formula.expand_formula(false));
+    }
+
+    #[test]
+    fn test_literal_escapes() {
+        let formula_strings = vec![
+            // Newer future and dynamic functions.
+            ("=REGEXTEST(A1, \"^a\")", "_xlfn.REGEXTEST(A1, \"^a\")"),
+            ("=PERCENTOF(A1, A1:A3)", "_xlfn.PERCENTOF(A1, A1:A3)"),
+            ("=TRIMRANGE(A:A)", "_xlfn.TRIMRANGE(A:A)"),
+            (
+                "=GROUPBY(A1:A9, B1:B9, SUM)",
+                "_xlfn.GROUPBY(A1:A9, B1:B9, SUM)",
+            ),
+            // Function names in strings aren't changed.
+            (
+                "=COUNTIF(A1:A10, \"SORT(x)\")",
+                "COUNTIF(A1:A10, \"SORT(x)\")",
+            ),
+            (
+                "=\"Try DAYS(1)\" & DAYS(B1, A1)",
+                "\"Try DAYS(1)\" & _xlfn.DAYS(B1, A1)",
+            ),
+            // Function names in sheet names aren't changed.
+            ("='SORT(1)'!A1 + 1", "'SORT(1)'!A1 + 1"),
+            // Function names in structured references aren't changed.
+            ("=SUM(Sales[DAYS(open)])", "SUM(Sales[DAYS(open)])"),
+            (
+                "=SUM(Sales[[#This Row],[XOR(a)]])*DAYS(B1, A1)",
+                "SUM(Sales[[#This Row],[XOR(a)]])*_xlfn.DAYS(B1, A1)",
+            ),
+        ];
+
+        for &(formula_string, expected) in &formula_strings {
+            let formula = Formula::new(formula_string).use_future_functions();
+            assert_eq!(expected, &*formula.expand_formula(false));
+        }
+
+        // Strings with function names aren't treated as dynamic formulas.
+        assert!(!Formula::new("=LEN(\"SORT(x)\")").is_dynamic_function());
+        assert!(!Formula::new("=SUM(Table1[UNIQUE(x)])").is_dynamic_function());
+        assert!(Formula::new("=LEN(SORT(A1:A3))").is_dynamic_function());
+
+        // Only "@" in structured references is changed by the table functions.
+        let formula = Formula::new("=Table1[@Sales]&\"@\"").use_table_functions();
+        assert_eq!(
+            "Table1[[#This Row],[Sales]]&\"@\"",
+            &*formula.expand_formula(false)
+        );
+
+        let formula_strings = [
+            (
+                "=[@Price]*[@Qty]",
+                "[[#This Row],[Price]]*[[#This Row],[Qty]]",
+            ),
+            (
+                "=SUM(Table1[@[Quarter 1]:[Quarter 4]])",
+                "SUM(Table1[[#This Row],[Quarter 1]:[Quarter 4]])",
+            ),
+            ("=[@[Unit Price]]*2", "[[#This Row],[Unit Price]]*2"),
+            ("=COUNTA(Table1[@])", "COUNTA(Table1[#This Row])"),
+            (
+                "=Table1[[#This Row],[Sales]]",
+                "Table1[[#This Row],[Sales]]",
+            ),
+            ("=[Email@Work]", "[Email@Work]"),
+        ];
+
+        for (formula_string, expected) in formula_strings {
+            let formula = Formula::new(formula_string).use_table_functions();
+            assert_eq!(expected, &*formula.expand_formula(false));
+        }
+
+        assert!(Formula::future_functions().contains(&"BETA.DIST"));
+        assert!(!Formula::future_functions().contains(&"SORT"));
+    }
+
+    #[test]
+    fn test_implicit_intersection_escapes() {
+        let formula_strings = vec![
+            ("=@A1:A10", "_xlfn.SINGLE(A1:A10)"),
+            ("=@A1:A10*2", "_xlfn.SINGLE(A1:A10)*2"),
+            ("=@$A$1:$A$10", "_xlfn.SINGLE($A$1:$A$10)"),
+            ("=@Sheet2!A1:A10", "_xlfn.SINGLE(Sheet2!A1:A10)"),
+            ("=@'Sheet 2'!A1:A10", "_xlfn.SINGLE('Sheet 2'!A1:A10)"),
+            (
+                "=@INDEX(A1:B10, 1, 0)+1",
+                "_xlfn.SINGLE(INDEX(A1:B10, 1, 0))+1",
+            ),
+            ("=@(A1:A3+B1:B3)", "_xlfn.SINGLE(A1:A3+B1:B3)"),
+            ("=@Table1[Sales]", "_xlfn.SINGLE(Table1[Sales])"),
+            (
+                "=SUM(@A1:A3, @B1:B3)",
+                "SUM(_xlfn.SINGLE(A1:A3), _xlfn.SINGLE(B1:B3))",
+            ),
+            (
+                "=@INDEX(@A1:A3, 1)",
+                "_xlfn.SINGLE(INDEX(_xlfn.SINGLE(A1:A3), 1))",
+            ),
+            // A "@" in a string or structured reference isn't changed.
+            ("=\"a@b.com\"&A1", "\"a@b.com\"&A1"),
+            ("=Table1[@Sales]", "Table1[@Sales]"),
+            // A "@" without an operand isn't changed.
+            ("=@", "@"),
+        ];
+
+        for &(formula_string, expected) in &formula_strings {
+            let formula = Formula::new(formula_string).use_implicit_intersection();
+            assert_eq!(expected, &*formula.expand_formula(false));
+        }
+
+        // The operator isn't changed by default.
+        let formula = Formula::new("=@A1:A10");
+        assert_eq!("@A1:A10", &*formula.expand_formula(false));
+    }
+
+    #[test]
+    fn test_formula_references() {
+        assert_eq!("$A$1", Formula::cell_reference("", 0, 0));
+        assert_eq!("Sheet2!$C$5", Formula::cell_reference("Sheet2", 4, 2));
+        assert_eq!(
+            "'Sales Data'!$A$1:$B$10",
+            Formula::range_reference("Sales Data", 0, 0, 9, 1)
+        );
+        assert_eq!("'Tom''s'!$A$1", Formula::cell_reference("Tom's", 0, 0));
+        assert_eq!("'Sheet 1'", Formula::quote_sheet_name("'Sheet 1'"));
+    }
+
+    #[test]
+    fn test_shift_references() {
+        let formula_strings = vec![
+            ("B1*2", 1, 0, "B2*2"),
+            ("B1*2", 0, 2, "D1*2"),
+            ("$B$1+B$1+$B1", 2, 2, "$B$1+D$1+$B3"),
+            ("SUM(A1:B2)", 1, 1, "SUM(B2:C3)"),
+            ("SUM(A:A)+SUM(1:1)", 1, 1, "SUM(B:B)+SUM(2:2)"),
+            ("Sheet2!A1+'Sheet 1'!A1", 1, 0, "Sheet2!A2+'Sheet 1'!A2"),
+            ("LOG10(A1)&\"A1\"", 1, 0, "LOG10(A2)&\"A1\""),
+            ("Sales2024+Table1[A1]", 1, 0, "Sales2024+Table1[A1]"),
+            ("A1", -1, 0, "#REF!"),
+            ("XFD1", 0, 1, "#REF!"),
+        ];
+
+        for (formula, row_offset, col_offset, expected) in formula_strings {
+            assert_eq!(
+                expected,
+                Formula::shift_references(formula, row_offset, col_offset)
+            );
+        }
+    }
+
+    #[test]
+    fn test_array_constants() {
+        let formula_strings = vec![
+            ("={1,2;3,4}", "{1,2;3,4}"),
+            ("=A1+{1,2}", "A1+{1,2}"),
+            ("=SUM(A1:B2*{1,2;3,4})", "SUM(A1:B2*{1,2;3,4})"),
+            ("{=SUM({1,2})}", "SUM({1,2})"),
+            ("{=SUM(A1:B1*A2:B2)}", "SUM(A1:B1*A2:B2)"),
+            ("=INDEX({\"SORT(\",\"b\"},2)", "INDEX({\"SORT(\",\"b\"},2)"),
+        ];
+
+        for &(formula_string, expected) in &formula_strings {
+            let formula = Formula::new(formula_string);
+            assert_eq!(expected, &*formula.expand_formula(false));
+        }
+    }
+
+    #[test]
+    fn test_formula_result_types() {
+        assert_eq!(None, Formula::new("=A1").result);
+        assert_eq!(None, Formula::new("=A1").set_result("").result);
+        assert_eq!(
+            Some(FormulaResult::Number(2.0)),
+            Formula::new("=A1").set_result("2").result
+        );
+        assert_eq!(
+            Some(FormulaResult::String("abc".to_string())),
+            Formula::new("=A1").set_result("abc").result
+        );
+        assert_eq!(
+            Some(FormulaResult::String("001".to_string())),
+            Formula::new("=A1").set_result_string("001").result
+        );
+        assert_eq!(
+            Some(FormulaResult::Boolean(true)),
+            Formula::new("=A1").set_result_boolean(true).result
+        );
+    }
+
+    #[test]
+    fn test_formula_string_literal() {
+        assert_eq!(r#""""#, Formula::string_literal(""));
+        assert_eq!(r#""Hello""#, Formula::string_literal("Hello"));
+        assert_eq!(r#""Say ""Hi""""#, Formula::string_literal(r#"Say "Hi""#));
+    }
+
+    #[test]
+    fn test_formula_validate() {
+        let valid_formulas = vec![
+            "=SUM(A1:A10)",
+            "{=SUM(A1:A10*B1:B10)}",
+            "=IF(A1>0, \"Yes (ok\", \"No\")",
+            "='Sales (2024)'!A1 + Sheet2!$B$1048576",
+            "=SUM(Table1[@[Column1]:[Column3]])",
+            "=sum(a1:a10)",
+            "=1.5E+10*2",
+            "=_xlfn.SEQUENCE(10)",
+            "=_xlfn._xlws.SORT(A1:A10)",
+            "=_xludf.MYFUNC(A1)",
+            "=STDEV.S(A1:A10)",
+            "=LET(x, 1, y, LAMBDA(z, z+1), y(x))",
+            "=_xlfn.LET(_xlpm.f, _xlfn.LAMBDA(_xlpm.a, _xlpm.a), _xlpm.f(1))",
+            "=XFD1+IV5",
+            "=\"Say \"\"#REF!\"\"\"",
+        ];
+
+        for formula in valid_formulas {
+            assert!(
+                Formula::new(formula).validate().is_ok(),
+                "Unexpected error for: {formula}"
+            );
+        }
+
+        let invalid_formulas = vec![
+            "=SUM(A1:A10",
+            "=SUM(A1:A10))",
+            "=)(",
+            "=SUMM(A1:A10)",
+            "=A0+1",
+            "=A1048577",
+            "=SUM(#REF!)",
+            "=\"Unterminated",
+            "='Sheet 1!A1",
+            "=SUM(Table1[Column1)",
+        ];
+
+        for formula in invalid_formulas {
+            assert!(
+                matches!(
+                    Formula::new(formula).validate(),
+                    Err(XlsxError::FormulaError(_))
+                ),
+                "Expected error for: {formula}"
+            );
+        }
+
+        // User defined functions.
+        let user_functions = vec!["MyLambda".to_string()];
+        assert!(Formula::check_syntax("MYLAMBDA(1)", &user_functions).is_ok());
+        assert!(Formula::check_syntax("MYLAMBDA(1)", &[]).is_err());
+    }
 }
@@ -0,0 +1,132 @@
+// index_sheet - A module for representing workbook index sheet options.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+use crate::{ColNum, RowNum};
+
+/// The `IndexSheetOptions` struct represents the options for a workbook table
+/// of contents worksheet.
+///
+/// `IndexSheetOptions` is used with
+/// [`Workbook::add_index_sheet()`](crate::Workbook::add_index_sheet) to
+/// control the name and title of the index worksheet and whether the other
+/// worksheets get a link back to it.
+///
+/// # Examples
+///
+/// The following example demonstrates adding an index worksheet with links to
+/// the other worksheets in a workbook.
+///
+/// ```
+/// # // This code is available in examples/doc_workbook_add_index_sheet.rs
+/// #
+/// use rust_xlsxwriter::{IndexSheetOptions, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///
+///     workbook.add_worksheet().set_name("Sales")?;
+///     workbook.add_worksheet().set_name("Costs")?;
+///     workbook.add_worksheet().set_name("Summary")?;
+///
+///     // Add an index sheet with a link back to it in cell H1 of each sheet.
+///     let options = IndexSheetOptions::new()
+///         .set_title("Contents")
+///         .set_back_link_cell(0, 7);
+///
+///     workbook.add_index_sheet(&options)?;
+///
+///     workbook.save("index_sheet.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexSheetOptions {
+    pub(crate) name: String,
+    pub(crate) title: Option<String>,
+    pub(crate) back_link_cell: Option<(RowNum, ColNum)>,
+    pub(crate) back_link_text: String,
+}
+
+impl Default for IndexSheetOptions {
+    fn default() -> Self {
+        IndexSheetOptions {
+            name: "Index".to_string(),
+            title: None,
+            back_link_cell: None,
+            back_link_text: "Back to index".to_string(),
+        }
+    }
+}
+
+impl IndexSheetOptions {
+    /// Create a new `IndexSheetOptions` object to represent the index sheet
+    /// options.
+    ///
+    pub fn new() -> IndexSheetOptions {
+        IndexSheetOptions::default()
+    }
+
+    /// Set the name of the index worksheet.
+    ///
+    /// The default name is `Index`. See
+    /// [`Worksheet::set_name()`](crate::Worksheet::set_name) for the naming
+    /// rules.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The worksheet name.
+    ///
+    pub fn set_name(mut self, name: impl Into<String>) -> IndexSheetOptions {
+        self.name = name.into();
+        self
+    }
+
+    /// Set a title to write above the list of links.
+    ///
+    /// By default there is no title and the links start in cell `A1`.
+    ///
+    /// # Parameters
+    ///
+    /// * `title` - The title string.
+    ///
+    pub fn set_title(mut self, title: impl Into<String>) -> IndexSheetOptions {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the cell for a link back to the index in each of the other
+    /// worksheets.
+    ///
+    /// Back links are off by default since they overwrite any data already in
+    /// the cell.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    ///
+    pub fn set_back_link_cell(mut self, row: RowNum, col: ColNum) -> IndexSheetOptions {
+        self.back_link_cell = Some((row, col));
+        self
+    }
+
+    /// Set the display text for the back links.
+    ///
+    /// The default text is `Back to index`.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The display text for the link.
+    ///
+    pub fn set_back_link_text(mut self, text: impl Into<String>) -> IndexSheetOptions {
+        self.back_link_text = text.into();
+        self
+    }
+}
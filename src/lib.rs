@@ -174,24 +174,54 @@
 //!   easier to write.
 //! - `wasm`: Adds a dependency on `js-sys` and `wasm-bindgen` to allow
 //!   compilation for wasm/JavaScript targets.
+//! - `encryption`: Adds a dependency on `aes` to allow saving password
+//!   encrypted xlsx files via `Workbook::save_with_password()`.
+//!   This is off by default.
+//! - `serde_json`: Adds a dependency on `serde_json` to allow writing arrays
+//!   of JSON objects via `Worksheet::write_json_rows()`. This is off by
+//!   default.
+//! - `ndarray`: Adds a dependency on `ndarray` to allow writing 2D arrays via
+//!   `Worksheet::write_array2()`. This is off by default.
+//!
+//! ## Warnings
+//!
+//! Some invalid but non-fatal parameters, such as an out of range worksheet
+//! zoom or a header longer than Excel's 255 character limit, are ignored
+//! rather than raising an error. In these cases `rust_xlsxwriter` emits a
+//! warning via the [`log`](https://docs.rs/log) facade at the `warn` level.
+//! Applications can capture, filter or redirect these messages by installing
+//! a `log` compatible logger such as `env_logger`. If no logger is installed
+//! the warnings are discarded.
 //!
 //!
 mod app;
+mod comment;
+#[cfg(feature = "encryption")]
+mod compound_file;
 mod content_types;
 mod core;
 mod custom;
+mod custom_xml;
 mod data_validation;
 mod datetime;
 mod drawing;
+#[cfg(feature = "encryption")]
+mod encryption;
 mod error;
 mod filter;
 mod format;
 mod formula;
 mod image;
+mod index_sheet;
+mod memory_usage;
 mod metadata;
+mod named_sheet_view;
+mod note;
 mod packager;
+mod page_setup;
 mod properties;
 mod protection;
+mod reader;
 mod relationship;
 mod rich_value;
 mod rich_value_rel;
@@ -204,6 +234,7 @@ mod table;
 mod theme;
 mod url;
 mod vml;
+mod warning;
 mod xmlwriter;
 
 #[cfg(feature = "serde")]
@@ -230,8 +261,16 @@ pub use filter::*;
 pub use format::*;
 pub use formula::*;
 pub use image::*;
+pub use index_sheet::IndexSheetOptions;
+pub use memory_usage::MemoryUsage;
+pub use named_sheet_view::NamedSheetView;
+pub use note::*;
+pub use page_setup::PageSetup;
 pub use properties::*;
 pub use protection::*;
+pub use reader::{
+    read_workbook, read_workbook_from_buffer, CellValue, SheetValues, WorkbookValues,
+};
 pub use table::*;
 pub use url::*;
 
@@ -0,0 +1,106 @@
+// memory_usage - A module for reporting the approximate memory used by
+// workbook and worksheet data.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+use std::ops::{Add, AddAssign};
+
+/// The `MemoryUsage` struct represents the approximate memory held by the data
+/// in a workbook or worksheet.
+///
+/// `MemoryUsage` is returned by
+/// [`Worksheet::memory_usage()`](crate::Worksheet::memory_usage) and
+/// [`Workbook::memory_usage()`](crate::Workbook::memory_usage). It reports the
+/// number of bytes used by the cell data, the string data and any images or
+/// other media. This can be used, for example, by services that need to
+/// enforce a memory budget for each request.
+///
+/// The values are approximations based on the size of the stored data and the
+/// containers that hold it. They don't include the smaller fixed overhead of
+/// the workbook and worksheet structures, or the temporary memory used while
+/// the file is saved.
+///
+/// # Examples
+///
+/// The following example demonstrates checking the approximate memory used by
+/// a worksheet.
+///
+/// ```
+/// # // This code is available in examples/doc_worksheet_memory_usage.rs
+/// #
+/// use rust_xlsxwriter::{Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///     let worksheet = workbook.add_worksheet();
+///
+///     for row in 0..1000 {
+///         worksheet.write(row, 0, row)?;
+///         worksheet.write(row, 1, format!("Item {row}"))?;
+///     }
+///
+///     let usage = worksheet.memory_usage();
+///     println!("Cells:   {} bytes", usage.cells());
+///     println!("Strings: {} bytes", usage.strings());
+///     println!("Total:   {} bytes", usage.total());
+///
+///     workbook.save("worksheet.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub(crate) cells: usize,
+    pub(crate) strings: usize,
+    pub(crate) media: usize,
+}
+
+impl MemoryUsage {
+    /// Get the approximate number of bytes used by the cell data tables.
+    ///
+    /// This includes numbers, formulas and the cell structures for strings,
+    /// but not the string data itself.
+    pub fn cells(&self) -> usize {
+        self.cells
+    }
+
+    /// Get the approximate number of bytes used by string data.
+    pub fn strings(&self) -> usize {
+        self.strings
+    }
+
+    /// Get the number of bytes used by images and other binary media such as
+    /// VBA projects.
+    pub fn media(&self) -> usize {
+        self.media
+    }
+
+    /// Get the total approximate number of bytes used.
+    pub fn total(&self) -> usize {
+        self.cells + self.strings + self.media
+    }
+}
+
+impl Add for MemoryUsage {
+    type Output = MemoryUsage;
+
+    fn add(self, other: MemoryUsage) -> MemoryUsage {
+        MemoryUsage {
+            cells: self.cells + other.cells,
+            strings: self.strings + other.strings,
+            media: self.media + other.media,
+        }
+    }
+}
+
+impl AddAssign for MemoryUsage {
+    fn add_assign(&mut self, other: MemoryUsage) {
+        *self = *self + other;
+    }
+}
@@ -0,0 +1,467 @@
+// named_sheet_view - A module for creating the Excel namedSheetView.xml file.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+mod tests;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use sha2::{Digest, Sha512};
+
+use crate::filter::{FilterData, FilterDateGroupItem};
+use crate::xmlwriter::XMLWriter;
+use crate::{
+    utility, ColNum, ConditionalFormatIconType, FilterCondition, FilterTop10, Format, RowNum,
+};
+
+/// The `NamedSheetView` struct represents a named sheet view in a worksheet.
+///
+/// Named sheet views, or "Sheet Views" in the Excel user interface, are a
+/// feature of Excel 365 that allows users who are collaborating on a shared
+/// workbook to create personal filter and sort views of a worksheet without
+/// affecting the view of the other users.
+///
+/// The `NamedSheetView` struct can be used to create views in advance so that
+/// they are available to the users of the file. The filter and sort
+/// conditions in a view apply to the worksheet autofilter range which must be
+/// set with [`Worksheet::autofilter()`](crate::Worksheet::autofilter) before
+/// the view is added with
+/// [`Worksheet::add_named_sheet_view()`](crate::Worksheet::add_named_sheet_view).
+///
+/// Note, named sheet views are only supported by Excel 365. Older versions
+/// of Excel ignore them.
+///
+/// # Examples
+///
+/// The following example demonstrates adding named sheet views with
+/// different filters and sorting to a worksheet.
+///
+/// ```
+/// # // This code is available in examples/doc_named_sheet_view.rs
+/// #
+/// use rust_xlsxwriter::{FilterCondition, NamedSheetView, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///     let worksheet = workbook.add_worksheet();
+///
+///     // Write some data to filter.
+///     let data = [
+///         ("Region", "Sales"),
+///         ("East", "3000"),
+///         ("West", "5000"),
+///         ("East", "4000"),
+///         ("South", "2000"),
+///     ];
+///     for (row, (region, sales)) in data.iter().enumerate() {
+///         worksheet.write_string(row as u32, 0, *region)?;
+///         worksheet.write_string(row as u32, 1, *sales)?;
+///     }
+///
+///     // The views apply to the worksheet autofilter range.
+///     worksheet.autofilter(0, 0, 4, 1)?;
+///
+///     // Create a view that shows the "East" region only.
+///     let filter = FilterCondition::new().add_list_filter("East");
+///     let view = NamedSheetView::new("East region").set_filter(0, &filter);
+///     worksheet.add_named_sheet_view(&view)?;
+///
+///     // Create a view that sorts the sales in descending order.
+///     let view = NamedSheetView::new("Top sales").set_sort(1, true);
+///     worksheet.add_named_sheet_view(&view)?;
+///
+///     workbook.save("named_sheet_views.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone)]
+pub struct NamedSheetView {
+    pub(crate) name: String,
+    pub(crate) filter_conditions: BTreeMap<ColNum, FilterCondition>,
+    pub(crate) sort_conditions: BTreeMap<ColNum, bool>,
+}
+
+impl NamedSheetView {
+    /// Create a new `NamedSheetView` object to represent a named sheet view.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the view as it appears in Excel. The name must
+    ///   be unique within the worksheet.
+    ///
+    pub fn new(name: impl Into<String>) -> NamedSheetView {
+        NamedSheetView {
+            name: name.into(),
+            filter_conditions: BTreeMap::new(),
+            sort_conditions: BTreeMap::new(),
+        }
+    }
+
+    /// Set the filter condition for a column in the view.
+    ///
+    /// The filter is defined in the same way as a worksheet autofilter filter
+    /// using a [`FilterCondition`]. See
+    /// [`Worksheet::filter_column()`](crate::Worksheet::filter_column) for
+    /// more details. The filter only applies to the view and doesn't affect
+    /// the worksheet autofilter.
+    ///
+    /// # Parameters
+    ///
+    /// * `col` - The zero indexed column number. It must be in the worksheet
+    ///   autofilter column range.
+    /// * `filter_condition` - The column filter condition defined by the
+    ///   [`FilterCondition`] struct.
+    ///
+    pub fn set_filter(mut self, col: ColNum, filter_condition: &FilterCondition) -> NamedSheetView {
+        self.filter_conditions.insert(col, filter_condition.clone());
+        self
+    }
+
+    /// Set the sort order for a column in the view.
+    ///
+    /// # Parameters
+    ///
+    /// * `col` - The zero indexed column number. It must be in the worksheet
+    ///   autofilter column range.
+    /// * `descending` - Sort the column in descending order if `true`, or in
+    ///   ascending order if `false`.
+    ///
+    pub fn set_sort(mut self, col: ColNum, descending: bool) -> NamedSheetView {
+        self.sort_conditions.insert(col, descending);
+        self
+    }
+}
+
+// -----------------------------------------------------------------------
+// NamedSheetViews - the xml file for the views in a worksheet.
+// -----------------------------------------------------------------------
+
+pub(crate) struct NamedSheetViews {
+    pub(crate) writer: XMLWriter,
+    pub(crate) views: Vec<NamedSheetView>,
+    pub(crate) sheet_name: String,
+    pub(crate) filter_range: Option<(RowNum, ColNum, RowNum, ColNum)>,
+    pub(crate) global_dxf_indices: Vec<u32>,
+}
+
+impl NamedSheetViews {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new NamedSheetViews struct.
+    pub(crate) fn new() -> NamedSheetViews {
+        NamedSheetViews {
+            writer: XMLWriter::new(),
+            views: vec![],
+            sheet_name: String::new(),
+            filter_range: None,
+            global_dxf_indices: vec![],
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and write the XML file.
+    pub(crate) fn assemble_xml_file(&mut self) {
+        self.writer.xml_declaration();
+
+        // Write the namedSheetViews element.
+        self.write_named_sheet_views();
+
+        for view in &self.views.clone() {
+            // Write the namedSheetView element.
+            self.write_named_sheet_view(view);
+        }
+
+        // Close the namedSheetViews tag.
+        self.writer.xml_end_tag("namedSheetViews");
+    }
+
+    // Write the <namedSheetViews> element.
+    fn write_named_sheet_views(&mut self) {
+        let attributes = [
+            (
+                "xmlns",
+                "http://schemas.microsoft.com/office/spreadsheetml/2019/namedsheetviews",
+            ),
+            (
+                "xmlns:x",
+                "http://schemas.openxmlformats.org/spreadsheetml/2006/main",
+            ),
+        ];
+
+        self.writer.xml_start_tag("namedSheetViews", &attributes);
+    }
+
+    // Write the <namedSheetView> element.
+    fn write_named_sheet_view(&mut self, view: &NamedSheetView) {
+        let attributes = [
+            ("name", view.name.clone()),
+            ("id", self.guid(&view.name, "view")),
+        ];
+
+        match self.filter_range {
+            Some(filter_range) => {
+                self.writer.xml_start_tag("namedSheetView", &attributes);
+
+                // Write the nsvFilter element.
+                self.write_nsv_filter(view, filter_range);
+
+                self.writer.xml_end_tag("namedSheetView");
+            }
+            None => self.writer.xml_empty_tag("namedSheetView", &attributes),
+        }
+    }
+
+    // Write the <nsvFilter> element.
+    fn write_nsv_filter(
+        &mut self,
+        view: &NamedSheetView,
+        filter_range: (RowNum, ColNum, RowNum, ColNum),
+    ) {
+        let (first_row, first_col, last_row, last_col) = filter_range;
+
+        let attributes = [
+            ("filterId", self.guid(&view.name, "filter")),
+            (
+                "ref",
+                utility::cell_range(first_row, first_col, last_row, last_col),
+            ),
+        ];
+
+        // Ignore any columns that are outside the autofilter range, which can
+        // happen if the range was changed after the view was added.
+        let filters: Vec<(&ColNum, &FilterCondition)> = view
+            .filter_conditions
+            .iter()
+            .filter(|(col, _)| (first_col..=last_col).contains(*col))
+            .collect();
+
+        let sorts: Vec<(&ColNum, &bool)> = view
+            .sort_conditions
+            .iter()
+            .filter(|(col, _)| (first_col..=last_col).contains(*col))
+            .collect();
+
+        if filters.is_empty() && sorts.is_empty() {
+            self.writer.xml_empty_tag("nsvFilter", &attributes);
+            return;
+        }
+
+        self.writer.xml_start_tag("nsvFilter", &attributes);
+
+        for (col, filter_condition) in filters {
+            // Write the columnFilter element.
+            self.write_column_filter(col - first_col, filter_condition);
+        }
+
+        if !sorts.is_empty() {
+            self.writer.xml_start_tag_only("sortRules");
+
+            for (col, descending) in sorts {
+                // Write the sortRule element.
+                let range = utility::cell_range(first_row + 1, *col, last_row, *col);
+                self.write_sort_rule(col - first_col, &range, *descending);
+            }
+
+            self.writer.xml_end_tag("sortRules");
+        }
+
+        self.writer.xml_end_tag("nsvFilter");
+    }
+
+    // Write the <columnFilter> element.
+    fn write_column_filter(&mut self, col: ColNum, filter_condition: &FilterCondition) {
+        let attributes = [("colId", col.to_string())];
+
+        self.writer.xml_start_tag("columnFilter", &attributes);
+        self.writer.xml_start_tag("filter", &attributes);
+
+        if let Some((icon_type, icon_index)) = filter_condition.icon {
+            self.write_icon_filter(icon_type, icon_index);
+        } else if let Some(format) = &filter_condition.color_format {
+            self.write_color_filter(format, filter_condition.is_cell_color);
+        } else if let Some((top10, value)) = filter_condition.top10 {
+            self.write_top10(top10, value);
+        } else if filter_condition.is_list_filter {
+            self.write_list_filters(filter_condition);
+        } else {
+            self.write_custom_filters(filter_condition);
+        }
+
+        self.writer.xml_end_tag("filter");
+        self.writer.xml_end_tag("columnFilter");
+    }
+
+    // Write the <x:colorFilter> element.
+    fn write_color_filter(&mut self, format: &Format, is_cell_color: bool) {
+        let dxf_index = self.global_dxf_indices[format.dxf_index as usize];
+        let mut attributes = vec![("dxfId", dxf_index.to_string())];
+
+        if !is_cell_color {
+            attributes.push(("cellColor", "0".to_string()));
+        }
+
+        self.writer.xml_empty_tag("x:colorFilter", &attributes);
+    }
+
+    // Write the <x:iconFilter> element.
+    fn write_icon_filter(&mut self, icon_type: ConditionalFormatIconType, icon_index: u8) {
+        let attributes = [
+            ("iconSet", icon_type.to_string()),
+            ("iconId", icon_index.to_string()),
+        ];
+
+        self.writer.xml_empty_tag("x:iconFilter", &attributes);
+    }
+
+    // Write the <x:top10> element.
+    fn write_top10(&mut self, top10: FilterTop10, value: u16) {
+        let mut attributes = vec![];
+
+        if !top10.is_top() {
+            attributes.push(("top", "0".to_string()));
+        }
+
+        if top10.is_percent() {
+            attributes.push(("percent", "1".to_string()));
+        }
+
+        attributes.push(("val", value.to_string()));
+
+        self.writer.xml_empty_tag("x:top10", &attributes);
+    }
+
+    // Write the <x:filters> element.
+    fn write_list_filters(&mut self, filter_condition: &FilterCondition) {
+        let mut attributes = vec![];
+
+        if filter_condition.should_match_blanks {
+            attributes.push(("blank", "1".to_string()));
+        }
+
+        if filter_condition.list.is_empty() && filter_condition.date_groups.is_empty() {
+            self.writer.xml_empty_tag("x:filters", &attributes);
+        } else {
+            self.writer.xml_start_tag("x:filters", &attributes);
+
+            for data in &filter_condition.list {
+                let attributes = [("val", data.string.clone())];
+                self.writer.xml_empty_tag("x:filter", &attributes);
+            }
+
+            for date_group in &filter_condition.date_groups {
+                self.write_date_group_item(date_group);
+            }
+
+            self.writer.xml_end_tag("x:filters");
+        }
+    }
+
+    // Write the <x:dateGroupItem> element.
+    fn write_date_group_item(&mut self, date_group: &FilterDateGroupItem) {
+        let names = ["year", "month", "day", "hour", "minute", "second"];
+        let mut attributes = vec![];
+
+        for (name, value) in names
+            .iter()
+            .zip(date_group.components)
+            .take(date_group.num_components())
+        {
+            attributes.push((*name, value.to_string()));
+        }
+
+        attributes.push(("dateTimeGrouping", date_group.grouping.to_string()));
+
+        self.writer.xml_empty_tag("x:dateGroupItem", &attributes);
+    }
+
+    // Write the <x:customFilters> element.
+    fn write_custom_filters(&mut self, filter_condition: &FilterCondition) {
+        let mut attributes = vec![];
+
+        if !filter_condition.apply_logical_or {
+            attributes.push(("and", "1".to_string()));
+        }
+
+        self.writer.xml_start_tag("x:customFilters", &attributes);
+
+        if let Some(data) = filter_condition.custom1.as_ref() {
+            self.write_custom_filter(data);
+        }
+        if let Some(data) = filter_condition.custom2.as_ref() {
+            self.write_custom_filter(data);
+        }
+
+        self.writer.xml_end_tag("x:customFilters");
+    }
+
+    // Write the <x:customFilter> element.
+    fn write_custom_filter(&mut self, data: &FilterData) {
+        let mut attributes = vec![];
+
+        if !data.criteria.operator().is_empty() {
+            attributes.push(("operator", data.criteria.operator()));
+        }
+
+        attributes.push(("val", data.value()));
+
+        self.writer.xml_empty_tag("x:customFilter", &attributes);
+    }
+
+    // Write the <sortRule> element.
+    fn write_sort_rule(&mut self, col: ColNum, range: &str, descending: bool) {
+        let attributes = [("colId", col.to_string())];
+
+        self.writer.xml_start_tag("sortRule", &attributes);
+
+        // Write the sortCondition element.
+        let mut attributes = vec![];
+
+        if descending {
+            attributes.push(("descending", "1".to_string()));
+        }
+
+        attributes.push(("ref", range.to_string()));
+
+        self.writer.xml_empty_tag("sortCondition", &attributes);
+
+        self.writer.xml_end_tag("sortRule");
+    }
+
+    // Create a repeatable GUID style id for a view or filter from a hash of the
+    // worksheet name and the view name.
+    fn guid(&self, view_name: &str, id_type: &str) -> String {
+        let mut hasher = Sha512::new();
+        hasher.update(self.sheet_name.as_bytes());
+        hasher.update([0]);
+        hasher.update(view_name.as_bytes());
+        hasher.update([0]);
+        hasher.update(id_type.as_bytes());
+        let hash = hasher.finalize();
+
+        let hex = hash[..16].iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02X}");
+            hex
+        });
+
+        format!(
+            "{{{}-{}-{}-{}-{}}}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )
+    }
+}
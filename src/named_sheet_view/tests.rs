@@ -0,0 +1,147 @@
+// Named sheet view unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod named_sheet_view_tests {
+
+    use crate::named_sheet_view::NamedSheetViews;
+    use crate::test_functions::xml_to_vec;
+    use crate::{FilterCondition, FilterCriteria, NamedSheetView, Worksheet, XlsxError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_assemble() {
+        let mut named_sheet_views = NamedSheetViews::new();
+        named_sheet_views.sheet_name = "Sheet1".to_string();
+        named_sheet_views.filter_range = Some((1, 1, 9, 4));
+
+        let filter1 = FilterCondition::new()
+            .add_list_filter("East")
+            .add_list_filter("West");
+        let filter2 = FilterCondition::new()
+            .add_custom_filter(FilterCriteria::GreaterThan, 4000)
+            .add_custom_filter(FilterCriteria::LessThan, 8000);
+
+        named_sheet_views.views = vec![
+            NamedSheetView::new("Regions")
+                .set_filter(1, &filter1)
+                .set_filter(3, &filter2),
+            NamedSheetView::new("Sorted").set_sort(2, true),
+            NamedSheetView::new("Unfiltered"),
+        ];
+
+        let view1_id = named_sheet_views.guid("Regions", "view");
+        let filter1_id = named_sheet_views.guid("Regions", "filter");
+        let view2_id = named_sheet_views.guid("Sorted", "view");
+        let filter2_id = named_sheet_views.guid("Sorted", "filter");
+        let view3_id = named_sheet_views.guid("Unfiltered", "view");
+        let filter3_id = named_sheet_views.guid("Unfiltered", "filter");
+
+        named_sheet_views.assemble_xml_file();
+
+        let got = named_sheet_views.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(&format!(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <namedSheetViews xmlns="http://schemas.microsoft.com/office/spreadsheetml/2019/namedsheetviews" xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <namedSheetView name="Regions" id="{view1_id}">
+                <nsvFilter filterId="{filter1_id}" ref="B2:E10">
+                  <columnFilter colId="0">
+                    <filter colId="0">
+                      <x:filters>
+                        <x:filter val="East"/>
+                        <x:filter val="West"/>
+                      </x:filters>
+                    </filter>
+                  </columnFilter>
+                  <columnFilter colId="2">
+                    <filter colId="2">
+                      <x:customFilters and="1">
+                        <x:customFilter operator="greaterThan" val="4000"/>
+                        <x:customFilter operator="lessThan" val="8000"/>
+                      </x:customFilters>
+                    </filter>
+                  </columnFilter>
+                </nsvFilter>
+              </namedSheetView>
+              <namedSheetView name="Sorted" id="{view2_id}">
+                <nsvFilter filterId="{filter2_id}" ref="B2:E10">
+                  <sortRules>
+                    <sortRule colId="1">
+                      <sortCondition descending="1" ref="C3:C10"/>
+                    </sortRule>
+                  </sortRules>
+                </nsvFilter>
+              </namedSheetView>
+              <namedSheetView name="Unfiltered" id="{view3_id}">
+                <nsvFilter filterId="{filter3_id}" ref="B2:E10"/>
+              </namedSheetView>
+            </namedSheetViews>
+            "#
+        ));
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_guid() {
+        let mut named_sheet_views = NamedSheetViews::new();
+        named_sheet_views.sheet_name = "Sheet1".to_string();
+
+        let guid = named_sheet_views.guid("View", "view");
+
+        assert_eq!(38, guid.len());
+        assert!(guid.starts_with('{') && guid.ends_with('}'));
+        assert_eq!(guid, named_sheet_views.guid("View", "view"));
+        assert_ne!(guid, named_sheet_views.guid("View", "filter"));
+        assert_ne!(guid, named_sheet_views.guid("View2", "view"));
+
+        named_sheet_views.sheet_name = "Sheet2".to_string();
+        assert_ne!(guid, named_sheet_views.guid("View", "view"));
+    }
+
+    #[test]
+    fn test_add_named_sheet_view_errors() {
+        let mut worksheet = Worksheet::new();
+        let filter = FilterCondition::new().add_list_filter("East");
+
+        // Blank name.
+        let result = worksheet.add_named_sheet_view(&NamedSheetView::new(""));
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        // Filter without an autofilter.
+        let view = NamedSheetView::new("View").set_filter(0, &filter);
+        let result = worksheet.add_named_sheet_view(&view);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        worksheet.autofilter(0, 1, 10, 3).unwrap();
+
+        // Column outside the autofilter range.
+        let view = NamedSheetView::new("View").set_filter(0, &filter);
+        let result = worksheet.add_named_sheet_view(&view);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let view = NamedSheetView::new("View").set_sort(4, false);
+        let result = worksheet.add_named_sheet_view(&view);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        // Empty filter condition.
+        let view = NamedSheetView::new("View").set_filter(1, &FilterCondition::new());
+        let result = worksheet.add_named_sheet_view(&view);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        // Valid view.
+        let view = NamedSheetView::new("View").set_filter(1, &filter);
+        let result = worksheet.add_named_sheet_view(&view);
+        assert!(result.is_ok());
+
+        // Duplicate name.
+        let result = worksheet.add_named_sheet_view(&NamedSheetView::new("VIEW"));
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+}
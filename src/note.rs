@@ -0,0 +1,408 @@
+// note - A module for creating the Excel notes, also called legacy comments.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+mod tests;
+
+use crate::drawing::{DrawingInfo, DrawingObject, DrawingType};
+use crate::warning::Warnings;
+use crate::worksheet::{COL_MAX, ROW_MAX};
+use crate::{ColNum, Color, IntoColor, ObjectMovement, RowNum};
+
+/// The `Note` struct represents a worksheet note.
+///
+/// Notes are a feature of Excel that allows the user to add a text annotation
+/// to a cell. They are displayed in a yellow text box when the user hovers over
+/// the cell and a small red triangle is shown in the corner of the cell to
+/// indicate that it contains a note.
+///
+/// In older versions of Excel notes were called "Comments". In newer versions
+/// of Excel "Comments" are threaded conversations and notes are a separate
+/// feature. `rust_xlsxwriter` only supports notes.
+///
+/// A note is added to a worksheet cell via the
+/// [`Worksheet::insert_note()`](crate::Worksheet::insert_note) method.
+///
+/// # Examples
+///
+/// Example of adding a note to a worksheet cell.
+///
+/// ```
+/// # // This code is available in examples/doc_worksheet_insert_note.rs
+/// #
+/// # use rust_xlsxwriter::{Note, Workbook, XlsxError};
+/// #
+/// # fn main() -> Result<(), XlsxError> {
+/// #     // Create a new Excel file object.
+/// #     let mut workbook = Workbook::new();
+/// #     let worksheet = workbook.add_worksheet();
+/// #
+///     // Write some data.
+///     worksheet.write(0, 0, "Hello")?;
+///
+///     // Create a new note.
+///     let note = Note::new("Some text for the note");
+///
+///     // Add the note to a worksheet cell.
+///     worksheet.insert_note(0, 0, &note)?;
+/// #
+/// #     // Save the file.
+/// #     workbook.save("notes.xlsx")?;
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone)]
+pub struct Note {
+    pub(crate) text: String,
+    pub(crate) author: Option<String>,
+    pub(crate) has_author_prefix: bool,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) is_visible: Option<bool>,
+    pub(crate) background_color: Color,
+    pub(crate) start_cell: Option<(RowNum, ColNum)>,
+    pub(crate) offsets: Option<(u32, u32)>,
+
+    // Properties that are set when the note is inserted into a worksheet.
+    pub(crate) cell_row: RowNum,
+    pub(crate) cell_col: ColNum,
+    pub(crate) drawing_info: Option<DrawingInfo>,
+    pub(crate) warnings: Warnings,
+}
+
+impl Note {
+    /// Create a new `Note` object to represent an Excel cell note.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The text for the note. The maximum string length is 32,767
+    ///   characters.
+    ///
+    pub fn new(text: impl Into<String>) -> Note {
+        Note {
+            text: text.into(),
+            author: None,
+            has_author_prefix: true,
+            width: 128,
+            height: 74,
+            is_visible: None,
+            background_color: Color::RGB(0xFFFFE1),
+            start_cell: None,
+            offsets: None,
+            cell_row: 0,
+            cell_col: 0,
+            drawing_info: None,
+            warnings: Warnings::new(),
+        }
+    }
+
+    /// Set the author name for a note.
+    ///
+    /// The author name is stored in the note and is displayed in the Excel
+    /// status bar when the user hovers over the cell. By default it is also
+    /// added, in bold, as a prefix to the note text. See
+    /// [`Note::add_author_prefix()`].
+    ///
+    /// If the author isn't set the default worksheet author is used, see
+    /// [`Worksheet::set_comment_author()`](crate::Worksheet::set_comment_author).
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The note author name.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding notes to worksheet cells with a note specific author
+    /// and with the default worksheet author.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_note_set_author.rs
+    /// #
+    /// # use rust_xlsxwriter::{Note, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Set the default author for notes in the worksheet.
+    ///     worksheet.set_comment_author("Rust");
+    ///
+    ///     // Write some data.
+    ///     worksheet.write(0, 0, "Hello")?;
+    ///     worksheet.write(2, 0, "World")?;
+    ///
+    ///     // Create a note with the default worksheet author.
+    ///     let note1 = Note::new("Some text for the note");
+    ///
+    ///     // Create a note with a different author.
+    ///     let note2 = Note::new("Some other text").set_author("Ferris");
+    ///
+    ///     // Add the notes to worksheet cells.
+    ///     worksheet.insert_note(0, 0, &note1)?;
+    ///     worksheet.insert_note(2, 0, &note2)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("notes.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_author(mut self, name: impl Into<String>) -> Note {
+        self.author = Some(name.into());
+        self
+    }
+
+    /// Turn on/off the author name prefix in the note text.
+    ///
+    /// When a note author is set, either via [`Note::set_author()`] or via
+    /// [`Worksheet::set_comment_author()`](crate::Worksheet::set_comment_author),
+    /// Excel adds the author name in bold, followed by a colon and a newline,
+    /// as a prefix to the note text. This option can be used to turn off the
+    /// prefix while still storing the author name.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is on by default.
+    ///
+    pub fn add_author_prefix(mut self, enable: bool) -> Note {
+        self.has_author_prefix = enable;
+        self
+    }
+
+    /// Set the width of the note box in pixels.
+    ///
+    /// The default width of a note box is 128 pixels.
+    ///
+    /// # Parameters
+    ///
+    /// * `width` - The width of the note box in pixels.
+    ///
+    pub fn set_width(mut self, width: u32) -> Note {
+        if width > 0 {
+            self.width = width;
+        }
+        self
+    }
+
+    /// Set the height of the note box in pixels.
+    ///
+    /// The default height of a note box is 74 pixels.
+    ///
+    /// # Parameters
+    ///
+    /// * `height` - The height of the note box in pixels.
+    ///
+    pub fn set_height(mut self, height: u32) -> Note {
+        if height > 0 {
+            self.height = height;
+        }
+        self
+    }
+
+    /// Make the note visible when the file loads.
+    ///
+    /// By default Excel hides cell notes until the user mouses over the parent
+    /// cell. This option makes the note visible when the worksheet is opened.
+    ///
+    /// All the notes in a worksheet can be made visible via the
+    /// [`Worksheet::show_all_notes()`](crate::Worksheet::show_all_notes)
+    /// method. This method can be used to override that setting for an
+    /// individual note.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a note to a worksheet cell that is always visible and
+    /// that has a user defined size and background color.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_note_set_visible.rs
+    /// #
+    /// # use rust_xlsxwriter::{Note, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Write some data.
+    ///     worksheet.write(1, 1, "Hello")?;
+    ///
+    ///     // Create a visible note with a custom size and color.
+    ///     let note = Note::new("This note is always visible")
+    ///         .set_visible(true)
+    ///         .set_width(200)
+    ///         .set_height(100)
+    ///         .set_background_color("#CCFFCC");
+    ///
+    ///     // Add the note to a worksheet cell.
+    ///     worksheet.insert_note(1, 1, &note)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("notes.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_visible(mut self, enable: bool) -> Note {
+        self.is_visible = Some(enable);
+        self
+    }
+
+    /// Set the background color of the note box.
+    ///
+    /// The default background color is the Excel note yellow: `#FFFFE1`.
+    ///
+    /// # Parameters
+    ///
+    /// * `color` - The background color property defined by a [`Color`] enum
+    ///   value or a type that can convert [`Into`] a [`Color`].
+    ///
+    pub fn set_background_color(mut self, color: impl IntoColor) -> Note {
+        let color = color.new_color();
+        if self.warnings.check_color("set_background_color", color) {
+            self.background_color = color;
+        }
+
+        self
+    }
+
+    /// Set the cell where the top left corner of the note box is positioned.
+    ///
+    /// By default the note box is positioned one column to the right and one
+    /// row above the cell that it annotates, with some adjustments for cells at
+    /// the edges of the worksheet. This method can be used to position the
+    /// note box at a different cell.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    ///
+    pub fn set_start_cell(mut self, row: RowNum, col: ColNum) -> Note {
+        self.start_cell = Some((row, col));
+        self
+    }
+
+    /// Set the pixel offsets of the note box from the start cell.
+    ///
+    /// This method can be used in conjunction with
+    /// [`Note::set_start_cell()`] to fine tune the position of the note box.
+    ///
+    /// # Parameters
+    ///
+    /// * `x_offset` - The horizontal offset within the start cell in pixels.
+    /// * `y_offset` - The vertical offset within the start cell in pixels.
+    ///
+    pub fn set_offset(mut self, x_offset: u32, y_offset: u32) -> Note {
+        self.offsets = Some((x_offset, y_offset));
+        self
+    }
+
+    // -----------------------------------------------------------------------
+    // Crate level helper methods.
+    // -----------------------------------------------------------------------
+
+    // Get the cell where the note box starts. Excel positions the box relative
+    // to the parent cell with some adjustments for the worksheet edges.
+    pub(crate) fn start_cell(&self) -> (RowNum, ColNum) {
+        if let Some(start_cell) = self.start_cell {
+            return start_cell;
+        }
+
+        let row = self.cell_row;
+        let col = self.cell_col;
+
+        let start_row = match row {
+            0 => 0,
+            _ if row == ROW_MAX - 3 => ROW_MAX - 7,
+            _ if row == ROW_MAX - 2 => ROW_MAX - 6,
+            _ if row == ROW_MAX - 1 => ROW_MAX - 5,
+            _ => row - 1,
+        };
+
+        let start_col = match col {
+            _ if col == COL_MAX - 3 => COL_MAX - 6,
+            _ if col == COL_MAX - 2 => COL_MAX - 5,
+            _ if col == COL_MAX - 1 => COL_MAX - 4,
+            _ => col + 1,
+        };
+
+        (start_row, start_col)
+    }
+
+    // Get the author name, if any, that should be displayed in the note text.
+    pub(crate) fn author_prefix(&self) -> Option<&str> {
+        match &self.author {
+            Some(author) if self.has_author_prefix && !author.is_empty() => Some(author),
+            _ => None,
+        }
+    }
+}
+
+impl DrawingObject for Note {
+    fn x_offset(&self) -> u32 {
+        if let Some((x_offset, _)) = self.offsets {
+            return x_offset;
+        }
+
+        if self.cell_col >= COL_MAX - 3 {
+            49
+        } else {
+            15
+        }
+    }
+
+    fn y_offset(&self) -> u32 {
+        if let Some((_, y_offset)) = self.offsets {
+            return y_offset;
+        }
+
+        match self.cell_row {
+            0 => 2,
+            row if row == ROW_MAX - 3 || row == ROW_MAX - 2 => 16,
+            row if row == ROW_MAX - 1 => 14,
+            _ => 10,
+        }
+    }
+
+    fn width_scaled(&self) -> f64 {
+        f64::from(self.width)
+    }
+
+    fn height_scaled(&self) -> f64 {
+        f64::from(self.height)
+    }
+
+    fn object_movement(&self) -> ObjectMovement {
+        ObjectMovement::MoveAndSizeWithCells
+    }
+
+    fn name(&self) -> String {
+        String::new()
+    }
+
+    fn alt_text(&self) -> String {
+        String::new()
+    }
+
+    fn decorative(&self) -> bool {
+        false
+    }
+
+    fn drawing_type(&self) -> DrawingType {
+        DrawingType::Vml
+    }
+}
@@ -0,0 +1,84 @@
+// Note unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod note_tests {
+
+    use crate::drawing::DrawingObject;
+    use crate::worksheet::{COL_MAX, ROW_MAX};
+    use crate::{Note, Worksheet, XlsxError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_positions() {
+        // Test the default note box position for cells at the worksheet edges.
+        let data = [
+            // row, col, start_row, start_col, x_offset, y_offset.
+            (0, 0, 0, 1, 15, 2),
+            (1, 1, 0, 2, 15, 10),
+            (ROW_MAX - 3, 0, ROW_MAX - 7, 1, 15, 16),
+            (ROW_MAX - 2, 0, ROW_MAX - 6, 1, 15, 16),
+            (ROW_MAX - 1, 0, ROW_MAX - 5, 1, 15, 14),
+            (1, COL_MAX - 3, 0, COL_MAX - 6, 49, 10),
+            (1, COL_MAX - 2, 0, COL_MAX - 5, 49, 10),
+            (1, COL_MAX - 1, 0, COL_MAX - 4, 49, 10),
+        ];
+
+        for (row, col, start_row, start_col, x_offset, y_offset) in data {
+            let mut note = Note::new("Some text");
+            note.cell_row = row;
+            note.cell_col = col;
+
+            assert_eq!((start_row, start_col), note.start_cell());
+            assert_eq!(x_offset, note.x_offset());
+            assert_eq!(y_offset, note.y_offset());
+        }
+    }
+
+    #[test]
+    fn test_user_positions() {
+        let mut note = Note::new("Some text").set_start_cell(5, 5).set_offset(0, 0);
+        note.cell_row = 1;
+        note.cell_col = 1;
+
+        assert_eq!((5, 5), note.start_cell());
+        assert_eq!(0, note.x_offset());
+        assert_eq!(0, note.y_offset());
+    }
+
+    #[test]
+    fn test_author_prefix() {
+        let note = Note::new("Some text");
+        assert_eq!(None, note.author_prefix());
+
+        let note = Note::new("Some text").set_author("");
+        assert_eq!(None, note.author_prefix());
+
+        let note = Note::new("Some text").set_author("John");
+        assert_eq!(Some("John"), note.author_prefix());
+
+        let note = Note::new("Some text")
+            .set_author("John")
+            .add_author_prefix(false);
+        assert_eq!(None, note.author_prefix());
+    }
+
+    #[test]
+    fn test_insert_note_errors() {
+        let mut worksheet = Worksheet::new();
+        let note = Note::new("Some text");
+
+        let result = worksheet.insert_note(ROW_MAX, 0, &note);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.insert_note(0, COL_MAX, &note);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let note = Note::new("a".repeat(32_768));
+        let result = worksheet.insert_note(0, 0, &note);
+        assert!(matches!(result, Err(XlsxError::MaxStringLengthExceeded)));
+    }
+}
@@ -43,18 +43,18 @@
 use std::collections::HashSet;
 use std::io::{Seek, Write};
 
-#[cfg(not(target_arch = "wasm32"))]
-use std::thread;
-
 use zip::write::FileOptions;
 use zip::{DateTime, ZipWriter};
 
 use crate::app::App;
+use crate::comment::Comment;
 use crate::content_types::ContentTypes;
 use crate::core::Core;
 use crate::custom::Custom;
+use crate::custom_xml::CustomXmlProps;
 use crate::error::XlsxError;
 use crate::metadata::Metadata;
+use crate::named_sheet_view::NamedSheetViews;
 use crate::relationship::Relationship;
 use crate::rich_value::RichValue;
 use crate::rich_value_rel::RichValueRel;
@@ -76,17 +76,26 @@ pub struct Packager<W: Write + Seek> {
     zip_options_for_binary_files: FileOptions,
 }
 
-impl<W: Write + Seek + Send> Packager<W> {
+impl<W: Write + Seek> Packager<W> {
     // -----------------------------------------------------------------------
     // Crate public methods.
     // -----------------------------------------------------------------------
 
     // Create a new Packager struct.
-    pub(crate) fn new(writer: W) -> Packager<W> {
+    pub(crate) fn new(writer: W, compression_level: Option<u8>) -> Packager<W> {
         let zip = zip::ZipWriter::new(writer);
 
+        // A compression level of 0 means that the files are stored without
+        // compression. Otherwise it is the deflate level.
+        let (compression_method, compression_level) = match compression_level {
+            Some(0) => (zip::CompressionMethod::Stored, None),
+            Some(level) => (zip::CompressionMethod::Deflated, Some(i32::from(level))),
+            None => (zip::CompressionMethod::Deflated, None),
+        };
+
         let zip_options = FileOptions::default()
-            .compression_method(zip::CompressionMethod::Deflated)
+            .compression_method(compression_method)
+            .compression_level(compression_level)
             .unix_permissions(0o600)
             .last_modified_time(DateTime::default())
             .large_file(false);
@@ -115,33 +124,29 @@ impl<W: Write + Seek + Send> Packager<W> {
         self.write_styles_file(workbook)?;
         self.write_workbook_file(workbook)?;
 
-        // Update the shared string table in each worksheet.
-        let mut string_table = SharedStringsTable::new();
-        for worksheet in &mut workbook.worksheets {
-            worksheet.update_string_table_ids(&mut string_table);
-        }
-
-        // Assemble, but don't write, the worksheet files in parallel. These are
-        // generally the largest files and the threading can help performance if
-        // there are multiple large worksheets.
-        #[cfg(not(target_arch = "wasm32"))]
-        thread::scope(|scope| {
-            for worksheet in &mut workbook.worksheets {
-                scope.spawn(|| {
-                    worksheet.assemble_xml_file();
-                });
-            }
-        });
+        workbook.check_cancelled()?;
 
-        // For wasm targets don't use threading.
-        #[cfg(target_arch = "wasm32")]
+        // Update the shared string table in each worksheet, unless the strings
+        // are written inline.
+        let mut string_table = if workbook.use_inline_strings {
+            SharedStringsTable::new()
+        } else {
+            SharedStringsTable::with_capacity(workbook.shared_strings_capacity)
+        };
         for worksheet in &mut workbook.worksheets {
-            worksheet.assemble_xml_file();
+            worksheet.use_inline_strings = workbook.use_inline_strings;
+            if !workbook.use_inline_strings {
+                worksheet.update_string_table_ids(&mut string_table);
+            }
         }
 
         // Write the worksheet file and and associated rel files.
-        for (index, worksheet) in workbook.worksheets.iter_mut().enumerate() {
-            self.write_worksheet_file(worksheet, index + 1)?;
+        for index in 0..workbook.worksheets.len() {
+            workbook.check_cancelled()?;
+
+            let worksheet = &mut workbook.worksheets[index];
+            self.write_worksheet_file(worksheet, index + 1)
+                .map_err(|error| error.in_worksheet(&worksheet.name, None))?;
             if worksheet.has_relationships() {
                 self.write_worksheet_rels_file(worksheet, index + 1)?;
             }
@@ -155,11 +160,21 @@ impl<W: Write + Seek + Send> Packager<W> {
         self.write_app_file(options)?;
         self.write_custom_file(options)?;
 
+        workbook.check_cancelled()?;
+
         self.write_drawing_files(workbook)?;
         self.write_vml_files(workbook)?;
+        self.write_comment_files(workbook)?;
         self.write_image_files(workbook)?;
         self.write_chart_files(workbook)?;
         self.write_table_files(workbook)?;
+        self.write_named_sheet_view_files(workbook)?;
+        self.write_vba_project_file(workbook)?;
+        self.write_custom_xml_files(workbook)?;
+
+        if options.has_vba_signature {
+            self.write_vba_project_rels_file()?;
+        }
 
         let mut image_index = 1;
         let mut vml_index = 1;
@@ -169,6 +184,13 @@ impl<W: Write + Seek + Send> Packager<W> {
                 self.write_drawing_rels_file(&worksheet.drawing_relationships, image_index)?;
                 image_index += 1;
             }
+
+            // Note vml files don't have a rels file but they are numbered in
+            // sequence with the header/footer vml files.
+            if worksheet.has_notes() {
+                vml_index += 1;
+            }
+
             if !worksheet.vml_drawing_relationships.is_empty() {
                 self.write_vml_drawing_rels_file(&worksheet.vml_drawing_relationships, vml_index)?;
                 vml_index += 1;
@@ -214,6 +236,14 @@ impl<W: Write + Seek + Send> Packager<W> {
             content_types.add_table_name(i + 1);
         }
 
+        for i in 0..options.num_named_sheet_views {
+            content_types.add_named_sheet_view_name(i + 1);
+        }
+
+        for i in 0..options.num_comments {
+            content_types.add_comment_name(i + 1);
+        }
+
         if options.has_sst_table {
             content_types.add_share_strings();
         }
@@ -226,6 +256,16 @@ impl<W: Write + Seek + Send> Packager<W> {
             content_types.add_rich_value();
         }
 
+        content_types.set_workbook_type(options.has_vba_project, options.is_template);
+
+        if options.has_vba_project {
+            content_types.add_vba_project();
+        }
+
+        if options.has_vba_signature {
+            content_types.add_vba_signature();
+        }
+
         if options.has_vml {
             content_types.add_default(
                 "vml",
@@ -251,6 +291,10 @@ impl<W: Write + Seek + Send> Packager<W> {
             content_types.add_custom_properties();
         }
 
+        for i in 0..options.num_custom_xml_parts {
+            content_types.add_custom_xml_props_name(i + 1);
+        }
+
         self.zip
             .start_file("[Content_Types].xml", self.zip_options)?;
 
@@ -299,10 +343,22 @@ impl<W: Write + Seek + Send> Packager<W> {
             rels.add_document_relationship("sharedStrings", "sharedStrings.xml", "");
         }
 
+        if options.has_vba_project {
+            rels.add_office_relationship("2006", "vbaProject", "vbaProject.bin", "");
+        }
+
         if options.has_metadata {
             rels.add_document_relationship("sheetMetadata", "metadata.xml", "");
         }
 
+        for index in 1..=options.num_custom_xml_parts {
+            rels.add_document_relationship(
+                "customXml",
+                format!("../customXml/item{index}.xml").as_str(),
+                "",
+            );
+        }
+
         if options.has_embedded_images {
             rels.add_office_relationship(
                 "2022/10",
@@ -345,7 +401,10 @@ impl<W: Write + Seek + Send> Packager<W> {
     ) -> Result<(), XlsxError> {
         let filename = format!("xl/worksheets/sheet{index}.xml");
         self.zip.start_file(filename, self.zip_options)?;
-        self.zip.write_all(worksheet.writer.xmlfile.get_ref())?;
+
+        // Stream the worksheet xml directly into the zip file entry rather than
+        // assembling the entire file in memory first.
+        worksheet.assemble_xml_file_to(&mut self.zip)?;
 
         Ok(())
     }
@@ -370,6 +429,19 @@ impl<W: Write + Seek + Send> Packager<W> {
             rels.add_document_relationship(&relationship.0, &relationship.1, &relationship.2);
         }
 
+        for relationship in &worksheet.comment_relationships {
+            rels.add_document_relationship(&relationship.0, &relationship.1, &relationship.2);
+        }
+
+        for relationship in &worksheet.named_sheet_view_relationships {
+            rels.add_office_relationship(
+                "2019/04",
+                &relationship.0,
+                &relationship.1,
+                &relationship.2,
+            );
+        }
+
         let filename = format!("xl/worksheets/_rels/sheet{index}.xml.rels");
 
         self.zip.start_file(filename, self.zip_options)?;
@@ -468,9 +540,7 @@ impl<W: Write + Seek + Send> Packager<W> {
         self.zip
             .start_file("xl/sharedStrings.xml", self.zip_options)?;
 
-        shared_strings.assemble_xml_file(string_table);
-        self.zip
-            .write_all(shared_strings.writer.xmlfile.get_ref())?;
+        shared_strings.assemble_xml_file_to(string_table, &mut self.zip)?;
 
         Ok(())
     }
@@ -566,6 +636,14 @@ impl<W: Write + Seek + Send> Packager<W> {
             }
         }
 
+        for (heading, part_names) in &options.app_heading_pairs {
+            app.add_heading_pair(heading, part_names.len() as u16);
+
+            for part_name in part_names {
+                app.add_part_name(part_name);
+            }
+        }
+
         self.zip.start_file("docProps/app.xml", self.zip_options)?;
 
         app.assemble_xml_file();
@@ -686,6 +764,20 @@ impl<W: Write + Seek + Send> Packager<W> {
     fn write_vml_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
         let mut index = 1;
         for worksheet in &mut workbook.worksheets {
+            if worksheet.has_notes() {
+                let filename = format!("xl/drawings/vmlDrawing{index}.vml");
+                self.zip.start_file(filename, self.zip_options)?;
+
+                let mut vml = Vml::new();
+                vml.notes.clone_from(&worksheet.notes_vml_info);
+                vml.data_id = worksheet.vml_data_id;
+                vml.shape_id = worksheet.vml_shape_id;
+                vml.assemble_xml_file();
+
+                self.zip.write_all(vml.writer.xmlfile.get_ref())?;
+                index += 1;
+            }
+
             if worksheet.has_header_footer_images() {
                 let filename = format!("xl/drawings/vmlDrawing{index}.vml");
                 self.zip.start_file(filename, self.zip_options)?;
@@ -693,8 +785,8 @@ impl<W: Write + Seek + Send> Packager<W> {
                 let mut vml = Vml::new();
                 vml.header_images
                     .append(&mut worksheet.header_footer_vml_info);
-                vml.data_id = index;
-                vml.shape_id = 1024 * index;
+                vml.data_id = worksheet.header_footer_vml_data_id;
+                vml.shape_id = 1024 * worksheet.header_footer_vml_data_id;
                 vml.assemble_xml_file();
 
                 self.zip.write_all(vml.writer.xmlfile.get_ref())?;
@@ -705,6 +797,95 @@ impl<W: Write + Seek + Send> Packager<W> {
         Ok(())
     }
 
+    // Write the comment files.
+    fn write_comment_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
+        let mut index = 1;
+        for worksheet in &mut workbook.worksheets {
+            if worksheet.has_notes() {
+                let filename = format!("xl/comments{index}.xml");
+                self.zip.start_file(filename, self.zip_options)?;
+
+                let mut comment = Comment::new();
+                comment.notes.clone_from(&worksheet.notes_vml_info);
+                comment.assemble_xml_file();
+
+                self.zip.write_all(comment.writer.xmlfile.get_ref())?;
+                index += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Write the vbaProject.bin file.
+    fn write_vba_project_file(&mut self, workbook: &Workbook) -> Result<(), XlsxError> {
+        if let Some(vba_project) = &workbook.vba_project {
+            self.zip
+                .start_file("xl/vbaProject.bin", self.zip_options_for_binary_files)?;
+
+            self.zip.write_all(vba_project)?;
+        }
+
+        if let Some(vba_signature) = &workbook.vba_signature {
+            self.zip.start_file(
+                "xl/vbaProjectSignature.bin",
+                self.zip_options_for_binary_files,
+            )?;
+
+            self.zip.write_all(vba_signature)?;
+        }
+
+        Ok(())
+    }
+
+    // Write the customXml item, itemProps and rels files.
+    fn write_custom_xml_files(&mut self, workbook: &Workbook) -> Result<(), XlsxError> {
+        for (index, (xml, schema, item_id)) in workbook.custom_xml_parts.iter().enumerate() {
+            let index = index + 1;
+
+            self.zip
+                .start_file(format!("customXml/item{index}.xml"), self.zip_options)?;
+            self.zip.write_all(xml.as_bytes())?;
+
+            let mut custom_xml_props = CustomXmlProps::new(item_id, schema);
+            self.zip
+                .start_file(format!("customXml/itemProps{index}.xml"), self.zip_options)?;
+            custom_xml_props.assemble_xml_file();
+            self.zip
+                .write_all(custom_xml_props.writer.xmlfile.get_ref())?;
+
+            let mut rels = Relationship::new();
+            rels.add_document_relationship(
+                "customXmlProps",
+                format!("itemProps{index}.xml").as_str(),
+                "",
+            );
+            self.zip.start_file(
+                format!("customXml/_rels/item{index}.xml.rels"),
+                self.zip_options,
+            )?;
+            rels.assemble_xml_file();
+            self.zip.write_all(rels.writer.xmlfile.get_ref())?;
+        }
+
+        Ok(())
+    }
+
+    // Write the vbaProject.bin.rels file.
+    fn write_vba_project_rels_file(&mut self) -> Result<(), XlsxError> {
+        let mut rels = Relationship::new();
+
+        rels.add_office_relationship("2006", "vbaProjectSignature", "vbaProjectSignature.bin", "");
+
+        self.zip
+            .start_file("xl/_rels/vbaProject.bin.rels", self.zip_options)?;
+
+        rels.assemble_xml_file();
+        self.zip.write_all(rels.writer.xmlfile.get_ref())?;
+
+        Ok(())
+    }
+
     // Write the image files.
     fn write_image_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
         let mut index = 1;
@@ -754,12 +935,23 @@ impl<W: Write + Seek + Send> Packager<W> {
     // Write the chart files.
     fn write_chart_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
         let mut index = 1;
+        let strict_mode = workbook.strict_mode;
 
         for worksheet in &mut workbook.worksheets {
-            for chart in worksheet.charts.values_mut() {
+            for (cell, chart) in &mut worksheet.charts {
                 let filename = format!("xl/charts/chart{index}.xml");
                 self.zip.start_file(filename, self.zip_options)?;
                 chart.assemble_xml_file();
+
+                // Chart warnings are collected as the chart is assembled so
+                // they are raised here in strict mode.
+                if strict_mode {
+                    if let Some(warning) = chart.warnings.first() {
+                        return Err(XlsxError::StrictModeWarning(warning.message.clone())
+                            .in_worksheet(&worksheet.name, Some(*cell)));
+                    }
+                }
+
                 self.zip.write_all(chart.writer.xmlfile.get_ref())?;
                 index += 1;
             }
@@ -784,6 +976,45 @@ impl<W: Write + Seek + Send> Packager<W> {
 
         Ok(())
     }
+
+    // Write the named sheet view files.
+    fn write_named_sheet_view_files(&mut self, workbook: &Workbook) -> Result<(), XlsxError> {
+        let mut index = 1;
+
+        for worksheet in &workbook.worksheets {
+            if worksheet.named_sheet_views.is_empty() {
+                continue;
+            }
+
+            let mut named_sheet_views = NamedSheetViews::new();
+            named_sheet_views
+                .views
+                .clone_from(&worksheet.named_sheet_views);
+            named_sheet_views.sheet_name.clone_from(&worksheet.name);
+            named_sheet_views
+                .global_dxf_indices
+                .clone_from(&worksheet.global_dxf_indices);
+
+            let autofilter = &worksheet.autofilter_defined_name;
+            if autofilter.in_use {
+                named_sheet_views.filter_range = Some((
+                    autofilter.first_row,
+                    autofilter.first_col,
+                    autofilter.last_row,
+                    autofilter.last_col,
+                ));
+            }
+
+            let filename = format!("xl/namedSheetViews/namedSheetView{index}.xml");
+            self.zip.start_file(filename, self.zip_options)?;
+            named_sheet_views.assemble_xml_file();
+            self.zip
+                .write_all(named_sheet_views.writer.xmlfile.get_ref())?;
+            index += 1;
+        }
+
+        Ok(())
+    }
 }
 
 // Internal struct to pass options to the Packager struct.
@@ -793,13 +1024,20 @@ pub(crate) struct PackagerOptions {
     pub(crate) has_dynamic_functions: bool,
     pub(crate) has_embedded_images: bool,
     pub(crate) has_vml: bool,
+    pub(crate) has_vba_project: bool,
+    pub(crate) has_vba_signature: bool,
+    pub(crate) is_template: bool,
     pub(crate) num_worksheets: u16,
     pub(crate) num_drawings: u16,
     pub(crate) num_charts: u16,
     pub(crate) num_tables: u16,
+    pub(crate) num_named_sheet_views: u16,
+    pub(crate) num_comments: u16,
+    pub(crate) num_custom_xml_parts: u16,
     pub(crate) doc_security: u8,
     pub(crate) worksheet_names: Vec<String>,
     pub(crate) defined_names: Vec<String>,
+    pub(crate) app_heading_pairs: Vec<(String, Vec<String>)>,
     pub(crate) image_types: [bool; NUM_IMAGE_FORMATS],
     pub(crate) properties: DocProperties,
     pub(crate) num_embedded_images: u32,
@@ -815,13 +1053,20 @@ impl PackagerOptions {
             has_dynamic_functions: false,
             has_embedded_images: false,
             has_vml: false,
+            has_vba_project: false,
+            has_vba_signature: false,
+            is_template: false,
             num_worksheets: 0,
             num_drawings: 0,
             num_charts: 0,
             num_tables: 0,
+            num_named_sheet_views: 0,
+            num_comments: 0,
+            num_custom_xml_parts: 0,
             doc_security: 0,
             worksheet_names: vec![],
             defined_names: vec![],
+            app_heading_pairs: vec![],
             image_types: [false; NUM_IMAGE_FORMATS],
             properties: DocProperties::new(),
             num_embedded_images: 0,
@@ -0,0 +1,209 @@
+// page_setup - A module for representing workbook default page setup options.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+use crate::warning::Warnings;
+
+/// The `PageSetup` struct represents default printed page options for the
+/// worksheets in a workbook.
+///
+/// `PageSetup` is used with
+/// [`Workbook::set_default_page_setup()`](crate::Workbook::set_default_page_setup)
+/// to set print options such as the paper size, orientation, margins and
+/// header/footer for every worksheet in a workbook. Each option is only
+/// applied to worksheets that haven't set the same option via the equivalent
+/// worksheet method, such as
+/// [`Worksheet::set_paper_size()`](crate::Worksheet::set_paper_size).
+///
+/// Options that aren't set in the `PageSetup` leave the worksheet values
+/// unchanged.
+///
+/// # Examples
+///
+/// The following example demonstrates setting default page setup options for
+/// all the worksheets in a workbook.
+///
+/// ```
+/// # // This code is available in examples/doc_workbook_set_default_page_setup.rs
+/// #
+/// use rust_xlsxwriter::{PageSetup, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///
+///     // Create a common page setup for the worksheets.
+///     let page_setup = PageSetup::new()
+///         .set_paper_size(9)
+///         .set_landscape()
+///         .set_margins(0.5, 0.5, 0.75, 0.75, 0.3, 0.3)
+///         .set_header("&CQuarterly Report")
+///         .set_footer("&CPage &P of &N");
+///
+///     workbook.set_default_page_setup(&page_setup);
+///
+///     workbook.add_worksheet();
+///     workbook.add_worksheet();
+///
+///     // Override the default orientation for one worksheet.
+///     let worksheet = workbook.add_worksheet();
+///     worksheet.set_portrait();
+///
+///     workbook.save("page_setup.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageSetup {
+    pub(crate) paper_size: Option<u8>,
+    pub(crate) portrait: Option<bool>,
+    pub(crate) margins: [Option<f64>; 6],
+    pub(crate) header: Option<String>,
+    pub(crate) footer: Option<String>,
+    pub(crate) warnings: Warnings,
+}
+
+impl PageSetup {
+    /// Create a new `PageSetup` object to represent default print options.
+    ///
+    pub fn new() -> PageSetup {
+        PageSetup::default()
+    }
+
+    /// Set the default paper type/size for the printed output.
+    ///
+    /// See [`Worksheet::set_paper_size()`](crate::Worksheet::set_paper_size)
+    /// for the list of paper size indices.
+    ///
+    /// # Parameters
+    ///
+    /// * `paper_size` - The paper size index.
+    ///
+    pub fn set_paper_size(mut self, paper_size: u8) -> PageSetup {
+        self.paper_size = Some(paper_size);
+        self
+    }
+
+    /// Set the default page orientation to landscape.
+    ///
+    pub fn set_landscape(mut self) -> PageSetup {
+        self.portrait = Some(false);
+        self
+    }
+
+    /// Set the default page orientation to portrait.
+    ///
+    pub fn set_portrait(mut self) -> PageSetup {
+        self.portrait = Some(true);
+        self
+    }
+
+    /// Set the default page margins.
+    ///
+    /// The units are in inches. Specifying `-1.0` for any parameter leaves the
+    /// corresponding worksheet margin unchanged. See
+    /// [`Worksheet::set_margins()`](crate::Worksheet::set_margins) for more
+    /// details.
+    ///
+    /// # Parameters
+    ///
+    /// * `left` - Left margin in inches. Excel default is 0.7.
+    /// * `right` - Right margin in inches. Excel default is 0.7.
+    /// * `top` - Top margin in inches. Excel default is 0.75.
+    /// * `bottom` - Bottom margin in inches. Excel default is 0.75.
+    /// * `header` - Header margin in inches. Excel default is 0.3.
+    /// * `footer` - Footer margin in inches. Excel default is 0.3.
+    ///
+    pub fn set_margins(
+        mut self,
+        left: f64,
+        right: f64,
+        top: f64,
+        bottom: f64,
+        header: f64,
+        footer: f64,
+    ) -> PageSetup {
+        let margins = [left, right, top, bottom, header, footer];
+
+        for (default, margin) in self.margins.iter_mut().zip(margins) {
+            if margin >= 0.0 {
+                *default = Some(margin);
+            }
+        }
+
+        self
+    }
+
+    /// Set the default printed page header caption.
+    ///
+    /// See [`Worksheet::set_header()`](crate::Worksheet::set_header) for
+    /// details on the syntax of the header string. Header images aren't
+    /// supported in the default header.
+    ///
+    /// # Parameters
+    ///
+    /// * `header` - The header string with optional control characters.
+    ///
+    pub fn set_header(mut self, header: impl Into<String>) -> PageSetup {
+        let header = header.into();
+
+        if expanded_length(&header) > 255 {
+            self.warnings.warn(
+                "set_header",
+                "Header string exceeds Excel's limit of 255 characters.",
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_header");
+        self.header = Some(header);
+        self
+    }
+
+    /// Set the default printed page footer caption.
+    ///
+    /// See [`Worksheet::set_header()`](crate::Worksheet::set_header) for
+    /// details on the syntax of the footer string. Footer images aren't
+    /// supported in the default footer.
+    ///
+    /// # Parameters
+    ///
+    /// * `footer` - The footer string with optional control characters.
+    ///
+    pub fn set_footer(mut self, footer: impl Into<String>) -> PageSetup {
+        let footer = footer.into();
+
+        if expanded_length(&footer) > 255 {
+            self.warnings.warn(
+                "set_footer",
+                "Footer string exceeds Excel's limit of 255 characters.",
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_footer");
+        self.footer = Some(footer);
+        self
+    }
+}
+
+// Get the length of a header/footer string after the user friendly control
+// characters are expanded to the Excel codes.
+fn expanded_length(string: &str) -> usize {
+    string
+        .replace("&[Tab]", "&A")
+        .replace("&[Date]", "&D")
+        .replace("&[File]", "&F")
+        .replace("&[Page]", "&P")
+        .replace("&[Path]", "&Z")
+        .replace("&[Time]", "&T")
+        .replace("&[Pages]", "&N")
+        .replace("&[Picture]", "&G")
+        .chars()
+        .count()
+}
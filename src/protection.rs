@@ -6,6 +6,10 @@
 
 #![warn(missing_docs)]
 
+use sha2::{Digest, Sha512};
+
+use crate::{utility, XlsxError};
+
 /// The `ProtectionOptions` struct is use to set protected elements in a worksheet.
 ///
 /// You can specify which worksheet elements protection should be on or off via
@@ -151,3 +155,58 @@ impl ProtectionOptions {
         }
     }
 }
+
+// The `PasswordHash` struct represents a password hashed with the SHA-512
+// based algorithm used by Excel 2013+ for worksheet and workbook protection.
+// This replaces the legacy 16 bit hash with an iterated and salted hash. See
+// ECMA-376-1:2016, §18.2.29 and §18.3.1.85.
+#[derive(Clone)]
+pub(crate) struct PasswordHash {
+    pub(crate) hash_value: String,
+    pub(crate) salt_value: String,
+    pub(crate) spin_count: u32,
+}
+
+impl PasswordHash {
+    // The number of hash iterations used by Excel.
+    pub(crate) const SPIN_COUNT: u32 = 100_000;
+
+    // Hash a password with a new random salt.
+    pub(crate) fn new(password: &str) -> Result<PasswordHash, XlsxError> {
+        let salt = Self::random_salt()?;
+
+        Ok(Self::new_with_salt(password, &salt))
+    }
+
+    // Hash a password with a user supplied salt. The password is hashed as
+    // UTF-16LE text, which is the encoding Excel uses.
+    pub(crate) fn new_with_salt(password: &str, salt: &[u8]) -> PasswordHash {
+        let mut hasher = Sha512::new();
+        hasher.update(salt);
+        for code_unit in password.encode_utf16() {
+            hasher.update(code_unit.to_le_bytes());
+        }
+        let mut hash = hasher.finalize();
+
+        for iteration in 0..Self::SPIN_COUNT {
+            let mut hasher = Sha512::new();
+            hasher.update(hash);
+            hasher.update(iteration.to_le_bytes());
+            hash = hasher.finalize();
+        }
+
+        PasswordHash {
+            hash_value: utility::base64_encode(&hash),
+            salt_value: utility::base64_encode(salt),
+            spin_count: Self::SPIN_COUNT,
+        }
+    }
+
+    // Generate a 16 byte salt from the operating system's random source.
+    fn random_salt() -> Result<[u8; 16], XlsxError> {
+        let mut salt = [0u8; 16];
+        getrandom::getrandom(&mut salt).map_err(|err| XlsxError::IoError(err.into()))?;
+
+        Ok(salt)
+    }
+}
@@ -0,0 +1,590 @@
+// reader - A module for reading back the cell values from xlsx files.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+mod tests;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+use crate::worksheet::{ColNum, RowNum};
+use crate::XlsxError;
+
+/// Read the sheet names and cell values from an xlsx file.
+///
+/// The `read_workbook()` function is a lightweight reader for the sheet names
+/// and cell values in an xlsx file. It is intended for round-trip testing of
+/// files created by `rust_xlsxwriter`, for example in integration tests, and
+/// for downstream tools that need to verify the generated output.
+///
+/// It is not a general purpose xlsx reader. It only reads the cell values and
+/// their types, see [`CellValue`], and ignores formatting, charts, images and
+/// other worksheet objects. Dates are returned as numbers since Excel stores
+/// them as numbers with a date format.
+///
+/// See also [`read_workbook_from_buffer()`] to read a file from memory.
+///
+/// # Parameters
+///
+/// * `path` - The path of the xlsx file to read.
+///
+/// # Errors
+///
+/// * [`XlsxError::IoError`] - A file read error.
+/// * [`XlsxError::ZipError`] - The file isn't a valid zip/xlsx container.
+/// * [`XlsxError::ReadError`] - A required part of the xlsx file is missing
+///   or invalid.
+///
+/// # Examples
+///
+/// The following example demonstrates reading back the values from a
+/// generated xlsx file.
+///
+/// ```
+/// # // This code is available in examples/doc_read_workbook.rs
+/// #
+/// use rust_xlsxwriter::{read_workbook, CellValue, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///     let worksheet = workbook.add_worksheet();
+///
+///     worksheet.write(0, 0, "Hello")?;
+///     worksheet.write(1, 0, 123)?;
+///     worksheet.write(2, 0, true)?;
+///
+///     workbook.save("workbook.xlsx")?;
+///
+///     // Read back the values to verify them.
+///     let values = read_workbook("workbook.xlsx")?;
+///
+///     assert_eq!(values.sheet_names(), vec!["Sheet1"]);
+///
+///     let sheet = values.sheet("Sheet1").unwrap();
+///     assert_eq!(sheet.cell(0, 0), Some(&CellValue::String("Hello".to_string())));
+///     assert_eq!(sheet.cell(1, 0), Some(&CellValue::Number(123.0)));
+///     assert_eq!(sheet.cell(2, 0), Some(&CellValue::Boolean(true)));
+///
+///     Ok(())
+/// }
+/// ```
+///
+pub fn read_workbook<P: AsRef<Path>>(path: P) -> Result<WorkbookValues, XlsxError> {
+    let file = File::open(path)?;
+    read_workbook_from_reader(file)
+}
+
+/// Read the sheet names and cell values from an xlsx file in memory.
+///
+/// This function is the same as [`read_workbook()`] except that it reads the
+/// file data from a buffer, such as the output of
+/// [`Workbook::save_to_buffer()`](crate::Workbook::save_to_buffer).
+///
+/// # Parameters
+///
+/// * `buffer` - The xlsx file data.
+///
+/// # Errors
+///
+/// * [`XlsxError::ZipError`] - The data isn't a valid zip/xlsx container.
+/// * [`XlsxError::ReadError`] - A required part of the xlsx file is missing
+///   or invalid.
+///
+pub fn read_workbook_from_buffer(buffer: &[u8]) -> Result<WorkbookValues, XlsxError> {
+    read_workbook_from_reader(Cursor::new(buffer))
+}
+
+/// The sheet names and cell values read from an xlsx file.
+///
+/// `WorkbookValues` is returned by [`read_workbook()`] and
+/// [`read_workbook_from_buffer()`]. The sheets are stored in workbook order.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkbookValues {
+    sheets: Vec<SheetValues>,
+}
+
+impl WorkbookValues {
+    /// Get the names of the sheets in the workbook, in workbook order.
+    pub fn sheet_names(&self) -> Vec<&str> {
+        self.sheets
+            .iter()
+            .map(|sheet| sheet.name.as_str())
+            .collect()
+    }
+
+    /// Get the sheets in the workbook, in workbook order.
+    pub fn sheets(&self) -> &[SheetValues] {
+        &self.sheets
+    }
+
+    /// Get a sheet by name.
+    pub fn sheet(&self, name: &str) -> Option<&SheetValues> {
+        self.sheets.iter().find(|sheet| sheet.name == name)
+    }
+}
+
+/// The cell values read from a sheet in an xlsx file.
+///
+/// Chartsheets, and any other sheets without cell data, have no cell values.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetValues {
+    name: String,
+    cells: BTreeMap<(RowNum, ColNum), CellValue>,
+}
+
+impl SheetValues {
+    /// Get the name of the sheet.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the value of a cell, if it has one.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    ///
+    pub fn cell(&self, row: RowNum, col: ColNum) -> Option<&CellValue> {
+        self.cells.get(&(row, col))
+    }
+
+    /// Get an iterator over the `(row, col, value)` cell data in row and
+    /// column order.
+    pub fn cells(&self) -> impl Iterator<Item = (RowNum, ColNum, &CellValue)> {
+        self.cells
+            .iter()
+            .map(|((row, col), value)| (*row, *col, value))
+    }
+}
+
+/// The value and type of a cell read from an xlsx file.
+///
+/// Formatted blank cells aren't included in the cell data since they don't
+/// have a value.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    /// A number, including dates and times.
+    Number(f64),
+
+    /// A string. Rich strings are returned as the unformatted text.
+    String(String),
+
+    /// A boolean value.
+    Boolean(bool),
+
+    /// An error value such as `#DIV/0!`.
+    Error(String),
+
+    /// A formula and its stored result. The formula doesn't have a leading
+    /// `=` and the result is `None` if it wasn't stored in the file.
+    Formula {
+        /// The formula string. This is empty for cells that use the shared
+        /// formula of another cell.
+        formula: String,
+
+        /// The stored result of the formula.
+        result: Option<Box<CellValue>>,
+    },
+}
+
+// -----------------------------------------------------------------------
+// Helper functions.
+// -----------------------------------------------------------------------
+
+// Read the workbook data from a zip container.
+fn read_workbook_from_reader<R: Read + Seek>(reader: R) -> Result<WorkbookValues, XlsxError> {
+    let mut zip = zip::ZipArchive::new(reader)?;
+
+    let workbook_xml = read_zip_part(&mut zip, "xl/workbook.xml")?
+        .ok_or_else(|| XlsxError::ReadError("missing xl/workbook.xml".to_string()))?;
+    let rels_xml = read_zip_part(&mut zip, "xl/_rels/workbook.xml.rels")?.unwrap_or_default();
+    let shared_strings = match read_zip_part(&mut zip, "xl/sharedStrings.xml")? {
+        Some(xml) => read_shared_strings(&xml),
+        None => vec![],
+    };
+
+    // Map the relationship ids to the sheet file paths.
+    let mut targets = HashMap::new();
+    for tag in XmlTags::new(&rels_xml) {
+        if let XmlTag::Start { name, attributes } = tag {
+            if name == "Relationship" {
+                if let (Some(id), Some(target)) = (attributes.get("Id"), attributes.get("Target")) {
+                    targets.insert(id.clone(), part_path(target));
+                }
+            }
+        }
+    }
+
+    let mut sheets = vec![];
+    for tag in XmlTags::new(&workbook_xml) {
+        let XmlTag::Start { name, attributes } = tag else {
+            continue;
+        };
+        if name != "sheet" {
+            continue;
+        }
+
+        let sheet_name = attributes.get("name").cloned().unwrap_or_default();
+        let mut cells = BTreeMap::new();
+
+        if let Some(path) = attributes.get("r:id").and_then(|id| targets.get(id)) {
+            if let Some(sheet_xml) = read_zip_part(&mut zip, path)? {
+                cells = read_cells(&sheet_xml, &shared_strings)?;
+            }
+        }
+
+        sheets.push(SheetValues {
+            name: sheet_name,
+            cells,
+        });
+    }
+
+    Ok(WorkbookValues { sheets })
+}
+
+// Read a part of the zip container as a string, if it exists.
+fn read_zip_part<R: Read + Seek>(
+    zip: &mut zip::ZipArchive<R>,
+    path: &str,
+) -> Result<Option<String>, XlsxError> {
+    let mut file = match zip.by_name(path) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+
+    let mut xml = String::new();
+    file.read_to_string(&mut xml)?;
+
+    Ok(Some(xml))
+}
+
+// Convert a relationship target to a path in the zip container. Targets are
+// usually relative to the "xl" directory but they can also be absolute.
+fn part_path(target: &str) -> String {
+    match target.strip_prefix('/') {
+        Some(path) => path.to_string(),
+        None => format!("xl/{target}"),
+    }
+}
+
+// Read the strings from the shared strings table. Rich strings are converted
+// to the unformatted text of their runs.
+fn read_shared_strings(xml: &str) -> Vec<String> {
+    let mut strings = vec![];
+    let mut string = String::new();
+    let mut in_text = false;
+    let mut in_phonetic = false;
+
+    for tag in XmlTags::new(xml) {
+        match tag {
+            XmlTag::Start { name: "si", .. } => string.clear(),
+            XmlTag::End { name: "si" } => strings.push(string.clone()),
+            XmlTag::Start { name: "rPh", .. } => in_phonetic = true,
+            XmlTag::End { name: "rPh" } => in_phonetic = false,
+            XmlTag::Start { name: "t", .. } => in_text = !in_phonetic,
+            XmlTag::End { name: "t" } => in_text = false,
+            XmlTag::Text(text) if in_text => string.push_str(&unescape_xml(text, true)),
+            _ => {}
+        }
+    }
+
+    strings
+}
+
+// Read the cell values from a worksheet.
+fn read_cells(
+    xml: &str,
+    shared_strings: &[String],
+) -> Result<BTreeMap<(RowNum, ColNum), CellValue>, XlsxError> {
+    let mut cells = BTreeMap::new();
+
+    let mut cell: Option<(RowNum, ColNum, String)> = None;
+    let mut value: Option<String> = None;
+    let mut formula: Option<String> = None;
+    let mut inline_string = String::new();
+    let mut element = "";
+
+    for tag in XmlTags::new(xml) {
+        match tag {
+            XmlTag::Start {
+                name: "c",
+                attributes,
+            } => {
+                let reference = attributes.get("r").map(String::as_str).unwrap_or_default();
+                let (row, col) = parse_cell_reference(reference).ok_or_else(|| {
+                    XlsxError::ReadError(format!("invalid cell reference '{reference}'"))
+                })?;
+                let cell_type = attributes.get("t").cloned().unwrap_or_default();
+
+                cell = Some((row, col, cell_type));
+                value = None;
+                formula = None;
+                inline_string.clear();
+            }
+            XmlTag::Start { name, .. } if cell.is_some() => {
+                element = match name {
+                    "v" => "v",
+                    "f" => {
+                        formula.get_or_insert_with(String::new);
+                        "f"
+                    }
+                    "t" => "t",
+                    _ => element,
+                };
+            }
+            XmlTag::Text(text) => {
+                let text = unescape_xml(text, true);
+                match element {
+                    "v" => value.get_or_insert_with(String::new).push_str(&text),
+                    "f" => formula.get_or_insert_with(String::new).push_str(&text),
+                    "t" => inline_string.push_str(&text),
+                    _ => {}
+                }
+            }
+            XmlTag::End { name: "c" } => {
+                if let Some((row, col, cell_type)) = cell.take() {
+                    let cell_value = match cell_type.as_str() {
+                        "inlineStr" => Some(CellValue::String(inline_string.clone())),
+                        cell_type => value
+                            .take()
+                            .and_then(|value| convert_value(cell_type, &value, shared_strings)),
+                    };
+
+                    let cell_value = match formula.take() {
+                        Some(formula) if !formula.is_empty() || cell_value.is_some() => {
+                            Some(CellValue::Formula {
+                                formula,
+                                result: cell_value.map(Box::new),
+                            })
+                        }
+                        _ => cell_value,
+                    };
+
+                    if let Some(cell_value) = cell_value {
+                        cells.insert((row, col), cell_value);
+                    }
+                }
+                element = "";
+            }
+            XmlTag::End { .. } => element = "",
+            _ => {}
+        }
+    }
+
+    Ok(cells)
+}
+
+// Convert a stored <v> value to a cell value based on the cell type.
+fn convert_value(cell_type: &str, value: &str, shared_strings: &[String]) -> Option<CellValue> {
+    match cell_type {
+        "s" => {
+            let index: usize = value.trim().parse().ok()?;
+            shared_strings.get(index).cloned().map(CellValue::String)
+        }
+        "str" => Some(CellValue::String(value.to_string())),
+        "b" => Some(CellValue::Boolean(value.trim() == "1")),
+        "e" => Some(CellValue::Error(value.to_string())),
+        _ => value.trim().parse().ok().map(CellValue::Number),
+    }
+}
+
+// Convert an "A1" style cell reference to zero indexed row and column numbers.
+fn parse_cell_reference(reference: &str) -> Option<(RowNum, ColNum)> {
+    let split = reference.find(|ch: char| ch.is_ascii_digit())?;
+    let (col_name, row_num) = reference.split_at(split);
+
+    if col_name.is_empty()
+        || col_name.len() > 3
+        || !col_name.bytes().all(|b| b.is_ascii_uppercase())
+    {
+        return None;
+    }
+
+    let row: RowNum = row_num.parse().ok()?;
+    let col = col_name
+        .bytes()
+        .fold(0u32, |col, b| col * 26 + u32::from(b - b'A' + 1));
+
+    Some((row.checked_sub(1)?, ColNum::try_from(col - 1).ok()?))
+}
+
+// Unescape the standard XML entities and, for cell text, the Excel _xHHHH_
+// escapes.
+fn unescape_xml(text: &str, excel_escapes: bool) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut remaining = text;
+
+    while let Some(index) = remaining.find(['&', '_']) {
+        unescaped.push_str(&remaining[..index]);
+        remaining = &remaining[index..];
+
+        if remaining.starts_with('&') {
+            if let Some(end) = remaining.find(';') {
+                let entity = &remaining[1..end];
+                let ch = match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    _ => entity
+                        .strip_prefix("#x")
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                        .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                        .and_then(char::from_u32),
+                };
+
+                if let Some(ch) = ch {
+                    unescaped.push(ch);
+                    remaining = &remaining[end + 1..];
+                    continue;
+                }
+            }
+        } else if excel_escapes {
+            let bytes = remaining.as_bytes();
+            if bytes.len() >= 7
+                && bytes[1] == b'x'
+                && bytes[2..6].iter().all(u8::is_ascii_hexdigit)
+                && bytes[6] == b'_'
+            {
+                if let Some(ch) = u32::from_str_radix(&remaining[2..6], 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                {
+                    unescaped.push(ch);
+                    remaining = &remaining[7..];
+                    continue;
+                }
+            }
+        }
+
+        // Not an escape, copy the character.
+        unescaped.push_str(&remaining[..1]);
+        remaining = &remaining[1..];
+    }
+
+    unescaped.push_str(remaining);
+    unescaped
+}
+
+// A minimal XML tag tokenizer for the simple, well-formed XML in xlsx files.
+// It doesn't handle DTDs or CDATA sections, which aren't used in the parts
+// that are read.
+enum XmlTag<'a> {
+    Start {
+        name: &'a str,
+        attributes: HashMap<&'a str, String>,
+    },
+    End {
+        name: &'a str,
+    },
+    Text(&'a str),
+}
+
+struct XmlTags<'a> {
+    xml: &'a str,
+    position: usize,
+    pending_end: Option<&'a str>,
+}
+
+impl<'a> XmlTags<'a> {
+    fn new(xml: &'a str) -> XmlTags<'a> {
+        XmlTags {
+            xml,
+            position: 0,
+            pending_end: None,
+        }
+    }
+}
+
+impl<'a> Iterator for XmlTags<'a> {
+    type Item = XmlTag<'a>;
+
+    fn next(&mut self) -> Option<XmlTag<'a>> {
+        // Empty elements are returned as a start tag and an end tag.
+        if let Some(name) = self.pending_end.take() {
+            return Some(XmlTag::End { name });
+        }
+
+        loop {
+            let remaining = &self.xml[self.position..];
+            if remaining.is_empty() {
+                return None;
+            }
+
+            if !remaining.starts_with('<') {
+                let end = remaining.find('<').unwrap_or(remaining.len());
+                self.position += end;
+                return Some(XmlTag::Text(&remaining[..end]));
+            }
+
+            let end = remaining.find('>')?;
+            let tag = &remaining[1..end];
+            self.position += end + 1;
+
+            // Skip declarations, processing instructions and comments.
+            if tag.starts_with('?') || tag.starts_with('!') {
+                continue;
+            }
+
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(XmlTag::End {
+                    name: local_name(name.trim()),
+                });
+            }
+
+            let (tag, is_empty) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let name = local_name(&tag[..name_end]);
+            let attributes = parse_attributes(&tag[name_end..]);
+
+            if is_empty {
+                self.pending_end = Some(name);
+            }
+
+            return Some(XmlTag::Start { name, attributes });
+        }
+    }
+}
+
+// Strip any namespace prefix, such as "x:", from an element name.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+// Parse the name="value" attributes of a tag.
+fn parse_attributes(mut attributes: &str) -> HashMap<&str, String> {
+    let mut parsed = HashMap::new();
+
+    while let Some(equals) = attributes.find('=') {
+        let name = attributes[..equals].trim();
+        let rest = attributes[equals + 1..].trim_start();
+
+        let Some(quote) = rest.chars().next().filter(|ch| *ch == '"' || *ch == '\'') else {
+            break;
+        };
+        let Some(end) = rest[1..].find(quote) else {
+            break;
+        };
+
+        parsed.insert(name, unescape_xml(&rest[1..=end], false));
+        attributes = &rest[end + 2..];
+    }
+
+    parsed
+}
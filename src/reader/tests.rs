@@ -0,0 +1,148 @@
+// Reader unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod reader_tests {
+
+    use crate::reader::{parse_cell_reference, unescape_xml};
+    use crate::{
+        read_workbook_from_buffer, CellValue, Color, Format, Formula, Workbook, XlsxError,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_read_workbook_values() -> Result<(), XlsxError> {
+        let mut workbook = Workbook::new();
+        let bold = Format::new().set_bold();
+        let red = Format::new().set_font_color(Color::Red);
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.write(0, 0, "Hello")?;
+        worksheet.write(0, 1, 1.5)?;
+        worksheet.write(0, 2, false)?;
+        worksheet.write(1, 0, " a & b <c> ")?;
+        worksheet.write(1, 1, "\x01_x0000_")?;
+        worksheet.write_rich_string(2, 0, &[(&bold, "Bold"), (&red, " red")])?;
+        worksheet.write_rich_string(2, 1, &[(&bold, "_x0041_"), (&red, "\x02")])?;
+        worksheet.write_formula(3, 0, Formula::new("=SUM(1,2)").set_result("3"))?;
+        worksheet.write_formula(3, 1, "=A1")?;
+        worksheet.write_blank(4, 0, &bold)?;
+
+        let worksheet = workbook.add_worksheet().set_name("Data & Notes")?;
+        worksheet.write(9, 27, 42)?;
+
+        let buffer = workbook.save_to_buffer()?;
+        let values = read_workbook_from_buffer(&buffer)?;
+
+        assert_eq!(values.sheet_names(), vec!["Sheet1", "Data & Notes"]);
+
+        let sheet = values.sheet("Sheet1").unwrap();
+        assert_eq!(
+            sheet.cell(0, 0),
+            Some(&CellValue::String("Hello".to_string()))
+        );
+        assert_eq!(sheet.cell(0, 1), Some(&CellValue::Number(1.5)));
+        assert_eq!(sheet.cell(0, 2), Some(&CellValue::Boolean(false)));
+        assert_eq!(
+            sheet.cell(1, 0),
+            Some(&CellValue::String(" a & b <c> ".to_string()))
+        );
+        assert_eq!(
+            sheet.cell(1, 1),
+            Some(&CellValue::String("\x01_x0000_".to_string()))
+        );
+        assert_eq!(
+            sheet.cell(2, 0),
+            Some(&CellValue::String("Bold red".to_string()))
+        );
+        assert_eq!(
+            sheet.cell(2, 1),
+            Some(&CellValue::String("_x0041_\x02".to_string()))
+        );
+        assert_eq!(
+            sheet.cell(3, 0),
+            Some(&CellValue::Formula {
+                formula: "SUM(1,2)".to_string(),
+                result: Some(Box::new(CellValue::Number(3.0))),
+            })
+        );
+        assert_eq!(
+            sheet.cell(3, 1),
+            Some(&CellValue::Formula {
+                formula: "A1".to_string(),
+                result: Some(Box::new(CellValue::Number(0.0))),
+            })
+        );
+        assert_eq!(sheet.cell(4, 0), None);
+        assert_eq!(sheet.cells().count(), 9);
+
+        let sheet = values.sheet("Data & Notes").unwrap();
+        assert_eq!(
+            sheet.cells().collect::<Vec<_>>(),
+            vec![(9, 27, &CellValue::Number(42.0))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_workbook_inline_strings() -> Result<(), XlsxError> {
+        let mut workbook = Workbook::new();
+        workbook.use_inline_strings(true);
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.write(0, 0, "Inline")?;
+        worksheet.write(1, 0, "  space ")?;
+
+        let buffer = workbook.save_to_buffer()?;
+        let values = read_workbook_from_buffer(&buffer)?;
+
+        let sheet = &values.sheets()[0];
+        assert_eq!(
+            sheet.cell(0, 0),
+            Some(&CellValue::String("Inline".to_string()))
+        );
+        assert_eq!(
+            sheet.cell(1, 0),
+            Some(&CellValue::String("  space ".to_string()))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_workbook_invalid() {
+        let result = read_workbook_from_buffer(b"not a zip file");
+        assert!(matches!(result, Err(XlsxError::ZipError(_))));
+    }
+
+    #[test]
+    fn test_parse_cell_reference() {
+        let tests = [
+            ("A1", Some((0, 0))),
+            ("B2", Some((1, 1))),
+            ("AA10", Some((9, 26))),
+            ("XFD1048576", Some((1_048_575, 16383))),
+            ("A0", None),
+            ("1A", None),
+            ("a1", None),
+            ("", None),
+        ];
+
+        for (reference, expected) in tests {
+            assert_eq!(expected, parse_cell_reference(reference));
+        }
+    }
+
+    #[test]
+    fn test_unescape_xml() {
+        assert_eq!("a & b < c", unescape_xml("a &amp; b &lt; c", true));
+        assert_eq!("\"A\"\n", unescape_xml("&quot;&#x41;&quot;&#10;", false));
+        assert_eq!("\x01_x0000_", unescape_xml("_x0001__x005F_x0000_", true));
+        assert_eq!("_x0001_", unescape_xml("_x0001_", false));
+        assert_eq!("& x_y", unescape_xml("& x_y", true));
+    }
+}
@@ -6,8 +6,10 @@
 
 mod tests;
 
+use std::io::{self, Write};
+
 use crate::shared_strings_table::SharedStringsTable;
-use crate::xmlwriter::XMLWriter;
+use crate::xmlwriter::{XMLWriter, XML_WRITE_ERROR};
 
 pub struct SharedStrings {
     pub(crate) writer: XMLWriter,
@@ -29,18 +31,43 @@ impl SharedStrings {
     // XML assembly methods.
     // -----------------------------------------------------------------------
 
-    // Assemble and write the XML file.
+    // Assemble and write the XML file to the in-memory buffer. This is mainly
+    // used for testing since the file is streamed to the output when saving.
+    #[allow(dead_code)]
     pub(crate) fn assemble_xml_file(&mut self, string_table: &SharedStringsTable) {
+        self.assemble_xml(string_table, None)
+            .expect(XML_WRITE_ERROR);
+    }
+
+    // Assemble the XML file and stream it to an output writer in chunks so
+    // that large string tables aren't held in memory twice.
+    pub(crate) fn assemble_xml_file_to(
+        &mut self,
+        string_table: &SharedStringsTable,
+        writer: &mut dyn Write,
+    ) -> io::Result<()> {
+        self.assemble_xml(string_table, Some(&mut *writer))?;
+        self.writer.flush_to(writer)
+    }
+
+    // Assemble the XML file with optional streaming of the strings.
+    fn assemble_xml(
+        &mut self,
+        string_table: &SharedStringsTable,
+        mut writer: Option<&mut dyn Write>,
+    ) -> io::Result<()> {
         self.writer.xml_declaration();
 
         // Write the sst element.
         self.write_sst(string_table);
 
         // Write the sst strings.
-        self.write_sst_strings(string_table);
+        self.write_sst_strings(string_table, &mut writer)?;
 
         // Close the sst tag.
         self.writer.xml_end_tag("sst");
+
+        Ok(())
     }
 
     // Write the <sst> element.
@@ -54,12 +81,12 @@ impl SharedStrings {
     }
 
     // Write the sst string elements.
-    #[allow(clippy::from_iter_instead_of_collect)] // from_iter() is faster than collect() here.
-    fn write_sst_strings(&mut self, string_table: &SharedStringsTable) {
-        let mut insertion_order_strings = Vec::from_iter(string_table.strings.iter());
-        insertion_order_strings.sort_by_key(|x| x.1);
-
-        for (string, _) in insertion_order_strings {
+    fn write_sst_strings(
+        &mut self,
+        string_table: &SharedStringsTable,
+        writer: &mut Option<&mut dyn Write>,
+    ) -> io::Result<()> {
+        for string in &string_table.strings {
             let preserve_whitespace =
                 string.starts_with(['\t', '\n', ' ']) || string.ends_with(['\t', '\n', ' ']);
 
@@ -68,6 +95,13 @@ impl SharedStrings {
             } else {
                 self.writer.xml_si_element(string, preserve_whitespace);
             }
+
+            // Flush the string data to the output writer, if required.
+            if let Some(writer) = writer {
+                self.writer.flush_if_full(*writer)?;
+            }
         }
+
+        Ok(())
     }
 }
@@ -85,4 +85,26 @@ mod shared_strings_tests {
 
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_shared_string_table_streamed() {
+        let mut string_table = SharedStringsTable::new();
+        for i in 0..10_000 {
+            string_table.shared_string_index(format!("String {i}").into());
+        }
+
+        let mut shared_strings = SharedStrings::new();
+        shared_strings.assemble_xml_file(&string_table);
+        let expected = shared_strings.writer.read_to_string();
+
+        // The strings are large enough to be flushed in more than one chunk.
+        let mut shared_strings = SharedStrings::new();
+        let mut streamed = vec![];
+        shared_strings
+            .assemble_xml_file_to(&string_table, &mut streamed)
+            .unwrap();
+        assert!(streamed.len() > crate::xmlwriter::XML_FLUSH_THRESHOLD);
+
+        assert_eq!(expected, String::from_utf8(streamed).unwrap());
+    }
 }
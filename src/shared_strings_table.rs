@@ -10,15 +10,22 @@
 
 mod tests;
 
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
 
 //
-// A metadata struct to store Excel unique strings between worksheets.
+// A metadata struct to store Excel unique strings between worksheets. The
+// strings are stored in insertion order, which is the order they are written
+// to the file, with a map of the same shared Arc<str> keys to their index. The
+// map uses a fast non-cryptographic hasher since string insertion is usually
+// the dominant cost of saving text heavy worksheets.
 //
 pub struct SharedStringsTable {
     pub count: u32,
     pub unique_count: u32,
-    pub strings: HashMap<Arc<str>, u32>,
+    pub strings: Vec<Arc<str>>,
+    indices: FxHashMap<Arc<str>, u32>,
 }
 
 impl SharedStringsTable {
@@ -31,18 +38,35 @@ impl SharedStringsTable {
         SharedStringsTable {
             count: 0,
             unique_count: 0,
-            strings: HashMap::new(),
+            strings: vec![],
+            indices: FxHashMap::default(),
+        }
+    }
+
+    // Create a new string table with space for at least `capacity` unique
+    // strings.
+    pub(crate) fn with_capacity(capacity: usize) -> SharedStringsTable {
+        SharedStringsTable {
+            count: 0,
+            unique_count: 0,
+            strings: Vec::with_capacity(capacity),
+            indices: FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
         }
     }
 
     // Get the index of the string in the Shared String table.
     pub(crate) fn shared_string_index(&mut self, key: Arc<str>) -> u32 {
-        let index = *self.strings.entry(key).or_insert_with(|| {
-            let index = self.unique_count;
-            self.unique_count += 1;
-            index
-        });
         self.count += 1;
+
+        if let Some(index) = self.indices.get(&key) {
+            return *index;
+        }
+
+        let index = self.unique_count;
+        self.unique_count += 1;
+        self.strings.push(Arc::clone(&key));
+        self.indices.insert(key, index);
+
         index
     }
 }
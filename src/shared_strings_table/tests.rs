@@ -34,5 +34,27 @@ mod shared_strings_table_tests {
 
         let index = string_table.shared_string_index("venus".into());
         assert_eq!(index, 2);
+
+        assert_eq!(string_table.count, 7);
+        assert_eq!(string_table.unique_count, 3);
+        assert_eq!(
+            string_table.strings,
+            vec!["neptune".into(), "mars".into(), "venus".into()]
+        );
+    }
+
+    #[test]
+    fn test_shared_string_table_with_capacity() {
+        let mut string_table = SharedStringsTable::with_capacity(100);
+        assert!(string_table.strings.capacity() >= 100);
+
+        for i in 0..200 {
+            let index = string_table.shared_string_index(format!("{}", i % 150).into());
+            assert_eq!(index, i % 150);
+        }
+
+        assert_eq!(string_table.count, 200);
+        assert_eq!(string_table.unique_count, 150);
+        assert_eq!(string_table.strings.len(), 150);
     }
 }
@@ -425,7 +425,8 @@
 #![warn(missing_docs)]
 
 use crate::{
-    utility, ChartEmptyCells, ChartRange, ColNum, Color, IntoChartRange, IntoColor, RowNum,
+    utility, warning::Warnings, ChartEmptyCells, ChartRange, ColNum, Color, IntoChartRange,
+    IntoColor, RowNum,
 };
 
 mod tests;
@@ -530,6 +531,7 @@ pub struct Sparkline {
     pub(crate) group_max: bool,
     pub(crate) group_min: bool,
     data_row_order: bool,
+    pub(crate) warnings: Warnings,
 }
 
 #[allow(clippy::new_without_default)]
@@ -565,6 +567,7 @@ impl Sparkline {
             group_max: false,
             group_min: false,
             data_row_order: true,
+            warnings: Warnings::new(),
         }
     }
 
@@ -619,6 +622,50 @@ impl Sparkline {
     ///
     /// * `sparkline_type` - A [`SparklineType`] value.
     ///
+    /// # Examples
+    ///
+    /// Example of adding column and win/lose sparklines to a worksheet, with
+    /// markers for the highest, lowest and negative points.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_sparkline_set_type.rs
+    /// #
+    /// # use rust_xlsxwriter::{Sparkline, SparklineType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Add some sample data to plot.
+    ///     worksheet.write_row(0, 0, [-2, 2, 3, -1, 0])?;
+    ///     worksheet.write_row(1, 0, [1, -1, -1, 1, -1])?;
+    ///
+    ///     // Create a column sparkline with high and low point markers.
+    ///     let sparkline1 = Sparkline::new()
+    ///         .set_range(("Sheet1", 0, 0, 0, 4))
+    ///         .set_type(SparklineType::Column)
+    ///         .show_high_point(true)
+    ///         .show_low_point(true);
+    ///
+    ///     // Create a win/lose sparkline with negative points highlighted.
+    ///     let sparkline2 = Sparkline::new()
+    ///         .set_range(("Sheet1", 1, 0, 1, 4))
+    ///         .set_type(SparklineType::WinLose)
+    ///         .show_negative_points(true)
+    ///         .set_negative_points_color("#FF0000");
+    ///
+    ///     // Add them to the worksheet.
+    ///     worksheet.add_sparkline(0, 5, &sparkline1)?;
+    ///     worksheet.add_sparkline(1, 5, &sparkline2)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("sparkline.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
     pub fn set_type(mut self, sparkline_type: SparklineType) -> Sparkline {
         self.sparkline_type = sparkline_type;
         self
@@ -812,7 +859,7 @@ impl Sparkline {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_sparkline_color", color) {
             self.series_color = color;
         }
         self
@@ -830,7 +877,7 @@ impl Sparkline {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_high_point_color", color) {
             self.high_point_color = color;
             self.show_high_point = true;
         }
@@ -849,7 +896,7 @@ impl Sparkline {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_low_point_color", color) {
             self.low_point_color = color;
             self.show_low_point = true;
         }
@@ -868,7 +915,7 @@ impl Sparkline {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_first_point_color", color) {
             self.first_point_color = color;
             self.show_first_point = true;
         }
@@ -887,7 +934,7 @@ impl Sparkline {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_last_point_color", color) {
             self.last_point_color = color;
             self.show_last_point = true;
         }
@@ -906,7 +953,10 @@ impl Sparkline {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self
+            .warnings
+            .check_color("set_negative_points_color", color)
+        {
             self.negative_points_color = color;
             self.show_negative_points = true;
         }
@@ -925,7 +975,7 @@ impl Sparkline {
         T: IntoColor,
     {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_markers_color", color) {
             self.markers_color = color;
             self.show_markers = true;
         }
@@ -1399,9 +1449,16 @@ impl Sparkline {
                 self.negative_points_color = Color::Theme(9, 0);
                 self.series_color = Color::Theme(1, 0);
             }
-            _ => eprintln!("Sparkline style '{style}' outside the Excel range 1-36."),
+            _ => {
+                self.warnings.warn(
+                    "set_style",
+                    format!("Sparkline style '{style}' outside the Excel range 1-36."),
+                );
+                return self;
+            }
         };
 
+        self.warnings.clear("set_style");
         self
     }
 
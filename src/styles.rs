@@ -10,7 +10,7 @@ use crate::format::Format;
 use crate::xmlwriter::XMLWriter;
 use crate::{
     Alignment, Border, Color, Fill, Font, FormatAlign, FormatBorder, FormatDiagonalBorder,
-    FormatPattern, FormatScript, FormatUnderline,
+    FormatGradient, FormatGradientType, FormatPattern, FormatScript, FormatUnderline,
 };
 
 pub struct Styles<'a> {
@@ -319,6 +319,13 @@ impl<'a> Styles<'a> {
 
     // Write the user defined <fill> element.
     fn write_fill(&mut self, fill: &Fill, dxf_format: bool) {
+        if let Some(gradient) = &fill.gradient {
+            self.writer.xml_start_tag_only("fill");
+            self.write_gradient_fill(gradient);
+            self.writer.xml_end_tag("fill");
+            return;
+        }
+
         // Special handling for pattern only case.
         if fill.pattern != FormatPattern::None
             && (fill.background_color == Color::Default
@@ -363,6 +370,40 @@ impl<'a> Styles<'a> {
         self.writer.xml_end_tag("fill");
     }
 
+    // Write the <gradientFill> element.
+    fn write_gradient_fill(&mut self, gradient: &FormatGradient) {
+        let mut attributes = vec![];
+
+        match gradient.gradient_type {
+            FormatGradientType::Linear => {
+                if gradient.angle != 0 {
+                    attributes.push(("degree", gradient.angle.to_string()));
+                }
+            }
+            FormatGradientType::Path => {
+                attributes.push(("type", "path".to_string()));
+                attributes.push(("left", "0.5".to_string()));
+                attributes.push(("right", "0.5".to_string()));
+                attributes.push(("top", "0.5".to_string()));
+                attributes.push(("bottom", "0.5".to_string()));
+            }
+        }
+
+        self.writer.xml_start_tag("gradientFill", &attributes);
+
+        for gradient_stop in &gradient.gradient_stops {
+            let position = f64::from(gradient_stop.position) / 100.0;
+            let attributes = [("position", position.to_string())];
+
+            self.writer.xml_start_tag("stop", &attributes);
+            self.writer
+                .xml_empty_tag("color", &gradient_stop.color.attributes());
+            self.writer.xml_end_tag("stop");
+        }
+
+        self.writer.xml_end_tag("gradientFill");
+    }
+
     // Write the <borders> element.
     fn write_borders(&mut self) {
         let attributes = [("count", self.border_count.to_string())];
@@ -689,6 +730,10 @@ impl<'a> Styles<'a> {
             attributes.push(("wrapText", "1".to_string()));
         }
 
+        if alignment.justify_last && horizontal_align == FormatAlign::Distributed {
+            attributes.push(("justifyLastLine", "1".to_string()));
+        }
+
         if shrink {
             attributes.push(("shrinkToFit", "1".to_string()));
         }
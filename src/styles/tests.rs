@@ -9,7 +9,10 @@ mod styles_tests {
 
     use crate::styles::Styles;
     use crate::test_functions::xml_to_vec;
-    use crate::Format;
+    use crate::{
+        Format, FormatAlign, FormatFontScheme, FormatGradient, FormatGradientStop,
+        FormatGradientType, FormatPattern,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -74,4 +77,232 @@ mod styles_tests {
 
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_write_gradient_fill() {
+        let xf_formats = vec![];
+        let dxf_formats = vec![];
+        let mut styles = Styles::new(&xf_formats, &dxf_formats, 1, 2, 1, vec![], false, false);
+
+        let stops = [
+            FormatGradientStop::new("#FFFFFF", 0),
+            FormatGradientStop::new("#4F81BD", 50),
+            FormatGradientStop::new("#C0504D", 100),
+        ];
+
+        let format = Format::new().set_background_color("#FF0000").set_gradient(
+            &FormatGradient::new()
+                .set_angle(90)
+                .set_gradient_stops(&stops),
+        );
+        styles.write_fill(&format.fill, false);
+
+        let format = Format::new().set_gradient(
+            &FormatGradient::new()
+                .set_type(FormatGradientType::Path)
+                .set_gradient_stops(&stops[..2]),
+        );
+        styles.write_fill(&format.fill, false);
+
+        let got = styles.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <fill>
+                  <gradientFill degree="90">
+                    <stop position="0"><color rgb="FFFFFFFF"/></stop>
+                    <stop position="0.5"><color rgb="FF4F81BD"/></stop>
+                    <stop position="1"><color rgb="FFC0504D"/></stop>
+                  </gradientFill>
+                </fill>
+                <fill>
+                  <gradientFill type="path" left="0.5" right="0.5" top="0.5" bottom="0.5">
+                    <stop position="0"><color rgb="FFFFFFFF"/></stop>
+                    <stop position="0.5"><color rgb="FF4F81BD"/></stop>
+                  </gradientFill>
+                </fill>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // Gradients with less than 2 valid stops are ignored.
+        let format = Format::new().set_gradient(
+            &FormatGradient::new().set_gradient_stops(&[FormatGradientStop::new("#FFFFFF", 101)]),
+        );
+        assert!(format.fill.gradient.is_none());
+    }
+
+    #[test]
+    fn test_write_alignment() {
+        let xf_formats = vec![];
+        let dxf_formats = vec![];
+        let mut styles = Styles::new(&xf_formats, &dxf_formats, 1, 2, 1, vec![], false, false);
+
+        let formats = [
+            Format::new().set_align(FormatAlign::JustifyLastLine),
+            Format::new()
+                .set_align(FormatAlign::JustifyLastLine)
+                .set_indent(2),
+            // A later horizontal alignment turns off justifyLastLine.
+            Format::new()
+                .set_align(FormatAlign::JustifyLastLine)
+                .set_align(FormatAlign::Right),
+            // Indentation defaults to left alignment.
+            Format::new().set_align(FormatAlign::Center).set_indent(1),
+            // Indent levels outside Excel's range are ignored.
+            Format::new().set_indent(251),
+        ];
+
+        for format in formats {
+            styles.write_alignment(format.alignment.clone());
+        }
+
+        let got = styles.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <alignment horizontal="distributed" justifyLastLine="1"/>
+                <alignment horizontal="distributed" indent="2" justifyLastLine="1"/>
+                <alignment horizontal="right"/>
+                <alignment horizontal="left" indent="1"/>
+                <alignment/>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_write_alignment_shrink() {
+        let xf_formats = vec![];
+        let dxf_formats = vec![];
+        let mut styles = Styles::new(&xf_formats, &dxf_formats, 1, 2, 1, vec![], false, false);
+
+        let formats = [
+            Format::new().set_shrink(),
+            Format::new().set_shrink().set_align(FormatAlign::Right),
+            // Properties that are mutually exclusive with shrink.
+            Format::new().set_shrink().set_text_wrap(),
+            Format::new().set_shrink().set_align(FormatAlign::Fill),
+            Format::new().set_shrink().set_align(FormatAlign::Justify),
+            Format::new()
+                .set_shrink()
+                .set_align(FormatAlign::Distributed),
+        ];
+
+        for format in formats {
+            styles.write_alignment(format.alignment.clone());
+        }
+
+        let got = styles.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <alignment shrinkToFit="1"/>
+                <alignment horizontal="right" shrinkToFit="1"/>
+                <alignment wrapText="1"/>
+                <alignment horizontal="fill"/>
+                <alignment horizontal="justify"/>
+                <alignment horizontal="distributed"/>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_write_pattern_fill() {
+        let xf_formats = vec![];
+        let dxf_formats = vec![];
+        let mut styles = Styles::new(&xf_formats, &dxf_formats, 1, 2, 1, vec![], false, false);
+
+        let formats = [
+            Format::new()
+                .set_pattern(FormatPattern::DarkTrellis)
+                .set_foreground_color("#FF0000")
+                .set_background_color("#FFFF00"),
+            Format::new()
+                .set_pattern(FormatPattern::LightUp)
+                .set_foreground_color("#0000FF"),
+            // A pattern without colors uses the default fill element.
+            Format::new().set_pattern(FormatPattern::Gray0625),
+        ];
+
+        for format in formats {
+            styles.write_fill(&format.fill, false);
+        }
+
+        let got = styles.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <fill>
+                  <patternFill patternType="darkTrellis">
+                    <fgColor rgb="FFFF0000"/>
+                    <bgColor rgb="FFFFFF00"/>
+                  </patternFill>
+                </fill>
+                <fill>
+                  <patternFill patternType="lightUp">
+                    <fgColor rgb="FF0000FF"/>
+                    <bgColor indexed="64"/>
+                  </patternFill>
+                </fill>
+                <fill>
+                  <patternFill patternType="gray0625"/>
+                </fill>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_write_font_scheme() {
+        let xf_formats = vec![];
+        let dxf_formats = vec![];
+        let mut styles = Styles::new(&xf_formats, &dxf_formats, 1, 2, 1, vec![], false, false);
+
+        let formats = [
+            Format::new().set_font_scheme(FormatFontScheme::Major),
+            Format::new()
+                .set_font_name("MS Gothic")
+                .set_font_family(3)
+                .set_font_charset(128)
+                .set_font_scheme(FormatFontScheme::None),
+        ];
+
+        for format in formats {
+            styles.write_font(&format.font, false);
+        }
+
+        let got = styles.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <font>
+                  <sz val="11"/>
+                  <color theme="1"/>
+                  <name val="Calibri"/>
+                  <family val="2"/>
+                  <scheme val="major"/>
+                </font>
+                <font>
+                  <sz val="11"/>
+                  <color theme="1"/>
+                  <name val="MS Gothic"/>
+                  <family val="3"/>
+                  <charset val="128"/>
+                </font>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
 }
@@ -8,10 +8,11 @@
 
 mod tests;
 
-use std::{collections::HashSet, fmt};
+use std::{collections::HashSet, fmt, str::FromStr};
 
 use crate::{
-    utility::ToXmlBoolean, xmlwriter::XMLWriter, CellRange, Format, Formula, RowNum, XlsxError,
+    utility::ToXmlBoolean, xmlwriter::XMLWriter, CellRange, Format, Formula, RowNum, Workbook,
+    XlsxError,
 };
 
 /// The `Table` struct represents a worksheet Table.
@@ -1014,6 +1015,13 @@ impl Table {
     ///
     /// * `name` - The name of the table. It must be unique across the workbook.
     ///
+    /// The name must also follow the Excel rules for defined names: it must
+    /// start with a letter, underscore or backslash, it can only contain
+    /// letters, numbers, underscores, periods and backslashes, and it cannot
+    /// look like a cell reference such as `A1` or `R1C1`. Invalid names are
+    /// reported as a [`XlsxError::TableError`] by
+    /// [`Worksheet::add_table()`](crate::Worksheet::add_table).
+    ///
     /// # Examples
     ///
     /// Example of setting the name of a worksheet table.
@@ -1191,6 +1199,31 @@ impl Table {
         Ok(())
     }
 
+    // Check that a user defined table name follows the Excel rules for names.
+    // These are the same as the rules for defined names.
+    pub(crate) fn validate_name(&self) -> Result<(), XlsxError> {
+        let name = &self.name;
+        if name.is_empty() {
+            return Ok(());
+        }
+
+        let error = if name.chars().count() > 255 {
+            "must be less than 256 characters"
+        } else if !name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '\\') {
+            "must start with a letter, underscore or backslash"
+        } else if name.contains(|c: char| !c.is_alphanumeric() && !"_.\\".contains(c)) {
+            "can only contain letters, numbers, underscores, periods and backslashes"
+        } else if Workbook::is_cell_reference(name) {
+            "cannot look like a cell reference such as `A1` or `R1C1`"
+        } else {
+            return Ok(());
+        };
+
+        Err(XlsxError::TableError(format!(
+            "Table name '{name}' {error} in Excel"
+        )))
+    }
+
     // Get the first row that can be used to write data.
     pub(crate) fn first_data_row(&self) -> RowNum {
         if self.show_header_row {
@@ -1451,6 +1484,7 @@ pub struct TableColumn {
     pub(crate) formula: Option<Formula>,
     pub(crate) format: Option<Format>,
     pub(crate) header_format: Option<Format>,
+    pub(crate) total_format: Option<Format>,
 }
 
 impl TableColumn {
@@ -1464,6 +1498,7 @@ impl TableColumn {
             formula: None,
             format: None,
             header_format: None,
+            total_format: None,
         }
     }
 
@@ -1671,6 +1706,12 @@ impl TableColumn {
     /// for several columns in a single row might you might use a formula like
     /// this: `SUM(Table1[@[Quarter 1]:[Quarter 4]])`.
     ///
+    /// The formula is written to every data row of the column and it is also
+    /// stored as the column's calculated formula, so Excel extends it to any
+    /// new rows added to the table. The Excel 2010 `@` "this row" syntax, such
+    /// as `[@Price]*[@Qty]`, is converted to the `[#This Row]` syntax that
+    /// Excel uses to store the formula in the file.
+    ///
     /// [Structured References]:
     ///     https://support.microsoft.com/en-us/office/using-structured-references-with-excel-tables-f5ed2452-2337-4f71-bed3-c8ae6d2b276e
     ///
@@ -1922,6 +1963,68 @@ impl TableColumn {
         self
     }
 
+    /// Set the format for the total row cell of the table column.
+    ///
+    /// The `set_total_format` method can be used to set the format for the
+    /// total row label or subtotal function of a column in a worksheet table.
+    /// See [`TableColumn::set_total_label()`] and
+    /// [`TableColumn::set_total_function()`].
+    ///
+    /// By default the total row label is unformatted and the subtotal
+    /// function uses the column format set with [`TableColumn::set_format()`].
+    /// The total format overrides both of these. It only applies if the table
+    /// has a total row, see [`Table::set_total_row()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `format` - The [`Format`] property for the column total row cell.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a format to the total row of a worksheet table.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_tablecolumn_set_total_format.rs
+    /// #
+    /// # use rust_xlsxwriter::{Format, Table, TableColumn, TableFunction, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Write the table data.
+    /// #     worksheet.write_column(3, 1, ["Apples", "Pears", "Bananas"])?;
+    /// #     worksheet.write_column(3, 2, [10000.0, 2000.0, 6000.0])?;
+    /// #
+    ///     let bold = Format::new().set_bold();
+    ///     let currency = Format::new().set_bold().set_num_format("$#,##0");
+    ///
+    ///     let columns = vec![
+    ///         TableColumn::new()
+    ///             .set_header("Product")
+    ///             .set_total_label("Totals")
+    ///             .set_total_format(bold),
+    ///         TableColumn::new()
+    ///             .set_header("Sales")
+    ///             .set_total_function(TableFunction::Sum)
+    ///             .set_total_format(currency),
+    ///     ];
+    ///
+    ///     let table = Table::new().set_columns(&columns).set_total_row(true);
+    ///
+    ///     worksheet.add_table(2, 1, 6, 2, &table)?;
+    /// #
+    /// #     workbook.save("tables.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_total_format(mut self, format: impl Into<Format>) -> TableColumn {
+        self.total_format = Some(format.into());
+        self
+    }
+
     // Convert the SUBTOTAL type to a worksheet formula.
     pub(crate) fn total_function(&self) -> Formula {
         let column_name = self
@@ -2082,6 +2185,16 @@ impl fmt::Display for TableFunction {
 /// The style is set via the [`table.set_style`](Table::set_style) method. The
 /// default table style in Excel is equivalent to [`TableStyle::Medium9`].
 ///
+/// A `TableStyle` can also be parsed from the style name used by Excel, such as
+/// `"TableStyleMedium9"`, or the short form, such as `"Medium9"`, using
+/// [`str::parse()`]. This is useful when the style comes from a configuration
+/// file.
+///
+/// The banded rows, banded columns and first/last column emphasis options of
+/// the style are set with [`Table::set_banded_rows()`],
+/// [`Table::set_banded_columns()`], [`Table::set_first_column()`] and
+/// [`Table::set_last_column()`].
+///
 /// # Examples
 ///
 /// Example of setting the style of a worksheet table.
@@ -2133,7 +2246,7 @@ impl fmt::Display for TableFunction {
 ///
 /// <img src="https://rustxlsxwriter.github.io/images/table_set_style.png">
 ///
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
 pub enum TableStyle {
     /// No table style.
     None,
@@ -2225,7 +2338,8 @@ pub enum TableStyle {
     /// Table Style Medium 8, Light Grey.
     Medium8,
 
-    /// Table Style Medium 9, Blue.
+    /// Table Style Medium 9, Blue. This is the default style.
+    #[default]
     Medium9,
 
     /// Table Style Medium 10, Orange.
@@ -2319,6 +2433,91 @@ pub enum TableStyle {
     Dark11,
 }
 
+// List of all the table styles, used for parsing table style names.
+const TABLE_STYLES: [TableStyle; 61] = [
+    TableStyle::None,
+    TableStyle::Light1,
+    TableStyle::Light2,
+    TableStyle::Light3,
+    TableStyle::Light4,
+    TableStyle::Light5,
+    TableStyle::Light6,
+    TableStyle::Light7,
+    TableStyle::Light8,
+    TableStyle::Light9,
+    TableStyle::Light10,
+    TableStyle::Light11,
+    TableStyle::Light12,
+    TableStyle::Light13,
+    TableStyle::Light14,
+    TableStyle::Light15,
+    TableStyle::Light16,
+    TableStyle::Light17,
+    TableStyle::Light18,
+    TableStyle::Light19,
+    TableStyle::Light20,
+    TableStyle::Light21,
+    TableStyle::Medium1,
+    TableStyle::Medium2,
+    TableStyle::Medium3,
+    TableStyle::Medium4,
+    TableStyle::Medium5,
+    TableStyle::Medium6,
+    TableStyle::Medium7,
+    TableStyle::Medium8,
+    TableStyle::Medium9,
+    TableStyle::Medium10,
+    TableStyle::Medium11,
+    TableStyle::Medium12,
+    TableStyle::Medium13,
+    TableStyle::Medium14,
+    TableStyle::Medium15,
+    TableStyle::Medium16,
+    TableStyle::Medium17,
+    TableStyle::Medium18,
+    TableStyle::Medium19,
+    TableStyle::Medium20,
+    TableStyle::Medium21,
+    TableStyle::Medium22,
+    TableStyle::Medium23,
+    TableStyle::Medium24,
+    TableStyle::Medium25,
+    TableStyle::Medium26,
+    TableStyle::Medium27,
+    TableStyle::Medium28,
+    TableStyle::Dark1,
+    TableStyle::Dark2,
+    TableStyle::Dark3,
+    TableStyle::Dark4,
+    TableStyle::Dark5,
+    TableStyle::Dark6,
+    TableStyle::Dark7,
+    TableStyle::Dark8,
+    TableStyle::Dark9,
+    TableStyle::Dark10,
+    TableStyle::Dark11,
+];
+
+impl FromStr for TableStyle {
+    type Err = XlsxError;
+
+    /// Parse a table style from the name used by Excel, such as
+    /// `"TableStyleMedium9"`, or the short form, such as `"Medium9"`. The
+    /// comparison is case insensitive. This is useful when the table style is
+    /// read from a configuration file or user input.
+    fn from_str(name: &str) -> Result<TableStyle, XlsxError> {
+        let lowercase_name = name.trim().to_lowercase();
+        let short_name = lowercase_name
+            .strip_prefix("tablestyle")
+            .unwrap_or(&lowercase_name);
+
+        TABLE_STYLES
+            .into_iter()
+            .find(|style| style.to_string()[10..].eq_ignore_ascii_case(short_name))
+            .ok_or_else(|| XlsxError::ParameterError(format!("Unknown table style name '{name}'")))
+    }
+}
+
 impl fmt::Display for TableStyle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -9,7 +9,7 @@ mod table_tests {
 
     use crate::table::Table;
     use crate::test_functions::xml_to_vec;
-    use crate::{TableColumn, TableFunction, Worksheet, XlsxError};
+    use crate::{TableColumn, TableFunction, TableStyle, Worksheet, XlsxError};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -74,6 +74,37 @@ mod table_tests {
         assert!(matches!(result, Err(XlsxError::TableError(_))));
     }
 
+    #[test]
+    fn test_name_validation() {
+        for name in [
+            "Sales",
+            "_Sales_2024",
+            "\\Sales.Q1",
+            "Ventes_été",
+            "AAAA1",
+            "",
+        ] {
+            let table = Table::new().set_name(name);
+            assert!(table.validate_name().is_ok(), "{name}");
+        }
+
+        let long_name = "A".repeat(256);
+        for name in [
+            "1Sales",
+            "Sales Data",
+            "Sales-2024",
+            "A1",
+            "XFD1048576",
+            "R1C1",
+            "r",
+            &long_name,
+        ] {
+            let table = Table::new().set_name(name);
+            let result = table.validate_name();
+            assert!(matches!(result, Err(XlsxError::TableError(_))), "{name}");
+        }
+    }
+
     #[test]
     fn test_assemble1() {
         let mut table = Table::new();
@@ -580,4 +611,34 @@ mod table_tests {
 
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_table_style_from_str() {
+        assert_eq!(TableStyle::default(), TableStyle::Medium9);
+        assert_eq!(
+            "TableStyleMedium9".parse::<TableStyle>().unwrap(),
+            TableStyle::Medium9
+        );
+        assert_eq!(
+            "medium9".parse::<TableStyle>().unwrap(),
+            TableStyle::Medium9
+        );
+        assert_eq!(
+            " Light21 ".parse::<TableStyle>().unwrap(),
+            TableStyle::Light21
+        );
+        assert_eq!(
+            "tablestyledark11".parse::<TableStyle>().unwrap(),
+            TableStyle::Dark11
+        );
+        assert_eq!("None".parse::<TableStyle>().unwrap(), TableStyle::None);
+
+        for name in ["", "Medium29", "Light0", "TableStyle", "Medium 9"] {
+            let result = name.parse::<TableStyle>();
+            assert!(
+                matches!(result, Err(XlsxError::ParameterError(_))),
+                "{name}"
+            );
+        }
+    }
 }
@@ -6,6 +6,11 @@
 
 #![warn(missing_docs)]
 
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::warning::Warnings;
+
 /// The `Url` struct is used to define a worksheet url.
 ///
 /// The `Url` struct creates a url type that can be used to write worksheet
@@ -99,8 +104,14 @@
 ///
 ///    * `http://`, `https://`, `ftp://`, `ftps://` and `mailto:`.
 ///
+///    The [`Url::mailto()`] constructor can be used to create `mailto:` links
+///    with an encoded subject and body.
+///
 /// 2. Local file links using the `file://` URI.
 ///
+///    The [`Url::file()`] constructor can be used to create these links from
+///    a file path.
+///
 ///    * `file:///Book2.xlsx`
 ///    * `file:///..\Sales\Book2.xlsx`
 ///    * `file:///C:\Temp\Book1.xlsx`
@@ -134,14 +145,24 @@
 /// style escapes. In which case it is assumed that the URL was escaped
 /// correctly by the user and will by passed directly to Excel.
 ///
+/// Web urls with non-ASCII characters are also converted to the form that
+/// Excel requires. Internationalized host names, like `bücher.example`, are
+/// converted to their ASCII "punycode" form, `xn--bcher-kva.example`, and any
+/// other non-ASCII characters are percent encoded as UTF-8. The displayed
+/// text of the url is unchanged.
+///
 /// Excel has a limit of around 2080 characters in the url string. Urls beyond
-/// this limit will raise an error when written.
+/// this limit will raise an error when written, unless a different policy is
+/// set with
+/// [`Worksheet::set_url_length_policy()`](crate::Worksheet::set_url_length_policy)
+/// and [`UrlLengthPolicy`].
 ///
 #[derive(Clone, Debug)]
 pub struct Url {
     pub(crate) link: String,
     pub(crate) text: String,
     pub(crate) tip: String,
+    pub(crate) warnings: Warnings,
 }
 
 impl Url {
@@ -156,7 +177,102 @@ impl Url {
             link: link.into(),
             text: String::new(),
             tip: String::new(),
+            warnings: Warnings::new(),
+        }
+    }
+
+    /// Create a new `mailto:` Url for an email address.
+    ///
+    /// Create a url for an email address. The address is used as the displayed
+    /// text of the url, unless it is changed with [`Url::set_text()`]. The
+    /// subject, body and other fields of the email can be added with
+    /// [`Url::set_subject()`], [`Url::set_body()`], [`Url::set_cc()`] and
+    /// [`Url::set_bcc()`]. These are percent encoded as required.
+    ///
+    /// # Parameters
+    ///
+    /// `address` - The email address, as a string or string like type.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing `mailto:` and file urls to a
+    /// worksheet using the `Url` helper constructors.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_url_mailto.rs
+    /// #
+    /// # use rust_xlsxwriter::{Url, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Write a mailto url with a subject and body.
+    ///     let url = Url::mailto("sales@example.com")
+    ///         .set_subject("Q3 Sales & Forecast")
+    ///         .set_body("See the attached report.");
+    ///
+    ///     worksheet.write(0, 0, url)?;
+    ///
+    ///     // Write a link to a local file with a Windows path.
+    ///     worksheet.write(1, 0, Url::file(r"C:\Sales Data\Q3 2024.xlsx"))?;
+    ///
+    ///     // Write a link to a file relative to the workbook.
+    ///     worksheet.write(2, 0, Url::file("Reports/Q3.xlsx"))?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn mailto(address: impl Into<String>) -> Url {
+        let address = address.into();
+        let link = format!("mailto:{}", percent_encode(&address, b"@!$'*+,;="));
+
+        Url::new(link).set_text(address)
+    }
+
+    /// Create a new `file://` Url for a local or network file path.
+    ///
+    /// Create a url for a file path with the `file:///` prefix that Excel
+    /// requires. The path can be an absolute Windows path like
+    /// `C:\Temp\Book1.xlsx`, a network path like `\\server\share\Book1.xlsx`,
+    /// an absolute Unix style path or a path relative to the workbook. Paths
+    /// that already have a `file://` prefix are used unchanged.
+    ///
+    /// Characters that need to be escaped, such as spaces, are handled when the
+    /// url is written. A `#` in the path is treated as the start of an anchor
+    /// to a location in the file, such as `Book2.xlsx#Sheet1!A1`.
+    ///
+    /// The displayed text of the url is the path, unless it is changed with
+    /// [`Url::set_text()`].
+    ///
+    /// # Parameters
+    ///
+    /// `path` - The file path, as a [`Path`](std::path::Path) or string like
+    /// type.
+    ///
+    /// # Examples
+    ///
+    /// See the example for [`Url::mailto()`] above.
+    ///
+    pub fn file(path: impl AsRef<Path>) -> Url {
+        let path = path.as_ref().to_string_lossy();
+
+        if path.starts_with("file://") {
+            return Url::new(path);
         }
+
+        let text = path.to_string();
+        let link = if path.starts_with('/') {
+            // Absolute Unix style paths keep their leading slash.
+            format!("file:///{path}")
+        } else {
+            format!("file:///{}", path.trim_start_matches("./"))
+        };
+
+        Url::new(link).set_text(text)
     }
 
     /// Set the alternative text for the url.
@@ -215,6 +331,81 @@ impl Url {
         self.tip = tip.into();
         self
     }
+
+    /// Set the subject of a `mailto:` url.
+    ///
+    /// The subject is percent encoded and added to the url. See
+    /// [`Url::mailto()`]. This is ignored, with a warning, for other url types.
+    ///
+    /// # Parameters
+    ///
+    /// `subject` - The email subject, as a string or string like type.
+    ///
+    pub fn set_subject(self, subject: impl Into<String>) -> Url {
+        self.add_mailto_field("subject", &subject.into())
+    }
+
+    /// Set the body of a `mailto:` url.
+    ///
+    /// The body is percent encoded and added to the url. See
+    /// [`Url::mailto()`]. This is ignored, with a warning, for other url types.
+    ///
+    /// # Parameters
+    ///
+    /// `body` - The email body, as a string or string like type.
+    ///
+    pub fn set_body(self, body: impl Into<String>) -> Url {
+        self.add_mailto_field("body", &body.into())
+    }
+
+    /// Set the "cc" address of a `mailto:` url.
+    ///
+    /// See [`Url::mailto()`]. This is ignored, with a warning, for other url
+    /// types.
+    ///
+    /// # Parameters
+    ///
+    /// `address` - The cc email address or addresses, as a string or string
+    /// like type.
+    ///
+    pub fn set_cc(self, address: impl Into<String>) -> Url {
+        self.add_mailto_field("cc", &address.into())
+    }
+
+    /// Set the "bcc" address of a `mailto:` url.
+    ///
+    /// See [`Url::mailto()`]. This is ignored, with a warning, for other url
+    /// types.
+    ///
+    /// # Parameters
+    ///
+    /// `address` - The bcc email address or addresses, as a string or string
+    /// like type.
+    ///
+    pub fn set_bcc(self, address: impl Into<String>) -> Url {
+        self.add_mailto_field("bcc", &address.into())
+    }
+
+    // Add a percent encoded "name=value" field to the query part of a mailto
+    // url.
+    fn add_mailto_field(mut self, name: &str, value: &str) -> Url {
+        if !self.link.starts_with("mailto:") {
+            self.warnings.warn(
+                "add_mailto_field",
+                format!(
+                    "Url::set_{name}() only applies to mailto: urls. Ignoring value for '{}'",
+                    self.link
+                ),
+            );
+            return self;
+        }
+
+        let separator = if self.link.contains('?') { '&' } else { '?' };
+        let value = percent_encode(value, b"@,");
+        self.link = format!("{}{separator}{name}={value}", self.link);
+
+        self
+    }
 }
 
 impl From<&str> for Url {
@@ -222,3 +413,199 @@ impl From<&str> for Url {
         Url::new(value)
     }
 }
+
+/// The `UrlLengthPolicy` enum defines how urls that exceed Excel's length
+/// limits are handled.
+///
+/// Excel has a limit of 2080 characters for the url string and anchor and a
+/// limit of 255 characters for the screen tip. By default a url that exceeds
+/// these limits raises an [`XlsxError::MaxUrlLengthExceeded`] error. This can
+/// be inconvenient when writing data driven exports where a single long url
+/// would stop the output, so the policy can be changed on a per worksheet
+/// basis using
+/// [`Worksheet::set_url_length_policy()`](crate::Worksheet::set_url_length_policy).
+///
+/// The `Truncate` and `WriteAsText` policies log a warning. See
+/// [Warnings](crate#warnings).
+///
+/// [`XlsxError::MaxUrlLengthExceeded`]: crate::XlsxError::MaxUrlLengthExceeded
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Default)]
+pub enum UrlLengthPolicy {
+    /// Raise an [`XlsxError::MaxUrlLengthExceeded`] error for urls that exceed
+    /// Excel's limits. This is the default.
+    ///
+    /// [`XlsxError::MaxUrlLengthExceeded`]: crate::XlsxError::MaxUrlLengthExceeded
+    #[default]
+    Error,
+
+    /// Truncate the url, anchor and screen tip to Excel's limits and write the
+    /// link with a warning. The displayed text of the url isn't changed.
+    Truncate,
+
+    /// Write the text of the url as a plain string, without a link, and log a
+    /// warning. The user supplied format is used, if there is one.
+    WriteAsText,
+}
+
+// -----------------------------------------------------------------------
+// Helper functions for encoding urls.
+// -----------------------------------------------------------------------
+
+// Percent encode all the characters in a string apart from the RFC 3986
+// unreserved characters and any additional safe characters for the context.
+fn percent_encode(data: &str, safe_chars: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len());
+
+    for byte in data.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) || safe_chars.contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    encoded
+}
+
+// Convert a web url with non-ASCII characters to the ASCII form required by
+// Excel. The host name labels are converted to punycode and any other non-ASCII
+// characters are percent encoded as UTF-8. Urls that are already ASCII, the
+// common case, are borrowed without allocating.
+pub(crate) fn encode_non_ascii_url(url: &str) -> Cow<'_, str> {
+    if url.is_ascii() {
+        return Cow::Borrowed(url);
+    }
+
+    let mut encoded = String::with_capacity(url.len() + 16);
+    let mut remaining = url;
+
+    // Convert the host part of the authority, if there is one. It is the part
+    // after any "user@" prefix and before any ":port" suffix.
+    if let Some(index) = remaining.find("://") {
+        let (scheme, rest) = remaining.split_at(index + 3);
+        let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+        let (authority, rest) = rest.split_at(authority_end);
+
+        let host_start = authority.rfind('@').map_or(0, |index| index + 1);
+        let host_end = authority[host_start..]
+            .find(':')
+            .map_or(authority.len(), |index| host_start + index);
+
+        encoded.push_str(scheme);
+        encoded.push_str(&authority[..host_start]);
+        encoded.push_str(&encode_host(&authority[host_start..host_end]));
+        encoded.push_str(&authority[host_end..]);
+        remaining = rest;
+    }
+
+    // Percent encode the non-ASCII characters in the rest of the url. Upper
+    // case hex digits are used as recommended by RFC 3986.
+    for ch in remaining.chars() {
+        if ch.is_ascii() {
+            encoded.push(ch);
+        } else {
+            let mut buffer = [0; 4];
+            for byte in ch.encode_utf8(&mut buffer).bytes() {
+                encoded.push_str(&format!("%{byte:02X}"));
+            }
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+// Convert the non-ASCII labels of a host name to the "xn--" punycode form.
+fn encode_host(host: &str) -> String {
+    host.split('.')
+        .map(|label| {
+            if label.is_ascii() {
+                return label.to_string();
+            }
+
+            match punycode_encode(&label.to_lowercase()) {
+                Some(encoded) => format!("xn--{encoded}"),
+                None => label.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+// Encode a string using the punycode algorithm from RFC 3492. Returns None on
+// overflow, which can only occur for unrealistically long labels.
+fn punycode_encode(input: &str) -> Option<String> {
+    const BASE: u32 = 36;
+    const INITIAL_N: u32 = 128;
+
+    let code_points: Vec<u32> = input.chars().map(u32::from).collect();
+    let mut output: String = input.chars().filter(char::is_ascii).collect();
+
+    let basic_count = output.len() as u32;
+    let mut handled_count = basic_count;
+    if basic_count > 0 {
+        output.push('-');
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = 72;
+
+    while (handled_count as usize) < code_points.len() {
+        let m = code_points.iter().copied().filter(|&c| c >= n).min()?;
+        delta = delta.checked_add((m - n).checked_mul(handled_count + 1)?)?;
+        n = m;
+
+        for &code_point in &code_points {
+            if code_point < n {
+                delta = delta.checked_add(1)?;
+            }
+
+            if code_point == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = k.saturating_sub(bias).clamp(1, 26);
+                    if q < t {
+                        break;
+                    }
+                    output.push(punycode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+
+                output.push(punycode_digit(q));
+                bias = punycode_adapt(delta, handled_count + 1, handled_count == basic_count);
+                delta = 0;
+                handled_count += 1;
+            }
+        }
+
+        delta = delta.checked_add(1)?;
+        n += 1;
+    }
+
+    Some(output)
+}
+
+// Bias adaptation function from RFC 3492 section 6.1.
+fn punycode_adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / 700 } else { delta / 2 };
+    delta += delta / num_points;
+
+    let mut k = 0;
+    while delta > ((36 - 1) * 26) / 2 {
+        delta /= 36 - 1;
+        k += 36;
+    }
+
+    k + (36 * delta) / (delta + 38)
+}
+
+// Convert a punycode digit value, 0-35, to a character: a-z then 0-9.
+fn punycode_digit(digit: u32) -> char {
+    if digit < 26 {
+        char::from(b'a' + digit as u8)
+    } else {
+        char::from(b'0' + (digit - 26) as u8)
+    }
+}
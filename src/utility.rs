@@ -52,27 +52,8 @@ use crate::XlsxError;
 /// ```
 ///
 pub fn column_number_to_name(col_num: ColNum) -> String {
-    let mut col_name = String::new();
-
-    let mut col_num = col_num + 1;
-
-    while col_num > 0 {
-        // Set remainder from 1 .. 26
-        let mut remainder = col_num % 26;
-
-        if remainder == 0 {
-            remainder = 26;
-        }
-
-        // Convert the remainder to a character.
-        let col_letter = char::from_u32(64u32 + u32::from(remainder)).unwrap();
-
-        // Accumulate the column letters, right to left.
-        col_name = format!("{col_letter}{col_name}");
-
-        // Get the next order of magnitude.
-        col_num = (col_num - 1) / 26;
-    }
+    let mut col_name = String::with_capacity(3);
+    push_column_name(&mut col_name, col_num);
 
     col_name
 }
@@ -119,7 +100,10 @@ pub fn column_name_to_number(column: &str) -> ColNum {
 /// ```
 ///
 pub fn row_col_to_cell(row_num: RowNum, col_num: ColNum) -> String {
-    format!("{}{}", column_number_to_name(col_num), row_num + 1)
+    let mut cell = String::with_capacity(10);
+    push_cell(&mut cell, row_num, col_num, false);
+
+    cell
 }
 
 /// Convert zero indexed row and column cell numbers to an absolute `$A$1`
@@ -140,7 +124,10 @@ pub fn row_col_to_cell(row_num: RowNum, col_num: ColNum) -> String {
 /// ```
 ///
 pub fn row_col_to_cell_absolute(row_num: RowNum, col_num: ColNum) -> String {
-    format!("${}${}", column_number_to_name(col_num), row_num + 1)
+    let mut cell = String::with_capacity(12);
+    push_cell(&mut cell, row_num, col_num, true);
+
+    cell
 }
 
 /// Convert zero indexed row and col cell numbers to a `A1:B1` style range
@@ -176,14 +163,10 @@ pub fn cell_range(
     last_row: RowNum,
     last_col: ColNum,
 ) -> String {
-    let range1 = row_col_to_cell(first_row, first_col);
-    let range2 = row_col_to_cell(last_row, last_col);
+    let mut range = String::with_capacity(21);
+    push_cell_range(&mut range, first_row, first_col, last_row, last_col, false);
 
-    if range1 == range2 {
-        range1
-    } else {
-        format!("{range1}:{range2}")
-    }
+    range
 }
 
 /// Convert zero indexed row and col cell numbers to an absolute `$A$1:$B$1`
@@ -219,13 +202,63 @@ pub fn cell_range_absolute(
     last_row: RowNum,
     last_col: ColNum,
 ) -> String {
-    let range1 = row_col_to_cell_absolute(first_row, first_col);
-    let range2 = row_col_to_cell_absolute(last_row, last_col);
+    let mut range = String::with_capacity(25);
+    push_cell_range(&mut range, first_row, first_col, last_row, last_col, true);
 
-    if range1 == range2 {
-        range1
-    } else {
-        format!("{range1}:{range2}")
+    range
+}
+
+// Append the name of a zero indexed column to a string buffer. This is the
+// allocation free version of column_number_to_name() for use in loops, or by
+// callers that reuse a buffer.
+pub(crate) fn push_column_name(buffer: &mut String, col_num: ColNum) {
+    // The u16 column range requires at most 4 letters.
+    let mut letters = [0u8; 4];
+    let mut position = letters.len();
+    let mut col_num = u32::from(col_num) + 1;
+
+    // Accumulate the column letters, right to left.
+    while col_num > 0 {
+        position -= 1;
+        letters[position] = b'A' + ((col_num - 1) % 26) as u8;
+        col_num = (col_num - 1) / 26;
+    }
+
+    for &letter in &letters[position..] {
+        buffer.push(char::from(letter));
+    }
+}
+
+// Append an `A1` or absolute `$A$1` style cell reference to a string buffer.
+pub(crate) fn push_cell(buffer: &mut String, row_num: RowNum, col_num: ColNum, absolute: bool) {
+    if absolute {
+        buffer.push('$');
+    }
+
+    push_column_name(buffer, col_num);
+
+    if absolute {
+        buffer.push('$');
+    }
+
+    buffer.push_str(itoa::Buffer::new().format(u64::from(row_num) + 1));
+}
+
+// Append an `A1:B1` or absolute `$A$1:$B$1` style range to a string buffer. If
+// the start and end cell are the same then a single cell reference is written.
+pub(crate) fn push_cell_range(
+    buffer: &mut String,
+    first_row: RowNum,
+    first_col: ColNum,
+    last_row: RowNum,
+    last_col: ColNum,
+    absolute: bool,
+) {
+    push_cell(buffer, first_row, first_col, absolute);
+
+    if first_row != last_row || first_col != last_col {
+        buffer.push(':');
+        push_cell(buffer, last_row, last_col, absolute);
     }
 }
 
@@ -503,34 +536,320 @@ pub(crate) fn quote_sheetname(sheetname: &str) -> String {
 /// }
 ///
 pub fn check_sheet_name(name: &str) -> Result<(), XlsxError> {
-    let error_message = format!("Invalid Excel worksheet name '{name}'");
-    validate_sheetname(name, &error_message)
+    validate_sheetname(name, || format!("Invalid Excel worksheet name '{name}'"))
 }
 
-// Internal function to validate worksheet name.
-pub(crate) fn validate_sheetname(name: &str, message: &str) -> Result<(), XlsxError> {
+/// Convert a string into a valid worksheet name.
+///
+/// The `sanitize_sheet_name()` function converts a string, for example a name
+/// from user data, into a name that follows the Excel worksheet naming rules
+/// shown in [`check_sheet_name()`]:
+///
+/// * The invalid characters `[ ] : * ? / \` are replaced with `_`.
+/// * Leading and trailing apostrophes are removed.
+/// * The name is truncated to 31 characters.
+/// * A blank name is replaced with `Sheet`.
+/// * The name `History`, which is reserved in English versions of Excel, is
+///   changed to `History_1`. The check is case-insensitive.
+///
+/// The function doesn't check for duplicate names. See
+/// [`Workbook::set_sanitize_sheet_names()`](crate::Workbook::set_sanitize_sheet_names)
+/// for a workbook mode that also makes the names unique.
+///
+/// # Parameters
+///
+/// * `name` - The worksheet name to convert.
+///
+/// # Examples
+///
+/// The following example demonstrates converting strings into valid worksheet
+/// names.
+///
+/// ```
+/// # // This code is available in examples/doc_utility_sanitize_sheet_name.rs
+/// #
+/// # use rust_xlsxwriter::utility;
+/// #
+/// # fn main() {
+///     assert_eq!(utility::sanitize_sheet_name("2030/01/01"), "2030_01_01");
+///     assert_eq!(utility::sanitize_sheet_name("'Sales'"), "Sales");
+///     assert_eq!(utility::sanitize_sheet_name(""), "Sheet");
+///     assert_eq!(utility::sanitize_sheet_name("history"), "history_1");
+/// # }
+/// ```
+///
+pub fn sanitize_sheet_name(name: &str) -> String {
+    let name: String = name
+        .trim_matches('\'')
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            _ => c,
+        })
+        .take(31)
+        .collect();
+
+    // Truncation may have exposed a trailing apostrophe.
+    let name = name.trim_end_matches('\'');
+
+    if name.is_empty() {
+        "Sheet".to_string()
+    } else if name.eq_ignore_ascii_case("history") {
+        format!("{name}_1")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Convert a column width in Excel character units to pixels.
+///
+/// Excel column widths are specified in character units based on the width of
+/// the "0" digit in the default font, Calibri 11, plus some padding. This
+/// function converts a width in those units to the equivalent width in pixels
+/// at the standard 96 dpi screen resolution.
+///
+/// # Parameters
+///
+/// * `width` - The column width in character units.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::column_width_to_pixels;
+///
+/// assert_eq!(column_width_to_pixels(8.43), 64);
+/// assert_eq!(column_width_to_pixels(20.0), 145);
+/// ```
+///
+pub fn column_width_to_pixels(width: f64) -> u16 {
+    // Properties for Calibri 11.
+    let max_digit_width = 7.0_f64;
+    let padding = 5.0_f64;
+
+    if width < 1.0 {
+        (width * (max_digit_width + padding)).round() as u16
+    } else {
+        ((width * max_digit_width).round() + padding) as u16
+    }
+}
+
+/// Convert a column width in pixels to Excel character units.
+///
+/// This is the inverse of [`column_width_to_pixels()`]. See that function for
+/// an explanation of the units.
+///
+/// # Parameters
+///
+/// * `pixels` - The column width in pixels.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::pixels_to_column_width;
+///
+/// assert_eq!(pixels_to_column_width(64), 8.428571428571429);
+/// assert_eq!(pixels_to_column_width(145), 20.0);
+/// ```
+///
+pub fn pixels_to_column_width(pixels: u16) -> f64 {
+    // Properties for Calibri 11.
+    let max_digit_width = 7.0_f64;
+    let padding = 5.0_f64;
+    let width = f64::from(pixels);
+
+    if width < 12.0 {
+        width / (max_digit_width + padding)
+    } else {
+        (width - padding) / max_digit_width
+    }
+}
+
+/// Convert a size in points to pixels.
+///
+/// Excel row heights are specified in points, where a point is 1/72 of an
+/// inch. This function converts a size in points to the equivalent size in
+/// pixels at the standard 96 dpi screen resolution. For example the default
+/// row height of 15 points is 20 pixels.
+///
+/// # Parameters
+///
+/// * `points` - The size in points.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::points_to_pixels;
+///
+/// assert_eq!(points_to_pixels(15.0), 20.0);
+/// ```
+///
+pub fn points_to_pixels(points: f64) -> f64 {
+    points * 4.0 / 3.0
+}
+
+/// Convert a size in pixels to points.
+///
+/// This is the inverse of [`points_to_pixels()`]. It can be used to convert a
+/// row height in pixels to the points used by
+/// [`Worksheet::set_row_height()`](crate::Worksheet::set_row_height).
+///
+/// # Parameters
+///
+/// * `pixels` - The size in pixels.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::pixels_to_points;
+///
+/// assert_eq!(pixels_to_points(20.0), 15.0);
+/// ```
+///
+pub fn pixels_to_points(pixels: f64) -> f64 {
+    pixels * 0.75
+}
+
+/// Convert a size in inches to points.
+///
+/// # Parameters
+///
+/// * `inches` - The size in inches.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::inches_to_points;
+///
+/// assert_eq!(inches_to_points(0.5), 36.0);
+/// ```
+///
+pub fn inches_to_points(inches: f64) -> f64 {
+    inches * 72.0
+}
+
+/// Convert a size in points to inches.
+///
+/// # Parameters
+///
+/// * `points` - The size in points.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::points_to_inches;
+///
+/// assert_eq!(points_to_inches(36.0), 0.5);
+/// ```
+///
+pub fn points_to_inches(points: f64) -> f64 {
+    points / 72.0
+}
+
+/// Convert a size in centimeters to points.
+///
+/// # Parameters
+///
+/// * `cm` - The size in centimeters.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::cm_to_points;
+///
+/// assert_eq!(cm_to_points(2.54), 72.0);
+/// ```
+///
+pub fn cm_to_points(cm: f64) -> f64 {
+    cm * 72.0 / 2.54
+}
+
+/// Convert a size in points to centimeters.
+///
+/// # Parameters
+///
+/// * `points` - The size in points.
+///
+/// # Examples:
+///
+/// ```
+/// use rust_xlsxwriter::points_to_cm;
+///
+/// assert_eq!(points_to_cm(72.0), 2.54);
+/// ```
+///
+pub fn points_to_cm(points: f64) -> f64 {
+    points * 2.54 / 72.0
+}
+
+// Create a unique worksheet name by adding a numeric suffix like " (2)" to the
+// name, truncating the name if required to keep it within Excel's limit of 31
+// characters.
+pub(crate) fn unique_sheetname(name: &str, existing_names: &[String]) -> String {
+    let is_used = |name: &str| {
+        existing_names
+            .iter()
+            .any(|existing| existing.to_lowercase() == name.to_lowercase())
+    };
+
+    if !is_used(name) {
+        return name.to_string();
+    }
+
+    let mut count = 2;
+    loop {
+        let suffix = format!(" ({count})");
+        let base: String = name.chars().take(31 - suffix.len()).collect();
+        let unique_name = format!("{base}{suffix}");
+
+        if !is_used(&unique_name) {
+            return unique_name;
+        }
+
+        count += 1;
+    }
+}
+
+// Internal function to validate worksheet name. The error message is only
+// created if the name is invalid.
+pub(crate) fn validate_sheetname(
+    name: &str,
+    message: impl FnOnce() -> String,
+) -> Result<(), XlsxError> {
     // Check that the sheet name isn't blank.
     if name.is_empty() {
-        return Err(XlsxError::SheetnameCannotBeBlank(message.to_string()));
+        return Err(XlsxError::SheetnameCannotBeBlank(message()));
     }
 
     // Check that sheet sheetname is <= 31, an Excel limit.
     if name.chars().count() > 31 {
-        return Err(XlsxError::SheetnameLengthExceeded(message.to_string()));
+        return Err(XlsxError::SheetnameLengthExceeded(message()));
     }
 
     // Check that sheetname doesn't contain any invalid characters.
     if name.contains(['*', '?', ':', '[', ']', '\\', '/']) {
-        return Err(XlsxError::SheetnameContainsInvalidCharacter(
-            message.to_string(),
-        ));
+        return Err(XlsxError::SheetnameContainsInvalidCharacter(message()));
     }
 
     // Check that sheetname doesn't start or end with an apostrophe.
     if name.starts_with('\'') || name.ends_with('\'') {
-        return Err(XlsxError::SheetnameStartsOrEndsWithApostrophe(
-            message.to_string(),
-        ));
+        return Err(XlsxError::SheetnameStartsOrEndsWithApostrophe(message()));
+    }
+
+    Ok(())
+}
+
+// Internal function to validate a VBA codename. It must start with a letter,
+// contain only letters, digits and underscores and be <= 31 characters.
+pub(crate) fn validate_vba_name(name: &str) -> Result<(), XlsxError> {
+    let mut chars = name.chars();
+
+    let starts_with_letter = chars.next().is_some_and(char::is_alphabetic);
+    let valid_chars = chars.all(|c| c.is_alphanumeric() || c == '_');
+
+    if !starts_with_letter || !valid_chars || name.chars().count() > 31 {
+        return Err(XlsxError::ParameterError(format!(
+            "Invalid VBA name '{name}': must start with a letter, contain only letters, digits or underscores and be <= 31 characters"
+        )));
     }
 
     Ok(())
@@ -599,6 +918,61 @@ pub(crate) fn hash_password(password: &str) -> u16 {
     hash
 }
 
+// Encode a byte slice as a standard, padded, Base64 string. This is used for
+// the password hashes and salts in the SHA-512 based protection scheme.
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    const CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b0 = u32::from(chunk[0]);
+        let b1 = u32::from(*chunk.get(1).unwrap_or(&0));
+        let b2 = u32::from(*chunk.get(2).unwrap_or(&0));
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        encoded.push(CHARS[(triple >> 18) as usize & 0x3F] as char);
+        encoded.push(CHARS[(triple >> 12) as usize & 0x3F] as char);
+
+        if chunk.len() > 1 {
+            encoded.push(CHARS[(triple >> 6) as usize & 0x3F] as char);
+        } else {
+            encoded.push('=');
+        }
+
+        if chunk.len() > 2 {
+            encoded.push(CHARS[triple as usize & 0x3F] as char);
+        } else {
+            encoded.push('=');
+        }
+    }
+
+    encoded
+}
+
+// Generate a random version 4 style GUID, in the Excel "{XXXXXXXX-XXXX-4XXX-
+// YXXX-XXXXXXXXXXXX}" format, from the operating system's random source.
+pub(crate) fn random_guid() -> Result<String, XlsxError> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|err| XlsxError::IoError(err.into()))?;
+
+    let value = u128::from_be_bytes(bytes);
+    let high = (value >> 64) as u64;
+    let low = value as u64;
+
+    let high = (high & 0xFFFF_FFFF_FFFF_0FFF) | 0x0000_0000_0000_4000;
+    let low = (low & 0x3FFF_FFFF_FFFF_FFFF) | 0x8000_0000_0000_0000;
+
+    Ok(format!(
+        "{{{:08X}-{:04X}-{:04X}-{:04X}-{:012X}}}",
+        high >> 32,
+        (high >> 16) & 0xFFFF,
+        high & 0xFFFF,
+        low >> 48,
+        low & 0xFFFF_FFFF_FFFF
+    ))
+}
+
 // Clone and strip the leading '=' from formulas, if present.
 pub(crate) fn formula_to_string(formula: &str) -> String {
     let mut formula = formula.to_string();
@@ -610,6 +984,65 @@ pub(crate) fn formula_to_string(formula: &str) -> String {
     formula
 }
 
+// Replace the references to a worksheet in a formula, such as `Sheet2!A1` or
+// `'Sheet 2'!A1`, with a `#REF!` error, like Excel does when a worksheet is
+// deleted. Strings in the formula are ignored.
+pub(crate) fn replace_sheet_references(formula: &str, sheet_name: &str) -> String {
+    let is_name_char = |char: char| char.is_alphanumeric() || char == '_' || char == '.';
+    let sheet_name = sheet_name.to_lowercase();
+    let chars: Vec<char> = formula.chars().collect();
+    let mut result = String::with_capacity(formula.len());
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let char = chars[i];
+
+        if char == '"' {
+            in_string = !in_string;
+        }
+
+        if in_string || !(char == '\'' || is_name_char(char)) {
+            result.push(char);
+            i += 1;
+            continue;
+        }
+
+        // Read a quoted or unquoted token that may be a sheet name.
+        let start = i;
+        let mut name = String::new();
+
+        if char == '\'' {
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' {
+                    if chars.get(i + 1) != Some(&'\'') {
+                        i += 1;
+                        break;
+                    }
+                    i += 1;
+                }
+                name.push(chars[i]);
+                i += 1;
+            }
+        } else {
+            while i < chars.len() && is_name_char(chars[i]) {
+                name.push(chars[i]);
+                i += 1;
+            }
+        }
+
+        if chars.get(i) == Some(&'!') && name.to_lowercase() == sheet_name {
+            result.push_str("#REF!");
+            i += 1;
+        } else {
+            result.extend(&chars[start..i]);
+        }
+    }
+
+    result
+}
+
 // Trait to convert bool to XML "0" or "1".
 pub(crate) trait ToXmlBoolean {
     fn to_xml_bool(self) -> String;
@@ -7,9 +7,46 @@
 #[cfg(test)]
 mod utility_tests {
 
+    use crate::protection::PasswordHash;
     use crate::{utility, XlsxError};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_hash_password_sha512() {
+        let salt: Vec<u8> = (0..16).collect();
+        let password_hash = PasswordHash::new_with_salt("password", &salt);
+
+        assert_eq!("AAECAwQFBgcICQoLDA0ODw==", password_hash.salt_value);
+        assert_eq!(
+            "x01qKaF9y9cQwPxHrE46zKhOLAHXLgmWjpZRPwqjkl6tpT1Lq9JXlHzPvHxsy/q0gWkWsUumW+mgF2sVqd4VXQ==",
+            password_hash.hash_value
+        );
+        assert_eq!(100_000, password_hash.spin_count);
+
+        // Random salts should differ.
+        let hash1 = PasswordHash::new("password").unwrap();
+        let hash2 = PasswordHash::new("password").unwrap();
+        assert_ne!(hash1.salt_value, hash2.salt_value);
+        assert_ne!(hash1.hash_value, hash2.hash_value);
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        let tests = vec![
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+
+        for (data, exp) in tests {
+            assert_eq!(exp, utility::base64_encode(data.as_bytes()));
+        }
+    }
+
     #[test]
     fn test_hash_password() {
         let tests = vec![
@@ -145,6 +182,27 @@ mod utility_tests {
         }
     }
 
+    #[test]
+    fn test_push_cell_buffers() {
+        let mut buffer = String::new();
+
+        utility::push_column_name(&mut buffer, 65535);
+        assert_eq!("CRXP", buffer);
+
+        buffer.clear();
+        utility::push_cell(&mut buffer, 1, 26, true);
+        assert_eq!("$AA$2", buffer);
+
+        // The buffer is appended to so it can be reused.
+        buffer.push('=');
+        utility::push_cell_range(&mut buffer, 0, 0, 9, 1, false);
+        assert_eq!("$AA$2=A1:B10", buffer);
+
+        buffer.clear();
+        utility::push_cell_range(&mut buffer, 4, 4, 4, 4, true);
+        assert_eq!("$E$5", buffer);
+    }
+
     #[test]
     fn test_quote_sheetname() {
         let tests = vec![
@@ -348,4 +406,115 @@ mod utility_tests {
             Err(XlsxError::SheetnameStartsOrEndsWithApostrophe(_))
         ));
     }
+
+    #[test]
+    fn test_sanitize_sheet_name() {
+        let tests = vec![
+            ("Sheet1", "Sheet1"),
+            ("Sales/Costs", "Sales_Costs"),
+            ("[a]:*?\\", "_a_____"),
+            ("'Quoted'", "Quoted"),
+            ("Don't", "Don't"),
+            ("''", "Sheet"),
+            ("", "Sheet"),
+            ("History", "History_1"),
+            ("HISTORY", "HISTORY_1"),
+            ("'history'", "history_1"),
+            ("History 2024", "History 2024"),
+            (
+                "Name that is longer than 31 characters",
+                "Name that is longer than 31 cha",
+            ),
+            (
+                "012345678901234567890123456789'123",
+                "012345678901234567890123456789",
+            ),
+        ];
+
+        for (name, expected) in tests {
+            let sanitized = utility::sanitize_sheet_name(name);
+            assert_eq!(expected, sanitized);
+            assert!(utility::check_sheet_name(&sanitized).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_random_guid() {
+        let guid1 = utility::random_guid().unwrap();
+        let guid2 = utility::random_guid().unwrap();
+
+        assert_ne!(guid1, guid2);
+
+        for guid in [guid1, guid2] {
+            assert_eq!(38, guid.len());
+            assert!(guid.starts_with('{') && guid.ends_with('}'));
+            assert_eq!(Some('4'), guid.chars().nth(15));
+            assert!(matches!(guid.chars().nth(20), Some('8' | '9' | 'A' | 'B')));
+            assert_eq!(
+                vec![8, 4, 4, 4, 12],
+                guid[1..37].split('-').map(str::len).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_unique_sheetname() {
+        let existing = vec!["Data".to_string(), "data (2)".to_string(), "A".repeat(31)];
+
+        assert_eq!("Sales", utility::unique_sheetname("Sales", &existing));
+        assert_eq!("DATA (3)", utility::unique_sheetname("DATA", &existing));
+        assert_eq!(
+            format!("{} (2)", "A".repeat(27)),
+            utility::unique_sheetname(&"A".repeat(31), &existing)
+        );
+    }
+
+    #[test]
+    fn test_replace_sheet_references() {
+        let tests = [
+            ("Sheet2!$A$1", "#REF!$A$1"),
+            ("SUM(Sheet2!A1:A5,Sheet1!B1)", "SUM(#REF!A1:A5,Sheet1!B1)"),
+            ("sheet2!A1+Sheet22!A1", "#REF!A1+Sheet22!A1"),
+            ("'Sheet2'!A1", "#REF!A1"),
+            ("\"Sheet2!A1\"&Sheet2!A1", "\"Sheet2!A1\"&#REF!A1"),
+            ("Sheet1!A1", "Sheet1!A1"),
+        ];
+
+        for (formula, expected) in tests {
+            assert_eq!(
+                expected,
+                utility::replace_sheet_references(formula, "Sheet2")
+            );
+        }
+
+        assert_eq!(
+            "#REF!A1+'It''s'!A2",
+            utility::replace_sheet_references("'My Data'!A1+'It''s'!A2", "my data")
+        );
+        assert_eq!(
+            "Sheet1!A1+#REF!A2",
+            utility::replace_sheet_references("Sheet1!A1+'It''s'!A2", "It's")
+        );
+    }
+
+    #[test]
+    fn test_unit_conversions() {
+        let widths = [(0.0, 0), (0.5, 6), (1.0, 12), (8.43, 64), (20.0, 145)];
+
+        for (width, pixels) in widths {
+            assert_eq!(pixels, utility::column_width_to_pixels(width));
+        }
+
+        for pixels in 0..=300 {
+            let width = utility::pixels_to_column_width(pixels);
+            assert_eq!(pixels, utility::column_width_to_pixels(width));
+        }
+
+        assert_eq!(20.0, utility::points_to_pixels(15.0));
+        assert_eq!(15.0, utility::pixels_to_points(20.0));
+        assert_eq!(72.0, utility::inches_to_points(1.0));
+        assert_eq!(1.0, utility::points_to_inches(72.0));
+        assert_eq!(72.0, utility::cm_to_points(2.54));
+        assert_eq!(2.54, utility::points_to_cm(72.0));
+    }
 }
@@ -6,11 +6,12 @@
 
 mod tests;
 
-use crate::xmlwriter::XMLWriter;
+use crate::{xmlwriter::XMLWriter, Note};
 
 pub struct Vml {
     pub(crate) writer: XMLWriter,
     pub(crate) header_images: Vec<VmlInfo>,
+    pub(crate) notes: Vec<Note>,
     pub(crate) data_id: u32,
     pub(crate) shape_id: u32,
 }
@@ -27,6 +28,7 @@ impl Vml {
         Vml {
             writer,
             header_images: vec![],
+            notes: vec![],
             data_id: 0,
             shape_id: 0,
         }
@@ -44,6 +46,18 @@ impl Vml {
         // Write the o:shapelayout element.
         self.write_shapelayout();
 
+        if !self.notes.is_empty() {
+            // Write the v:shapetype element.
+            self.write_note_shapetype();
+
+            for (z_index, note) in self.notes.clone().iter().enumerate() {
+                self.shape_id += 1;
+
+                // Write the v:shape element.
+                self.write_note_shape(z_index + 1, note);
+            }
+        }
+
         if !self.header_images.is_empty() {
             // Write the v:shapetype element.
             self.write_image_shapetype();
@@ -227,6 +241,143 @@ impl Vml {
 
         self.writer.xml_empty_tag("o:lock", &attributes);
     }
+
+    // Write the <v:shapetype> element for notes.
+    fn write_note_shapetype(&mut self) {
+        let attributes = [
+            ("id", "_x0000_t202"),
+            ("coordsize", "21600,21600"),
+            ("o:spt", "202"),
+            ("path", "m,l,21600r21600,l21600,xe"),
+        ];
+
+        self.writer.xml_start_tag("v:shapetype", &attributes);
+
+        // Write the v:stroke element.
+        self.write_stroke();
+
+        // Write the v:path element.
+        let attributes = [("gradientshapeok", "t"), ("o:connecttype", "rect")];
+        self.writer.xml_empty_tag("v:path", &attributes);
+
+        self.writer.xml_end_tag("v:shapetype");
+    }
+
+    // Write the <v:shape> element for notes.
+    fn write_note_shape(&mut self, z_index: usize, note: &Note) {
+        let Some(drawing_info) = &note.drawing_info else {
+            return;
+        };
+
+        // Convert the pixel dimensions to points.
+        let left = drawing_info.col_absolute as f64 * 0.75;
+        let top = drawing_info.row_absolute as f64 * 0.75;
+        let width = drawing_info.width * 0.75;
+        let height = drawing_info.height * 0.75;
+
+        let visibility = if note.is_visible.unwrap_or(false) {
+            "visible"
+        } else {
+            "hidden"
+        };
+
+        let style = format!(
+            "position:absolute;\
+             margin-left:{left}pt;\
+             margin-top:{top}pt;\
+             width:{width}pt;\
+             height:{height}pt;\
+             z-index:{z_index};\
+             visibility:{visibility}"
+        );
+
+        let fill_color = format!("#{}", note.background_color.rgb_hex_value().to_lowercase());
+
+        let attributes = [
+            ("id", format!("_x0000_s{}", self.shape_id)),
+            ("type", "#_x0000_t202".to_string()),
+            ("style", style),
+            ("fillcolor", fill_color.clone()),
+            ("o:insetmode", "auto".to_string()),
+        ];
+
+        self.writer.xml_start_tag("v:shape", &attributes);
+
+        // Write the v:fill element.
+        self.writer
+            .xml_empty_tag("v:fill", &[("color2", fill_color.as_str())]);
+
+        // Write the v:shadow element.
+        let attributes = [("on", "t"), ("color", "black"), ("obscured", "t")];
+        self.writer.xml_empty_tag("v:shadow", &attributes);
+
+        // Write the v:path element.
+        self.writer
+            .xml_empty_tag("v:path", &[("o:connecttype", "none")]);
+
+        // Write the v:textbox element.
+        self.write_note_textbox();
+
+        // Write the x:ClientData element.
+        self.write_note_client_data(note);
+
+        self.writer.xml_end_tag("v:shape");
+    }
+
+    // Write the <v:textbox> element.
+    fn write_note_textbox(&mut self) {
+        let attributes = [("style", "mso-direction-alt:auto")];
+
+        self.writer.xml_start_tag("v:textbox", &attributes);
+
+        // Write the div element.
+        self.writer
+            .xml_start_tag("div", &[("style", "text-align:left")]);
+        self.writer.xml_end_tag("div");
+
+        self.writer.xml_end_tag("v:textbox");
+    }
+
+    // Write the <x:ClientData> element.
+    fn write_note_client_data(&mut self, note: &Note) {
+        let attributes = [("ObjectType", "Note")];
+
+        self.writer.xml_start_tag("x:ClientData", &attributes);
+
+        self.writer.xml_empty_tag_only("x:MoveWithCells");
+        self.writer.xml_empty_tag_only("x:SizeWithCells");
+
+        // Write the x:Anchor element.
+        if let Some(drawing_info) = &note.drawing_info {
+            let from = &drawing_info.from;
+            let to = &drawing_info.to;
+            let anchor = format!(
+                "{}, {}, {}, {}, {}, {}, {}, {}",
+                from.col,
+                from.col_offset,
+                from.row,
+                from.row_offset,
+                to.col,
+                to.col_offset,
+                to.row,
+                to.row_offset
+            );
+
+            self.writer.xml_data_element_only("x:Anchor", &anchor);
+        }
+
+        self.writer.xml_data_element_only("x:AutoFill", "False");
+        self.writer
+            .xml_data_element_only("x:Row", &note.cell_row.to_string());
+        self.writer
+            .xml_data_element_only("x:Column", &note.cell_col.to_string());
+
+        if note.is_visible.unwrap_or(false) {
+            self.writer.xml_empty_tag_only("x:Visible");
+        }
+
+        self.writer.xml_end_tag("x:ClientData");
+    }
 }
 
 // -----------------------------------------------------------------------
@@ -8,7 +8,7 @@
 mod theme_tests {
 
     use crate::vml::Vml;
-    use crate::{test_functions::vml_to_vec, vml::VmlInfo};
+    use crate::{test_functions::vml_to_vec, vml::VmlInfo, Note, Worksheet};
 
     use pretty_assertions::assert_eq;
 
@@ -141,4 +141,76 @@ mod theme_tests {
 
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_assemble_notes() {
+        let mut worksheet = Worksheet::new();
+        let note1 = Note::new("Some text");
+        let note2 = Note::new("Some text")
+            .set_visible(true)
+            .set_background_color("#FF0000");
+
+        worksheet.insert_note(1, 1, &note1).unwrap();
+        worksheet.insert_note(2, 3, &note2).unwrap();
+        worksheet.prepare_vml_objects(1, 1024, 1, 1);
+
+        let mut vml = Vml::new();
+        vml.notes.clone_from(&worksheet.notes_vml_info);
+        vml.data_id = 1;
+        vml.shape_id = 1024;
+
+        vml.assemble_xml_file();
+
+        let got = vml.writer.read_to_str();
+        let got = vml_to_vec(got);
+
+        let expected = vml_to_vec(
+            r##"
+                <xml xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office" xmlns:x="urn:schemas-microsoft-com:office:excel">
+                <o:shapelayout v:ext="edit">
+                  <o:idmap v:ext="edit" data="1"/>
+                </o:shapelayout>
+                <v:shapetype id="_x0000_t202" coordsize="21600,21600" o:spt="202" path="m,l,21600r21600,l21600,xe">
+                  <v:stroke joinstyle="miter"/>
+                  <v:path gradientshapeok="t" o:connecttype="rect"/>
+                </v:shapetype>
+                <v:shape id="_x0000_s1025" type="#_x0000_t202" style="position:absolute;margin-left:107.25pt;margin-top:7.5pt;width:96pt;height:55.5pt;z-index:1;visibility:hidden" fillcolor="#ffffe1" o:insetmode="auto">
+                  <v:fill color2="#ffffe1"/>
+                  <v:shadow on="t" color="black" obscured="t"/>
+                  <v:path o:connecttype="none"/>
+                  <v:textbox style="mso-direction-alt:auto">
+                    <div style="text-align:left"></div>
+                  </v:textbox>
+                  <x:ClientData ObjectType="Note">
+                    <x:MoveWithCells/>
+                    <x:SizeWithCells/>
+                    <x:Anchor>2, 15, 0, 10, 4, 15, 4, 4</x:Anchor>
+                    <x:AutoFill>False</x:AutoFill>
+                    <x:Row>1</x:Row>
+                    <x:Column>1</x:Column>
+                  </x:ClientData>
+                </v:shape>
+                <v:shape id="_x0000_s1026" type="#_x0000_t202" style="position:absolute;margin-left:203.25pt;margin-top:22.5pt;width:96pt;height:55.5pt;z-index:2;visibility:visible" fillcolor="#ff0000" o:insetmode="auto">
+                  <v:fill color2="#ff0000"/>
+                  <v:shadow on="t" color="black" obscured="t"/>
+                  <v:path o:connecttype="none"/>
+                  <v:textbox style="mso-direction-alt:auto">
+                    <div style="text-align:left"></div>
+                  </v:textbox>
+                  <x:ClientData ObjectType="Note">
+                    <x:MoveWithCells/>
+                    <x:SizeWithCells/>
+                    <x:Anchor>4, 15, 1, 10, 6, 15, 5, 4</x:Anchor>
+                    <x:AutoFill>False</x:AutoFill>
+                    <x:Row>2</x:Row>
+                    <x:Column>3</x:Column>
+                    <x:Visible/>
+                  </x:ClientData>
+                </v:shape>
+                </xml>
+            "##,
+        );
+
+        assert_eq!(expected, got);
+    }
 }
@@ -0,0 +1,140 @@
+// warning - A module for storing the warnings for ignored parameters.
+//
+// Some invalid, but non-fatal, parameters are ignored with a warning via the
+// log facade. The warnings can also be stored so that they can be raised as
+// an error when the workbook is saved in strict mode.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+mod tests;
+
+use crate::{ColNum, Color, RowNum};
+
+// A warning message along with the setting, and optionally the cell, that
+// raised it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Warning {
+    pub(crate) message: String,
+    pub(crate) cell: Option<(RowNum, ColNum)>,
+    key: &'static str,
+}
+
+// A store for the warnings raised by an object. Each warning is keyed by the
+// setting, and cell, that raised it so that it is replaced or cleared by a
+// later call for the same setting.
+//
+// Warnings are always stored, even if the workbook isn't in strict mode, since
+// strict mode can be turned on, or a worksheet can be added to a strict mode
+// workbook, after the warning is raised. The check is made when the workbook
+// is saved. Builder objects such as charts and formats pass their warnings on
+// to the worksheet that they are used with.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Warnings {
+    warnings: Vec<Warning>,
+}
+
+impl Warnings {
+    // Create a new warning store.
+    pub(crate) fn new() -> Warnings {
+        Warnings { warnings: vec![] }
+    }
+
+    // Log and store a warning for an ignored parameter.
+    pub(crate) fn warn(&mut self, key: &'static str, message: impl Into<String>) {
+        self.store(key, None, message.into());
+    }
+
+    // Log and store a warning for an ignored parameter that applies to a cell.
+    pub(crate) fn warn_at_cell(
+        &mut self,
+        key: &'static str,
+        row: RowNum,
+        col: ColNum,
+        message: impl Into<String>,
+    ) {
+        self.store(key, Some((row, col)), message.into());
+    }
+
+    // Clear the warning for a setting after a valid call.
+    pub(crate) fn clear(&mut self, key: &'static str) {
+        self.remove(key, None);
+    }
+
+    // Clear the warning for a setting at a cell after a valid call.
+    pub(crate) fn clear_at_cell(&mut self, key: &'static str, row: RowNum, col: ColNum) {
+        self.remove(key, Some((row, col)));
+    }
+
+    // Check that a color is valid and warn, or clear any previous warning, for
+    // the setting.
+    pub(crate) fn check_color(&mut self, key: &'static str, color: Color) -> bool {
+        match color.validate() {
+            Ok(()) => {
+                self.clear(key);
+                true
+            }
+            Err(message) => {
+                self.warn(key, message);
+                false
+            }
+        }
+    }
+
+    // Add the warnings from a builder object. These have already been logged.
+    pub(crate) fn extend(&mut self, other: &Warnings) {
+        for warning in &other.warnings {
+            if !self.warnings.contains(warning) {
+                self.warnings.push(warning.clone());
+            }
+        }
+    }
+
+    // Add the warning messages from a builder object that can't share its
+    // warnings directly. These have already been logged.
+    pub(crate) fn extend_messages(&mut self, key: &'static str, messages: Vec<String>) {
+        for message in messages {
+            let warning = Warning {
+                message,
+                cell: None,
+                key,
+            };
+
+            if !self.warnings.contains(&warning) {
+                self.warnings.push(warning);
+            }
+        }
+    }
+
+    // Get the first warning, if any.
+    pub(crate) fn first(&self) -> Option<&Warning> {
+        self.warnings.first()
+    }
+
+    // Get the warning messages.
+    pub(crate) fn messages(&self) -> Vec<String> {
+        self.warnings
+            .iter()
+            .map(|warning| warning.message.clone())
+            .collect()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    fn store(&mut self, key: &'static str, cell: Option<(RowNum, ColNum)>, message: String) {
+        log::warn!("{message}");
+
+        self.remove(key, cell);
+        self.warnings.push(Warning { message, cell, key });
+    }
+
+    fn remove(&mut self, key: &'static str, cell: Option<(RowNum, ColNum)>) {
+        if !self.warnings.is_empty() {
+            self.warnings
+                .retain(|warning| warning.key != key || warning.cell != cell);
+        }
+    }
+}
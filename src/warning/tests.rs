@@ -0,0 +1,67 @@
+// Warning unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2024, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod warning_tests {
+
+    use crate::warning::Warnings;
+    use crate::Color;
+
+    #[test]
+    fn warnings_stored() {
+        let mut warnings = Warnings::new();
+        assert!(warnings.is_empty());
+
+        warnings.warn("zoom", "Invalid zoom.");
+        assert_eq!(warnings.messages(), vec!["Invalid zoom."]);
+    }
+
+    #[test]
+    fn warnings_replaced_and_cleared_by_key() {
+        let mut warnings = Warnings::new();
+
+        warnings.warn("zoom", "Invalid zoom 1.");
+        warnings.warn("zoom", "Invalid zoom 2.");
+        warnings.warn("scale", "Invalid scale.");
+        assert_eq!(
+            warnings.messages(),
+            vec!["Invalid zoom 2.", "Invalid scale."]
+        );
+
+        warnings.clear("zoom");
+        assert_eq!(warnings.messages(), vec!["Invalid scale."]);
+
+        warnings.warn_at_cell("url", 1, 1, "Long url 1.");
+        warnings.warn_at_cell("url", 2, 2, "Long url 2.");
+        warnings.clear_at_cell("url", 1, 1);
+        assert_eq!(warnings.messages(), vec!["Invalid scale.", "Long url 2."]);
+
+        let warning = warnings.first().unwrap();
+        assert_eq!(warning.cell, None);
+    }
+
+    #[test]
+    fn warnings_check_color() {
+        let mut warnings = Warnings::new();
+
+        assert!(!warnings.check_color("color", Color::RGB(0x1000000)));
+        assert!(!warnings.is_empty());
+
+        assert!(warnings.check_color("color", Color::Red));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn warnings_extend() {
+        let mut builder = Warnings::new();
+        builder.warn("rotation", "Invalid rotation.");
+
+        let mut worksheet = Warnings::new();
+        worksheet.extend(&builder);
+        worksheet.extend(&builder);
+        assert_eq!(worksheet.messages(), vec!["Invalid rotation."]);
+    }
+}
@@ -220,17 +220,26 @@ mod tests;
 use std::collections::{HashMap, HashSet};
 use std::io::{Cursor, Seek, Write};
 use std::mem;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 
+use regex::Regex;
+
+#[cfg(feature = "encryption")]
+use crate::encryption::Encryptor;
 use crate::error::XlsxError;
-use crate::format::Format;
+use crate::format::{Format, FormatId};
 use crate::packager::Packager;
 use crate::packager::PackagerOptions;
-use crate::worksheet::Worksheet;
+use crate::protection::PasswordHash;
+use crate::warning::Warnings;
+use crate::worksheet::{Worksheet, COL_MAX, ROW_MAX};
 use crate::xmlwriter::XMLWriter;
 use crate::{
     utility, Border, Chart, ChartRange, ChartRangeCacheData, ColNum, DefinedName, DefinedNameType,
-    DocProperties, Fill, Font, Image, RowNum, Visible, NUM_IMAGE_FORMATS,
+    DocProperties, Fill, Font, Image, IndexSheetOptions, IntoExcelData, MemoryUsage, PageSetup,
+    RowNum, SheetVisibility, Url, NUM_IMAGE_FORMATS,
 };
 use crate::{Color, FormatPattern};
 
@@ -315,6 +324,21 @@ pub struct Workbook {
     pub(crate) num_formats: Vec<String>,
     pub(crate) has_hyperlink_style: bool,
     pub(crate) embedded_images: Vec<Image>,
+    pub(crate) vba_project: Option<Vec<u8>>,
+    pub(crate) vba_signature: Option<Vec<u8>>,
+    pub(crate) custom_xml_parts: Vec<(String, String, String)>,
+    registered_formats: HashMap<String, Format>,
+    format_ids: HashMap<Format, FormatId>,
+    compression_level: Option<u8>,
+    pub(crate) strict_mode: bool,
+    warnings: Warnings,
+    pub(crate) shared_strings_capacity: usize,
+    pub(crate) use_inline_strings: bool,
+    overwrite_policy: OverwritePolicy,
+    app_heading_pairs: Vec<(String, Vec<String>)>,
+    is_template: bool,
+    sanitize_sheet_names: bool,
+    cancellation_token: Option<Arc<AtomicBool>>,
     xf_indices: HashMap<Format, u32>,
     dxf_indices: HashMap<Format, u32>,
     active_tab: u16,
@@ -322,6 +346,19 @@ pub struct Workbook {
     defined_names: Vec<DefinedName>,
     user_defined_names: Vec<DefinedName>,
     read_only_mode: u8,
+    protection_on: bool,
+    protection_hash: u16,
+    protection_password_hash: Option<PasswordHash>,
+    protect_windows: bool,
+    tab_ratio: u16,
+    default_page_setup: PageSetup,
+    calc_mode: CalcMode,
+    calc_id: u32,
+    full_calc_on_load: bool,
+    iterative_calculation: bool,
+    max_iterations: u16,
+    max_change: f64,
+    vba_name: Option<String>,
 }
 
 impl Default for Workbook {
@@ -383,6 +420,34 @@ impl Workbook {
             border_count: 0,
             num_formats: vec![],
             read_only_mode: 0,
+            protection_on: false,
+            protection_hash: 0,
+            protection_password_hash: None,
+            protect_windows: false,
+            tab_ratio: 600,
+            default_page_setup: PageSetup::default(),
+            calc_mode: CalcMode::Auto,
+            calc_id: 124_519,
+            full_calc_on_load: true,
+            iterative_calculation: false,
+            max_iterations: 100,
+            max_change: 0.001,
+            vba_project: None,
+            vba_signature: None,
+            custom_xml_parts: vec![],
+            registered_formats: HashMap::new(),
+            format_ids: HashMap::new(),
+            compression_level: None,
+            strict_mode: false,
+            warnings: Warnings::new(),
+            shared_strings_capacity: 0,
+            use_inline_strings: false,
+            overwrite_policy: OverwritePolicy::Replace,
+            app_heading_pairs: vec![],
+            is_template: false,
+            sanitize_sheet_names: false,
+            cancellation_token: None,
+            vba_name: None,
             has_hyperlink_style: false,
             worksheets: vec![],
             xf_formats: vec![],
@@ -460,13 +525,67 @@ impl Workbook {
 
         let mut worksheet = Worksheet::new();
         worksheet.set_name(&name).unwrap();
-
         self.worksheets.push(worksheet);
         let worksheet = self.worksheets.last_mut().unwrap();
 
         worksheet
     }
 
+    /// Add a new worksheet with a given name to a workbook.
+    ///
+    /// The `add_worksheet_with_name()` method adds a new worksheet and sets its
+    /// name in one step. It is equivalent to calling
+    /// [`workbook.add_worksheet()`](Workbook::add_worksheet) followed by
+    /// [`worksheet.set_name()`](Worksheet::set_name) except that the name is
+    /// also checked against the names of the other worksheets in the workbook.
+    ///
+    /// If the sanitize mode has been turned on with
+    /// [`Workbook::set_sanitize_sheet_names()`] the name is converted into a
+    /// valid, unique, worksheet name instead of returning an error.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The worksheet name.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::SheetnameReused`] - Worksheet name is already in use in
+    ///   the workbook.
+    /// * [`XlsxError::SheetnameCannotBeBlank`] - Worksheet name cannot be
+    ///   blank.
+    /// * [`XlsxError::SheetnameLengthExceeded`] - Worksheet name exceeds
+    ///   Excel's limit of 31 characters.
+    /// * [`XlsxError::SheetnameContainsInvalidCharacter`] - Worksheet name
+    ///   cannot contain invalid characters: `[ ] : * ? / \`
+    /// * [`XlsxError::SheetnameStartsOrEndsWithApostrophe`] - Worksheet name
+    ///   cannot start or end with an apostrophe.
+    ///
+    /// The errors aren't raised in sanitize mode.
+    ///
+    pub fn add_worksheet_with_name(&mut self, name: &str) -> Result<&mut Worksheet, XlsxError> {
+        let existing_names = self.sheet_names();
+
+        let name = if self.sanitize_sheet_names {
+            utility::unique_sheetname(&utility::sanitize_sheet_name(name), &existing_names)
+        } else {
+            if existing_names
+                .iter()
+                .any(|existing| existing.to_lowercase() == name.to_lowercase())
+            {
+                return Err(XlsxError::SheetnameReused(name.to_string()));
+            }
+
+            name.to_string()
+        };
+
+        let mut worksheet = Worksheet::new();
+        worksheet.set_name(name)?;
+        self.worksheets.push(worksheet);
+        let worksheet = self.worksheets.last_mut().unwrap();
+
+        Ok(worksheet)
+    }
+
     /// Get a worksheet reference by index.
     ///
     /// Get a reference to a worksheet created via
@@ -737,6 +856,205 @@ impl Workbook {
         &self.worksheets
     }
 
+    /// Get the names of the worksheets in a workbook.
+    ///
+    /// The `sheet_names()` method returns the names of the worksheets in the
+    /// workbook in the order that they were added. This can be used to inspect
+    /// the structure of a workbook before accessing the worksheets with
+    /// [`Workbook::worksheet_from_name()`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates getting the worksheet names in a
+    /// workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_sheet_names.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     workbook.add_worksheet();
+    ///     workbook.add_worksheet().set_name("Data")?;
+    ///     workbook.add_worksheet();
+    ///
+    ///     assert_eq!(workbook.sheet_names(), vec!["Sheet1", "Data", "Sheet3"]);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn sheet_names(&self) -> Vec<String> {
+        self.worksheets
+            .iter()
+            .map(|worksheet| worksheet.name.clone())
+            .collect()
+    }
+
+    /// Remove a worksheet from the workbook.
+    ///
+    /// The `remove_worksheet()` method removes a worksheet from the workbook,
+    /// by name, and returns it. This can be useful in applications that
+    /// assemble the worksheets of a workbook conditionally.
+    ///
+    /// If the removed worksheet was the active worksheet then the first
+    /// visible worksheet in the workbook is made active, and any other
+    /// worksheets that were selected along with it are deselected.
+    ///
+    /// The references to the removed worksheet elsewhere in the workbook are
+    /// handled in the same way as Excel:
+    ///
+    /// - Internal hyperlinks in the remaining worksheets that refer to the
+    ///   removed worksheet are removed, since they would be invalid. The text
+    ///   of the hyperlink cells is retained.
+    /// - Defined names that are local to the removed worksheet, see
+    ///   [`Workbook::define_local_name()`], are removed.
+    /// - References to the removed worksheet in other defined names are
+    ///   changed to `#REF!` errors. For example `=Sheet2!$A$1` becomes
+    ///   `=#REF!$A$1`.
+    ///
+    /// Formulas in the remaining worksheets aren't changed.
+    ///
+    /// Note, worksheet names that were generated automatically, such as
+    /// `Sheet3`, aren't renamed when a previous worksheet is removed.
+    ///
+    /// # Parameters
+    ///
+    /// * `sheetname` - The name of the worksheet to remove. Like Excel, the
+    ///   name is matched case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::UnknownWorksheetNameOrIndex`] - Error when trying to
+    ///   remove a worksheet name that doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates removing and moving worksheets in
+    /// a workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_remove_worksheet.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     workbook.add_worksheet().set_name("Summary")?;
+    ///     workbook.add_worksheet().set_name("Data")?;
+    ///     workbook.add_worksheet().set_name("Scratch")?;
+    ///
+    ///     // Remove a worksheet that isn't required.
+    ///     workbook.remove_worksheet("Scratch")?;
+    ///
+    ///     // Move the "Data" worksheet to the start of the workbook.
+    ///     workbook.move_worksheet(1, 0)?;
+    ///
+    ///     assert_eq!(workbook.sheet_names(), vec!["Data", "Summary"]);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn remove_worksheet(&mut self, sheetname: &str) -> Result<Worksheet, XlsxError> {
+        let sheetname_lower = sheetname.to_lowercase();
+
+        let Some(index) = self
+            .worksheets
+            .iter()
+            .position(|worksheet| worksheet.name.to_lowercase() == sheetname_lower)
+        else {
+            return Err(XlsxError::UnknownWorksheetNameOrIndex(
+                sheetname.to_string(),
+            ));
+        };
+
+        let worksheet = self.worksheets.remove(index);
+
+        for other in &mut self.worksheets {
+            other.remove_internal_hyperlinks(&worksheet.name);
+        }
+
+        // Like Excel, remove the names that are local to the worksheet and
+        // change the references to it in other names to `#REF!` errors.
+        self.user_defined_names.retain(|defined_name| {
+            defined_name
+                .unquoted_sheet_name()
+                .replace("''", "'")
+                .to_lowercase()
+                != sheetname_lower
+        });
+
+        for defined_name in &mut self.user_defined_names {
+            defined_name.range =
+                utility::replace_sheet_references(&defined_name.range, &worksheet.name);
+        }
+
+        // The worksheets that were selected along with a removed active
+        // worksheet are deselected so that only the new active worksheet is
+        // selected.
+        if worksheet.active {
+            for other in &mut self.worksheets {
+                other.selected = false;
+            }
+        }
+
+        self.update_active_worksheet();
+
+        Ok(worksheet)
+    }
+
+    /// Move a worksheet to a new position in the workbook.
+    ///
+    /// The `move_worksheet()` method moves the worksheet at `from_index` to
+    /// `to_index`, shifting the worksheets in between. The indexes are zero
+    /// based and `to_index` is the position of the worksheet after the move.
+    ///
+    /// The active, selected and first visible worksheet settings move with
+    /// the worksheet that they were set on. If no worksheet has been set as
+    /// active, and a hidden worksheet is moved to the first position, then
+    /// the first visible worksheet is made active so that the hidden
+    /// worksheet isn't shown. Internal hyperlinks and defined names refer to
+    /// worksheets by name so they don't need to be updated.
+    ///
+    /// See the example in [`Workbook::remove_worksheet()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `from_index` - The current index of the worksheet.
+    /// * `to_index` - The new index of the worksheet.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::UnknownWorksheetNameOrIndex`] - Error when either index
+    ///   is out of range for the worksheets in the workbook.
+    ///
+    pub fn move_worksheet(
+        &mut self,
+        from_index: usize,
+        to_index: usize,
+    ) -> Result<&mut Workbook, XlsxError> {
+        for index in [from_index, to_index] {
+            if index >= self.worksheets.len() {
+                return Err(XlsxError::UnknownWorksheetNameOrIndex(index.to_string()));
+            }
+        }
+
+        let worksheet = self.worksheets.remove(from_index);
+        self.worksheets.insert(to_index, worksheet);
+
+        self.update_active_worksheet();
+
+        Ok(self)
+    }
+
     /// Add a worksheet object to a workbook.
     ///
     /// Add a worksheet created directly using `Workbook::new()` to a workbook.
@@ -802,475 +1120,2635 @@ impl Workbook {
         self.worksheets.push(worksheet);
     }
 
-    /// Save the Workbook as an xlsx file.
+    /// Add worksheets for a dataset that may exceed the worksheet row limit.
     ///
-    /// The workbook `save()` method writes all the Workbook data to a new xlsx
-    /// file. It will overwrite any existing file.
+    /// An Excel worksheet can have a maximum of 1,048,576 rows. The
+    /// `add_worksheets_from_rows()` method writes rows of data from an
+    /// iterator to a new worksheet and, when the row limit is reached, spills
+    /// the remaining rows into further worksheets called `name (2)`, `name
+    /// (3)`, and so on, instead of returning an error part way through the
+    /// export.
     ///
-    /// The `save()` method can be called multiple times so it is possible to
-    /// get incremental files at different stages of a process, or to save the
-    /// same Workbook object to different paths. However, `save()` is an
-    /// expensive operation which assembles multiple files into an xlsx/zip
-    /// container so for performance reasons you shouldn't call it
-    /// unnecessarily.
+    /// If `repeat_header` is `true` the first row of the data is treated as a
+    /// header and is repeated at the top of each additional worksheet.
     ///
     /// # Parameters
     ///
-    /// * `path` - The path of the new Excel file to create as a `&str` or as a
-    ///   [`std::path`] `Path` or `PathBuf` instance.
+    /// * `name` - The name of the first worksheet. See
+    ///   [`Worksheet::set_name()`] for the naming rules.
+    /// * `rows` - An iterator of rows where each row is an iterator of types
+    ///   that implement [`IntoExcelData`](crate::IntoExcelData).
+    /// * `repeat_header` - Repeat the first row at the top of each worksheet.
+    ///
+    /// Returns the number of worksheets that were added.
     ///
     /// # Errors
     ///
-    /// * [`XlsxError::SheetnameReused`] - Worksheet name is already in use in
-    ///   the workbook.
-    /// * [`XlsxError::TableNameReused`] - Worksheet Table name is already in
-    ///   use in the workbook.
-    /// * [`XlsxError::IoError`] - A wrapper for various IO errors when creating
-    ///   the xlsx file, or its sub-files.
-    /// * [`XlsxError::ZipError`] - A wrapper for various zip errors when
-    ///   creating the xlsx file, or its sub-files.
+    /// * [`XlsxError::SheetnameCannotBeBlank`] - Worksheet name cannot be
+    ///   blank.
+    /// * [`XlsxError::SheetnameLengthExceeded`] - Worksheet name, including
+    ///   the numeric suffix, exceeds Excel's limit of 31 characters.
+    /// * [`XlsxError::SheetnameContainsInvalidCharacter`] - Worksheet name
+    ///   cannot contain invalid characters: `[ ] : * ? / \`
+    /// * [`XlsxError::SheetnameStartsOrEndsWithApostrophe`] - Worksheet name
+    ///   cannot start or end with an apostrophe.
+    /// * [`XlsxError::SheetnameReused`] - Worksheet name, including the
+    ///   numeric suffix, is already in use in the workbook.
+    /// * [`XlsxError::RowColumnLimitError`] - Column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
     ///
     /// # Examples
     ///
-    /// The following example demonstrates creating a simple workbook using a
-    /// string path.
+    /// The following example demonstrates writing a dataset that is split
+    /// across worksheets when it exceeds the Excel row limit.
     ///
     /// ```
-    /// # // This code is available in examples/doc_workbook_save.rs
+    /// # // This code is available in examples/doc_workbook_add_worksheets_from_rows.rs
     /// #
     /// # use rust_xlsxwriter::{Workbook, XlsxError};
     /// #
-    /// fn main() -> Result<(), XlsxError> {
-    ///     let mut workbook = Workbook::new();
-    ///
-    ///     let _worksheet = workbook.add_worksheet();
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     // A header row followed by some data rows.
+    ///     let header = vec!["Id".to_string(), "Name".to_string()];
+    ///     let data = (1..=10).map(|id| vec![id.to_string(), format!("Item {id}")]);
+    ///     let rows = std::iter::once(header).chain(data);
     ///
-    ///     workbook.save("workbook.xlsx")?;
+    ///     // Write the data. Additional worksheets are added if required.
+    ///     let count = workbook.add_worksheets_from_rows("Data", rows, true)?;
+    ///     assert_eq!(count, 1);
     ///
-    ///     Ok(())
-    /// }
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
     /// ```
     ///
-    /// The following example demonstrates creating a simple workbook using a
-    /// Rust [`std::path`] Path.
+    pub fn add_worksheets_from_rows<I, R>(
+        &mut self,
+        name: &str,
+        rows: I,
+        repeat_header: bool,
+    ) -> Result<usize, XlsxError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator,
+        R::Item: IntoExcelData + Clone,
+    {
+        self.add_worksheets_from_rows_with_limit(name, rows, repeat_header, ROW_MAX)
+    }
+
+    // Write rows to worksheets with a given row limit per worksheet. The limit
+    // is a parameter so that the chunking can be tested with small datasets.
+    pub(crate) fn add_worksheets_from_rows_with_limit<I, R>(
+        &mut self,
+        name: &str,
+        rows: I,
+        repeat_header: bool,
+        max_rows: RowNum,
+    ) -> Result<usize, XlsxError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator,
+        R::Item: IntoExcelData + Clone,
+    {
+        let mut header: Option<Vec<R::Item>> = None;
+        let mut sheet_count = 1;
+        let mut row_num = 0;
+
+        // The worksheet names are validated before the worksheets are added so
+        // that an invalid name doesn't leave an unnamed worksheet behind.
+        let mut worksheet = self.add_worksheet_with_name(name)?;
+
+        for row in rows {
+            if row_num >= max_rows {
+                sheet_count += 1;
+                row_num = 0;
+
+                worksheet = self.add_worksheet_with_name(&format!("{name} ({sheet_count})"))?;
+
+                if let Some(header) = &header {
+                    worksheet.write_row(row_num, 0, header.iter().cloned())?;
+                    row_num += 1;
+                }
+            }
+
+            if repeat_header && header.is_none() {
+                let data: Vec<R::Item> = row.into_iter().collect();
+                worksheet.write_row(row_num, 0, data.iter().cloned())?;
+                header = Some(data);
+            } else {
+                worksheet.write_row(row_num, 0, row)?;
+            }
+
+            row_num += 1;
+        }
+
+        Ok(sheet_count)
+    }
+
+    /// Add an index worksheet with links to the other worksheets.
+    ///
+    /// The `add_index_sheet()` method adds a table of contents worksheet as
+    /// the first worksheet in the workbook. It contains an internal hyperlink
+    /// to cell `A1` of each of the other visible worksheets, in workbook
+    /// order. Optionally, each of the other worksheets can also get a link
+    /// back to the index, see [`IndexSheetOptions::set_back_link_cell()`].
+    ///
+    /// The index only links to worksheets that exist when the method is called
+    /// so it should be called after all the other worksheets have been added.
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - The [`IndexSheetOptions`] for the index worksheet.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::SheetnameReused`] - The index worksheet name is already
+    ///   in use.
+    /// * [`XlsxError::SheetnameCannotBeBlank`] - Worksheet name cannot be
+    ///   blank.
+    /// * [`XlsxError::SheetnameLengthExceeded`] - Worksheet name exceeds
+    ///   Excel's limit of 31 characters.
+    /// * [`XlsxError::SheetnameContainsInvalidCharacter`] - Worksheet name
+    ///   cannot contain invalid characters: `[ ] : * ? / \`
+    /// * [`XlsxError::SheetnameStartsOrEndsWithApostrophe`] - Worksheet name
+    ///   cannot start or end with an apostrophe.
+    /// * [`XlsxError::RowColumnLimitError`] - The back link cell exceeds
+    ///   Excel's worksheet limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates adding an index worksheet with links
+    /// to the other worksheets in a workbook.
     ///
     /// ```
-    /// # // This code is available in examples/doc_workbook_save_to_path.rs
+    /// # // This code is available in examples/doc_workbook_add_index_sheet.rs
     /// #
-    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// # use rust_xlsxwriter::{IndexSheetOptions, Workbook, XlsxError};
     /// #
-    /// fn main() -> Result<(), XlsxError> {
-    ///     let mut workbook = Workbook::new();
-    ///
-    ///     let _worksheet = workbook.add_worksheet();
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     workbook.add_worksheet().set_name("Sales")?;
+    ///     workbook.add_worksheet().set_name("Costs")?;
+    ///     workbook.add_worksheet().set_name("Summary")?;
     ///
-    ///     let path = std::path::Path::new("workbook.xlsx");
-    ///     workbook.save(path)?;
+    ///     // Add an index sheet with a link back to it in cell H1 of each sheet.
+    ///     let options = IndexSheetOptions::new()
+    ///         .set_title("Contents")
+    ///         .set_back_link_cell(0, 7);
     ///
-    ///     Ok(())
-    /// }
+    ///     workbook.add_index_sheet(&options)?;
+    /// #
+    /// #     workbook.save("index_sheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
     /// ```
     ///
-    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<(), XlsxError> {
-        #[cfg(feature = "test-resave")]
+    pub fn add_index_sheet(
+        &mut self,
+        options: &IndexSheetOptions,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        if self
+            .worksheets
+            .iter()
+            .any(|worksheet| worksheet.name.to_lowercase() == options.name.to_lowercase())
         {
-            // Some test code to test double/multiple saves.
-            let file = std::fs::File::create(<&std::path::Path>::clone(&path.as_ref()))?;
-            self.save_internal(file)?;
+            return Err(XlsxError::SheetnameReused(options.name.clone()));
         }
 
-        let file = std::fs::File::create(path)?;
-        self.save_internal(file)?;
-        Ok(())
+        let mut index_sheet = Worksheet::new();
+        index_sheet.set_name(&options.name)?;
+        let mut row = 0;
+        if let Some(title) = &options.title {
+            index_sheet.write_string_with_format(row, 0, title, &Format::new().set_bold())?;
+            row += 2;
+        }
+
+        let back_link = Url::new(format!(
+            "internal:{}!A1",
+            utility::quote_sheetname(&options.name)
+        ))
+        .set_text(&options.back_link_text);
+
+        let mut max_width = 0;
+        for worksheet in &mut self.worksheets {
+            if worksheet.visible != SheetVisibility::Visible {
+                continue;
+            }
+
+            let link = Url::new(format!(
+                "internal:{}!A1",
+                utility::quote_sheetname(&worksheet.name)
+            ))
+            .set_text(&worksheet.name);
+
+            index_sheet.write_url(row, 0, link)?;
+            max_width = max_width.max(worksheet.name.chars().count());
+            row += 1;
+
+            if let Some((back_row, back_col)) = options.back_link_cell {
+                worksheet.write_url(back_row, back_col, back_link.clone())?;
+            }
+        }
+
+        if max_width > 0 {
+            index_sheet.set_column_width(0, max_width.max(8) as f64 + 2.0)?;
+        }
+
+        self.worksheets.insert(0, index_sheet);
+
+        Ok(&mut self.worksheets[0])
     }
 
-    /// Save the Workbook as an xlsx file and return it as a byte vector.
+    /// Register a named format with the workbook.
     ///
-    /// The workbook `save_to_buffer()` method is similar to the
-    /// [`save()`](Workbook::save) method except that it returns the xlsx file
-    /// as a `Vec<u8>` buffer suitable for streaming in a web application.
+    /// The `register_format()` method stores a [`Format`] in the workbook
+    /// under a user defined name so that it can be retrieved later with
+    /// [`Workbook::format_by_name()`]. This allows applications to define
+    /// their styles in one place instead of passing `&Format` references
+    /// through several layers of function calls.
+    ///
+    /// Registering a format with a name that is already in use replaces the
+    /// previous format. Names are case sensitive.
+    ///
+    /// Registered formats are only a convenience for looking up formats. They
+    /// don't add any additional styles to the output file unless they are
+    /// used in a worksheet.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name to store the format under.
+    /// * `format` - The [`Format`] property for the name.
     ///
     /// # Errors
     ///
-    /// * [`XlsxError::SheetnameReused`] - Worksheet name is already in use in
-    ///   the workbook.
-    /// * [`XlsxError::IoError`] - A wrapper for various IO errors when creating
-    ///   the xlsx file, or its sub-files.
-    /// * [`XlsxError::ZipError`] - A wrapper for various zip errors when
-    ///   creating the xlsx file, or its sub-files.
+    /// * [`XlsxError::ParameterError`] - The name is blank.
     ///
     /// # Examples
     ///
-    /// The following example demonstrates creating a simple workbook to a
-    /// `Vec<u8>` buffer.
+    /// The following example demonstrates registering named formats with a
+    /// workbook and looking them up when writing data.
     ///
     /// ```
-    /// # // This code is available in examples/doc_workbook_save_to_buffer.rs
+    /// # // This code is available in examples/doc_workbook_register_format.rs
     /// #
-    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// # use rust_xlsxwriter::{Format, Workbook, XlsxError};
     /// #
     /// fn main() -> Result<(), XlsxError> {
     ///     let mut workbook = Workbook::new();
     ///
-    ///     let worksheet = workbook.add_worksheet();
-    ///     worksheet.write_string(0, 0, "Hello")?;
+    ///     // Define the styles used in the workbook in one place.
+    ///     workbook.register_format("header", &Format::new().set_bold())?;
+    ///     workbook.register_format("currency", &Format::new().set_num_format("$#,##0.00"))?;
     ///
-    ///     let buf = workbook.save_to_buffer()?;
+    ///     write_report(&mut workbook)?;
     ///
-    ///     println!("File size: {}", buf.len());
+    ///     workbook.save("formats.xlsx")?;
     ///
     ///     Ok(())
     /// }
-    /// ```
     ///
-    pub fn save_to_buffer(&mut self) -> Result<Vec<u8>, XlsxError> {
-        let mut buf = vec![];
-        let cursor = Cursor::new(&mut buf);
-        self.save_internal(cursor)?;
-        Ok(buf)
-    }
-
-    /// Save the Workbook as an xlsx file to a user supplied file/buffer.
+    /// // Look up the formats by name instead of passing them as parameters.
+    /// fn write_report(workbook: &mut Workbook) -> Result<(), XlsxError> {
+    ///     let header = workbook.format_by_name("header")?;
+    ///     let currency = workbook.format_by_name("currency")?;
     ///
-    /// The workbook `save_to_writer()` method is similar to the
-    /// [`save()`](Workbook::save) method except that it writes the xlsx file to
-    /// types that implement the [`Write`] trait such as the [`std::fs::File`]
-    /// type or buffers.
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write_with_format(0, 0, "Sales", &header)?;
+    ///     worksheet.write_with_format(1, 0, 1234.5, &currency)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn register_format(
+        &mut self,
+        name: impl Into<String>,
+        format: &Format,
+    ) -> Result<&mut Workbook, XlsxError> {
+        let name = name.into();
+
+        if name.is_empty() {
+            return Err(XlsxError::ParameterError(
+                "Registered format name cannot be blank".to_string(),
+            ));
+        }
+
+        self.registered_formats.insert(name, format.clone());
+
+        Ok(self)
+    }
+
+    /// Get a format that was registered with the workbook by name.
+    ///
+    /// Returns a copy of a [`Format`] that was stored using
+    /// [`Workbook::register_format()`]. A copy is returned so that the format
+    /// can be used with a worksheet while the workbook is borrowed mutably.
+    /// See the example in [`Workbook::register_format()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the registered format.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - There is no format registered with
+    ///   the name.
+    ///
+    pub fn format_by_name(&self, name: &str) -> Result<Format, XlsxError> {
+        match self.registered_formats.get(name) {
+            Some(format) => Ok(format.clone()),
+            None => Err(XlsxError::ParameterError(format!(
+                "Unknown registered format name '{name}'"
+            ))),
+        }
+    }
+
+    /// Get an interned id for a format to reduce the overhead of formatted
+    /// writes.
+    ///
+    /// Each formatted write requires the worksheet to look up all of the
+    /// properties of the [`Format`] to find its index. For large or wide
+    /// formatted tables this can be a noticeable overhead. The `format_id()`
+    /// method interns the format once and returns a [`FormatId`] that the
+    /// worksheets can resolve cheaply.
+    ///
+    /// A `FormatId` dereferences to a [`Format`] so it can be used with any of
+    /// the worksheet `*_with_format()` methods. Equal formats return the same
+    /// id.
+    ///
+    /// # Parameters
+    ///
+    /// * `format` - The [`Format`] to intern.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating an interned format id and
+    /// using it to write a large range of formatted cells.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_format_id.rs
+    /// #
+    /// # use rust_xlsxwriter::{Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     // Intern the format once.
+    ///     let format = workbook.format_id(&Format::new().set_num_format("0.00"));
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     for row in 0..1000 {
+    ///         for col in 0..20 {
+    ///             worksheet.write_number_with_format(row, col, row, &format)?;
+    ///         }
+    ///     }
+    /// #
+    /// #     workbook.save("formats.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn format_id(&mut self, format: &Format) -> FormatId {
+        self.format_ids
+            .entry(format.clone())
+            .or_insert_with(|| FormatId::new(format))
+            .clone()
+    }
+
+    /// Get the approximate memory used by the workbook data.
+    ///
+    /// The `memory_usage()` method returns a [`MemoryUsage`] struct with the
+    /// approximate number of bytes held by the cell data, string data and
+    /// media, such as images and VBA projects, in all of the worksheets in the
+    /// workbook. See [`Worksheet::memory_usage()`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates enforcing a memory budget while
+    /// writing data to a workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_memory_usage.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///     workbook.add_worksheet();
+    ///
+    ///     let budget = 10 * 1024 * 1024;
+    ///
+    ///     for row in 0..10_000 {
+    ///         let worksheet = workbook.worksheet_from_index(0)?;
+    ///         worksheet.write(row, 0, format!("Item {row}"))?;
+    ///
+    ///         if row % 1000 == 0 && workbook.memory_usage().total() > budget {
+    ///             return Err(XlsxError::ParameterError("Memory budget exceeded".into()));
+    ///         }
+    ///     }
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage::default();
+
+        for worksheet in &self.worksheets {
+            usage += worksheet.memory_usage();
+        }
+
+        if let Some(vba_project) = &self.vba_project {
+            usage.media += vba_project.len();
+        }
+
+        for (xml, schema, _) in &self.custom_xml_parts {
+            usage.strings += xml.len() + schema.len();
+        }
+
+        usage
+    }
+
+    /// Check the workbook for problems before it is saved.
+    ///
+    /// Some workbook errors are only detected when the file is saved, or not
+    /// at all, in which case Excel will complain about the file and try to
+    /// repair it when it is opened. The `validate()` method checks for these
+    /// issues in advance and returns a list of all the problems found so that
+    /// an application can report them together. An empty list means that no
+    /// problems were found.
+    ///
+    /// The following checks are made:
+    ///
+    /// * Duplicate worksheet names ([`XlsxError::SheetnameReused`]).
+    /// * Worksheets named `History`, which is reserved by Excel
+    ///   ([`XlsxError::ParameterError`]).
+    /// * Duplicate worksheet table names ([`XlsxError::TableNameReused`]).
+    /// * Chart ranges that refer to unknown worksheets
+    ///   ([`XlsxError::UnknownWorksheetNameOrIndex`]).
+    /// * Header and footer images without a matching `&[Picture]` or `&G`
+    ///   variable in the header or footer string
+    ///   ([`XlsxError::ParameterError`]).
+    /// * Formula syntax errors, see
+    ///   [`Formula::validate()`](crate::Formula::validate)
+    ///   ([`XlsxError::FormulaError`]).
+    ///
+    /// Problems that belong to a worksheet are wrapped in a
+    /// [`XlsxError::WorksheetError`] with the worksheet name and, where
+    /// available, the cell of the formula, table or chart. The underlying
+    /// error can be retrieved with [`XlsxError::inner()`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates validating a workbook before saving
+    /// it.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_validate.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Add a chart that refers to a worksheet that doesn't exist.
+    ///     let mut chart = Chart::new(ChartType::Column);
+    ///     chart.add_series().set_values("Data!$A$1:$A$5");
+    ///     worksheet.insert_chart(0, 2, &chart)?;
+    ///
+    ///     // Check the workbook before saving it.
+    ///     for problem in workbook.validate() {
+    ///         println!("{problem}");
+    ///     }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn validate(&self) -> Vec<XlsxError> {
+        let mut errors = vec![];
+
+        // Defined names can be used as functions, for LAMBDA formulas.
+        let user_functions: Vec<String> = self
+            .user_defined_names
+            .iter()
+            .map(|defined_name| defined_name.name.clone())
+            .collect();
+
+        let mut unique_worksheet_names = HashSet::new();
+        for worksheet in &self.worksheets {
+            let worksheet_name = worksheet.name.to_lowercase();
+
+            if !unique_worksheet_names.insert(worksheet_name.clone()) {
+                errors.push(XlsxError::SheetnameReused(worksheet.name.clone()));
+            }
+
+            if worksheet_name == "history" {
+                errors.push(
+                    XlsxError::ParameterError(format!(
+                        "Worksheet name '{}' is reserved by Excel",
+                        worksheet.name
+                    ))
+                    .in_worksheet(&worksheet.name, None),
+                );
+            }
+
+            for error in worksheet.header_footer_image_errors() {
+                errors.push(error.in_worksheet(&worksheet.name, None));
+            }
+
+            for (row, col, message) in worksheet.formula_errors(&user_functions) {
+                errors.push(
+                    XlsxError::FormulaError(message)
+                        .in_worksheet(&worksheet.name, Some((row, col))),
+                );
+            }
+        }
+
+        let mut seen_table_names = HashSet::new();
+        for worksheet in &self.worksheets {
+            for table in &worksheet.tables {
+                if !table.name.is_empty() && !seen_table_names.insert(table.name.to_lowercase()) {
+                    let cell = (table.cell_range.first_row, table.cell_range.first_col);
+                    errors.push(
+                        XlsxError::TableNameReused(table.name.clone())
+                            .in_worksheet(&worksheet.name, Some(cell)),
+                    );
+                }
+            }
+        }
+
+        for worksheet in &self.worksheets {
+            for (cell, chart) in &worksheet.charts {
+                for key in Self::chart_range_keys(chart) {
+                    if !self.worksheets.iter().any(|sheet| sheet.name == key.0) {
+                        errors.push(
+                            Self::unknown_chart_worksheet_error(&key)
+                                .in_worksheet(&worksheet.name, Some(*cell)),
+                        );
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Save the Workbook as an xlsx file.
+    ///
+    /// The workbook `save()` method writes all the Workbook data to a new xlsx
+    /// file. By default it will overwrite any existing file. This can be
+    /// changed with [`Workbook::set_overwrite_policy()`].
+    ///
+    /// The file is first written to a temporary file in the destination
+    /// directory and then renamed to the target path once it is complete. This
+    /// means that a failed save doesn't leave a truncated or partial file in
+    /// place of an existing file.
+    ///
+    /// The `save()` method can be called multiple times so it is possible to
+    /// get incremental files at different stages of a process, or to save the
+    /// same Workbook object to different paths. However, `save()` is an
+    /// expensive operation which assembles multiple files into an xlsx/zip
+    /// container so for performance reasons you shouldn't call it
+    /// unnecessarily.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path of the new Excel file to create as a `&str` or as a
+    ///   [`std::path`] `Path` or `PathBuf` instance.
     ///
     /// # Errors
     ///
     /// * [`XlsxError::SheetnameReused`] - Worksheet name is already in use in
     ///   the workbook.
+    /// * [`XlsxError::TableNameReused`] - Worksheet Table name is already in
+    ///   use in the workbook. This is returned wrapped in a
+    ///   [`XlsxError::WorksheetError`], see below.
+    /// * [`XlsxError::ParameterError`] - The workbook contains a VBA project
+    ///   or is a template and the file doesn't have the matching `.xlsm`,
+    ///   `.xltx` or `.xltm` extension.
     /// * [`XlsxError::IoError`] - A wrapper for various IO errors when creating
-    ///   the xlsx file, or its sub-files.
+    ///   the xlsx file, or its sub-files. This includes an
+    ///   [`std::io::ErrorKind::AlreadyExists`] error if the file exists and the
+    ///   overwrite policy is [`OverwritePolicy::Error`].
     /// * [`XlsxError::ZipError`] - A wrapper for various zip errors when
     ///   creating the xlsx file, or its sub-files.
+    /// * [`XlsxError::WorksheetError`] - Errors that occur while writing a
+    ///   particular worksheet, such as a [`XlsxError::TableNameReused`],
+    ///   [`XlsxError::IoError`] or [`XlsxError::ZipError`] error, are wrapped
+    ///   with the worksheet name and, where available, the cell. The
+    ///   underlying error can be retrieved with [`XlsxError::inner()`].
     ///
     /// # Examples
     ///
-    /// The following example demonstrates creating a simple workbook to some
-    /// types that implement the `Write` trait like a file and a buffer.
+    /// The following example demonstrates creating a simple workbook using a
+    /// string path.
     ///
     /// ```
-    /// # // This code is available in examples/doc_workbook_save_to_writer.rs
+    /// # // This code is available in examples/doc_workbook_save.rs
     /// #
-    /// # use std::fs::File;
-    /// # use std::io::{Cursor, Write};
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet = workbook.add_worksheet();
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// The following example demonstrates creating a simple workbook using a
+    /// Rust [`std::path`] Path.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_save_to_path.rs
     /// #
     /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet = workbook.add_worksheet();
+    ///
+    ///     let path = std::path::Path::new("workbook.xlsx");
+    ///     workbook.save(path)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<(), XlsxError> {
+        self.check_file_extension(path.as_ref())?;
+
+        #[cfg(feature = "test-resave")]
+        {
+            // Some test code to test double/multiple saves.
+            let file = std::fs::File::create(<&std::path::Path>::clone(&path.as_ref()))?;
+            self.save_internal(file)?;
+        }
+
+        self.save_atomically(path.as_ref(), |workbook, file| workbook.save_internal(file))
+    }
+
+    /// Save the Workbook as an xlsx file and return it as a byte vector.
+    ///
+    /// The workbook `save_to_buffer()` method is similar to the
+    /// [`save()`](Workbook::save) method except that it returns the xlsx file
+    /// as a `Vec<u8>` buffer suitable for streaming in a web application.
+    ///
+    /// Since there is no file name the `.xlsm` extension check for workbooks
+    /// with a VBA project isn't applied. In that case the buffer should be
+    /// served or saved with an `.xlsm` file name and the
+    /// `application/vnd.ms-excel.sheet.macroEnabled.12` MIME type.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::SheetnameReused`] - Worksheet name is already in use in
+    ///   the workbook.
+    /// * [`XlsxError::TableNameReused`] - Worksheet Table name is already in
+    ///   use in the workbook. This is returned wrapped in a
+    ///   [`XlsxError::WorksheetError`], see below.
+    /// * [`XlsxError::IoError`] - A wrapper for various IO errors when creating
+    ///   the xlsx file, or its sub-files.
+    /// * [`XlsxError::ZipError`] - A wrapper for various zip errors when
+    ///   creating the xlsx file, or its sub-files.
+    /// * [`XlsxError::WorksheetError`] - Errors that occur while writing a
+    ///   particular worksheet, such as a [`XlsxError::TableNameReused`],
+    ///   [`XlsxError::IoError`] or [`XlsxError::ZipError`] error, are wrapped
+    ///   with the worksheet name and, where available, the cell. The
+    ///   underlying error can be retrieved with [`XlsxError::inner()`].
+    ///
+    /// # Examples
     ///
+    /// The following example demonstrates creating a simple workbook to a
+    /// `Vec<u8>` buffer.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_save_to_buffer.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
     /// fn main() -> Result<(), XlsxError> {
     ///     let mut workbook = Workbook::new();
     ///
     ///     let worksheet = workbook.add_worksheet();
     ///     worksheet.write_string(0, 0, "Hello")?;
     ///
-    ///     // Save the file to a File object.
-    ///     let file = File::create("workbook1.xlsx")?;
-    ///     workbook.save_to_writer(file)?;
+    ///     let buf = workbook.save_to_buffer()?;
     ///
-    ///     // Save the file to a buffer. It is wrapped in a Cursor because it need to
-    ///     // implement the `Seek` trait.
-    ///     let mut cursor = Cursor::new(Vec::new());
-    ///     workbook.save_to_writer(&mut cursor)?;
+    ///     println!("File size: {}", buf.len());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn save_to_buffer(&mut self) -> Result<Vec<u8>, XlsxError> {
+        let mut buf = vec![];
+        let cursor = Cursor::new(&mut buf);
+        self.save_internal(cursor)?;
+        Ok(buf)
+    }
+
+    /// Save the Workbook as an xlsx file to a user supplied file/buffer.
+    ///
+    /// The workbook `save_to_writer()` method is similar to the
+    /// [`save()`](Workbook::save) method except that it writes the xlsx file to
+    /// types that implement the [`Write`] trait such as the [`std::fs::File`]
+    /// type or buffers.
+    ///
+    /// The writer must also implement the [`Seek`] trait, which is required to
+    /// write the zip container, but it doesn't need to be [`Send`]. This allows
+    /// the file to be written directly to sockets, upload streams or other
+    /// custom sinks without an intermediate buffer.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::SheetnameReused`] - Worksheet name is already in use in
+    ///   the workbook.
+    /// * [`XlsxError::IoError`] - A wrapper for various IO errors when creating
+    ///   the xlsx file, or its sub-files.
+    /// * [`XlsxError::ZipError`] - A wrapper for various zip errors when
+    ///   creating the xlsx file, or its sub-files.
+    /// * [`XlsxError::WorksheetError`] - Errors that occur while writing a
+    ///   particular worksheet, such as a [`XlsxError::TableNameReused`],
+    ///   [`XlsxError::IoError`] or [`XlsxError::ZipError`] error, are wrapped
+    ///   with the worksheet name and, where available, the cell. The
+    ///   underlying error can be retrieved with [`XlsxError::inner()`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating a simple workbook to some
+    /// types that implement the `Write` trait like a file and a buffer.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_save_to_writer.rs
+    /// #
+    /// # use std::fs::File;
+    /// # use std::io::{Cursor, Write};
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    ///
+    /// fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write_string(0, 0, "Hello")?;
+    ///
+    ///     // Save the file to a File object.
+    ///     let file = File::create("workbook1.xlsx")?;
+    ///     workbook.save_to_writer(file)?;
+    ///
+    ///     // Save the file to a buffer. It is wrapped in a Cursor because it need to
+    ///     // implement the `Seek` trait.
+    ///     let mut cursor = Cursor::new(Vec::new());
+    ///     workbook.save_to_writer(&mut cursor)?;
+    ///
+    ///     // Write the buffer to a file for the sake of the example.
+    ///     let buf = cursor.into_inner();
+    ///     let mut file = File::create("workbook2.xlsx")?;
+    ///     Write::write_all(&mut file, &buf)?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    pub fn save_to_writer<W>(&mut self, writer: W) -> Result<(), XlsxError>
+    where
+        W: Write + Seek,
+    {
+        self.save_internal(writer)?;
+        Ok(())
+    }
+
+    /// Save the Workbook as an encrypted, password protected, xlsx file.
+    ///
+    /// The `save_with_password()` method is similar to the
+    /// [`save()`](Workbook::save) method except that the file is encrypted
+    /// with a password. Excel will ask for the password when the file is
+    /// opened.
+    ///
+    /// The file is encrypted using the same "Agile Encryption" scheme, with
+    /// AES-256 and SHA-512, as Excel 2010 and later. Unlike the worksheet and
+    /// workbook protection passwords this encrypts the file contents and
+    /// provides strong protection.
+    ///
+    /// **Note**: This method requires the `encryption` feature to be enabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path of the new Excel file to create as a `&str` or as a
+    ///   [`std::path`] `Path` or `PathBuf` instance.
+    /// * `password` - The password string used to encrypt the file. It must
+    ///   be between 1 and 255 characters long.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::SheetnameReused`] - Worksheet name is already in use in
+    ///   the workbook.
+    /// * [`XlsxError::ParameterError`] - The password is empty or longer than
+    ///   255 characters.
+    /// * [`XlsxError::IoError`] - A wrapper for various IO errors when creating
+    ///   the xlsx file, or its sub-files.
+    /// * [`XlsxError::ZipError`] - A wrapper for various zip errors when
+    ///   creating the xlsx file, or its sub-files.
+    /// * [`XlsxError::WorksheetError`] - Errors that occur while writing a
+    ///   particular worksheet, such as a [`XlsxError::TableNameReused`],
+    ///   [`XlsxError::IoError`] or [`XlsxError::ZipError`] error, are wrapped
+    ///   with the worksheet name and, where available, the cell. The
+    ///   underlying error can be retrieved with [`XlsxError::inner()`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates saving a workbook as an encrypted
+    /// file that requires a password to open.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_save_with_password.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write_string(0, 0, "Hello")?;
+    ///
+    ///     workbook.save_with_password("workbook.xlsx", "abc123")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "encryption")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+    pub fn save_with_password<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        password: &str,
+    ) -> Result<(), XlsxError> {
+        self.check_file_extension(path.as_ref())?;
+
+        let package = self.save_to_buffer()?;
+
+        let mut encryptor = Encryptor::new()?;
+        let encrypted_file = encryptor.encrypt_file(&package, password)?;
+
+        self.save_atomically(path.as_ref(), |_, file| {
+            file.write_all(&encrypted_file)?;
+            Ok(())
+        })
+    }
+
+    /// Create a defined name in the workbook to use as a variable.
+    ///
+    /// The `define_name()` method is used to defined a variable name that can
+    /// be used to represent a value, a single cell or a range of cells in a
+    /// workbook. These are sometimes referred to as a "Named Ranges".
+    ///
+    /// Defined names are generally used to simplify or clarify formulas by
+    /// using descriptive variable names. For example:
+    ///
+    /// ```text
+    ///     // Global workbook name.
+    ///     workbook.define_name("Exchange_rate", "=0.96")?;
+    ///     worksheet.write_formula(0, 0, "=Exchange_rate")?;
+    /// ```
+    ///
+    /// A name defined like this is "global" to the workbook and can be used in
+    /// any worksheet in the workbook.  It is also possible to define a
+    /// local/worksheet name by prefixing it with the sheet name using the
+    /// syntax `"sheetname!defined_name"`:
+    ///
+    /// ```text
+    ///     // Local worksheet name.
+    ///     workbook.define_name('Sheet2!Sales', '=Sheet2!$G$1:$G$10')?;
+    /// ```
+    ///
+    /// See the full example below.
+    ///
+    /// Note, Excel has limitations on names used in defined names. For example
+    /// it must start with a letter or underscore and cannot contain a space or
+    /// any of the characters: `,/*[]:\"'`. It also cannot look like an Excel
+    /// range such as `A1`, `XFD12345` or `R1C1`. If in doubt it best to test
+    /// the name in Excel first.
+    ///
+    /// For local defined names sheet name must exist (at the time of saving)
+    /// and if the sheet name contains spaces or special characters you must
+    /// follow the Excel convention and enclose it in single quotes:
+    ///
+    /// ```text
+    ///     workbook.define_name("'New Data'!Sales", ""=Sheet2!$G$1:$G$10")?;
+    /// ```
+    ///
+    /// The rules for names in Excel are explained in the Microsoft Office
+    /// documentation on how to [Define and use names in
+    /// formulas](https://support.microsoft.com/en-us/office/define-and-use-names-in-formulas-4d0f13ac-53b7-422e-afd2-abd7ff379c64)
+    /// and subsections.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The variable name to define.
+    /// * `formula` - The formula, value or range that the name defines..
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The following Excel error cases will
+    ///   raise a `ParameterError` error:
+    ///   * If the name is blank.
+    ///   * If the name doesn't start with a letter or underscore.
+    ///   * If the name contains `,/*[]:\"'` or `space`.
+    ///   * If the name looks like a cell reference such as `A1` or `R1C1`.
+    ///   * If the name is one of the names reserved by Excel for print areas,
+    ///     print titles and autofilters, such as `Print_Area`.
+    ///   * If the name has already been defined in the same global or local
+    ///     scope. The comparison is case insensitive.
+    ///
+    /// # Examples
+    ///
+    /// Example of how to create defined names using the `rust_xlsxwriter`
+    /// library.
+    ///
+    /// This functionality is used to define user friendly variable names to
+    /// represent a value, a single cell,  or a range of cells in a workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/app_defined_name.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add two worksheets to the workbook.
+    /// #     let _worksheet1 = workbook.add_worksheet();
+    /// #     let _worksheet2 = workbook.add_worksheet();
+    /// #
+    ///     // Define some global/workbook names.
+    ///     workbook.define_name("Exchange_rate", "=0.96")?;
+    ///     workbook.define_name("Sales", "=Sheet1!$G$1:$H$10")?;
+    ///
+    ///     // Define a local/worksheet name. Over-rides the "Sales" name above.
+    ///     workbook.define_name("Sheet2!Sales", "=Sheet2!$G$1:$G$10")?;
+    ///
+    /// #     // Write some text in the file and one of the defined names in a formula.
+    /// #     for worksheet in workbook.worksheets_mut() {
+    /// #         worksheet.set_column_width(0, 45)?;
+    /// #         worksheet.write_string(0, 0, "This worksheet contains some defined names.")?;
+    /// #         worksheet.write_string(1, 0, "See Formulas -> Name Manager above.")?;
+    /// #         worksheet.write_string(2, 0, "Example formula in cell B3 ->")?;
+    /// #
+    /// #         worksheet.write_formula(2, 1, "=Exchange_rate")?;
+    /// #     }
+    /// #
+    /// #     // Save the file to disk.
+    /// #     workbook.save("defined_name.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img
+    /// src="https://rustxlsxwriter.github.io/images/app_defined_name1.png">
+    ///
+    /// Here is the output in the Excel Name Manager. Note that there is a
+    /// Global/Workbook "Sales" variable name and a Local/Worksheet version.
+    ///
+    /// <img
+    /// src="https://rustxlsxwriter.github.io/images/app_defined_name2.png">
+    ///
+    pub fn define_name(
+        &mut self,
+        name: impl Into<String>,
+        formula: &str,
+    ) -> Result<&mut Workbook, XlsxError> {
+        let mut defined_name = DefinedName::new();
+        let name = name.into();
+
+        // Match Global/Workbook or Local/Worksheet defined names.
+        match name.find('!') {
+            Some(position) => {
+                defined_name.quoted_sheet_name = name[0..position].to_string();
+                defined_name.name = name[position + 1..].to_string();
+                defined_name.name_type = DefinedNameType::Local;
+            }
+            None => {
+                defined_name.name = name.to_string();
+                defined_name.name_type = DefinedNameType::Global;
+            }
+        }
+
+        if defined_name.name.is_empty() {
+            let error = "Defined name cannot be blank".to_string();
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        // Excel requires that the name starts with a letter or underscore.
+        // Also, backspace is allowed but undocumented by Excel.
+        if !defined_name.name.chars().next().unwrap().is_alphabetic()
+            && !defined_name.name.starts_with('_')
+            && !defined_name.name.starts_with('\\')
+        {
+            let error = format!(
+                "Name '{}' must start with a letter or underscore in Excel",
+                defined_name.name
+            );
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        // Excel also prohibits certain characters in the name.
+        if defined_name
+            .name
+            .contains([' ', ',', '/', '*', '[', ']', ':', '"', '\''])
+        {
+            let error = format!(
+                "Name '{}' cannot contain any of the characters `,/*[]:\"'` or `space` in Excel",
+                defined_name.name
+            );
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        // Excel doesn't allow names that look like cell references.
+        if Self::is_cell_reference(&defined_name.name) {
+            let error = format!(
+                "Name '{}' cannot look like a cell reference such as `A1` or `R1C1` in Excel",
+                defined_name.name
+            );
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        // The built-in names are reserved for the worksheet print and filter
+        // settings.
+        let lowercase_name = defined_name.name.to_lowercase();
+        if ["print_area", "print_titles", "_filterdatabase"].contains(&lowercase_name.as_str())
+            || lowercase_name.starts_with("_xlnm.")
+        {
+            let error = format!(
+                "Name '{}' is reserved by Excel. Use the equivalent worksheet methods such as `set_print_area()` instead",
+                defined_name.name
+            );
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        // Check that the name hasn't already been defined in the same scope.
+        let sheet_name = defined_name.unquoted_sheet_name().to_lowercase();
+        if self.user_defined_names.iter().any(|existing| {
+            existing.name.to_lowercase() == lowercase_name
+                && existing.unquoted_sheet_name().to_lowercase() == sheet_name
+        }) {
+            let error = format!(
+                "Name '{}' has already been defined in the same scope",
+                defined_name.name
+            );
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        defined_name.range = utility::formula_to_string(formula);
+        defined_name.set_sort_name();
+
+        self.user_defined_names.push(defined_name);
+
+        Ok(self)
+    }
+
+    /// Create a worksheet scoped defined name in the workbook.
+    ///
+    /// The `define_local_name()` method is used to define a local/worksheet
+    /// name that is only valid in the specified worksheet. It is equivalent to
+    /// calling [`Workbook::define_name()`] with a `"sheetname!defined_name"`
+    /// name except that the sheet name is quoted automatically if it contains
+    /// spaces or other special characters.
+    ///
+    /// A local name takes precedence over a global name with the same name
+    /// in the worksheet that it is defined for.
+    ///
+    /// # Parameters
+    ///
+    /// * `sheet_name` - The name of the worksheet that the name is scoped to.
+    ///   The worksheet must exist at the time of saving.
+    /// * `name` - The variable name to define.
+    /// * `formula` - The formula, value or range that the name defines.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The same name validation errors as
+    ///   [`Workbook::define_name()`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating global and worksheet
+    /// scoped defined names.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_define_local_name.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     workbook.add_worksheet();
+    /// #     workbook.add_worksheet().set_name("Sales Data")?;
+    /// #
+    ///     // Define a global/workbook name.
+    ///     workbook.define_name("Costs", "=Sheet1!$A$1:$A$10")?;
+    ///
+    ///     // Define a local/worksheet name. The sheet name is quoted
+    ///     // automatically.
+    ///     workbook.define_local_name("Sales Data", "Costs", "='Sales Data'!$B$1:$B$10")?;
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn define_local_name(
+        &mut self,
+        sheet_name: &str,
+        name: impl Into<String>,
+        formula: &str,
+    ) -> Result<&mut Workbook, XlsxError> {
+        let name = format!("{}!{}", utility::quote_sheetname(sheet_name), name.into());
+
+        self.define_name(name, formula)
+    }
+
+    /// Set the Excel document metadata properties.
+    ///
+    /// Set various Excel document metadata properties such as Author or
+    /// Creation Date. It is used in conjunction with the [`DocProperties`]
+    /// struct.
+    ///
+    /// # Parameters
+    ///
+    /// * `properties` - A reference to a [`DocProperties`] object.
+    ///
+    /// # Examples
+    ///
+    /// An example of setting workbook document properties for a file created
+    /// using the `rust_xlsxwriter` library.
+    ///
+    /// ```
+    /// # // This code is available in examples/app_doc_properties.rs
+    /// #
+    /// # use rust_xlsxwriter::{DocProperties, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let properties = DocProperties::new()
+    ///         .set_title("This is an example spreadsheet")
+    ///         .set_subject("That demonstrates document properties")
+    ///         .set_author("A. Rust User")
+    ///         .set_manager("J. Alfred Prufrock")
+    ///         .set_company("Rust Solutions Inc")
+    ///         .set_category("Sample spreadsheets")
+    ///         .set_keywords("Sample, Example, Properties")
+    ///         .set_comment("Created with Rust and rust_xlsxwriter");
+    ///
+    ///     workbook.set_properties(&properties);
+    ///
+    /// #     let worksheet = workbook.add_worksheet();
+    ///
+    /// #     worksheet.set_column_width(0, 30)?;
+    /// #     worksheet.write_string(0, 0, "See File -> Info -> Properties")?;
+    /// #
+    /// #     workbook.save("doc_properties.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img
+    /// src="https://rustxlsxwriter.github.io/images/app_doc_properties.png">
+    ///
+    ///
+    /// The document properties can also be used to set a constant creation date
+    /// so that a file generated by a `rust_xlsxwriter` program will have the
+    /// same checksum no matter when it is created.
+    ///
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_properties_checksum2.rs
+    /// #
+    /// use rust_xlsxwriter::{DocProperties, ExcelDateTime, Workbook, XlsxError};
+    ///
+    /// fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     // Create a file creation date for the file.
+    ///     let date = ExcelDateTime::from_ymd(2023, 1, 1)?;
+    ///
+    ///     // Add it to the document metadata.
+    ///     let properties = DocProperties::new().set_creation_datetime(&date);
+    ///     workbook.set_properties(&properties);
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write_string(0, 0, "Hello")?;
+    ///
+    ///     workbook.save("properties.xlsx")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    ///  See also [Checksum of a saved
+    ///  file](../workbook/index.html#checksum-of-a-saved-file).
+    ///
+    pub fn set_properties(&mut self, properties: &DocProperties) -> &mut Workbook {
+        self.properties = properties.clone();
+        self
+    }
+
+    /// Set the hyperlink base used to resolve relative links in the workbook.
+    ///
+    /// The `set_hyperlink_base()` method sets the "Hyperlink base" document
+    /// property. Excel uses it as the base path or url for relative hyperlinks
+    /// in the workbook, such as a link to `"file:///report.xlsx"` or
+    /// `"report.html"`. This is useful for files that are distributed with a
+    /// set of linked documents that may be moved together.
+    ///
+    /// This is a shortcut for the
+    /// [`DocProperties::set_hyperlink_base()`] property. Note, a subsequent
+    /// call to [`Workbook::set_properties()`] replaces the value.
+    ///
+    /// # Parameters
+    ///
+    /// * `hyperlink_base` - The base path or url, for example
+    ///   `"https://example.com/reports/"` or `"C:\\Reports\\"`.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the hyperlink base for
+    /// relative links in a workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_hyperlink_base.rs
+    /// #
+    /// # use rust_xlsxwriter::{Url, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Write a relative link to another file.
+    ///     worksheet.write_url(0, 0, Url::new("file:///summary.xlsx"))?;
+    ///
+    ///     // Set the base path used to resolve relative links.
+    ///     workbook.set_hyperlink_base("https://example.com/reports/");
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_hyperlink_base(&mut self, hyperlink_base: impl Into<String>) -> &mut Workbook {
+        self.properties.hyperlink_base = hyperlink_base.into();
+        self
+    }
+
+    /// Set the company name in the document properties.
+    ///
+    /// This is a shortcut for the [`DocProperties::set_company()`] property.
+    /// Note, a subsequent call to [`Workbook::set_properties()`] replaces the
+    /// value.
+    ///
+    /// # Parameters
+    ///
+    /// * `company` - The company string property.
+    ///
+    pub fn set_company(&mut self, company: impl Into<String>) -> &mut Workbook {
+        self.properties.company = company.into();
+        self
+    }
+
+    /// Set the manager name in the document properties.
+    ///
+    /// This is a shortcut for the [`DocProperties::set_manager()`] property.
+    /// Note, a subsequent call to [`Workbook::set_properties()`] replaces the
+    /// value.
+    ///
+    /// # Parameters
+    ///
+    /// * `manager` - The manager string property.
+    ///
+    pub fn set_manager(&mut self, manager: impl Into<String>) -> &mut Workbook {
+        self.properties.manager = manager.into();
+        self
+    }
+
+    /// Add a heading and list of document parts to the application
+    /// properties.
+    ///
+    /// The application properties of an xlsx file contain a list of headings,
+    /// such as "Worksheets" and "Named Ranges", and the titles of the
+    /// document parts under each heading. These are generated automatically
+    /// by `rust_xlsxwriter`. The `add_app_heading_pair()` method can be used
+    /// to add additional headings and part titles, for example to describe
+    /// other parts of a document for a document management system. The custom
+    /// headings are added after the default headings.
+    ///
+    /// # Parameters
+    ///
+    /// * `heading` - The heading name.
+    /// * `part_names` - The titles of the document parts under the heading.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The heading is blank, is one of the
+    ///   default "Worksheets" or "Named Ranges" headings, or the list of part
+    ///   names is empty.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the company and manager
+    /// and adding a custom heading to the application properties.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_add_app_heading_pair.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     workbook.add_worksheet();
+    /// #
+    ///     workbook.set_company("Acme Corp");
+    ///     workbook.set_manager("Jane Smith");
+    ///
+    ///     // Add a custom heading and part titles.
+    ///     workbook.add_app_heading_pair("Reports", &["Q1 Summary", "Q2 Summary"])?;
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn add_app_heading_pair(
+        &mut self,
+        heading: impl Into<String>,
+        part_names: &[&str],
+    ) -> Result<&mut Workbook, XlsxError> {
+        let heading = heading.into();
+
+        if heading.is_empty() || heading == "Worksheets" || heading == "Named Ranges" {
+            return Err(XlsxError::ParameterError(format!(
+                "App heading '{heading}' cannot be blank or a default heading"
+            )));
+        }
+
+        if part_names.is_empty() {
+            return Err(XlsxError::ParameterError(format!(
+                "App heading '{heading}' must have at least one part name"
+            )));
+        }
+
+        let part_names = part_names.iter().map(|name| (*name).to_string()).collect();
+        self.app_heading_pairs.push((heading, part_names));
+
+        Ok(self)
+    }
+
+    /// Add a recommendation to open the file in “read-only” mode.
+    ///
+    /// This method can be used to set the Excel “Read-only Recommended” option
+    /// that is available when saving a file. This presents the user of the file
+    /// with an option to open it in "read-only" mode. This means that any
+    /// changes to the file can’t be saved back to the same file and must be
+    /// saved to a new file.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating a simple workbook which opens
+    /// with a recommendation that the file should be opened in read only mode.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_read_only_recommended.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet = workbook.add_worksheet();
+    ///
+    ///     workbook.read_only_recommended();
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Alert when you open the output file:
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/workbook_read_only_recommended.png">
+    ///
+    pub fn read_only_recommended(&mut self) -> &mut Workbook {
+        self.read_only_mode = 2;
+        self
+    }
+
+    /// Protect the structure of a workbook from modification.
+    ///
+    /// The `protect()` method protects the structure of a workbook so that
+    /// users can't add, delete, rename, move, hide or unhide worksheets. This
+    /// is the equivalent of the Excel "Review -> Protect Workbook" option.
+    ///
+    /// Workbook structure protection is separate from worksheet protection,
+    /// see [`Worksheet::protect()`](crate::Worksheet::protect).
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting the structure of a
+    /// workbook so that worksheets can't be added, deleted or moved.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_protect.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet1 = workbook.add_worksheet();
+    ///     let _worksheet2 = workbook.add_worksheet().set_hidden(true);
+    ///
+    ///     // Protect the workbook structure.
+    ///     workbook.protect();
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn protect(&mut self) -> &mut Workbook {
+        self.protection_on = true;
+        self
+    }
+
+    /// Protect the structure of a workbook from modification with a password.
+    ///
+    /// The `protect_with_password()` method is like the
+    /// [`protect()`](Workbook::protect) method, see above, except that you
+    /// can add an optional, weak, password to prevent modification.
+    ///
+    /// **Note**: Workbook level passwords in Excel offer very weak protection.
+    /// They do not encrypt your data and are very easy to deactivate.
+    ///
+    /// # Parameters
+    ///
+    /// * `password` - The password string. Note, only ascii text passwords are
+    ///   supported. Passing the empty string "" is the same as turning on
+    ///   protection without a password.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting the structure of a
+    /// workbook with a password.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_protect_with_password.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet = workbook.add_worksheet();
+    ///
+    ///     // Protect the workbook structure with a password.
+    ///     workbook.protect_with_password("abc123");
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn protect_with_password(&mut self, password: &str) -> &mut Workbook {
+        self.protection_on = true;
+        self.protection_hash = utility::hash_password(password);
+        self.protection_password_hash = None;
+        self
+    }
+
+    /// Protect the structure of a workbook with a SHA-512 hashed password.
+    ///
+    /// The `protect_with_sha512_password()` method is like the
+    /// [`protect_with_password()`](Workbook::protect_with_password) method
+    /// except that the password is stored using the salted and iterated
+    /// SHA-512 hash scheme that is used by Excel 2013 and later instead of the
+    /// legacy 16 bit hash.
+    ///
+    /// The salt used for the hash is randomly generated so files saved with
+    /// this option will differ between runs.
+    ///
+    /// # Parameters
+    ///
+    /// * `password` - The password string. Passing the empty string "" is the
+    ///   same as turning on protection without a password.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::IoError`] - The operating system random source used to
+    ///   generate the salt failed.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting the structure of a
+    /// workbook with a SHA-512 hashed password.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_protect_with_sha512_password.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet = workbook.add_worksheet();
+    ///
+    ///     // Protect the workbook structure with a password.
+    ///     workbook.protect_with_sha512_password("abc123")?;
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn protect_with_sha512_password(
+        &mut self,
+        password: &str,
+    ) -> Result<&mut Workbook, XlsxError> {
+        let password_hash = if password.is_empty() {
+            None
+        } else {
+            Some(PasswordHash::new(password)?)
+        };
+
+        self.protection_on = true;
+        self.protection_hash = 0;
+        self.protection_password_hash = password_hash;
+
+        Ok(self)
+    }
+
+    /// Protect the workbook windows from being moved or resized.
+    ///
+    /// The `protect_windows()` method turns on workbook protection, like
+    /// [`protect()`](Workbook::protect), and also locks the size and position
+    /// of the workbook windows. Note, this option is only supported by some
+    /// versions of Excel, such as Excel for Mac.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    ///
+    pub fn protect_windows(&mut self, enable: bool) -> &mut Workbook {
+        self.protect_windows = enable;
+
+        if enable {
+            self.protection_on = true;
+        }
+        self
+    }
+
+    /// Set the ratio between the worksheet tabs and the horizontal scrollbar.
+    ///
+    /// The `set_tab_ratio()` method sets the width of the worksheet tab area
+    /// at the bottom of the workbook window as a percentage of the width
+    /// shared with the horizontal scrollbar. Increasing the ratio can be
+    /// useful for workbooks with a lot of worksheets.
+    ///
+    /// # Parameters
+    ///
+    /// * `ratio` - The tab ratio as a percentage in the range 0.0 to 100.0.
+    ///   The Excel default is 60.0. Values outside the range are ignored.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates increasing the width of the
+    /// worksheet tab area in a workbook with several worksheets.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_tab_ratio.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     for _ in 0..12 {
+    /// #         workbook.add_worksheet();
+    /// #     }
+    /// #
+    ///     workbook.set_tab_ratio(80.0);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_tab_ratio(&mut self, ratio: f64) -> &mut Workbook {
+        if !(0.0..=100.0).contains(&ratio) {
+            self.warnings.warn(
+                "set_tab_ratio",
+                format!("Tab ratio {ratio} outside Excel range: 0.0 <= ratio <= 100.0."),
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_tab_ratio");
+        // Excel stores the ratio in thousandths.
+        self.tab_ratio = (ratio * 10.0).round() as u16;
+        self
+    }
+
+    /// Set the default page setup options for all worksheets in a workbook.
+    ///
+    /// The `set_default_page_setup()` method sets printed page options such as
+    /// the paper size, orientation, margins and header/footer for all of the
+    /// worksheets in a workbook. This is useful for reports where each
+    /// worksheet should print with the same settings. See [`PageSetup`] for
+    /// the available options.
+    ///
+    /// The defaults are applied when the workbook is saved so they also apply
+    /// to worksheets that are added after this method is called. Any option
+    /// that has been set explicitly for a worksheet, for example with
+    /// [`Worksheet::set_paper_size()`], takes precedence over the default.
+    ///
+    /// # Parameters
+    ///
+    /// * `page_setup` - The [`PageSetup`] options to apply to the worksheets.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting default page setup options
+    /// for all the worksheets in a workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_default_page_setup.rs
+    /// #
+    /// # use rust_xlsxwriter::{PageSetup, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     // Create a common page setup for the worksheets.
+    ///     let page_setup = PageSetup::new()
+    ///         .set_paper_size(9)
+    ///         .set_landscape()
+    ///         .set_margins(0.5, 0.5, 0.75, 0.75, 0.3, 0.3)
+    ///         .set_header("&CQuarterly Report")
+    ///         .set_footer("&CPage &P of &N");
+    ///
+    ///     workbook.set_default_page_setup(&page_setup);
+    ///
+    ///     workbook.add_worksheet();
+    ///     workbook.add_worksheet();
+    ///
+    ///     // Override the default orientation for one worksheet.
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.set_portrait();
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_default_page_setup(&mut self, page_setup: &PageSetup) -> &mut Workbook {
+        self.default_page_setup = page_setup.clone();
+        self
+    }
+
+    /// Set the calculation mode for formulas in the workbook.
+    ///
+    /// The `set_calc_mode()` method is used to set the Excel calculation mode
+    /// for the workbook. See [`CalcMode`] for the available options. The
+    /// default is [`CalcMode::Auto`] where Excel recalculates formulas when
+    /// their dependencies change.
+    ///
+    /// The [`CalcMode::Manual`] mode is useful for large workbooks where the
+    /// recalculation time is noticeable. In this mode formulas are only
+    /// recalculated when the user presses `F9` and Excel doesn't recalculate
+    /// the formulas when the file is opened, so they will display the values
+    /// written by `rust_xlsxwriter` (zero by default, see
+    /// [`Worksheet::write_formula()`](crate::Worksheet::write_formula) and
+    /// [`Formula::set_result()`](crate::Formula::set_result)).
+    ///
+    /// # Parameters
+    ///
+    /// * `mode` - The [`CalcMode`] calculation mode.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the workbook calculation
+    /// mode to manual.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_calc_mode.rs
+    /// #
+    /// # use rust_xlsxwriter::{CalcMode, Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write_number(0, 0, 2)?;
+    ///     worksheet.write_number(1, 0, 3)?;
+    ///
+    ///     // Set the formula result since Excel won't recalculate it on load.
+    ///     worksheet.write_formula(2, 0, Formula::new("=SUM(A1:A2)").set_result("5"))?;
+    ///
+    ///     workbook.set_calc_mode(CalcMode::Manual);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_calc_mode(&mut self, mode: CalcMode) -> &mut Workbook {
+        self.calc_mode = mode;
+        self
+    }
+
+    /// Turn on iterative calculation for circular references.
+    ///
+    /// By default Excel raises a warning for formulas with circular
+    /// references. If iterative calculation is turned on then Excel
+    /// recalculates the formulas repeatedly until either the maximum number
+    /// of iterations is reached or the change between iterations is less than
+    /// the maximum change value. This is the "Enable iterative calculation"
+    /// option in the Excel formula options.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    /// * `max_iterations` - The maximum number of iterations in the range 1 to
+    ///   32767. The Excel default is 100.
+    /// * `max_change` - The maximum change between iterations. It must be
+    ///   greater than or equal to 0.0. The Excel default is 0.001.
+    ///
+    /// Invalid `max_iterations` or `max_change` values are ignored and the
+    /// defaults are used.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates turning on iterative calculation
+    /// for a workbook with a circular reference.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_iterative_calculation.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Write formulas with a circular reference.
+    ///     worksheet.write_formula(0, 0, "=B1/2 + 1")?;
+    ///     worksheet.write_formula(0, 1, "=A1")?;
+    ///
+    ///     workbook.set_iterative_calculation(true, 50, 0.0001);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_iterative_calculation(
+        &mut self,
+        enable: bool,
+        max_iterations: u16,
+        max_change: f64,
+    ) -> &mut Workbook {
+        self.iterative_calculation = enable;
+
+        if (1..=32767).contains(&max_iterations) {
+            self.max_iterations = max_iterations;
+            self.warnings.clear("max_iterations");
+        } else {
+            self.warnings.warn(
+                "max_iterations",
+                format!(
+                    "Maximum iterations {max_iterations} outside Excel range: 1 <= iterations <= 32767."
+                ),
+            );
+            self.max_iterations = 100;
+        }
+
+        if max_change >= 0.0 {
+            self.max_change = max_change;
+            self.warnings.clear("max_change");
+        } else {
+            self.warnings.warn(
+                "max_change",
+                format!("Maximum change {max_change} outside Excel range: change >= 0.0."),
+            );
+            self.max_change = 0.001;
+        }
+
+        self
+    }
+
+    /// Turn off the option to recalculate all formulas when the file is opened.
+    ///
+    /// By default `rust_xlsxwriter` sets a flag that tells Excel to recalculate
+    /// all of the formulas in the workbook when it is opened. This is required
+    /// since `rust_xlsxwriter` doesn't calculate the formula results and
+    /// writes a default result of 0 instead.
+    ///
+    /// For very large files the full recalculation can take a long time. If
+    /// the formula results have been supplied via
+    /// [`Worksheet::set_formula_result()`](crate::Worksheet::set_formula_result)
+    /// or [`Formula::set_result()`](crate::Formula::set_result) then the
+    /// recalculation can be turned off with `set_calc_on_load(false)`.
+    ///
+    /// Note, Excel also recalculates a file on load if it was saved by an
+    /// older version of the Excel calculation engine. This is determined by
+    /// the `calcId` of the file which can be set to a newer version with
+    /// [`Workbook::set_calc_id()`].
+    ///
+    /// The recalculation flag isn't used in [`CalcMode::Manual`] mode.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is on by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates turning off the recalculation of
+    /// formulas when the file is opened.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_calc_on_load.rs
+    /// #
+    /// # use rust_xlsxwriter::{Formula, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write_number(0, 0, 2)?;
+    ///     worksheet.write_number(1, 0, 3)?;
+    ///
+    ///     // Supply the formula result so that it doesn't need to be calculated.
+    ///     worksheet.write_formula(2, 0, Formula::new("=SUM(A1:A2)").set_result("5"))?;
+    ///
+    ///     // Don't recalculate the formulas on load. Set a calcId that matches
+    ///     // recent versions of Excel to avoid a version triggered
+    ///     // recalculation.
+    ///     workbook.set_calc_on_load(false);
+    ///     workbook.set_calc_id(191_029);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_calc_on_load(&mut self, enable: bool) -> &mut Workbook {
+        self.full_calc_on_load = enable;
+        self
+    }
+
+    /// Set the calculation engine id stored in the file.
+    ///
+    /// Excel stores the version of the calculation engine that last
+    /// calculated a file in the `calcId` property. If the id is older than
+    /// the version of Excel opening the file then Excel recalculates the
+    /// formulas in the file. The `rust_xlsxwriter` default is 124519, which
+    /// corresponds to Excel 2007, so that files are always recalculated.
+    ///
+    /// This method can be used with [`Workbook::set_calc_on_load()`] to set a
+    /// newer id, such as 191029 for Excel 365, to avoid the recalculation.
+    ///
+    /// # Parameters
+    ///
+    /// * `calc_id` - The calculation engine id.
+    ///
+    pub fn set_calc_id(&mut self, calc_id: u32) -> &mut Workbook {
+        self.calc_id = calc_id;
+        self
+    }
+
+    /// Set the zip compression level used when saving the file.
+    ///
+    /// An xlsx file is a zip container of XML files. By default the files are
+    /// compressed with the "deflate" method using the default compression
+    /// level. The `set_compression_level()` method can be used to trade file
+    /// size for speed when saving large workbooks.
+    ///
+    /// A level of 0 stores the files without compression. This is the fastest
+    /// option but creates the largest files. Levels 1 to 9 use deflate
+    /// compression with 1 being the fastest and 9 giving the best compression.
+    ///
+    /// Images and other binary files are always stored without compression
+    /// since they are generally already compressed.
+    ///
+    /// Note, other compression methods such as zstd aren't supported since
+    /// Excel can't read them.
+    ///
+    /// # Parameters
+    ///
+    /// * `level` - The compression level in the range 0-9. Values outside the
+    ///   range are ignored.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the compression level of
+    /// a workbook to favor saving speed over file size.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_compression_level.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     for row in 0..1000 {
+    ///         worksheet.write(row, 0, row)?;
+    ///     }
+    ///
+    ///     // Use the fastest deflate compression.
+    ///     workbook.set_compression_level(1);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_compression_level(&mut self, level: u8) -> &mut Workbook {
+        if level > 9 {
+            self.warnings.warn(
+                "set_compression_level",
+                format!("Compression level '{level}' must be in the range 0-9."),
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_compression_level");
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Raise an error on save for any ignored invalid parameters.
+    ///
+    /// By default `rust_xlsxwriter` ignores some invalid but non-fatal
+    /// parameters and emits a warning via the [`log`](https://docs.rs/log)
+    /// facade. For example a worksheet zoom outside Excel's range of 10-400,
+    /// or a header or footer longer than Excel's limit of 255 characters. The
+    /// file is still written but it may not be exactly what was intended.
+    ///
+    /// The `set_strict_mode()` method turns these warnings into an
+    /// [`XlsxError::StrictModeWarning`] error when the workbook is saved. This
+    /// is useful for pipelines that must never produce a subtly incorrect
+    /// file.
+    ///
+    /// Strict mode covers warnings raised by [`Workbook`], [`Worksheet`] and
+    /// the default [`PageSetup`] methods. It also covers the objects that are
+    /// added to a worksheet such as [`Format`], [`Chart`](crate::Chart),
+    /// [`Sparkline`](crate::Sparkline), [`Note`](crate::Note),
+    /// [`Url`](crate::Url), [`FilterCondition`](crate::FilterCondition) and
+    /// the conditional formats. Parameters that already raise an error, such
+    /// as URLs longer than Excel's limit of 2080 characters, aren't affected.
+    ///
+    /// The check is made when the workbook is saved so it covers warnings
+    /// raised before strict mode was turned on, and warnings raised by a
+    /// worksheet before it was added to the workbook. A warning is cleared if
+    /// the same property is later set to a valid value. Chart warnings are
+    /// raised when the chart is written during the save.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::StrictModeWarning`] - Returned by the workbook `save()`
+    ///   methods if any parameters were ignored with a warning. Worksheet
+    ///   warnings are wrapped in a [`XlsxError::WorksheetError`] with the
+    ///   worksheet name and, where applicable, the cell location.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates turning on strict mode so that an
+    /// invalid zoom level raises an error when the workbook is saved.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_strict_mode.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     workbook.set_strict_mode(true);
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // The zoom is outside Excel's range of 10-400.
+    ///     worksheet.set_zoom(500);
+    ///
+    ///     let error = workbook.save("workbook.xlsx").unwrap_err();
+    ///     assert!(matches!(error.inner(), XlsxError::StrictModeWarning(_)));
+    ///     assert_eq!(error.sheet_name(), Some("Sheet1"));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_strict_mode(&mut self, enable: bool) -> &mut Workbook {
+        self.strict_mode = enable;
+        self
+    }
+
+    /// Set the expected number of unique strings in the workbook.
+    ///
+    /// Excel stores worksheet strings in a workbook level "shared strings"
+    /// table of unique strings. For large text heavy workbooks building this
+    /// table can be a significant part of the time taken to save the file. If
+    /// the approximate number of unique strings is known in advance the
+    /// `set_shared_strings_capacity()` method can be used to reserve space for
+    /// them and avoid resizing the table during the save.
+    ///
+    /// This is an optimization hint only. The table will grow as needed if
+    /// there are more strings.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - The expected number of unique strings.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates reserving space for the unique
+    /// strings in a large workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_shared_strings_capacity.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     for row in 0..10_000 {
+    ///         worksheet.write(row, 0, format!("Item {row}"))?;
+    ///     }
+    ///
+    ///     // Reserve space for the unique strings.
+    ///     workbook.set_shared_strings_capacity(10_000);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_shared_strings_capacity(&mut self, capacity: usize) -> &mut Workbook {
+        self.shared_strings_capacity = capacity;
+        self
+    }
+
+    /// Write worksheet strings inline instead of via a shared strings table.
+    ///
+    /// By default Excel, and `rust_xlsxwriter`, stores worksheet strings in a
+    /// workbook level table of unique strings, the "shared strings table", and
+    /// writes the index of the string in the table to the worksheet cell. This
+    /// reduces the file size when strings are repeated but requires all the
+    /// unique strings to be tracked while the file is saved.
+    ///
+    /// For very large exports with millions of mostly unique strings the
+    /// shared strings table gives little benefit and adds significant time and
+    /// memory overhead. The `use_inline_strings()` method turns off the shared
+    /// strings table and writes each string directly into its worksheet cell
+    /// as an "inline" string.
+    ///
+    /// Files with inline strings are valid xlsx files and can be read by Excel
+    /// and other applications. However, Excel converts the strings back to
+    /// shared strings when it resaves the file.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing a large number of unique
+    /// strings as inline strings.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_use_inline_strings.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     for row in 0..10_000 {
+    ///         worksheet.write(row, 0, format!("Order {row:06}"))?;
+    ///     }
+    ///
+    ///     // Write the strings inline without a shared strings table.
+    ///     workbook.use_inline_strings(true);
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn use_inline_strings(&mut self, enable: bool) -> &mut Workbook {
+        self.use_inline_strings = enable;
+        self
+    }
+
+    /// Save the workbook as an Excel template file.
+    ///
+    /// The `set_template()` method changes the file type of the workbook to an
+    /// Excel template. When a template file is opened Excel creates a new,
+    /// unsaved, workbook based on it instead of opening the file itself.
+    ///
+    /// Templates must be saved with an `.xltx` extension, or `.xltm` for
+    /// templates that also contain a VBA project added with
+    /// [`Workbook::add_vba_project()`]. The [`Workbook::save()`] method returns
+    /// an error if the extension doesn't match.
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates saving a workbook as an Excel
+    /// template.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_template.rs
+    /// #
+    /// # use rust_xlsxwriter::{Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let bold = Format::new().set_bold();
     ///
-    ///     // Write the buffer to a file for the sake of the example.
-    ///     let buf = cursor.into_inner();
-    ///     let mut file = File::create("workbook2.xlsx")?;
-    ///     Write::write_all(&mut file, &buf)?;
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write_with_format(0, 0, "Region", &bold)?;
+    ///     worksheet.write_with_format(0, 1, "Sales", &bold)?;
     ///
-    ///     Ok(())
-    /// }
+    ///     // Save the workbook as a template.
+    ///     workbook.set_template(true);
+    /// #
+    /// #     workbook.save("template.xltx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
     ///
-    pub fn save_to_writer<W>(&mut self, writer: W) -> Result<(), XlsxError>
-    where
-        W: Write + Seek + Send,
-    {
-        self.save_internal(writer)?;
-        Ok(())
+    pub fn set_template(&mut self, enable: bool) -> &mut Workbook {
+        self.is_template = enable;
+        self
     }
 
-    /// Create a defined name in the workbook to use as a variable.
+    /// Set a token that can be used to cancel a save in progress.
     ///
-    /// The `define_name()` method is used to defined a variable name that can
-    /// be used to represent a value, a single cell or a range of cells in a
-    /// workbook. These are sometimes referred to as a "Named Ranges".
+    /// Saving a large workbook can take some time. The
+    /// `set_cancellation_token()` method sets a shared flag that is checked
+    /// at several points while the file is being assembled. If the flag is set
+    /// to `true`, for example from another thread in response to a user
+    /// action, the save stops and returns [`XlsxError::SaveCancelled`].
     ///
-    /// Defined names are generally used to simplify or clarify formulas by
-    /// using descriptive variable names. For example:
+    /// When saving with [`Workbook::save()`] the partially written temporary
+    /// file is removed and any existing file at the target path is left
+    /// unchanged.
     ///
-    /// ```text
-    ///     // Global workbook name.
-    ///     workbook.define_name("Exchange_rate", "=0.96")?;
-    ///     worksheet.write_formula(0, 0, "=Exchange_rate")?;
-    /// ```
+    /// The flag isn't reset by the save so it should be cleared before the
+    /// workbook is saved again.
     ///
-    /// A name defined like this is "global" to the workbook and can be used in
-    /// any worksheet in the workbook.  It is also possible to define a
-    /// local/worksheet name by prefixing it with the sheet name using the
-    /// syntax `"sheetname!defined_name"`:
+    /// # Parameters
     ///
-    /// ```text
-    ///     // Local worksheet name.
-    ///     workbook.define_name('Sheet2!Sales', '=Sheet2!$G$1:$G$10')?;
-    /// ```
+    /// * `token` - A shared [`AtomicBool`] flag.
     ///
-    /// See the full example below.
+    /// # Examples
     ///
-    /// Note, Excel has limitations on names used in defined names. For example
-    /// it must start with a letter or underscore and cannot contain a space or
-    /// any of the characters: `,/*[]:\"'`. It also cannot look like an Excel
-    /// range such as `A1`, `XFD12345` or `R1C1`. If in doubt it best to test
-    /// the name in Excel first.
+    /// The following example demonstrates cancelling a workbook save.
     ///
-    /// For local defined names sheet name must exist (at the time of saving)
-    /// and if the sheet name contains spaces or special characters you must
-    /// follow the Excel convention and enclose it in single quotes:
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_cancellation_token.rs
+    /// #
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// # use std::sync::Arc;
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write(0, 0, "Hello")?;
     ///
-    /// ```text
-    ///     workbook.define_name("'New Data'!Sales", ""=Sheet2!$G$1:$G$10")?;
+    ///     let cancel = Arc::new(AtomicBool::new(false));
+    ///     workbook.set_cancellation_token(&cancel);
+    ///
+    ///     // Cancel the save, typically from another thread.
+    ///     cancel.store(true, Ordering::Relaxed);
+    ///
+    ///     let result = workbook.save("workbook.xlsx");
+    ///     assert!(matches!(result, Err(XlsxError::SaveCancelled)));
+    /// #
+    /// #     Ok(())
+    /// # }
     /// ```
     ///
-    /// The rules for names in Excel are explained in the Microsoft Office
-    /// documentation on how to [Define and use names in
-    /// formulas](https://support.microsoft.com/en-us/office/define-and-use-names-in-formulas-4d0f13ac-53b7-422e-afd2-abd7ff379c64)
-    /// and subsections.
+    pub fn set_cancellation_token(&mut self, token: &Arc<AtomicBool>) -> &mut Workbook {
+        self.cancellation_token = Some(Arc::clone(token));
+        self
+    }
+
+    /// Turn on the sanitize mode for worksheet names.
     ///
-    /// # Parameters
+    /// Applications that create worksheet names from untrusted data, such as
+    /// user input or database values, need to handle names that are invalid
+    /// in Excel or that are duplicated. When the sanitize mode is on the
+    /// [`Workbook::add_worksheet_with_name()`] method converts the name with
+    /// [`utility::sanitize_sheet_name()`] and, if the name is already in use,
+    /// appends a numeric suffix like ` (2)`, ` (3)` to make it unique.
     ///
-    /// * `name` - The variable name to define.
-    /// * `formula` - The formula, value or range that the name defines..
+    /// Names set directly with [`Worksheet::set_name()`] aren't changed.
     ///
-    /// # Errors
+    /// # Parameters
     ///
-    /// * [`XlsxError::ParameterError`] - The following Excel error cases will
-    ///   raise a `ParameterError` error:
-    ///   * If the name doesn't start with a letter or underscore.
-    ///   * If the name contains `,/*[]:\"'` or `space`.
+    /// * `enable` - Turn the property on/off. It is off by default.
     ///
     /// # Examples
     ///
-    /// Example of how to create defined names using the `rust_xlsxwriter`
-    /// library.
-    ///
-    /// This functionality is used to define user friendly variable names to
-    /// represent a value, a single cell,  or a range of cells in a workbook.
+    /// The following example demonstrates adding worksheets with names from
+    /// untrusted data.
     ///
     /// ```
-    /// # // This code is available in examples/app_defined_name.rs
+    /// # // This code is available in examples/doc_workbook_set_sanitize_sheet_names.rs
     /// #
     /// # use rust_xlsxwriter::{Workbook, XlsxError};
     /// #
     /// # fn main() -> Result<(), XlsxError> {
-    /// #     // Create a new Excel file object.
     /// #     let mut workbook = Workbook::new();
     /// #
-    /// #     // Add two worksheets to the workbook.
-    /// #     let _worksheet1 = workbook.add_worksheet();
-    /// #     let _worksheet2 = workbook.add_worksheet();
-    /// #
-    ///     // Define some global/workbook names.
-    ///     workbook.define_name("Exchange_rate", "=0.96")?;
-    ///     workbook.define_name("Sales", "=Sheet1!$G$1:$H$10")?;
+    ///     workbook.set_sanitize_sheet_names(true);
     ///
-    ///     // Define a local/worksheet name. Over-rides the "Sales" name above.
-    ///     workbook.define_name("Sheet2!Sales", "=Sheet2!$G$1:$G$10")?;
+    ///     for name in ["Sales/Costs", "Q1", "q1", "Summary: 2024 [Final]"] {
+    ///         workbook.add_worksheet_with_name(name)?;
+    ///     }
     ///
-    /// #     // Write some text in the file and one of the defined names in a formula.
-    /// #     for worksheet in workbook.worksheets_mut() {
-    /// #         worksheet.set_column_width(0, 45)?;
-    /// #         worksheet.write_string(0, 0, "This worksheet contains some defined names.")?;
-    /// #         worksheet.write_string(1, 0, "See Formulas -> Name Manager above.")?;
-    /// #         worksheet.write_string(2, 0, "Example formula in cell B3 ->")?;
-    /// #
-    /// #         worksheet.write_formula(2, 1, "=Exchange_rate")?;
-    /// #     }
+    ///     assert_eq!(
+    ///         workbook.sheet_names(),
+    ///         vec!["Sales_Costs", "Q1", "q1 (2)", "Summary_ 2024 _Final_"]
+    ///     );
     /// #
-    /// #     // Save the file to disk.
-    /// #     workbook.save("defined_name.xlsx")?;
+    /// #     workbook.save("workbook.xlsx")?;
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    /// Output file:
+    pub fn set_sanitize_sheet_names(&mut self, enable: bool) -> &mut Workbook {
+        self.sanitize_sheet_names = enable;
+        self
+    }
+
+    /// Set the policy for saving over an existing file.
+    ///
+    /// The `set_overwrite_policy()` method controls what
+    /// [`Workbook::save()`] does if the target file already exists. The
+    /// options are defined by the [`OverwritePolicy`] enum:
+    ///
+    /// - [`OverwritePolicy::Replace`]: Replace the existing file. This is the
+    ///   default.
+    /// - [`OverwritePolicy::Error`]: Return an [`XlsxError::IoError`] with an
+    ///   [`std::io::ErrorKind::AlreadyExists`] kind and leave the existing
+    ///   file unchanged.
+    /// - [`OverwritePolicy::Backup`]: Copy the existing file to a file with a
+    ///   `.bak` extension appended, such as `report.xlsx.bak`, and then
+    ///   replace it.
+    ///
+    /// In all cases the new file is written to a uniquely named temporary file
+    /// in the same directory, flushed to disk, and only then renamed over the
+    /// target file. A crash or an error during the save doesn't leave a
+    /// partially written file. The backup file is created in the same way.
+    ///
+    /// Since the new file replaces the target file, rather than overwriting the
+    /// contents of the existing file, there are some points to be aware of:
+    ///
+    /// - If the target path is a symbolic link then the link is followed and
+    ///   the file that it points to is replaced. The link itself is left
+    ///   unchanged. The temporary and backup files are created in the
+    ///   directory of the file that the link points to.
+    /// - The new file is given the permissions of the file that it replaces,
+    ///   where the filesystem supports it. Other attributes, such as the owner
+    ///   or hard links to the existing file, aren't preserved.
+    /// - With [`OverwritePolicy::Error`] the new file is moved into place with
+    ///   a hard link, which fails if the target exists. This ensures that a
+    ///   file created by another process during the save isn't replaced. On
+    ///   filesystems without hard link support a check followed by a rename
+    ///   is used instead.
+    /// - After the move the directory is synced to make the move durable. An
+    ///   error at this stage isn't returned since the file has already been
+    ///   saved.
     ///
-    /// <img
-    /// src="https://rustxlsxwriter.github.io/images/app_defined_name1.png">
+    /// # Parameters
     ///
-    /// Here is the output in the Excel Name Manager. Note that there is a
-    /// Global/Workbook "Sales" variable name and a Local/Worksheet version.
+    /// * `policy` - An [`OverwritePolicy`] enum value.
     ///
-    /// <img
-    /// src="https://rustxlsxwriter.github.io/images/app_defined_name2.png">
+    /// # Examples
     ///
-    pub fn define_name(
-        &mut self,
-        name: impl Into<String>,
-        formula: &str,
-    ) -> Result<&mut Workbook, XlsxError> {
-        let mut defined_name = DefinedName::new();
-        let name = name.into();
-
-        // Match Global/Workbook or Local/Worksheet defined names.
-        match name.find('!') {
-            Some(position) => {
-                defined_name.quoted_sheet_name = name[0..position].to_string();
-                defined_name.name = name[position + 1..].to_string();
-                defined_name.name_type = DefinedNameType::Local;
-            }
-            None => {
-                defined_name.name = name.to_string();
-                defined_name.name_type = DefinedNameType::Global;
-            }
-        }
-
-        // Excel requires that the name starts with a letter or underscore.
-        // Also, backspace is allowed but undocumented by Excel.
-        if !defined_name.name.chars().next().unwrap().is_alphabetic()
-            && !defined_name.name.starts_with('_')
-            && !defined_name.name.starts_with('\\')
-        {
-            let error = format!(
-                "Name '{}' must start with a letter or underscore in Excel",
-                defined_name.name
-            );
-            return Err(XlsxError::ParameterError(error));
-        }
-
-        // Excel also prohibits certain characters in the name.
-        if defined_name
-            .name
-            .contains([' ', ',', '/', '*', '[', ']', ':', '"', '\''])
-        {
-            let error = format!(
-                "Name '{}' cannot contain any of the characters `,/*[]:\"'` or `space` in Excel",
-                defined_name.name
-            );
-            return Err(XlsxError::ParameterError(error));
-        }
-
-        defined_name.range = utility::formula_to_string(formula);
-        defined_name.set_sort_name();
-
-        self.user_defined_names.push(defined_name);
-
-        Ok(self)
+    /// The following example demonstrates keeping a backup of an existing file
+    /// when saving a workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_overwrite_policy.rs
+    /// #
+    /// # use rust_xlsxwriter::{OverwritePolicy, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write(0, 0, "Version 1")?;
+    ///     workbook.save("report.xlsx")?;
+    ///
+    ///     // Keep a copy of the previous version when saving again.
+    ///     workbook.set_overwrite_policy(OverwritePolicy::Backup);
+    ///
+    ///     let worksheet = workbook.worksheet_from_index(0)?;
+    ///     worksheet.write(0, 0, "Version 2")?;
+    ///     workbook.save("report.xlsx")?;
+    /// #
+    /// #     std::fs::remove_file("report.xlsx.bak")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_overwrite_policy(&mut self, policy: OverwritePolicy) -> &mut Workbook {
+        self.overwrite_policy = policy;
+        self
     }
 
-    /// Set the Excel document metadata properties.
+    /// Add a VBA macro project to the workbook.
     ///
-    /// Set various Excel document metadata properties such as Author or
-    /// Creation Date. It is used in conjunction with the [`DocProperties`]
-    /// struct.
+    /// The `add_vba_project()` method can be used to add macros or functions
+    /// to a workbook using a binary VBA project file that has been extracted
+    /// from an existing Excel `xlsm` file.
+    ///
+    /// An Excel `xlsm` file is a zip file and the VBA project is stored in the
+    /// `xl/vbaProject.bin` file within it. This can be extracted using any
+    /// standard unzip utility.
+    ///
+    /// Workbooks that contain macros must be saved with an `.xlsm` extension.
+    /// Excel won't open a file with macros and an `.xlsx` extension, so
+    /// [`Workbook::save()`] returns an error in that case.
+    ///
+    /// If a VBA project is added the workbook and worksheets are given the
+    /// default VBA codenames `ThisWorkbook` and `Sheet1`, `Sheet2`, etc. These
+    /// can be changed with [`Workbook::set_vba_name()`] and
+    /// [`Worksheet::set_vba_name()`] to match the names used by the VBA
+    /// project.
     ///
     /// # Parameters
     ///
-    /// * `properties` - A reference to a [`DocProperties`] object.
+    /// * `path` - The path of the vbaProject.bin file to add.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::IoError`] - I/O errors if the path doesn't exist or is
+    ///   restricted.
     ///
     /// # Examples
     ///
-    /// An example of setting workbook document properties for a file created
-    /// using the `rust_xlsxwriter` library.
+    /// The following example demonstrates adding a VBA macro project to a
+    /// workbook and saving it as an `xlsm` file.
     ///
-    /// ```
-    /// # // This code is available in examples/app_doc_properties.rs
+    /// ```no_run
+    /// # // This code is available in examples/doc_workbook_add_vba_project.rs
     /// #
-    /// # use rust_xlsxwriter::{DocProperties, Workbook, XlsxError};
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
     /// #
     /// # fn main() -> Result<(), XlsxError> {
-    /// #     let mut workbook = Workbook::new();
-    /// #
-    ///     let properties = DocProperties::new()
-    ///         .set_title("This is an example spreadsheet")
-    ///         .set_subject("That demonstrates document properties")
-    ///         .set_author("A. Rust User")
-    ///         .set_manager("J. Alfred Prufrock")
-    ///         .set_company("Rust Solutions Inc")
-    ///         .set_category("Sample spreadsheets")
-    ///         .set_keywords("Sample, Example, Properties")
-    ///         .set_comment("Created with Rust and rust_xlsxwriter");
+    ///     let mut workbook = Workbook::new();
     ///
-    ///     workbook.set_properties(&properties);
+    ///     // Add the VBA project extracted from an existing xlsm file.
+    ///     workbook.add_vba_project("examples/vbaProject.bin")?;
     ///
-    /// #     let worksheet = workbook.add_worksheet();
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write(0, 0, "Run the macros from the Developer tab.")?;
     ///
-    /// #     worksheet.set_column_width(0, 30)?;
-    /// #     worksheet.write_string(0, 0, "See File -> Info -> Properties")?;
-    /// #
-    /// #     workbook.save("doc_properties.xlsx")?;
+    ///     // Files with macros must have an xlsm extension.
+    ///     workbook.save("macros.xlsm")?;
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    /// Output file:
+    pub fn add_vba_project<P: AsRef<Path>>(&mut self, path: P) -> Result<&mut Workbook, XlsxError> {
+        let data = std::fs::read(path)?;
+
+        Ok(self.add_vba_project_from_buffer(&data))
+    }
+
+    /// Add a VBA macro project to the workbook from a buffer.
     ///
-    /// <img
-    /// src="https://rustxlsxwriter.github.io/images/app_doc_properties.png">
+    /// This method is similar to [`Workbook::add_vba_project()`] except that
+    /// the VBA project data is read from a `u8` buffer instead of a file.
     ///
+    /// # Parameters
     ///
-    /// The document properties can also be used to set a constant creation date
-    /// so that a file generated by a `rust_xlsxwriter` program will have the
-    /// same checksum no matter when it is created.
+    /// * `buffer` - The vbaProject.bin data as a u8 buffer.
     ///
+    pub fn add_vba_project_from_buffer(&mut self, buffer: &[u8]) -> &mut Workbook {
+        self.vba_project = Some(buffer.to_vec());
+        self.vba_signature = None;
+        self
+    }
+
+    /// Add a signed VBA macro project to the workbook.
     ///
-    /// ```
-    /// # // This code is available in examples/doc_properties_checksum2.rs
-    /// #
-    /// use rust_xlsxwriter::{DocProperties, ExcelDateTime, Workbook, XlsxError};
+    /// The `add_signed_vba_project()` method is similar to
+    /// [`Workbook::add_vba_project()`] except that it also adds a
+    /// `vbaProjectSignature.bin` file that contains the digital signature of
+    /// the VBA project. This allows the macros to run in environments where
+    /// only signed macros are trusted.
     ///
-    /// fn main() -> Result<(), XlsxError> {
+    /// The signature file is stored in the `xl/vbaProjectSignature.bin` file
+    /// of a signed `xlsm` file and can be extracted along with the
+    /// `xl/vbaProject.bin` file using any standard unzip utility. The
+    /// signature is only valid for the VBA project that it was created with.
+    ///
+    /// # Parameters
+    ///
+    /// * `project_path` - The path of the vbaProject.bin file to add.
+    /// * `signature_path` - The path of the vbaProjectSignature.bin file to
+    ///   add.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::IoError`] - I/O errors if either path doesn't exist or
+    ///   is restricted.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates adding a signed VBA macro project
+    /// to a workbook.
+    ///
+    /// ```no_run
+    /// # // This code is available in examples/doc_workbook_add_signed_vba_project.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
     ///     let mut workbook = Workbook::new();
     ///
-    ///     // Create a file creation date for the file.
-    ///     let date = ExcelDateTime::from_ymd(2023, 1, 1)?;
-    ///
-    ///     // Add it to the document metadata.
-    ///     let properties = DocProperties::new().set_creation_datetime(&date);
-    ///     workbook.set_properties(&properties);
+    ///     // Add the VBA project and signature extracted from a signed xlsm file.
+    ///     workbook.add_signed_vba_project(
+    ///         "examples/vbaProject.bin",
+    ///         "examples/vbaProjectSignature.bin",
+    ///     )?;
     ///
     ///     let worksheet = workbook.add_worksheet();
-    ///     worksheet.write_string(0, 0, "Hello")?;
-    ///
-    ///     workbook.save("properties.xlsx")?;
+    ///     worksheet.write(0, 0, "This workbook contains signed macros.")?;
     ///
-    ///     Ok(())
-    /// }
+    ///     workbook.save("signed_macros.xlsm")?;
+    /// #
+    /// #     Ok(())
+    /// # }
     /// ```
     ///
-    ///  See also [Checksum of a saved
-    ///  file](../workbook/index.html#checksum-of-a-saved-file).
+    pub fn add_signed_vba_project<P1: AsRef<Path>, P2: AsRef<Path>>(
+        &mut self,
+        project_path: P1,
+        signature_path: P2,
+    ) -> Result<&mut Workbook, XlsxError> {
+        let project = std::fs::read(project_path)?;
+        let signature = std::fs::read(signature_path)?;
+
+        Ok(self.add_signed_vba_project_from_buffers(&project, &signature))
+    }
+
+    /// Add a signed VBA macro project to the workbook from buffers.
     ///
-    pub fn set_properties(&mut self, properties: &DocProperties) -> &mut Workbook {
-        self.properties = properties.clone();
+    /// This method is similar to [`Workbook::add_signed_vba_project()`]
+    /// except that the VBA project and signature data are read from `u8`
+    /// buffers instead of files.
+    ///
+    /// # Parameters
+    ///
+    /// * `project` - The vbaProject.bin data as a u8 buffer.
+    /// * `signature` - The vbaProjectSignature.bin data as a u8 buffer.
+    ///
+    pub fn add_signed_vba_project_from_buffers(
+        &mut self,
+        project: &[u8],
+        signature: &[u8],
+    ) -> &mut Workbook {
+        self.vba_project = Some(project.to_vec());
+        self.vba_signature = Some(signature.to_vec());
         self
     }
 
-    /// Add a recommendation to open the file in “read-only” mode.
+    /// Add a custom XML part to the workbook.
     ///
-    /// This method can be used to set the Excel “Read-only Recommended” option
-    /// that is available when saving a file. This presents the user of the file
-    /// with an option to open it in "read-only" mode. This means that any
-    /// changes to the file can’t be saved back to the same file and must be
-    /// saved to a new file.
+    /// The `add_custom_xml_part()` method can be used to store arbitrary XML
+    /// data in the workbook file. Custom XML parts are stored in the
+    /// `customXml` folder of the xlsx package and are ignored by Excel, but
+    /// they are preserved when the file is edited and saved. They are
+    /// typically used by document management, e-signature and workflow
+    /// systems to store metadata within the file.
+    ///
+    /// The XML data is written to the file unchanged so it should be a
+    /// complete, well formed, XML document. The `schema` is the namespace URI
+    /// of the XML data, which is used by applications to find the part. It can
+    /// be an empty string if it isn't required.
+    ///
+    /// Each call to `add_custom_xml_part()` adds a new part to the workbook.
+    /// Each part is given a unique, randomly generated, GUID as its item id.
+    ///
+    /// # Parameters
+    ///
+    /// * `xml` - The XML data, as a string or string like type.
+    /// * `schema` - The namespace URI of the XML data, as a string or string
+    ///   like type.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The XML data is empty or doesn't
+    ///   start with an XML declaration or element.
+    /// * [`XlsxError::IoError`] - The operating system random source used to
+    ///   generate the item id failed.
     ///
     /// # Examples
     ///
-    /// The following example demonstrates creating a simple workbook which opens
-    /// with a recommendation that the file should be opened in read only mode.
+    /// The following example demonstrates adding a custom XML part to a
+    /// workbook.
     ///
     /// ```
-    /// # // This code is available in examples/doc_workbook_read_only_recommended.rs
+    /// # // This code is available in examples/doc_workbook_add_custom_xml_part.rs
     /// #
     /// # use rust_xlsxwriter::{Workbook, XlsxError};
     /// #
     /// # fn main() -> Result<(), XlsxError> {
     ///     let mut workbook = Workbook::new();
     ///
-    ///     let _worksheet = workbook.add_worksheet();
+    ///     let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <invoice xmlns="http://example.com/schemas/invoice">
+    ///   <id>INV-1024</id>
+    ///   <status>Approved</status>
+    /// </invoice>"#;
     ///
-    ///     workbook.read_only_recommended();
+    ///     workbook.add_custom_xml_part(xml, "http://example.com/schemas/invoice")?;
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write(0, 0, "Invoice INV-1024")?;
     ///
     ///     workbook.save("workbook.xlsx")?;
     /// #
@@ -1278,19 +3756,304 @@ impl Workbook {
     /// # }
     /// ```
     ///
-    /// Alert when you open the output file:
+    pub fn add_custom_xml_part(
+        &mut self,
+        xml: impl Into<String>,
+        schema: impl Into<String>,
+    ) -> Result<&mut Workbook, XlsxError> {
+        let xml = xml.into();
+
+        if !xml
+            .trim_start_matches('\u{FEFF}')
+            .trim_start()
+            .starts_with('<')
+        {
+            return Err(XlsxError::ParameterError(
+                "Custom XML part must be a non-empty XML document.".to_string(),
+            ));
+        }
+
+        let item_id = utility::random_guid()?;
+        self.custom_xml_parts.push((xml, schema.into(), item_id));
+
+        Ok(self)
+    }
+
+    /// Set the VBA name for the workbook.
     ///
-    /// <img src="https://rustxlsxwriter.github.io/images/workbook_read_only_recommended.png">
+    /// The `set_vba_name()` method can be used to set the VBA codename for the
+    /// workbook. This is sometimes required when a VBA project added via
+    /// [`Workbook::add_vba_project()`] refers to the workbook by a name other
+    /// than the default `ThisWorkbook`.
     ///
-    pub fn read_only_recommended(&mut self) -> &mut Workbook {
-        self.read_only_mode = 2;
-        self
+    /// See also [`Worksheet::set_vba_name()`] to set the codename of a
+    /// worksheet.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The VBA name. It must start with a letter and contain only
+    ///   letters, digits and underscores. The maximum length is 31
+    ///   characters.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The name doesn't follow the VBA
+    ///   naming rules.
+    ///
+    pub fn set_vba_name(&mut self, name: impl Into<String>) -> Result<&mut Workbook, XlsxError> {
+        let name = name.into();
+        utility::validate_vba_name(&name)?;
+
+        self.vba_name = Some(name);
+
+        Ok(self)
     }
 
     // -----------------------------------------------------------------------
     // Internal function/methods.
     // -----------------------------------------------------------------------
 
+    // Check that a workbook with a VBA project or a template is saved with a
+    // matching extension since Excel won't open it otherwise.
+    fn check_file_extension(&self, path: &Path) -> Result<(), XlsxError> {
+        let required_extension = match (self.vba_project.is_some(), self.is_template) {
+            (false, false) => return Ok(()),
+            (false, true) => "xltx",
+            (true, false) => "xlsm",
+            (true, true) => "xltm",
+        };
+
+        let is_valid = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(required_extension));
+
+        if !is_valid {
+            return Err(XlsxError::ParameterError(format!(
+                "Workbook must be saved with an '.{required_extension}' extension: '{}'",
+                path.display()
+            )));
+        }
+
+        Ok(())
+    }
+
+    // Write the file to a temporary file in the same directory as the target
+    // and then move it into place, according to the overwrite policy. The
+    // temporary file is removed if there is an error.
+    fn save_atomically<F>(&mut self, path: &Path, write_file: F) -> Result<(), XlsxError>
+    where
+        F: FnOnce(&mut Workbook, &mut std::fs::File) -> Result<(), XlsxError>,
+    {
+        // Save to the file that a symbolic link points to, rather than
+        // replacing the link.
+        let path = &Self::resolve_symlinks(path);
+
+        let exists_error = || {
+            XlsxError::IoError(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("File '{}' already exists", path.display()),
+            ))
+        };
+
+        // Fail early, without writing the file, if the target already exists.
+        // The final move below also checks this without a race.
+        if self.overwrite_policy == OverwritePolicy::Error && path.exists() {
+            return Err(exists_error());
+        }
+
+        let Some(file_name) = path.file_name() else {
+            return Err(XlsxError::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Invalid file path '{}'", path.display()),
+            )));
+        };
+
+        let file_name = file_name.to_string_lossy();
+        let temp_path = Self::temp_file_path(path, &file_name);
+
+        let result = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+            .map_err(XlsxError::from)
+            .and_then(|mut file| {
+                write_file(self, &mut file)?;
+
+                // Keep the permissions of the file that is being replaced. This
+                // is best effort since some filesystems don't support it.
+                if let Ok(metadata) = std::fs::metadata(path) {
+                    let _ = file.set_permissions(metadata.permissions());
+                }
+
+                // Flush the file to disk before it is moved into place.
+                file.sync_all()?;
+                Ok(())
+            })
+            .and_then(|()| {
+                match self.overwrite_policy {
+                    OverwritePolicy::Error => {
+                        return Self::move_no_clobber(&temp_path, path, exists_error)
+                    }
+                    OverwritePolicy::Backup if path.exists() => {
+                        Self::backup_file(path, &file_name)?;
+                    }
+                    OverwritePolicy::Backup | OverwritePolicy::Replace => {}
+                }
+
+                std::fs::rename(&temp_path, path)?;
+                Ok(())
+            });
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+
+        // The file is in place at this point so a failure to sync the
+        // directory isn't reported as a failed save.
+        if result.is_ok() {
+            Self::sync_parent_dir(path);
+        }
+
+        result
+    }
+
+    // Move a file into place without replacing an existing file. A hard link
+    // fails atomically if the target exists so there is no window in which
+    // another process can create the file between a check and the move. On
+    // filesystems that don't support hard links this falls back to a check
+    // followed by a rename.
+    fn move_no_clobber(
+        temp_path: &Path,
+        path: &Path,
+        exists_error: impl Fn() -> XlsxError,
+    ) -> Result<(), XlsxError> {
+        match std::fs::hard_link(temp_path, path) {
+            Ok(()) => {
+                let _ = std::fs::remove_file(temp_path);
+                Ok(())
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => Err(exists_error()),
+            Err(_) => {
+                if path.exists() {
+                    return Err(exists_error());
+                }
+
+                std::fs::rename(temp_path, path)?;
+                Ok(())
+            }
+        }
+    }
+
+    // Follow a chain of symbolic links to the file that they point to. The
+    // number of links followed is limited to avoid loops. The target doesn't
+    // need to exist.
+    fn resolve_symlinks(path: &Path) -> PathBuf {
+        let mut path = path.to_path_buf();
+
+        for _ in 0..40 {
+            let is_symlink = std::fs::symlink_metadata(&path)
+                .is_ok_and(|metadata| metadata.file_type().is_symlink());
+
+            if !is_symlink {
+                break;
+            }
+
+            let Ok(target) = std::fs::read_link(&path) else {
+                break;
+            };
+
+            path = match path.parent() {
+                Some(dir) => dir.join(target),
+                None => target,
+            };
+        }
+
+        path
+    }
+
+    // Get a temporary file name in the same directory as the target file so
+    // that it can be renamed into place. The process id and a counter are used
+    // to avoid collisions between concurrent saves to the same file.
+    fn temp_file_path(path: &Path, file_name: &str) -> PathBuf {
+        static TEMP_FILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let count = TEMP_FILE_COUNT.fetch_add(1, Ordering::Relaxed);
+        let process_id = std::process::id();
+
+        path.with_file_name(format!(".~{file_name}.{process_id}.{count}.tmp"))
+    }
+
+    // Copy an existing file to a ".bak" file. The copy is written to a
+    // temporary file and renamed so that the backup is never left partially
+    // written.
+    fn backup_file(path: &Path, file_name: &str) -> Result<(), XlsxError> {
+        let backup_path = path.with_file_name(format!("{file_name}.bak"));
+        let temp_path = Self::temp_file_path(path, file_name);
+
+        let result = std::fs::copy(path, &temp_path)
+            .and_then(|_| {
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .open(&temp_path)?
+                    .sync_all()
+            })
+            .and_then(|()| std::fs::rename(&temp_path, backup_path));
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+
+        Ok(result?)
+    }
+
+    // Sync the directory of a file that has been moved into place so that the
+    // move is durable. This is best effort and errors are ignored. Directories
+    // can't be synced, or opened as files, on Windows and other non Unix
+    // platforms so this is skipped on those.
+    #[cfg(unix)]
+    fn sync_parent_dir(path: &Path) {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+
+        if let Ok(dir) = std::fs::File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn sync_parent_dir(_path: &Path) {}
+
+    // Return the first warning, if any, raised by the workbook, the default
+    // page setup or the worksheets as a strict mode error.
+    fn check_warnings(&self) -> Result<(), XlsxError> {
+        if let Some(warning) = self
+            .warnings
+            .first()
+            .or_else(|| self.default_page_setup.warnings.first())
+        {
+            return Err(XlsxError::StrictModeWarning(warning.message.clone()));
+        }
+
+        for worksheet in &self.worksheets {
+            if let Some(warning) = worksheet.warnings.first() {
+                return Err(XlsxError::StrictModeWarning(warning.message.clone())
+                    .in_worksheet(&worksheet.name, warning.cell));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Return an error if the save has been cancelled via the cancellation token.
+    pub(crate) fn check_cancelled(&self) -> Result<(), XlsxError> {
+        match &self.cancellation_token {
+            Some(token) if token.load(Ordering::Relaxed) => Err(XlsxError::SaveCancelled),
+            _ => Ok(()),
+        }
+    }
+
     // Reset workbook between saves.
     fn reset(&mut self) {
         self.writer.reset();
@@ -1312,9 +4075,10 @@ impl Workbook {
     // Internal function to prepare the workbook and other component files for
     // writing to the xlsx file.
     #[allow(clippy::similar_names)]
-    fn save_internal<W: Write + Seek + Send>(&mut self, writer: W) -> Result<(), XlsxError> {
+    fn save_internal<W: Write + Seek>(&mut self, writer: W) -> Result<(), XlsxError> {
         // Reset workbook and worksheet state data between saves.
         self.reset();
+        self.check_cancelled()?;
 
         // Ensure that there is at least one worksheet in the workbook.
         if self.worksheets.is_empty() {
@@ -1346,10 +4110,38 @@ impl Workbook {
             unique_worksheet_names.insert(worksheet_name);
         }
 
+        // In strict mode any parameters that were ignored with a warning are
+        // raised as an error instead.
+        if self.strict_mode {
+            self.check_warnings()?;
+        }
+
+        // Set the default VBA codenames for the workbook and worksheets, if
+        // required.
+        if self.vba_project.is_some() {
+            if self.vba_name.is_none() {
+                self.vba_name = Some("ThisWorkbook".to_string());
+            }
+
+            for (index, worksheet) in self.worksheets.iter_mut().enumerate() {
+                if worksheet.vba_name.is_none() {
+                    worksheet.vba_name = Some(format!("Sheet{}", index + 1));
+                }
+            }
+        }
+
+        // Apply the default page setup options to the worksheets.
+        for worksheet in &mut self.worksheets {
+            worksheet.apply_default_page_setup(&self.default_page_setup);
+        }
+
         // Write any Tables associated with serialization areas.
         #[cfg(feature = "serde")]
         for worksheet in &mut self.worksheets {
-            worksheet.store_serialized_tables()?;
+            let name = worksheet.name.clone();
+            worksheet
+                .store_serialized_tables()
+                .map_err(|error| error.in_worksheet(&name, None))?;
         }
 
         // Convert any worksheet local formats to workbook/global formats. At
@@ -1391,7 +4183,9 @@ impl Workbook {
             worksheet.set_global_dxf_indices(&worksheet_dxf_indices[i]);
 
             // Perform the autofilter row hiding.
-            worksheet.hide_autofilter_rows();
+            worksheet
+                .hide_autofilter_rows()
+                .map_err(|error| error.in_worksheet(&worksheet.name, None))?;
 
             // Set the index of the worksheets.
             worksheet.sheet_index = i;
@@ -1410,23 +4204,55 @@ impl Workbook {
         self.prepare_format_properties();
 
         // Prepare worksheet tables.
-        self.prepare_tables()?;
+        self.prepare_tables()
+            .map_err(|error| self.table_error_in_worksheet(error))?;
+
+        // Set the rel linkages for any named sheet views.
+        self.prepare_named_sheet_views();
 
         // Collect workbook level metadata to help generate the xlsx file.
         let mut package_options = PackagerOptions::new();
         package_options = self.set_package_options(package_options)?;
+        self.check_cancelled()?;
 
         // Create the Packager object that will assemble the zip/xlsx file.
-        let mut packager = Packager::new(writer);
+        let mut packager = Packager::new(writer, self.compression_level);
         packager.assemble_file(self, &package_options)?;
 
         Ok(())
     }
 
+    // Ensure that the active worksheet is visible after a worksheet is removed
+    // or moved. If no worksheet has been set as active then the first
+    // worksheet is made active when the file is saved, which would unhide it
+    // if it is hidden. In that case the first visible worksheet is made active
+    // instead.
+    fn update_active_worksheet(&mut self) {
+        if self.worksheets.iter().any(|worksheet| worksheet.active) {
+            return;
+        }
+
+        let is_visible = |worksheet: &Worksheet| worksheet.visible == SheetVisibility::Visible;
+
+        match self.worksheets.first() {
+            Some(worksheet) if !is_visible(worksheet) => {}
+            _ => return,
+        }
+
+        if let Some(worksheet) = self
+            .worksheets
+            .iter_mut()
+            .find(|worksheet| is_visible(worksheet))
+        {
+            worksheet.set_active(true);
+        }
+    }
+
     // Iterates through the worksheets and find which is the user defined Active
     // sheet. If none has been set then default to the first sheet, like Excel.
     fn set_active_worksheets(&mut self) {
         let mut active_index = 0;
+        self.first_sheet = 0;
 
         for (i, worksheet) in self.worksheets.iter().enumerate() {
             if worksheet.active {
@@ -1480,6 +4306,9 @@ impl Workbook {
         let mut chart_id = 1;
         let mut drawing_id = 1;
         let mut vml_drawing_id = 1;
+        let mut vml_data_id = 1;
+        let mut vml_shape_id = 1024;
+        let mut comment_id = 1;
         let mut image_id = self.embedded_images.len() as u32;
 
         // These are the image ids for each unique image file.
@@ -1504,15 +4333,36 @@ impl Workbook {
                 drawing_id += 1;
             }
 
+            if worksheet.has_notes() {
+                worksheet.prepare_vml_objects(
+                    vml_data_id,
+                    vml_shape_id,
+                    vml_drawing_id,
+                    comment_id,
+                );
+
+                // Each VML data id block can hold 1024 shapes.
+                let num_blocks = 1 + worksheet.num_notes() / 1024;
+                vml_data_id += num_blocks;
+                vml_shape_id += 1024 * num_blocks;
+                vml_drawing_id += 1;
+                comment_id += 1;
+            }
+
             if worksheet.has_header_footer_images() {
                 // The header/footer images are counted from the last worksheet id.
                 let base_image_id = worksheet_image_ids.len() as u32;
 
+                // The header/footer VML shares the data and shape ids with the
+                // note VML so that they are unique in the workbook.
                 worksheet.prepare_header_footer_images(
                     &mut header_footer_image_ids,
                     base_image_id,
+                    vml_data_id,
                     vml_drawing_id,
                 );
+                vml_data_id += 1;
+                vml_shape_id += 1024;
                 vml_drawing_id += 1;
             }
         }
@@ -1545,6 +4395,61 @@ impl Workbook {
         Ok(())
     }
 
+    // Add the worksheet and cell of the table to a table name error.
+    fn table_error_in_worksheet(&self, error: XlsxError) -> XlsxError {
+        let XlsxError::TableNameReused(name) = &error else {
+            return error;
+        };
+
+        // The error is raised for the last table with the duplicate name.
+        for worksheet in self.worksheets.iter().rev() {
+            for table in worksheet.tables.iter().rev() {
+                if table.name.to_lowercase() == name.to_lowercase() {
+                    let cell = (table.cell_range.first_row, table.cell_range.first_col);
+                    return error.in_worksheet(&worksheet.name, Some(cell));
+                }
+            }
+        }
+
+        error
+    }
+
+    // Check if a defined name looks like an A1 or R1C1 style cell reference.
+    pub(crate) fn is_cell_reference(name: &str) -> bool {
+        lazy_static! {
+            static ref A1_REFERENCE: Regex = Regex::new(r"^([A-Za-z]{1,3})(\d+)$").unwrap();
+            static ref R1C1_REFERENCE: Regex =
+                Regex::new(r"^([Rr]\d*([Cc]\d*)?|[Cc]\d*)$").unwrap();
+        }
+
+        if R1C1_REFERENCE.is_match(name) {
+            return true;
+        }
+
+        match A1_REFERENCE.captures(name) {
+            Some(caps) => {
+                let col = utility::column_name_to_number(&caps[1].to_uppercase());
+                let row = caps[2].parse::<u64>().unwrap_or(u64::MAX);
+
+                col < COL_MAX && row >= 1 && row <= u64::from(ROW_MAX)
+            }
+            None => false,
+        }
+    }
+
+    // Set a unique id for the named sheet view file of each worksheet and also
+    // set the .rel file linkages.
+    fn prepare_named_sheet_views(&mut self) {
+        let mut named_sheet_view_id = 1;
+
+        for worksheet in &mut self.worksheets {
+            if !worksheet.named_sheet_views.is_empty() {
+                worksheet.prepare_worksheet_named_sheet_views(named_sheet_view_id);
+                named_sheet_view_id += 1;
+            }
+        }
+    }
+
     // Add worksheet number/string cache data to chart ranges. This isn't
     // strictly necessary but it helps non-Excel apps to render charts
     // correctly.
@@ -1575,12 +4480,18 @@ impl Workbook {
             if let Ok(worksheet) = self.worksheet_from_name(&key.0) {
                 *cache = worksheet.get_cache_data(key.1, key.2, key.3, key.4);
             } else {
-                let sheet_name = key.0.clone();
-                let range = utility::chart_range_abs(&key.0, key.1, key.2, key.3, key.4);
-                let error =
-                    format!("Unknown worksheet name '{sheet_name}' in chart range '{range}'");
+                let error = Self::unknown_chart_worksheet_error(key);
+
+                // Add the location of the first chart that uses the range.
+                for worksheet in &self.worksheets {
+                    for (cell, chart) in &worksheet.charts {
+                        if Self::chart_range_keys(chart).contains(key) {
+                            return Err(error.in_worksheet(&worksheet.name, Some(*cell)));
+                        }
+                    }
+                }
 
-                return Err(XlsxError::UnknownWorksheetNameOrIndex(error));
+                return Err(error);
             }
         }
 
@@ -1600,6 +4511,31 @@ impl Workbook {
         Ok(())
     }
 
+    // Get the sorted keys of the worksheet ranges used by a chart, including
+    // any combined chart.
+    fn chart_range_keys(chart: &Chart) -> Vec<(String, RowNum, ColNum, RowNum, ColNum)> {
+        let mut chart_ranges = HashMap::new();
+        Self::insert_chart_ranges_to_cache(chart, &mut chart_ranges);
+
+        if let Some(chart) = &chart.combined_chart {
+            Self::insert_chart_ranges_to_cache(chart, &mut chart_ranges);
+        }
+
+        let mut keys: Vec<_> = chart_ranges.into_keys().collect();
+        keys.sort();
+        keys
+    }
+
+    // Create the error for a chart range that refers to an unknown worksheet.
+    fn unknown_chart_worksheet_error(key: &(String, RowNum, ColNum, RowNum, ColNum)) -> XlsxError {
+        let range = utility::chart_range_abs(&key.0, key.1, key.2, key.3, key.4);
+
+        XlsxError::UnknownWorksheetNameOrIndex(format!(
+            "Unknown worksheet name '{}' in chart range '{range}'",
+            key.0
+        ))
+    }
+
     // Insert all the various chart ranges into the lookup range cache.
     fn insert_chart_ranges_to_cache(
         chart: &Chart,
@@ -1608,6 +4544,8 @@ impl Workbook {
         Self::insert_to_chart_cache(&chart.title.range, chart_caches);
         Self::insert_to_chart_cache(&chart.x_axis.title.range, chart_caches);
         Self::insert_to_chart_cache(&chart.y_axis.title.range, chart_caches);
+        Self::insert_to_chart_cache(&chart.x2_axis.title.range, chart_caches);
+        Self::insert_to_chart_cache(&chart.y2_axis.title.range, chart_caches);
 
         for series in &chart.series {
             Self::insert_to_chart_cache(&series.title.range, chart_caches);
@@ -1638,6 +4576,8 @@ impl Workbook {
         Self::update_range_cache(&mut chart.title.range, chart_caches);
         Self::update_range_cache(&mut chart.x_axis.title.range, chart_caches);
         Self::update_range_cache(&mut chart.y_axis.title.range, chart_caches);
+        Self::update_range_cache(&mut chart.x2_axis.title.range, chart_caches);
+        Self::update_range_cache(&mut chart.y2_axis.title.range, chart_caches);
 
         for series in &mut chart.series {
             Self::update_range_cache(&mut series.title.range, chart_caches);
@@ -1883,6 +4823,19 @@ impl Workbook {
         package_options.num_worksheets = self.worksheets.len() as u16;
         package_options.doc_security = self.read_only_mode;
         package_options.num_embedded_images = self.embedded_images.len() as u32;
+        package_options.has_vba_project = self.vba_project.is_some();
+        package_options.has_vba_signature = self.vba_signature.is_some();
+        package_options.is_template = self.is_template;
+        package_options.num_custom_xml_parts =
+            u16::try_from(self.custom_xml_parts.len()).map_err(|_| {
+                XlsxError::ParameterError(format!(
+                    "Number of custom XML parts exceeds Excel's limit of {}.",
+                    u16::MAX
+                ))
+            })?;
+        package_options
+            .app_heading_pairs
+            .clone_from(&self.app_heading_pairs);
 
         let mut defined_names = self.user_defined_names.clone();
         let mut sheet_names: HashMap<String, u16> = HashMap::new();
@@ -1894,7 +4847,7 @@ impl Workbook {
             let quoted_sheet_name = utility::quote_sheetname(&sheet_name);
             sheet_names.insert(sheet_name.clone(), sheet_index as u16);
 
-            if worksheet.visible == Visible::VeryHidden {
+            if worksheet.visible == SheetVisibility::VeryHidden {
                 package_options.worksheet_names.push(String::new());
             } else {
                 package_options.worksheet_names.push(sheet_name.clone());
@@ -1902,7 +4855,7 @@ impl Workbook {
 
             package_options.properties = self.properties.clone();
 
-            if worksheet.uses_string_table {
+            if worksheet.uses_string_table && !self.use_inline_strings {
                 package_options.has_sst_table = true;
             }
 
@@ -1923,6 +4876,11 @@ impl Workbook {
                 package_options.has_vml = true;
             }
 
+            if worksheet.has_notes() {
+                package_options.has_vml = true;
+                package_options.num_comments += 1;
+            }
+
             if !worksheet.drawing.drawings.is_empty() {
                 package_options.num_drawings += 1;
             }
@@ -1935,6 +4893,10 @@ impl Workbook {
                 package_options.num_tables += worksheet.tables.len() as u16;
             }
 
+            if !worksheet.named_sheet_views.is_empty() {
+                package_options.num_named_sheet_views += 1;
+            }
+
             // Store the autofilter areas which are a category of defined name.
             if worksheet.autofilter_defined_name.in_use {
                 let mut defined_name = worksheet.autofilter_defined_name.clone();
@@ -2022,6 +4984,11 @@ impl Workbook {
         // Write the workbookPr element.
         self.write_workbook_pr();
 
+        // Write the workbookProtection element.
+        if self.protection_on {
+            self.write_workbook_protection();
+        }
+
         // Write the bookViews element.
         self.write_book_views();
 
@@ -2071,11 +5038,41 @@ impl Workbook {
 
     // Write the <workbookPr> element.
     fn write_workbook_pr(&mut self) {
-        let attributes = [("defaultThemeVersion", "124226")];
+        let mut attributes = vec![];
+
+        if let Some(vba_name) = &self.vba_name {
+            attributes.push(("codeName", vba_name.clone()));
+        }
+
+        attributes.push(("defaultThemeVersion", "124226".to_string()));
 
         self.writer.xml_empty_tag("workbookPr", &attributes);
     }
 
+    // Write the <workbookProtection> element.
+    fn write_workbook_protection(&mut self) {
+        let mut attributes = vec![];
+
+        if self.protection_hash != 0 {
+            attributes.push(("workbookPassword", format!("{:04X}", self.protection_hash)));
+        }
+
+        if let Some(password_hash) = &self.protection_password_hash {
+            attributes.push(("workbookAlgorithmName", "SHA-512".to_string()));
+            attributes.push(("workbookHashValue", password_hash.hash_value.clone()));
+            attributes.push(("workbookSaltValue", password_hash.salt_value.clone()));
+            attributes.push(("workbookSpinCount", password_hash.spin_count.to_string()));
+        }
+
+        attributes.push(("lockStructure", "1".to_string()));
+
+        if self.protect_windows {
+            attributes.push(("lockWindows", "1".to_string()));
+        }
+
+        self.writer.xml_empty_tag("workbookProtection", &attributes);
+    }
+
     // Write the <bookViews> element.
     fn write_book_views(&mut self) {
         self.writer.xml_start_tag_only("bookViews");
@@ -2095,6 +5092,11 @@ impl Workbook {
             ("windowHeight", "9660".to_string()),
         ];
 
+        // Store the tabRatio attribute when it isn't the default.
+        if self.tab_ratio != 600 {
+            attributes.push(("tabRatio", self.tab_ratio.to_string()));
+        }
+
         // Store the firstSheet attribute when it isn't the first sheet.
         if self.first_sheet > 0 {
             let first_sheet = self.first_sheet + 1;
@@ -2127,16 +5129,16 @@ impl Workbook {
     }
 
     // Write the <sheet> element.
-    fn write_sheet(&mut self, name: &str, visible: Visible, index: u16) {
+    fn write_sheet(&mut self, name: &str, visible: SheetVisibility, index: u16) {
         let sheet_id = format!("{index}");
         let ref_id = format!("rId{index}");
 
         let mut attributes = vec![("name", name.to_string()), ("sheetId", sheet_id)];
 
         match visible {
-            Visible::Default => {}
-            Visible::Hidden => attributes.push(("state", "hidden".to_string())),
-            Visible::VeryHidden => attributes.push(("state", "veryHidden".to_string())),
+            SheetVisibility::Visible => {}
+            SheetVisibility::Hidden => attributes.push(("state", "hidden".to_string())),
+            SheetVisibility::VeryHidden => attributes.push(("state", "veryHidden".to_string())),
         }
 
         attributes.push(("r:id", ref_id));
@@ -2171,8 +5173,78 @@ impl Workbook {
 
     // Write the <calcPr> element.
     fn write_calc_pr(&mut self) {
-        let attributes = [("calcId", "124519"), ("fullCalcOnLoad", "1")];
+        let mut attributes = vec![("calcId", self.calc_id.to_string())];
+
+        match self.calc_mode {
+            CalcMode::Auto => {}
+            CalcMode::AutoExceptTables => attributes.push(("calcMode", "autoNoTable".to_string())),
+            CalcMode::Manual => attributes.push(("calcMode", "manual".to_string())),
+        }
+
+        if self.full_calc_on_load && self.calc_mode != CalcMode::Manual {
+            attributes.push(("fullCalcOnLoad", "1".to_string()));
+        }
+
+        if self.iterative_calculation {
+            attributes.push(("iterate", "1".to_string()));
+
+            if self.max_iterations != 100 {
+                attributes.push(("iterateCount", self.max_iterations.to_string()));
+            }
+
+            if (self.max_change - 0.001).abs() > f64::EPSILON {
+                attributes.push(("iterateDelta", self.max_change.to_string()));
+            }
+        }
+
+        if self.calc_mode == CalcMode::Manual {
+            attributes.push(("calcOnSave", "0".to_string()));
+        }
 
         self.writer.xml_empty_tag("calcPr", &attributes);
     }
 }
+
+// -----------------------------------------------------------------------
+// Helper enums/structs/functions.
+// -----------------------------------------------------------------------
+
+/// The `CalcMode` enum defines the calculation mode for formulas in a
+/// workbook.
+///
+/// Used with the [`Workbook::set_calc_mode()`] method.
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CalcMode {
+    /// Excel recalculates formulas automatically when their dependencies
+    /// change. This is the default.
+    #[default]
+    Auto,
+
+    /// Excel recalculates formulas automatically except for formulas in data
+    /// tables.
+    AutoExceptTables,
+
+    /// Excel only recalculates formulas when the user requests it, for
+    /// example by pressing `F9`.
+    Manual,
+}
+
+/// The `OverwritePolicy` enum defines how an existing file is handled when a
+/// workbook is saved.
+///
+/// Used with the [`Workbook::set_overwrite_policy()`] method.
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OverwritePolicy {
+    /// Replace the existing file. This is the default.
+    #[default]
+    Replace,
+
+    /// Return an error if the file already exists.
+    Error,
+
+    /// Copy the existing file to a file with a `.bak` extension appended
+    /// before replacing it.
+    Backup,
+}
@@ -8,8 +8,14 @@
 mod workbook_tests {
 
     use crate::{test_functions::xml_to_vec, XlsxError};
-    use crate::{Table, Workbook};
+    use crate::{
+        CalcMode, Chart, ChartType, ConditionalFormat3ColorScale, ConditionalFormatType,
+        DocProperties, Format, HeaderImagePosition, Image, IndexSheetOptions, Note,
+        OverwritePolicy, PageSetup, SheetVisibility, Table, Workbook, Worksheet,
+    };
     use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
 
     #[test]
     fn test_assemble() {
@@ -50,13 +56,996 @@ mod workbook_tests {
             ".foo",    // Invalid start character.
             "foo bar", // Space in name
             "Foo,",    // Other invalid characters.
-            "Foo/", "Foo[", "Foo]", "Foo'", "Foo\"bar", "Foo:", "Foo*",
+            "Foo/",
+            "Foo[",
+            "Foo]",
+            "Foo'",
+            "Foo\"bar",
+            "Foo:",
+            "Foo*",
+            "",                    // Blank name.
+            "A1",                  // Cell reference.
+            "xfd1048576",          // Cell reference.
+            "R",                   // R1C1 reference.
+            "c",                   // R1C1 reference.
+            "R1C1",                // R1C1 reference.
+            "rc12",                // R1C1 reference.
+            "Print_Area",          // Reserved name.
+            "_xlnm.Foo",           // Reserved name.
+            "Sheet1!print_titles", // Reserved name.
         ];
 
         for name in names {
             let result = workbook.define_name(name, "");
             assert!(matches!(result, Err(XlsxError::ParameterError(_))));
         }
+
+        // Test valid defined names that are similar to cell references.
+        let names = vec!["XFE1", "A1048577", "A0", "abcd1", "a3f6", "Rate", "Cost"];
+
+        for name in names {
+            let result = workbook.define_name(name, "=1");
+            assert!(result.is_ok(), "{name}");
+        }
+    }
+
+    #[test]
+    fn define_name_duplicates() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.add_worksheet().set_name("Sheet 2").unwrap();
+
+        workbook.define_name("Costs", "=Sheet1!$A$1").unwrap();
+        workbook
+            .define_name("Sheet1!Costs", "=Sheet1!$A$1")
+            .unwrap();
+        workbook
+            .define_local_name("Sheet 2", "Costs", "='Sheet 2'!$A$1")
+            .unwrap();
+
+        // Names in the same scope, ignoring case and quoting.
+        let result = workbook.define_name("COSTS", "=Sheet1!$A$2");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = workbook.define_local_name("Sheet1", "costs", "=Sheet1!$A$2");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = workbook.define_name("'Sheet 2'!Costs", "='Sheet 2'!$A$2");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn calc_mode() {
+        let test_data = [
+            (
+                CalcMode::Auto,
+                None,
+                r#"calcId="124519" fullCalcOnLoad="1""#,
+            ),
+            (
+                CalcMode::AutoExceptTables,
+                None,
+                r#"calcId="124519" calcMode="autoNoTable" fullCalcOnLoad="1""#,
+            ),
+            (
+                CalcMode::Manual,
+                None,
+                r#"calcId="124519" calcMode="manual" calcOnSave="0""#,
+            ),
+            (
+                CalcMode::Auto,
+                Some((100, 0.001)),
+                r#"calcId="124519" fullCalcOnLoad="1" iterate="1""#,
+            ),
+            (
+                CalcMode::Manual,
+                Some((50, 0.0001)),
+                r#"calcId="124519" calcMode="manual" iterate="1" iterateCount="50" iterateDelta="0.0001" calcOnSave="0""#,
+            ),
+            (
+                CalcMode::Auto,
+                Some((0, -1.0)),
+                r#"calcId="124519" fullCalcOnLoad="1" iterate="1""#,
+            ),
+        ];
+
+        for (mode, iterations, attributes) in test_data {
+            let mut workbook = Workbook::default();
+            workbook.set_calc_mode(mode);
+
+            if let Some((max_iterations, max_change)) = iterations {
+                workbook.set_iterative_calculation(true, max_iterations, max_change);
+            }
+
+            workbook.write_calc_pr();
+
+            let got = workbook.writer.read_to_str();
+            let got = xml_to_vec(got);
+
+            let expected = xml_to_vec(&format!("<calcPr {attributes}/>"));
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn calc_on_load() {
+        let mut workbook = Workbook::default();
+        workbook.set_calc_on_load(false);
+        workbook.write_calc_pr();
+
+        let got = workbook.writer.read_to_str();
+        let got = xml_to_vec(got);
+        let expected = xml_to_vec(r#"<calcPr calcId="124519"/>"#);
+
+        assert_eq!(expected, got);
+
+        let mut workbook = Workbook::default();
+        workbook.set_calc_id(191_029);
+        workbook.write_calc_pr();
+
+        let got = workbook.writer.read_to_str();
+        let got = xml_to_vec(got);
+        let expected = xml_to_vec(r#"<calcPr calcId="191029" fullCalcOnLoad="1"/>"#);
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn set_hyperlink_base() {
+        let mut workbook = Workbook::default();
+        workbook.set_properties(&DocProperties::new().set_author("Someone"));
+        workbook.set_hyperlink_base("https://example.com/");
+
+        assert_eq!(workbook.properties.hyperlink_base, "https://example.com/");
+        assert_eq!(workbook.properties.author, "Someone");
+    }
+
+    #[test]
+    fn vba_project() {
+        let mut workbook = Workbook::default();
+        workbook.add_vba_project_from_buffer(&[0xD0, 0xCF, 0x11, 0xE0]);
+        workbook.add_worksheet();
+        workbook.add_worksheet().set_vba_name("Summary").unwrap();
+
+        workbook.save_to_buffer().unwrap();
+
+        workbook.writer.reset();
+        workbook.write_workbook_pr();
+
+        let got = workbook.writer.read_to_str();
+        let got = xml_to_vec(got);
+        let expected =
+            xml_to_vec(r#"<workbookPr codeName="ThisWorkbook" defaultThemeVersion="124226"/>"#);
+
+        assert_eq!(expected, got);
+
+        let names: Vec<Option<String>> = workbook
+            .worksheets()
+            .iter()
+            .map(|worksheet| worksheet.vba_name.clone())
+            .collect();
+        assert_eq!(
+            names,
+            vec![Some("Sheet1".to_string()), Some("Summary".to_string())]
+        );
+
+        // Workbooks with macros must be saved as xlsm files.
+        let result = workbook.save("macros.xlsx");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[test]
+    fn signed_vba_project() {
+        let mut workbook = Workbook::default();
+        workbook.add_signed_vba_project_from_buffers(&[1, 2, 3], &[4, 5, 6]);
+        workbook.add_worksheet();
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+
+        let mut names: Vec<&str> = zip
+            .file_names()
+            .filter(|name| name.contains("vba"))
+            .collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            vec![
+                "xl/_rels/vbaProject.bin.rels",
+                "xl/vbaProject.bin",
+                "xl/vbaProjectSignature.bin"
+            ]
+        );
+
+        let mut signature = vec![];
+        std::io::Read::read_to_end(
+            &mut zip.by_name("xl/vbaProjectSignature.bin").unwrap(),
+            &mut signature,
+        )
+        .unwrap();
+        assert_eq!(signature, vec![4, 5, 6]);
+
+        // Adding an unsigned project removes the signature.
+        workbook.add_vba_project_from_buffer(&[1, 2, 3]);
+        assert!(workbook.vba_signature.is_none());
+
+        // The project and signature paths can be different path types.
+        let result = workbook.add_signed_vba_project(
+            "missing_vbaProject.bin",
+            std::path::PathBuf::from("missing_vbaProjectSignature.bin"),
+        );
+        assert!(matches!(result, Err(XlsxError::IoError(_))));
+    }
+
+    #[test]
+    fn template() {
+        let read_content_types = |workbook: &mut Workbook| {
+            let buffer = workbook.save_to_buffer().unwrap();
+            let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+            let mut content_types = String::new();
+            std::io::Read::read_to_string(
+                &mut zip.by_name("[Content_Types].xml").unwrap(),
+                &mut content_types,
+            )
+            .unwrap();
+            content_types
+        };
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.set_template(true);
+
+        let content_types = read_content_types(&mut workbook);
+        assert!(content_types.contains(
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.template.main+xml"
+        ));
+
+        let result = workbook.save("template.xlsx");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        workbook.add_vba_project_from_buffer(&[1, 2, 3]);
+
+        let content_types = read_content_types(&mut workbook);
+        assert!(content_types.contains("application/vnd.ms-excel.template.macroEnabled.main+xml"));
+
+        let result = workbook.save("template.xltx");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[test]
+    fn vba_names() {
+        let mut workbook = Workbook::default();
+
+        for name in [
+            "",
+            "1Sheet",
+            "_Sheet",
+            "My Sheet",
+            "Sheet-1",
+            &"A".repeat(32),
+        ] {
+            let result = workbook.set_vba_name(name);
+            assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+        }
+
+        for name in ["MyWorkbook", "Sheet_1", &"A".repeat(31)] {
+            assert!(workbook.set_vba_name(name).is_ok());
+        }
+    }
+
+    #[test]
+    fn registered_formats() {
+        let mut workbook = Workbook::default();
+        let bold = Format::new().set_bold();
+        let italic = Format::new().set_italic();
+
+        let result = workbook.register_format("", &bold);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        workbook.register_format("header", &bold).unwrap();
+        assert_eq!(workbook.format_by_name("header").unwrap(), bold);
+
+        // Names are case sensitive.
+        let result = workbook.format_by_name("Header");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        // Re-registering a name replaces the format.
+        workbook.register_format("header", &italic).unwrap();
+        assert_eq!(workbook.format_by_name("header").unwrap(), italic);
+    }
+
+    #[test]
+    fn inline_strings() {
+        let mut workbook = Workbook::default();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Hello").unwrap();
+        workbook.use_inline_strings(true);
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        assert!(zip.by_name("xl/sharedStrings.xml").is_err());
+
+        let mut sheet = String::new();
+        std::io::Read::read_to_string(
+            &mut zip.by_name("xl/worksheets/sheet1.xml").unwrap(),
+            &mut sheet,
+        )
+        .unwrap();
+        assert!(sheet.contains(r#"<c r="A1" t="inlineStr"><is><t>Hello</t></is></c>"#));
+
+        // The shared strings table is used by default.
+        workbook.use_inline_strings(false);
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        assert!(zip.by_name("xl/sharedStrings.xml").is_ok());
+    }
+
+    #[test]
+    fn format_ids() {
+        let mut workbook = Workbook::default();
+        let bold = workbook.format_id(&Format::new().set_bold());
+        let italic = workbook.format_id(&Format::new().set_italic());
+
+        // Equal formats are interned to the same id.
+        let bold2 = workbook.format_id(&Format::new().set_bold());
+        assert_eq!(bold.interned_id.0, bold2.interned_id.0);
+        assert_ne!(bold.interned_id.0, italic.interned_id.0);
+        assert_eq!(*bold, Format::new().set_bold());
+
+        // Copies of an interned format don't keep the id.
+        let copy = bold.format().clone().set_italic();
+        assert!(copy.interned_id.0.is_none());
+    }
+
+    #[test]
+    fn remove_and_move_worksheets() {
+        let mut workbook = Workbook::default();
+
+        workbook.add_worksheet();
+        workbook.add_worksheet().set_name("My Data").unwrap();
+        workbook.add_worksheet();
+
+        let result = workbook.remove_worksheet("Sheet4");
+        assert!(matches!(
+            result,
+            Err(XlsxError::UnknownWorksheetNameOrIndex(_))
+        ));
+
+        // Remove the active worksheet.
+        workbook.worksheet_from_index(1).unwrap().set_active(true);
+        workbook
+            .worksheet_from_index(0)
+            .unwrap()
+            .write_url(0, 0, "internal:'my data'!A1")
+            .unwrap();
+
+        let removed = workbook.remove_worksheet("MY DATA").unwrap();
+        assert_eq!(removed.name(), "My Data");
+        assert_eq!(workbook.sheet_names(), vec!["Sheet1", "Sheet3"]);
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        assert_eq!(workbook.active_tab, 0);
+
+        // The internal hyperlink to the removed worksheet is also removed.
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        let mut sheet1 = String::new();
+        std::io::Read::read_to_string(
+            &mut zip.by_name("xl/worksheets/sheet1.xml").unwrap(),
+            &mut sheet1,
+        )
+        .unwrap();
+        assert!(!sheet1.contains("<hyperlink"));
+
+        // Move the first worksheet, and the active and first tab flags, to the
+        // end.
+        workbook.add_worksheet().set_name("Sheet4").unwrap();
+        workbook
+            .worksheet_from_index(0)
+            .unwrap()
+            .set_first_tab(true);
+
+        workbook.move_worksheet(0, 2).unwrap();
+        assert_eq!(workbook.sheet_names(), vec!["Sheet3", "Sheet4", "Sheet1"]);
+
+        workbook.save_to_buffer().unwrap();
+        assert_eq!(workbook.active_tab, 2);
+        assert_eq!(workbook.first_sheet, 2);
+
+        let result = workbook.move_worksheet(0, 3);
+        assert!(matches!(
+            result,
+            Err(XlsxError::UnknownWorksheetNameOrIndex(_))
+        ));
+    }
+
+    #[test]
+    fn remove_and_move_worksheet_fixups() {
+        let read_part = |buffer: Vec<u8>, name: &str| {
+            let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+            let mut data = String::new();
+            std::io::Read::read_to_string(&mut zip.by_name(name).unwrap(), &mut data).unwrap();
+            data
+        };
+
+        // Removing the active worksheet makes the first visible worksheet
+        // active and deselects the other worksheets.
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet().set_hidden(true);
+        workbook.add_worksheet();
+        workbook.add_worksheet().set_active(true);
+        workbook.add_worksheet().set_selected(true);
+
+        workbook.remove_worksheet("Sheet3").unwrap();
+        assert_eq!(workbook.sheet_names(), vec!["Sheet1", "Sheet2", "Sheet4"]);
+
+        let worksheets = &workbook.worksheets;
+        assert_eq!(worksheets[0].visible, SheetVisibility::Hidden);
+        assert!(!worksheets[0].selected);
+        assert!(worksheets[1].active);
+        assert!(worksheets[1].selected);
+        assert!(!worksheets[2].selected);
+
+        workbook.save_to_buffer().unwrap();
+        assert_eq!(workbook.active_tab, 1);
+
+        // Removing a worksheet that isn't active doesn't change the flags.
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.add_worksheet().set_active(true);
+        workbook.add_worksheet().set_selected(true);
+
+        workbook.remove_worksheet("Sheet1").unwrap();
+        assert!(workbook.worksheets[0].active);
+        assert!(workbook.worksheets[1].selected);
+
+        // Moving a hidden worksheet to the start doesn't make it active.
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.add_worksheet();
+        workbook.add_worksheet().set_hidden(true);
+
+        workbook.move_worksheet(2, 0).unwrap();
+        assert_eq!(workbook.sheet_names(), vec!["Sheet3", "Sheet1", "Sheet2"]);
+
+        workbook.save_to_buffer().unwrap();
+        assert_eq!(workbook.active_tab, 1);
+        assert_eq!(workbook.worksheets[0].visible, SheetVisibility::Hidden);
+        assert!(!workbook.worksheets[0].selected);
+
+        // Moving worksheets keeps internal hyperlinks, which refer to
+        // worksheets by name.
+        let mut workbook = Workbook::default();
+        workbook
+            .add_worksheet()
+            .write_url(0, 0, "internal:Sheet3!A1")
+            .unwrap();
+        workbook.add_worksheet();
+        workbook.add_worksheet();
+
+        workbook.move_worksheet(0, 2).unwrap();
+        workbook.move_worksheet(1, 0).unwrap();
+        assert_eq!(workbook.sheet_names(), vec!["Sheet3", "Sheet2", "Sheet1"]);
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let sheet3 = read_part(buffer, "xl/worksheets/sheet3.xml");
+        assert!(sheet3.contains(r#"<hyperlink ref="A1" location="Sheet3!A1""#));
+
+        // Defined names that refer to a removed worksheet.
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.add_worksheet();
+        workbook.add_worksheet().set_name("It's").unwrap();
+        workbook
+            .define_name("Total", "=SUM(Sheet2!$A$1:$A$5)")
+            .unwrap();
+        workbook.define_name("Other", "=Sheet1!$A$1").unwrap();
+        workbook
+            .define_local_name("Sheet2", "Local", "=Sheet2!$B$1")
+            .unwrap();
+        workbook
+            .define_local_name("It's", "Quoted", "=Sheet1!$B$1")
+            .unwrap();
+
+        workbook.remove_worksheet("Sheet2").unwrap();
+        workbook.remove_worksheet("it's").unwrap();
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let workbook_xml = read_part(buffer, "xl/workbook.xml");
+        assert!(
+            workbook_xml.contains(r#"<definedName name="Total">SUM(#REF!$A$1:$A$5)</definedName>"#)
+        );
+        assert!(workbook_xml.contains(r#"<definedName name="Other">Sheet1!$A$1</definedName>"#));
+        assert!(!workbook_xml.contains("Local"));
+        assert!(!workbook_xml.contains("Quoted"));
+    }
+
+    #[test]
+    fn save_to_non_send_writer() {
+        // A writer that isn't Send due to the Rc field.
+        struct LocalWriter {
+            cursor: std::io::Cursor<Vec<u8>>,
+            _marker: std::rc::Rc<()>,
+        }
+
+        impl std::io::Write for LocalWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.cursor.write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.cursor.flush()
+            }
+        }
+
+        impl std::io::Seek for LocalWriter {
+            fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+                self.cursor.seek(pos)
+            }
+        }
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet().write(0, 0, "Hello").unwrap();
+
+        let mut writer = LocalWriter {
+            cursor: std::io::Cursor::new(vec![]),
+            _marker: std::rc::Rc::new(()),
+        };
+        workbook.save_to_writer(&mut writer).unwrap();
+
+        // Check for the zip file signature.
+        let buffer = writer.cursor.into_inner();
+        assert!(buffer.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn compression_level() {
+        let mut workbook = Workbook::default();
+        let worksheet = workbook.add_worksheet();
+        for row in 0..100 {
+            worksheet.write(row, 0, "Some repeated data").unwrap();
+        }
+
+        let default_size = workbook.save_to_buffer().unwrap().len();
+
+        workbook.set_compression_level(0);
+        let stored_size = workbook.save_to_buffer().unwrap().len();
+
+        workbook.set_compression_level(9);
+        let deflated_size = workbook.save_to_buffer().unwrap().len();
+
+        // Out of range levels are ignored.
+        workbook.set_compression_level(10);
+        assert_eq!(workbook.compression_level, Some(9));
+
+        assert!(stored_size > default_size);
+        assert!(stored_size > deflated_size);
+    }
+
+    #[test]
+    fn overwrite_policy() {
+        let dir = std::env::temp_dir().join(format!("rxw_overwrite_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("policy.xlsx");
+        let backup_path = dir.join("policy.xlsx.bak");
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.save(&path).unwrap();
+        let original = std::fs::read(&path).unwrap();
+
+        // The existing file is left unchanged.
+        workbook.set_overwrite_policy(OverwritePolicy::Error);
+        let result = workbook.save(&path);
+        assert!(
+            matches!(result, Err(XlsxError::IoError(error)) if error.kind() == std::io::ErrorKind::AlreadyExists)
+        );
+        assert_eq!(std::fs::read(&path).unwrap(), original);
+
+        // The existing file is copied to a backup.
+        workbook.set_overwrite_policy(OverwritePolicy::Backup);
+        workbook
+            .worksheet_from_index(0)
+            .unwrap()
+            .write(0, 0, "New")
+            .unwrap();
+        workbook.save(&path).unwrap();
+        assert_eq!(std::fs::read(&backup_path).unwrap(), original);
+        assert_ne!(std::fs::read(&path).unwrap(), original);
+
+        // A failed save doesn't affect the existing file or leave a temp file.
+        workbook.set_overwrite_policy(OverwritePolicy::Replace);
+        workbook.add_worksheet().set_name("Sheet1").unwrap();
+        let current = std::fs::read(&path).unwrap();
+        let result = workbook.save(&path);
+        assert!(matches!(result, Err(XlsxError::SheetnameReused(_))));
+        assert_eq!(std::fs::read(&path).unwrap(), current);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn overwrite_policy_error_new_file() {
+        let dir = std::env::temp_dir().join(format!("rxw_no_clobber_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("new.xlsx");
+
+        // The file is moved into place and the temp file is removed.
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.set_overwrite_policy(OverwritePolicy::Error);
+        workbook.save(&path).unwrap();
+        assert!(path.exists());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn overwrite_keeps_symlinks_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("rxw_symlink_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("target.xlsx");
+        let link_path = dir.join("link.xlsx");
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.save(&path).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+        std::os::unix::fs::symlink("target.xlsx", &link_path).unwrap();
+        let original = std::fs::read(&path).unwrap();
+
+        // The link is followed and the file it points to is replaced.
+        workbook
+            .worksheet_from_index(0)
+            .unwrap()
+            .write(0, 0, "New")
+            .unwrap();
+        workbook.save(&link_path).unwrap();
+
+        let link_metadata = std::fs::symlink_metadata(&link_path).unwrap();
+        assert!(link_metadata.file_type().is_symlink());
+        assert_ne!(std::fs::read(&path).unwrap(), original);
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+
+        // The link target already exists.
+        workbook.set_overwrite_policy(OverwritePolicy::Error);
+        let result = workbook.save(&link_path);
+        assert!(
+            matches!(result, Err(XlsxError::IoError(error)) if error.kind() == std::io::ErrorKind::AlreadyExists)
+        );
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn app_properties() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.set_company("Acme Corp").set_manager("Jane Smith");
+
+        let result = workbook.add_app_heading_pair("Worksheets", &["Foo"]);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = workbook.add_app_heading_pair("Reports", &[]);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        workbook
+            .add_app_heading_pair("Reports", &["Q1", "Q2"])
+            .unwrap();
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        let mut app = String::new();
+        std::io::Read::read_to_string(&mut zip.by_name("docProps/app.xml").unwrap(), &mut app)
+            .unwrap();
+
+        let got = xml_to_vec(&app);
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+              <Application>Microsoft Excel</Application>
+              <DocSecurity>0</DocSecurity>
+              <ScaleCrop>false</ScaleCrop>
+              <HeadingPairs>
+                <vt:vector size="4" baseType="variant">
+                  <vt:variant>
+                    <vt:lpstr>Worksheets</vt:lpstr>
+                  </vt:variant>
+                  <vt:variant>
+                    <vt:i4>1</vt:i4>
+                  </vt:variant>
+                  <vt:variant>
+                    <vt:lpstr>Reports</vt:lpstr>
+                  </vt:variant>
+                  <vt:variant>
+                    <vt:i4>2</vt:i4>
+                  </vt:variant>
+                </vt:vector>
+              </HeadingPairs>
+              <TitlesOfParts>
+                <vt:vector size="3" baseType="lpstr">
+                  <vt:lpstr>Sheet1</vt:lpstr>
+                  <vt:lpstr>Q1</vt:lpstr>
+                  <vt:lpstr>Q2</vt:lpstr>
+                </vt:vector>
+              </TitlesOfParts>
+              <Manager>Jane Smith</Manager>
+              <Company>Acme Corp</Company>
+              <LinksUpToDate>false</LinksUpToDate>
+              <SharedDoc>false</SharedDoc>
+              <HyperlinksChanged>false</HyperlinksChanged>
+              <AppVersion>12.0000</AppVersion>
+            </Properties>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn cancelled_save() {
+        let dir = std::env::temp_dir().join(format!("rxw_cancel_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cancel.xlsx");
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet().write(0, 0, "Hello").unwrap();
+        workbook.save(&path).unwrap();
+        let original = std::fs::read(&path).unwrap();
+
+        let cancel = Arc::new(AtomicBool::new(true));
+        workbook.set_cancellation_token(&cancel);
+        workbook.add_worksheet();
+
+        let result = workbook.save_to_buffer();
+        assert!(matches!(result, Err(XlsxError::SaveCancelled)));
+
+        // The existing file is unchanged and the temp file is removed.
+        let result = workbook.save(&path);
+        assert!(matches!(result, Err(XlsxError::SaveCancelled)));
+        assert_eq!(std::fs::read(&path).unwrap(), original);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        cancel.store(false, Ordering::Relaxed);
+        assert!(workbook.save_to_buffer().is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn add_worksheets_from_rows() {
+        let rows = (0..5).map(|row| vec![row, row * 10]);
+
+        let mut workbook = Workbook::default();
+        let count = workbook
+            .add_worksheets_from_rows_with_limit("Data", rows, true, 3)
+            .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(workbook.sheet_names(), vec!["Data", "Data (2)"]);
+
+        // The second worksheet repeats the header row.
+        let worksheets = workbook.worksheets_mut();
+        worksheets[0].assemble_xml_file();
+        worksheets[1].assemble_xml_file();
+        assert!(worksheets[0]
+            .writer
+            .read_to_str()
+            .contains(r#"<dimension ref="A1:B3"/>"#));
+        assert!(worksheets[1]
+            .writer
+            .read_to_str()
+            .contains(r#"<dimension ref="A1:B3"/>"#));
+
+        // Without a header the rows are split evenly.
+        let rows = (0..6).map(|row| vec![row]);
+        let mut workbook = Workbook::default();
+        let count = workbook
+            .add_worksheets_from_rows_with_limit("Data", rows, false, 3)
+            .unwrap();
+
+        assert_eq!(count, 2);
+        let worksheet = &mut workbook.worksheets_mut()[1];
+        worksheet.assemble_xml_file();
+        assert!(worksheet
+            .writer
+            .read_to_str()
+            .contains(r#"<dimension ref="A1:A3"/>"#));
+
+        // The numeric suffix counts towards the sheet name length.
+        let rows = (0..4).map(|row| vec![row]);
+        let mut workbook = Workbook::default();
+        let result = workbook.add_worksheets_from_rows_with_limit(&"A".repeat(31), rows, false, 3);
+        assert!(matches!(result, Err(XlsxError::SheetnameLengthExceeded(_))));
+        assert_eq!(workbook.sheet_names(), vec!["A".repeat(31)]);
+
+        // An invalid name doesn't add an unnamed worksheet.
+        let rows = (0..4).map(|row| vec![row]);
+        let mut workbook = Workbook::default();
+        let result = workbook.add_worksheets_from_rows_with_limit("Data[1]", rows, false, 3);
+        assert!(matches!(
+            result,
+            Err(XlsxError::SheetnameContainsInvalidCharacter(_))
+        ));
+        assert!(workbook.worksheets().is_empty());
+
+        // The generated names are checked against the existing names.
+        let rows = (0..4).map(|row| vec![row]);
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet_with_name("data (2)").unwrap();
+        let result = workbook.add_worksheets_from_rows_with_limit("Data", rows, false, 3);
+        assert!(matches!(result, Err(XlsxError::SheetnameReused(_))));
+        assert_eq!(workbook.sheet_names(), vec!["data (2)", "Data"]);
+    }
+
+    #[test]
+    fn add_index_sheet() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet().set_name("Sales").unwrap();
+        workbook.add_worksheet().set_name("Sales Data").unwrap();
+        workbook
+            .add_worksheet()
+            .set_name("Hidden")
+            .unwrap()
+            .set_hidden(true);
+
+        let options = IndexSheetOptions::new()
+            .set_title("Contents")
+            .set_back_link_cell(0, 7);
+        workbook.add_index_sheet(&options).unwrap();
+
+        assert_eq!(
+            workbook.sheet_names(),
+            vec!["Index", "Sales", "Sales Data", "Hidden"]
+        );
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        let mut read_sheet = |name: &str| {
+            let mut sheet = String::new();
+            std::io::Read::read_to_string(&mut zip.by_name(name).unwrap(), &mut sheet).unwrap();
+            sheet
+        };
+
+        let got = read_sheet("xl/worksheets/sheet1.xml");
+        assert!(got.contains(r#"<hyperlink ref="A3" location="Sales!A1" display="Sales"/>"#));
+        assert!(got
+            .contains(r#"<hyperlink ref="A4" location="'Sales Data'!A1" display="Sales Data"/>"#));
+        assert!(!got.contains("Hidden"));
+
+        let got = read_sheet("xl/worksheets/sheet2.xml");
+        assert!(
+            got.contains(r#"<hyperlink ref="H1" location="Index!A1" display="Back to index"/>"#)
+        );
+
+        // The index name must be unique.
+        let result = workbook.add_index_sheet(&IndexSheetOptions::new().set_name("sales"));
+        assert!(matches!(result, Err(XlsxError::SheetnameReused(_))));
+    }
+
+    #[test]
+    fn validate() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        assert!(workbook.validate().is_empty());
+
+        let image = Image::new("tests/input/images/red.jpg").unwrap();
+        let mut chart = Chart::new(ChartType::Column);
+        chart.add_series().set_values("Data!$A$1:$A$5");
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_header("&L&G");
+        worksheet
+            .set_header_image(&image, HeaderImagePosition::Left)
+            .unwrap();
+        worksheet.set_header("&LTitle");
+        worksheet.insert_chart(0, 0, &chart).unwrap();
+        worksheet
+            .add_table(0, 5, 3, 6, &Table::new().set_name("Sales"))
+            .unwrap();
+        worksheet
+            .add_table(5, 5, 8, 6, &Table::new().set_name("sales"))
+            .unwrap();
+
+        workbook.add_worksheet().set_name("sheet1").unwrap();
+        workbook.add_worksheet().set_name("History").unwrap();
+
+        // Formulas using LAMBDA defined names are valid.
+        workbook.define_name("MyFunc", "=LAMBDA(x, x + 1)").unwrap();
+        let worksheet = workbook.worksheet_from_index(1).unwrap();
+        worksheet.write_formula(10, 0, "=MyFunc(1)").unwrap();
+        worksheet.write_formula(11, 0, "=SUM(A1:A2").unwrap();
+
+        let errors = workbook.validate();
+        assert_eq!(errors.len(), 6);
+        assert!(matches!(errors[0].inner(), XlsxError::ParameterError(_)));
+        assert_eq!(errors[0].sheet_name(), Some("Sheet2"));
+        assert!(matches!(errors[1].inner(), XlsxError::FormulaError(_)));
+        assert_eq!(errors[1].sheet_name(), Some("Sheet2"));
+        assert_eq!((errors[1].row(), errors[1].col()), (Some(11), Some(0)));
+        assert!(matches!(errors[2], XlsxError::SheetnameReused(_)));
+        assert!(matches!(errors[3].inner(), XlsxError::ParameterError(_)));
+        assert_eq!(errors[3].sheet_name(), Some("History"));
+        assert!(matches!(errors[4].inner(), XlsxError::TableNameReused(_)));
+        assert_eq!(errors[4].sheet_name(), Some("Sheet2"));
+        assert_eq!((errors[4].row(), errors[4].col()), (Some(5), Some(5)));
+        assert!(matches!(
+            errors[5].inner(),
+            XlsxError::UnknownWorksheetNameOrIndex(_)
+        ));
+        assert_eq!(errors[5].sheet_name(), Some("Sheet2"));
+        assert_eq!((errors[5].row(), errors[5].col()), (Some(0), Some(0)));
+    }
+
+    #[test]
+    fn save_error_context() {
+        // Chart ranges with an unknown worksheet.
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        let mut chart = Chart::new(ChartType::Column);
+        chart.add_series().set_values("Data!$A$1:$A$5");
+        workbook.add_worksheet().insert_chart(3, 2, &chart).unwrap();
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(
+            error.inner(),
+            XlsxError::UnknownWorksheetNameOrIndex(_)
+        ));
+        assert_eq!(error.sheet_name(), Some("Sheet2"));
+        assert_eq!((error.row(), error.col()), (Some(3), Some(2)));
+
+        // Duplicate table names.
+        let mut workbook = Workbook::default();
+        workbook
+            .add_worksheet()
+            .add_table(0, 0, 3, 1, &Table::new().set_name("Sales"))
+            .unwrap();
+        workbook
+            .add_worksheet()
+            .add_table(4, 2, 8, 3, &Table::new().set_name("SALES"))
+            .unwrap();
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(error.inner(), XlsxError::TableNameReused(_)));
+        assert_eq!(error.sheet_name(), Some("Sheet2"));
+        assert_eq!((error.row(), error.col()), (Some(4), Some(2)));
+    }
+
+    #[test]
+    fn add_worksheet_with_name() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet_with_name("Data").unwrap();
+
+        let result = workbook.add_worksheet_with_name("data");
+        assert!(matches!(result, Err(XlsxError::SheetnameReused(_))));
+
+        let result = workbook.add_worksheet_with_name("Data/2024");
+        assert!(matches!(
+            result,
+            Err(XlsxError::SheetnameContainsInvalidCharacter(_))
+        ));
+
+        workbook.set_sanitize_sheet_names(true);
+        workbook.add_worksheet_with_name("data").unwrap();
+        workbook.add_worksheet_with_name("Data/2024").unwrap();
+        workbook.add_worksheet_with_name("").unwrap();
+
+        assert_eq!(
+            workbook.sheet_names(),
+            vec!["Data", "data (2)", "Data_2024", "Sheet"]
+        );
     }
 
     #[test]
@@ -97,4 +1086,341 @@ mod workbook_tests {
 
         assert!(matches!(result, Err(XlsxError::TableNameReused(_))));
     }
+
+    #[test]
+    fn workbook_protection() {
+        let test_data = [
+            (false, "", r#"<workbookProtection lockStructure="1"/>"#),
+            (
+                false,
+                "password",
+                r#"<workbookProtection workbookPassword="83AF" lockStructure="1"/>"#,
+            ),
+            (
+                true,
+                "",
+                r#"<workbookProtection lockStructure="1" lockWindows="1"/>"#,
+            ),
+        ];
+
+        for (windows, password, protection) in test_data {
+            let mut workbook = Workbook::default();
+            workbook.add_worksheet();
+            workbook.protect_with_password(password);
+            workbook.protect_windows(windows);
+
+            workbook.assemble_xml_file();
+
+            let got = workbook.writer.read_to_str();
+            let got = xml_to_vec(got);
+
+            let expected = xml_to_vec(&format!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+                  <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+                  <workbookPr defaultThemeVersion="124226"/>
+                  {protection}
+                  <bookViews>
+                    <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+                  </bookViews>
+                  <sheets>
+                    <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+                  </sheets>
+                  <calcPr calcId="124519" fullCalcOnLoad="1"/>
+                </workbook>
+                "#
+            ));
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn tab_ratio() {
+        let test_data = [
+            (60.0, ""),
+            (75.5, r#" tabRatio="755""#),
+            (0.0, r#" tabRatio="0""#),
+            (101.0, ""),
+        ];
+
+        for (ratio, attribute) in test_data {
+            let mut workbook = Workbook::default();
+            workbook.add_worksheet();
+            workbook.set_tab_ratio(ratio);
+
+            workbook.assemble_xml_file();
+
+            let got = workbook.writer.read_to_str();
+            let got = xml_to_vec(got);
+
+            let expected = xml_to_vec(&format!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+                  <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+                  <workbookPr defaultThemeVersion="124226"/>
+                  <bookViews>
+                    <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"{attribute}/>
+                  </bookViews>
+                  <sheets>
+                    <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+                  </sheets>
+                  <calcPr calcId="124519" fullCalcOnLoad="1"/>
+                </workbook>
+                "#
+            ));
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn strict_mode() {
+        // Warnings are ignored by default.
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_zoom(500);
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+
+        // Warnings raised before strict mode is turned on are also raised as
+        // errors in strict mode.
+        workbook.set_strict_mode(true);
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(
+            error.inner(),
+            XlsxError::StrictModeWarning(warning) if warning.starts_with("Zoom factor 500")
+        ));
+        assert_eq!(error.sheet_name(), Some("Sheet1"));
+        assert_eq!(error.row(), None);
+
+        // A later valid call clears the warning.
+        let worksheet = workbook.worksheet_from_index(0).unwrap();
+        worksheet.set_zoom(200);
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+
+        // Turning strict mode off and on again keeps the stored warnings.
+        let worksheet = workbook.worksheet_from_index(0).unwrap();
+        worksheet.set_zoom(500);
+        workbook.set_strict_mode(false);
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+
+        workbook.set_strict_mode(true);
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_err());
+
+        // Warnings raised by a worksheet before it is added to a strict mode
+        // workbook.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        let mut worksheet = Worksheet::new();
+        worksheet.set_zoom(1000);
+        workbook.push_worksheet(worksheet);
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(
+            error.inner(),
+            XlsxError::StrictModeWarning(warning) if warning.starts_with("Zoom factor 1000")
+        ));
+        assert_eq!(error.sheet_name(), Some("Sheet1"));
+
+        // Warnings raised by a worksheet before strict mode is turned on.
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet().set_zoom(1000);
+        workbook.set_strict_mode(true);
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(error.inner(), XlsxError::StrictModeWarning(_)));
+
+        // Cell warnings include the cell location.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        let worksheet = workbook.add_worksheet();
+        worksheet.write(2, 1, 123).unwrap();
+        worksheet.set_formula_result(2, 1, "456");
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert_eq!(error.sheet_name(), Some("Sheet1"));
+        assert_eq!(error.row(), Some(2));
+        assert_eq!(error.col(), Some(1));
+
+        // Workbook warnings.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        workbook.set_tab_ratio(101.0);
+
+        let result = workbook.save_to_buffer();
+        assert!(matches!(result, Err(XlsxError::StrictModeWarning(_))));
+
+        // Default page setup warnings.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        workbook.set_default_page_setup(&PageSetup::new().set_header("x".repeat(256)));
+
+        let result = workbook.save_to_buffer();
+        assert!(matches!(result, Err(XlsxError::StrictModeWarning(_))));
+
+        // Format warnings are raised when the format is used.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        let worksheet = workbook.add_worksheet();
+        let format = Format::new().set_rotation(100);
+        worksheet.write_with_format(1, 1, "Hello", &format).unwrap();
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(
+            error.inner(),
+            XlsxError::StrictModeWarning(warning) if warning.starts_with("Rotation outside")
+        ));
+
+        // A later valid call on the builder clears the warning.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        let worksheet = workbook.add_worksheet();
+        let format = Format::new().set_rotation(100).set_rotation(45);
+        worksheet.write_with_format(1, 1, "Hello", &format).unwrap();
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+
+        // Conditional format warnings.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        let worksheet = workbook.add_worksheet();
+        let conditional_format =
+            ConditionalFormat3ColorScale::new().set_midpoint(ConditionalFormatType::Percent, 101);
+        worksheet
+            .add_conditional_format(0, 0, 9, 0, &conditional_format)
+            .unwrap();
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(error.inner(), XlsxError::StrictModeWarning(_)));
+
+        // Chart warnings include the chart cell location.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        let worksheet = workbook.add_worksheet();
+        let mut chart = Chart::new(ChartType::Column);
+        chart.add_series().set_values("Sheet1!$A$1:$A$3");
+        chart.set_style(50);
+        worksheet.insert_chart(4, 2, &chart).unwrap();
+
+        let error = workbook.save_to_buffer().unwrap_err();
+        assert!(matches!(
+            error.inner(),
+            XlsxError::StrictModeWarning(warning) if warning.starts_with("Style id '50'")
+        ));
+        assert_eq!(error.row(), Some(4));
+        assert_eq!(error.col(), Some(2));
+
+        // Builder warnings aren't raised when strict mode is off.
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.insert_chart(4, 2, &chart).unwrap();
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+
+        // Valid parameters don't raise an error.
+        let mut workbook = Workbook::new();
+        workbook.set_strict_mode(true);
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_zoom(200);
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn custom_xml_parts() {
+        let read_part = |zip: &mut zip::ZipArchive<std::io::Cursor<Vec<u8>>>, name: &str| {
+            let mut data = String::new();
+            std::io::Read::read_to_string(&mut zip.by_name(name).unwrap(), &mut data).unwrap();
+            data
+        };
+
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet();
+
+        let result = workbook.add_custom_xml_part("  ", "");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        workbook
+            .add_custom_xml_part("<a>1</a>", "urn:example:a")
+            .unwrap();
+        workbook.add_custom_xml_part("<b/>", "").unwrap();
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+
+        assert_eq!(read_part(&mut zip, "customXml/item1.xml"), "<a>1</a>");
+        assert_eq!(read_part(&mut zip, "customXml/item2.xml"), "<b/>");
+        assert!(read_part(&mut zip, "customXml/itemProps1.xml")
+            .contains(r#"<ds:schemaRef ds:uri="urn:example:a"/>"#));
+
+        let item_id = |xml: String| xml.split("ds:itemID=\"").nth(1).unwrap()[..38].to_string();
+        let item_id1 = item_id(read_part(&mut zip, "customXml/itemProps1.xml"));
+        let item_id2 = item_id(read_part(&mut zip, "customXml/itemProps2.xml"));
+        assert_ne!(item_id1, item_id2);
+        assert_eq!(item_id1, workbook.custom_xml_parts[0].2);
+        assert!(read_part(&mut zip, "customXml/_rels/item2.xml.rels")
+            .contains(r#"relationships/customXmlProps" Target="itemProps2.xml"/>"#));
+        assert!(read_part(&mut zip, "xl/_rels/workbook.xml.rels")
+            .contains(r#"relationships/customXml" Target="../customXml/item2.xml"/>"#));
+        assert!(read_part(&mut zip, "[Content_Types].xml")
+            .contains(r#"<Override PartName="/customXml/itemProps1.xml" ContentType="application/vnd.openxmlformats-officedocument.customXmlProperties+xml"/>"#));
+    }
+
+    #[test]
+    fn notes_and_header_image_vml_ids() {
+        let mut workbook = Workbook::new();
+        let image = Image::new("tests/input/images/red.jpg").unwrap();
+        let note = Note::new("Some text");
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.insert_note(0, 0, &note).unwrap();
+        worksheet.set_header("&L&G");
+        worksheet
+            .set_header_image(&image, HeaderImagePosition::Left)
+            .unwrap();
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.insert_note(0, 0, &note).unwrap();
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+
+        // The note and header image VML files must have unique data and shape
+        // ids across the workbook.
+        let mut data_ids = vec![];
+        let mut shape_ids = vec![];
+        for index in 1..=3 {
+            let mut vml = String::new();
+            let name = format!("xl/drawings/vmlDrawing{index}.vml");
+            std::io::Read::read_to_string(&mut zip.by_name(&name).unwrap(), &mut vml).unwrap();
+
+            let start = vml.find(r#"<o:idmap v:ext="edit" data=""#).unwrap() + 28;
+            let end = start + vml[start..].find('"').unwrap();
+            data_ids.push(vml[start..end].to_string());
+
+            let start = vml.find(r#""_x0000_s"#).unwrap() + 1;
+            let end = start + vml[start..].find('"').unwrap();
+            shape_ids.push(vml[start..end].to_string());
+        }
+
+        assert_eq!(data_ids, vec!["1", "2", "3"]);
+        assert_eq!(
+            shape_ids,
+            vec!["_x0000_s1025", "_x0000_s2049", "_x0000_s3073"]
+        );
+    }
 }
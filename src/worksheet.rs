@@ -308,8 +308,9 @@
 //! guaranteed performance benefit from creating and working with worksheets in
 //! parallelized/async mode since the main overhead comes from **writing** the
 //! worksheets which will occur after the worksheets are joined back to the main
-//! workbook `save()` thread. In addition `rust_xlsxwriter` already parallelizes
-//! the writing of worksheets as much as possible.
+//! workbook `save()` thread. In addition `rust_xlsxwriter` already streams the
+//! worksheet data directly into the output file to keep the memory overhead
+//! of writing as low as possible.
 //!
 //! [`Workbook::add_worksheet()`]: crate::Workbook::add_worksheet
 //! [`Workbook::worksheets_mut()`]: crate::Workbook::worksheets_mut
@@ -342,6 +343,7 @@
 //! 3. [`Worksheet::set_print_scale()`]
 //! 4. [`Worksheet::set_print_fit_to_pages()`]
 //! 5. [`Worksheet::set_print_first_page_number()`]
+//! 6. [`Worksheet::set_print_resolution()`]
 //!
 //! Note, for [`Worksheet::set_print_fit_to_pages()`] a common requirement is to
 //! fit the printed output to `n` pages wide but have the height be as long as
@@ -416,6 +418,7 @@
 //! 6. [`Worksheet::set_print_draft()`]
 //! 7. [`Worksheet::set_print_headings()`]
 //! 8. [`Worksheet::set_page_order()`]
+//! 9. [`Worksheet::set_print_errors_as()`]
 //!
 //!
 //! # Adding Headers and Footers
@@ -936,8 +939,8 @@
 //! src="https://rustxlsxwriter.github.io/images/worksheet_protect_with_password.png">
 //!
 //! **Note**: Worksheet level passwords in Excel offer **very weak protection**.
-//! They do not encrypt your data and are very easy to deactivate. Full workbook
-//! encryption is not supported by `rust_xlsxwriter`. See the section on
+//! They do not encrypt your data and are very easy to deactivate. For strong
+//! protection the workbook file can be encrypted. See the section on
 //! [Workbook Protection](#workbook-protection) below.
 //!
 //! ## Choosing which worksheet elements to protect
@@ -993,15 +996,36 @@
 //!
 //! ## Workbook protection
 //!
-//! As noted above `rust_xlsxwriter` doesn't provide workbook level
-//! encryption/protection and it is unlikely that it will be added.
+//! The structure of a workbook can be protected so that users can't add,
+//! delete, move, hide or unhide worksheets using the [`Workbook::protect()`]
+//! and [`Workbook::protect_with_password()`] methods:
 //!
-//! However, it is possible to encrypt an `rust_xlsxwriter` file using a third
-//! party open source tool called [msoffice-crypt]. This works for macOS, Linux
-//! and Windows:
+//! ```
+//! # // This code is available in examples/doc_workbook_protect_with_password.rs
+//! #
+//! # use rust_xlsxwriter::{Workbook, XlsxError};
+//! #
+//! # fn main() -> Result<(), XlsxError> {
+//! #     let mut workbook = Workbook::new();
+//! #
+//! #     let _worksheet = workbook.add_worksheet();
+//! #
+//!     // Protect the workbook structure with a password.
+//!     workbook.protect_with_password("abc123");
+//! #
+//! #     workbook.save("workbook.xlsx")?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
 //!
-//! ```bash
-//! msoffice-crypt.exe -e -p password clear.xlsx encrypted.xlsx
+//! As noted above worksheet and workbook passwords don't encrypt the file
+//! data. If you need to encrypt the file, so that a password is required to
+//! open it, you can use `Workbook::save_with_password()`. This requires the
+//! `encryption` feature to be enabled:
+//!
+//! ```text
+//!     workbook.save_with_password("workbook.xlsx", "abc123")?;
 //! ```
 //!
 //!
@@ -1038,8 +1062,9 @@
 //! src="https://rustxlsxwriter.github.io/images/workbook_read_only_recommended.png">
 //!
 //!
-//! [msoffice-crypt]: https://github.com/herumi/msoffice
 //! [`ProtectionOptions`]: crate::ProtectionOptions
+//! [`Workbook::protect()`]: crate::Workbook::protect
+//! [`Workbook::protect_with_password()`]: crate::Workbook::protect_with_password
 //! [`Workbook::read_only_recommended()`]:
 //!     crate::Workbook::read_only_recommended
 //!
@@ -1050,38 +1075,47 @@ use std::borrow::Cow;
 use std::cmp;
 use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::io::Write;
-use std::mem;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
+use std::{env, mem, process};
 
 #[cfg(feature = "chrono")]
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
 use regex::Regex;
+use rustc_hash::FxHashMap;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::data_validation::DataValidation;
 #[cfg(feature = "serde")]
 use crate::{
     deserialize_headers, serializer::SerializerState, CustomSerializeField,
     SerializationHeaderConfig, SerializeFieldOptions, SerializerHeader, TableData, XlsxSerialize,
 };
 
+use crate::data_validation::DataValidationType;
 use crate::drawing::{Drawing, DrawingCoordinates, DrawingInfo, DrawingObject};
 use crate::error::XlsxError;
+use crate::filter::FilterDateGroupItem;
 use crate::format::Format;
-use crate::formula::Formula;
+use crate::formula::{Formula, FormulaResult};
+use crate::protection::PasswordHash;
 use crate::shared_strings_table::SharedStringsTable;
 use crate::styles::Styles;
 use crate::vml::VmlInfo;
+use crate::warning::Warnings;
 use crate::xmlwriter::{XMLWriter, XML_WRITE_ERROR};
 use crate::{
     utility, Chart, ChartEmptyCells, ChartRangeCacheData, ChartRangeCacheDataType, Color,
-    ConditionalFormat, ExcelDateTime, FilterCondition, FilterCriteria, FilterData, FilterDataType,
-    HeaderImagePosition, Image, IntoColor, IntoExcelDateTime, ObjectMovement, ProtectionOptions,
-    Sparkline, SparklineType, Table, TableFunction, Url,
+    ConditionalFormat, ConditionalFormatIconType, DataValidation, DataValidationErrorStyle,
+    ExcelDateTime, FilterCondition, FilterCriteria, FilterData, FilterDataType, FilterTop10,
+    FormatPattern, HeaderImagePosition, Image, IntoColor, IntoExcelDateTime, MemoryUsage,
+    NamedSheetView, Note, ObjectMovement, PageSetup, ProtectionOptions, Sparkline, SparklineType,
+    Table, TableFunction, Url, UrlLengthPolicy,
 };
 
 /// Integer type to represent a zero indexed row number. Excel's limit for rows
@@ -1177,10 +1211,14 @@ pub struct Worksheet {
     pub(crate) sheet_index: usize,
     pub(crate) active: bool,
     pub(crate) selected: bool,
-    pub(crate) visible: Visible,
+    pub(crate) visible: SheetVisibility,
     pub(crate) first_sheet: bool,
     pub(crate) uses_string_table: bool,
+    pub(crate) use_inline_strings: bool,
+    flushed_rows: RowNum,
+    spill: Option<SpillFile>,
     pub(crate) has_dynamic_arrays: bool,
+    shared_formula_ranges: Vec<(RowNum, ColNum, RowNum, ColNum)>,
     pub(crate) print_area_defined_name: DefinedName,
     pub(crate) repeat_row_cols_defined_name: DefinedName,
     pub(crate) autofilter_defined_name: DefinedName,
@@ -1188,37 +1226,53 @@ pub struct Worksheet {
     pub(crate) xf_formats: Vec<Format>,
     pub(crate) dxf_formats: Vec<Format>,
     pub(crate) has_hyperlink_style: bool,
+    pub(crate) warnings: Warnings,
     pub(crate) table_relationships: Vec<(String, String, String)>,
+    pub(crate) named_sheet_view_relationships: Vec<(String, String, String)>,
     pub(crate) hyperlink_relationships: Vec<(String, String, String)>,
     pub(crate) drawing_object_relationships: Vec<(String, String, String)>,
     pub(crate) drawing_relationships: Vec<(String, String, String)>,
     pub(crate) vml_drawing_relationships: Vec<(String, String, String)>,
     pub(crate) images: BTreeMap<(RowNum, ColNum), Image>,
     pub(crate) header_footer_vml_info: Vec<VmlInfo>,
+    pub(crate) comment_relationships: Vec<(String, String, String)>,
+    pub(crate) notes_vml_info: Vec<Note>,
+    pub(crate) vml_data_id: u32,
+    pub(crate) vml_shape_id: u32,
+    pub(crate) header_footer_vml_data_id: u32,
+    notes: BTreeMap<RowNum, BTreeMap<ColNum, Note>>,
+    comment_author: String,
+    show_all_notes: bool,
     pub(crate) drawing: Drawing,
     pub(crate) image_types: [bool; NUM_IMAGE_FORMATS],
     pub(crate) header_footer_images: [Option<Image>; 6],
     pub(crate) charts: BTreeMap<(RowNum, ColNum), Chart>,
     pub(crate) tables: Vec<Table>,
+    pub(crate) named_sheet_views: Vec<NamedSheetView>,
     pub(crate) has_embedded_image_descriptions: bool,
     pub(crate) embedded_images: Vec<Image>,
     pub(crate) global_embedded_image_indices: Vec<u32>,
 
-    data_table: BTreeMap<RowNum, BTreeMap<ColNum, CellType>>,
+    data_table: BTreeMap<RowNum, CellRow>,
     merged_ranges: Vec<CellRange>,
     merged_cells: HashMap<(RowNum, ColNum), usize>,
     table_ranges: Vec<CellRange>,
     table_cells: HashMap<(RowNum, ColNum), usize>,
-    col_names: HashMap<ColNum, String>,
+    col_names: FxHashMap<ColNum, String>,
     dimensions: CellRange,
     xf_indices: HashMap<Format, u32>,
+    interned_xf_indices: HashMap<u32, u32>,
     dxf_indices: HashMap<Format, u32>,
     global_xf_indices: Vec<u32>,
-    global_dxf_indices: Vec<u32>,
+    pub(crate) global_dxf_indices: Vec<u32>,
     changed_rows: HashMap<RowNum, RowOptions>,
     changed_cols: HashMap<ColNum, ColOptions>,
+    row_capacity: RowNum,
+    col_capacity: ColNum,
     page_setup_changed: bool,
+    page_setup_overrides: PageSetupOverrides,
     tab_color: Color,
+    pub(crate) vba_name: Option<String>,
     fit_to_page: bool,
     fit_width: u16,
     fit_height: u16,
@@ -1228,14 +1282,20 @@ pub struct Worksheet {
     portrait: bool,
     page_view: PageView,
     zoom: u16,
+    zoom_normal: u16,
+    zoom_page_layout: u16,
+    zoom_page_break_preview: u16,
     print_scale: u16,
+    print_resolution: u16,
     print_options_changed: bool,
     center_horizontally: bool,
     center_vertically: bool,
     screen_gridlines: bool,
+    gridline_color_id: u8,
     print_gridlines: bool,
     print_black_and_white: bool,
     print_draft: bool,
+    print_errors: PrintErrorOption,
     print_headings: bool,
     header: String,
     footer: String,
@@ -1250,12 +1310,15 @@ pub struct Worksheet {
     margin_footer: f64,
     first_page_number: u16,
     default_result: Box<str>,
+    url_length_policy: UrlLengthPolicy,
     use_future_functions: bool,
+    use_implicit_intersection: bool,
     panes: Panes,
     hyperlinks: BTreeMap<(RowNum, ColNum), Hyperlink>,
     rel_count: u16,
     protection_on: bool,
     protection_hash: u16,
+    protection_password_hash: Option<PasswordHash>,
     protection_options: ProtectionOptions,
     unprotected_ranges: Vec<(String, String, u16)>,
     selected_range: (String, String),
@@ -1272,7 +1335,8 @@ pub struct Worksheet {
     has_x14_conditional_formats: bool,
     has_sparklines: bool,
     sparklines: Vec<Sparkline>,
-    data_validations: Vec<DataValidation>,
+    data_validations: BTreeMap<String, DataValidation>,
+    legacy_data_validations: Vec<DataValidation>,
 
     embedded_image_ids: HashMap<u64, u32>,
 
@@ -1366,6 +1430,10 @@ impl Worksheet {
         let panes = Panes {
             freeze_cell: (0, 0),
             top_cell: (0, 0),
+            split: (0.0, 0.0),
+            frozen_split: false,
+            active_pane: None,
+            selections: HashMap::new(),
         };
 
         Worksheet {
@@ -1374,33 +1442,41 @@ impl Worksheet {
             sheet_index: 0,
             active: false,
             selected: false,
-            visible: Visible::Default,
+            visible: SheetVisibility::Visible,
             first_sheet: false,
             uses_string_table: false,
+            use_inline_strings: false,
+            flushed_rows: 0,
+            spill: None,
             has_dynamic_arrays: false,
+            shared_formula_ranges: vec![],
             print_area_defined_name: DefinedName::new(),
             repeat_row_cols_defined_name: DefinedName::new(),
             autofilter_defined_name: DefinedName::new(),
             autofilter_area: String::new(),
             data_table: BTreeMap::new(),
-            col_names: HashMap::new(),
+            col_names: FxHashMap::default(),
             dimensions,
             merged_ranges: vec![],
             merged_cells: HashMap::new(),
             tables: vec![],
+            named_sheet_views: vec![],
             table_ranges: vec![],
             table_cells: HashMap::new(),
             xf_formats: vec![Format::default()],
             dxf_formats: vec![],
             xf_indices: HashMap::from([(Format::default(), 0)]),
+            interned_xf_indices: HashMap::new(),
             dxf_indices: HashMap::new(),
             global_xf_indices: vec![],
             global_dxf_indices: vec![],
             changed_rows: HashMap::new(),
             changed_cols: HashMap::new(),
             page_setup_changed: false,
+            page_setup_overrides: PageSetupOverrides::default(),
             fit_to_page: false,
             tab_color: Color::Default,
+            vba_name: None,
             fit_width: 1,
             fit_height: 1,
             paper_size: 0,
@@ -1409,14 +1485,20 @@ impl Worksheet {
             portrait: true,
             page_view: PageView::Normal,
             zoom: 100,
+            zoom_normal: 0,
+            zoom_page_layout: 0,
+            zoom_page_break_preview: 0,
             print_scale: 100,
+            print_resolution: 200,
             print_options_changed: false,
             center_horizontally: false,
             center_vertically: false,
             screen_gridlines: true,
+            gridline_color_id: 0,
             print_gridlines: false,
             print_black_and_white: false,
             print_draft: false,
+            print_errors: PrintErrorOption::Displayed,
             print_headings: false,
             header: String::new(),
             footer: String::new(),
@@ -1431,11 +1513,15 @@ impl Worksheet {
             margin_footer: 0.3,
             first_page_number: 0,
             default_result: Box::from("0"),
+            url_length_policy: UrlLengthPolicy::default(),
             use_future_functions: false,
+            use_implicit_intersection: false,
             panes,
             has_hyperlink_style: false,
+            warnings: Warnings::new(),
             hyperlinks: BTreeMap::new(),
             table_relationships: vec![],
+            named_sheet_view_relationships: vec![],
             hyperlink_relationships: vec![],
             drawing_object_relationships: vec![],
             drawing_relationships: vec![],
@@ -1445,9 +1531,18 @@ impl Worksheet {
             image_types: [false; NUM_IMAGE_FORMATS],
             header_footer_images: [None, None, None, None, None, None],
             header_footer_vml_info: vec![],
+            comment_relationships: vec![],
+            notes_vml_info: vec![],
+            vml_data_id: 0,
+            vml_shape_id: 0,
+            header_footer_vml_data_id: 0,
+            notes: BTreeMap::new(),
+            comment_author: String::new(),
+            show_all_notes: false,
             rel_count: 0,
             protection_on: false,
             protection_hash: 0,
+            protection_password_hash: None,
             protection_options: ProtectionOptions::new(),
             unprotected_ranges: vec![],
             selected_range: (String::new(), String::new()),
@@ -1469,13 +1564,72 @@ impl Worksheet {
             has_embedded_image_descriptions: false,
             has_sparklines: false,
             sparklines: vec![],
-            data_validations: vec![],
+            data_validations: BTreeMap::new(),
+            legacy_data_validations: vec![],
+            row_capacity: 0,
+            col_capacity: 0,
 
             #[cfg(feature = "serde")]
             serializer_state: SerializerState::new(),
         }
     }
 
+    /// Create a new Worksheet object with capacity hints for the data.
+    ///
+    /// The `with_capacity()` constructor is the same as
+    /// [`Worksheet::new()`](Worksheet::new) except that it takes the expected
+    /// number of rows and columns of data and uses them to pre-size the
+    /// internal structures that hold the cell data. This avoids repeated
+    /// reallocation when writing large exports of a known size.
+    ///
+    /// The values are only hints. Data can still be written outside the
+    /// range and using values that are larger than the actual data will waste
+    /// memory.
+    ///
+    /// # Parameters
+    ///
+    /// * `rows` - The expected number of rows of data.
+    /// * `cols` - The expected number of columns of data in each row.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating a worksheet with capacity
+    /// hints for a known-size export.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_with_capacity.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     // Create a worksheet sized for 1000 rows of 10 columns.
+    ///     let mut worksheet = Worksheet::with_capacity(1000, 10);
+    ///
+    ///     for row in 0..1000 {
+    ///         for col in 0..10 {
+    ///             worksheet.write(row, col, row * 10 + u32::from(col))?;
+    ///         }
+    ///     }
+    ///
+    ///     workbook.push_worksheet(worksheet);
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn with_capacity(rows: RowNum, cols: ColNum) -> Worksheet {
+        let mut worksheet = Worksheet::new();
+        worksheet.row_capacity = rows;
+        worksheet.col_capacity = cols;
+        worksheet.col_names.reserve(usize::from(cols));
+
+        worksheet
+    }
+
     /// Set the worksheet name.
     ///
     /// Set the worksheet name. If no name is set the default Excel convention
@@ -1546,8 +1700,7 @@ impl Worksheet {
     pub fn set_name(&mut self, name: impl Into<String>) -> Result<&mut Worksheet, XlsxError> {
         let name = name.into();
 
-        let error_message = format!(r#"worksheet.set_name("{name}")"#);
-        utility::validate_sheetname(&name, &error_message)?;
+        utility::validate_sheetname(&name, || format!(r#"worksheet.set_name("{name}")"#))?;
 
         self.name = name;
 
@@ -1720,6 +1873,12 @@ impl Worksheet {
     /// implement [`IntoIterator`] and that contain a data type that implements
     /// [`IntoExcelData`].
     ///
+    /// Iterators can also be used directly, for example when each row of a
+    /// large export is generated on the fly from a database cursor or by
+    /// splitting a line of text. The values are written directly into the
+    /// worksheet cell storage so there is no need to collect them into an
+    /// intermediate `Vec` first.
+    ///
     /// See also [`worksheet.write_column()`](Worksheet::write_column) for a
     /// similar function that works in an orthogonal direction.
     ///
@@ -1829,6 +1988,34 @@ impl Worksheet {
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/app_write_arrays.png">
     ///
+    /// The following example demonstrates writing rows of data from an iterator
+    /// without collecting the values into an intermediate vector.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_row2.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let data = "Region,Product,Units\nEast,Apples,100\nWest,Pears,150";
+    ///
+    ///     // Write each line of text as a row, without allocating the fields.
+    ///     for (row, line) in data.lines().enumerate() {
+    ///         worksheet.write_row(row as u32, 0, line.split(','))?;
+    ///     }
+    ///
+    ///     // Or write computed values.
+    ///     worksheet.write_row(4, 0, (1..=10).map(|n| n * n))?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
     pub fn write_row<I>(
         &mut self,
         row: RowNum,
@@ -1839,10 +2026,359 @@ impl Worksheet {
         I: IntoIterator,
         I::Item: IntoExcelData,
     {
-        let mut col = col;
-        for item in data {
-            self.write(row, col, item)?;
-            col += 1;
+        let data = data.into_iter();
+
+        // Reserve space in the row for the expected number of values.
+        let capacity = data.size_hint().0;
+        let is_new_row = !self.data_table.contains_key(&row);
+        if capacity > 1 && row < ROW_MAX && row >= self.flushed_rows {
+            self.data_table.entry(row).or_default().0.reserve(capacity);
+        }
+
+        let mut result = Ok(());
+        for (offset, item) in data.enumerate() {
+            let col_num = usize::from(col) + offset;
+            if col_num >= usize::from(COL_MAX) {
+                result = Err(XlsxError::RowColumnLimitError);
+                break;
+            }
+
+            if let Err(error) = self.write(row, col_num as ColNum, item) {
+                result = Err(error);
+                break;
+            }
+        }
+
+        // Remove the reserved row if no values were stored in it.
+        if is_new_row
+            && self
+                .data_table
+                .get(&row)
+                .is_some_and(|columns| columns.0.is_empty())
+        {
+            self.data_table.remove(&row);
+        }
+
+        result.map(|()| self)
+    }
+
+    /// Write an array of JSON objects to a worksheet as a table of data.
+    ///
+    /// The `write_json_rows()` method writes a [`serde_json::Value`] array of
+    /// objects, such as a deserialized API payload, to a worksheet. The keys
+    /// of the objects are written as the column headers in the first row and
+    /// the object values are written in the rows below.
+    ///
+    /// The headers are the union of the keys of all the objects, in the order
+    /// that they are first seen. Objects that don't have a value for a key
+    /// leave the cell in that column empty.
+    ///
+    /// The JSON values are mapped to Excel types as follows:
+    ///
+    /// - `Number`: written as an Excel number.
+    /// - `String`: written as an Excel string.
+    /// - `Bool`: written as an Excel boolean.
+    /// - `Null`: not written, the cell is left empty.
+    /// - `Array` and `Object`: nested values are written as a string of
+    ///   compact JSON text.
+    ///
+    /// This method requires the `serde_json` feature.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number for the headers.
+    /// * `col` - The zero indexed column number.
+    /// * `data` - A [`serde_json::Value`] array of JSON objects.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The data isn't an array of JSON
+    ///   objects.
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing an array of JSON objects to
+    /// a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_json_rows.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// # use serde_json::json;
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let data = json!([
+    ///         {"id": 1, "name": "Apples", "in_stock": true, "tags": ["fruit"]},
+    ///         {"id": 2, "name": "Pears", "in_stock": false},
+    ///         {"id": 3, "name": "Bread", "price": 2.5, "tags": null},
+    ///     ]);
+    ///
+    ///     worksheet.write_json_rows(0, 0, &data)?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "serde_json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+    pub fn write_json_rows(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        data: &serde_json::Value,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        use serde_json::Value;
+
+        let Value::Array(records) = data else {
+            return Err(XlsxError::ParameterError(
+                "JSON data must be an array of objects".to_string(),
+            ));
+        };
+
+        // Collect the headers from the keys of all the objects, in the order
+        // that they are first seen.
+        let mut headers: Vec<&str> = vec![];
+        let mut header_cols: HashMap<&str, usize> = HashMap::new();
+        for record in records {
+            let Value::Object(object) = record else {
+                return Err(XlsxError::ParameterError(
+                    "JSON data must be an array of objects".to_string(),
+                ));
+            };
+
+            for key in object.keys() {
+                if !header_cols.contains_key(key.as_str()) {
+                    header_cols.insert(key, headers.len());
+                    headers.push(key);
+                }
+            }
+        }
+
+        if headers.is_empty() {
+            return Ok(self);
+        }
+
+        // Check that the last cell of the data is within the worksheet limits
+        // before writing any of the data.
+        let last_row = u64::from(row) + records.len() as u64;
+        let last_col = usize::from(col) + headers.len() - 1;
+        if last_row >= u64::from(ROW_MAX) || last_col >= usize::from(COL_MAX) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        for (offset, header) in headers.iter().enumerate() {
+            self.write_string(row, col + offset as ColNum, *header)?;
+        }
+
+        for (row_offset, record) in records.iter().enumerate() {
+            let Value::Object(object) = record else {
+                continue;
+            };
+            let row = row + 1 + row_offset as RowNum;
+
+            for (key, value) in object {
+                let col = col + header_cols[key.as_str()] as ColNum;
+
+                match value {
+                    Value::Null => {}
+                    Value::Bool(boolean) => {
+                        self.write_boolean(row, col, *boolean)?;
+                    }
+                    Value::Number(number) => match number.as_f64() {
+                        Some(number) => {
+                            self.write_number(row, col, number)?;
+                        }
+                        None => {
+                            self.write_string(row, col, number.to_string())?;
+                        }
+                    },
+                    Value::String(string) => {
+                        self.write_string(row, col, string)?;
+                    }
+                    Value::Array(_) | Value::Object(_) => {
+                        self.write_string(row, col, value.to_string())?;
+                    }
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Write a 2D `ndarray` array to a worksheet.
+    ///
+    /// The `write_array2()` method writes the elements of an
+    /// [`ndarray::Array2`], or a 2D view of an array, to a worksheet with the
+    /// first element at the `row` and `col` position. The array rows are
+    /// written as worksheet rows and the array columns as worksheet columns.
+    ///
+    /// The elements can be any type that implements [`IntoExcelData`] such as
+    /// `f64` and other numeric types, `bool` or strings.
+    ///
+    /// See also [`Worksheet::write_array2_with_labels()`] to add row and column
+    /// labels to the data.
+    ///
+    /// This method requires the `ndarray` feature.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `data` - A 2D `ndarray` array or view.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing 2D `ndarray` arrays to a
+    /// worksheet, with and without labels.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_array2.rs
+    /// #
+    /// # use ndarray::array;
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let matrix = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    ///
+    ///     // Write the matrix.
+    ///     worksheet.write_array2(0, 0, &matrix)?;
+    ///
+    ///     // Write the transposed matrix with labels.
+    ///     worksheet.write_array2_with_labels(
+    ///         0,
+    ///         4,
+    ///         &matrix.t(),
+    ///         &["x", "y", "z"],
+    ///         &["Sample 1", "Sample 2"],
+    ///     )?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "ndarray")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    pub fn write_array2<S, T>(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        data: &ndarray::ArrayBase<S, ndarray::Ix2>,
+    ) -> Result<&mut Worksheet, XlsxError>
+    where
+        S: ndarray::Data<Elem = T>,
+        T: IntoExcelData + Clone,
+    {
+        self.write_array2_with_labels::<S, T, &str, &str>(row, col, data, &[], &[])
+    }
+
+    /// Write a 2D `ndarray` array to a worksheet with row and column labels.
+    ///
+    /// This method is similar to [`Worksheet::write_array2()`] except that it
+    /// also writes optional labels for the rows and columns of the data. The
+    /// column labels are written in the row above the data and the row labels
+    /// are written in the column to the left of the data, with the top left
+    /// cell at the `row` and `col` position left empty.
+    ///
+    /// An empty slice can be used to omit either of the labels.
+    ///
+    /// This method requires the `ndarray` feature.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `data` - A 2D `ndarray` array or view.
+    /// * `row_labels` - The labels for the array rows, or an empty slice.
+    /// * `col_labels` - The labels for the array columns, or an empty slice.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The number of labels doesn't match the
+    ///   number of rows or columns in the array.
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
+    ///
+    #[cfg(feature = "ndarray")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+    pub fn write_array2_with_labels<S, T, R, C>(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        data: &ndarray::ArrayBase<S, ndarray::Ix2>,
+        row_labels: &[R],
+        col_labels: &[C],
+    ) -> Result<&mut Worksheet, XlsxError>
+    where
+        S: ndarray::Data<Elem = T>,
+        T: IntoExcelData + Clone,
+        R: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let (num_rows, num_cols) = data.dim();
+
+        if !row_labels.is_empty() && row_labels.len() != num_rows {
+            return Err(XlsxError::ParameterError(format!(
+                "Number of row labels '{}' doesn't match the number of array rows '{num_rows}'",
+                row_labels.len()
+            )));
+        }
+
+        if !col_labels.is_empty() && col_labels.len() != num_cols {
+            return Err(XlsxError::ParameterError(format!(
+                "Number of column labels '{}' doesn't match the number of array columns '{num_cols}'",
+                col_labels.len()
+            )));
+        }
+
+        // Check that the labels and data are within the worksheet limits
+        // before writing any of the data.
+        let first_row = u64::from(row) + u64::from(!col_labels.is_empty());
+        let first_col = usize::from(col) + usize::from(!row_labels.is_empty());
+        if first_row + num_rows as u64 > u64::from(ROW_MAX)
+            || first_col + num_cols > usize::from(COL_MAX)
+        {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+        let first_row = first_row as RowNum;
+        let first_col = first_col as ColNum;
+
+        for (offset, label) in col_labels.iter().enumerate() {
+            self.write_string(row, first_col + offset as ColNum, label.as_ref())?;
+        }
+
+        for (offset, label) in row_labels.iter().enumerate() {
+            self.write_string(first_row + offset as RowNum, col, label.as_ref())?;
+        }
+
+        for ((row_offset, col_offset), value) in data.indexed_iter() {
+            self.write(
+                first_row + row_offset as RowNum,
+                first_col + col_offset as ColNum,
+                value.clone(),
+            )?;
         }
 
         Ok(self)
@@ -2873,16 +3409,127 @@ impl Worksheet {
         self.store_formula(row, col, formula.into(), Some(format))
     }
 
-    /// Write an  array formula to a worksheet cell.
+    /// Write a formula with a typed result to a worksheet cell.
     ///
-    /// The `write_array_formula()` method writes an array formula to a
-    /// cell range. In Excel an array formula is a formula that performs a
-    /// calculation on a range of values. It can return a single value or a
-    /// range/"array" of values.
+    /// The `write_formula_with_result()` method writes a formula and its
+    /// result in one call. It is equivalent to [`Worksheet::write_formula()`]
+    /// followed by [`Worksheet::set_formula_result()`] except that the type of
+    /// the result is taken from the Rust type: numbers are stored as numbers,
+    /// `bool` values as `TRUE`/`FALSE` and strings as strings, even if they look
+    /// like numbers. Error results can be written with [`FormulaResult::Error`].
     ///
-    /// An array formula is displayed with a pair of curly brackets around the
-    /// formula like this: `{=SUM(A1:B1*A2:B2)}`. The `write_array()`
-    /// method doesn't require actually require these so you can omit them in
+    /// See [Formula Results](crate::Formula#formula-results) for an
+    /// explanation of when a formula result is required.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `formula` - The formula to write to the cell as a string or
+    ///   [`Formula`].
+    /// * `result` - The formula result as a number, `bool`, string or
+    ///   [`FormulaResult`].
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::ParameterError`] - The result is an unknown Excel error
+    ///   value.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing formulas with typed results.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_formula_with_result.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write_number(0, 0, 5)?;
+    ///
+    ///     worksheet.write_formula_with_result(0, 1, "=A1*2", 10)?;
+    ///     worksheet.write_formula_with_result(0, 2, "=A1>3", true)?;
+    ///     worksheet.write_formula_with_result(0, 3, r#"=TEXT(A1, "00")"#, "05")?;
+    /// #
+    /// #     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn write_formula_with_result(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        formula: impl Into<Formula>,
+        result: impl Into<FormulaResult>,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let result = result.into();
+        result.validate()?;
+
+        let mut formula = formula.into();
+        formula.result = Some(result);
+
+        // Store the cell data.
+        self.store_formula(row, col, formula, None)
+    }
+
+    /// Write a formatted formula with a typed result to a worksheet cell.
+    ///
+    /// The `write_formula_with_format_and_result()` method is the same as
+    /// [`Worksheet::write_formula_with_result()`] except that it also applies
+    /// a [`Format`] to the cell.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `formula` - The formula to write to the cell as a string or
+    ///   [`Formula`].
+    /// * `format` - The [`Format`] property for the cell.
+    /// * `result` - The formula result as a number, `bool`, string or
+    ///   [`FormulaResult`].
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::ParameterError`] - The result is an unknown Excel error
+    ///   value.
+    ///
+    pub fn write_formula_with_format_and_result(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        formula: impl Into<Formula>,
+        format: &Format,
+        result: impl Into<FormulaResult>,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let result = result.into();
+        result.validate()?;
+
+        let mut formula = formula.into();
+        formula.result = Some(result);
+
+        // Store the cell data.
+        self.store_formula(row, col, formula, Some(format))
+    }
+
+    /// Write an  array formula to a worksheet cell.
+    ///
+    /// The `write_array_formula()` method writes an array formula to a
+    /// cell range. In Excel an array formula is a formula that performs a
+    /// calculation on a range of values. It can return a single value or a
+    /// range/"array" of values.
+    ///
+    /// An array formula is displayed with a pair of curly brackets around the
+    /// formula like this: `{=SUM(A1:B1*A2:B2)}`. The `write_array()`
+    /// method doesn't require actually require these so you can omit them in
     /// the formula, and the equal sign, if you wish like this:
     /// `SUM(A1:B1*A2:B2)`.
     ///
@@ -3066,6 +3713,136 @@ impl Worksheet {
         )
     }
 
+    /// Write a shared formula to a range of worksheet cells.
+    ///
+    /// The `write_shared_formula()` method writes the same formula to every
+    /// cell in a range, with relative cell references adjusted for each cell,
+    /// in the same way as filling or copying a formula in Excel. For example
+    /// writing `=B1*C1` to the range `A1:A3` gives the formulas `=B1*C1`,
+    /// `=B2*C2` and `=B3*C3`.
+    ///
+    /// Excel stores the formula once, in the first cell of the range, and the
+    /// other cells refer back to it. This makes files with the same formula in
+    /// thousands of rows much smaller than writing each formula separately
+    /// with [`Worksheet::write_formula()`].
+    ///
+    /// The formula should be written as it would appear in the first cell of
+    /// the range. Absolute references, like `$B$1`, aren't adjusted. The first
+    /// cell of the range shouldn't be overwritten with other data since the
+    /// rest of the range depends on it.
+    ///
+    /// # Parameters
+    ///
+    /// * `first_row` - The first row of the range. (All zero indexed.)
+    /// * `first_col` - The first column of the range.
+    /// * `last_row` - The last row of the range.
+    /// * `last_col` - The last column of the range.
+    /// * `formula` - The formula to write to the cells as a string or
+    ///   [`Formula`].
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::RowColumnOrderError`] - First row or column is larger
+    ///   than the last row or column.
+    /// * [`XlsxError::ParameterError`] - Dynamic array formulas can't be
+    ///   shared.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing a shared formula to a
+    /// column of cells.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_shared_formula.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Write some test data.
+    /// #     for row in 0..1000 {
+    /// #         worksheet.write(row, 0, row)?;
+    /// #         worksheet.write(row, 1, 2)?;
+    /// #     }
+    /// #
+    ///     // Write "=A1*B1" to C1, "=A2*B2" to C2, and so on.
+    ///     worksheet.write_shared_formula(0, 2, 999, 2, "=A1*B1")?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn write_shared_formula(
+        &mut self,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+        formula: impl Into<Formula>,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Store the cell data.
+        self.store_shared_formula(
+            first_row,
+            first_col,
+            last_row,
+            last_col,
+            formula.into(),
+            None,
+        )
+    }
+
+    /// Write a formatted shared formula to a range of worksheet cells.
+    ///
+    /// The `write_shared_formula_with_format()` method writes the same
+    /// formula, with adjusted relative references, and the same format to
+    /// every cell in a range. See [`Worksheet::write_shared_formula()`] for
+    /// details.
+    ///
+    /// # Parameters
+    ///
+    /// * `first_row` - The first row of the range. (All zero indexed.)
+    /// * `first_col` - The first column of the range.
+    /// * `last_row` - The last row of the range.
+    /// * `last_col` - The last column of the range.
+    /// * `formula` - The formula to write to the cells as a string or
+    ///   [`Formula`].
+    /// * `format` - The [`Format`] property for the cells.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::RowColumnOrderError`] - First row or column is larger
+    ///   than the last row or column.
+    /// * [`XlsxError::ParameterError`] - Dynamic array formulas can't be
+    ///   shared.
+    ///
+    pub fn write_shared_formula_with_format(
+        &mut self,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+        formula: impl Into<Formula>,
+        format: &Format,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Store the cell data.
+        self.store_shared_formula(
+            first_row,
+            first_col,
+            last_row,
+            last_col,
+            formula.into(),
+            Some(format),
+        )
+    }
+
     /// Write a dynamic array formula to a worksheet cell or range of cells.
     ///
     /// The `write_dynamic_array_formula()` function writes an Excel 365
@@ -3483,6 +4260,7 @@ impl Worksheet {
     ///   worksheet limits.
     /// * [`XlsxError::MaxUrlLengthExceeded`] - URL string or anchor exceeds
     ///   Excel's limit of 2080 characters.
+    ///   This can be changed with [`Worksheet::set_url_length_policy()`].
     /// * [`XlsxError::UnknownUrlType`] - The URL has an unknown URI type. See
     ///   the supported types listed above.
     ///
@@ -3603,6 +4381,7 @@ impl Worksheet {
     ///   limit of 32,767 characters.
     /// * [`XlsxError::MaxUrlLengthExceeded`] - URL string or anchor exceeds
     ///   Excel's limit of 2080 characters.
+    ///   This can be changed with [`Worksheet::set_url_length_policy()`].
     /// * [`XlsxError::UnknownUrlType`] - The URL has an unknown URI type. See
     ///   the supported types listed above.
     ///
@@ -3694,6 +4473,7 @@ impl Worksheet {
     ///   worksheet limits.
     /// * [`XlsxError::MaxUrlLengthExceeded`] - URL string or anchor exceeds
     ///   Excel's limit of 2080 characters.
+    ///   This can be changed with [`Worksheet::set_url_length_policy()`].
     /// * [`XlsxError::UnknownUrlType`] - The URL has an unknown URI type. See
     ///   the supported types listed above.
     ///
@@ -3772,6 +4552,7 @@ impl Worksheet {
     ///   limit of 32,767 characters.
     /// * [`XlsxError::MaxUrlLengthExceeded`] - URL string or anchor exceeds
     ///   Excel's limit of 2080 characters or the screen tip exceed 255 characters.
+    ///   This can be changed with [`Worksheet::set_url_length_policy()`].
     /// * [`XlsxError::UnknownUrlType`] - The URL has an unknown URI type. See
     ///   the supported types listed above.
     ///
@@ -3789,6 +4570,55 @@ impl Worksheet {
         self.store_url(row, col, link, format)
     }
 
+    /// Set the policy for handling urls that exceed Excel's length limits.
+    ///
+    /// Excel has a limit of 2080 characters for a url and its anchor and a
+    /// limit of 255 characters for the screen tip. By default the url writing
+    /// methods, such as [`Worksheet::write_url()`], return an
+    /// [`XlsxError::MaxUrlLengthExceeded`] error for urls beyond these limits.
+    ///
+    /// For data driven output, where the urls aren't known in advance, it may
+    /// be preferable to truncate the url or write it as plain text and continue.
+    /// See [`UrlLengthPolicy`] for the available options.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - A [`UrlLengthPolicy`] enum value.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing an over-length url as plain
+    /// text instead of raising an error.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_url_length_policy.rs
+    /// #
+    /// # use rust_xlsxwriter::{UrlLengthPolicy, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.set_url_length_policy(UrlLengthPolicy::WriteAsText);
+    ///
+    ///     // This url is too long for Excel and is written as a string.
+    ///     let url = format!("https://www.example.com/{}", "a".repeat(2100));
+    ///     worksheet.write_url(0, 0, url.as_str())?;
+    ///
+    ///     // This url is written as a link.
+    ///     worksheet.write_url(1, 0, "https://www.example.com/")?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_url_length_policy(&mut self, policy: UrlLengthPolicy) -> &mut Worksheet {
+        self.url_length_policy = policy;
+        self
+    }
+
     /// Write a formatted date and/or time to a worksheet cell.
     ///
     /// The method method writes dates/times that implements [`IntoExcelDateTime`]
@@ -4072,111 +4902,222 @@ impl Worksheet {
         self.store_datetime(row, col, datetime, None)
     }
 
-    #[doc(hidden)] // Hide the docs since this functionality is provided by `write_datetime_with_format()`.
-    /// Write a formatted date to a worksheet cell.
-    ///
-    /// The method method writes dates/times that implements [`IntoExcelDateTime`]
-    /// to a worksheet cell.
-    ///
-    /// The date/time types supported are:
-    /// - [`ExcelDateTime`].
+    /// Write a formatted Unix timestamp to a worksheet cell.
     ///
-    /// If the `chrono` feature is enabled you can use the following types:
+    /// The `write_unix_timestamp()` method writes a [Unix Time], the number of
+    /// seconds since the 1970-01-01 00:00:00 UTC epoch, to a worksheet cell as
+    /// an Excel date/time. This is a common format for timestamps exported
+    /// from databases and log files.
     ///
-    /// - [`chrono::NaiveDateTime`].
-    /// - [`chrono::NaiveDate`].
-    /// - [`chrono::NaiveTime`].
+    /// Dates and times in Excel are numbers with a format so a `format` is
+    /// required to display the cell as a date/time. See
+    /// [`write_datetime_with_format()`](Worksheet::write_datetime_with_format)
+    /// for more details.
     ///
-    /// [`chrono::NaiveDate`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDate.html
-    /// [`chrono::NaiveTime`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveTime.html
-    /// [`chrono::NaiveDateTime`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDateTime.html
+    /// The timestamp is converted with
+    /// [`ExcelDateTime::from_timestamp()`]. Excel doesn't have a concept of
+    /// timezones so the time is written as UTC.
     ///
-    /// Excel stores dates and times as a floating point number with a number
-    /// format to defined how it is displayed. The number format is set via a
-    /// [`Format`] struct which can also control visual formatting such as bold
-    /// and italic text.
+    /// [Unix Time]: https://en.wikipedia.org/wiki/Unix_time
     ///
     /// # Parameters
     ///
     /// * `row` - The zero indexed row number.
     /// * `col` - The zero indexed column number.
-    /// * `date` - A date/time instance that implements [`IntoExcelDateTime`].
+    /// * `timestamp` - Unix time in seconds in the range -2,209,075,200 to
+    ///   253,402,300,800 (years 1900 to 9999).
     /// * `format` - The [`Format`] property for the cell.
     ///
     /// # Errors
     ///
     /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
     ///   worksheet limits.
+    /// * [`XlsxError::DateTimeRangeError`] - The timestamp is outside Excel's
+    ///   date range of 1900 to 9999.
     ///
     /// # Examples
     ///
-    /// The following example demonstrates writing formatted dates in an Excel
-    /// worksheet.
+    /// The following example demonstrates writing Unix timestamps in seconds
+    /// and milliseconds to a worksheet.
     ///
     /// ```
-    /// # // This code is available in examples/doc_worksheet_write_date.rs
+    /// # // This code is available in examples/doc_worksheet_write_unix_timestamp.rs
     /// #
-    /// # use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
+    /// # use rust_xlsxwriter::{Format, Workbook, XlsxError};
     /// #
     /// # fn main() -> Result<(), XlsxError> {
     /// #     let mut workbook = Workbook::new();
     /// #
     /// #     // Add a worksheet to the workbook.
-    ///     let worksheet = workbook.add_worksheet();
-    ///
-    ///     // Create some formats to use with the dates below.
-    ///     let format1 = Format::new().set_num_format("dd/mm/yyyy");
-    ///     let format2 = Format::new().set_num_format("mm/dd/yyyy");
-    ///     let format3 = Format::new().set_num_format("yyyy-mm-dd");
-    ///     let format4 = Format::new().set_num_format("ddd dd mmm yyyy");
-    ///     let format5 = Format::new().set_num_format("dddd, mmmm dd, yyyy");
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create a format to use with the timestamps.
+    ///     let format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss.000");
     ///
     ///     // Set the column width for clarity.
-    ///     worksheet.set_column_width(0, 30)?;
-    ///
-    ///     // Create a date object.
-    ///     let date = ExcelDateTime::from_ymd(2023, 1, 25)?;
-    ///
-    ///     // Write the date with different Excel formats.
-    ///     worksheet.write_date_with_format(0, 0, &date, &format1)?;
-    ///     worksheet.write_date_with_format(1, 0, &date, &format2)?;
-    ///     worksheet.write_date_with_format(2, 0, &date, &format3)?;
-    ///     worksheet.write_date_with_format(3, 0, &date, &format4)?;
-    ///     worksheet.write_date_with_format(4, 0, &date, &format5)?;
+    ///     worksheet.set_column_width(0, 25)?;
     ///
+    ///     // Write timestamps in seconds and milliseconds.
+    ///     worksheet.write_unix_timestamp(0, 0, 1_687_108_108, &format)?;
+    ///     worksheet.write_unix_timestamp_millis(1, 0, 1_687_108_108_250, &format)?;
+    /// #
     /// #     workbook.save("worksheet.xlsx")?;
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    /// Output file:
-    ///
-    /// <img src="https://rustxlsxwriter.github.io/images/worksheet_write_date.png">
-    ///
-    pub fn write_date_with_format(
+    pub fn write_unix_timestamp(
         &mut self,
         row: RowNum,
         col: ColNum,
-        date: impl IntoExcelDateTime,
+        timestamp: i64,
         format: &Format,
     ) -> Result<&mut Worksheet, XlsxError> {
-        let datetime = date.to_excel_serial_date();
+        let datetime = ExcelDateTime::from_timestamp(timestamp)?;
 
-        // Store the cell data.
-        self.store_datetime(row, col, datetime, Some(format))
+        self.write_datetime_with_format(row, col, datetime, format)
     }
 
-    #[doc(hidden)] // Hide the docs since this functionality is provided by `write_datetime_with_format()`.
-    /// Write a formatted time to a worksheet cell.
+    /// Write a formatted Unix timestamp in milliseconds to a worksheet cell.
     ///
-    /// The method method writes dates/times that implements [`IntoExcelDateTime`]
-    /// to a worksheet cell.
+    /// The `write_unix_timestamp_millis()` method is the same as
+    /// [`write_unix_timestamp()`](Worksheet::write_unix_timestamp) except that
+    /// the timestamp is in milliseconds since the Unix epoch. Excel supports
+    /// millisecond resolution in times.
     ///
-    /// The date/time types supported are:
-    /// - [`ExcelDateTime`].
+    /// # Parameters
     ///
-    /// If the `chrono` feature is enabled you can use the following types:
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `timestamp` - Unix time in milliseconds in the range
+    ///   -2,209,075,200,000 to 253,402,300,800,000 (years 1900 to 9999).
+    /// * `format` - The [`Format`] property for the cell.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::DateTimeRangeError`] - The timestamp is outside Excel's
+    ///   date range of 1900 to 9999.
+    ///
+    pub fn write_unix_timestamp_millis(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        timestamp: i64,
+        format: &Format,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let datetime = ExcelDateTime::from_timestamp_millis(timestamp)?;
+
+        self.write_datetime_with_format(row, col, datetime, format)
+    }
+
+    #[doc(hidden)] // Hide the docs since this functionality is provided by `write_datetime_with_format()`.
+    /// Write a formatted date to a worksheet cell.
+    ///
+    /// The method method writes dates/times that implements [`IntoExcelDateTime`]
+    /// to a worksheet cell.
+    ///
+    /// The date/time types supported are:
+    /// - [`ExcelDateTime`].
+    ///
+    /// If the `chrono` feature is enabled you can use the following types:
+    ///
+    /// - [`chrono::NaiveDateTime`].
+    /// - [`chrono::NaiveDate`].
+    /// - [`chrono::NaiveTime`].
+    ///
+    /// [`chrono::NaiveDate`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDate.html
+    /// [`chrono::NaiveTime`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveTime.html
+    /// [`chrono::NaiveDateTime`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDateTime.html
+    ///
+    /// Excel stores dates and times as a floating point number with a number
+    /// format to defined how it is displayed. The number format is set via a
+    /// [`Format`] struct which can also control visual formatting such as bold
+    /// and italic text.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `date` - A date/time instance that implements [`IntoExcelDateTime`].
+    /// * `format` - The [`Format`] property for the cell.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing formatted dates in an Excel
+    /// worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_date.rs
+    /// #
+    /// # use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Create some formats to use with the dates below.
+    ///     let format1 = Format::new().set_num_format("dd/mm/yyyy");
+    ///     let format2 = Format::new().set_num_format("mm/dd/yyyy");
+    ///     let format3 = Format::new().set_num_format("yyyy-mm-dd");
+    ///     let format4 = Format::new().set_num_format("ddd dd mmm yyyy");
+    ///     let format5 = Format::new().set_num_format("dddd, mmmm dd, yyyy");
+    ///
+    ///     // Set the column width for clarity.
+    ///     worksheet.set_column_width(0, 30)?;
+    ///
+    ///     // Create a date object.
+    ///     let date = ExcelDateTime::from_ymd(2023, 1, 25)?;
+    ///
+    ///     // Write the date with different Excel formats.
+    ///     worksheet.write_date_with_format(0, 0, &date, &format1)?;
+    ///     worksheet.write_date_with_format(1, 0, &date, &format2)?;
+    ///     worksheet.write_date_with_format(2, 0, &date, &format3)?;
+    ///     worksheet.write_date_with_format(3, 0, &date, &format4)?;
+    ///     worksheet.write_date_with_format(4, 0, &date, &format5)?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/worksheet_write_date.png">
+    ///
+    pub fn write_date_with_format(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        date: impl IntoExcelDateTime,
+        format: &Format,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let datetime = date.to_excel_serial_date();
+
+        // Store the cell data.
+        self.store_datetime(row, col, datetime, Some(format))
+    }
+
+    #[doc(hidden)] // Hide the docs since this functionality is provided by `write_datetime_with_format()`.
+    /// Write a formatted time to a worksheet cell.
+    ///
+    /// The method method writes dates/times that implements [`IntoExcelDateTime`]
+    /// to a worksheet cell.
+    ///
+    /// The date/time types supported are:
+    /// - [`ExcelDateTime`].
+    ///
+    /// If the `chrono` feature is enabled you can use the following types:
     ///
     /// - [`chrono::NaiveDateTime`].
     /// - [`chrono::NaiveDate`].
@@ -4472,6 +5413,7 @@ impl Worksheet {
         format: &Format,
     ) -> Result<&mut Worksheet, XlsxError> {
         // Check rows and cols are in the allowed range.
+        self.check_flushed_row(cmp::min(first_row, last_row))?;
         if !self.check_dimensions(first_row, first_col)
             || !self.check_dimensions(last_row, last_col)
         {
@@ -5081,6 +6023,160 @@ impl Worksheet {
         Ok(self)
     }
 
+    /// Add a note to a worksheet cell.
+    ///
+    /// Notes are used to add a text annotation to a worksheet cell. They are
+    /// displayed in a text box when the user hovers over the cell and a small
+    /// red triangle is shown in the corner of the cell to indicate that it
+    /// contains a note. See [`Note`] for details of the properties that can be
+    /// set for a note.
+    ///
+    /// In older versions of Excel notes were called "Comments".
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `note` - The [`Note`] to insert into the cell.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::MaxStringLengthExceeded`] - The note text exceeds Excel's
+    ///   limit of 32,767 characters.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a note to a worksheet cell.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_insert_note.rs
+    /// #
+    /// # use rust_xlsxwriter::{Note, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Write some data.
+    ///     worksheet.write(0, 0, "Hello")?;
+    ///
+    ///     // Create a new note.
+    ///     let note = Note::new("Some text for the note");
+    ///
+    ///     // Add the note to a worksheet cell.
+    ///     worksheet.insert_note(0, 0, &note)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("notes.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn insert_note(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        note: &Note,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check row and columns are in the allowed range.
+        if !self.check_dimensions_only(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Check that the note text is within Excel's limit.
+        if note.text.chars().count() > MAX_STRING_LEN {
+            return Err(XlsxError::MaxStringLengthExceeded);
+        }
+
+        self.warnings.extend(&note.warnings);
+
+        let mut note = note.clone();
+        note.cell_row = row;
+        note.cell_col = col;
+
+        self.notes.entry(row).or_default().insert(col, note);
+
+        Ok(self)
+    }
+
+    /// Set the default author name for all the notes in a worksheet.
+    ///
+    /// The author name is stored in a note and is displayed in the Excel
+    /// status bar when the user hovers over the cell. This method sets a
+    /// default author for all the notes in the worksheet. It can be overridden
+    /// for individual notes via [`Note::set_author()`].
+    ///
+    /// By default the author name is also added, in bold, as a prefix to the
+    /// note text. This can be turned off via [`Note::add_author_prefix()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The default note author name.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding notes to worksheet cells with a note specific author
+    /// and with the default worksheet author.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_note_set_author.rs
+    /// #
+    /// # use rust_xlsxwriter::{Note, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Set the default author for notes in the worksheet.
+    ///     worksheet.set_comment_author("Rust");
+    ///
+    ///     // Write some data.
+    ///     worksheet.write(0, 0, "Hello")?;
+    ///     worksheet.write(2, 0, "World")?;
+    ///
+    ///     // Create a note with the default worksheet author.
+    ///     let note1 = Note::new("Some text for the note");
+    ///
+    ///     // Create a note with a different author.
+    ///     let note2 = Note::new("Some other text").set_author("Ferris");
+    ///
+    ///     // Add the notes to worksheet cells.
+    ///     worksheet.insert_note(0, 0, &note1)?;
+    ///     worksheet.insert_note(2, 0, &note2)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("notes.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_comment_author(&mut self, name: impl Into<String>) -> &mut Worksheet {
+        self.comment_author = name.into();
+        self
+    }
+
+    /// Make all the notes in a worksheet visible when the file loads.
+    ///
+    /// By default Excel hides cell notes until the user mouses over the parent
+    /// cell. This method makes all the notes in the worksheet visible when the
+    /// file is opened. It can be overridden for individual notes via
+    /// [`Note::set_visible()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    ///
+    pub fn show_all_notes(&mut self, enable: bool) -> &mut Worksheet {
+        self.show_all_notes = enable;
+        self
+    }
+
     /// Set the height for a row of cells.
     ///
     /// The `set_row_height()` method is used to change the default height of a
@@ -5147,6 +6243,7 @@ impl Worksheet {
         let min_col = self.get_min_col();
 
         // Check row is in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, min_col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -5220,8 +6317,68 @@ impl Worksheet {
         row: RowNum,
         height: u16,
     ) -> Result<&mut Worksheet, XlsxError> {
-        let height = 0.75 * f64::from(height);
+        let height = utility::pixels_to_points(f64::from(height));
+
+        self.set_row_height(row, height)
+    }
 
+    /// Set the height for a row of cells, in points.
+    ///
+    /// The `set_row_height_points()` method is used to change the default
+    /// height of a row. The height is specified in points, where a point is
+    /// 1/72 of an inch and the default height is 15. This is the same unit used
+    /// by [`set_row_height()`](Worksheet::set_row_height()) and this method is
+    /// provided to make the unit explicit in code.
+    ///
+    /// Heights in other units can be converted to points with
+    /// [`inches_to_points()`](crate::inches_to_points),
+    /// [`cm_to_points()`](crate::cm_to_points) and
+    /// [`pixels_to_points()`](crate::pixels_to_points).
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `height` - The row height in points.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row exceeds Excel's worksheet
+    ///   limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the height for a row in
+    /// points, and in centimeters converted to points.
+    ///
+    /// ```
+    /// # use rust_xlsxwriter::{cm_to_points, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Add some text.
+    ///     worksheet.write_string(0, 0, "Normal")?;
+    ///     worksheet.write_string(2, 0, "Taller")?;
+    ///     worksheet.write_string(4, 0, "1 cm")?;
+    ///
+    ///     // Set the row heights in points.
+    ///     worksheet.set_row_height_points(2, 30)?;
+    ///     worksheet.set_row_height_points(4, cm_to_points(1.0))?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_row_height_points(
+        &mut self,
+        row: RowNum,
+        height: impl Into<f64>,
+    ) -> Result<&mut Worksheet, XlsxError> {
         self.set_row_height(row, height)
     }
 
@@ -5291,6 +6448,7 @@ impl Worksheet {
         let min_col = self.get_min_col();
 
         // Check row is in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, min_col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -5364,6 +6522,7 @@ impl Worksheet {
         let min_col = self.get_min_col();
 
         // Check row is in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, min_col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -5404,6 +6563,7 @@ impl Worksheet {
         let min_col = self.get_min_col();
 
         // Check row is in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, min_col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -5565,16 +6725,68 @@ impl Worksheet {
         col: ColNum,
         width: u16,
     ) -> Result<&mut Worksheet, XlsxError> {
-        // Properties for Calibri 11.
-        let max_digit_width = 7.0_f64;
-        let padding = 5.0_f64;
-        let mut width = f64::from(width);
+        let width = utility::pixels_to_column_width(width);
 
-        if width < 12.0 {
-            width /= max_digit_width + padding;
-        } else {
-            width = (width - padding) / max_digit_width;
-        }
+        self.set_column_width(col, width)
+    }
+
+    /// Set the width for a worksheet column in centimeters.
+    ///
+    /// The `set_column_width_cm()` method is used to change the default width
+    /// of a worksheet column using a physical size, for example to match a
+    /// printed layout. The width is converted to pixels at the standard 96 dpi
+    /// screen resolution and then to Excel character units, so the actual
+    /// width is rounded to the nearest pixel.
+    ///
+    /// See also the [`column_width_to_pixels()`](crate::column_width_to_pixels)
+    /// and related unit conversion functions.
+    ///
+    /// # Parameters
+    ///
+    /// * `col` - The zero indexed column number.
+    /// * `width` - The column width in centimeters.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Column exceeds Excel's worksheet
+    ///   limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the width of columns in
+    /// centimeters.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_column_width_cm.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Add some text.
+    ///     worksheet.write_string(0, 0, "Normal")?;
+    ///     worksheet.write_string(0, 2, "5 cm")?;
+    ///
+    ///     // Set the column width in centimeters.
+    ///     worksheet.set_column_width_cm(2, 5.0)?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_column_width_cm(
+        &mut self,
+        col: ColNum,
+        width: impl Into<f64>,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let pixels = utility::points_to_pixels(utility::cm_to_points(width.into()));
+        let width = utility::pixels_to_column_width(pixels.round() as u16);
 
         self.set_column_width(col, width)
     }
@@ -5643,8 +6855,9 @@ impl Worksheet {
         col: ColNum,
         format: &Format,
     ) -> Result<&mut Worksheet, XlsxError> {
-        // Set a suitable row range for the dimension check/set.
-        let min_row = self.get_min_row();
+        // Set a suitable row range for the dimension check/set. Flushed rows
+        // can't be used for the check.
+        let min_row = cmp::max(self.get_min_row(), self.flushed_rows);
 
         // Check column is in the allowed range.
         if !self.check_dimensions(min_row, col) {
@@ -5767,6 +6980,8 @@ impl Worksheet {
     ///   worksheet limits.
     /// * [`XlsxError::RowColumnOrderError`] - First row larger than the last
     ///   row.
+    /// * [`XlsxError::RowFlushedError`] - The range overlaps rows that have
+    ///   been flushed with [`Worksheet::flush_rows()`].
     ///
     /// # Examples
     ///
@@ -5831,6 +7046,9 @@ impl Worksheet {
             return Err(XlsxError::RowColumnOrderError);
         }
 
+        // Rows that don't match the filters can't be hidden once flushed.
+        self.check_flushed_row(first_row)?;
+
         // Store the defined name information.
         self.autofilter_defined_name.in_use = true;
         self.autofilter_defined_name.name_type = DefinedNameType::Autofilter;
@@ -5900,12 +7118,15 @@ impl Worksheet {
     ///
     /// * [`XlsxError::RowColumnLimitError`] - Column exceeds Excel's worksheet
     ///   limits.
+    /// * [`XlsxError::RowFlushedError`] - The autofilter range overlaps rows
+    ///   that have been flushed with [`Worksheet::flush_rows()`].
     /// * [`XlsxError::ParameterError`] - Parameter error for the following
     ///   issues:
     ///   - The [`autofilter()`](Worksheet::autofilter) range hasn't been set.
     ///   - The column is outside the [`autofilter()`](Worksheet::autofilter)
     ///     range.
     ///   - The [`FilterCondition`] doesn't have a condition set.
+    ///   - The [`FilterCondition`] color filter has an invalid color.
     ///
     /// # Examples
     ///
@@ -5987,17 +7208,21 @@ impl Worksheet {
             return Err(XlsxError::ParameterError(error));
         }
 
+        // Rows that don't match the filter can't be hidden once flushed.
+        self.check_flushed_row(self.autofilter_defined_name.first_row)?;
+
         // Check the filter condition have been set up correctly.
-        if filter_condition.list.is_empty()
-            && filter_condition.custom1.is_none()
-            && !filter_condition.should_match_blanks
-        {
-            let error =
-                "The 'filter_condition' doesn't have a data value or condition set.".to_string();
-            return Err(XlsxError::ParameterError(error));
+        filter_condition.validate()?;
+
+        self.warnings.extend(&filter_condition.warnings);
+
+        // Register the color filter format, if any, as a local dxf index.
+        let mut filter_condition = filter_condition.clone();
+        if let Some(format) = filter_condition.color_format.as_mut() {
+            format.dxf_index = self.format_dxf_index(format);
         }
 
-        self.filter_conditions.insert(col, filter_condition.clone());
+        self.filter_conditions.insert(col, filter_condition);
 
         Ok(self)
     }
@@ -6022,6 +7247,145 @@ impl Worksheet {
         self
     }
 
+    /// Add a named sheet view to a worksheet.
+    ///
+    /// Named sheet views, or "Sheet Views" in the Excel user interface, allow
+    /// users collaborating on a shared Excel 365 file to switch between
+    /// temporary filter and sort views of a worksheet without affecting the
+    /// view of other users. This method can be used to create views in
+    /// advance. See [`NamedSheetView`] for more details.
+    ///
+    /// Any filter or sort conditions in the view apply to the worksheet
+    /// autofilter range so [`Worksheet::autofilter()`] must be called first.
+    ///
+    /// # Parameters
+    ///
+    /// * `view` - The [`NamedSheetView`] to add to the worksheet.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - Parameter error for the following
+    ///   issues:
+    ///   - The view name is blank or has already been used in the worksheet.
+    ///   - The view has filter or sort conditions but the worksheet
+    ///     autofilter range hasn't been set.
+    ///   - A filter or sort column is outside the autofilter column range.
+    ///   - A filter condition doesn't have a data value or condition set.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates adding named sheet views with
+    /// different filters and sorting to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_named_sheet_view.rs
+    /// #
+    /// # use rust_xlsxwriter::{FilterCondition, NamedSheetView, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Write some data to filter.
+    /// #     let data = [
+    /// #         ("Region", "Sales"),
+    /// #         ("East", "3000"),
+    /// #         ("West", "5000"),
+    /// #         ("East", "4000"),
+    /// #         ("South", "2000"),
+    /// #     ];
+    /// #     for (row, (region, sales)) in data.iter().enumerate() {
+    /// #         worksheet.write_string(row as u32, 0, *region)?;
+    /// #         worksheet.write_string(row as u32, 1, *sales)?;
+    /// #     }
+    /// #
+    ///     // The views apply to the worksheet autofilter range.
+    ///     worksheet.autofilter(0, 0, 4, 1)?;
+    ///
+    ///     // Create a view that shows the "East" region only.
+    ///     let filter = FilterCondition::new().add_list_filter("East");
+    ///     let view = NamedSheetView::new("East region").set_filter(0, &filter);
+    ///     worksheet.add_named_sheet_view(&view)?;
+    ///
+    ///     // Create a view that sorts the sales in descending order.
+    ///     let view = NamedSheetView::new("Top sales").set_sort(1, true);
+    ///     worksheet.add_named_sheet_view(&view)?;
+    /// #
+    /// #     workbook.save("named_sheet_views.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn add_named_sheet_view(
+        &mut self,
+        view: &NamedSheetView,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        if view.name.trim().is_empty() {
+            let error = "Named sheet view name cannot be blank.".to_string();
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        if self
+            .named_sheet_views
+            .iter()
+            .any(|existing| existing.name.to_lowercase() == view.name.to_lowercase())
+        {
+            let error = format!(
+                "Named sheet view name '{}' has already been used.",
+                view.name
+            );
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        if view.filter_conditions.is_empty() && view.sort_conditions.is_empty() {
+            self.named_sheet_views.push(view.clone());
+            return Ok(self);
+        }
+
+        // Check that an autofilter has been created before a view filter or
+        // sort can be applied to it.
+        if !self.autofilter_defined_name.in_use {
+            let error =
+                "The 'autofilter()' range must be set before a named sheet view filter or sort can be applied."
+                    .to_string();
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        // Check if the columns are within the autofilter column range.
+        let first_col = self.autofilter_defined_name.first_col;
+        let last_col = self.autofilter_defined_name.last_col;
+        for col in view
+            .filter_conditions
+            .keys()
+            .chain(view.sort_conditions.keys())
+        {
+            if *col < first_col || *col > last_col {
+                let error = format!(
+                    "Col '{col}' outside user defined autofilter column range '{first_col}-{last_col}'"
+                );
+                return Err(XlsxError::ParameterError(error));
+            }
+        }
+
+        // Check the filter conditions have been set up correctly.
+        for filter_condition in view.filter_conditions.values() {
+            filter_condition.validate()?;
+        }
+
+        // Register the color filter formats, if any, as local dxf indices.
+        let mut view = view.clone();
+        for filter_condition in view.filter_conditions.values_mut() {
+            if let Some(format) = filter_condition.color_format.as_mut() {
+                format.dxf_index = self.format_dxf_index(format);
+            }
+        }
+
+        self.named_sheet_views.push(view);
+
+        Ok(self)
+    }
+
     /// Add a table to a worksheet.
     ///
     /// Tables in Excel are a way of grouping a range of cells into a single
@@ -6157,6 +7521,7 @@ impl Worksheet {
 
         let mut table = table.clone();
         table.cell_range = CellRange::new(first_row, first_col, last_row, last_col);
+        table.validate_name()?;
         table.initialize_columns(&default_headers)?;
 
         let first_data_row = table.first_data_row();
@@ -6181,10 +7546,29 @@ impl Worksheet {
             // Write the total row strings or formulas.
             if table.show_total_row {
                 if !column.total_label.is_empty() {
-                    self.write_string(last_row, col, &column.total_label)?;
+                    match &column.total_format {
+                        Some(total_format) => {
+                            self.write_string_with_format(
+                                last_row,
+                                col,
+                                &column.total_label,
+                                total_format,
+                            )?;
+                        }
+                        None => {
+                            self.write_string(last_row, col, &column.total_label)?;
+                        }
+                    }
                 } else if column.total_function != TableFunction::None {
                     let formula = column.total_function();
-                    self.write_formula(last_row, col, formula)?;
+                    match &column.total_format {
+                        Some(total_format) => {
+                            self.write_formula_with_format(last_row, col, formula, total_format)?;
+                        }
+                        None => {
+                            self.write_formula(last_row, col, formula)?;
+                        }
+                    }
                 }
             }
 
@@ -6203,7 +7587,10 @@ impl Worksheet {
                     self.update_cell_format(row, col, format_index);
                 }
 
-                if table.show_total_row && column.total_function != TableFunction::None {
+                if table.show_total_row
+                    && column.total_function != TableFunction::None
+                    && column.total_format.is_none()
+                {
                     self.update_cell_format(last_row, col, format_index);
                 }
             }
@@ -6384,6 +7771,9 @@ impl Worksheet {
         // Validate the conditional format.
         conditional_format.validate()?;
 
+        self.warnings
+            .extend_messages("add_conditional_format", conditional_format.warnings());
+
         // Check for extended Excel 2010 data bars/icons.
         if conditional_format.has_x14_extensions() {
             self.use_x14_extensions = true;
@@ -6417,6 +7807,98 @@ impl Worksheet {
         Ok(self)
     }
 
+    /// Add a data validation to one or more cells to restrict user input.
+    ///
+    /// Data validation is a feature of Excel which allows you to restrict the
+    /// data that a user enters in a cell and to display associated help and
+    /// warning messages. It also allows you to restrict input to values in a
+    /// dropdown list.
+    ///
+    /// The data validation rules are configured with a [`DataValidation`]
+    /// instance. If a data validation is added to a range that already has one
+    /// then it replaces the previous data validation.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::RowColumnOrderError`] - First row larger than the last
+    ///   row.
+    /// * [`XlsxError::DataValidationError`] - A general error that is raised
+    ///   when a data validation parameter is incorrect or missing.
+    ///
+    /// # Parameters
+    ///
+    /// * `first_row` - The first row of the range. (All zero indexed.)
+    /// * `first_col` - The first row of the range.
+    /// * `last_row` - The last row of the range.
+    /// * `last_col` - The last row of the range.
+    /// * `data_validation` - A [`DataValidation`] data validation instance.
+    ///
+    /// # Examples
+    ///
+    /// Example of adding a data validation to a worksheet cell. This validation
+    /// restricts users to a selection of values from a dropdown list.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_data_validation_allow_list_strings.rs
+    /// #
+    /// # use rust_xlsxwriter::{DataValidation, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     worksheet.write(1, 0, "Select value in cell D2:")?;
+    /// #
+    ///     let data_validation =
+    ///         DataValidation::new().allow_list_strings(&["Pass", "Fail", "Incomplete"])?;
+    ///
+    ///     worksheet.add_data_validation(1, 3, 1, 3, &data_validation)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("data_validation.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/data_validation_allow_list_strings.png">
+    ///
+    pub fn add_data_validation(
+        &mut self,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+        data_validation: &DataValidation,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check rows and cols are in the allowed range.
+        if !self.check_dimensions_only(first_row, first_col)
+            || !self.check_dimensions_only(last_row, last_col)
+        {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Check order of first/last values.
+        if first_row > last_row || first_col > last_col {
+            return Err(XlsxError::RowColumnOrderError);
+        }
+
+        // Validate the data validation.
+        data_validation.validate()?;
+
+        // Store the data validation based on its range.
+        let cell_range = utility::cell_range(first_row, first_col, last_row, last_col);
+        self.data_validations
+            .insert(cell_range, data_validation.clone());
+
+        Ok(self)
+    }
+
     /// Add a sparkline to a worksheet cell.
     ///
     /// Sparklines are a feature of Excel 2010+ which allows you to add small
@@ -6518,6 +8000,8 @@ impl Worksheet {
             )));
         }
 
+        self.warnings.extend(&sparkline.warnings);
+
         // Clone the sparkline and set a data range.
         let mut sparkline = sparkline.clone();
         sparkline.add_cell_range(row, col);
@@ -6674,6 +8158,8 @@ impl Worksheet {
             )));
         }
 
+        self.warnings.extend(&sparkline.warnings);
+
         // Clone the sparkline and set a data range.
         let mut sparkline = sparkline.clone();
         sparkline.add_group_range(first_row, first_col, last_row, last_col);
@@ -6770,15 +8256,9 @@ impl Worksheet {
     /// add an optional, weak, password to prevent modification.
     ///
     /// **Note**: Worksheet level passwords in Excel offer very weak protection.
-    /// They do not encrypt your data and are very easy to deactivate. Full
-    /// workbook encryption is not supported by `rust_xlsxwriter`. However, it
-    /// is possible to encrypt an `rust_xlsxwriter` file using a third party open
-    /// source tool called [msoffice-crypt](https://github.com/herumi/msoffice).
-    /// This works for macOS, Linux and Windows:
-    ///
-    /// ```text
-    /// msoffice-crypt.exe -e -p password clear.xlsx encrypted.xlsx
-    /// ```
+    /// They do not encrypt your data and are very easy to deactivate. If you
+    /// need to encrypt the file you can use `Workbook::save_with_password()`
+    /// with the `encryption` feature enabled.
     ///
     /// # Parameters
     ///
@@ -6821,10 +8301,81 @@ impl Worksheet {
     pub fn protect_with_password(&mut self, password: &str) -> &mut Worksheet {
         self.protection_on = true;
         self.protection_hash = utility::hash_password(password);
+        self.protection_password_hash = None;
 
         self
     }
 
+    /// Protect a worksheet from modification with a SHA-512 hashed password.
+    ///
+    /// The `protect_with_sha512_password()` method is like the
+    /// [`protect_with_password()`](Worksheet::protect_with_password) method
+    /// except that the password is stored using the salted and iterated
+    /// SHA-512 hash scheme that is used by Excel 2013 and later instead of the
+    /// legacy 16 bit hash. This is the format that current versions of Excel
+    /// write and it is preferred by some strict file validators.
+    ///
+    /// The salt used for the hash is randomly generated so, unlike most
+    /// `rust_xlsxwriter` output, files saved with this option will differ
+    /// between runs.
+    ///
+    /// **Note**: As with the legacy hash this doesn't encrypt the data in the
+    /// worksheet. It only makes the protection password harder to recover.
+    ///
+    /// # Parameters
+    ///
+    /// * `password` - The password string. Passing the empty string "" is the
+    ///   same as turning on protection without a password.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::IoError`] - The operating system random source used to
+    ///   generate the salt failed.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting a worksheet from editing
+    /// with a SHA-512 hashed password.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_protect_with_sha512_password.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Protect the worksheet from modification.
+    ///     worksheet.protect_with_sha512_password("abc123")?;
+    ///
+    /// #     worksheet.write_string(0, 0, "Unlock the worksheet to edit the cell")?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn protect_with_sha512_password(
+        &mut self,
+        password: &str,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let password_hash = if password.is_empty() {
+            None
+        } else {
+            Some(PasswordHash::new(password)?)
+        };
+
+        self.protection_on = true;
+        self.protection_hash = 0;
+        self.protection_password_hash = password_hash;
+
+        Ok(self)
+    }
+
     /// Specify which worksheet elements should, or shouldn't, be protected.
     ///
     /// The `protect_with_password()` method is like the
@@ -7124,27 +8675,7 @@ impl Worksheet {
             return Err(XlsxError::RowColumnLimitError);
         }
 
-        // The first/last order can be reversed to allow a selection to go from
-        // the end to the start. We take the active cell from the user first
-        // row/col and then reverse them as required for the full range.
-        let active_cell = utility::row_col_to_cell(first_row, first_col);
-
-        let mut first_row = first_row;
-        let mut first_col = first_col;
-        let mut last_row = last_row;
-        let mut last_col = last_col;
-
-        if first_row > last_row {
-            std::mem::swap(&mut first_row, &mut last_row);
-        }
-
-        if first_col > last_col {
-            std::mem::swap(&mut first_col, &mut last_col);
-        }
-
-        let range = utility::cell_range(first_row, first_col, last_row, last_col);
-
-        self.selected_range = (active_cell, range);
+        self.selected_range = Self::selection_range(first_row, first_col, last_row, last_col);
 
         Ok(self)
     }
@@ -7282,27 +8813,243 @@ impl Worksheet {
         col: ColNum,
         result: impl Into<String>,
     ) -> &mut Worksheet {
-        if let Some(columns) = self.data_table.get_mut(&row) {
-            if let Some(cell) = columns.get_mut(&col) {
-                match cell {
-                    CellType::Formula {
-                        result: cell_result,
-                        ..
-                    }
-                    | CellType::ArrayFormula {
-                        result: cell_result,
-                        ..
-                    } => {
-                        *cell_result = Box::from(result.into());
-                    }
-                    _ => {
-                        eprintln!("Cell ({row}, {col}) doesn't contain a formula.");
-                    }
+        self.store_formula_result(row, col, FormulaResult::parse(&result.into()))
+    }
+
+    /// Set the number result of a formula.
+    ///
+    /// The `set_formula_result_number()` method is similar to
+    /// [`Worksheet::set_formula_result()`] except that the result is stored as
+    /// a number.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `result` - The formula result.
+    ///
+    /// # Warnings
+    ///
+    /// You will get a warning if you try to set a formula result for a cell
+    /// that doesn't have a formula.
+    ///
+    pub fn set_formula_result_number(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        result: impl Into<f64>,
+    ) -> &mut Worksheet {
+        self.store_formula_result(row, col, FormulaResult::Number(result.into()))
+    }
+
+    /// Set the boolean result of a formula.
+    ///
+    /// The `set_formula_result_boolean()` method is similar to
+    /// [`Worksheet::set_formula_result()`] except that the result is stored as
+    /// a boolean `TRUE` or `FALSE` value instead of a string.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `result` - The formula result.
+    ///
+    /// # Warnings
+    ///
+    /// You will get a warning if you try to set a formula result for a cell
+    /// that doesn't have a formula.
+    ///
+    pub fn set_formula_result_boolean(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        result: bool,
+    ) -> &mut Worksheet {
+        self.store_formula_result(row, col, FormulaResult::Boolean(result))
+    }
+
+    /// Set the error result of a formula.
+    ///
+    /// The `set_formula_result_error()` method is similar to
+    /// [`Worksheet::set_formula_result()`] except that the result is stored as
+    /// an Excel error value such as `#N/A`, `#DIV/0!` or `#VALUE!`.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    /// * `error` - The Excel error value.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The error isn't a known Excel error
+    ///   value.
+    ///
+    /// # Warnings
+    ///
+    /// You will get a warning if you try to set a formula result for a cell
+    /// that doesn't have a formula.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting an error value as the
+    /// result of a formula.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_formula_result_error.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Write a formula and set its result to a divide by zero error.
+    ///     worksheet
+    ///         .write_formula(0, 0, "1/0")?
+    ///         .set_formula_result_error(0, 0, "#DIV/0!")?;
+    /// #
+    /// #     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_formula_result_error(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        error: impl Into<String>,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let result = FormulaResult::Error(error.into());
+        result.validate()?;
+
+        Ok(self.store_formula_result(row, col, result))
+    }
+
+    /// Set the results of the formulas in a range of cells.
+    ///
+    /// The `set_formula_results()` method sets the results of all the formulas
+    /// in a range in one call. It is intended for exporters that have already
+    /// calculated the results and want to store them in the file so that
+    /// applications that don't recalculate formulas can display them.
+    ///
+    /// The results are applied to the cells of the range in row order. They can
+    /// be any type that converts to [`FormulaResult`] such as `f64`, `bool` or
+    /// `&str`, or [`FormulaResult`] values for mixed types and errors. Cells in
+    /// the range that don't contain a formula are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `first_row` - The first row of the range. (All zero indexed.)
+    /// * `first_col` - The first column of the range.
+    /// * `last_row` - The last row of the range.
+    /// * `last_col` - The last column of the range.
+    /// * `results` - A slice of formula results, one per cell in the range.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::RowColumnOrderError`] - First row or column is larger
+    ///   than the last row or column.
+    /// * [`XlsxError::ParameterError`] - The number of results doesn't match
+    ///   the number of cells in the range, or a result is an unknown Excel
+    ///   error value.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the results of a column of
+    /// formulas.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_formula_results.rs
+    /// #
+    /// # use rust_xlsxwriter::{FormulaResult, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write_column(0, 0, [1, 2, 0])?;
+    ///
+    ///     for row in 0..3 {
+    ///         worksheet.write_formula(row, 1, format!("=A{}*2", row + 1))?;
+    ///         worksheet.write_formula(row, 2, format!("=10/A{}", row + 1))?;
+    ///     }
+    ///
+    ///     // Set the results of a column of formulas.
+    ///     worksheet.set_formula_results(0, 1, 2, 1, &[2.0, 4.0, 0.0])?;
+    ///
+    ///     // Set mixed number and error results.
+    ///     worksheet.set_formula_results(
+    ///         0,
+    ///         2,
+    ///         2,
+    ///         2,
+    ///         &[
+    ///             FormulaResult::Number(10.0),
+    ///             FormulaResult::Number(5.0),
+    ///             FormulaResult::Error("#DIV/0!".to_string()),
+    ///         ],
+    ///     )?;
+    /// #
+    /// #     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_formula_results<T>(
+        &mut self,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+        results: &[T],
+    ) -> Result<&mut Worksheet, XlsxError>
+    where
+        T: Into<FormulaResult> + Clone,
+    {
+        // Check rows and cols are in the allowed range.
+        if !self.check_dimensions_only(first_row, first_col)
+            || !self.check_dimensions_only(last_row, last_col)
+        {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Check order of first/last values.
+        if first_row > last_row || first_col > last_col {
+            return Err(XlsxError::RowColumnOrderError);
+        }
+
+        let num_cells = (last_row - first_row + 1) as usize * (last_col - first_col + 1) as usize;
+        if results.len() != num_cells {
+            return Err(XlsxError::ParameterError(format!(
+                "Number of formula results '{}' doesn't match the number of cells '{num_cells}'",
+                results.len()
+            )));
+        }
+
+        let results: Vec<FormulaResult> = results.iter().cloned().map(Into::into).collect();
+        for result in &results {
+            result.validate()?;
+        }
+
+        let mut results = results.into_iter();
+        for row in first_row..=last_row {
+            for col in first_col..=last_col {
+                let result = results.next().unwrap();
+                if let Some(cell_result) = self.formula_result_mut(row, col) {
+                    *cell_result = result;
                 }
             }
         }
 
-        self
+        Ok(self)
     }
 
     /// Write the default formula result for worksheet formulas.
@@ -7358,8 +9105,11 @@ impl Worksheet {
     /// for a cell or range of cells. Data validation is a feature in Excel
     /// which allows you to control what a user can enter into a cell.
     ///
+    /// Deprecated: use [`Worksheet::add_data_validation()`] instead.
+    ///
+    #[deprecated(since = "0.65.0", note = "use Worksheet::add_data_validation()")]
     pub fn set_data_validation(&mut self, data_validations: Vec<DataValidation>) -> &mut Worksheet {
-        self.data_validations = data_validations;
+        self.legacy_data_validations = data_validations;
         self
     }
 
@@ -7433,92 +9183,377 @@ impl Worksheet {
         self.use_future_functions = enable;
     }
 
-    // -----------------------------------------------------------------------
-    // Worksheet serde methods.
-    // -----------------------------------------------------------------------
-
-    /// Write a Serde serializable struct to a worksheet.
-    ///
-    /// This method can be used to serialize [Serde](https://serde.rs) enabled
-    /// data structures into cells in a worksheet.
-    ///
-    /// See [Working with Serde](crate::serializer#working-with-serde) for
-    /// background details on how serialization works with `rust_xlsxwriter`.
+    /// Enable the Implicit Intersection Operator "@" in formulas.
     ///
-    /// When serializing structs `rust_xlsxwriter` needs to know location where
-    /// the serialization starts and also the type and field names of the struct
-    /// being serialized. The field names are used as headers and the type name
-    /// allows for several distinct structs to be serialized to the same
-    /// worksheet.
+    /// Convert the "@" operator in formulas written to the worksheet to the
+    /// `_xlfn.SINGLE()` function that Excel uses to store it. See
+    /// [`Formula::use_implicit_intersection()`] for details.
     ///
-    /// <img src="https://rustxlsxwriter.github.io/images/serialize_intro1.png">
+    /// The setting applies to formulas written after it is turned on.
     ///
-    /// The worksheet methods that perform this function fall into two types:
-    /// methods which use deserialization to find the fields from the *type* and
-    /// methods that use serialization to find the fields from an *instance of
-    /// the type*. The deserialization methods are easier to use but require
-    /// that the struct derives the Serde [`Deserialize`] trait as well as the
-    /// [`Serialize`] trait. The serialization methods work for anything else.
+    /// # Parameters
     ///
-    /// There available methods are.
+    /// * `enable` - Turn the property on/off. It is off by default.
     ///
-    /// - [`Worksheet::deserialize_headers()`]: The simplest most direct method.
-    ///   It only requires the type of struct that you wish to serialize and
-    ///   that it derives the [`Deserialize`] and [`Serialize`] traits. The
-    ///   library uses this to infer the struct name and fields (via
-    ///   deserialization).
+    pub fn use_implicit_intersection(&mut self, enable: bool) {
+        self.use_implicit_intersection = enable;
+    }
+
+    /// Check the formulas in the worksheet for syntax errors.
     ///
-    /// - [`Worksheet::deserialize_headers_with_format()`]: This is similar to
-    ///   the previous method but it allows you to add a cell format for the
-    ///   headers.
+    /// The `validate_formulas()` method runs the checks described in
+    /// [`Formula::validate()`] on each formula and array formula in the
+    /// worksheet and returns the row, column and error for each formula with
+    /// a problem. An empty list means that no problems were found.
     ///
-    /// - [`Worksheet::deserialize_headers_with_options()`]: Similar to the
-    ///   previous methods but also allows configuration of the headers and
-    ///   fields via [`SerializeFieldOptions`].
+    /// Formulas are also checked, along with other issues, by
+    /// [`Workbook::validate()`](crate::Workbook::validate).
     ///
-    /// - [`Worksheet::serialize_headers()`]: Similar to the
-    ///   `deserialize_headers()` method but it requires a concrete instance of
-    ///   the type of struct that you wish to serialize. The library uses this
-    ///   to infer the struct name and fields (via serialization). This method
-    ///   only requires that the struct derives [`Serialize`].
+    /// # Examples
     ///
-    /// Once the headers are set up an subsequent calls to `serialize()` will
-    /// write the struct data in rows beneath the header.
+    /// The following example demonstrates finding the cells with invalid
+    /// formulas in a worksheet.
     ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_validate_formulas.rs
+    /// #
+    /// # use rust_xlsxwriter::{utility, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write_formula(0, 0, "=SUM(B1:B10)")?;
+    ///     worksheet.write_formula(1, 0, "=SUM(B1:B10")?;
+    ///     worksheet.write_formula(2, 0, "=AVERAGEE(B1:B10)")?;
     ///
-    /// # Parameters
+    ///     for (row, col, error) in worksheet.validate_formulas() {
+    ///         println!("{}: {error}", utility::row_col_to_cell(row, col));
+    ///     }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
     ///
-    /// * `data_structure` - A reference to a struct that implements the
-    ///   [`serde::Serializer`] trait.
+    pub fn validate_formulas(&self) -> Vec<(RowNum, ColNum, XlsxError)> {
+        self.formula_errors(&[])
+            .into_iter()
+            .map(|(row, col, message)| (row, col, XlsxError::FormulaError(message)))
+            .collect()
+    }
+
+    // Check the worksheet formulas with a list of additional, user defined,
+    // function names.
+    pub(crate) fn formula_errors(
+        &self,
+        user_functions: &[String],
+    ) -> Vec<(RowNum, ColNum, String)> {
+        let mut errors = vec![];
+
+        for (row, columns) in &self.data_table {
+            for (col, cell) in columns.iter() {
+                let formula = match cell {
+                    CellType::Formula { formula, .. } | CellType::ArrayFormula { formula, .. } => {
+                        formula
+                    }
+                    CellType::SharedFormula { formula, .. } if formula.range.is_some() => formula,
+                    _ => continue,
+                };
+
+                if let Err(message) = Formula::check_syntax(&formula.formula, user_functions) {
+                    errors.push((*row, *col, message));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Get the approximate memory used by the worksheet data.
     ///
-    /// # Errors
+    /// The `memory_usage()` method returns a [`MemoryUsage`] struct with the
+    /// approximate number of bytes held by the worksheet cell table, the
+    /// string data in the cells and any images. This can be used to enforce a
+    /// memory budget when creating large worksheets, for example in
+    /// combination with [`Worksheet::flush_rows()`].
     ///
-    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
-    ///   worksheet limits.
-    /// * [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
-    ///   of 32,767 characters.
-    /// * [`XlsxError::SerdeError`] - Errors encountered during the Serde
-    ///   serialization.
+    /// The values are approximate. Strings that are shared between cells are
+    /// counted for each cell.
     ///
     /// # Examples
     ///
-    /// The following example demonstrates serializing instances of a Serde derived
-    /// data structure to a worksheet.
+    /// The following example demonstrates checking the approximate memory used
+    /// by a worksheet.
     ///
     /// ```
-    /// # // This code is available in examples/doc_worksheet_serialize.rs
+    /// # // This code is available in examples/doc_worksheet_memory_usage.rs
     /// #
-    /// use rust_xlsxwriter::{Format, Workbook, XlsxError};
-    /// use serde::{Deserialize, Serialize};
-    ///
-    /// fn main() -> Result<(), XlsxError> {
-    ///     let mut workbook = Workbook::new();
-    ///
-    ///     // Add a worksheet to the workbook.
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
     ///     let worksheet = workbook.add_worksheet();
     ///
-    ///     // Add a simple format for the headers.
-    ///     let format = Format::new().set_bold();
+    ///     for row in 0..1000 {
+    ///         worksheet.write(row, 0, row)?;
+    ///         worksheet.write(row, 1, format!("Item {row}"))?;
+    ///     }
+    ///
+    ///     let usage = worksheet.memory_usage();
+    ///     println!("Cells:   {} bytes", usage.cells());
+    ///     println!("Strings: {} bytes", usage.strings());
+    ///     println!("Total:   {} bytes", usage.total());
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage::default();
+
+        let result_size = |result: &FormulaResult| match result {
+            FormulaResult::String(string) | FormulaResult::Error(string) => string.len(),
+            FormulaResult::Number(_) | FormulaResult::Boolean(_) => 0,
+        };
+
+        for columns in self.data_table.values() {
+            usage.cells += mem::size_of::<(RowNum, CellRow)>()
+                + columns.0.capacity() * mem::size_of::<(ColNum, CellType)>();
+
+            for cell in columns.values() {
+                match cell {
+                    CellType::String { string, .. } => usage.strings += string.len(),
+                    CellType::RichString { rich_string, .. } => {
+                        usage.cells += mem::size_of::<RichStringCell>();
+                        usage.strings += rich_string.string.len() + rich_string.raw_string.len();
+                    }
+                    CellType::Formula { formula, .. }
+                    | CellType::ArrayFormula { formula, .. }
+                    | CellType::SharedFormula { formula, .. } => {
+                        usage.cells += mem::size_of::<FormulaCell>()
+                            + formula.formula.len()
+                            + result_size(&formula.result)
+                            + formula.range.as_ref().map_or(0, |range| range.len());
+                    }
+                    CellType::Blank { .. }
+                    | CellType::Boolean { .. }
+                    | CellType::Error { .. }
+                    | CellType::Number { .. }
+                    | CellType::DateTime { .. } => {}
+                }
+            }
+        }
+
+        for image in self
+            .images
+            .values()
+            .chain(&self.embedded_images)
+            .chain(self.header_footer_images.iter().flatten())
+        {
+            usage.media += image.data.len();
+        }
+
+        usage
+    }
+
+    /// Flush completed rows to a temporary file to reduce memory usage.
+    ///
+    /// By default all of the worksheet data is held in memory until the
+    /// workbook is saved. For very large worksheets that are written row by
+    /// row, from top to bottom, the `flush_rows()` method can be used to write
+    /// the rows before `row` out to a temporary file and release the memory
+    /// they use. Only the unfinished rows after that point are kept in memory.
+    /// The flushed rows are copied into the worksheet when the workbook is
+    /// saved and the temporary file is removed when the worksheet is dropped.
+    ///
+    /// Once rows have been flushed they can't be changed:
+    ///
+    /// - Writing a cell in a flushed row returns a
+    ///   [`XlsxError::RowFlushedError`] error.
+    /// - Strings in flushed rows are written as "inline" strings rather than
+    ///   via the shared strings table. See
+    ///   [`Workbook::use_inline_strings()`](crate::Workbook::use_inline_strings).
+    /// - Row formats and column formats should be set before the rows are
+    ///   flushed.
+    /// - Features that read back cell data such as [`Worksheet::autofit()`],
+    ///   autofilter row hiding and chart data caches don't see flushed data.
+    ///   Autofilter ranges and filter conditions can't be added to flushed
+    ///   rows and flushed rows that don't match the filter conditions aren't
+    ///   hidden.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number. Rows before this row are flushed.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::IoError`] - A file IO error occurred while creating or
+    ///   writing to the temporary file.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates flushing rows to reduce the memory
+    /// used by a large worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_flush_rows.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     for row in 0..100_000 {
+    ///         for col in 0..10 {
+    ///             worksheet.write(row, col, row)?;
+    ///         }
+    ///
+    ///         // Release the memory used by completed rows every 10,000 rows.
+    ///         if row % 10_000 == 0 {
+    ///             worksheet.flush_rows(row)?;
+    ///         }
+    ///     }
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn flush_rows(&mut self, row: RowNum) -> Result<&mut Worksheet, XlsxError> {
+        if row <= self.flushed_rows {
+            return Ok(self);
+        }
+
+        let mut spill = match self.spill.take() {
+            Some(spill) => spill,
+            None => SpillFile::new()?,
+        };
+
+        let first_row = cmp::max(self.dimensions.first_row, self.flushed_rows);
+        let last_row = cmp::min(self.dimensions.last_row, row - 1);
+
+        if first_row <= last_row {
+            // Write the rows using the local format and image indices since the
+            // global indices aren't known until the workbook is saved. They are
+            // converted when the rows are copied back from the spill file.
+            let xf_indices = (0..self.xf_formats.len() as u32).collect();
+            let image_indices = (0..self.embedded_images.len() as u32).collect();
+            let global_xf_indices = mem::replace(&mut self.global_xf_indices, xf_indices);
+            let global_image_indices =
+                mem::replace(&mut self.global_embedded_image_indices, image_indices);
+            let use_inline_strings = mem::replace(&mut self.use_inline_strings, true);
+            let writer = mem::take(&mut self.writer);
+
+            let result = self.write_spilled_rows(&mut spill, first_row, last_row);
+
+            self.global_xf_indices = global_xf_indices;
+            self.global_embedded_image_indices = global_image_indices;
+            self.use_inline_strings = use_inline_strings;
+            self.writer = writer;
+            self.spill = Some(spill);
+
+            result?;
+        } else {
+            self.spill = Some(spill);
+        }
+
+        // Release the memory used by the flushed rows.
+        self.data_table = self.data_table.split_off(&row);
+        self.flushed_rows = row;
+
+        Ok(self)
+    }
+
+    // -----------------------------------------------------------------------
+    // Worksheet serde methods.
+    // -----------------------------------------------------------------------
+
+    /// Write a Serde serializable struct to a worksheet.
+    ///
+    /// This method can be used to serialize [Serde](https://serde.rs) enabled
+    /// data structures into cells in a worksheet.
+    ///
+    /// See [Working with Serde](crate::serializer#working-with-serde) for
+    /// background details on how serialization works with `rust_xlsxwriter`.
+    ///
+    /// When serializing structs `rust_xlsxwriter` needs to know location where
+    /// the serialization starts and also the type and field names of the struct
+    /// being serialized. The field names are used as headers and the type name
+    /// allows for several distinct structs to be serialized to the same
+    /// worksheet.
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/serialize_intro1.png">
+    ///
+    /// The worksheet methods that perform this function fall into two types:
+    /// methods which use deserialization to find the fields from the *type* and
+    /// methods that use serialization to find the fields from an *instance of
+    /// the type*. The deserialization methods are easier to use but require
+    /// that the struct derives the Serde [`Deserialize`] trait as well as the
+    /// [`Serialize`] trait. The serialization methods work for anything else.
+    ///
+    /// There available methods are.
+    ///
+    /// - [`Worksheet::deserialize_headers()`]: The simplest most direct method.
+    ///   It only requires the type of struct that you wish to serialize and
+    ///   that it derives the [`Deserialize`] and [`Serialize`] traits. The
+    ///   library uses this to infer the struct name and fields (via
+    ///   deserialization).
+    ///
+    /// - [`Worksheet::deserialize_headers_with_format()`]: This is similar to
+    ///   the previous method but it allows you to add a cell format for the
+    ///   headers.
+    ///
+    /// - [`Worksheet::deserialize_headers_with_options()`]: Similar to the
+    ///   previous methods but also allows configuration of the headers and
+    ///   fields via [`SerializeFieldOptions`].
+    ///
+    /// - [`Worksheet::serialize_headers()`]: Similar to the
+    ///   `deserialize_headers()` method but it requires a concrete instance of
+    ///   the type of struct that you wish to serialize. The library uses this
+    ///   to infer the struct name and fields (via serialization). This method
+    ///   only requires that the struct derives [`Serialize`].
+    ///
+    /// Once the headers are set up an subsequent calls to `serialize()` will
+    /// write the struct data in rows beneath the header.
+    ///
+    ///
+    /// # Parameters
+    ///
+    /// * `data_structure` - A reference to a struct that implements the
+    ///   [`serde::Serializer`] trait.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// * [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
+    /// * [`XlsxError::SerdeError`] - Errors encountered during the Serde
+    ///   serialization.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates serializing instances of a Serde derived
+    /// data structure to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_serialize.rs
+    /// #
+    /// use rust_xlsxwriter::{Format, Workbook, XlsxError};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Add a simple format for the headers.
+    ///     let format = Format::new().set_bold();
     ///
     ///     // Create a serializable struct.
     ///     #[derive(Deserialize, Serialize)]
@@ -9078,12 +11113,23 @@ impl Worksheet {
         // Activated worksheets must also be selected and cannot be hidden.
         if self.active {
             self.selected = true;
-            self.visible = Visible::Default;
+            self.visible = SheetVisibility::Visible;
         }
 
         self
     }
 
+    /// Check if the worksheet has been set as the active worksheet.
+    ///
+    /// Returns `true` if the worksheet has been made active with
+    /// [`Worksheet::set_active()`]. Note, if no worksheet is set as active
+    /// then Excel makes the first worksheet active when the file is opened.
+    /// That default isn't reflected in the return value of this method.
+    ///
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
     /// Set a worksheet tab as selected.
     ///
     /// The `set_selected()` method is used to indicate that a worksheet is
@@ -9138,7 +11184,7 @@ impl Worksheet {
 
         // Selected worksheets cannot be hidden.
         if self.selected {
-            self.visible = Visible::Default;
+            self.visible = SheetVisibility::Visible;
         }
 
         self
@@ -9195,18 +11241,10 @@ impl Worksheet {
     ///
     pub fn set_hidden(&mut self, enable: bool) -> &mut Worksheet {
         if enable {
-            self.visible = Visible::Hidden;
+            self.set_visibility(SheetVisibility::Hidden)
         } else {
-            self.visible = Visible::Default;
-        }
-
-        // Hidden worksheets cannot be active or hidden.
-        if self.visible == Visible::Hidden {
-            self.selected = false;
-            self.active = false;
+            self.set_visibility(SheetVisibility::Visible)
         }
-
-        self
     }
 
     /// Hide a worksheet. Can only be unhidden in Excel by VBA.
@@ -9217,15 +11255,72 @@ impl Worksheet {
     /// The Excel worksheet `xlSheetVeryHidden` option can only be unset
     /// programmatically by VBA.
     ///
+    /// # Parameters
+    ///
+    /// * `enable` - Turn the property on/off. It is off by default.
+    ///
     pub fn set_very_hidden(&mut self, enable: bool) -> &mut Worksheet {
         if enable {
-            self.visible = Visible::VeryHidden;
+            self.set_visibility(SheetVisibility::VeryHidden)
         } else {
-            self.visible = Visible::Default;
+            self.set_visibility(SheetVisibility::Visible)
         }
+    }
+
+    /// Set the visibility state of a worksheet.
+    ///
+    /// The `set_visibility()` method is used to set a worksheet as visible,
+    /// hidden or "very hidden" using the [`SheetVisibility`] enum. It is a
+    /// more general version of the [`set_hidden()`](Worksheet::set_hidden)
+    /// and [`set_very_hidden()`](Worksheet::set_very_hidden) methods.
+    ///
+    /// A [`SheetVisibility::VeryHidden`] worksheet cannot be unhidden in the
+    /// Excel user interface and can only be unhidden programmatically by VBA.
+    /// This is commonly used for worksheets that contain lookup or
+    /// configuration data that end users shouldn't see.
+    ///
+    /// As with `set_hidden()` a hidden worksheet cannot be active or selected.
+    ///
+    /// # Parameters
+    ///
+    /// * `visibility` - A [`SheetVisibility`] enum value.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the visibility of
+    /// worksheets. The second worksheet can be unhidden by the user but the
+    /// third one can only be unhidden using VBA.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_visibility.rs
+    /// #
+    /// # use rust_xlsxwriter::{SheetVisibility, Workbook, Worksheet, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet1 = Worksheet::new();
+    ///     let mut worksheet2 = Worksheet::new();
+    ///     let mut worksheet3 = Worksheet::new();
+    ///
+    ///     worksheet2.set_visibility(SheetVisibility::Hidden);
+    ///     worksheet3.set_visibility(SheetVisibility::VeryHidden);
+    ///
+    /// #    workbook.push_worksheet(worksheet1);
+    /// #    workbook.push_worksheet(worksheet2);
+    /// #    workbook.push_worksheet(worksheet3);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_visibility(&mut self, visibility: SheetVisibility) -> &mut Worksheet {
+        self.visible = visibility;
 
-        // Hidden worksheets cannot be active or hidden.
-        if self.visible == Visible::VeryHidden {
+        // Hidden worksheets cannot be active or selected.
+        if self.visible != SheetVisibility::Visible {
             self.selected = false;
             self.active = false;
         }
@@ -9233,6 +11328,65 @@ impl Worksheet {
         self
     }
 
+    /// Get the visibility state of the worksheet.
+    ///
+    /// Returns the [`SheetVisibility`] set via
+    /// [`Worksheet::set_visibility()`], [`Worksheet::set_hidden()`] or
+    /// [`Worksheet::set_very_hidden()`]. The default is
+    /// [`SheetVisibility::Visible`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates inspecting the state of the
+    /// worksheets in a workbook.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_visibility.rs
+    /// #
+    /// # use rust_xlsxwriter::{Color, SheetVisibility, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     workbook.add_worksheet().set_tab_color(Color::Red);
+    ///     workbook.add_worksheet().set_active(true);
+    ///     workbook.add_worksheet().set_hidden(true);
+    ///
+    ///     // Inspect the worksheet states.
+    ///     for worksheet in workbook.worksheets() {
+    ///         println!(
+    ///             "{}: active = {}, hidden = {}, visibility = {:?}, tab color = {:?}",
+    ///             worksheet.name(),
+    ///             worksheet.is_active(),
+    ///             worksheet.is_hidden(),
+    ///             worksheet.visibility(),
+    ///             worksheet.tab_color(),
+    ///         );
+    ///     }
+    ///
+    ///     let worksheet = workbook.worksheet_from_index(2)?;
+    ///     assert_eq!(worksheet.visibility(), SheetVisibility::Hidden);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn visibility(&self) -> SheetVisibility {
+        self.visible
+    }
+
+    /// Check if the worksheet is hidden.
+    ///
+    /// Returns `true` if the worksheet visibility is
+    /// [`SheetVisibility::Hidden`] or [`SheetVisibility::VeryHidden`]. See
+    /// [`Worksheet::visibility()`] to distinguish between the two states.
+    ///
+    pub fn is_hidden(&self) -> bool {
+        self.visible != SheetVisibility::Visible
+    }
+
     /// Set current worksheet as the first visible sheet tab.
     ///
     /// The [`set_active()`](Worksheet::set_active)  method determines
@@ -9253,7 +11407,7 @@ impl Worksheet {
 
         // First visible worksheet cannot be hidden.
         if self.selected {
-            self.visible = Visible::Default;
+            self.visible = SheetVisibility::Visible;
         }
         self
     }
@@ -9310,13 +11464,55 @@ impl Worksheet {
     ///
     pub fn set_tab_color(&mut self, color: impl IntoColor) -> &mut Worksheet {
         let color = color.new_color();
-        if color.is_valid() {
+        if self.warnings.check_color("set_tab_color", color) {
             self.tab_color = color;
         }
 
         self
     }
 
+    /// Get the worksheet tab color.
+    ///
+    /// Returns the color set via [`Worksheet::set_tab_color()`] or
+    /// [`Color::Default`] if the tab color hasn't been set.
+    ///
+    pub fn tab_color(&self) -> Color {
+        self.tab_color
+    }
+
+    /// Set the VBA name for the worksheet.
+    ///
+    /// The `set_vba_name()` method can be used to set the VBA codename for the
+    /// worksheet. This is sometimes required when a VBA macro included via
+    /// [`Workbook::add_vba_project()`](crate::Workbook::add_vba_project)
+    /// refers to the worksheet by its codename.
+    ///
+    /// The codename is the sheet name used by VBA, which is separate from the
+    /// name shown on the worksheet tab. If a VBA project is added to the
+    /// workbook the default codename for each worksheet is `Sheet1`,
+    /// `Sheet2`, etc., based on the sheet order, so this method only needs to
+    /// be used if the VBA project uses different names.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The VBA name. It must start with a letter and contain only
+    ///   letters, digits and underscores. The maximum length is 31
+    ///   characters.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::ParameterError`] - The name doesn't follow the VBA
+    ///   naming rules.
+    ///
+    pub fn set_vba_name(&mut self, name: impl Into<String>) -> Result<&mut Worksheet, XlsxError> {
+        let name = name.into();
+        utility::validate_vba_name(&name)?;
+
+        self.vba_name = Some(name);
+
+        Ok(self)
+    }
+
     /// Set the paper type/size when printing.
     ///
     /// This method is used to set the paper format for the printed output of a
@@ -9406,6 +11602,7 @@ impl Worksheet {
     pub fn set_paper_size(&mut self, paper_size: u8) -> &mut Worksheet {
         self.paper_size = paper_size;
         self.page_setup_changed = true;
+        self.page_setup_overrides.paper_size = true;
         self
     }
 
@@ -9493,6 +11690,7 @@ impl Worksheet {
     pub fn set_landscape(&mut self) -> &mut Worksheet {
         self.portrait = false;
         self.page_setup_changed = true;
+        self.page_setup_overrides.orientation = true;
         self
     }
 
@@ -9505,6 +11703,7 @@ impl Worksheet {
     pub fn set_portrait(&mut self) -> &mut Worksheet {
         self.portrait = true;
         self.page_setup_changed = true;
+        self.page_setup_overrides.orientation = true;
         self
     }
 
@@ -9657,6 +11856,9 @@ impl Worksheet {
     /// the scale of the printed page in Excel. For that you should use
     /// [`set_print_scale()`](Worksheet::set_print_scale).
     ///
+    /// The zoom applies to the current worksheet view. To set the zoom of the
+    /// other views see [`set_zoom_normal()`](Worksheet::set_zoom_normal).
+    ///
     /// # Parameters
     ///
     /// * `zoom` - The worksheet zoom level.
@@ -9692,27 +11894,142 @@ impl Worksheet {
     ///
     pub fn set_zoom(&mut self, zoom: u16) -> &mut Worksheet {
         if !(10..=400).contains(&zoom) {
-            eprintln!("Zoom factor {zoom} outside Excel range: 10 <= zoom <= 400.");
+            self.warnings.warn(
+                "set_zoom",
+                format!("Zoom factor {zoom} outside Excel range: 10 <= zoom <= 400."),
+            );
             return self;
         }
 
+        self.warnings.clear("set_zoom");
         self.zoom = zoom;
         self
     }
 
-    /// Freeze panes in a worksheet.
-    ///
-    /// The `set_freeze_panes()` method can be used to divide a worksheet into
-    /// horizontal or vertical regions known as panes and to “freeze” these
-    /// panes so that the splitter bars are not visible.
+    /// Set the worksheet zoom factor for the Normal view.
     ///
-    /// As with Excel the split is to the top and left of the cell. So to freeze
-    /// the top row and leftmost column you would use `(1, 1)` (zero-indexed).
-    /// Also, you can set one of the row and col parameters as 0 if you do not
-    /// want either the vertical or horizontal split. See the example below.
+    /// Excel stores a separate zoom level for each of the Normal, Page Layout
+    /// and Page Break Preview views of a worksheet. The
+    /// [`set_zoom()`](Worksheet::set_zoom) method sets the zoom for the
+    /// current view. The `set_zoom_normal()` method, and the related
+    /// [`set_zoom_page_layout()`](Worksheet::set_zoom_page_layout) and
+    /// [`set_zoom_page_break_preview()`](Worksheet::set_zoom_page_break_preview)
+    /// methods, can be used to set the zoom level of each view independently
+    /// so that it is applied when the user switches to that view.
     ///
-    /// In Excel it is also possible to set "split" panes without freezing them.
-    /// That feature isn't currently supported by `rust_xlsxwriter`.
+    /// A zoom set for the current view with one of these methods takes
+    /// precedence over the value set with `set_zoom()`.
+    ///
+    /// # Parameters
+    ///
+    /// * `zoom` - The worksheet zoom level in the range 10 <= zoom <= 400.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting different zoom levels for
+    /// the worksheet views.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_zoom_normal.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write_string(0, 0, "Hello")?;
+    ///
+    ///     // Set a zoom level for each of the views.
+    ///     worksheet.set_zoom_normal(150);
+    ///     worksheet.set_zoom_page_layout(75);
+    ///     worksheet.set_zoom_page_break_preview(60);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_zoom_normal(&mut self, zoom: u16) -> &mut Worksheet {
+        if !(10..=400).contains(&zoom) {
+            self.warnings.warn(
+                "set_zoom_normal",
+                format!("Zoom factor {zoom} outside Excel range: 10 <= zoom <= 400."),
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_zoom_normal");
+        self.zoom_normal = zoom;
+        self
+    }
+
+    /// Set the worksheet zoom factor for the Page Layout view.
+    ///
+    /// See [`set_zoom_normal()`](Worksheet::set_zoom_normal) above for
+    /// details and an example.
+    ///
+    /// # Parameters
+    ///
+    /// * `zoom` - The worksheet zoom level in the range 10 <= zoom <= 400.
+    ///
+    pub fn set_zoom_page_layout(&mut self, zoom: u16) -> &mut Worksheet {
+        if !(10..=400).contains(&zoom) {
+            self.warnings.warn(
+                "set_zoom_page_layout",
+                format!("Zoom factor {zoom} outside Excel range: 10 <= zoom <= 400."),
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_zoom_page_layout");
+        self.zoom_page_layout = zoom;
+        self
+    }
+
+    /// Set the worksheet zoom factor for the Page Break Preview view.
+    ///
+    /// See [`set_zoom_normal()`](Worksheet::set_zoom_normal) above for
+    /// details and an example.
+    ///
+    /// # Parameters
+    ///
+    /// * `zoom` - The worksheet zoom level in the range 10 <= zoom <= 400.
+    ///
+    pub fn set_zoom_page_break_preview(&mut self, zoom: u16) -> &mut Worksheet {
+        if !(10..=400).contains(&zoom) {
+            self.warnings.warn(
+                "set_zoom_page_break_preview",
+                format!("Zoom factor {zoom} outside Excel range: 10 <= zoom <= 400."),
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_zoom_page_break_preview");
+        self.zoom_page_break_preview = zoom;
+        self
+    }
+
+    /// Freeze panes in a worksheet.
+    ///
+    /// The `set_freeze_panes()` method can be used to divide a worksheet into
+    /// horizontal or vertical regions known as panes and to “freeze” these
+    /// panes so that the splitter bars are not visible.
+    ///
+    /// As with Excel the split is to the top and left of the cell. So to freeze
+    /// the top row and leftmost column you would use `(1, 1)` (zero-indexed).
+    /// Also, you can set one of the row and col parameters as 0 if you do not
+    /// want either the vertical or horizontal split. See the example below.
+    ///
+    /// In Excel it is also possible to set "split" panes without freezing them.
+    /// See [`Worksheet::set_split_panes()`]. To freeze panes that revert to a
+    /// split when they are unfrozen see [`Worksheet::set_frozen_split_panes()`].
+    ///
+    /// For the common cases of freezing a header row or the first column see
+    /// [`Worksheet::freeze_top_row()`] and [`Worksheet::freeze_first_column()`].
     ///
     /// # Parameters
     ///
@@ -9778,72 +12095,460 @@ impl Worksheet {
         }
 
         self.panes.freeze_cell = (row, col);
+        self.panes.split = (0.0, 0.0);
+        self.panes.frozen_split = false;
         Ok(self)
     }
 
-    /// Set the top most cell in the scrolling area of a freeze pane.
-    ///
-    /// This method is used in conjunction with the
-    /// [`set_freeze_panes()`](Worksheet::set_freeze_panes) method to set the
-    /// top most visible cell in the scrolling range. For example you may want
-    /// to freeze the top row but have the worksheet pre-scrolled so that cell
-    /// `A20` is visible in the scrolled area. See the example below.
+    /// Set the top most cell in the scrolling area of a freeze pane.
+    ///
+    /// This method is used in conjunction with the
+    /// [`set_freeze_panes()`](Worksheet::set_freeze_panes) method to set the
+    /// top most visible cell in the scrolling range. For example you may want
+    /// to freeze the top row but have the worksheet pre-scrolled so that cell
+    /// `A20` is visible in the scrolled area. See the example below.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the worksheet panes and also
+    /// setting the topmost visible cell in the scrolled area.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_freeze_panes_top_cell.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     worksheet.write_string(0, 0, "Scroll down")?;
+    /// #
+    ///     // Freeze the top row only.
+    ///     worksheet.set_freeze_panes(1, 0)?;
+    ///
+    ///     // Pre-scroll to the row 20.
+    ///     worksheet.set_freeze_panes_top_cell(19, 0)?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img
+    /// src="https://rustxlsxwriter.github.io/images/worksheet_set_freeze_panes_top_cell.png">
+    ///
+    pub fn set_freeze_panes_top_cell(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check row and col are in the allowed range.
+        if !self.check_dimensions_only(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        self.panes.top_cell = (row, col);
+        Ok(self)
+    }
+
+    /// Freeze panes in a worksheet so that they revert to split panes when
+    /// unfrozen.
+    ///
+    /// The `set_frozen_split_panes()` method is the same as
+    /// [`Worksheet::set_freeze_panes()`] except that the panes are stored in
+    /// Excel's "frozenSplit" state. The panes are frozen when the file is
+    /// opened but if the user turns off "Freeze Panes" in Excel the worksheet
+    /// keeps a split at the same position, which the user can then adjust,
+    /// rather than removing the panes completely.
+    ///
+    /// # Parameters
+    ///
+    /// * `row` - The zero indexed row number.
+    /// * `col` - The zero indexed column number.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting frozen panes that revert to
+    /// split panes when they are unfrozen.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_frozen_split_panes.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     worksheet.write_string(0, 0, "Scroll down or across")?;
+    /// #
+    ///     // Freeze the top row and leftmost column.
+    ///     worksheet.set_frozen_split_panes(1, 1)?;
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_frozen_split_panes(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        self.set_freeze_panes(row, col)?;
+        self.panes.frozen_split = true;
+        Ok(self)
+    }
+
+    /// Freeze the top row of a worksheet.
+    ///
+    /// The `freeze_top_row()` method is a convenience wrapper around
+    /// [`Worksheet::set_freeze_panes()`] to freeze the first row of the
+    /// worksheet. This is the most common use of freeze panes since it keeps
+    /// a header row visible while scrolling down through the data. It is
+    /// equivalent to `set_freeze_panes(1, 0)`.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates freezing the top row and the first
+    /// column of worksheets.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_freeze_top_row.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     // Freeze the header row.
+    ///     let worksheet1 = workbook.add_worksheet();
+    ///     worksheet1.write_string(0, 0, "Scroll down")?;
+    ///     worksheet1.freeze_top_row();
+    ///
+    ///     // Freeze the first column.
+    ///     let worksheet2 = workbook.add_worksheet();
+    ///     worksheet2.write_string(0, 0, "Scroll across")?;
+    ///     worksheet2.freeze_first_column();
+    ///
+    ///     // Freeze the top 2 rows and first 3 columns.
+    ///     let worksheet3 = workbook.add_worksheet();
+    ///     worksheet3.write_string(0, 0, "Scroll down or across")?;
+    ///     worksheet3.freeze_rows_and_columns(2, 3)?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn freeze_top_row(&mut self) -> &mut Worksheet {
+        self.panes.freeze_cell = (1, 0);
+        self.panes.split = (0.0, 0.0);
+        self.panes.frozen_split = false;
+        self
+    }
+
+    /// Freeze the first column of a worksheet.
+    ///
+    /// The `freeze_first_column()` method is a convenience wrapper around
+    /// [`Worksheet::set_freeze_panes()`] to freeze the first column of the
+    /// worksheet. It is equivalent to `set_freeze_panes(0, 1)`.
+    ///
+    /// See [`Worksheet::freeze_top_row()`] above for an example.
+    ///
+    pub fn freeze_first_column(&mut self) -> &mut Worksheet {
+        self.panes.freeze_cell = (0, 1);
+        self.panes.split = (0.0, 0.0);
+        self.panes.frozen_split = false;
+        self
+    }
+
+    /// Freeze a number of rows and columns at the top left of a worksheet.
+    ///
+    /// The `freeze_rows_and_columns()` method is a convenience wrapper around
+    /// [`Worksheet::set_freeze_panes()`] that freezes the specified number of
+    /// rows at the top of the worksheet and columns at the left of the
+    /// worksheet. Either value can be 0 to freeze only rows or columns.
+    ///
+    /// See [`Worksheet::freeze_top_row()`] above for an example.
+    ///
+    /// # Parameters
+    ///
+    /// * `num_rows` - The number of rows to freeze.
+    /// * `num_cols` - The number of columns to freeze.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    pub fn freeze_rows_and_columns(
+        &mut self,
+        num_rows: RowNum,
+        num_cols: ColNum,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        self.set_freeze_panes(num_rows, num_cols)
+    }
+
+    /// Split panes in a worksheet.
+    ///
+    /// The `set_split_panes()` method can be used to divide a worksheet into
+    /// horizontal or vertical regions known as panes. This is similar to
+    /// [`Worksheet::set_freeze_panes()`] except that the splits aren't frozen
+    /// and the user can drag the splitter bars to a new position.
+    ///
+    /// The split positions are specified in the same units that Excel uses for
+    /// column widths and row heights. The `x` parameter is the horizontal
+    /// position of the vertical split in character width units, such as those
+    /// used by [`Worksheet::set_column_width()`]. The `y` parameter is the
+    /// vertical position of the horizontal split in points, such as those used
+    /// by [`Worksheet::set_row_height()`]. You can set one of the parameters to
+    /// 0 if you do not want either the vertical or horizontal split.
+    ///
+    /// If you wish to split the panes at a row or column boundary you can use
+    /// multiples of the default column width (8.43) and row height (15). This
+    /// doesn't take any user adjusted column widths or row heights into
+    /// account.
+    ///
+    /// The top most visible cell in the scrolled area is estimated from the
+    /// split positions. It can be set explicitly using
+    /// [`Worksheet::set_freeze_panes_top_cell()`].
+    ///
+    /// # Parameters
+    ///
+    /// * `x` - The horizontal position of the split in character width units.
+    /// * `y` - The vertical position of the split in points.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting split panes in worksheets.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_split_panes.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let mut worksheet1 = Worksheet::new();
+    /// #     let mut worksheet2 = Worksheet::new();
+    /// #     let mut worksheet3 = Worksheet::new();
+    /// #
+    /// #     worksheet1.write_string(0, 0, "Scroll down")?;
+    /// #     worksheet2.write_string(0, 0, "Scroll across")?;
+    /// #     worksheet3.write_string(0, 0, "Scroll down or across")?;
+    /// #
+    ///     // Split the worksheet below the first row.
+    ///     worksheet1.set_split_panes(0, 15);
+    ///
+    ///     // Split the worksheet to the right of the first column.
+    ///     worksheet2.set_split_panes(8.43, 0);
+    ///
+    ///     // Split the worksheet below the first row and to the right of the
+    ///     // first column.
+    ///     worksheet3.set_split_panes(8.43, 15);
+    ///
+    /// #     workbook.push_worksheet(worksheet1);
+    /// #     workbook.push_worksheet(worksheet2);
+    /// #     workbook.push_worksheet(worksheet3);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_split_panes(&mut self, x: impl Into<f64>, y: impl Into<f64>) -> &mut Worksheet {
+        let x = x.into().max(0.0);
+        let y = y.into().max(0.0);
+
+        // Properties for Calibri 11.
+        let max_digit_width = 7.0_f64;
+        let padding = 5.0_f64;
+
+        // Convert the column width to pixels.
+        let x_pixels = if x == 0.0 {
+            0.0
+        } else if x < 1.0 {
+            (x * (max_digit_width + padding) + 0.5).floor()
+        } else {
+            (x * max_digit_width + 0.5).floor() + padding
+        };
+
+        // Convert the row height in points to pixels.
+        let y_pixels = y / 0.75;
+
+        self.store_split_panes(x_pixels, y_pixels);
+        self
+    }
+
+    /// Split panes in a worksheet using pixel positions.
+    ///
+    /// The `set_split_panes_pixels()` method is the same as
+    /// [`Worksheet::set_split_panes()`] except that the split positions are
+    /// specified in pixels.
+    ///
+    /// # Parameters
+    ///
+    /// * `x` - The horizontal position of the split in pixels.
+    /// * `y` - The vertical position of the split in pixels.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting split panes in a worksheet
+    /// using pixel positions.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_split_panes_pixels.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     worksheet.write_string(0, 0, "Scroll down or across")?;
+    /// #
+    ///     // Split the worksheet below the first row and to the right of the
+    ///     // first column.
+    ///     worksheet.set_split_panes_pixels(64, 20);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_split_panes_pixels(&mut self, x: u16, y: u16) -> &mut Worksheet {
+        self.store_split_panes(f64::from(x), f64::from(y));
+        self
+    }
+
+    /// Set the active pane in a worksheet with frozen or split panes.
     ///
-    /// # Parameters
+    /// When a worksheet is divided into panes, using
+    /// [`Worksheet::set_freeze_panes()`] or [`Worksheet::set_split_panes()`],
+    /// Excel makes the last pane, usually the bottom right scrolling pane, the
+    /// active pane. The `set_active_pane()` method can be used to make a
+    /// different pane the active one. This is the pane that contains the
+    /// active cell when the file is opened.
     ///
-    /// * `row` - The zero indexed row number.
-    /// * `col` - The zero indexed column number.
+    /// The pane must exist for the type of split in the worksheet. For
+    /// example a worksheet that is only split horizontally doesn't have
+    /// [`Pane::TopRight`] or [`Pane::BottomRight`] panes. Invalid panes are
+    /// ignored.
     ///
-    /// # Errors
+    /// # Parameters
     ///
-    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
-    ///   worksheet limits.
+    /// * `pane` - A [`Pane`] enum value.
     ///
     /// # Examples
     ///
-    /// The following example demonstrates setting the worksheet panes and also
-    /// setting the topmost visible cell in the scrolled area.
+    /// The following example demonstrates setting the active pane and the cell
+    /// selections in a worksheet with frozen panes.
     ///
     /// ```
-    /// # // This code is available in examples/doc_worksheet_set_freeze_panes_top_cell.rs
+    /// # // This code is available in examples/doc_worksheet_set_active_pane.rs
     /// #
-    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// # use rust_xlsxwriter::{Pane, Workbook, XlsxError};
     /// #
     /// # fn main() -> Result<(), XlsxError> {
     /// #     let mut workbook = Workbook::new();
     /// #
     /// #     let worksheet = workbook.add_worksheet();
     /// #
-    /// #     worksheet.write_string(0, 0, "Scroll down")?;
-    /// #
-    ///     // Freeze the top row only.
-    ///     worksheet.set_freeze_panes(1, 0)?;
+    ///     // Freeze the top row and first column.
+    ///     worksheet.set_freeze_panes(1, 1)?;
     ///
-    ///     // Pre-scroll to the row 20.
-    ///     worksheet.set_freeze_panes_top_cell(19, 0)?;
+    ///     // Make the top right pane the active pane and select a range in it.
+    ///     worksheet.set_active_pane(Pane::TopRight);
+    ///     worksheet.set_pane_selection(Pane::TopRight, 0, 3, 0, 5)?;
     ///
+    ///     // Set the selection in the bottom right scrolling pane.
+    ///     worksheet.set_pane_selection(Pane::BottomRight, 9, 3, 9, 3)?;
+    /// #
     /// #     workbook.save("worksheet.xlsx")?;
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    /// Output file:
+    pub fn set_active_pane(&mut self, pane: Pane) -> &mut Worksheet {
+        self.panes.active_pane = Some(pane);
+        self
+    }
+
+    /// Set the cell selection in one of the panes of a worksheet.
     ///
-    /// <img
-    /// src="https://rustxlsxwriter.github.io/images/worksheet_set_freeze_panes_top_cell.png">
+    /// The `set_pane_selection()` method is similar to
+    /// [`Worksheet::set_selection()`] except that it sets the selected cell
+    /// or range in an individual pane of a worksheet with frozen or split
+    /// panes. Each pane in Excel has its own independent selection. By
+    /// default the selections are set to the first cell in each pane.
     ///
-    pub fn set_freeze_panes_top_cell(
+    /// The top left cell that is visible in the scrolling pane can be set
+    /// with [`Worksheet::set_freeze_panes_top_cell()`] and the top left cell
+    /// that is visible in the worksheet with
+    /// [`Worksheet::set_top_left_cell()`].
+    ///
+    /// See [`Worksheet::set_active_pane()`] above for an example.
+    ///
+    /// # Parameters
+    ///
+    /// * `pane` - A [`Pane`] enum value.
+    /// * `first_row` - The first row of the range. (All zero indexed.)
+    /// * `first_col` - The first row of the range.
+    /// * `last_row` - The last row of the range.
+    /// * `last_col` - The last row of the range.
+    ///
+    /// # Errors
+    ///
+    /// * [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    pub fn set_pane_selection(
         &mut self,
-        row: RowNum,
-        col: ColNum,
+        pane: Pane,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
     ) -> Result<&mut Worksheet, XlsxError> {
-        // Check row and col are in the allowed range.
-        if !self.check_dimensions_only(row, col) {
+        // Check rows and cols are in the allowed range.
+        if !self.check_dimensions_only(first_row, first_col)
+            || !self.check_dimensions_only(last_row, last_col)
+        {
             return Err(XlsxError::RowColumnLimitError);
         }
 
-        self.panes.top_cell = (row, col);
+        let selection = Self::selection_range(first_row, first_col, last_row, last_col);
+        self.panes.selections.insert(pane, selection);
+
         Ok(self)
     }
 
@@ -10050,13 +12755,18 @@ impl Worksheet {
             .replace("&[Picture]", "&G");
 
         if header_expanded.chars().count() > 255 {
-            eprintln!("Header string exceeds Excel's limit of 255 characters.");
+            self.warnings.warn(
+                "set_header",
+                "Header string exceeds Excel's limit of 255 characters.",
+            );
             return self;
         }
 
+        self.warnings.clear("set_header");
         self.header = header;
         self.page_setup_changed = true;
         self.head_footer_changed = true;
+        self.page_setup_overrides.header = true;
         self
     }
 
@@ -10084,13 +12794,18 @@ impl Worksheet {
             .replace("&[Picture]", "&G");
 
         if footer_expanded.chars().count() > 255 {
-            eprintln!("Footer string exceeds Excel's limit of 255 characters.");
+            self.warnings.warn(
+                "set_footer",
+                "Footer string exceeds Excel's limit of 255 characters.",
+            );
             return self;
         }
 
+        self.warnings.clear("set_footer");
         self.footer = footer;
         self.page_setup_changed = true;
         self.head_footer_changed = true;
+        self.page_setup_overrides.footer = true;
         self
     }
 
@@ -10355,26 +13070,32 @@ impl Worksheet {
         if left >= 0.0 {
             self.margin_left = left;
             self.page_setup_changed = true;
+            self.page_setup_overrides.margins[0] = true;
         }
         if right >= 0.0 {
             self.margin_right = right;
             self.page_setup_changed = true;
+            self.page_setup_overrides.margins[1] = true;
         }
         if top >= 0.0 {
             self.margin_top = top;
             self.page_setup_changed = true;
+            self.page_setup_overrides.margins[2] = true;
         }
         if bottom >= 0.0 {
             self.margin_bottom = bottom;
             self.page_setup_changed = true;
+            self.page_setup_overrides.margins[3] = true;
         }
         if header >= 0.0 {
             self.margin_header = header;
             self.page_setup_changed = true;
+            self.page_setup_overrides.margins[4] = true;
         }
         if footer >= 0.0 {
             self.margin_footer = footer;
             self.page_setup_changed = true;
+            self.page_setup_overrides.margins[5] = true;
         }
 
         self
@@ -10467,10 +13188,14 @@ impl Worksheet {
     ///
     pub fn set_print_scale(&mut self, scale: u16) -> &mut Worksheet {
         if !(10..=400).contains(&scale) {
-            eprintln!("Scale factor {scale} outside Excel range: 10 <= zoom <= 400.");
+            self.warnings.warn(
+                "set_print_scale",
+                format!("Scale factor {scale} outside Excel range: 10 <= zoom <= 400."),
+            );
             return self;
         }
 
+        self.warnings.clear("set_print_scale");
         // This property is mutually exclusive with fit to page.
         self.fit_to_page = false;
 
@@ -10479,6 +13204,62 @@ impl Worksheet {
         self
     }
 
+    /// Set the page setup option to set the print resolution.
+    ///
+    /// Set the horizontal and vertical resolution, in dots per inch, that is
+    /// stored with the worksheet page setup. This is used by some print
+    /// workflows that require a specific printer resolution such as 600 or
+    /// 1200 dpi.
+    ///
+    /// The default resolution is 200 dpi.
+    ///
+    /// See also the documentation on [Worksheet Page Setup -
+    /// Page](../worksheet/index.html#page-setup---page).
+    ///
+    /// # Parameters
+    ///
+    /// * `dpi` - The print resolution in dots per inch.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the print resolution of the
+    /// worksheet page.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_print_resolution.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Set the print resolution to 600 dpi.
+    ///     worksheet.set_print_resolution(600);
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_print_resolution(&mut self, dpi: u16) -> &mut Worksheet {
+        if dpi == 0 {
+            self.warnings.warn(
+                "set_print_resolution",
+                "Print resolution must be greater than 0 dpi.",
+            );
+            return self;
+        }
+
+        self.warnings.clear("set_print_resolution");
+        self.print_resolution = dpi;
+        self.page_setup_changed = true;
+        self
+    }
+
     /// Fit the printed area to a specific number of pages both vertically and
     /// horizontally.
     ///
@@ -10655,6 +13436,77 @@ impl Worksheet {
         self
     }
 
+    /// Set the color of the screen gridlines.
+    ///
+    /// The `set_screen_gridlines_color()` method is used to change the color of
+    /// the gridlines displayed in the worksheet, for example to make them
+    /// lighter to match a dashboard style. It doesn't affect printed
+    /// gridlines.
+    ///
+    /// Excel only supports colors from its legacy 56 color palette for
+    /// gridlines so the color is converted to the nearest palette color.
+    /// Theme colors aren't supported and are ignored. Use [`Color::Default`] or
+    /// [`Color::Automatic`] to restore the default gridline color.
+    ///
+    /// # Parameters
+    ///
+    /// * `color` - The gridline color property defined by a [`Color`] enum
+    ///   value or a type that implements the [`IntoColor`] trait.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting a light gray color for the
+    /// worksheet screen gridlines.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_screen_gridlines_color.rs
+    /// #
+    /// # use rust_xlsxwriter::{Color, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(0, 0, "Hello")?;
+    ///
+    ///     // Set a lighter color for the screen gridlines.
+    ///     worksheet.set_screen_gridlines_color(Color::Silver);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_screen_gridlines_color(&mut self, color: impl IntoColor) -> &mut Worksheet {
+        let color = color.new_color();
+
+        match color {
+            Color::Default | Color::Automatic => {
+                self.warnings.clear("set_screen_gridlines_color");
+                self.gridline_color_id = 0;
+            }
+            Color::Theme(_, _) => {
+                self.warnings.warn(
+                    "set_screen_gridlines_color",
+                    "Theme colors aren't supported for the gridline color.",
+                );
+            }
+            _ => {
+                if self
+                    .warnings
+                    .check_color("set_screen_gridlines_color", color)
+                {
+                    self.gridline_color_id = color.palette_index();
+                }
+            }
+        }
+
+        self
+    }
+
     /// Set the page setup option to turn on printed gridlines.
     ///
     /// The `set_print_gridlines()` method is use to turn on/off gridlines on
@@ -10720,6 +13572,57 @@ impl Worksheet {
         self
     }
 
+    /// Set the page setup option for how cell errors are printed.
+    ///
+    /// The `set_print_errors_as()` method can be used to set how cells with
+    /// formula errors such as `#DIV/0!` or `#N/A` are displayed in the printed
+    /// output. This is the equivalent of the "Cell errors as" option in the
+    /// Excel "Page Setup - Sheet" dialog.
+    ///
+    /// The default is to print the errors as they are displayed in Excel.
+    ///
+    /// See also the documentation on [Worksheet Page Setup -
+    /// Sheet](../worksheet/index.html#page-setup---sheet).
+    ///
+    /// # Parameters
+    ///
+    /// * `option` - A [`PrintErrorOption`] enum value.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates printing cell errors as blanks.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_print_errors_as.rs
+    /// #
+    /// # use rust_xlsxwriter::{Formula, PrintErrorOption, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(0, 0, Formula::new("=1/0"))?;
+    ///
+    ///     // Print the cell error as a blank.
+    ///     worksheet.set_print_errors_as(PrintErrorOption::Blank);
+    ///
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_print_errors_as(&mut self, option: PrintErrorOption) -> &mut Worksheet {
+        self.print_errors = option;
+
+        if option != PrintErrorOption::Displayed {
+            self.page_setup_changed = true;
+        }
+        self
+    }
+
     /// Set the page setup option to print the row and column headers on the
     /// printed page.
     ///
@@ -11099,10 +14002,8 @@ impl Worksheet {
                             // strings we use the unformatted string. We also
                             // split multi-line strings and handle each part
                             // separately.
-                            CellType::String { string, .. }
-                            | CellType::RichString {
-                                raw_string: string, ..
-                            } => {
+                            CellType::String { .. } | CellType::RichString { .. } => {
+                                let string = cell.unformatted_string().unwrap_or_default();
                                 let mut max = 0;
                                 for segment in string.lines() {
                                     let length = utility::pixel_width(segment);
@@ -11130,12 +14031,14 @@ impl Worksheet {
 
                             // For formulas we autofit the result of the formula
                             // if it has a non-zero/default value.
-                            CellType::Formula { result, .. }
-                            | CellType::ArrayFormula { result, .. } => {
-                                if result.as_ref() == "0" || result.is_empty() {
+                            CellType::Formula { formula, .. }
+                            | CellType::ArrayFormula { formula, .. }
+                            | CellType::SharedFormula { formula, .. } => {
+                                let result = formula.result.display_value();
+                                if result == "0" || result.is_empty() {
                                     0
                                 } else {
-                                    utility::pixel_width(result)
+                                    utility::pixel_width(&result)
                                 }
                             }
 
@@ -11180,7 +14083,7 @@ impl Worksheet {
 
         // Set the max character width for each column.
         for (col, pixels) in &max_widths {
-            let width = Self::pixels_to_width(*pixels + 7);
+            let width = utility::pixels_to_column_width(*pixels + 7);
             self.store_column_width(*col, width, true);
         }
 
@@ -11205,38 +14108,198 @@ impl Worksheet {
         if self.dimensions.first_col == COL_MAX {
             0
         } else {
-            self.dimensions.first_col
+            self.dimensions.first_col
+        }
+    }
+
+    // Hide any rows in the autofilter range that don't match the autofilter
+    // conditions, like Excel does at runtime. Rows that were flushed after the
+    // filters were set have already been written and are skipped.
+    pub(crate) fn hide_autofilter_rows(&mut self) -> Result<(), XlsxError> {
+        if self.filter_conditions.is_empty() || self.filter_automatic_off {
+            return Ok(());
+        }
+
+        // Get the range that the autofilter applies to.
+        let first_row = self.autofilter_defined_name.first_row + 1; // Skip header.
+        let first_row = cmp::max(first_row, self.flushed_rows);
+        let last_row = self.autofilter_defined_name.last_row;
+
+        for col_num in self.filter_conditions.clone().keys() {
+            // Iterate through each column filter conditions.
+            let filter_condition = self.filter_conditions.get(col_num).unwrap().clone();
+
+            // Handle "Top 10" filters, which depend on all the data in the column.
+            if let Some((top10, _)) = filter_condition.top10 {
+                let filter_value = self.top10_filter_value(*col_num, &filter_condition);
+
+                for row_num in first_row..=last_row {
+                    if !self.row_matches_top10_filter(row_num, *col_num, top10, filter_value) {
+                        self.set_row_hidden(row_num)?;
+                    }
+                }
+
+                continue;
+            }
+
+            // Icons are generated by Excel at runtime so we can't determine
+            // which rows match an icon filter.
+            if filter_condition.icon.is_some() {
+                continue;
+            }
+
+            // Handle cell and font color filters.
+            if let Some(format) = &filter_condition.color_format {
+                for row_num in first_row..=last_row {
+                    if !self.row_matches_color_filter(row_num, *col_num, format) {
+                        self.set_row_hidden(row_num)?;
+                    }
+                }
+
+                continue;
+            }
+
+            for row_num in first_row..=last_row {
+                if filter_condition.is_list_filter {
+                    // Handle list filters.
+                    if !self.row_matches_list_filter(row_num, *col_num, &filter_condition) {
+                        self.set_row_hidden(row_num)?;
+                    }
+                } else {
+                    // Handle custom filters.
+                    if !self.row_matches_custom_filters(row_num, *col_num, &filter_condition) {
+                        self.set_row_hidden(row_num)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Get the numeric data in an autofilter column, excluding the header.
+    fn autofilter_column_numbers(&self, col_num: ColNum) -> Vec<f64> {
+        let first_row = self.autofilter_defined_name.first_row + 1;
+        let last_row = self.autofilter_defined_name.last_row;
+        let mut numbers = vec![];
+
+        for row_num in first_row..=last_row {
+            if let Some(number) = self.cell_number(row_num, col_num) {
+                numbers.push(number);
+            }
+        }
+
+        numbers
+    }
+
+    // Get the number value, if any, of a cell. Dates are stored as numbers in
+    // Excel so they are also included.
+    fn cell_number(&self, row_num: RowNum, col_num: ColNum) -> Option<f64> {
+        match self.data_table.get(&row_num)?.get(&col_num)? {
+            CellType::Number { number, .. } | CellType::DateTime { number, .. } => Some(*number),
+            _ => None,
+        }
+    }
+
+    // Get the cut off value for a "Top 10" filter. This is the smallest value
+    // in the top N items (or the largest in the bottom N items). Excel stores
+    // this value in the filter and uses it to decide which rows to show.
+    pub(crate) fn top10_filter_value(
+        &self,
+        col_num: ColNum,
+        filter_condition: &FilterCondition,
+    ) -> Option<f64> {
+        let (top10, value) = filter_condition.top10?;
+        let mut numbers = self.autofilter_column_numbers(col_num);
+
+        if numbers.is_empty() {
+            return None;
+        }
+
+        if top10.is_top() {
+            numbers.sort_by(|a, b| b.total_cmp(a));
+        } else {
+            numbers.sort_by(f64::total_cmp);
+        }
+
+        // Get the number of items to show. For percentages Excel rounds down
+        // but always shows at least one item.
+        let num_items = if top10.is_percent() {
+            (numbers.len() * value as usize / 100).max(1)
+        } else {
+            value as usize
+        };
+
+        let index = num_items.min(numbers.len()) - 1;
+
+        Some(numbers[index])
+    }
+
+    // Check if the data in a cell matches a "Top 10" filter. Excel only
+    // considers numbers and it also shows any values that are equal to the cut
+    // off value so more than N rows may be shown.
+    fn row_matches_top10_filter(
+        &self,
+        row_num: RowNum,
+        col_num: ColNum,
+        top10: FilterTop10,
+        filter_value: Option<f64>,
+    ) -> bool {
+        let (Some(number), Some(filter_value)) = (self.cell_number(row_num, col_num), filter_value)
+        else {
+            return false;
+        };
+
+        if top10.is_top() {
+            number >= filter_value
+        } else {
+            number <= filter_value
         }
     }
 
-    // Hide any rows in the autofilter range that don't match the autofilter
-    // conditions, like Excel does at runtime.
-    pub(crate) fn hide_autofilter_rows(&mut self) {
-        if self.filter_conditions.is_empty() || self.filter_automatic_off {
-            return;
-        }
+    // Check if the cell fill or font color matches a color filter. The cell
+    // format is taken from the cell, or failing that, the row or column format.
+    fn row_matches_color_filter(&self, row_num: RowNum, col_num: ColNum, filter: &Format) -> bool {
+        let mut xf_index = self
+            .data_table
+            .get(&row_num)
+            .and_then(|columns| columns.get(&col_num))
+            .map_or(0, CellType::xf_index);
 
-        // Get the range that the autofilter applies to.
-        let first_row = self.autofilter_defined_name.first_row + 1; // Skip header.
-        let last_row = self.autofilter_defined_name.last_row;
+        if xf_index == 0 {
+            if let Some(row_options) = self.changed_rows.get(&row_num) {
+                xf_index = row_options.xf_index;
+            }
+        }
 
-        for col_num in self.filter_conditions.clone().keys() {
-            // Iterate through each column filter conditions.
-            let filter_condition = self.filter_conditions.get(col_num).unwrap().clone();
-            for row_num in first_row..=last_row {
-                if filter_condition.is_list_filter {
-                    // Handle list filters.
-                    if !self.row_matches_list_filter(row_num, *col_num, &filter_condition) {
-                        self.set_row_hidden(row_num).unwrap();
-                    }
-                } else {
-                    // Handle custom filters.
-                    if !self.row_matches_custom_filters(row_num, *col_num, &filter_condition) {
-                        self.set_row_hidden(row_num).unwrap();
-                    }
-                }
+        if xf_index == 0 {
+            if let Some(col_options) = self.changed_cols.get(&col_num) {
+                xf_index = col_options.xf_index;
             }
         }
+
+        let format = &self.xf_formats[xf_index as usize];
+
+        if filter.font.color != Color::Default {
+            return format.font.color == filter.font.color;
+        }
+
+        // Excel uses the background color for solid fills unless the
+        // foreground color has also been set.
+        let fill = &format.fill;
+        let fill_color = match fill.pattern {
+            FormatPattern::None => fill.background_color,
+            FormatPattern::Solid
+                if fill.foreground_color != Color::Default
+                    && fill.foreground_color != Color::Automatic =>
+            {
+                fill.foreground_color
+            }
+            FormatPattern::Solid => fill.background_color,
+            _ => Color::Default,
+        };
+
+        fill_color == filter.fill.background_color
     }
 
     // Check if the data in a cell matches one of the values in the list of
@@ -11261,11 +14324,9 @@ impl Worksheet {
                 has_cell_data = true;
 
                 match cell {
-                    CellType::String { string, .. }
-                    | CellType::RichString {
-                        raw_string: string, ..
-                    } => {
-                        let cell_string = string.clone().to_lowercase().trim().to_string();
+                    CellType::String { .. } | CellType::RichString { .. } => {
+                        let string = cell.unformatted_string().unwrap_or_default();
+                        let cell_string = string.to_lowercase().trim().to_string();
 
                         for filter in &filter_condition.list {
                             if cell_string == filter.string.to_lowercase().trim() {
@@ -11286,6 +14347,13 @@ impl Worksheet {
                             }
                         }
                     }
+                    CellType::DateTime { number, .. } => {
+                        for date_group in &filter_condition.date_groups {
+                            if date_group.matches(*number) {
+                                return true;
+                            }
+                        }
+                    }
                     CellType::Blank { .. } => {
                         if filter_condition.should_match_blanks {
                             return true;
@@ -11353,11 +14421,9 @@ impl Worksheet {
         if let Some(columns) = self.data_table.get(&row_num) {
             if let Some(cell) = columns.get(&col_num) {
                 match cell {
-                    CellType::String { string, .. }
-                    | CellType::RichString {
-                        raw_string: string, ..
-                    } => {
-                        let cell_string = string.clone().to_lowercase().trim().to_string();
+                    CellType::String { .. } | CellType::RichString { .. } => {
+                        let string = cell.unformatted_string().unwrap_or_default();
+                        let cell_string = string.to_lowercase().trim().to_string();
                         let filter_string = filter.string.to_lowercase().trim().to_string();
 
                         match filter.criteria {
@@ -11485,6 +14551,7 @@ impl Worksheet {
         is_datetime: bool,
     ) -> Result<&mut Worksheet, XlsxError> {
         // Check row and col are in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -11535,6 +14602,7 @@ impl Worksheet {
         }
 
         // Check row and col are in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -11582,6 +14650,7 @@ impl Worksheet {
         }
 
         // Check row and col are in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -11599,10 +14668,12 @@ impl Worksheet {
 
         // Create the appropriate cell type to hold the data.
         let cell = CellType::RichString {
-            string: Arc::from(string),
             xf_index,
-            raw_string: Arc::from(raw_string),
             string_id: 0,
+            rich_string: Box::new(RichStringCell {
+                string: Arc::from(string),
+                raw_string: Box::from(raw_string),
+            }),
         };
 
         self.insert_cell(row, col, cell);
@@ -11619,12 +14690,19 @@ impl Worksheet {
         formula: Formula,
         format: Option<&Format>,
     ) -> Result<&mut Worksheet, XlsxError> {
+        let formula = if self.use_implicit_intersection {
+            formula.use_implicit_intersection()
+        } else {
+            formula
+        };
+
         // Transfer to dynamic formula handling function.
         if formula.is_dynamic_function() {
             return self.store_array_formula(row, col, row, col, formula, None, true);
         }
 
         // Check row and col are in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -11636,17 +14714,19 @@ impl Worksheet {
         };
 
         // Set the formula result to the default or user defined
-        let result = if formula.result.is_empty() {
-            self.default_result.clone()
-        } else {
-            formula.result.clone()
+        let result = match &formula.result {
+            Some(result) => result.clone(),
+            None => FormulaResult::parse(&self.default_result),
         };
 
         // Create the appropriate cell type to hold the data.
         let cell = CellType::Formula {
-            formula: formula.expand_formula(self.use_future_functions),
             xf_index,
-            result,
+            formula: Box::new(FormulaCell {
+                formula: formula.expand_formula(self.use_future_functions),
+                result,
+                range: None,
+            }),
         };
 
         self.insert_cell(row, col, cell);
@@ -11667,6 +14747,7 @@ impl Worksheet {
         is_dynamic: bool,
     ) -> Result<&mut Worksheet, XlsxError> {
         // Check rows and cols are in the allowed range.
+        self.check_flushed_row(cmp::min(first_row, last_row))?;
         if !self.check_dimensions(first_row, first_col)
             || !self.check_dimensions(last_row, last_col)
         {
@@ -11687,6 +14768,12 @@ impl Worksheet {
         // Create the array range reference.
         let range = utility::cell_range(first_row, first_col, last_row, last_col);
 
+        let formula = if self.use_implicit_intersection {
+            formula.use_implicit_intersection()
+        } else {
+            formula
+        };
+
         // Check for a dynamic function in a standard static array formula.
         let mut is_dynamic = is_dynamic;
         if !is_dynamic && formula.is_dynamic_function() {
@@ -11698,19 +14785,20 @@ impl Worksheet {
         }
 
         // Set the formula result to the default or user defined
-        let result = if formula.result.is_empty() {
-            self.default_result.clone()
-        } else {
-            formula.result.clone()
+        let result = match &formula.result {
+            Some(result) => result.clone(),
+            None => FormulaResult::parse(&self.default_result),
         };
 
         // Create the appropriate cell type to hold the data.
         let cell = CellType::ArrayFormula {
-            formula: formula.expand_formula(self.use_future_functions),
             xf_index,
-            result,
             is_dynamic,
-            range: range.into_boxed_str(),
+            formula: Box::new(FormulaCell {
+                formula: formula.expand_formula(self.use_future_functions),
+                result,
+                range: Some(range.into_boxed_str()),
+            }),
         };
 
         self.insert_cell(first_row, first_col, cell);
@@ -11730,6 +14818,129 @@ impl Worksheet {
         Ok(self)
     }
 
+    // Set the result of a formula cell, with a warning if the cell doesn't
+    // contain a formula.
+    fn store_formula_result(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        result: FormulaResult,
+    ) -> &mut Worksheet {
+        let has_cell = self
+            .data_table
+            .get(&row)
+            .is_some_and(|columns| columns.contains_key(&col));
+
+        match self.formula_result_mut(row, col) {
+            Some(cell_result) => {
+                *cell_result = result;
+                self.warnings.clear_at_cell("formula_result", row, col);
+            }
+            None if has_cell => {
+                self.warnings.warn_at_cell(
+                    "formula_result",
+                    row,
+                    col,
+                    format!("Cell ({row}, {col}) doesn't contain a formula."),
+                );
+            }
+            None => {}
+        }
+
+        self
+    }
+
+    // Get a mutable reference to the result of a formula cell, if there is one.
+    fn formula_result_mut(&mut self, row: RowNum, col: ColNum) -> Option<&mut FormulaResult> {
+        match self.data_table.get_mut(&row)?.get_mut(&col)? {
+            CellType::Formula { formula, .. }
+            | CellType::ArrayFormula { formula, .. }
+            | CellType::SharedFormula { formula, .. } => Some(&mut formula.result),
+            _ => None,
+        }
+    }
+
+    // Store a shared formula in the worksheet data table structure. The first
+    // cell holds the formula and the range and the other cells refer to it via
+    // the shared index.
+    fn store_shared_formula(
+        &mut self,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+        formula: Formula,
+        format: Option<&Format>,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check rows and cols are in the allowed range.
+        self.check_flushed_row(cmp::min(first_row, last_row))?;
+        if !self.check_dimensions(first_row, first_col)
+            || !self.check_dimensions(last_row, last_col)
+        {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Check order of first/last values.
+        if first_row > last_row || first_col > last_col {
+            return Err(XlsxError::RowColumnOrderError);
+        }
+
+        let formula = if self.use_implicit_intersection {
+            formula.use_implicit_intersection()
+        } else {
+            formula
+        };
+
+        if formula.is_dynamic_function() {
+            return Err(XlsxError::ParameterError(
+                "Dynamic array formulas can't be written as shared formulas".to_string(),
+            ));
+        }
+
+        // Get the index of the format object, if any.
+        let xf_index = match format {
+            Some(format) => self.format_xf_index(format),
+            None => 0,
+        };
+
+        // Set the formula result to the default or user defined
+        let result = match &formula.result {
+            Some(result) => result.clone(),
+            None => FormulaResult::parse(&self.default_result),
+        };
+
+        let shared_index = self.shared_formula_ranges.len() as u32;
+        self.shared_formula_ranges
+            .push((first_row, first_col, last_row, last_col));
+
+        let range = utility::cell_range(first_row, first_col, last_row, last_col);
+        let expanded_formula = formula.expand_formula(self.use_future_functions);
+
+        for row in first_row..=last_row {
+            for col in first_col..=last_col {
+                let is_first = row == first_row && col == first_col;
+
+                let cell = CellType::SharedFormula {
+                    xf_index,
+                    shared_index,
+                    formula: Box::new(FormulaCell {
+                        formula: if is_first {
+                            expanded_formula.clone()
+                        } else {
+                            Box::from("")
+                        },
+                        result: result.clone(),
+                        range: is_first.then(|| Box::from(range.as_str())),
+                    }),
+                };
+
+                self.insert_cell(row, col, cell);
+            }
+        }
+
+        Ok(self)
+    }
+
     // Store a blank cell in the worksheet data table structure.
     fn store_blank(
         &mut self,
@@ -11738,6 +14949,7 @@ impl Worksheet {
         format: &Format,
     ) -> Result<&mut Worksheet, XlsxError> {
         // Check row and col are in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -11762,6 +14974,7 @@ impl Worksheet {
         format: Option<&Format>,
     ) -> Result<&mut Worksheet, XlsxError> {
         // Check row and col are in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -11791,7 +15004,18 @@ impl Worksheet {
         url: Url,
         format: Option<&Format>,
     ) -> Result<&mut Worksheet, XlsxError> {
-        let hyperlink = Hyperlink::new(url)?;
+        self.warnings.extend(&url.warnings);
+        let mut hyperlink = Hyperlink::new(url);
+
+        if !self.check_hyperlink_length(row, col, &mut hyperlink)? {
+            // Write the url text as a plain string for the WriteAsText policy.
+            match format {
+                Some(format) => self.write_string_with_format(row, col, &hyperlink.text, format)?,
+                None => self.write_string(row, col, &hyperlink.text)?,
+            };
+
+            return Ok(self);
+        }
 
         match format {
             Some(format) => self.write_string_with_format(row, col, &hyperlink.text, format)?,
@@ -11806,6 +15030,50 @@ impl Worksheet {
         Ok(self)
     }
 
+    // Check a hyperlink against Excel's length limits and apply the url length
+    // policy. Returns false if the link should be dropped and the text written
+    // as a plain string.
+    fn check_hyperlink_length(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        hyperlink: &mut Hyperlink,
+    ) -> Result<bool, XlsxError> {
+        if !hyperlink.exceeds_max_len() {
+            self.warnings.clear_at_cell("url_length", row, col);
+            return Ok(true);
+        }
+
+        match self.url_length_policy {
+            UrlLengthPolicy::Error => Err(XlsxError::MaxUrlLengthExceeded),
+            UrlLengthPolicy::Truncate => {
+                self.warnings.warn_at_cell(
+                    "url_length",
+                    row,
+                    col,
+                    format!(
+                        "Url exceeds Excel's length limits and has been truncated: '{}'",
+                        hyperlink.text
+                    ),
+                );
+                hyperlink.truncate();
+                Ok(true)
+            }
+            UrlLengthPolicy::WriteAsText => {
+                self.warnings.warn_at_cell(
+                    "url_length",
+                    row,
+                    col,
+                    format!(
+                        "Url exceeds Excel's length limits and has been written as text: '{}'",
+                        hyperlink.text
+                    ),
+                );
+                Ok(false)
+            }
+        }
+    }
+
     // Store a reference to an embedded cell image.
     fn store_embedded_image(
         &mut self,
@@ -11815,6 +15083,7 @@ impl Worksheet {
         format: Option<&Format>,
     ) -> Result<&mut Worksheet, XlsxError> {
         // Check row and columns are in the allowed range.
+        self.check_flushed_row(row)?;
         if !self.check_dimensions(row, col) {
             return Err(XlsxError::RowColumnLimitError);
         }
@@ -11839,10 +15108,13 @@ impl Worksheet {
 
         // Store the image hyperlink, if any.
         if let Some(url) = &image.url {
-            let mut hyperlink = Hyperlink::new(url.clone())?;
+            self.warnings.extend(&url.warnings);
+            let mut hyperlink = Hyperlink::new(url.clone());
             hyperlink.display = true;
 
-            self.hyperlinks.insert((row, col), hyperlink);
+            if self.check_hyperlink_length(row, col, &mut hyperlink)? {
+                self.hyperlinks.insert((row, col), hyperlink);
+            }
         }
 
         // Get the index of the format object, if any.
@@ -11907,12 +15179,16 @@ impl Worksheet {
             // First segment doesn't require a font run for the default format.
             if format.is_default() && first_segment {
                 styler.writer.xml_start_tag_only("r");
-                styler.writer.xml_data_element("t", string, &attributes);
+                styler
+                    .writer
+                    .xml_string_data_element("t", string, &attributes);
                 styler.writer.xml_end_tag("r");
             } else {
                 styler.writer.xml_start_tag_only("r");
                 styler.write_font(&format.font, false);
-                styler.writer.xml_data_element("t", string, &attributes);
+                styler
+                    .writer
+                    .xml_string_data_element("t", string, &attributes);
                 styler.writer.xml_end_tag("r");
             }
             first_segment = false;
@@ -11923,6 +15199,25 @@ impl Worksheet {
 
     // Insert a cell value into the worksheet data table structure.
     fn insert_cell(&mut self, row: RowNum, col: ColNum, cell: CellType) {
+        // Overwriting the master cell of a shared formula would leave the other
+        // cells in the range referring to a formula that no longer exists so
+        // they are converted to standalone formulas first.
+        if let Some(CellType::SharedFormula {
+            shared_index,
+            formula,
+            ..
+        }) = self
+            .data_table
+            .get(&row)
+            .and_then(|columns| columns.get(&col))
+        {
+            if formula.range.is_some() {
+                let shared_index = *shared_index;
+                let formula = formula.formula.clone();
+                self.expand_shared_formula(shared_index, &formula);
+            }
+        }
+
         match self.data_table.entry(row) {
             Entry::Occupied(mut entry) => {
                 // The row already exists. Insert/replace column value.
@@ -11931,9 +15226,55 @@ impl Worksheet {
             }
             Entry::Vacant(entry) => {
                 // The row doesn't exist, create a new row with columns and insert
-                // the cell value.
-                let columns = BTreeMap::from([(col, cell)]);
-                entry.insert(columns);
+                // the cell value. The row is pre-sized if there is a capacity
+                // hint.
+                let mut columns = Vec::with_capacity(usize::from(self.col_capacity).max(1));
+                columns.push((col, cell));
+                entry.insert(CellRow(columns));
+            }
+        }
+    }
+
+    // Convert the cells of a shared formula to standalone formulas. The master
+    // formula is relative to the first cell in the range so the references
+    // are shifted for each of the other cells, like Excel does.
+    fn expand_shared_formula(&mut self, shared_index: u32, formula: &str) {
+        let (first_row, first_col, last_row, last_col) =
+            self.shared_formula_ranges[shared_index as usize];
+
+        for (row, columns) in self.data_table.range_mut(first_row..=last_row) {
+            for (col, cell) in &mut columns.0 {
+                if *col < first_col || *col > last_col {
+                    continue;
+                }
+
+                let CellType::SharedFormula {
+                    xf_index,
+                    shared_index: index,
+                    formula: shared_formula,
+                } = cell
+                else {
+                    continue;
+                };
+
+                if *index != shared_index {
+                    continue;
+                }
+
+                let expanded = Formula::shift_references(
+                    formula,
+                    i64::from(*row) - i64::from(first_row),
+                    i64::from(*col) - i64::from(first_col),
+                );
+
+                *cell = CellType::Formula {
+                    xf_index: *xf_index,
+                    formula: Box::new(FormulaCell {
+                        formula: Box::from(expanded),
+                        result: shared_formula.result.clone(),
+                        range: None,
+                    }),
+                };
             }
         }
     }
@@ -11975,6 +15316,47 @@ impl Worksheet {
         }
     }
 
+    // Get the active cell and the cell range for a selection. The first/last
+    // order can be reversed to allow a selection to go from the end to the
+    // start. We take the active cell from the user first row/col and then
+    // reverse them as required for the full range.
+    fn selection_range(
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+    ) -> (String, String) {
+        let active_cell = utility::row_col_to_cell(first_row, first_col);
+
+        let range = utility::cell_range(
+            cmp::min(first_row, last_row),
+            cmp::min(first_col, last_col),
+            cmp::max(first_row, last_row),
+            cmp::max(first_col, last_col),
+        );
+
+        (active_cell, range)
+    }
+
+    // Store the split pane positions. Excel stores these in twips (1/20 of a
+    // point) with some additional padding for the row and column headers.
+    fn store_split_panes(&mut self, x_pixels: f64, y_pixels: f64) {
+        let x_split = if x_pixels > 0.0 {
+            x_pixels * 15.0 + 390.0
+        } else {
+            0.0
+        };
+
+        let y_split = if y_pixels > 0.0 {
+            y_pixels * 15.0 + 300.0
+        } else {
+            0.0
+        };
+
+        self.panes.split = (x_split, y_split);
+        self.panes.freeze_cell = (0, 0);
+    }
+
     // Check that row and col are within the allowed Excel range and store max
     // and min values for use in other methods/elements.
     fn check_dimensions(&mut self, row: RowNum, col: ColNum) -> bool {
@@ -11986,6 +15368,11 @@ impl Worksheet {
             return false;
         }
 
+        // Rows that have been flushed from memory can't be changed.
+        if row < self.flushed_rows {
+            return false;
+        }
+
         // Store any changes in worksheet dimensions.
         self.dimensions.first_row = cmp::min(self.dimensions.first_row, row);
         self.dimensions.first_col = cmp::min(self.dimensions.first_col, col);
@@ -11995,6 +15382,16 @@ impl Worksheet {
         true
     }
 
+    // Check that a row hasn't already been flushed from memory to the
+    // temporary file.
+    fn check_flushed_row(&self, row: RowNum) -> Result<(), XlsxError> {
+        if row < self.flushed_rows {
+            Err(XlsxError::RowFlushedError)
+        } else {
+            Ok(())
+        }
+    }
+
     // Check that row and col are within the allowed Excel range but don't
     // modify the worksheet cell range.
     #[allow(clippy::unused_self)]
@@ -12011,7 +15408,9 @@ impl Worksheet {
     }
 
     // Cached/faster version of utility.col_to_name() to use in the inner loop.
-    fn col_to_name(col_names: &mut HashMap<u16, String>, col_num: ColNum) -> &str {
+    // The cache is shared by all the rows in the worksheet so each column name
+    // is only created once.
+    fn col_to_name(col_names: &mut FxHashMap<ColNum, String>, col_num: ColNum) -> &str {
         if col_num < 26 {
             &COLUMN_LETTERS[col_num as usize..(col_num + 1) as usize]
         } else {
@@ -12025,6 +15424,27 @@ impl Worksheet {
     // indexes will be replaced by global/workbook indices before the worksheet
     // is saved. XF indexed are used for cell formats.
     fn format_xf_index(&mut self, format: &Format) -> u32 {
+        if !format.warnings.is_empty() {
+            self.warnings.extend(&format.warnings);
+        }
+
+        // Formats interned by the workbook, via a FormatId, are looked up by
+        // their id to avoid hashing the full format properties.
+        if let Some(id) = format.interned_id.0 {
+            if let Some(xf_index) = self.interned_xf_indices.get(&id) {
+                return *xf_index;
+            }
+
+            let xf_index = self.format_xf_index_uncached(format);
+            self.interned_xf_indices.insert(id, xf_index);
+            return xf_index;
+        }
+
+        self.format_xf_index_uncached(format)
+    }
+
+    // Get the local format index by looking up the full format properties.
+    fn format_xf_index_uncached(&mut self, format: &Format) -> u32 {
         match self.xf_indices.get_mut(format) {
             Some(xf_index) => *xf_index,
             None => {
@@ -12055,6 +15475,10 @@ impl Worksheet {
     ///
     #[doc(hidden)] // Set dxf_index (public for testing).
     pub fn format_dxf_index(&mut self, format: &Format) -> u32 {
+        if !format.warnings.is_empty() {
+            self.warnings.extend(&format.warnings);
+        }
+
         match self.dxf_indices.get_mut(format) {
             Some(dxf_index) => *dxf_index,
             None => {
@@ -12189,8 +15613,11 @@ impl Worksheet {
         &mut self,
         image_ids: &mut HashMap<u64, u32>,
         base_image_id: u32,
+        vml_data_id: u32,
         drawing_id: u32,
     ) {
+        self.header_footer_vml_data_id = vml_data_id;
+
         let mut rel_ids: HashMap<u64, u32> = HashMap::new();
         for image in self.header_footer_images.clone().into_iter().flatten() {
             let image_id = match image_ids.get(&image.hash) {
@@ -12248,6 +15675,56 @@ impl Worksheet {
         ));
     }
 
+    // Set up the cell notes. Excel stores the note text in a comments file and
+    // the note box shape and position in a VML file.
+    pub(crate) fn prepare_vml_objects(
+        &mut self,
+        vml_data_id: u32,
+        vml_shape_id: u32,
+        vml_drawing_id: u32,
+        comment_id: u32,
+    ) {
+        self.vml_data_id = vml_data_id;
+        self.vml_shape_id = vml_shape_id;
+
+        let notes: Vec<Note> = self
+            .notes
+            .values()
+            .flat_map(|columns| columns.values().cloned())
+            .collect();
+
+        for mut note in notes {
+            let (row, col) = note.start_cell();
+
+            // Set the default worksheet properties if they haven't been
+            // overridden by the note.
+            if note.author.is_none() && !self.comment_author.is_empty() {
+                note.author = Some(self.comment_author.clone());
+            }
+
+            if note.is_visible.is_none() {
+                note.is_visible = Some(self.show_all_notes);
+            }
+
+            // Convert the note box dimensions to drawing dimensions.
+            note.drawing_info = Some(self.position_object_pixels(row, col, &note));
+
+            self.notes_vml_info.push(note);
+        }
+
+        // Store the linkages to the worksheets rels file.
+        let vml_drawing_name = format!("../drawings/vmlDrawing{vml_drawing_id}.vml");
+        self.drawing_object_relationships.push((
+            "vmlDrawing".to_string(),
+            vml_drawing_name,
+            String::new(),
+        ));
+
+        let comment_name = format!("../comments{comment_id}.xml");
+        self.comment_relationships
+            .push(("comments".to_string(), comment_name, String::new()));
+    }
+
     // Convert the chart dimensions into drawing dimensions and add them to the
     // Drawing object. Also set the rel linkages between the files.
     pub(crate) fn prepare_worksheet_charts(&mut self, mut chart_id: u32, drawing_id: u32) -> u32 {
@@ -12316,6 +15793,16 @@ impl Worksheet {
         table_id
     }
 
+    // Set the rel linkage between the worksheet and its named sheet view xml
+    // file.
+    pub(crate) fn prepare_worksheet_named_sheet_views(&mut self, named_sheet_view_id: u16) {
+        self.named_sheet_view_relationships.push((
+            "namedSheetView".to_string(),
+            format!("../namedSheetViews/namedSheetView{named_sheet_view_id}.xml"),
+            String::new(),
+        ));
+    }
+
     // Calculate the vertices that define the position of a graphical object
     // within the worksheet in EMUs. The vertices are expressed as English
     // Metric Units (EMUs). There are 12,700 EMUs per point. Therefore, 12,700 *
@@ -12543,22 +16030,102 @@ impl Worksheet {
         self.writer.reset();
         self.drawing.writer.reset();
 
-        for chart in self.charts.values_mut() {
-            chart.writer.reset();
-        }
+        for chart in self.charts.values_mut() {
+            chart.writer.reset();
+        }
+
+        for table in &mut self.tables {
+            table.writer.reset();
+        }
+
+        self.rel_count = 0;
+        self.drawing.drawings.clear();
+        self.table_relationships.clear();
+        self.named_sheet_view_relationships.clear();
+        self.hyperlink_relationships.clear();
+        self.drawing_object_relationships.clear();
+        self.drawing_relationships.clear();
+        self.vml_drawing_relationships.clear();
+        self.header_footer_vml_info.clear();
+        self.comment_relationships.clear();
+        self.notes_vml_info.clear();
+    }
+
+    // Apply the workbook default page setup options to any options that
+    // haven't been set explicitly for the worksheet.
+    pub(crate) fn apply_default_page_setup(&mut self, page_setup: &PageSetup) {
+        let overrides = &self.page_setup_overrides;
+
+        if let Some(paper_size) = page_setup.paper_size {
+            if !overrides.paper_size {
+                self.paper_size = paper_size;
+                self.page_setup_changed = true;
+            }
+        }
+
+        if let Some(portrait) = page_setup.portrait {
+            if !overrides.orientation {
+                self.portrait = portrait;
+                self.page_setup_changed = true;
+            }
+        }
+
+        let margins = [
+            &mut self.margin_left,
+            &mut self.margin_right,
+            &mut self.margin_top,
+            &mut self.margin_bottom,
+            &mut self.margin_header,
+            &mut self.margin_footer,
+        ];
+
+        for (i, margin) in margins.into_iter().enumerate() {
+            if let Some(default_margin) = page_setup.margins[i] {
+                if !overrides.margins[i] {
+                    *margin = default_margin;
+                    self.page_setup_changed = true;
+                }
+            }
+        }
+
+        if let Some(header) = &page_setup.header {
+            if !overrides.header {
+                self.header.clone_from(header);
+                self.page_setup_changed = true;
+                self.head_footer_changed = true;
+            }
+        }
+
+        if let Some(footer) = &page_setup.footer {
+            if !overrides.footer {
+                self.footer.clone_from(footer);
+                self.page_setup_changed = true;
+                self.head_footer_changed = true;
+            }
+        }
+    }
+
+    // Remove any internal hyperlinks that refer to a worksheet that has been
+    // removed from the workbook. The cell data is retained.
+    pub(crate) fn remove_internal_hyperlinks(&mut self, sheet_name: &str) {
+        let sheet_name = sheet_name.to_lowercase();
+
+        self.hyperlinks.retain(|_, hyperlink| {
+            if !matches!(hyperlink.link_type, HyperlinkType::Internal) {
+                return true;
+            }
+
+            let Some((target_sheet, _)) = hyperlink.location.rsplit_once('!') else {
+                return true;
+            };
 
-        for table in &mut self.tables {
-            table.writer.reset();
-        }
+            let target_sheet = match target_sheet.strip_prefix('\'') {
+                Some(quoted) => quoted.trim_end_matches('\'').replace("''", "'"),
+                None => target_sheet.to_string(),
+            };
 
-        self.rel_count = 0;
-        self.drawing.drawings.clear();
-        self.table_relationships.clear();
-        self.hyperlink_relationships.clear();
-        self.drawing_object_relationships.clear();
-        self.drawing_relationships.clear();
-        self.vml_drawing_relationships.clear();
-        self.header_footer_vml_info.clear();
+            target_sheet.to_lowercase() != sheet_name
+        });
     }
 
     // Check if any external relationships are required.
@@ -12566,6 +16133,21 @@ impl Worksheet {
         !self.hyperlink_relationships.is_empty()
             || !self.drawing_object_relationships.is_empty()
             || !self.table_relationships.is_empty()
+            || !self.comment_relationships.is_empty()
+            || !self.named_sheet_view_relationships.is_empty()
+    }
+
+    // Check if there are any cell notes in the worksheet.
+    pub(crate) fn has_notes(&self) -> bool {
+        !self.notes.is_empty()
+    }
+
+    // Get the number of cell notes in the worksheet.
+    pub(crate) fn num_notes(&self) -> u32 {
+        self.notes
+            .values()
+            .map(|columns| columns.len() as u32)
+            .sum()
     }
 
     // Check if there is a header image.
@@ -12578,6 +16160,40 @@ impl Worksheet {
             || self.header_footer_images[5].is_some()
     }
 
+    // Check that each header/footer image still has a matching &[Picture]
+    // variable. The header or footer string may have been changed after the
+    // image was added.
+    pub(crate) fn header_footer_image_errors(&self) -> Vec<XlsxError> {
+        let mut errors = vec![];
+        let positions = [
+            HeaderImagePosition::Left,
+            HeaderImagePosition::Center,
+            HeaderImagePosition::Right,
+        ];
+
+        for (index, position) in positions.iter().enumerate() {
+            if self.header_footer_images[index].is_some()
+                && !Self::verify_header_footer_image(&self.header, position)
+            {
+                errors.push(XlsxError::ParameterError(format!(
+                    "No &[Picture] or &[G] variable in header string: '{}' for position = '{:?}' in worksheet '{}'",
+                    self.header, position, self.name
+                )));
+            }
+
+            if self.header_footer_images[3 + index].is_some()
+                && !Self::verify_header_footer_image(&self.footer, position)
+            {
+                errors.push(XlsxError::ParameterError(format!(
+                    "No &[Picture] or &[G] variable in footer string: '{}' for position = '{:?}' in worksheet '{}'",
+                    self.footer, position, self.name
+                )));
+            }
+        }
+
+        errors
+    }
+
     // Check that there is a header/footer &[Picture] variable in the correct
     // position to match the corresponding image object.
     fn verify_header_footer_image(string: &str, position: &HeaderImagePosition) -> bool {
@@ -12602,22 +16218,6 @@ impl Worksheet {
         }
     }
 
-    // Convert column pixel width to character width.
-    pub(crate) fn pixels_to_width(pixels: u16) -> f64 {
-        // Properties for Calibri 11.
-        let max_digit_width = 7.0_f64;
-        let padding = 5.0_f64;
-        let mut width = f64::from(pixels);
-
-        if width < 12.0 {
-            width /= max_digit_width + padding;
-        } else {
-            width = (width - padding) / max_digit_width;
-        }
-
-        width
-    }
-
     // Return a range of data from a worksheet to use as cache data in a chart
     // file. If the range doesn't contain string/number data then we return a
     // default struct with an empty cache.
@@ -12713,7 +16313,8 @@ impl Worksheet {
                     | CellType::Formula { xf_index, .. }
                     | CellType::DateTime { xf_index, .. }
                     | CellType::RichString { xf_index, .. }
-                    | CellType::ArrayFormula { xf_index, .. } => {
+                    | CellType::ArrayFormula { xf_index, .. }
+                    | CellType::SharedFormula { xf_index, .. } => {
                         if *xf_index == 0 {
                             *xf_index = format_id;
                         }
@@ -12729,8 +16330,27 @@ impl Worksheet {
     // XML assembly methods.
     // -----------------------------------------------------------------------
 
-    // Assemble and write the XML file.
+    // Assemble and write the XML file to the in-memory buffer.
+    #[allow(dead_code)] // Used in tests.
     pub(crate) fn assemble_xml_file(&mut self) {
+        self.assemble_xml(None).expect(XML_WRITE_ERROR);
+    }
+
+    // Assemble the XML file and stream it to an output writer. The row data,
+    // which is generally the bulk of the file, is flushed to the writer in
+    // chunks so that the full file isn't held in memory.
+    pub(crate) fn assemble_xml_file_to(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        self.assemble_xml(Some(&mut *writer))?;
+        self.writer.flush_to(writer)?;
+
+        // Release the flush buffer since it isn't needed after saving.
+        self.writer = XMLWriter::new();
+
+        Ok(())
+    }
+
+    // Assemble the XML file with optional streaming of the row data.
+    fn assemble_xml(&mut self, mut writer: Option<&mut dyn Write>) -> io::Result<()> {
         self.writer.xml_declaration();
 
         // Write the worksheet element.
@@ -12752,9 +16372,7 @@ impl Worksheet {
         self.write_cols();
 
         // Write the sheetData element.
-        self.write_sheet_data();
-
-        self.write_data_validations();
+        self.write_sheet_data(&mut writer)?;
 
         // Write the sheetProtection element.
         if self.protection_on {
@@ -12781,6 +16399,11 @@ impl Worksheet {
             self.write_conditional_formats();
         }
 
+        // Write the dataValidations element.
+        if !self.data_validations.is_empty() || !self.legacy_data_validations.is_empty() {
+            self.write_data_validations();
+        }
+
         // Write the hyperlinks elements.
         if !self.hyperlinks.is_empty() {
             self.write_hyperlinks();
@@ -12819,6 +16442,11 @@ impl Worksheet {
             self.write_drawing();
         }
 
+        // Write the legacyDrawing element.
+        if self.has_notes() {
+            self.write_legacy_drawing();
+        }
+
         // Write the legacyDrawingHF element.
         if self.has_header_footer_images() {
             self.write_legacy_drawing_hf();
@@ -12836,6 +16464,8 @@ impl Worksheet {
 
         // Close the worksheet tag.
         self.writer.xml_end_tag("worksheet");
+
+        Ok(())
     }
 
     // Write the <worksheet> element.
@@ -12871,11 +16501,16 @@ impl Worksheet {
         if self.filter_conditions.is_empty()
             && !self.fit_to_page
             && (self.tab_color == Color::Default || self.tab_color == Color::Automatic)
+            && self.vba_name.is_none()
         {
             return;
         }
 
         let mut attributes = vec![];
+        if let Some(vba_name) = &self.vba_name {
+            attributes.push(("codeName", vba_name.clone()));
+        }
+
         if !self.filter_conditions.is_empty() {
             attributes.push(("filterMode", "1".to_string()));
         }
@@ -12968,6 +16603,10 @@ impl Worksheet {
             attributes.push(("tabSelected", "1".to_string()));
         }
 
+        if self.gridline_color_id != 0 {
+            attributes.push(("defaultGridColor", "0".to_string()));
+        }
+
         match self.page_view {
             PageView::PageLayout => {
                 attributes.push(("view", "pageLayout".to_string()));
@@ -12982,20 +16621,43 @@ impl Worksheet {
             attributes.push(("topLeftCell", self.top_left_cell.clone()));
         }
 
-        if self.zoom != 100 {
-            attributes.push(("zoomScale", self.zoom.to_string()));
+        if self.gridline_color_id != 0 {
+            attributes.push(("colorId", self.gridline_color_id.to_string()));
+        }
 
-            match self.page_view {
-                PageView::PageLayout => {
-                    attributes.push(("zoomScalePageLayoutView", self.zoom.to_string()));
-                }
-                PageView::PageBreaks => {
-                    attributes.push(("zoomScaleSheetLayoutView", self.zoom.to_string()));
-                }
-                PageView::Normal => {
-                    attributes.push(("zoomScaleNormal", self.zoom.to_string()));
-                }
-            }
+        // The zoom set with set_zoom() applies to the current view unless a
+        // zoom was set explicitly for that view.
+        let mut zoom_normal = self.zoom_normal;
+        let mut zoom_page_layout = self.zoom_page_layout;
+        let mut zoom_page_break_preview = self.zoom_page_break_preview;
+
+        let view_zoom = match self.page_view {
+            PageView::Normal => &mut zoom_normal,
+            PageView::PageLayout => &mut zoom_page_layout,
+            PageView::PageBreaks => &mut zoom_page_break_preview,
+        };
+
+        if *view_zoom == 0 && self.zoom != 100 {
+            *view_zoom = self.zoom;
+        }
+
+        if *view_zoom != 0 && *view_zoom != 100 {
+            attributes.push(("zoomScale", view_zoom.to_string()));
+        }
+
+        if zoom_normal != 0 {
+            attributes.push(("zoomScaleNormal", zoom_normal.to_string()));
+        }
+
+        if zoom_page_break_preview != 0 {
+            attributes.push((
+                "zoomScaleSheetLayoutView",
+                zoom_page_break_preview.to_string(),
+            ));
+        }
+
+        if zoom_page_layout != 0 {
+            attributes.push(("zoomScalePageLayoutView", zoom_page_layout.to_string()));
         }
 
         attributes.push(("workbookViewId", "0".to_string()));
@@ -13012,7 +16674,13 @@ impl Worksheet {
 
     // Write the elements associated with cell selections.
     fn write_selections(&mut self) {
-        if self.selected_range.0.is_empty() {
+        // For split panes the selection is written as part of the panes.
+        if self.selected_range.0.is_empty() || self.panes.is_split() {
+            return;
+        }
+
+        // The top left pane selection can also be set explicitly.
+        if self.panes.selections.contains_key(&Pane::TopLeft) {
             return;
         }
 
@@ -13028,34 +16696,70 @@ impl Worksheet {
             return;
         }
 
+        if self.panes.is_split() {
+            self.write_split_panes();
+            return;
+        }
+
         let row = self.panes.freeze_cell.0;
         let col = self.panes.freeze_cell.1;
-
-        // Write the pane and selection elements.
-        if row > 0 && col > 0 {
-            self.write_pane("bottomRight");
-            self.write_selection(
-                "topRight",
-                &utility::row_col_to_cell(0, col),
-                &utility::row_col_to_cell(0, col),
-            );
-            self.write_selection(
-                "bottomLeft",
-                &utility::row_col_to_cell(row, 0),
-                &utility::row_col_to_cell(row, 0),
-            );
-            self.write_selection("bottomRight", "", "");
+        let top_right_cell = utility::row_col_to_cell(0, col);
+        let bottom_left_cell = utility::row_col_to_cell(row, 0);
+
+        // Get the default selections for the panes. The last pane is the
+        // default active pane.
+        let selections = if row > 0 && col > 0 {
+            vec![
+                (Pane::TopRight, top_right_cell.clone(), top_right_cell),
+                (Pane::BottomLeft, bottom_left_cell.clone(), bottom_left_cell),
+                (Pane::BottomRight, String::new(), String::new()),
+            ]
         } else if col > 0 {
-            self.write_pane("topRight");
-            self.write_selection("topRight", "", "");
+            vec![(Pane::TopRight, String::new(), String::new())]
         } else {
-            self.write_pane("bottomLeft");
-            self.write_selection("bottomLeft", "", "");
+            vec![(Pane::BottomLeft, String::new(), String::new())]
+        };
+
+        // Write the pane and selection elements.
+        let active_pane = self.active_pane(&selections);
+        self.write_pane(active_pane);
+        self.write_pane_selections(selections);
+    }
+
+    // Get the active pane, ignoring any user value that isn't valid for the
+    // panes in the worksheet.
+    fn active_pane(&self, selections: &[(Pane, String, String)]) -> Pane {
+        let default_pane = selections
+            .last()
+            .map_or(Pane::BottomRight, |selection| selection.0);
+
+        match self.panes.active_pane {
+            Some(pane) if pane == Pane::TopLeft || selections.iter().any(|s| s.0 == pane) => pane,
+            _ => default_pane,
+        }
+    }
+
+    // Write the <selection> elements for the panes, replacing the default
+    // selections with any that were set by the user.
+    fn write_pane_selections(&mut self, selections: Vec<(Pane, String, String)>) {
+        for (pane, active_cell, range) in selections {
+            let (active_cell, range) = self
+                .panes
+                .selections
+                .get(&pane)
+                .cloned()
+                .unwrap_or((active_cell, range));
+
+            self.write_selection(pane.name(), &active_cell, &range);
+        }
+
+        if let Some((active_cell, range)) = self.panes.selections.get(&Pane::TopLeft).cloned() {
+            self.write_selection(Pane::TopLeft.name(), &active_cell, &range);
         }
     }
 
     // Write the <pane> element.
-    fn write_pane(&mut self, active_pane: &str) {
+    fn write_pane(&mut self, active_pane: Pane) {
         let row = self.panes.freeze_cell.0;
         let col = self.panes.freeze_cell.1;
         let mut attributes = vec![];
@@ -13069,10 +16773,66 @@ impl Worksheet {
         }
 
         attributes.push(("topLeftCell", self.panes.top_left()));
-        attributes.push(("activePane", active_pane.to_string()));
-        attributes.push(("state", "frozen".to_string()));
+        attributes.push(("activePane", active_pane.name().to_string()));
+
+        if self.panes.frozen_split {
+            attributes.push(("state", "frozenSplit".to_string()));
+        } else {
+            attributes.push(("state", "frozen".to_string()));
+        }
+
+        self.writer.xml_empty_tag("pane", &attributes);
+    }
+
+    // Write the elements associated with split panes.
+    fn write_split_panes(&mut self) {
+        let (x_split, y_split) = self.panes.split;
+        let (top_row, left_col) = self.panes.split_top_left();
+        let top_left_cell = utility::row_col_to_cell(top_row, left_col);
+
+        // Move any user cell selection into the scrolled pane. If there isn't
+        // one then the active cell is the top left cell.
+        let (active_cell, range) = if self.selected_range.0.is_empty() {
+            (top_left_cell.clone(), top_left_cell.clone())
+        } else {
+            self.selected_range.clone()
+        };
+
+        let mut attributes = vec![];
 
+        if x_split > 0.0 {
+            attributes.push(("xSplit", x_split.to_string()));
+        }
+
+        if y_split > 0.0 {
+            attributes.push(("ySplit", y_split.to_string()));
+        }
+
+        attributes.push(("topLeftCell", top_left_cell));
+
+        // Get the default selections for the panes. The last pane is the
+        // default active pane.
+        let selections = if x_split > 0.0 && y_split > 0.0 {
+            let row_cell = utility::row_col_to_cell(top_row, 0);
+            let col_cell = utility::row_col_to_cell(0, left_col);
+
+            vec![
+                (Pane::TopRight, col_cell.clone(), col_cell),
+                (Pane::BottomLeft, row_cell.clone(), row_cell),
+                (Pane::BottomRight, active_cell, range),
+            ]
+        } else if x_split > 0.0 {
+            vec![(Pane::TopRight, active_cell, range)]
+        } else {
+            vec![(Pane::BottomLeft, active_cell, range)]
+        };
+
+        // Write the pane and selection elements.
+        let active_pane = self.active_pane(&selections);
+        attributes.push(("activePane", active_pane.name().to_string()));
         self.writer.xml_empty_tag("pane", &attributes);
+
+        self.write_pane_selections(selections);
     }
 
     // Write the <selection> element.
@@ -13106,54 +16866,124 @@ impl Worksheet {
     }
 
     // Write the <sheetData> element.
-    fn write_sheet_data(&mut self) {
-        if self.data_table.is_empty() && self.changed_rows.is_empty() {
+    fn write_sheet_data(&mut self, writer: &mut Option<&mut dyn Write>) -> io::Result<()> {
+        if self.data_table.is_empty() && self.changed_rows.is_empty() && self.spill.is_none() {
             self.writer.xml_empty_tag_only("sheetData");
         } else {
             self.writer.xml_start_tag_only("sheetData");
-            self.write_data_table();
+
+            // Write any rows stored by flush_rows() followed by the rest.
+            self.copy_spilled_rows(writer)?;
+            let first_row = cmp::max(self.dimensions.first_row, self.flushed_rows);
+            self.write_data_table(first_row, self.dimensions.last_row, writer)?;
+
             self.writer.xml_end_tag("sheetData");
         }
+
+        Ok(())
     }
 
     // Write the <dataValidations> element.
     fn write_data_validations(&mut self) {
-        if self.data_validations.is_empty() {
-            return;
-        }
-
-        let attributes = vec![("count", self.data_validations.len().to_string())];
+        let count = self.data_validations.len() + self.legacy_data_validations.len();
+        let attributes = [("count", count.to_string())];
 
         self.writer.xml_start_tag("dataValidations", &attributes);
 
-        for data_validation in &self.data_validations.clone() {
-            self.write_data_validation(data_validation);
+        for (cell_range, data_validation) in &self.data_validations.clone() {
+            // Write the dataValidation element.
+            self.write_data_validation(cell_range, data_validation);
+        }
+
+        for data_validation in &self.legacy_data_validations.clone() {
+            // Write the dataValidation element.
+            self.write_legacy_data_validation(data_validation);
         }
 
         self.writer.xml_end_tag("dataValidations");
     }
 
     // Write the <dataValidation> element.
-    fn write_data_validation(&mut self, data_validation: &DataValidation) {
-        self.writer
-            .xml_start_tag("dataValidation", &data_validation.get_attributes());
+    fn write_data_validation(&mut self, cell_range: &str, data_validation: &DataValidation) {
+        let mut attributes = vec![];
 
-        let attributes: [(&str, &str); 0] = [];
+        if data_validation.validation_type != DataValidationType::Any {
+            attributes.push(("type", data_validation.validation_type.to_string()));
+        }
 
-        if let Some(formula1) = &data_validation.formula1 {
-            self.writer.xml_data_element(
-                "formula1",
-                format!("{}", formula1.value).as_str(),
-                &attributes,
-            );
+        if data_validation.error_style != DataValidationErrorStyle::Stop {
+            attributes.push(("errorStyle", data_validation.error_style.to_string()));
         }
 
-        if let Some(formula2) = &data_validation.formula2 {
-            self.writer.xml_data_element(
-                "formula2",
-                format!("{}", formula2.value).as_str(),
-                &attributes,
-            );
+        // The "between" operator is the default and is omitted.
+        if !data_validation.operator.is_empty() && data_validation.operator != "between" {
+            attributes.push(("operator", data_validation.operator.clone()));
+        }
+
+        if data_validation.ignore_blank {
+            attributes.push(("allowBlank", "1".to_string()));
+        }
+
+        // Note, Excel uses an inverted "showDropDown" value to hide the
+        // dropdown.
+        if !data_validation.show_dropdown {
+            attributes.push(("showDropDown", "1".to_string()));
+        }
+
+        if data_validation.show_input_message {
+            attributes.push(("showInputMessage", "1".to_string()));
+        }
+
+        if data_validation.show_error_message {
+            attributes.push(("showErrorMessage", "1".to_string()));
+        }
+
+        if !data_validation.error_title.is_empty() {
+            attributes.push(("errorTitle", data_validation.error_title.clone()));
+        }
+
+        if !data_validation.error_message.is_empty() {
+            attributes.push(("error", data_validation.error_message.clone()));
+        }
+
+        if !data_validation.input_title.is_empty() {
+            attributes.push(("promptTitle", data_validation.input_title.clone()));
+        }
+
+        if !data_validation.input_message.is_empty() {
+            attributes.push(("prompt", data_validation.input_message.clone()));
+        }
+
+        attributes.push(("sqref", cell_range.to_string()));
+
+        if data_validation.validation_type == DataValidationType::Any {
+            self.writer.xml_empty_tag("dataValidation", &attributes);
+        } else {
+            self.writer.xml_start_tag("dataValidation", &attributes);
+            self.writer
+                .xml_data_element_only("formula1", &data_validation.formula1);
+
+            if !data_validation.formula2.is_empty() {
+                self.writer
+                    .xml_data_element_only("formula2", &data_validation.formula2);
+            }
+            self.writer.xml_end_tag("dataValidation");
+        }
+    }
+
+    // Write the <dataValidation> element for the deprecated data validation API.
+    fn write_legacy_data_validation(&mut self, data_validation: &DataValidation) {
+        let legacy = &data_validation.legacy;
+
+        self.writer
+            .xml_start_tag("dataValidation", &legacy.attributes());
+
+        if let Some(formula1) = legacy.formula1_value() {
+            self.writer.xml_data_element_only("formula1", &formula1);
+        }
+
+        if let Some(formula2) = legacy.formula2_value() {
+            self.writer.xml_data_element_only("formula2", &formula2);
         }
 
         self.writer.xml_end_tag("dataValidation");
@@ -13416,8 +17246,15 @@ impl Worksheet {
             attributes.push(("draft", "1".to_string()));
         }
 
-        attributes.push(("horizontalDpi", "200".to_string()));
-        attributes.push(("verticalDpi", "200".to_string()));
+        match self.print_errors {
+            PrintErrorOption::Displayed => {}
+            PrintErrorOption::Blank => attributes.push(("errors", "blank".to_string())),
+            PrintErrorOption::Dash => attributes.push(("errors", "dash".to_string())),
+            PrintErrorOption::NA => attributes.push(("errors", "NA".to_string())),
+        }
+
+        attributes.push(("horizontalDpi", self.print_resolution.to_string()));
+        attributes.push(("verticalDpi", self.print_resolution.to_string()));
 
         self.writer.xml_empty_tag("pageSetup", &attributes);
     }
@@ -13448,7 +17285,16 @@ impl Worksheet {
 
         self.writer.xml_start_tag("filterColumn", &attributes);
 
-        if filter_condition.is_list_filter {
+        if let Some((icon_type, icon_index)) = filter_condition.icon {
+            self.write_icon_filter(icon_type, icon_index);
+        } else if let Some(format) = &filter_condition.color_format {
+            self.write_color_filter(format, filter_condition.is_cell_color);
+        } else if filter_condition.top10.is_some() {
+            // The filter value is calculated from the absolute column number.
+            let col_num = col + self.autofilter_defined_name.first_col;
+            let filter_value = self.top10_filter_value(col_num, filter_condition);
+            self.write_top10(filter_condition, filter_value);
+        } else if filter_condition.is_list_filter {
             self.write_list_filters(filter_condition);
         } else {
             self.write_custom_filters(filter_condition);
@@ -13457,6 +17303,53 @@ impl Worksheet {
         self.writer.xml_end_tag("filterColumn");
     }
 
+    // Write the <colorFilter> element.
+    fn write_color_filter(&mut self, format: &Format, is_cell_color: bool) {
+        let dxf_index = self.global_dxf_indices[format.dxf_index as usize];
+        let mut attributes = vec![("dxfId", dxf_index.to_string())];
+
+        if !is_cell_color {
+            attributes.push(("cellColor", "0".to_string()));
+        }
+
+        self.writer.xml_empty_tag("colorFilter", &attributes);
+    }
+
+    // Write the <iconFilter> element.
+    fn write_icon_filter(&mut self, icon_type: ConditionalFormatIconType, icon_index: u8) {
+        let attributes = [
+            ("iconSet", icon_type.to_string()),
+            ("iconId", icon_index.to_string()),
+        ];
+
+        self.writer.xml_empty_tag("iconFilter", &attributes);
+    }
+
+    // Write the <top10> element.
+    fn write_top10(&mut self, filter_condition: &FilterCondition, filter_value: Option<f64>) {
+        let Some((top10, value)) = filter_condition.top10 else {
+            return;
+        };
+
+        let mut attributes = vec![];
+
+        if !top10.is_top() {
+            attributes.push(("top", "0".to_string()));
+        }
+
+        if top10.is_percent() {
+            attributes.push(("percent", "1".to_string()));
+        }
+
+        attributes.push(("val", value.to_string()));
+
+        if let Some(filter_value) = filter_value {
+            attributes.push(("filterVal", filter_value.to_string()));
+        }
+
+        self.writer.xml_empty_tag("top10", &attributes);
+    }
+
     // Write the <filters> element.
     fn write_list_filters(&mut self, filter_condition: &FilterCondition) {
         let mut attributes = vec![];
@@ -13465,7 +17358,7 @@ impl Worksheet {
             attributes.push(("blank", "1".to_string()));
         }
 
-        if filter_condition.list.is_empty() {
+        if filter_condition.list.is_empty() && filter_condition.date_groups.is_empty() {
             self.writer.xml_empty_tag("filters", &attributes);
         } else {
             self.writer.xml_start_tag("filters", &attributes);
@@ -13475,10 +17368,33 @@ impl Worksheet {
                 self.write_filter(data.string.clone());
             }
 
+            for date_group in &filter_condition.date_groups {
+                // Write the dateGroupItem element.
+                self.write_date_group_item(date_group);
+            }
+
             self.writer.xml_end_tag("filters");
         }
     }
 
+    // Write the <dateGroupItem> element.
+    fn write_date_group_item(&mut self, date_group: &FilterDateGroupItem) {
+        let names = ["year", "month", "day", "hour", "minute", "second"];
+        let mut attributes = vec![];
+
+        for (name, value) in names
+            .iter()
+            .zip(date_group.components)
+            .take(date_group.num_components())
+        {
+            attributes.push((*name, value.to_string()));
+        }
+
+        attributes.push(("dateTimeGrouping", date_group.grouping.to_string()));
+
+        self.writer.xml_empty_tag("dateGroupItem", &attributes);
+    }
+
     // Write the <filter> element.
     fn write_filter(&mut self, value: String) {
         let attributes = [("val", value)];
@@ -13510,52 +17426,172 @@ impl Worksheet {
     fn write_custom_filter(&mut self, data: &FilterData) {
         let mut attributes = vec![];
 
-        if !data.criteria.operator().is_empty() {
-            attributes.push(("operator", data.criteria.operator()));
+        if !data.criteria.operator().is_empty() {
+            attributes.push(("operator", data.criteria.operator()));
+        }
+
+        attributes.push(("val", data.value()));
+
+        self.writer.xml_empty_tag("customFilter", &attributes);
+    }
+
+    // Store unique strings in the SST table and convert them to a string id
+    // which is used when writing out the string cells.
+    pub(crate) fn update_string_table_ids(&mut self, string_table: &mut SharedStringsTable) {
+        if !self.uses_string_table {
+            return;
+        }
+
+        for columns in self.data_table.values_mut() {
+            for cell in columns.values_mut() {
+                match cell {
+                    CellType::String {
+                        string, string_id, ..
+                    } => {
+                        *string_id = string_table.shared_string_index(Arc::clone(string));
+                    }
+                    CellType::RichString {
+                        rich_string,
+                        string_id,
+                        ..
+                    } => {
+                        *string_id =
+                            string_table.shared_string_index(Arc::clone(&rich_string.string));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Append a range of rows to the spill file used by flush_rows().
+    fn write_spilled_rows(
+        &mut self,
+        spill: &mut SpillFile,
+        first_row: RowNum,
+        last_row: RowNum,
+    ) -> io::Result<()> {
+        spill.file.seek(SeekFrom::End(0))?;
+        let mut writer = BufWriter::new(&mut spill.file);
+
+        self.write_data_table(first_row, last_row, &mut Some(&mut writer))?;
+        self.writer.flush_to(&mut writer)?;
+
+        writer.flush()
+    }
+
+    // Copy the rows stored by flush_rows() to the output, converting the local
+    // format and image indices to the global/workbook indices.
+    fn copy_spilled_rows(&mut self, writer: &mut Option<&mut dyn Write>) -> io::Result<()> {
+        let Some(spill) = &mut self.spill else {
+            return Ok(());
+        };
+
+        let output: &mut dyn Write = match writer {
+            Some(writer) => {
+                self.writer.flush_to(*writer)?;
+                *writer
+            }
+            None => &mut self.writer.xmlfile,
+        };
+
+        spill.file.seek(SeekFrom::Start(0))?;
+        let mut reader = BufReader::new(&spill.file);
+        let mut chunk = vec![];
+
+        // Read the data up to the end of each tag. Since "<" is always escaped
+        // in text data the last "<" in the chunk is the start of the tag.
+        loop {
+            chunk.clear();
+            if reader.read_until(b'>', &mut chunk)? == 0 {
+                break;
+            }
+
+            match chunk.iter().rposition(|&byte| byte == b'<') {
+                Some(start) if chunk[start..].starts_with(b"<c ") => {
+                    output.write_all(&chunk[..start])?;
+                    let tag = String::from_utf8_lossy(&chunk[start..]);
+                    output.write_all(
+                        Self::global_tag_indices(
+                            &tag,
+                            &self.global_xf_indices,
+                            &self.global_embedded_image_indices,
+                        )
+                        .as_bytes(),
+                    )?;
+                }
+                Some(start) if chunk[start..].starts_with(b"<row ") => {
+                    output.write_all(&chunk[..start])?;
+                    let tag = String::from_utf8_lossy(&chunk[start..]);
+                    output.write_all(
+                        Self::global_tag_indices(&tag, &self.global_xf_indices, &[]).as_bytes(),
+                    )?;
+                }
+                _ => output.write_all(&chunk)?,
+            }
         }
 
-        attributes.push(("val", data.value()));
-
-        self.writer.xml_empty_tag("customFilter", &attributes);
+        Ok(())
     }
 
-    // Store unique strings in the SST table and convert them to a string id
-    // which is used when writing out the string cells.
-    pub(crate) fn update_string_table_ids(&mut self, string_table: &mut SharedStringsTable) {
-        if !self.uses_string_table {
-            return;
-        }
+    // Convert the local "s" format index and "vm" image index attributes in a
+    // spilled <row> or <c> tag to global indices.
+    fn global_tag_indices(tag: &str, xf_indices: &[u32], image_indices: &[u32]) -> String {
+        let mut converted = String::with_capacity(tag.len() + 4);
 
-        for columns in self.data_table.values_mut() {
-            for cell in columns.values_mut() {
-                match cell {
-                    CellType::String {
-                        string, string_id, ..
-                    }
-                    | CellType::RichString {
-                        string, string_id, ..
-                    } => {
-                        let string_index = string_table.shared_string_index(Arc::clone(string));
-                        *string_id = string_index;
-                    }
-                    _ => {}
+        for (i, attribute) in tag.split(' ').enumerate() {
+            if i > 0 {
+                converted.push(' ');
+            }
+
+            let (name, indices) = if attribute.starts_with("s=\"") {
+                ("s", xf_indices)
+            } else if attribute.starts_with("vm=\"") {
+                ("vm", image_indices)
+            } else {
+                converted.push_str(attribute);
+                continue;
+            };
+
+            let value = &attribute[name.len() + 2..];
+            let end = value.find('"').unwrap_or(value.len());
+            let index = value[..end]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| indices.get(index));
+
+            match index {
+                Some(index) => {
+                    converted.push_str(&format!(r#"{name}="{index}"#));
+                    converted.push_str(&value[end..]);
                 }
+                None => converted.push_str(attribute),
             }
         }
+
+        converted
     }
 
-    // Write out all the row and cell data in the worksheet data table.
-    fn write_data_table(&mut self) {
-        let spans = self.calculate_spans();
+    // Write out the row and cell data in the worksheet data table for a range
+    // of rows. If an output writer is supplied the buffered data is flushed to
+    // it in chunks.
+    fn write_data_table(
+        &mut self,
+        first_row: RowNum,
+        last_row: RowNum,
+        writer: &mut Option<&mut dyn Write>,
+    ) -> io::Result<()> {
+        let spans = self.calculate_row_spans(first_row, last_row);
 
         // Swap out the worksheet data structures so we can iterate over it and
         // still call self.write_xml() methods.
-        let mut temp_table: BTreeMap<RowNum, BTreeMap<ColNum, CellType>> = BTreeMap::new();
+        let mut temp_table: BTreeMap<RowNum, CellRow> = BTreeMap::new();
         let mut temp_changed_rows: HashMap<RowNum, RowOptions> = HashMap::new();
         mem::swap(&mut temp_table, &mut self.data_table);
         mem::swap(&mut temp_changed_rows, &mut self.changed_rows);
 
-        for row_num in self.dimensions.first_row..=self.dimensions.last_row {
+        let mut result = Ok(());
+        for row_num in first_row..=last_row {
             let span_index = row_num / 16;
             let span = spans.get(&span_index).map(AsRef::as_ref);
 
@@ -13569,7 +17605,7 @@ impl Worksheet {
             };
 
             self.write_table_row(row_num, span, row_options, true);
-            for (&col_num, cell) in columns {
+            for (&col_num, cell) in columns.iter() {
                 match cell {
                     CellType::Number { number, xf_index }
                     | CellType::DateTime { number, xf_index } => {
@@ -13577,42 +17613,74 @@ impl Worksheet {
                         self.write_number_cell(row_num, col_num, *number, xf_index);
                     }
                     CellType::String {
+                        string,
                         string_id,
                         xf_index,
-                        ..
+                    } => {
+                        let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
+                        if self.use_inline_strings {
+                            self.write_inline_string_cell(row_num, col_num, string, xf_index);
+                        } else {
+                            self.write_string_cell(row_num, col_num, *string_id, xf_index);
+                        }
                     }
-                    | CellType::RichString {
+                    CellType::RichString {
+                        rich_string,
                         string_id,
                         xf_index,
-                        ..
                     } => {
                         let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
-                        self.write_string_cell(row_num, col_num, *string_id, xf_index);
+                        if self.use_inline_strings {
+                            self.write_inline_string_cell(
+                                row_num,
+                                col_num,
+                                &rich_string.string,
+                                xf_index,
+                            );
+                        } else {
+                            self.write_string_cell(row_num, col_num, *string_id, xf_index);
+                        }
                     }
-                    CellType::Formula {
-                        formula,
-                        xf_index,
-                        result,
-                    } => {
+                    CellType::Formula { formula, xf_index } => {
                         let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
-                        self.write_formula_cell(row_num, col_num, formula, xf_index, result);
+                        self.write_formula_cell(
+                            row_num,
+                            col_num,
+                            &formula.formula,
+                            xf_index,
+                            &formula.result,
+                        );
                     }
                     CellType::ArrayFormula {
                         formula,
                         xf_index,
-                        result,
                         is_dynamic,
-                        range,
                     } => {
                         let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
                         self.write_array_formula_cell(
                             row_num,
                             col_num,
-                            formula,
+                            &formula.formula,
                             xf_index,
-                            result,
+                            &formula.result,
                             *is_dynamic,
-                            range,
+                            formula.range.as_deref().unwrap_or_default(),
+                        );
+                    }
+                    CellType::SharedFormula {
+                        formula,
+                        xf_index,
+                        shared_index,
+                    } => {
+                        let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
+                        self.write_shared_formula_cell(
+                            row_num,
+                            col_num,
+                            &formula.formula,
+                            xf_index,
+                            &formula.result,
+                            *shared_index,
+                            formula.range.as_deref(),
                         );
                     }
                     CellType::Blank { xf_index } => {
@@ -13631,22 +17699,41 @@ impl Worksheet {
                 }
             }
             self.writer.xml_end_tag("row");
+
+            // Flush the row data to the output writer, if required.
+            if let Some(writer) = writer {
+                if let Err(error) = self.writer.flush_if_full(*writer) {
+                    result = Err(error);
+                    break;
+                }
+            }
         }
 
         // Swap back in data.
         mem::swap(&mut temp_table, &mut self.data_table);
         mem::swap(&mut temp_changed_rows, &mut self.changed_rows);
+
+        result
     }
 
     // Calculate the "spans" attribute of the <row> tag. This is an xlsx
     // optimization and isn't strictly required. However, it makes comparing
     // files easier. The span is the same for each block of 16 rows.
+    #[allow(dead_code)] // Used in tests.
     fn calculate_spans(&mut self) -> HashMap<u32, String> {
-        let mut spans: HashMap<RowNum, String> = HashMap::new();
+        self.calculate_row_spans(self.dimensions.first_row, self.dimensions.last_row)
+    }
+
+    // Calculate the spans for a range of rows.
+    fn calculate_row_spans(&mut self, first_row: RowNum, last_row: RowNum) -> HashMap<u32, String> {
+        // Pre-size the span map if there is a row capacity hint.
+        let span_rows = cmp::min(last_row.saturating_sub(first_row), self.row_capacity);
+        let mut spans: HashMap<RowNum, String> =
+            HashMap::with_capacity(span_rows as usize / 16 + 1);
         let mut span_min = COL_MAX;
         let mut span_max = 0;
 
-        for row_num in self.dimensions.first_row..=self.dimensions.last_row {
+        for row_num in first_row..=last_row {
             if let Some(columns) = self.data_table.get(&row_num) {
                 for &col_num in columns.keys() {
                     if span_min == COL_MAX {
@@ -13660,7 +17747,7 @@ impl Worksheet {
             }
 
             // Store the span range for each block or 16 rows.
-            if (row_num + 1) % 16 == 0 || row_num == self.dimensions.last_row {
+            if (row_num + 1) % 16 == 0 || row_num == last_row {
                 let span_index = row_num / 16;
                 if span_min != COL_MAX {
                     span_min += 1;
@@ -13683,43 +17770,50 @@ impl Worksheet {
         row_options: Option<&RowOptions>,
         has_data: bool,
     ) {
-        let row_num = (row_num + 1).to_string();
-        let mut attributes = vec![("r", row_num)];
+        let writer = &mut self.writer;
+
+        writer.xml_raw_string(r#"<row r=""#);
+        writer.xml_integer(row_num + 1);
+        writer.xml_raw_string("\"");
 
         if let Some(span_range) = span {
-            attributes.push(("spans", span_range.to_string()));
+            writer.xml_raw_string(r#" spans=""#);
+            writer.xml_raw_string(span_range);
+            writer.xml_raw_string("\"");
         }
 
         if self.use_x14_extensions {
-            attributes.push(("x14ac:dyDescent", "0.25".to_string()));
+            writer.xml_raw_string(r#" x14ac:dyDescent="0.25""#);
         }
 
         if let Some(row_options) = row_options {
             let xf_index = row_options.xf_index;
 
             if xf_index != 0 {
-                let xf_index = self.global_xf_indices[xf_index as usize];
-                attributes.push(("s", xf_index.to_string()));
-                attributes.push(("customFormat", "1".to_string()));
+                writer.xml_raw_string(r#" s=""#);
+                writer.xml_integer(self.global_xf_indices[xf_index as usize]);
+                writer.xml_raw_string(r#"" customFormat="1""#);
             }
 
             if row_options.height != DEFAULT_ROW_HEIGHT {
-                attributes.push(("ht", row_options.height.to_string()));
+                writer.xml_raw_string(r#" ht=""#);
+                writer.xml_number(row_options.height);
+                writer.xml_raw_string("\"");
             }
 
             if row_options.hidden {
-                attributes.push(("hidden", "1".to_string()));
+                writer.xml_raw_string(r#" hidden="1""#);
             }
 
             if row_options.height != DEFAULT_ROW_HEIGHT {
-                attributes.push(("customHeight", "1".to_string()));
+                writer.xml_raw_string(r#" customHeight="1""#);
             }
         }
 
         if has_data {
-            self.writer.xml_start_tag("row", &attributes);
+            writer.xml_raw_string(">");
         } else {
-            self.writer.xml_empty_tag("row", &attributes);
+            writer.xml_raw_string("/>");
         }
     }
 
@@ -13727,52 +17821,53 @@ impl Worksheet {
     fn write_number_cell(&mut self, row: RowNum, col: ColNum, number: f64, xf_index: u32) {
         let col_name = Self::col_to_name(&mut self.col_names, col);
 
-        if xf_index > 0 {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" s="{}"><v>{}</v></c>"#,
-                col_name,
-                row + 1,
-                xf_index,
-                number
-            )
-            .expect(XML_WRITE_ERROR);
-        } else {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}"><v>{}</v></c>"#,
-                col_name,
-                row + 1,
-                number
-            )
-            .expect(XML_WRITE_ERROR);
-        }
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        self.writer.xml_raw_string("><v>");
+        self.writer.xml_number(number);
+        self.writer.xml_raw_string("</v></c>");
     }
 
     // Write the <c> element for a string.
     fn write_string_cell(&mut self, row: RowNum, col: ColNum, string_index: u32, xf_index: u32) {
         let col_name = Self::col_to_name(&mut self.col_names, col);
 
-        if xf_index > 0 {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" s="{}" t="s"><v>{}</v></c>"#,
-                col_name,
-                row + 1,
-                xf_index,
-                string_index
-            )
-            .expect(XML_WRITE_ERROR);
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        self.writer.xml_raw_string(r#" t="s"><v>"#);
+        self.writer.xml_integer(string_index);
+        self.writer.xml_raw_string("</v></c>");
+    }
+
+    // Write the <c> element for an inline string.
+    fn write_inline_string_cell(&mut self, row: RowNum, col: ColNum, string: &str, xf_index: u32) {
+        let col_name = Self::col_to_name(&mut self.col_names, col);
+
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        self.writer.xml_raw_string(r#" t="inlineStr">"#);
+
+        if string.starts_with("<r>") && string.ends_with("</r>") {
+            self.writer.xml_rich_is_element(string);
         } else {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" t="s"><v>{}</v></c>"#,
-                col_name,
-                row + 1,
-                string_index
-            )
-            .expect(XML_WRITE_ERROR);
+            let preserve_whitespace =
+                string.starts_with(['\t', '\n', ' ']) || string.ends_with(['\t', '\n', ' ']);
+            self.writer.xml_is_element(string, preserve_whitespace);
+        }
+
+        self.writer.xml_end_tag("c");
+    }
+
+    // Write the <v> element and the end of a <c> element for a formula result.
+    fn write_formula_result(&mut self, result: &FormulaResult) {
+        self.writer.xml_raw_string("<v>");
+
+        match result {
+            FormulaResult::Number(number) => self.writer.xml_number(*number),
+            FormulaResult::Boolean(boolean) => self.writer.xml_integer(u8::from(*boolean)),
+            FormulaResult::String(string) | FormulaResult::Error(string) => {
+                self.writer.xml_escaped_data(string)
+            }
         }
+
+        self.writer.xml_raw_string("</v></c>");
     }
 
     // Write the <c> element for a formula.
@@ -13782,33 +17877,17 @@ impl Worksheet {
         col: ColNum,
         formula: &str,
         xf_index: u32,
-        result: &str,
+        result: &FormulaResult,
     ) {
         let col_name = Self::col_to_name(&mut self.col_names, col);
 
-        let style = if xf_index > 0 {
-            format!(r#" s="{xf_index}""#)
-        } else {
-            String::new()
-        };
-
-        let result_type = if result.parse::<f64>().is_err() {
-            r#" t="str""#
-        } else {
-            ""
-        };
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        self.writer.xml_raw_string(result.xml_type());
+        self.writer.xml_raw_string("><f>");
+        self.writer.xml_escaped_data(formula);
+        self.writer.xml_raw_string("</f>");
 
-        write!(
-            &mut self.writer.xmlfile,
-            r#"<c r="{}{}"{}{}><f>{}</f><v>{}</v></c>"#,
-            col_name,
-            row + 1,
-            style,
-            result_type,
-            crate::xmlwriter::escape_xml_data(formula),
-            crate::xmlwriter::escape_xml_data(result),
-        )
-        .expect(XML_WRITE_ERROR);
+        self.write_formula_result(result);
     }
 
     // Write the <c> element for an array formula.
@@ -13819,39 +17898,64 @@ impl Worksheet {
         col: ColNum,
         formula: &str,
         xf_index: u32,
-        result: &str,
+        result: &FormulaResult,
         is_dynamic: bool,
         range: &str,
     ) {
         let col_name = Self::col_to_name(&mut self.col_names, col);
 
-        let style = if xf_index > 0 {
-            format!(r#" s="{xf_index}""#)
-        } else {
-            String::new()
-        };
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        if is_dynamic {
+            self.writer.xml_raw_string(r#" cm="1""#);
+        }
+        self.writer.xml_raw_string(result.xml_type());
+        self.writer.xml_raw_string(r#"><f t="array" ref=""#);
+        self.writer.xml_raw_string(range);
+        self.writer.xml_raw_string(r#"">"#);
+        self.writer.xml_escaped_data(formula);
+        self.writer.xml_raw_string("</f>");
+
+        self.write_formula_result(result);
+    }
 
-        let cm = if is_dynamic { r#" cm="1""# } else { "" };
+    // Write the <c> element for a shared formula. The first cell in the range
+    // has the formula and the range and the other cells only have the shared
+    // index.
+    #[allow(clippy::too_many_arguments)]
+    fn write_shared_formula_cell(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        formula: &str,
+        xf_index: u32,
+        result: &FormulaResult,
+        shared_index: u32,
+        range: Option<&str>,
+    ) {
+        let col_name = Self::col_to_name(&mut self.col_names, col);
 
-        let result_type = if result.parse::<f64>().is_err() {
-            r#" t="str""#
-        } else {
-            ""
-        };
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        self.writer.xml_raw_string(result.xml_type());
+        self.writer.xml_raw_string(r#"><f t="shared""#);
+
+        match range {
+            Some(range) => {
+                self.writer.xml_raw_string(r#" ref=""#);
+                self.writer.xml_raw_string(range);
+                self.writer.xml_raw_string(r#"" si=""#);
+                self.writer.xml_integer(shared_index);
+                self.writer.xml_raw_string(r#"">"#);
+                self.writer.xml_escaped_data(formula);
+                self.writer.xml_raw_string("</f>");
+            }
+            None => {
+                self.writer.xml_raw_string(r#" si=""#);
+                self.writer.xml_integer(shared_index);
+                self.writer.xml_raw_string(r#""/>"#);
+            }
+        }
 
-        write!(
-            &mut self.writer.xmlfile,
-            r#"<c r="{}{}"{}{}{}><f t="array" ref="{}">{}</f><v>{}</v></c>"#,
-            col_name,
-            row + 1,
-            style,
-            cm,
-            result_type,
-            range,
-            crate::xmlwriter::escape_xml_data(formula),
-            crate::xmlwriter::escape_xml_data(result),
-        )
-        .expect(XML_WRITE_ERROR);
+        self.write_formula_result(result);
     }
 
     // Write the <c> element for a blank cell.
@@ -13861,42 +17965,19 @@ impl Worksheet {
         // Write formatted blank cells and ignore unformatted blank cells (like
         // Excel does).
         if xf_index > 0 {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" s="{}"/>"#,
-                col_name,
-                row + 1,
-                xf_index
-            )
-            .expect(XML_WRITE_ERROR);
+            self.writer.xml_cell_start(col_name, row, xf_index);
+            self.writer.xml_raw_string("/>");
         }
     }
 
     // Write the <c> element for a boolean cell.
     fn write_boolean_cell(&mut self, row: RowNum, col: ColNum, boolean: bool, xf_index: u32) {
         let col_name = Self::col_to_name(&mut self.col_names, col);
-        let boolean = i32::from(boolean);
 
-        if xf_index > 0 {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" s="{}" t="b"><v>{}</v></c>"#,
-                col_name,
-                row + 1,
-                xf_index,
-                boolean
-            )
-            .expect(XML_WRITE_ERROR);
-        } else {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" t="b"><v>{}</v></c>"#,
-                col_name,
-                row + 1,
-                boolean
-            )
-            .expect(XML_WRITE_ERROR);
-        }
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        self.writer.xml_raw_string(r#" t="b"><v>"#);
+        self.writer.xml_integer(u8::from(boolean));
+        self.writer.xml_raw_string("</v></c>");
     }
 
     // Write the <c> element for an error cell. We currently only support the
@@ -13904,26 +17985,10 @@ impl Worksheet {
     fn write_error_cell(&mut self, row: RowNum, col: ColNum, value: u32, xf_index: u32) {
         let col_name = Self::col_to_name(&mut self.col_names, col);
 
-        if xf_index > 0 {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" s="{}" t="e" vm="{}"><v>#VALUE!</v></c>"#,
-                col_name,
-                row + 1,
-                xf_index,
-                value
-            )
-            .expect(XML_WRITE_ERROR);
-        } else {
-            write!(
-                &mut self.writer.xmlfile,
-                r#"<c r="{}{}" t="e" vm="{}"><v>#VALUE!</v></c>"#,
-                col_name,
-                row + 1,
-                value
-            )
-            .expect(XML_WRITE_ERROR);
-        }
+        self.writer.xml_cell_start(col_name, row, xf_index);
+        self.writer.xml_raw_string(r#" t="e" vm=""#);
+        self.writer.xml_integer(value);
+        self.writer.xml_raw_string(r#""><v>#VALUE!</v></c>"#);
     }
 
     // Write the <cols> element.
@@ -13935,49 +18000,52 @@ impl Worksheet {
         self.writer.xml_start_tag_only("cols");
 
         // We need to write contiguous equivalent columns as a range with first
-        // and last columns, so we convert the HashMap to a sorted vector and
-        // iterate over that.
-        let mut col_options: Vec<_> = self
-            .changed_cols
-            .iter()
-            .map(|(k, v)| (*k, v.clone()))
-            .collect();
-        col_options.sort_unstable_by_key(|x| x.0);
-        let mut col_options = col_options.into_iter();
-
-        // Remove the first (key, value) tuple in the vector and use it to set
-        // the initial/previous properties.
-        let first_col_options = col_options.next().unwrap();
-        let mut first_col = first_col_options.0;
-        let mut prev_col_options = first_col_options.1;
+        // and last columns, so we convert the HashMap to a sorted vector of
+        // column records and iterate over that. The comparison is done on the
+        // output records rather than the user options so that columns with,
+        // for example, widths that round to the same value or formats that
+        // map to the same style are collapsed into a single span.
+        let mut col_nums: Vec<ColNum> = self.changed_cols.keys().copied().collect();
+        col_nums.sort_unstable();
+
+        let mut col_records = col_nums
+            .into_iter()
+            .map(|col_num| (col_num, self.col_record(&self.changed_cols[&col_num])));
+
+        // Remove the first (key, value) tuple and use it to set the
+        // initial/previous properties.
+        let (mut first_col, mut prev_col_record) = col_records.next().unwrap();
         let mut last_col = first_col;
+        let mut spans = vec![];
 
-        for (col_num, col_options) in col_options {
+        for (col_num, col_record) in col_records {
             // Check if the column number is contiguous with the previous column
-            // and if the format is the same.
-            if col_num == last_col + 1 && col_options == prev_col_options {
+            // and if the record is the same.
+            if col_num == last_col + 1 && col_record == prev_col_record {
                 last_col = col_num;
             } else {
-                // If not write out the current range of columns and start again.
-                self.write_col_element(first_col, last_col, &prev_col_options);
+                // If not store the current range of columns and start again.
+                spans.push((first_col, last_col, prev_col_record));
                 first_col = col_num;
                 last_col = first_col;
-                prev_col_options = col_options;
+                prev_col_record = col_record;
             }
         }
 
         // We will exit the previous loop with one unhandled column range.
-        self.write_col_element(first_col, last_col, &prev_col_options);
+        spans.push((first_col, last_col, prev_col_record));
+
+        for (first_col, last_col, col_record) in spans {
+            self.write_col_element(first_col, last_col, &col_record);
+        }
 
         self.writer.xml_end_tag("cols");
     }
 
-    // Write the <col> element.
-    fn write_col_element(&mut self, first_col: ColNum, last_col: ColNum, col_options: &ColOptions) {
-        let first_col = first_col + 1;
-        let last_col = last_col + 1;
+    // Convert the user column options to the values written to the <col>
+    // element.
+    fn col_record(&self, col_options: &ColOptions) -> ColRecord {
         let mut width = col_options.width;
-        let xf_index = col_options.xf_index;
         let has_custom_width = width != DEFAULT_COL_WIDTH;
         let hidden = col_options.hidden;
 
@@ -14003,26 +18071,44 @@ impl Worksheet {
             }
         }
 
+        let xf_index = match col_options.xf_index {
+            0 => 0,
+            xf_index => self.global_xf_indices[xf_index as usize],
+        };
+
+        ColRecord {
+            width,
+            xf_index,
+            best_fit: col_options.autofit,
+            hidden,
+            custom_width: has_custom_width || hidden,
+        }
+    }
+
+    // Write the <col> element.
+    fn write_col_element(&mut self, first_col: ColNum, last_col: ColNum, col_record: &ColRecord) {
+        let first_col = first_col + 1;
+        let last_col = last_col + 1;
+
         let mut attributes = vec![
             ("min", first_col.to_string()),
             ("max", last_col.to_string()),
-            ("width", width.to_string()),
+            ("width", col_record.width.to_string()),
         ];
 
-        if xf_index > 0 {
-            let xf_index = self.global_xf_indices[xf_index as usize];
-            attributes.push(("style", xf_index.to_string()));
+        if col_record.xf_index > 0 {
+            attributes.push(("style", col_record.xf_index.to_string()));
         }
 
-        if col_options.autofit {
+        if col_record.best_fit {
             attributes.push(("bestFit", "1".to_string()));
         }
 
-        if hidden {
+        if col_record.hidden {
             attributes.push(("hidden", "1".to_string()));
         }
 
-        if has_custom_width || hidden {
+        if col_record.custom_width {
             attributes.push(("customWidth", "1".to_string()));
         }
 
@@ -14100,6 +18186,14 @@ impl Worksheet {
         self.writer.xml_empty_tag("drawing", &attributes);
     }
 
+    // Write the <legacyDrawing> element.
+    fn write_legacy_drawing(&mut self) {
+        self.rel_count += 1;
+        let attributes = [("r:id", format!("rId{}", self.rel_count))];
+
+        self.writer.xml_empty_tag("legacyDrawing", &attributes);
+    }
+
     // Write the <legacyDrawingHF> element.
     fn write_legacy_drawing_hf(&mut self) {
         self.rel_count += 1;
@@ -14141,6 +18235,13 @@ impl Worksheet {
             attributes.push(("password", format!("{:04X}", self.protection_hash)));
         }
 
+        if let Some(password_hash) = &self.protection_password_hash {
+            attributes.push(("algorithmName", "SHA-512".to_string()));
+            attributes.push(("hashValue", password_hash.hash_value.clone()));
+            attributes.push(("saltValue", password_hash.salt_value.clone()));
+            attributes.push(("spinCount", password_hash.spin_count.to_string()));
+        }
+
         attributes.push(("sheet", "1".to_string()));
 
         if !self.protection_options.edit_objects {
@@ -15012,7 +19113,7 @@ impl Default for CellRange {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 struct RowOptions {
     height: f64,
     xf_index: u32,
@@ -15027,14 +19128,22 @@ struct ColOptions {
     autofit: bool,
 }
 
+// The output values of a <col> element, used to collapse identical columns.
+#[derive(PartialEq)]
+struct ColRecord {
+    width: f64,
+    xf_index: u32,
+    best_fit: bool,
+    hidden: bool,
+    custom_width: bool,
+}
+
 #[derive(Clone)]
 enum CellType {
     ArrayFormula {
-        formula: Box<str>,
         xf_index: u32,
-        result: Box<str>,
         is_dynamic: bool,
-        range: Box<str>,
+        formula: Box<FormulaCell>,
     },
     Blank {
         xf_index: u32,
@@ -15048,9 +19157,13 @@ enum CellType {
         value: u32,
     },
     Formula {
-        formula: Box<str>,
         xf_index: u32,
-        result: Box<str>,
+        formula: Box<FormulaCell>,
+    },
+    SharedFormula {
+        xf_index: u32,
+        shared_index: u32,
+        formula: Box<FormulaCell>,
     },
     Number {
         number: f64,
@@ -15066,13 +19179,158 @@ enum CellType {
         string_id: u32,
     },
     RichString {
-        string: Arc<str>,
         xf_index: u32,
-        raw_string: Arc<str>,
         string_id: u32,
+        rich_string: Box<RichStringCell>,
     },
 }
 
+impl CellType {
+    // Get the local format index of the cell.
+    fn xf_index(&self) -> u32 {
+        match self {
+            CellType::ArrayFormula { xf_index, .. }
+            | CellType::Blank { xf_index }
+            | CellType::Boolean { xf_index, .. }
+            | CellType::Error { xf_index, .. }
+            | CellType::Formula { xf_index, .. }
+            | CellType::SharedFormula { xf_index, .. }
+            | CellType::Number { xf_index, .. }
+            | CellType::DateTime { xf_index, .. }
+            | CellType::String { xf_index, .. }
+            | CellType::RichString { xf_index, .. } => *xf_index,
+        }
+    }
+
+    // Get the unformatted string value of a string or rich string cell.
+    fn unformatted_string(&self) -> Option<&str> {
+        match self {
+            CellType::String { string, .. } => Some(string),
+            CellType::RichString { rich_string, .. } => Some(&rich_string.raw_string),
+            _ => None,
+        }
+    }
+}
+
+// The data for the formula cell types. This is boxed in the CellType variants
+// since it is much larger than the data for the more common number and string
+// cells and would otherwise increase the size of every cell. The range is only
+// used for array formulas and for the first cell of a shared formula.
+#[derive(Clone)]
+struct FormulaCell {
+    formula: Box<str>,
+    result: FormulaResult,
+    range: Option<Box<str>>,
+}
+
+// The data for a rich string cell, boxed for the same reason as FormulaCell.
+// The string is the rich string XML and the raw string is the unformatted text.
+#[derive(Clone)]
+struct RichStringCell {
+    string: Arc<str>,
+    raw_string: Box<str>,
+}
+
+// A row of cells in the worksheet data table. The cells are stored in a vector
+// of (column, cell) pairs sorted by column number. This has better locality
+// and lower per-cell overhead than a map and since cells are generally written
+// in column order most inserts are simple appends.
+#[derive(Clone, Default)]
+struct CellRow(Vec<(ColNum, CellType)>);
+
+impl CellRow {
+    // Find the position of a column in the row, or where it should be inserted.
+    fn position(&self, col: ColNum) -> Result<usize, usize> {
+        match self.0.last() {
+            Some((last_col, _)) if *last_col < col => Err(self.0.len()),
+            _ => self.0.binary_search_by_key(&col, |(col, _)| *col),
+        }
+    }
+
+    fn get(&self, col: &ColNum) -> Option<&CellType> {
+        match self.position(*col) {
+            Ok(index) => Some(&self.0[index].1),
+            Err(_) => None,
+        }
+    }
+
+    fn get_mut(&mut self, col: &ColNum) -> Option<&mut CellType> {
+        match self.position(*col) {
+            Ok(index) => Some(&mut self.0[index].1),
+            Err(_) => None,
+        }
+    }
+
+    fn contains_key(&self, col: &ColNum) -> bool {
+        self.position(*col).is_ok()
+    }
+
+    // Insert a cell, replacing any existing cell in the same column.
+    fn insert(&mut self, col: ColNum, cell: CellType) {
+        match self.position(col) {
+            Ok(index) => self.0[index].1 = cell,
+            Err(index) => self.0.insert(index, (col, cell)),
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&ColNum, &CellType)> {
+        self.0.iter().map(|(col, cell)| (col, cell))
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &ColNum> {
+        self.0.iter().map(|(col, _)| col)
+    }
+
+    fn values(&self) -> impl Iterator<Item = &CellType> {
+        self.0.iter().map(|(_, cell)| cell)
+    }
+
+    fn values_mut(&mut self) -> impl Iterator<Item = &mut CellType> {
+        self.0.iter_mut().map(|(_, cell)| cell)
+    }
+}
+
+impl std::ops::Index<&ColNum> for CellRow {
+    type Output = CellType;
+
+    fn index(&self, col: &ColNum) -> &CellType {
+        self.get(col).expect("no cell in column")
+    }
+}
+
+// A temporary file used to store the rows written by flush_rows(). The file is
+// removed when the worksheet is dropped.
+struct SpillFile {
+    file: File,
+    path: PathBuf,
+}
+
+impl SpillFile {
+    fn new() -> io::Result<SpillFile> {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+        let path = env::temp_dir().join(format!(
+            "rust_xlsxwriter_{}_{}.xml",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+
+        Ok(SpillFile { file, path })
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
 #[derive(Clone, Copy)]
 enum PageView {
     Normal,
@@ -15084,11 +19342,42 @@ enum PageView {
 struct Panes {
     freeze_cell: (RowNum, ColNum),
     top_cell: (RowNum, ColNum),
+    split: (f64, f64),
+    frozen_split: bool,
+    active_pane: Option<Pane>,
+    selections: HashMap<Pane, (String, String)>,
 }
 
 impl Panes {
     fn is_empty(&self) -> bool {
-        self.freeze_cell.0 == 0 && self.freeze_cell.1 == 0
+        self.freeze_cell.0 == 0 && self.freeze_cell.1 == 0 && !self.is_split()
+    }
+
+    fn is_split(&self) -> bool {
+        self.split.0 > 0.0 || self.split.1 > 0.0
+    }
+
+    // Get the top left cell of the scrolled area for split panes. If it hasn't
+    // been set explicitly it is estimated from the split positions using the
+    // default row height and column width.
+    fn split_top_left(&self) -> (RowNum, ColNum) {
+        if self.top_cell.0 != 0 || self.top_cell.1 != 0 {
+            return self.top_cell;
+        }
+
+        let row = if self.split.1 > 0.0 {
+            ((self.split.1 - 300.0) / 20.0 / 15.0 + 0.5) as RowNum
+        } else {
+            0
+        };
+
+        let col = if self.split.0 > 0.0 {
+            ((self.split.0 - 390.0) / 20.0 / 3.0 * 4.0 / 64.0 + 0.5) as ColNum
+        } else {
+            0
+        };
+
+        (row, col)
     }
 
     fn top_left(&self) -> String {
@@ -15113,7 +19402,7 @@ struct Hyperlink {
 }
 
 impl Hyperlink {
-    fn new(url: Url) -> Result<Hyperlink, XlsxError> {
+    fn new(url: Url) -> Hyperlink {
         let mut hyperlink = Hyperlink {
             url: url.link,
             text: url.text,
@@ -15126,16 +19415,37 @@ impl Hyperlink {
 
         Self::initialize(&mut hyperlink);
 
-        // Check the hyperlink string lengths are within Excel's limits. The text
-        // length is checked by write_string_with_format().
-        if hyperlink.url.chars().count() > MAX_URL_LEN
-            || hyperlink.location.chars().count() > MAX_URL_LEN
-            || hyperlink.tip.chars().count() > MAX_PARAMETER_LEN
+        hyperlink
+    }
+
+    // Check if the hyperlink string lengths exceed Excel's limits. The text
+    // length is checked by write_string_with_format().
+    fn exceeds_max_len(&self) -> bool {
+        self.url.chars().count() > MAX_URL_LEN
+            || self.location.chars().count() > MAX_URL_LEN
+            || self.tip.chars().count() > MAX_PARAMETER_LEN
+    }
+
+    // Truncate the hyperlink strings to Excel's limits. The url is truncated
+    // before any %xx escape that would be split.
+    fn truncate(&mut self) {
+        fn truncate_chars(string: &mut String, max_len: usize) {
+            if let Some((index, _)) = string.char_indices().nth(max_len) {
+                string.truncate(index);
+            }
+        }
+
+        truncate_chars(&mut self.url, MAX_URL_LEN);
+        if let Some(index) = self
+            .url
+            .get(self.url.len().saturating_sub(2)..)
+            .and_then(|end| end.find('%'))
         {
-            return Err(XlsxError::MaxUrlLengthExceeded);
+            self.url.truncate(self.url.len() - 2 + index);
         }
 
-        Ok(hyperlink)
+        truncate_chars(&mut self.location, MAX_URL_LEN);
+        truncate_chars(&mut self.tip, MAX_PARAMETER_LEN);
     }
 
     // This method handles a variety of different string processing that needs
@@ -15147,9 +19457,12 @@ impl Hyperlink {
             static ref REMOTE_FILE: Regex = Regex::new(r"^(\\\\|\w:)").unwrap();
         }
 
+        let mut is_web_link = false;
+
         if URL.is_match(&self.url) {
             // Handle web links like http://.
             self.link_type = HyperlinkType::Url;
+            is_web_link = true;
 
             if self.text.is_empty() {
                 self.text = self.url.clone();
@@ -15164,6 +19477,7 @@ impl Hyperlink {
         } else if self.url.starts_with("mailto:") {
             // Handle mail address links.
             self.link_type = HyperlinkType::Url;
+            is_web_link = true;
 
             if self.text.is_empty() {
                 self.text = self.url.replacen("mailto:", "", 1);
@@ -15203,6 +19517,12 @@ impl Hyperlink {
         if !URL_ESCAPE.is_match(&self.url) {
             self.url = crate::xmlwriter::escape_url(&self.url).into();
         }
+
+        // Convert any non-ASCII characters in web links. This is done after the
+        // escaping above since it adds %xx escapes.
+        if is_web_link {
+            self.url = crate::url::encode_non_ascii_url(&self.url).into();
+        }
     }
 
     // Increment the ref id
@@ -15390,9 +19710,86 @@ pub(crate) enum DefinedNameType {
     PrintTitles,
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
-pub(crate) enum Visible {
-    Default,
+/// The `SheetVisibility` enum defines the visibility state of a worksheet.
+///
+/// Used with the [`Worksheet::set_visibility()`] method.
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SheetVisibility {
+    /// The worksheet is visible. This is the default.
+    #[default]
+    Visible,
+
+    /// The worksheet is hidden. It can be unhidden by the user in Excel.
     Hidden,
+
+    /// The worksheet is hidden and it can't be unhidden in the Excel user
+    /// interface. It can only be unhidden programmatically using VBA.
     VeryHidden,
 }
+
+/// The `PrintErrorOption` enum defines how cell errors are printed.
+///
+/// Used with the [`Worksheet::set_print_errors_as()`] method.
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PrintErrorOption {
+    /// Print cell errors as they are displayed in Excel. This is the default.
+    #[default]
+    Displayed,
+
+    /// Print cell errors as blank cells.
+    Blank,
+
+    /// Print cell errors as a dash: `--`.
+    Dash,
+
+    /// Print cell errors as `#N/A`.
+    NA,
+}
+
+/// The `Pane` enum defines the panes in a worksheet with frozen or split
+/// panes.
+///
+/// Used with the [`Worksheet::set_active_pane()`] and
+/// [`Worksheet::set_pane_selection()`] methods.
+///
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Pane {
+    /// The top left pane. This is the only pane in a worksheet without
+    /// splits. This is the default.
+    #[default]
+    TopLeft,
+
+    /// The top right pane, when the worksheet is split vertically.
+    TopRight,
+
+    /// The bottom left pane, when the worksheet is split horizontally.
+    BottomLeft,
+
+    /// The bottom right pane, when the worksheet is split both horizontally
+    /// and vertically.
+    BottomRight,
+}
+
+impl Pane {
+    fn name(self) -> &'static str {
+        match self {
+            Pane::TopLeft => "topLeft",
+            Pane::TopRight => "topRight",
+            Pane::BottomLeft => "bottomLeft",
+            Pane::BottomRight => "bottomRight",
+        }
+    }
+}
+
+// Track the page setup options that have been set explicitly for a worksheet
+// so that they aren't overwritten by the workbook default page setup.
+#[derive(Clone, Default)]
+struct PageSetupOverrides {
+    paper_size: bool,
+    orientation: bool,
+    margins: [bool; 6],
+    header: bool,
+    footer: bool,
+}
@@ -7,9 +7,10 @@
 #[cfg(test)]
 mod worksheet_tests {
 
+    use crate::protection::PasswordHash;
     use crate::test_functions::xml_to_vec;
     use crate::worksheet::*;
-    use crate::XlsxError;
+    use crate::{FilterCondition, FilterDateGrouping, TableColumn, XlsxError};
     use pretty_assertions::assert_eq;
     use std::collections::HashMap;
 
@@ -186,6 +187,326 @@ mod worksheet_tests {
         assert!(!worksheet.row_matches_list_filter(5, 0, &filter_condition));
     }
 
+    #[test]
+    fn top10_filter_values() {
+        let mut worksheet = Worksheet::new();
+
+        worksheet.write_string(0, 0, "Header").unwrap();
+        worksheet.write_number(1, 0, 3000).unwrap();
+        worksheet.write_number(2, 0, 8000).unwrap();
+        worksheet.write_number(3, 0, 5000).unwrap();
+        worksheet.write_string(4, 0, "9999").unwrap();
+        worksheet.write_number(5, 0, 4000).unwrap();
+        worksheet.write_number(6, 0, 7000).unwrap();
+        worksheet.write_number(7, 0, 9000).unwrap();
+        worksheet.autofilter(0, 0, 8, 0).unwrap();
+
+        let data = [
+            (FilterTop10::TopItems, 3, 7000.0),
+            (FilterTop10::TopItems, 10, 3000.0),
+            (FilterTop10::BottomItems, 2, 4000.0),
+            (FilterTop10::TopPercent, 50, 7000.0),
+            (FilterTop10::BottomPercent, 10, 3000.0),
+        ];
+
+        for (top10, value, expected) in data {
+            let filter_condition = FilterCondition::new().add_top10_filter(top10, value);
+            let got = worksheet.top10_filter_value(0, &filter_condition);
+
+            assert_eq!(Some(expected), got);
+        }
+
+        // Test the rows that match a "Top 3" filter with a cut off of 7000.
+        let top10 = FilterTop10::TopItems;
+        assert!(!worksheet.row_matches_top10_filter(1, 0, top10, Some(7000.0)));
+        assert!(worksheet.row_matches_top10_filter(2, 0, top10, Some(7000.0)));
+        assert!(!worksheet.row_matches_top10_filter(3, 0, top10, Some(7000.0)));
+        assert!(!worksheet.row_matches_top10_filter(4, 0, top10, Some(7000.0)));
+        assert!(!worksheet.row_matches_top10_filter(5, 0, top10, Some(7000.0)));
+        assert!(worksheet.row_matches_top10_filter(6, 0, top10, Some(7000.0)));
+        assert!(worksheet.row_matches_top10_filter(7, 0, top10, Some(7000.0)));
+        assert!(!worksheet.row_matches_top10_filter(8, 0, top10, Some(7000.0)));
+
+        // Test the rows that match a "Bottom 2" filter with a cut off of 4000.
+        let top10 = FilterTop10::BottomItems;
+        assert!(worksheet.row_matches_top10_filter(1, 0, top10, Some(4000.0)));
+        assert!(!worksheet.row_matches_top10_filter(2, 0, top10, Some(4000.0)));
+        assert!(worksheet.row_matches_top10_filter(5, 0, top10, Some(4000.0)));
+    }
+
+    #[test]
+    fn top10_filter_xml() {
+        let mut worksheet = Worksheet::new();
+
+        worksheet.write_string(0, 1, "Sales").unwrap();
+        worksheet.write_number(1, 1, 3000).unwrap();
+        worksheet.write_number(2, 1, 8000).unwrap();
+        worksheet.write_number(3, 1, 5000).unwrap();
+        worksheet.autofilter(0, 0, 3, 1).unwrap();
+
+        let filter_condition =
+            FilterCondition::new().add_top10_filter(FilterTop10::BottomPercent, 40);
+        worksheet.filter_column(1, &filter_condition).unwrap();
+
+        worksheet.write_auto_filter();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <autoFilter ref="A1:B4">
+              <filterColumn colId="1">
+                <top10 top="0" percent="1" val="40" filterVal="3000"/>
+              </filterColumn>
+            </autoFilter>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn top10_filter_errors() {
+        let mut worksheet = Worksheet::new();
+        worksheet.autofilter(0, 0, 3, 1).unwrap();
+
+        let data = [
+            (FilterTop10::TopItems, 0),
+            (FilterTop10::TopItems, 501),
+            (FilterTop10::BottomPercent, 0),
+            (FilterTop10::BottomPercent, 101),
+        ];
+
+        for (top10, value) in data {
+            let filter_condition = FilterCondition::new().add_top10_filter(top10, value);
+            let result = worksheet.filter_column(1, &filter_condition);
+            assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+        }
+    }
+
+    #[test]
+    fn row_matches_color_filter() {
+        let mut worksheet = Worksheet::new();
+        let yellow_fill = Format::new().set_background_color(Color::Yellow);
+        let yellow_solid = Format::new()
+            .set_pattern(FormatPattern::Solid)
+            .set_foreground_color(Color::Yellow);
+        let red_font = Format::new().set_font_color(Color::Red);
+
+        worksheet.write_number(1, 0, 1).unwrap();
+        worksheet
+            .write_number_with_format(2, 0, 2, &yellow_fill)
+            .unwrap();
+        worksheet
+            .write_number_with_format(3, 0, 3, &yellow_solid)
+            .unwrap();
+        worksheet
+            .write_number_with_format(4, 0, 4, &red_font)
+            .unwrap();
+        worksheet.write_blank(5, 0, &yellow_fill).unwrap();
+
+        let fill_filter = Format::new().set_background_color(Color::Yellow);
+        assert!(!worksheet.row_matches_color_filter(1, 0, &fill_filter));
+        assert!(worksheet.row_matches_color_filter(2, 0, &fill_filter));
+        assert!(worksheet.row_matches_color_filter(3, 0, &fill_filter));
+        assert!(!worksheet.row_matches_color_filter(4, 0, &fill_filter));
+        assert!(worksheet.row_matches_color_filter(5, 0, &fill_filter));
+        assert!(!worksheet.row_matches_color_filter(6, 0, &fill_filter));
+
+        let font_filter = Format::new().set_font_color(Color::Red);
+        assert!(!worksheet.row_matches_color_filter(1, 0, &font_filter));
+        assert!(!worksheet.row_matches_color_filter(2, 0, &font_filter));
+        assert!(worksheet.row_matches_color_filter(4, 0, &font_filter));
+    }
+
+    #[test]
+    fn color_filter_xml() {
+        let mut worksheet = Worksheet::new();
+        worksheet.autofilter(0, 0, 3, 2).unwrap();
+
+        let filter_condition = FilterCondition::new().add_cell_color_filter(Color::Yellow);
+        worksheet.filter_column(0, &filter_condition).unwrap();
+
+        let filter_condition = FilterCondition::new().add_font_color_filter(Color::Red);
+        worksheet.filter_column(1, &filter_condition).unwrap();
+
+        let filter_condition =
+            FilterCondition::new().add_icon_filter(ConditionalFormatIconType::ThreeArrows, 2);
+        worksheet.filter_column(2, &filter_condition).unwrap();
+
+        worksheet.set_global_dxf_indices(&[3, 4]);
+        worksheet.write_auto_filter();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <autoFilter ref="A1:C4">
+              <filterColumn colId="0">
+                <colorFilter dxfId="3"/>
+              </filterColumn>
+              <filterColumn colId="1">
+                <colorFilter dxfId="4" cellColor="0"/>
+              </filterColumn>
+              <filterColumn colId="2">
+                <iconFilter iconSet="3Arrows" iconId="2"/>
+              </filterColumn>
+            </autoFilter>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn icon_filter_errors() {
+        let mut worksheet = Worksheet::new();
+        worksheet.autofilter(0, 0, 3, 1).unwrap();
+
+        let data = [
+            (ConditionalFormatIconType::ThreeFlags, 3),
+            (ConditionalFormatIconType::FourArrows, 4),
+            (ConditionalFormatIconType::FiveBoxes, 5),
+        ];
+
+        for (icon_type, icon_index) in data {
+            let filter_condition = FilterCondition::new().add_icon_filter(icon_type, icon_index);
+            let result = worksheet.filter_column(1, &filter_condition);
+            assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+        }
+
+        let filter_condition =
+            FilterCondition::new().add_icon_filter(ConditionalFormatIconType::FiveBoxes, 4);
+        let result = worksheet.filter_column(1, &filter_condition);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn color_filter_errors() {
+        let mut worksheet = Worksheet::new();
+        worksheet.autofilter(0, 0, 3, 1).unwrap();
+
+        let filter_condition = FilterCondition::new().add_cell_color_filter(Color::RGB(0x1000000));
+        let result = worksheet.filter_column(1, &filter_condition);
+        assert!(matches!(result, Err(XlsxError::ParameterError(message))
+            if message.starts_with("Invalid color filter")));
+
+        let filter_condition = FilterCondition::new().add_font_color_filter(Color::Theme(10, 0));
+        let result = worksheet.filter_column(1, &filter_condition);
+        assert!(matches!(result, Err(XlsxError::ParameterError(message))
+            if message.starts_with("Invalid color filter")));
+
+        // A later valid filter replaces the invalid color.
+        let filter_condition = FilterCondition::new()
+            .add_cell_color_filter(Color::RGB(0x1000000))
+            .add_top10_filter(FilterTop10::TopItems, 3);
+        let result = worksheet.filter_column(1, &filter_condition);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn special_filters_replace_each_other() {
+        let filter_condition = FilterCondition::new()
+            .add_top10_filter(FilterTop10::TopItems, 3)
+            .add_cell_color_filter(Color::Yellow)
+            .add_icon_filter(ConditionalFormatIconType::ThreeArrows, 2);
+
+        assert!(filter_condition.top10.is_none());
+        assert!(filter_condition.color_format.is_none());
+        assert!(filter_condition.icon.is_some());
+
+        let filter_condition = FilterCondition::new()
+            .add_icon_filter(ConditionalFormatIconType::ThreeArrows, 2)
+            .add_font_color_filter(Color::Red)
+            .add_top10_filter(FilterTop10::BottomPercent, 10);
+
+        assert!(filter_condition.icon.is_none());
+        assert!(filter_condition.color_format.is_none());
+        assert!(filter_condition.top10.is_some());
+    }
+
+    #[test]
+    fn row_matches_list_filter_dates() {
+        let mut worksheet = Worksheet::new();
+
+        let dates = [(2023, 5, 12), (2024, 3, 1), (2024, 3, 15), (2024, 4, 2)];
+
+        worksheet.write_string(0, 0, "Header").unwrap();
+        for (row, (year, month, day)) in dates.into_iter().enumerate() {
+            let date = ExcelDateTime::from_ymd(year, month, day).unwrap();
+            worksheet.write_datetime(row as u32 + 1, 0, &date).unwrap();
+        }
+        worksheet.write_number(5, 0, 45_352).unwrap(); // 2024-03-01 as a number.
+        worksheet.write_string(6, 0, "2024-03-01").unwrap();
+
+        let filter_condition = FilterCondition::new()
+            .add_list_date_filter(
+                &ExcelDateTime::from_ymd(2024, 3, 31).unwrap(),
+                FilterDateGrouping::Month,
+            )
+            .add_list_date_filter(
+                &ExcelDateTime::from_ymd(2023, 1, 1).unwrap(),
+                FilterDateGrouping::Year,
+            );
+
+        assert!(worksheet.row_matches_list_filter(1, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(2, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
+        assert!(!worksheet.row_matches_list_filter(4, 0, &filter_condition));
+        assert!(!worksheet.row_matches_list_filter(5, 0, &filter_condition));
+        assert!(!worksheet.row_matches_list_filter(6, 0, &filter_condition));
+
+        let filter_condition = FilterCondition::new().add_list_date_filter(
+            &ExcelDateTime::from_ymd(2024, 3, 15).unwrap(),
+            FilterDateGrouping::Day,
+        );
+
+        assert!(!worksheet.row_matches_list_filter(2, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
+    }
+
+    #[test]
+    fn date_group_filter_xml() {
+        let mut worksheet = Worksheet::new();
+        worksheet.autofilter(0, 0, 3, 1).unwrap();
+
+        let datetime = ExcelDateTime::from_ymd(2024, 3, 15)
+            .unwrap()
+            .and_hms(13, 45, 30)
+            .unwrap();
+
+        let filter_condition = FilterCondition::new()
+            .add_list_filter("East")
+            .add_list_date_filter(&datetime, FilterDateGrouping::Year)
+            .add_list_date_filter(&datetime, FilterDateGrouping::Day)
+            .add_list_date_filter(&datetime, FilterDateGrouping::Second);
+        worksheet.filter_column(0, &filter_condition).unwrap();
+
+        worksheet.write_auto_filter();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <autoFilter ref="A1:B4">
+              <filterColumn colId="0">
+                <filters>
+                  <filter val="East"/>
+                  <dateGroupItem year="2024" dateTimeGrouping="year"/>
+                  <dateGroupItem year="2024" month="3" day="15" dateTimeGrouping="day"/>
+                  <dateGroupItem year="2024" month="3" day="15" hour="13" minute="45" second="30" dateTimeGrouping="second"/>
+                </filters>
+              </filterColumn>
+            </autoFilter>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
     #[test]
     fn process_pagebreaks() {
         let mut worksheet = Worksheet::new();
@@ -227,6 +548,29 @@ mod worksheet_tests {
         assert!(matches!(result, Err(XlsxError::ParameterError(_))));
     }
 
+    #[test]
+    fn validate_formulas() {
+        let mut worksheet = Worksheet::new();
+
+        worksheet.write_formula(0, 0, "=SUM(B1:B10)").unwrap();
+        worksheet.write_formula(1, 0, "=SUM(B1:B10").unwrap();
+        worksheet
+            .write_array_formula(2, 0, 2, 0, "{=SUMM(B1:B10*C1:C10)}")
+            .unwrap();
+        worksheet
+            .write_dynamic_formula(3, 0, "=SORT(B1:B0)")
+            .unwrap();
+
+        let errors = worksheet.validate_formulas();
+        let cells: Vec<(RowNum, ColNum)> =
+            errors.iter().map(|(row, col, _)| (*row, *col)).collect();
+
+        assert_eq!(cells, vec![(1, 0), (2, 0), (3, 0)]);
+        assert!(errors
+            .iter()
+            .all(|(_, _, error)| matches!(error, XlsxError::FormulaError(_))));
+    }
+
     #[test]
     fn rich_string() {
         let mut worksheet = Worksheet::new();
@@ -690,4 +1034,1352 @@ mod worksheet_tests {
         let result = worksheet.write_string(0, 0, long_string.unwrap());
         assert!(matches!(result, Err(XlsxError::MaxStringLengthExceeded)));
     }
+
+    #[test]
+    fn split_panes() {
+        let test_data = [
+            (
+                0.0,
+                15.0,
+                r#"<pane ySplit="600" topLeftCell="A2" activePane="bottomLeft"/>
+                   <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>"#,
+            ),
+            (
+                8.43,
+                0.0,
+                r#"<pane xSplit="1350" topLeftCell="B1" activePane="topRight"/>
+                   <selection pane="topRight" activeCell="B1" sqref="B1"/>"#,
+            ),
+            (
+                8.43,
+                15.0,
+                r#"<pane xSplit="1350" ySplit="600" topLeftCell="B2" activePane="bottomRight"/>
+                   <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                   <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                   <selection pane="bottomRight" activeCell="B2" sqref="B2"/>"#,
+            ),
+            (
+                43.0,
+                45.0,
+                r#"<pane xSplit="4980" ySplit="1200" topLeftCell="F4" activePane="bottomRight"/>
+                   <selection pane="topRight" activeCell="F1" sqref="F1"/>
+                   <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
+                   <selection pane="bottomRight" activeCell="F4" sqref="F4"/>"#,
+            ),
+        ];
+
+        for (x, y, panes) in test_data {
+            let mut worksheet = Worksheet::new();
+            worksheet.set_split_panes(x, y);
+            worksheet.write_sheet_views();
+
+            let got = worksheet.writer.read_to_str();
+            let got = xml_to_vec(got);
+
+            let expected = xml_to_vec(&format!(
+                r#"<sheetViews><sheetView workbookViewId="0">{panes}</sheetView></sheetViews>"#
+            ));
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn split_panes_with_selection() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_split_panes(8.43, 15);
+        worksheet.set_freeze_panes_top_cell(5, 5).unwrap();
+        worksheet.set_selection(6, 6, 7, 7).unwrap();
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+              <sheetView workbookViewId="0">
+                <pane xSplit="1350" ySplit="600" topLeftCell="F6" activePane="bottomRight"/>
+                <selection pane="topRight" activeCell="F1" sqref="F1"/>
+                <selection pane="bottomLeft" activeCell="A6" sqref="A6"/>
+                <selection pane="bottomRight" activeCell="G7" sqref="G7:H8"/>
+              </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn zoom_per_view() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_zoom(200);
+        worksheet.write_sheet_view();
+
+        let got = worksheet.writer.read_to_str();
+        let expected = r#"<sheetView zoomScale="200" zoomScaleNormal="200" workbookViewId="0"/>"#;
+        assert_eq!(expected, got);
+
+        let mut worksheet = Worksheet::new();
+        worksheet.set_zoom_normal(150);
+        worksheet.set_zoom_page_layout(75);
+        worksheet.set_zoom_page_break_preview(60);
+        worksheet.write_sheet_view();
+
+        let got = worksheet.writer.read_to_str();
+        let expected = r#"<sheetView zoomScale="150" zoomScaleNormal="150" zoomScaleSheetLayoutView="60" zoomScalePageLayoutView="75" workbookViewId="0"/>"#;
+        assert_eq!(expected, got);
+
+        let mut worksheet = Worksheet::new();
+        worksheet.set_view_page_layout();
+        worksheet.set_zoom(200);
+        worksheet.set_zoom_normal(80);
+        worksheet.write_sheet_view();
+
+        let got = worksheet.writer.read_to_str();
+        let expected = r#"<sheetView view="pageLayout" zoomScale="200" zoomScaleNormal="80" zoomScalePageLayoutView="200" workbookViewId="0"/>"#;
+        assert_eq!(expected, got);
+
+        let mut worksheet = Worksheet::new();
+        worksheet.set_view_page_break_preview();
+        worksheet.set_zoom(200);
+        worksheet.set_zoom_page_break_preview(90);
+        worksheet.set_zoom_normal(5);
+        worksheet.write_sheet_view();
+
+        let got = worksheet.writer.read_to_str();
+        let expected = r#"<sheetView view="pageBreakPreview" zoomScale="90" zoomScaleSheetLayoutView="90" workbookViewId="0"/>"#;
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn freeze_helpers() {
+        let mut worksheet1 = Worksheet::new();
+        let mut worksheet2 = Worksheet::new();
+        worksheet1.freeze_top_row();
+        worksheet2.set_freeze_panes(1, 0).unwrap();
+        worksheet1.write_sheet_views();
+        worksheet2.write_sheet_views();
+        assert_eq!(
+            worksheet2.writer.read_to_str(),
+            worksheet1.writer.read_to_str()
+        );
+
+        let mut worksheet1 = Worksheet::new();
+        let mut worksheet2 = Worksheet::new();
+        worksheet1.set_split_panes(8.43, 15);
+        worksheet1.freeze_first_column();
+        worksheet2.set_freeze_panes(0, 1).unwrap();
+        worksheet1.write_sheet_views();
+        worksheet2.write_sheet_views();
+        assert_eq!(
+            worksheet2.writer.read_to_str(),
+            worksheet1.writer.read_to_str()
+        );
+
+        let mut worksheet1 = Worksheet::new();
+        let mut worksheet2 = Worksheet::new();
+        worksheet1.freeze_rows_and_columns(2, 3).unwrap();
+        worksheet2.set_freeze_panes(2, 3).unwrap();
+        worksheet1.write_sheet_views();
+        worksheet2.write_sheet_views();
+        assert_eq!(
+            worksheet2.writer.read_to_str(),
+            worksheet1.writer.read_to_str()
+        );
+
+        let mut worksheet = Worksheet::new();
+        let result = worksheet.freeze_rows_and_columns(2, 16_384);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    #[test]
+    fn freeze_panes_active_pane() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_freeze_panes(1, 1).unwrap();
+        worksheet.set_active_pane(Pane::TopRight);
+        worksheet
+            .set_pane_selection(Pane::TopRight, 0, 5, 0, 3)
+            .unwrap();
+        worksheet
+            .set_pane_selection(Pane::BottomRight, 9, 3, 9, 3)
+            .unwrap();
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+              <sheetView workbookViewId="0">
+                <pane xSplit="1" ySplit="1" topLeftCell="B2" activePane="topRight" state="frozen"/>
+                <selection pane="topRight" activeCell="F1" sqref="D1:F1"/>
+                <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                <selection pane="bottomRight" activeCell="D10" sqref="D10"/>
+              </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn frozen_split_panes() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_frozen_split_panes(1, 1).unwrap();
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+              <sheetView workbookViewId="0">
+                <pane xSplit="1" ySplit="1" topLeftCell="B2" activePane="bottomRight" state="frozenSplit"/>
+                <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                <selection pane="bottomRight"/>
+              </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // A subsequent freeze pane resets the state.
+        let mut worksheet = Worksheet::new();
+        worksheet.set_frozen_split_panes(1, 0).unwrap();
+        worksheet.freeze_top_row();
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        assert!(got.contains(r#"state="frozen""#));
+
+        let mut worksheet = Worksheet::new();
+        let result = worksheet.set_frozen_split_panes(1_048_576, 0);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    #[test]
+    fn freeze_panes_top_left_selection() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_freeze_panes(2, 0).unwrap();
+        worksheet.set_active_pane(Pane::TopLeft);
+        worksheet
+            .set_pane_selection(Pane::TopLeft, 0, 1, 1, 1)
+            .unwrap();
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+              <sheetView workbookViewId="0">
+                <pane ySplit="2" topLeftCell="A3" activePane="topLeft" state="frozen"/>
+                <selection pane="bottomLeft"/>
+                <selection pane="topLeft" activeCell="B1" sqref="B1:B2"/>
+              </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn freeze_panes_invalid_active_pane() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_freeze_panes(0, 3).unwrap();
+        worksheet.set_active_pane(Pane::BottomLeft);
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+              <sheetView workbookViewId="0">
+                <pane xSplit="3" topLeftCell="D1" activePane="topRight" state="frozen"/>
+                <selection pane="topRight"/>
+              </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn split_panes_active_pane() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_split_panes_pixels(64, 20);
+        worksheet.set_active_pane(Pane::BottomLeft);
+        worksheet
+            .set_pane_selection(Pane::BottomLeft, 4, 0, 4, 0)
+            .unwrap();
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+              <sheetView workbookViewId="0">
+                <pane xSplit="1350" ySplit="600" topLeftCell="B2" activePane="bottomLeft"/>
+                <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                <selection pane="bottomLeft" activeCell="A5" sqref="A5"/>
+                <selection pane="bottomRight" activeCell="B2" sqref="B2"/>
+              </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn split_panes_pixels() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_split_panes_pixels(64, 20);
+        worksheet.write_sheet_views();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+              <sheetView workbookViewId="0">
+                <pane xSplit="1350" ySplit="600" topLeftCell="B2" activePane="bottomRight"/>
+                <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                <selection pane="bottomRight" activeCell="B2" sqref="B2"/>
+              </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn print_resolution() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_print_resolution(600);
+        worksheet.write_page_setup();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"<pageSetup orientation="portrait" horizontalDpi="600" verticalDpi="600"/>"#,
+        );
+
+        assert_eq!(expected, got);
+
+        // Invalid values are ignored.
+        let mut worksheet = Worksheet::new();
+        worksheet.set_print_resolution(0);
+        worksheet.write_page_setup();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"<pageSetup orientation="portrait" horizontalDpi="200" verticalDpi="200"/>"#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn default_page_setup() {
+        let page_setup = PageSetup::new()
+            .set_paper_size(9)
+            .set_landscape()
+            .set_margins(0.5, 0.5, 1.0, 1.0, -1.0, -1.0)
+            .set_header("&CReport")
+            .set_footer("&CDefault");
+
+        // Worksheet with no explicit page setup.
+        let mut worksheet = Worksheet::new();
+        worksheet.apply_default_page_setup(&page_setup);
+        worksheet.write_page_margins();
+        worksheet.write_page_setup();
+        worksheet.write_header_footer();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <pageMargins left="0.5" right="0.5" top="1" bottom="1" header="0.3" footer="0.3"/>
+            <pageSetup paperSize="9" orientation="landscape" horizontalDpi="200" verticalDpi="200"/>
+            <headerFooter>
+              <oddHeader>&amp;CReport</oddHeader>
+              <oddFooter>&amp;CDefault</oddFooter>
+            </headerFooter>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // Worksheet with options that override the defaults.
+        let mut worksheet = Worksheet::new();
+        worksheet.set_portrait();
+        worksheet.set_margins(-1.0, -1.0, 2.0, -1.0, -1.0, -1.0);
+        worksheet.set_footer("&CMine");
+        worksheet.apply_default_page_setup(&page_setup);
+        worksheet.write_page_margins();
+        worksheet.write_page_setup();
+        worksheet.write_header_footer();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <pageMargins left="0.5" right="0.5" top="2" bottom="1" header="0.3" footer="0.3"/>
+            <pageSetup paperSize="9" orientation="portrait" horizontalDpi="200" verticalDpi="200"/>
+            <headerFooter>
+              <oddHeader>&amp;CReport</oddHeader>
+              <oddFooter>&amp;CMine</oddFooter>
+            </headerFooter>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn screen_gridlines_color() {
+        let test_data = [
+            (Color::Silver, r#" defaultGridColor="0" colorId="22""#),
+            (
+                Color::RGB(0xC8C8C8),
+                r#" defaultGridColor="0" colorId="22""#,
+            ),
+            (Color::Red, r#" defaultGridColor="0" colorId="10""#),
+            (Color::Default, ""),
+            (Color::Theme(1, 0), ""),
+        ];
+
+        for (color, attributes) in test_data {
+            let mut worksheet = Worksheet::new();
+            worksheet.set_screen_gridlines_color(color);
+            worksheet.write_sheet_view();
+
+            let got = worksheet.writer.read_to_str();
+            let got = xml_to_vec(got);
+
+            let expected = xml_to_vec(&format!(r#"<sheetView{attributes} workbookViewId="0"/>"#));
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn set_visibility() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_active(true);
+        assert!(worksheet.active && worksheet.selected);
+
+        worksheet.set_visibility(SheetVisibility::VeryHidden);
+        assert_eq!(worksheet.visible, SheetVisibility::VeryHidden);
+        assert!(!worksheet.active && !worksheet.selected);
+
+        worksheet.set_hidden(false);
+        assert_eq!(worksheet.visible, SheetVisibility::Visible);
+
+        worksheet.set_visibility(SheetVisibility::Hidden);
+        worksheet.set_selected(true);
+        assert_eq!(worksheet.visible, SheetVisibility::Visible);
+    }
+
+    #[test]
+    fn sheet_state_getters() {
+        let mut worksheet = Worksheet::new();
+        assert!(!worksheet.is_active());
+        assert!(!worksheet.is_hidden());
+        assert_eq!(worksheet.visibility(), SheetVisibility::Visible);
+        assert_eq!(worksheet.tab_color(), Color::Default);
+
+        worksheet.set_active(true);
+        worksheet.set_tab_color(Color::Red);
+        assert!(worksheet.is_active());
+        assert_eq!(worksheet.tab_color(), Color::Red);
+
+        worksheet.set_very_hidden(true);
+        assert!(!worksheet.is_active());
+        assert!(worksheet.is_hidden());
+        assert_eq!(worksheet.visibility(), SheetVisibility::VeryHidden);
+    }
+
+    #[test]
+    fn vba_name() {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_vba_name("MySheet").unwrap();
+        worksheet.write_sheet_pr();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+        let expected = xml_to_vec(r#"<sheetPr codeName="MySheet"/>"#);
+
+        assert_eq!(expected, got);
+
+        let mut worksheet = Worksheet::new();
+        worksheet.set_vba_name("MySheet").unwrap();
+        worksheet.set_tab_color(Color::Red);
+        worksheet.write_sheet_pr();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+        let expected =
+            xml_to_vec(r#"<sheetPr codeName="MySheet"><tabColor rgb="FFFF0000"/></sheetPr>"#);
+
+        assert_eq!(expected, got);
+
+        let result = worksheet.set_vba_name("My Sheet");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[test]
+    fn remove_internal_hyperlinks() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write_url(0, 0, "internal:'My Data'!A1").unwrap();
+        worksheet.write_url(1, 0, "internal:Sheet3!A1").unwrap();
+        worksheet
+            .write_url(2, 0, "https://www.rust-lang.org")
+            .unwrap();
+        worksheet.write_url(3, 0, "internal:'my data'!B2").unwrap();
+
+        worksheet.remove_internal_hyperlinks("My Data");
+
+        let cells: Vec<(RowNum, ColNum)> = worksheet.hyperlinks.keys().copied().collect();
+        assert_eq!(cells, vec![(1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn table_total_format() {
+        let bold = Format::new().set_bold();
+        let currency = Format::new().set_num_format("$#,##0");
+        let italic = Format::new().set_italic();
+
+        let mut worksheet = Worksheet::new();
+        let columns = vec![
+            TableColumn::new()
+                .set_total_label("Totals")
+                .set_total_format(&bold),
+            TableColumn::new()
+                .set_total_function(TableFunction::Sum)
+                .set_format(&italic)
+                .set_total_format(&currency),
+            TableColumn::new()
+                .set_total_function(TableFunction::Sum)
+                .set_format(&italic),
+        ];
+        let table = Table::new().set_columns(&columns).set_total_row(true);
+        worksheet.add_table(0, 0, 4, 2, &table).unwrap();
+
+        let bold_index = worksheet.format_xf_index(&bold);
+        let currency_index = worksheet.format_xf_index(&currency);
+        let italic_index = worksheet.format_xf_index(&italic);
+
+        let total_row = &worksheet.data_table[&4];
+        assert!(matches!(
+            total_row.get(&0),
+            Some(CellType::String { xf_index, .. }) if *xf_index == bold_index
+        ));
+        assert!(matches!(
+            total_row.get(&1),
+            Some(CellType::Formula { xf_index, .. }) if *xf_index == currency_index
+        ));
+        assert!(matches!(
+            total_row.get(&2),
+            Some(CellType::Formula { xf_index, .. }) if *xf_index == italic_index
+        ));
+    }
+
+    #[test]
+    fn url_non_ascii() {
+        let mut worksheet = Worksheet::new();
+        worksheet
+            .write_url(0, 0, "https://bücher.example:8080/straße?q=ü#Ä")
+            .unwrap();
+        worksheet
+            .write_url(1, 0, "http://user@münchen.例え.jp/a b")
+            .unwrap();
+        worksheet
+            .write_url(2, 0, "mailto:jürgen@example.com")
+            .unwrap();
+
+        let hyperlink = &worksheet.hyperlinks[&(0, 0)];
+        assert_eq!(
+            hyperlink.url,
+            "https://xn--bcher-kva.example:8080/stra%C3%9Fe?q=%C3%BC"
+        );
+        assert_eq!(hyperlink.location, "Ä");
+        assert_eq!(hyperlink.text, "https://bücher.example:8080/straße?q=ü#Ä");
+
+        let hyperlink = &worksheet.hyperlinks[&(1, 0)];
+        assert_eq!(
+            hyperlink.url,
+            "http://user@xn--mnchen-3ya.xn--r8jz45g.jp/a%20b"
+        );
+
+        let hyperlink = &worksheet.hyperlinks[&(2, 0)];
+        assert_eq!(hyperlink.url, "mailto:j%C3%BCrgen@example.com");
+    }
+
+    #[test]
+    fn url_helpers() {
+        let mut worksheet = Worksheet::new();
+        worksheet
+            .write(
+                0,
+                0,
+                Url::mailto("jo@example.com")
+                    .set_subject("Q3 Sales & Forecast")
+                    .set_body("Line 1\nLine 2")
+                    .set_cc("a@example.com,b@example.com"),
+            )
+            .unwrap();
+        worksheet
+            .write(1, 0, Url::file(r"C:\Sales Data\Q3.xlsx"))
+            .unwrap();
+        worksheet
+            .write(2, 0, Url::file(r"\\server\share\Q3.xlsx#Sheet2!A1"))
+            .unwrap();
+        worksheet.write(3, 0, Url::file("Reports/Q3.xlsx")).unwrap();
+        worksheet
+            .write(4, 0, Url::file("/home/jo/Q3.xlsx"))
+            .unwrap();
+        worksheet
+            .write(5, 0, Url::new("https://example.com").set_subject("Ignored"))
+            .unwrap();
+
+        let hyperlink = &worksheet.hyperlinks[&(0, 0)];
+        assert_eq!(
+            hyperlink.url,
+            "mailto:jo@example.com?subject=Q3%20Sales%20%26%20Forecast&body=Line%201%0ALine%202&cc=a@example.com,b@example.com"
+        );
+        assert_eq!(hyperlink.text, "jo@example.com");
+
+        let hyperlink = &worksheet.hyperlinks[&(1, 0)];
+        assert_eq!(hyperlink.url, r"file:///C:\Sales%20Data\Q3.xlsx");
+        assert_eq!(hyperlink.text, r"C:\Sales Data\Q3.xlsx");
+
+        let hyperlink = &worksheet.hyperlinks[&(2, 0)];
+        assert_eq!(hyperlink.url, r"file:///\\server\share\Q3.xlsx");
+        assert_eq!(hyperlink.location, "Sheet2!A1");
+
+        let hyperlink = &worksheet.hyperlinks[&(3, 0)];
+        assert_eq!(hyperlink.url, "Reports/Q3.xlsx");
+
+        let hyperlink = &worksheet.hyperlinks[&(4, 0)];
+        assert_eq!(hyperlink.url, "/home/jo/Q3.xlsx");
+
+        let hyperlink = &worksheet.hyperlinks[&(5, 0)];
+        assert_eq!(hyperlink.url, "https://example.com");
+    }
+
+    #[test]
+    fn url_length_policy() {
+        let long_url = format!("https://www.example.com/{}", "%20".repeat(700));
+        let long_tip = "a".repeat(300);
+
+        let mut worksheet = Worksheet::new();
+        let result = worksheet.write_url(0, 0, long_url.as_str());
+        assert!(matches!(result, Err(XlsxError::MaxUrlLengthExceeded)));
+
+        worksheet.set_url_length_policy(UrlLengthPolicy::Truncate);
+        worksheet
+            .write_url_with_options(0, 0, long_url.as_str(), "Link", &long_tip, None)
+            .unwrap();
+
+        let hyperlink = &worksheet.hyperlinks[&(0, 0)];
+        assert_eq!(hyperlink.url.len(), 2_079);
+        assert!(hyperlink.url.ends_with("%20"));
+        assert_eq!(hyperlink.tip.len(), 255);
+        assert_eq!(worksheet.warnings.messages().len(), 1);
+        assert_eq!(worksheet.warnings.first().unwrap().cell, Some((0, 0)));
+
+        worksheet.set_url_length_policy(UrlLengthPolicy::WriteAsText);
+        worksheet.write_url(1, 0, long_url.as_str()).unwrap();
+
+        assert!(!worksheet.hyperlinks.contains_key(&(1, 0)));
+        assert!(matches!(
+            worksheet.data_table[&1].get(&0),
+            Some(CellType::String { .. })
+        ));
+        assert_eq!(worksheet.warnings.messages().len(), 2);
+
+        // A valid url clears the warning for the cell.
+        worksheet.write_url(1, 0, "https://example.com").unwrap();
+        assert_eq!(worksheet.warnings.messages().len(), 1);
+    }
+
+    #[test]
+    fn print_errors_as() {
+        let test_data = [
+            (PrintErrorOption::Blank, r#"errors="blank" "#),
+            (PrintErrorOption::Dash, r#"errors="dash" "#),
+            (PrintErrorOption::NA, r#"errors="NA" "#),
+            (PrintErrorOption::Displayed, ""),
+        ];
+
+        for (option, attribute) in test_data {
+            let mut worksheet = Worksheet::new();
+            worksheet.set_print_errors_as(option);
+            worksheet.write_page_setup();
+
+            let got = worksheet.writer.read_to_str();
+            let got = xml_to_vec(got);
+
+            let expected = xml_to_vec(&format!(
+                r#"<pageSetup orientation="portrait" {attribute}horizontalDpi="200" verticalDpi="200"/>"#
+            ));
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn sheet_protection_sha512() {
+        let mut worksheet = Worksheet::new();
+        worksheet.protect_with_sha512_password("password").unwrap();
+
+        // Replace the random salted hash with a known value.
+        let salt: Vec<u8> = (0..16).collect();
+        worksheet.protection_password_hash = Some(PasswordHash::new_with_salt("password", &salt));
+
+        worksheet.write_sheet_protection();
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"<sheetProtection algorithmName="SHA-512" hashValue="x01qKaF9y9cQwPxHrE46zKhOLAHXLgmWjpZRPwqjkl6tpT1Lq9JXlHzPvHxsy/q0gWkWsUumW+mgF2sVqd4VXQ==" saltValue="AAECAwQFBgcICQoLDA0ODw==" spinCount="100000" sheet="1" objects="1" scenarios="1"/>"#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn shared_formula() {
+        let mut worksheet = Worksheet::new();
+        worksheet
+            .write_shared_formula(0, 2, 2, 2, "=A1*B1")
+            .unwrap();
+        worksheet.write_shared_formula(0, 3, 1, 4, "=C1+1").unwrap();
+
+        assert_eq!(worksheet.shared_formula_ranges.len(), 2);
+        assert!(matches!(
+            worksheet.data_table[&2][&2],
+            CellType::SharedFormula {
+                shared_index: 0,
+                ref formula,
+                ..
+            } if formula.range.is_none()
+        ));
+        assert!(matches!(
+            &worksheet.data_table[&0][&3],
+            CellType::SharedFormula {
+                shared_index: 1,
+                formula,
+                ..
+            } if formula.range.as_deref() == Some("D1:E2")
+        ));
+
+        worksheet.write_shared_formula_cell(
+            0,
+            2,
+            "A1*B1",
+            0,
+            &FormulaResult::Number(0.0),
+            0,
+            Some("C1:C3"),
+        );
+        worksheet.write_shared_formula_cell(1, 2, "", 0, &FormulaResult::Number(0.0), 0, None);
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <c r="C1"><f t="shared" ref="C1:C3" si="0">A1*B1</f><v>0</v></c>
+            <c r="C2"><f t="shared" si="0"/><v>0</v></c>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        let result = worksheet.write_shared_formula(2, 0, 1, 0, "=A1");
+        assert!(matches!(result, Err(XlsxError::RowColumnOrderError)));
+
+        let result = worksheet.write_shared_formula(0, 0, ROW_MAX, 0, "=A1");
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.write_shared_formula(0, 0, 1, 0, "=SORT(B1:B3)");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[test]
+    fn shared_formula_overwrite_master() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write_shared_formula(0, 0, 3, 0, "=B1*2").unwrap();
+        worksheet.write_number(0, 0, 5).unwrap();
+
+        // The other cells in the range no longer refer to the shared formula.
+        for (row, expected) in [(1, "B2*2"), (2, "B3*2"), (3, "B4*2")] {
+            assert!(matches!(
+                &worksheet.data_table[&row][&0],
+                CellType::Formula { formula, .. } if &*formula.formula == expected
+            ));
+        }
+
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        assert!(!got.contains(r#"t="shared""#));
+        assert!(got.contains(r#"<c r="A4"><f>B4*2</f><v>0</v></c>"#));
+
+        // Overwriting a non-master cell leaves the shared formula in place.
+        let mut worksheet = Worksheet::new();
+        worksheet.write_shared_formula(0, 0, 3, 0, "=B1*2").unwrap();
+        worksheet.write_number(2, 0, 5).unwrap();
+
+        assert!(matches!(
+            &worksheet.data_table[&0][&0],
+            CellType::SharedFormula { formula, .. } if formula.range.is_some()
+        ));
+        assert!(matches!(
+            &worksheet.data_table[&3][&0],
+            CellType::SharedFormula { .. }
+        ));
+    }
+
+    #[test]
+    fn formula_results() {
+        let mut worksheet = Worksheet::new();
+        for row in 0..4 {
+            worksheet.write_formula(row, 0, "=B1").unwrap();
+        }
+        worksheet.write_number(4, 0, 1).unwrap();
+
+        worksheet.set_formula_result(0, 0, "2.5");
+        worksheet.set_formula_result_boolean(1, 0, true);
+        worksheet.set_formula_result_error(2, 0, "#N/A").unwrap();
+
+        let result = worksheet.set_formula_result_error(3, 0, "#OOPS!");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let expected = [
+            FormulaResult::Number(2.5),
+            FormulaResult::Boolean(true),
+            FormulaResult::Error("#N/A".to_string()),
+            FormulaResult::Number(0.0),
+        ];
+        for (row, expected) in expected.iter().enumerate() {
+            assert_eq!(
+                Some(expected),
+                worksheet.formula_result_mut(row as RowNum, 0).as_deref()
+            );
+        }
+
+        worksheet
+            .set_formula_results(0, 0, 4, 0, &["a", "b", "c", "d", "e"])
+            .unwrap();
+        assert_eq!(
+            Some(&mut FormulaResult::String("d".to_string())),
+            worksheet.formula_result_mut(3, 0)
+        );
+        assert_eq!(None, worksheet.formula_result_mut(4, 0));
+
+        let result = worksheet.set_formula_results(0, 0, 4, 0, &[1.0]);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = worksheet.set_formula_results(
+            0,
+            0,
+            0,
+            0,
+            &[FormulaResult::Error("#OOPS!".to_string())],
+        );
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = worksheet.set_formula_results(1, 0, 0, 0, &[1.0]);
+        assert!(matches!(result, Err(XlsxError::RowColumnOrderError)));
+
+        worksheet.write_formula_cell(0, 0, "B1", 0, &FormulaResult::Number(2.0));
+        worksheet.write_formula_cell(1, 0, "B1", 0, &FormulaResult::String("1".to_string()));
+        worksheet.write_formula_cell(2, 0, "B1", 0, &FormulaResult::Boolean(false));
+        worksheet.write_formula_cell(3, 0, "B1", 0, &FormulaResult::Error("#N/A".to_string()));
+
+        let got = worksheet.writer.read_to_str();
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <c r="A1"><f>B1</f><v>2</v></c>
+            <c r="A2" t="str"><f>B1</f><v>1</v></c>
+            <c r="A3" t="b"><f>B1</f><v>0</v></c>
+            <c r="A4" t="e"><f>B1</f><v>#N/A</v></c>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn formula_with_result() {
+        let mut worksheet = Worksheet::new();
+        let format = Format::new().set_bold();
+
+        worksheet
+            .write_formula_with_result(0, 0, "=1+1", 2)
+            .unwrap();
+        worksheet
+            .write_formula_with_result(1, 0, "=B1", "2")
+            .unwrap();
+        worksheet
+            .write_formula_with_format_and_result(2, 0, "=B1>0", &format, false)
+            .unwrap();
+        worksheet
+            .write_formula_with_result(3, 0, "=NA()", FormulaResult::Error("#N/A".to_string()))
+            .unwrap();
+
+        let expected = [
+            FormulaResult::Number(2.0),
+            FormulaResult::String("2".to_string()),
+            FormulaResult::Boolean(false),
+            FormulaResult::Error("#N/A".to_string()),
+        ];
+        for (row, expected) in expected.iter().enumerate() {
+            assert_eq!(
+                Some(expected),
+                worksheet.formula_result_mut(row as RowNum, 0).as_deref()
+            );
+        }
+
+        let result = worksheet.write_formula_with_result(
+            4,
+            0,
+            "=NA()",
+            FormulaResult::Error("N/A".to_string()),
+        );
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[test]
+    fn streamed_xml_file() {
+        let mut worksheet = Worksheet::new();
+        for row in 0..5000 {
+            worksheet.write_number(row, 0, row).unwrap();
+            worksheet.write_formula(row, 1, "=A1*2").unwrap();
+        }
+
+        worksheet.assemble_xml_file();
+        let expected = worksheet.writer.read_to_string();
+        worksheet.writer.reset();
+
+        // The data is large enough to be flushed in more than one chunk.
+        let mut streamed = vec![];
+        worksheet.assemble_xml_file_to(&mut streamed).unwrap();
+        assert!(streamed.len() > crate::xmlwriter::XML_FLUSH_THRESHOLD);
+        assert!(worksheet.writer.read_to_str().is_empty());
+
+        assert_eq!(expected, String::from_utf8(streamed).unwrap());
+    }
+
+    #[test]
+    fn format_id_xf_indices() {
+        let mut workbook = crate::Workbook::new();
+        let bold = workbook.format_id(&Format::new().set_bold());
+        let italic = Format::new().set_italic();
+
+        let mut worksheet = Worksheet::new();
+        worksheet.write_number_with_format(0, 0, 1, &bold).unwrap();
+        worksheet
+            .write_number_with_format(0, 1, 1, &italic)
+            .unwrap();
+        worksheet.write_number_with_format(0, 2, 1, &bold).unwrap();
+
+        // An equal, but not interned, format maps to the same index.
+        let format = Format::new().set_bold();
+        worksheet
+            .write_number_with_format(0, 3, 1, &format)
+            .unwrap();
+
+        let xf_indices: Vec<u32> = worksheet.data_table[&0]
+            .values()
+            .map(|cell| match cell {
+                CellType::Number { xf_index, .. } => *xf_index,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(xf_indices, vec![1, 2, 1, 1]);
+        assert_eq!(worksheet.interned_xf_indices.len(), 1);
+    }
+
+    #[test]
+    fn inline_strings() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write_string(0, 0, "Hello").unwrap();
+        worksheet.write_string(0, 1, " Space").unwrap();
+        worksheet.write_string(0, 2, "a < b").unwrap();
+
+        worksheet.use_inline_strings = true;
+        worksheet.assemble_xml_file();
+
+        let got = worksheet.writer.read_to_str();
+        assert!(got.contains(r#"<c r="A1" t="inlineStr"><is><t>Hello</t></is></c>"#));
+        assert!(got.contains(
+            r#"<c r="B1" t="inlineStr"><is><t xml:space="preserve"> Space</t></is></c>"#
+        ));
+        assert!(got.contains(r#"<c r="C1" t="inlineStr"><is><t>a &lt; b</t></is></c>"#));
+    }
+
+    #[test]
+    fn cell_row_ordering() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write_number(0, 5, 5).unwrap();
+        worksheet.write_number(0, 1, 1).unwrap();
+        worksheet.write_number(0, 9, 9).unwrap();
+        worksheet.write_number(0, 3, 3).unwrap();
+
+        // Overwrite an existing cell.
+        worksheet.write_number(0, 5, 50).unwrap();
+
+        let row = &worksheet.data_table[&0];
+        assert_eq!(row.keys().copied().collect::<Vec<_>>(), vec![1, 3, 5, 9]);
+        assert!(matches!(row[&5], CellType::Number { number, .. } if number == 50.0));
+        assert!(row.contains_key(&9));
+        assert!(!row.contains_key(&2));
+        assert!(row.get(&10).is_none());
+
+        worksheet.assemble_xml_file();
+        let got = worksheet.writer.read_to_str();
+        assert!(got.contains(
+            r#"<row r="1" spans="2:10"><c r="B1"><v>1</v></c><c r="D1"><v>3</v></c><c r="F1"><v>50</v></c><c r="J1"><v>9</v></c></row>"#
+        ));
+    }
+
+    #[test]
+    fn flush_rows() {
+        let bold = Format::new().set_bold();
+        let italic = Format::new().set_italic();
+
+        let write_data = |worksheet: &mut Worksheet, flush: bool| {
+            worksheet.set_row_format(3, &italic).unwrap();
+            for row in 0..40 {
+                worksheet.write_number(row, 0, row).unwrap();
+                worksheet
+                    .write_number_with_format(row, 1, row, &bold)
+                    .unwrap();
+                worksheet.write_boolean(row, 2, true).unwrap();
+
+                if flush && row % 10 == 9 {
+                    worksheet.flush_rows(row + 1).unwrap();
+                }
+            }
+            worksheet.set_global_xf_indices(&[0, 5, 7]);
+        };
+
+        let mut worksheet = Worksheet::new();
+        write_data(&mut worksheet, false);
+        worksheet.assemble_xml_file();
+        let expected = worksheet.writer.read_to_string();
+
+        let mut worksheet = Worksheet::new();
+        write_data(&mut worksheet, true);
+        assert_eq!(worksheet.data_table.len(), 0);
+
+        // Flushed rows can't be written to.
+        let result = worksheet.write_number(5, 0, 1);
+        assert!(matches!(result, Err(XlsxError::RowFlushedError)));
+
+        let result = worksheet.set_row_height(5, 20);
+        assert!(matches!(result, Err(XlsxError::RowFlushedError)));
+
+        // The output is the same as for the unflushed worksheet, including when
+        // it is saved more than once.
+        worksheet.assemble_xml_file();
+        assert_eq!(expected, worksheet.writer.read_to_string());
+        worksheet.writer.reset();
+
+        let mut streamed = vec![];
+        worksheet.assemble_xml_file_to(&mut streamed).unwrap();
+        assert_eq!(expected, String::from_utf8(streamed).unwrap());
+    }
+
+    #[test]
+    fn flush_rows_autofilter() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write(0, 0, "Region").unwrap();
+        for row in 1..20 {
+            let region = if row % 2 == 0 { "East" } else { "West" };
+            worksheet.write(row, 0, region).unwrap();
+        }
+
+        worksheet.autofilter(0, 0, 19, 0).unwrap();
+        let filter_condition = FilterCondition::new().add_list_filter("East");
+        worksheet.filter_column(0, &filter_condition).unwrap();
+
+        // Rows flushed after the filter was set can't be hidden and are
+        // skipped. The rows that are still in memory are hidden as usual.
+        worksheet.flush_rows(10).unwrap();
+        worksheet.hide_autofilter_rows().unwrap();
+
+        assert!(!worksheet.changed_rows.contains_key(&9));
+        assert!(!worksheet.changed_rows.contains_key(&10));
+        assert!(worksheet.changed_rows[&11].hidden);
+
+        // An autofilter or filter can't be added to flushed rows.
+        let result = worksheet.filter_column(0, &filter_condition);
+        assert!(matches!(result, Err(XlsxError::RowFlushedError)));
+
+        let result = worksheet.autofilter(5, 0, 19, 0);
+        assert!(matches!(result, Err(XlsxError::RowFlushedError)));
+
+        assert!(worksheet.autofilter(10, 0, 19, 0).is_ok());
+
+        // Column formats don't depend on the flushed rows.
+        let bold = Format::new().set_bold();
+        assert!(worksheet.set_column_format(1, &bold).is_ok());
+    }
+
+    #[test]
+    fn flush_rows_strings() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write_string(0, 0, "Hello").unwrap();
+        worksheet.flush_rows(1).unwrap();
+        worksheet.write_string(1, 0, "World").unwrap();
+
+        worksheet.assemble_xml_file();
+        let got = worksheet.writer.read_to_str();
+
+        // Flushed strings are written inline.
+        assert!(got.contains(r#"<c r="A1" t="inlineStr"><is><t>Hello</t></is></c>"#));
+        assert!(got.contains(r#"<c r="A2" t="s"><v>0</v></c>"#));
+    }
+
+    #[test]
+    fn global_tag_indices() {
+        let got = Worksheet::global_tag_indices(r#"<c r="A1" s="1" t="e" vm="0">"#, &[0, 9], &[4]);
+        assert_eq!(got, r#"<c r="A1" s="9" t="e" vm="4">"#);
+
+        let got =
+            Worksheet::global_tag_indices(r#"<row r="1" s="1" customFormat="1"/>"#, &[0, 3], &[]);
+        assert_eq!(got, r#"<row r="1" s="3" customFormat="1"/>"#);
+    }
+
+    #[test]
+    fn memory_usage() {
+        let mut worksheet = Worksheet::new();
+        assert_eq!(worksheet.memory_usage(), MemoryUsage::default());
+
+        worksheet.write_number(0, 0, 1).unwrap();
+        worksheet.write_string(0, 1, "Hello").unwrap();
+        worksheet.write_formula(1, 0, "=A1").unwrap();
+
+        let usage = worksheet.memory_usage();
+        assert_eq!(usage.strings(), 5);
+        assert_eq!(usage.media(), 0);
+        assert!(usage.cells() > 0);
+        assert_eq!(usage.total(), usage.cells() + usage.strings());
+
+        // Flushed rows no longer use memory.
+        worksheet.flush_rows(2).unwrap();
+        assert_eq!(worksheet.memory_usage(), MemoryUsage::default());
+    }
+
+    #[test]
+    fn write_row_from_iterator() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write_row(0, 1, "a,b,c".split(',')).unwrap();
+        worksheet.write_row(1, 0, (1..=3).map(f64::from)).unwrap();
+
+        assert_eq!(
+            worksheet.data_table[&0].keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(
+            matches!(worksheet.data_table[&1][&2], CellType::Number { number, .. } if number == 3.0)
+        );
+
+        // Empty strings are ignored so no row is stored.
+        worksheet.write_row(2, 0, ["", ""]).unwrap();
+        assert!(!worksheet.data_table.contains_key(&2));
+
+        // Values beyond the last column are an error.
+        let result = worksheet.write_row(3, COL_MAX - 1, [1, 2]);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.write_row(ROW_MAX, 0, [1, 2]);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        assert!(!worksheet.data_table.contains_key(&ROW_MAX));
+    }
+
+    #[test]
+    fn collapsed_cols_and_rows() {
+        let mut worksheet = Worksheet::new();
+
+        // Column widths that differ in user units but map to the same
+        // character width are written as a single span.
+        worksheet.set_column_width(0, 10).unwrap();
+        worksheet.set_column_width(1, 10.01).unwrap();
+        worksheet.set_column_width(2, 10).unwrap();
+        worksheet.set_column_width(3, 10).unwrap();
+        worksheet.set_column_width(5, 10).unwrap();
+
+        worksheet.set_row_height(0, 30).unwrap();
+        worksheet.set_row_height(1, 30).unwrap();
+        worksheet.set_row_height(2, 30).unwrap();
+        worksheet.set_row_hidden(2).unwrap();
+        worksheet.write_number(1, 0, 1).unwrap();
+
+        worksheet.assemble_xml_file();
+        let got = worksheet.writer.read_to_str();
+
+        assert!(got.contains(
+            r#"<cols><col min="1" max="4" width="10.7109375" customWidth="1"/><col min="6" max="6" width="10.7109375" customWidth="1"/></cols>"#
+        ));
+        assert!(got.contains(r#"<row r="1" spans="1:1" ht="30" customHeight="1"/>"#));
+        assert!(got.contains(r#"<row r="2" spans="1:1" ht="30" customHeight="1">"#));
+        assert!(got.contains(r#"<row r="3" spans="1:1" ht="30" hidden="1" customHeight="1"/>"#));
+    }
+
+    #[test]
+    fn cell_type_size() {
+        // The cell type is stored for every cell so it should be kept small.
+        assert!(std::mem::size_of::<CellType>() <= 32);
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut worksheet = Worksheet::with_capacity(100, 8);
+        worksheet.write_number(0, 0, 1).unwrap();
+        worksheet.write_number(0, 1, 2).unwrap();
+
+        assert!(worksheet.data_table[&0].0.capacity() >= 8);
+        assert!(worksheet.col_names.capacity() >= 8);
+
+        worksheet.assemble_xml_file();
+        let got = worksheet.writer.read_to_str();
+        assert!(got.contains(
+            r#"<row r="1" spans="1:2"><c r="A1"><v>1</v></c><c r="B1"><v>2</v></c></row>"#
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn write_json_rows() {
+        let mut worksheet = Worksheet::new();
+        let data = serde_json::json!([
+            {"id": 1, "name": "Apples", "tags": ["fruit"]},
+            {"id": 2.5, "in_stock": false, "name": null},
+        ]);
+
+        worksheet.write_json_rows(1, 1, &data).unwrap();
+
+        // The serde_json keys are sorted by default.
+        let headers: Vec<_> = worksheet.data_table[&1]
+            .values()
+            .map(|cell| match cell {
+                CellType::String { string, .. } => string.to_string(),
+                _ => String::new(),
+            })
+            .collect();
+        assert_eq!(headers, vec!["id", "name", "tags", "in_stock"]);
+
+        assert!(
+            matches!(worksheet.data_table[&2][&2], CellType::String { ref string, .. } if string.as_ref() == "Apples")
+        );
+        assert!(
+            matches!(worksheet.data_table[&2][&3], CellType::String { ref string, .. } if string.as_ref() == r#"["fruit"]"#)
+        );
+        assert!(
+            matches!(worksheet.data_table[&3][&1], CellType::Number { number, .. } if number == 2.5)
+        );
+        assert!(matches!(
+            worksheet.data_table[&3][&4],
+            CellType::Boolean { boolean: false, .. }
+        ));
+        assert!(!worksheet.data_table[&3].contains_key(&2));
+
+        let result = worksheet.write_json_rows(0, 0, &serde_json::json!({"id": 1}));
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = worksheet.write_json_rows(0, 0, &serde_json::json!([1, 2]));
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = worksheet.write_json_rows(ROW_MAX - 1, 0, &data);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn write_array2() {
+        let mut worksheet = Worksheet::new();
+        let matrix = ndarray::array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+
+        worksheet.write_array2(0, 0, &matrix).unwrap();
+        assert!(
+            matches!(worksheet.data_table[&1][&2], CellType::Number { number, .. } if number == 6.0)
+        );
+
+        worksheet
+            .write_array2_with_labels(4, 0, &matrix.t(), &["x", "y", "z"], &["a", "b"])
+            .unwrap();
+        assert!(!worksheet.data_table[&4].contains_key(&0));
+        assert!(
+            matches!(worksheet.data_table[&4][&2], CellType::String { ref string, .. } if string.as_ref() == "b")
+        );
+        assert!(
+            matches!(worksheet.data_table[&7][&0], CellType::String { ref string, .. } if string.as_ref() == "z")
+        );
+        assert!(
+            matches!(worksheet.data_table[&7][&2], CellType::Number { number, .. } if number == 6.0)
+        );
+
+        let result = worksheet.write_array2_with_labels(0, 0, &matrix, &["x"], &["a", "b", "c"]);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = worksheet.write_array2(ROW_MAX - 1, 0, &matrix);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    #[test]
+    fn write_unix_timestamp() {
+        let mut worksheet = Worksheet::new();
+        let format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+
+        worksheet.write_unix_timestamp(0, 0, 0, &format).unwrap();
+        worksheet
+            .write_unix_timestamp_millis(1, 0, 946_728_000_000, &format)
+            .unwrap();
+
+        let numbers: Vec<f64> = [0, 1]
+            .iter()
+            .map(|row| match worksheet.data_table[row][&0] {
+                CellType::DateTime { number, .. } => number,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(vec![25569.0, 36526.5], numbers);
+
+        let result = worksheet.write_unix_timestamp(2, 0, 253_402_300_800, &format);
+        assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
+    }
 }
@@ -12,10 +12,12 @@ use std::borrow::Cow;
 use std::io::{Cursor, Write};
 use std::str;
 
-use regex::Regex;
-
 pub(crate) const XML_WRITE_ERROR: &str = "Couldn't write to xml file";
 
+// The size at which buffered xml data is flushed to the output writer when
+// streaming large files such as worksheets.
+pub(crate) const XML_FLUSH_THRESHOLD: usize = 64 * 1024;
+
 #[derive(Clone)]
 pub struct XMLWriter {
     pub(crate) xmlfile: Cursor<Vec<u8>>,
@@ -52,6 +54,26 @@ impl XMLWriter {
         self.xmlfile.set_position(0);
     }
 
+    // Write the buffered xml data to an output writer and clear the buffer.
+    // The buffer capacity is retained so that it can be reused for the next
+    // chunk of data.
+    pub(crate) fn flush_to(&mut self, writer: &mut dyn Write) -> std::io::Result<()> {
+        writer.write_all(self.xmlfile.get_ref())?;
+        self.reset();
+
+        Ok(())
+    }
+
+    // Flush the buffered xml data to an output writer if it exceeds the flush
+    // threshold.
+    pub(crate) fn flush_if_full(&mut self, writer: &mut dyn Write) -> std::io::Result<()> {
+        if self.xmlfile.get_ref().len() >= XML_FLUSH_THRESHOLD {
+            self.flush_to(writer)?;
+        }
+
+        Ok(())
+    }
+
     // Write an XML file declaration.
     pub(crate) fn xml_declaration(&mut self) {
         self.xmlfile
@@ -104,14 +126,9 @@ impl XMLWriter {
 
     // Write an XML element containing data without attributes.
     pub(crate) fn xml_data_element_only(&mut self, tag: &str, data: &str) {
-        write!(
-            &mut self.xmlfile,
-            "<{}>{}</{}>",
-            tag,
-            escape_xml_data(data),
-            tag
-        )
-        .expect(XML_WRITE_ERROR);
+        write!(&mut self.xmlfile, "<{tag}>").expect(XML_WRITE_ERROR);
+        self.xml_escaped_data(data);
+        write!(&mut self.xmlfile, "</{tag}>").expect(XML_WRITE_ERROR);
     }
 
     // Write an XML element containing data with attributes.
@@ -125,26 +142,90 @@ impl XMLWriter {
             attribute.write_to(&mut self.xmlfile);
         }
 
-        write!(&mut self.xmlfile, ">{}</{}>", escape_xml_data(data), tag).expect(XML_WRITE_ERROR);
+        self.xmlfile.write_all(b">").expect(XML_WRITE_ERROR);
+        self.xml_escaped_data(data);
+        write!(&mut self.xmlfile, "</{tag}>").expect(XML_WRITE_ERROR);
+    }
+
+    // Write an XML element containing cell string data. This is the same as
+    // xml_data_element() but literal strings that look like the Excel _xHHHH_
+    // escapes are also escaped so that they aren't unescaped by Excel.
+    pub(crate) fn xml_string_data_element<T>(&mut self, tag: &str, data: &str, attributes: &[T])
+    where
+        T: IntoAttribute,
+    {
+        self.xml_data_element(tag, &escape_xml_escapes(data), attributes);
+    }
+
+    // Write XML escaped data directly to the buffer, without an intermediate
+    // string allocation.
+    pub(crate) fn xml_escaped_data(&mut self, data: &str) {
+        write_escaped(&mut self.xmlfile, data, find_xml_char, match_xml_char);
+    }
+
+    // Write an integer value without an intermediate string allocation.
+    pub(crate) fn xml_integer<T: itoa::Integer>(&mut self, value: T) {
+        let mut buffer = itoa::Buffer::new();
+        self.xmlfile
+            .write_all(buffer.format(value).as_bytes())
+            .expect(XML_WRITE_ERROR);
+    }
+
+    // Write a number in the same format as the f64 Display trait but without
+    // an intermediate string allocation. Integers, the most common case, are
+    // written via itoa and other values via ryu unless ryu would use a
+    // different format, such as an exponent, than Display.
+    pub(crate) fn xml_number(&mut self, number: f64) {
+        if number.fract() == 0.0
+            && number.abs() < 1e15
+            && !(number == 0.0 && number.is_sign_negative())
+        {
+            self.xml_integer(number as i64);
+            return;
+        }
+
+        if number.is_finite() {
+            let mut buffer = ryu::Buffer::new();
+            let formatted = buffer.format_finite(number);
+            if !formatted.contains('e') && !formatted.ends_with(".0") {
+                self.xmlfile
+                    .write_all(formatted.as_bytes())
+                    .expect(XML_WRITE_ERROR);
+                return;
+            }
+        }
+
+        write!(&mut self.xmlfile, "{number}").expect(XML_WRITE_ERROR);
+    }
+
+    // Write the start of a worksheet cell <c> element with the cell reference
+    // and the optional style attribute. The start tag isn't closed so that
+    // other attributes can be added.
+    pub(crate) fn xml_cell_start(&mut self, col_name: &str, row: u32, xf_index: u32) {
+        self.xmlfile.write_all(br#"<c r=""#).expect(XML_WRITE_ERROR);
+        self.xmlfile
+            .write_all(col_name.as_bytes())
+            .expect(XML_WRITE_ERROR);
+        self.xml_integer(row + 1);
+        self.xmlfile.write_all(b"\"").expect(XML_WRITE_ERROR);
+
+        if xf_index > 0 {
+            self.xmlfile.write_all(br#" s=""#).expect(XML_WRITE_ERROR);
+            self.xml_integer(xf_index);
+            self.xmlfile.write_all(b"\"").expect(XML_WRITE_ERROR);
+        }
     }
 
     // Optimized tag writer for shared strings <si> elements.
     pub(crate) fn xml_si_element(&mut self, string: &str, preserve_whitespace: bool) {
         if preserve_whitespace {
-            write!(
-                &mut self.xmlfile,
-                r#"<si><t xml:space="preserve">{}</t></si>"#,
-                escape_xml_data(&escape_xml_escapes(string))
-            )
-            .expect(XML_WRITE_ERROR);
+            self.xml_raw_string(r#"<si><t xml:space="preserve">"#);
         } else {
-            write!(
-                &mut self.xmlfile,
-                "<si><t>{}</t></si>",
-                escape_xml_data(&escape_xml_escapes(string))
-            )
-            .expect(XML_WRITE_ERROR);
+            self.xml_raw_string("<si><t>");
         }
+
+        self.xml_escaped_data(&escape_xml_escapes(string));
+        self.xml_raw_string("</t></si>");
     }
 
     // Write <si> element for rich strings.
@@ -152,6 +233,23 @@ impl XMLWriter {
         write!(&mut self.xmlfile, r#"<si>{string}</si>"#).expect(XML_WRITE_ERROR);
     }
 
+    // Optimized tag writer for inline string <is> elements.
+    pub(crate) fn xml_is_element(&mut self, string: &str, preserve_whitespace: bool) {
+        if preserve_whitespace {
+            self.xml_raw_string(r#"<is><t xml:space="preserve">"#);
+        } else {
+            self.xml_raw_string("<is><t>");
+        }
+
+        self.xml_escaped_data(&escape_xml_escapes(string));
+        self.xml_raw_string("</t></is>");
+    }
+
+    // Write <is> element for inline rich strings.
+    pub(crate) fn xml_rich_is_element(&mut self, string: &str) {
+        write!(&mut self.xmlfile, r#"<is>{string}</is>"#).expect(XML_WRITE_ERROR);
+    }
+
     // Write the theme string to the theme file.
     pub(crate) fn write_theme(&mut self, theme: &str) {
         writeln!(&mut self.xmlfile, "{theme}").expect(XML_WRITE_ERROR);
@@ -165,140 +263,239 @@ impl XMLWriter {
     }
 }
 
-// Escape XML characters in attributes.
-pub(crate) fn escape_attributes(attribute: &str) -> Cow<str> {
-    escape_string(attribute, match_attribute_html_char)
-}
-
-// Escape XML characters in data sections of tags.
-pub(crate) fn escape_xml_data(data: &str) -> Cow<str> {
-    escape_string(data, match_xml_char)
-}
-
 // Escape non-url characters in a hyperlink/url.
 pub(crate) fn escape_url(data: &str) -> Cow<str> {
-    escape_string(data, match_url_char)
+    escape_string(data, find_escape_char, match_url_char)
 }
 
 // -----------------------------------------------------------------------
 // Helper functions. Mainly for string escaping.
 // -----------------------------------------------------------------------
 
-// Match function for escape_attributes().
-fn match_attribute_html_char(ch: char) -> Option<&'static str> {
-    match ch {
-        '&' => Some("&amp;"),
-        '"' => Some("&quot;"),
-        '<' => Some("&lt;"),
-        '>' => Some("&gt;"),
-        '\n' => Some("&#xA;"),
-        _ => None,
+// Match function for escaping XML characters in attributes. Control
+// characters aren't allowed in XML, even as character references, so they are
+// escaped with the Excel _xHHHH_ convention in the same way as data sections.
+fn match_attribute_html_char(byte: u8) -> Option<&'static str> {
+    match byte {
+        b'"' => Some("&quot;"),
+        b'\n' => Some("&#xA;"),
+        _ => match_xml_char(byte),
     }
 }
 
-// Match function for escape_xml_data().
+// Match function for escaping XML characters in data sections of tags.
 //
 // Note, this is different from match_attribute_html_char() because double
 // quotes and newline are not escaped by Excel.
 //
 // We need to mimic Excel by escaping control and non-printing characters in the
 // range '\x00' - '\x1F'.
-fn match_xml_char(ch: char) -> Option<&'static str> {
-    match ch {
+fn match_xml_char(byte: u8) -> Option<&'static str> {
+    match byte {
         // Excel escapes control characters and other non-printing characters in
         // the range '\x00' - '\x1F' with _xHHHH_.
-        '\x00' => Some("_x0000_"),
-        '\x01' => Some("_x0001_"),
-        '\x02' => Some("_x0002_"),
-        '\x03' => Some("_x0003_"),
-        '\x04' => Some("_x0004_"),
-        '\x05' => Some("_x0005_"),
-        '\x06' => Some("_x0006_"),
-        '\x07' => Some("_x0007_"),
-        '\x08' => Some("_x0008_"),
+        b'\x00' => Some("_x0000_"),
+        b'\x01' => Some("_x0001_"),
+        b'\x02' => Some("_x0002_"),
+        b'\x03' => Some("_x0003_"),
+        b'\x04' => Some("_x0004_"),
+        b'\x05' => Some("_x0005_"),
+        b'\x06' => Some("_x0006_"),
+        b'\x07' => Some("_x0007_"),
+        b'\x08' => Some("_x0008_"),
         // No escape required for '\x09' = '\t'
         // No escape required for '\x0A' = '\n'
-        '\x0B' => Some("_x000B_"),
-        '\x0C' => Some("_x000C_"),
-        '\x0D' => Some("_x000D_"),
-        '\x0E' => Some("_x000E_"),
-        '\x0F' => Some("_x000F_"),
-        '\x10' => Some("_x0010_"),
-        '\x11' => Some("_x0011_"),
-        '\x12' => Some("_x0012_"),
-        '\x13' => Some("_x0013_"),
-        '\x14' => Some("_x0014_"),
-        '\x15' => Some("_x0015_"),
-        '\x16' => Some("_x0016_"),
-        '\x17' => Some("_x0017_"),
-        '\x18' => Some("_x0018_"),
-        '\x19' => Some("_x0019_"),
-        '\x1A' => Some("_x001A_"),
-        '\x1B' => Some("_x001B_"),
-        '\x1C' => Some("_x001C_"),
-        '\x1D' => Some("_x001D_"),
-        '\x1E' => Some("_x001E_"),
-        '\x1F' => Some("_x001F_"),
+        b'\x0B' => Some("_x000B_"),
+        b'\x0C' => Some("_x000C_"),
+        b'\x0D' => Some("_x000D_"),
+        b'\x0E' => Some("_x000E_"),
+        b'\x0F' => Some("_x000F_"),
+        b'\x10' => Some("_x0010_"),
+        b'\x11' => Some("_x0011_"),
+        b'\x12' => Some("_x0012_"),
+        b'\x13' => Some("_x0013_"),
+        b'\x14' => Some("_x0014_"),
+        b'\x15' => Some("_x0015_"),
+        b'\x16' => Some("_x0016_"),
+        b'\x17' => Some("_x0017_"),
+        b'\x18' => Some("_x0018_"),
+        b'\x19' => Some("_x0019_"),
+        b'\x1A' => Some("_x001A_"),
+        b'\x1B' => Some("_x001B_"),
+        b'\x1C' => Some("_x001C_"),
+        b'\x1D' => Some("_x001D_"),
+        b'\x1E' => Some("_x001E_"),
+        b'\x1F' => Some("_x001F_"),
 
         // Standard XML escapes.
-        '&' => Some("&amp;"),
-        '<' => Some("&lt;"),
-        '>' => Some("&gt;"),
+        b'&' => Some("&amp;"),
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
         _ => None,
     }
 }
 
 // Match the url characters that Excel escapes.
-fn match_url_char(ch: char) -> Option<&'static str> {
-    match ch {
-        '%' => Some("%25"),
-        '"' => Some("%22"),
-        ' ' => Some("%20"),
-        '<' => Some("%3c"),
-        '>' => Some("%3e"),
-        '[' => Some("%5b"),
-        ']' => Some("%5d"),
-        '^' => Some("%5e"),
-        '`' => Some("%60"),
-        '{' => Some("%7b"),
-        '}' => Some("%7d"),
+fn match_url_char(byte: u8) -> Option<&'static str> {
+    match byte {
+        b'%' => Some("%25"),
+        b'"' => Some("%22"),
+        b' ' => Some("%20"),
+        b'<' => Some("%3c"),
+        b'>' => Some("%3e"),
+        b'[' => Some("%5b"),
+        b']' => Some("%5d"),
+        b'^' => Some("%5e"),
+        b'`' => Some("%60"),
+        b'{' => Some("%7b"),
+        b'}' => Some("%7d"),
         _ => None,
     }
 }
 
-// Generic escape function with function pointer for the required handler.
-fn escape_string<F>(original: &str, char_handler: F) -> Cow<str>
-where
-    F: FnOnce(char) -> Option<&'static str> + Copy,
+// Find the next character that requires XML data escaping. The standard XML
+// escapes are found with memchr and the range is then checked for the rarer
+// control characters.
+fn find_xml_char(bytes: &[u8]) -> Option<usize> {
+    let end = memchr::memchr3(b'&', b'<', b'>', bytes);
+
+    bytes[..end.unwrap_or(bytes.len())]
+        .iter()
+        .position(|&byte| byte < 0x20 && byte != b'\t' && byte != b'\n')
+        .or(end)
+}
+
+// Generic find function for the escapes that don't have a specialized version.
+// It is only used for the less common attribute and url strings. Control
+// characters are found so that they can be escaped in attributes. They aren't
+// escaped by the url match function.
+fn find_escape_char(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().position(|&byte| {
+        matches!(
+            byte,
+            b'\x00'..=b'\x08'
+                | b'\x0B'..=b'\x1F'
+                | b'&'
+                | b'"'
+                | b'<'
+                | b'>'
+                | b'\n'
+                | b'%'
+                | b' '
+                | b'['
+                | b']'
+                | b'^'
+                | b'`'
+                | b'{'
+                | b'}'
+        )
+    })
+}
+
+// Split a string into unescaped runs and escaped characters and pass them, in
+// order, to an output function. All the escaped characters are ASCII so the
+// string can be searched as bytes and the runs are always valid UTF-8 slices.
+fn for_each_escaped<O>(
+    original: &str,
+    find_handler: fn(&[u8]) -> Option<usize>,
+    char_handler: fn(u8) -> Option<&'static str>,
+    mut output: O,
+) where
+    O: FnMut(&str),
 {
-    for (i, ch) in original.char_indices() {
-        if char_handler(ch).is_some() {
-            let mut escaped_string = original[..i].to_string();
-            let remaining = &original[i..];
-            escaped_string.reserve(remaining.len());
-
-            for ch in remaining.chars() {
-                match char_handler(ch) {
-                    Some(escaped_char) => escaped_string.push_str(escaped_char),
-                    None => escaped_string.push(ch),
-                };
-            }
+    let bytes = original.as_bytes();
+    let mut start = 0;
+
+    while let Some(offset) = find_handler(&bytes[start..]) {
+        let index = start + offset;
+        if let Some(escaped_char) = char_handler(bytes[index]) {
+            output(&original[start..index]);
+            output(escaped_char);
+        } else {
+            // A character found by a shared find function that isn't escaped
+            // in this context.
+            output(&original[start..=index]);
+        }
+        start = index + 1;
+    }
 
-            return Cow::Owned(escaped_string);
+    output(&original[start..]);
+}
+
+// Generic escape function with function pointers for the required handlers.
+// The common case of strings that don't need escaping is borrowed without
+// allocating.
+fn escape_string<'a>(
+    original: &'a str,
+    find_handler: fn(&[u8]) -> Option<usize>,
+    char_handler: fn(u8) -> Option<&'static str>,
+) -> Cow<'a, str> {
+    let needs_escape = {
+        let mut bytes = original.as_bytes();
+        let mut needs_escape = false;
+        while let Some(index) = find_handler(bytes) {
+            if char_handler(bytes[index]).is_some() {
+                needs_escape = true;
+                break;
+            }
+            bytes = &bytes[index + 1..];
         }
+        needs_escape
+    };
+
+    if !needs_escape {
+        return Cow::Borrowed(original);
     }
 
-    Cow::Borrowed(original)
+    let mut escaped_string = String::with_capacity(original.len() + 16);
+    for_each_escaped(original, find_handler, char_handler, |segment| {
+        escaped_string.push_str(segment);
+    });
+
+    Cow::Owned(escaped_string)
+}
+
+// Write a string with escaping directly to the xml buffer.
+fn write_escaped(
+    xmlfile: &mut Cursor<Vec<u8>>,
+    original: &str,
+    find_handler: fn(&[u8]) -> Option<usize>,
+    char_handler: fn(u8) -> Option<&'static str>,
+) {
+    for_each_escaped(original, find_handler, char_handler, |segment| {
+        xmlfile
+            .write_all(segment.as_bytes())
+            .expect(XML_WRITE_ERROR);
+    });
 }
 
 // Excel escapes control characters with _xHHHH_ and also escapes any literal
 // strings of that type by encoding the leading underscore. So "\0" -> _x0000_
 // and "_x0000_" -> _x005F_x0000_.
+// This is called for every shared string so it avoids a regex and exits early
+// for the common case of strings without escapes.
 fn escape_xml_escapes(si_string: &str) -> Cow<str> {
-    lazy_static! {
-        static ref XML_ESCAPE: Regex = Regex::new("(_x[0-9a-fA-F]{4}_)").unwrap();
+    let is_escape = |bytes: &[u8]| {
+        bytes.len() >= 7 && bytes[2..6].iter().all(u8::is_ascii_hexdigit) && bytes[6] == b'_'
+    };
+
+    let mut escaped = String::new();
+    let mut position = 0;
+    for (index, _) in si_string.match_indices("_x") {
+        if index >= position && is_escape(&si_string.as_bytes()[index..]) {
+            escaped.push_str(&si_string[position..index]);
+            escaped.push_str("_x005F");
+            escaped.push_str(&si_string[index..index + 7]);
+            position = index + 7;
+        }
     }
-    XML_ESCAPE.replace_all(si_string, "_x005F$1")
+
+    if escaped.is_empty() {
+        return Cow::Borrowed(si_string);
+    }
+
+    escaped.push_str(&si_string[position..]);
+    Cow::Owned(escaped)
 }
 
 // Trait to write attribute tuple values to an XML file.
@@ -308,12 +505,19 @@ pub(crate) trait IntoAttribute {
 
 impl IntoAttribute for (&str, &str) {
     fn write_to(&self, xmlfile: &mut Cursor<Vec<u8>>) {
-        write!(xmlfile, r#" {}="{}""#, self.0, escape_attributes(self.1)).expect(XML_WRITE_ERROR);
+        write_attribute(xmlfile, self.0, self.1);
     }
 }
 
 impl IntoAttribute for (&str, String) {
     fn write_to(&self, xmlfile: &mut Cursor<Vec<u8>>) {
-        write!(xmlfile, r#" {}="{}""#, self.0, escape_attributes(&self.1)).expect(XML_WRITE_ERROR);
+        write_attribute(xmlfile, self.0, &self.1);
     }
 }
+
+// Write an attribute with an escaped value directly to the xml buffer.
+fn write_attribute(xmlfile: &mut Cursor<Vec<u8>>, name: &str, value: &str) {
+    write!(xmlfile, r#" {name}=""#).expect(XML_WRITE_ERROR);
+    write_escaped(xmlfile, value, find_escape_char, match_attribute_html_char);
+    xmlfile.write_all(b"\"").expect(XML_WRITE_ERROR);
+}
@@ -7,8 +7,9 @@
 #[cfg(test)]
 mod xmlwriter_tests {
 
-    use crate::xmlwriter::XMLWriter;
+    use crate::xmlwriter::{escape_url, XMLWriter};
     use pretty_assertions::assert_eq;
+    use std::borrow::Cow;
 
     #[test]
     fn test_xml_declaration() {
@@ -160,4 +161,153 @@ mod xmlwriter_tests {
         let got = writer.read_to_str();
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn test_xml_si_element_escapes() {
+        let strings = [
+            ("_x0000_", "_x005F_x0000_"),
+            ("a_x00aF_b", "a_x005F_x00aF_b"),
+            ("_x0000_x0000_", "_x005F_x0000_x0000_"),
+            ("_x0000__x0001_", "_x005F_x0000__x005F_x0001_"),
+            ("_x000_", "_x000_"),
+            ("_x00G0_", "_x00G0_"),
+            ("_x0000", "_x0000"),
+            ("é_x0000_é", "é_x005F_x0000_é"),
+        ];
+
+        for (string, escaped) in strings {
+            let expected = format!("<si><t>{escaped}</t></si>");
+
+            let mut writer = XMLWriter::new();
+            writer.xml_si_element(string, false);
+
+            let got = writer.read_to_str();
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn test_xml_number() {
+        let numbers = [
+            0.0,
+            -0.0,
+            1.0,
+            -123.0,
+            0.5,
+            1.23456789,
+            -0.000123,
+            1e-7,
+            123456789012345.0,
+            1e15,
+            1.5e20,
+            1e300,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+
+        for number in numbers {
+            let expected = number.to_string();
+
+            let mut writer = XMLWriter::new();
+            writer.xml_number(number);
+
+            let got = writer.read_to_str();
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn test_xml_cell_start() {
+        let expected = r#"<c r="B3" s="4""#;
+
+        let mut writer = XMLWriter::new();
+        writer.xml_cell_start("B", 2, 4);
+
+        let got = writer.read_to_str();
+        assert_eq!(expected, got);
+
+        let expected = r#"<c r="A1""#;
+
+        let mut writer = XMLWriter::new();
+        writer.xml_cell_start("A", 0, 0);
+
+        let got = writer.read_to_str();
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_xml_escaped_data() {
+        let strings = [
+            ("", ""),
+            ("Plain text", "Plain text"),
+            ("a & b < c > d", "a &amp; b &lt; c &gt; d"),
+            ("&<>", "&amp;&lt;&gt;"),
+            ("\"quoted\"\tand\nnewline", "\"quoted\"\tand\nnewline"),
+            ("\x01é<\x1F", "_x0001_é&lt;_x001F_"),
+            ("é & ü", "é &amp; ü"),
+        ];
+
+        for (string, expected) in strings {
+            let mut writer = XMLWriter::new();
+            writer.xml_escaped_data(string);
+
+            let got = writer.read_to_str();
+            assert_eq!(expected, got);
+        }
+    }
+
+    #[test]
+    fn test_xml_escaped_attribute() {
+        let expected = r#"<foo bar="&quot;a&quot; &amp; [b]&#xA;"/>"#;
+
+        let mut writer = XMLWriter::new();
+        writer.xml_empty_tag("foo", &[("bar", "\"a\" & [b]\n")]);
+
+        let got = writer.read_to_str();
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_escape_url() {
+        assert!(matches!(
+            escape_url("https://www.example.com/path"),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            escape_url("https://example.com/a b[1]%"),
+            "https://example.com/a%20b%5b1%5d%25"
+        );
+    }
+
+    #[test]
+    fn test_xml_escaped_attribute_control_chars() {
+        let expected = r#"<foo bar="a_x0001_b_x001F_&#xA;	c"/>"#;
+
+        let mut writer = XMLWriter::new();
+        writer.xml_empty_tag("foo", &[("bar", "a\x01b\x1F\n\tc")]);
+
+        let got = writer.read_to_str();
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_xml_string_data_element() {
+        let strings = [
+            ("Plain text", "<t>Plain text</t>"),
+            ("_x0000_", "<t>_x005F_x0000_</t>"),
+            ("a\x00b_x0041_", "<t>a_x0000_b_x005F_x0041_</t>"),
+            ("<_x000D_>", "<t>&lt;_x005F_x000D_&gt;</t>"),
+        ];
+
+        for (string, expected) in strings {
+            let mut writer = XMLWriter::new();
+            writer.xml_string_data_element::<(&str, &str)>("t", string, &[]);
+
+            let got = writer.read_to_str();
+            assert_eq!(expected, got);
+        }
+    }
 }
@@ -458,6 +458,7 @@ mod cond_format16;
 mod cond_format18;
 mod cond_format19;
 mod cond_format20;
+#[allow(deprecated)]
 mod data_validation;
 mod defined_name01;
 mod defined_name02;